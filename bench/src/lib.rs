@@ -0,0 +1,201 @@
+#![deny(clippy::str_to_string)]
+
+mod report;
+mod workload;
+
+pub use {
+    report::{Measurement, Report, json, markdown},
+    workload::Workload,
+};
+
+use {
+    gluesql_core::{
+        error::Result,
+        prelude::Glue,
+        store::{GStore, GStoreMut},
+    },
+    std::time::{Duration, Instant},
+};
+
+/// Rows inserted by a single statement of the bulk load.
+const BATCH_SIZE: usize = 500;
+/// Rows of `BenchCategory`, referred to by `BenchItem.category`.
+const CATEGORIES: usize = 10;
+/// Rows selected by each range scan and join.
+const RANGE: usize = 100;
+
+/// Standard workloads timed end to end, from parsing the SQL to collecting the rows, so
+/// that the numbers of different storages can be compared with each other.
+///
+/// [`Bench::run`] loads `BenchItem` with the rows first, whether the bulk load is measured
+/// or not, so it expects a storage without the `BenchItem` and `BenchCategory` tables.
+#[derive(Clone, Debug)]
+pub struct Bench {
+    rows: usize,
+    iterations: usize,
+    workloads: Vec<Workload>,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self {
+            rows: 10_000,
+            iterations: 100,
+            workloads: Workload::ALL.to_vec(),
+        }
+    }
+}
+
+impl Bench {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of rows loaded, 10,000 by default.
+    pub fn with_rows(mut self, rows: usize) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+
+    /// Sets how many times each query workload runs, 100 by default.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations.max(1);
+        self
+    }
+
+    /// Limits the run to the workloads, all of them by default.
+    pub fn with_workloads(mut self, workloads: Vec<Workload>) -> Self {
+        self.workloads = workloads;
+        self
+    }
+
+    pub async fn run<T: GStore + GStoreMut>(
+        &self,
+        storage_name: &str,
+        storage: T,
+    ) -> Result<Report> {
+        let mut glue = Glue::new(storage);
+        let load = self.load(&mut glue).await?;
+
+        let mut measurements = Vec::new();
+        for workload in &self.workloads {
+            let samples = match workload {
+                Workload::BulkLoad => load.clone(),
+                _ => self.repeat(&mut glue, *workload).await?,
+            };
+
+            measurements.push(Measurement::new(*workload, samples));
+        }
+
+        Ok(Report {
+            storage: storage_name.to_owned(),
+            rows: self.rows,
+            measurements,
+        })
+    }
+
+    /// Creates the tables and inserts the rows, timing each batch.
+    async fn load<T: GStore + GStoreMut>(&self, glue: &mut Glue<T>) -> Result<Vec<Duration>> {
+        glue.execute(
+            "
+            CREATE TABLE BenchCategory (id INTEGER PRIMARY KEY, name TEXT);
+            CREATE TABLE BenchItem (
+                id INTEGER PRIMARY KEY,
+                category INTEGER,
+                price FLOAT,
+                name TEXT
+            );
+            ",
+        )
+        .await?;
+
+        let categories = (0..CATEGORIES)
+            .map(|id| format!("({id}, 'category {id}')"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        glue.execute(format!("INSERT INTO BenchCategory VALUES {categories}"))
+            .await?;
+
+        let mut samples = Vec::new();
+        for start in (0..self.rows).step_by(BATCH_SIZE) {
+            let values = (start..(start + BATCH_SIZE).min(self.rows))
+                .map(|id| {
+                    let category = id % CATEGORIES;
+                    let price = (id * 7919 % 10_000) as f64 / 100.0;
+
+                    format!("({id}, {category}, {price}, 'item {id}')")
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let sql = format!("INSERT INTO BenchItem VALUES {values}");
+
+            let started = Instant::now();
+            glue.execute(sql).await?;
+            samples.push(started.elapsed());
+        }
+
+        Ok(samples)
+    }
+
+    async fn repeat<T: GStore + GStoreMut>(
+        &self,
+        glue: &mut Glue<T>,
+        workload: Workload,
+    ) -> Result<Vec<Duration>> {
+        let mut keys = Keys::new(self.rows);
+        let mut samples = Vec::with_capacity(self.iterations);
+
+        for _ in 0..self.iterations {
+            let key = keys.next();
+            let start = key.min(self.rows.saturating_sub(RANGE));
+            let end = start + RANGE;
+            let sql = match workload {
+                Workload::BulkLoad => unreachable!("bulk load is timed while loading"),
+                Workload::PointLookup => format!("SELECT * FROM BenchItem WHERE id = {key}"),
+                Workload::RangeScan => {
+                    format!("SELECT * FROM BenchItem WHERE id >= {start} AND id < {end}")
+                }
+                Workload::Join => format!(
+                    "SELECT i.id, i.name, c.name
+                     FROM BenchItem i
+                     JOIN BenchCategory c ON c.id = i.category
+                     WHERE i.id >= {start} AND i.id < {end}"
+                ),
+                Workload::Aggregate => "SELECT category, COUNT(*), SUM(price), AVG(price)
+                     FROM BenchItem
+                     GROUP BY category"
+                    .to_owned(),
+            };
+
+            let started = Instant::now();
+            glue.execute(sql).await?;
+            samples.push(started.elapsed());
+        }
+
+        Ok(samples)
+    }
+}
+
+/// Deterministic sequence of the primary keys looked up, so that every storage is
+/// measured with the same queries.
+struct Keys {
+    state: u64,
+    rows: usize,
+}
+
+impl Keys {
+    fn new(rows: usize) -> Self {
+        Self {
+            state: 0x2545_f491_4f6c_dd1d,
+            rows,
+        }
+    }
+
+    fn next(&mut self) -> usize {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        (self.state % self.rows as u64) as usize
+    }
+}
@@ -0,0 +1,118 @@
+use {
+    anyhow::Result,
+    clap::Parser,
+    futures::executor::block_on,
+    gluesql_bench::{Bench, Report, Workload, json, markdown},
+    gluesql_csv_storage::CsvStorage,
+    gluesql_file_storage::FileStorage,
+    gluesql_json_storage::JsonStorage,
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_parquet_storage::ParquetStorage,
+    gluesql_redb_storage::RedbStorage,
+    gluesql_sled_storage::SledStorage,
+    std::{fs, path::PathBuf},
+};
+
+#[derive(Parser, Debug)]
+#[clap(name = "gluesql-bench", about, version)]
+struct Args {
+    /// Storage to benchmark, repeat it to compare several
+    #[clap(short, long, value_parser, default_value = "memory")]
+    storage: Vec<Storage>,
+
+    /// Directory the storages other than memory are created in, emptied before each run
+    #[clap(short, long, value_parser, default_value = "bench-data")]
+    path: PathBuf,
+
+    /// Number of rows loaded
+    #[clap(long, value_parser, default_value_t = 10_000)]
+    rows: usize,
+
+    /// Number of times each query workload runs
+    #[clap(long, value_parser, default_value_t = 100)]
+    iterations: usize,
+
+    /// Workload to run, every workload by default
+    #[clap(short, long, value_parser)]
+    workload: Vec<Workload>,
+
+    /// Format of the report
+    #[clap(short, long, value_parser, default_value = "markdown")]
+    format: Format,
+
+    /// File to write the report to, stdout by default
+    #[clap(short, long, value_parser)]
+    output: Option<PathBuf>,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone)]
+enum Storage {
+    Memory,
+    Sled,
+    Redb,
+    Json,
+    Csv,
+    Parquet,
+    File,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone)]
+enum Format {
+    Json,
+    Markdown,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let mut bench = Bench::new()
+        .with_rows(args.rows)
+        .with_iterations(args.iterations);
+    if !args.workload.is_empty() {
+        bench = bench.with_workloads(args.workload);
+    }
+
+    let reports = args
+        .storage
+        .iter()
+        .map(|storage| {
+            eprintln!("[{storage:?}] running");
+
+            run(&bench, storage, &args.path)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let report = match args.format {
+        Format::Json => json(&reports),
+        Format::Markdown => markdown(&reports),
+    };
+    match args.output {
+        Some(output) => fs::write(output, report)?,
+        None => println!("{report}"),
+    }
+
+    Ok(())
+}
+
+fn run(bench: &Bench, storage: &Storage, path: &std::path::Path) -> Result<Report> {
+    let name = format!("{storage:?}").to_lowercase();
+    let path = path.join(&name);
+    if path.exists() {
+        fs::remove_dir_all(&path)?;
+    }
+
+    let report = match storage {
+        Storage::Memory => block_on(bench.run(&name, MemoryStorage::default())),
+        Storage::Sled => block_on(bench.run(&name, SledStorage::new(&path)?)),
+        Storage::Redb => {
+            fs::create_dir_all(&path)?;
+            block_on(bench.run(&name, RedbStorage::new(path.join("bench.redb"))?))
+        }
+        Storage::Json => block_on(bench.run(&name, JsonStorage::new(&path)?)),
+        Storage::Csv => block_on(bench.run(&name, CsvStorage::new(&path)?)),
+        Storage::Parquet => block_on(bench.run(&name, ParquetStorage::new(&path)?)),
+        Storage::File => block_on(bench.run(&name, FileStorage::new(&path)?)),
+    }?;
+
+    Ok(report)
+}
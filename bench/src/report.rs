@@ -0,0 +1,93 @@
+use {
+    crate::Workload,
+    serde::Serialize,
+    std::{fmt::Write, time::Duration},
+};
+
+/// Timings of the workloads run against a storage.
+#[derive(Clone, Debug, Serialize)]
+pub struct Report {
+    pub storage: String,
+    pub rows: usize,
+    pub measurements: Vec<Measurement>,
+}
+
+/// Timings of the operations of a workload.
+#[derive(Clone, Debug, Serialize)]
+pub struct Measurement {
+    pub workload: Workload,
+    pub operations: usize,
+    pub mean_us: f64,
+    pub p50_us: f64,
+    pub p95_us: f64,
+    pub min_us: f64,
+    pub max_us: f64,
+    pub ops_per_sec: f64,
+}
+
+impl Measurement {
+    pub(crate) fn new(workload: Workload, mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+
+        let micros = |duration: Duration| duration.as_secs_f64() * 1_000_000.0;
+        let percentile = |p: usize| {
+            samples
+                .get((samples.len() * p / 100).min(samples.len().saturating_sub(1)))
+                .copied()
+                .map(micros)
+                .unwrap_or_default()
+        };
+        let total = samples.iter().sum::<Duration>();
+        let (mean_us, ops_per_sec) = if total.is_zero() {
+            (0.0, 0.0)
+        } else {
+            let n = samples.len() as f64;
+
+            (micros(total) / n, n / total.as_secs_f64())
+        };
+
+        Self {
+            workload,
+            operations: samples.len(),
+            mean_us,
+            p50_us: percentile(50),
+            p95_us: percentile(95),
+            min_us: samples.first().copied().map(micros).unwrap_or_default(),
+            max_us: samples.last().copied().map(micros).unwrap_or_default(),
+            ops_per_sec,
+        }
+    }
+}
+
+pub fn json(reports: &[Report]) -> String {
+    serde_json::to_string_pretty(reports).expect("reports are serializable")
+}
+
+/// Renders the reports as a single markdown table, a row per storage and workload.
+pub fn markdown(reports: &[Report]) -> String {
+    let mut table = String::from(
+        "| storage | rows | workload | ops | mean (us) | p50 (us) | p95 (us) | min (us) | max (us) | ops/s |\n\
+         |---|---:|---|---:|---:|---:|---:|---:|---:|---:|\n",
+    );
+
+    for report in reports {
+        for m in &report.measurements {
+            let _ = writeln!(
+                table,
+                "| {} | {} | {} | {} | {:.1} | {:.1} | {:.1} | {:.1} | {:.1} | {:.1} |",
+                report.storage,
+                report.rows,
+                m.workload,
+                m.operations,
+                m.mean_us,
+                m.p50_us,
+                m.p95_us,
+                m.min_us,
+                m.max_us,
+                m.ops_per_sec,
+            );
+        }
+    }
+
+    table
+}
@@ -0,0 +1,56 @@
+use {
+    serde::Serialize,
+    std::{fmt, str::FromStr},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Workload {
+    /// Inserting every row, a batch per operation.
+    BulkLoad,
+    /// Selecting a row by its primary key.
+    PointLookup,
+    /// Selecting a range of rows by their primary keys.
+    RangeScan,
+    /// Joining a range of rows with the table they refer to.
+    Join,
+    /// Grouping every row and aggregating each group.
+    Aggregate,
+}
+
+impl Workload {
+    pub const ALL: [Workload; 5] = [
+        Workload::BulkLoad,
+        Workload::PointLookup,
+        Workload::RangeScan,
+        Workload::Join,
+        Workload::Aggregate,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Workload::BulkLoad => "bulk_load",
+            Workload::PointLookup => "point_lookup",
+            Workload::RangeScan => "range_scan",
+            Workload::Join => "join",
+            Workload::Aggregate => "aggregate",
+        }
+    }
+}
+
+impl fmt::Display for Workload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Workload {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Workload::ALL
+            .into_iter()
+            .find(|workload| workload.name() == s.replace('-', "_"))
+            .ok_or_else(|| format!("unknown workload: {s}"))
+    }
+}
@@ -0,0 +1,65 @@
+use {
+    gluesql_bench::{Bench, Workload, json, markdown},
+    gluesql_memory_storage::MemoryStorage,
+};
+
+#[tokio::test]
+async fn bench() {
+    let report = Bench::new()
+        .with_rows(1_200)
+        .with_iterations(5)
+        .run("memory", MemoryStorage::default())
+        .await
+        .unwrap();
+
+    assert_eq!(report.storage, "memory");
+    assert_eq!(
+        report
+            .measurements
+            .iter()
+            .map(|m| (m.workload, m.operations))
+            .collect::<Vec<_>>(),
+        vec![
+            (Workload::BulkLoad, 3),
+            (Workload::PointLookup, 5),
+            (Workload::RangeScan, 5),
+            (Workload::Join, 5),
+            (Workload::Aggregate, 5),
+        ]
+    );
+    assert!(report.measurements.iter().all(|m| m.min_us <= m.p50_us
+        && m.p50_us <= m.p95_us
+        && m.p95_us <= m.max_us
+        && m.ops_per_sec > 0.0));
+
+    let reports = [report];
+    let json: serde_json::Value = serde_json::from_str(&json(&reports)).unwrap();
+    assert_eq!(json[0]["measurements"][1]["workload"], "point_lookup");
+    assert_eq!(json[0]["rows"], 1_200);
+
+    let markdown = markdown(&reports);
+    assert_eq!(markdown.lines().count(), 2 + 5);
+    assert!(markdown.contains("| memory | 1200 | join | 5 |"));
+}
+
+#[tokio::test]
+async fn bench_workloads() {
+    let report = Bench::new()
+        .with_rows(10)
+        .with_iterations(2)
+        .with_workloads(vec!["aggregate".parse().unwrap(), Workload::PointLookup])
+        .run("memory", MemoryStorage::default())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        report
+            .measurements
+            .iter()
+            .map(|m| m.workload)
+            .collect::<Vec<_>>(),
+        vec![Workload::Aggregate, Workload::PointLookup]
+    );
+    assert!("point-lookup".parse::<Workload>().is_ok());
+    assert!("scan".parse::<Workload>().is_err());
+}
@@ -126,6 +126,14 @@ where
                 Command::SpoolOff => {
                     self.print.spool_off();
                 }
+                Command::Backup(path) => match block_on(self.glue.backup(&path)) {
+                    Ok(()) => {
+                        println!("backup written to {}\n", path);
+                    }
+                    Err(e) => {
+                        println!("[error] {}\n", e);
+                    }
+                },
                 Command::Set(option) => self.print.set_option(option),
                 Command::Show(option) => self.print.show_option(option)?,
                 Command::Edit(file_name) => {
@@ -8,6 +8,7 @@ pub enum Command {
     ExecuteFromFile(String),
     SpoolOn(String),
     SpoolOff,
+    Backup(String),
     Set(SetOption),
     Show(ShowOption),
     Edit(Option<String>),
@@ -131,6 +132,10 @@ impl Command {
                     Some(path) => Ok(Self::SpoolOn(path.to_string())),
                     None => Err(CommandError::LackOfFile),
                 },
+                ".backup" => match params.get(1) {
+                    Some(path) => Ok(Self::Backup(path.to_string())),
+                    None => Err(CommandError::LackOfFile),
+                },
                 ".set" => match (params.get(1), params.get(2)) {
                     (Some(key), value) => Ok(Self::Set(SetOption::parse(key, value, option)?)),
                     (None, _) => Err(CommandError::LackOfOption),
@@ -199,6 +204,11 @@ mod tests {
         );
         assert_eq!(parse(".spool off"), Ok(Command::SpoolOff));
         assert_eq!(parse(".spool"), Err(CommandError::LackOfFile));
+        assert_eq!(
+            parse(".backup backup.db;"),
+            Ok(Command::Backup("backup.db".into()))
+        );
+        assert_eq!(parse(".backup"), Err(CommandError::LackOfFile));
         assert_eq!(
             parse(".set colsep ,"),
             Err(CommandError::WrongOption("run .set tabular OFF".into()))
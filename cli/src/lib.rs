@@ -16,9 +16,12 @@ use {
     gluesql_core::{
         ast::{Expr, ToSql},
         data::Value,
+        migration::Migrator,
+        prelude::Glue,
         store::{DataRow, GStore, GStoreMut, Store, Transaction},
     },
     gluesql_csv_storage::CsvStorage,
+    gluesql_dump::Dump,
     gluesql_file_storage::FileStorage,
     gluesql_json_storage::JsonStorage,
     gluesql_memory_storage::MemoryStorage,
@@ -28,7 +31,7 @@ use {
     std::{
         fmt::Debug,
         fs::File,
-        io::Write,
+        io::{BufReader, BufWriter, Write},
         path::{Path, PathBuf},
     },
 };
@@ -51,6 +54,68 @@ struct Args {
     /// Storage path to load
     #[clap(short, long, value_parser)]
     path: Option<PathBuf>,
+
+    #[clap(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Write a logical dump of the storage, restorable into any other storage
+    Dump {
+        /// File to write the dump to
+        #[clap(value_parser)]
+        output: PathBuf,
+
+        /// Table to dump, every table by default
+        #[clap(long, value_parser)]
+        include: Vec<String>,
+
+        /// Table to leave out of the dump
+        #[clap(long, value_parser)]
+        exclude: Vec<String>,
+
+        /// Number of tables exported at the same time
+        #[clap(long, value_parser, default_value_t = 4)]
+        parallelism: usize,
+    },
+    /// Create the tables of a dump in the storage and insert their rows
+    Restore {
+        /// File to read the dump from
+        #[clap(value_parser)]
+        input: PathBuf,
+
+        /// Table to restore, every table of the dump by default
+        #[clap(long, value_parser)]
+        include: Vec<String>,
+
+        /// Table to leave out of the restore
+        #[clap(long, value_parser)]
+        exclude: Vec<String>,
+    },
+    /// Apply or revert the versioned SQL migrations of a directory
+    Migrate {
+        /// Directory of <version>_<name>.up.sql and <version>_<name>.down.sql scripts
+        #[clap(long, value_parser, default_value = "migrations")]
+        dir: PathBuf,
+
+        #[clap(subcommand)]
+        action: MigrateAction,
+    },
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum MigrateAction {
+    /// Apply every migration not applied yet
+    Up,
+    /// Revert the latest applied migrations
+    Down {
+        /// Number of migrations to revert
+        #[clap(long, value_parser, default_value_t = 1)]
+        steps: usize,
+    },
+    /// List the migrations and when they were applied
+    Status,
 }
 
 #[derive(clap::ValueEnum, Debug, Clone)]
@@ -67,12 +132,13 @@ enum Storage {
 pub fn run() -> Result<()> {
     let args = Args::parse();
     let path = args.path.as_deref().and_then(Path::to_str);
+    let command = args.command;
 
     match (path, args.storage, args.dump) {
         (None, None, _) | (None, Some(Storage::Memory), _) => {
             println!("[memory-storage] initialized");
 
-            run(MemoryStorage::default(), args.execute);
+            run(MemoryStorage::default(), args.execute, command)?;
         }
         (Some(_), Some(Storage::Memory), _) => {
             panic!("failed to load memory-storage: it should be without path");
@@ -83,7 +149,8 @@ pub fn run() -> Result<()> {
             run(
                 SledStorage::new(path).expect("failed to load sled-storage"),
                 args.execute,
-            );
+                command,
+            )?;
         }
         (Some(path), Some(Storage::Redb), _) => {
             println!("[redb-storage] connected to {path}");
@@ -91,7 +158,8 @@ pub fn run() -> Result<()> {
             run(
                 RedbStorage::new(path).expect("failed to load redb-storage"),
                 args.execute,
-            );
+                command,
+            )?;
         }
         (Some(path), Some(Storage::Json), _) => {
             println!("[json-storage] connected to {path}");
@@ -99,7 +167,8 @@ pub fn run() -> Result<()> {
             run(
                 JsonStorage::new(path).expect("failed to load json-storage"),
                 args.execute,
-            );
+                command,
+            )?;
         }
         (Some(path), Some(Storage::Csv), _) => {
             println!("[csv-storage] connected to {path}");
@@ -107,7 +176,8 @@ pub fn run() -> Result<()> {
             run(
                 CsvStorage::new(path).expect("failed to load csv-storage"),
                 args.execute,
-            );
+                command,
+            )?;
         }
         (Some(path), Some(Storage::Parquet), _) => {
             println!("[parquet-storage] connected to {path}");
@@ -115,7 +185,8 @@ pub fn run() -> Result<()> {
             run(
                 ParquetStorage::new(path).expect("failed to load parquet-storage"),
                 args.execute,
-            );
+                command,
+            )?;
         }
         (Some(path), Some(Storage::File), _) => {
             println!("[file-storage] connected to {path}");
@@ -123,7 +194,8 @@ pub fn run() -> Result<()> {
             run(
                 FileStorage::new(path).expect("failed to load file-storage"),
                 args.execute,
-            );
+                command,
+            )?;
         }
         (Some(path), None, Some(dump_path)) => {
             let mut storage = SledStorage::new(path).expect("failed to load sled-storage");
@@ -135,7 +207,15 @@ pub fn run() -> Result<()> {
         }
     }
 
-    fn run<T: GStore + GStoreMut>(storage: T, input: Option<PathBuf>) {
+    fn run<T: GStore + GStoreMut>(
+        storage: T,
+        input: Option<PathBuf>,
+        command: Option<Command>,
+    ) -> Result<()> {
+        if let Some(command) = command {
+            return run_command(storage, command);
+        }
+
         let output = std::io::stdout();
         let mut cli = Cli::new(storage, output);
 
@@ -148,6 +228,85 @@ pub fn run() -> Result<()> {
         if let Err(e) = cli.run() {
             eprintln!("{e}");
         }
+
+        Ok(())
+    }
+
+    Ok(())
+}
+
+fn run_command<T: GStore + GStoreMut>(mut storage: T, command: Command) -> Result<()> {
+    match command {
+        Command::Dump {
+            output,
+            include,
+            exclude,
+            parallelism,
+        } => {
+            let dump = filter(Dump::new(), include, exclude).with_parallelism(parallelism);
+            let file = File::create(&output)?;
+            let summary = block_on(dump.dump(&mut storage, BufWriter::new(file)))?;
+
+            println!(
+                "[dump] {} tables, {} rows written to {}",
+                summary.tables,
+                summary.rows,
+                output.display()
+            );
+        }
+        Command::Restore {
+            input,
+            include,
+            exclude,
+        } => {
+            let dump = filter(Dump::new(), include, exclude);
+            let file = File::open(&input)?;
+            let summary = block_on(dump.restore(&mut storage, BufReader::new(file)))?;
+
+            println!(
+                "[restore] {} tables, {} rows restored from {}",
+                summary.tables,
+                summary.rows,
+                input.display()
+            );
+        }
+        Command::Migrate { dir, action } => {
+            let migrator = Migrator::from_dir(dir)?;
+            let mut glue = Glue::new(storage);
+
+            match action {
+                MigrateAction::Up => {
+                    for version in block_on(migrator.up(&mut glue))? {
+                        println!("[migrate] applied {version}");
+                    }
+                }
+                MigrateAction::Down { steps } => {
+                    for version in block_on(migrator.down(&mut glue, steps))? {
+                        println!("[migrate] reverted {version}");
+                    }
+                }
+                MigrateAction::Status => {
+                    for status in block_on(migrator.status(&mut glue))? {
+                        let applied_at = status
+                            .applied_at
+                            .map(|applied_at| applied_at.to_string())
+                            .unwrap_or_else(|| "pending".to_owned());
+
+                        println!("{} {} {applied_at}", status.version, status.name);
+                    }
+                }
+            }
+        }
+    }
+
+    fn filter(dump: Dump, include: Vec<String>, exclude: Vec<String>) -> Dump {
+        let dump = include.iter().fold(dump, |dump, table_name| {
+            dump.with_included_table(table_name)
+        });
+
+        exclude.iter().fold(dump, |dump, table_name| {
+            dump.with_excluded_table(table_name)
+        })
     }
 
     Ok(())
@@ -102,6 +102,8 @@ impl<'a, W: Write> Print<W> {
         #[strum(serialize_all = "snake_case")]
         enum Target {
             Table,
+            View,
+            Sequence,
             Row,
         }
         let mut affected = |n: usize, target: Target, msg: &str| -> IOResult<()> {
@@ -113,12 +115,21 @@ impl<'a, W: Write> Print<W> {
         match payload {
             Payload::Create => self.writeln("Table created")?,
             Payload::DropTable(n) => affected(*n, Table, "dropped")?,
+            Payload::DropView(n) => affected(*n, View, "dropped")?,
+            Payload::DropSequence(n) => affected(*n, Sequence, "dropped")?,
+            Payload::RefreshMaterializedView(n) => affected(*n, Row, "refreshed")?,
             Payload::DropFunction => self.writeln("Function dropped")?,
+            Payload::DropUser => self.writeln("User dropped")?,
+            Payload::Grant => self.writeln("Privileges granted")?,
+            Payload::Revoke => self.writeln("Privileges revoked")?,
             Payload::AlterTable => self.writeln("Table altered")?,
             Payload::CreateIndex => self.writeln("Index created")?,
             Payload::DropIndex => self.writeln("Index dropped")?,
+            Payload::CreateTrigger => self.writeln("Trigger created")?,
+            Payload::DropTrigger => self.writeln("Trigger dropped")?,
             Payload::Commit => self.writeln("Commit completed")?,
             Payload::Rollback => self.writeln("Rollback completed")?,
+            Payload::SetVariable => self.writeln("Variable set")?,
             Payload::StartTransaction => self.writeln("Transaction started")?,
             Payload::Insert(n) => affected(*n, Row, "inserted")?,
             Payload::Delete(n) => affected(*n, Row, "deleted")?,
@@ -148,24 +159,26 @@ impl<'a, W: Write> Print<W> {
                 let table = self.build_table(table);
                 self.writeln(table)?;
             }
-            Payload::Select { labels, rows } => match &self.option.tabular {
-                true => {
-                    let labels = labels.iter().map(AsRef::as_ref);
-                    let mut table = self.get_table(labels);
-                    for row in rows {
-                        let row: Vec<String> = row.iter().map(Into::into).collect();
-
-                        table.add_record(row);
+            Payload::Select { labels, rows } | Payload::Returning { labels, rows } => {
+                match &self.option.tabular {
+                    true => {
+                        let labels = labels.iter().map(AsRef::as_ref);
+                        let mut table = self.get_table(labels);
+                        for row in rows {
+                            let row: Vec<String> = row.iter().map(Into::into).collect();
+
+                            table.add_record(row);
+                        }
+                        let table = self.build_table(table);
+                        self.writeln(table)?;
+                    }
+                    false => {
+                        self.write_header(labels.iter().map(|s| s.as_str()))?;
+                        let rows = rows.iter().map(|row| row.iter().map(String::from));
+                        self.write_rows(rows)?;
                     }
-                    let table = self.build_table(table);
-                    self.writeln(table)?;
-                }
-                false => {
-                    self.write_header(labels.iter().map(|s| s.as_str()))?;
-                    let rows = rows.iter().map(|row| row.iter().map(String::from));
-                    self.write_rows(rows)?;
                 }
-            },
+            }
             Payload::SelectMap(rows) => {
                 let mut labels = rows
                     .iter()
@@ -261,7 +274,7 @@ impl<'a, W: Write> Print<W> {
 
     pub fn help(&mut self) -> IOResult<()> {
         const HEADER: [&str; 2] = ["command", "description"];
-        const CONTENT: [[&str; 2]; 12] = [
+        const CONTENT: [[&str; 2]; 13] = [
             [".help", "show help"],
             [".quit", "quit program"],
             [".tables", "show table names"],
@@ -270,6 +283,7 @@ impl<'a, W: Write> Print<W> {
             [".version", "show version"],
             [".execute PATH", "execute SQL from PATH"],
             [".spool PATH|off", "spool to PATH or off"],
+            [".backup PATH", "copy database to PATH"],
             [".show OPTION", "show print option eg).show all"],
             [".set OPTION", "set print option eg).set tabular off"],
             [".edit [PATH]", "open editor with last command or PATH"],
@@ -352,6 +366,7 @@ mod tests {
 | .version        | show version                          |
 | .execute PATH   | execute SQL from PATH                 |
 | .spool PATH|off | spool to PATH or off                  |
+| .backup PATH    | copy database to PATH                 |
 | .show OPTION    | show print option eg).show all        |
 | .set OPTION     | set print option eg).set tabular off  |
 | .edit [PATH]    | open editor with last command or PATH |
@@ -390,12 +405,21 @@ mod tests {
 
         test!(Payload::Create, "Table created");
         test!(Payload::DropTable(1), "1 table dropped");
+        test!(Payload::DropView(2), "2 views dropped");
+        test!(Payload::DropSequence(1), "1 sequence dropped");
+        test!(Payload::RefreshMaterializedView(3), "3 rows refreshed");
         test!(Payload::AlterTable, "Table altered");
         test!(Payload::CreateIndex, "Index created");
         test!(Payload::DropIndex, "Index dropped");
+        test!(Payload::CreateTrigger, "Trigger created");
+        test!(Payload::DropTrigger, "Trigger dropped");
         test!(Payload::DropFunction, "Function dropped");
+        test!(Payload::DropUser, "User dropped");
+        test!(Payload::Grant, "Privileges granted");
+        test!(Payload::Revoke, "Privileges revoked");
         test!(Payload::Commit, "Commit completed");
         test!(Payload::Rollback, "Rollback completed");
+        test!(Payload::SetVariable, "Variable set");
         test!(Payload::StartTransaction, "Transaction started");
         test!(Payload::Insert(0), "0 row inserted");
         test!(Payload::Insert(1), "1 row inserted");
@@ -1,5 +1,6 @@
 mod ast_literal;
 mod data_type;
+mod dcl;
 mod ddl;
 mod expr;
 mod function;
@@ -9,9 +10,10 @@ mod query;
 pub use {
     ast_literal::{AstLiteral, DateTimeField, TrimWhereField},
     data_type::DataType,
+    dcl::*,
     ddl::*,
     expr::Expr,
-    function::{Aggregate, AggregateFunction, CountArgExpr, Function},
+    function::{Aggregate, AggregateFunction, CountArgExpr, Function, WindowSpec},
     operator::*,
     query::*,
 };
@@ -39,10 +41,67 @@ pub struct ForeignKey {
     pub on_update: ReferentialAction,
 }
 
+/// `ON CONFLICT` of `INSERT`, handling the rows whose unique columns take values other rows
+/// already have.
+#[derive(PartialEq, Debug, Clone, Eq, Hash, Serialize, Deserialize)]
+pub struct OnConflict {
+    /// The unique columns conflicts are looked for on, all of them when empty
+    pub target: Vec<String>,
+    pub action: OnConflictAction,
+}
+
+#[derive(PartialEq, Debug, Clone, Eq, Hash, Serialize, Deserialize)]
+pub enum OnConflictAction {
+    /// `DO NOTHING`, skipping the conflicting rows
+    DoNothing,
+    /// `DO UPDATE SET ... [WHERE ...]`, updating the row conflicted with instead, which reads
+    /// the row proposed for insertion as `EXCLUDED`
+    DoUpdate {
+        assignments: Vec<Assignment>,
+        selection: Option<Expr>,
+    },
+}
+
+/// A `CHECK` constraint, which the rows of its table must not make false.
+#[derive(PartialEq, Debug, Clone, Eq, Hash, Serialize, Deserialize)]
+pub struct CheckConstraint {
+    pub name: String,
+    pub expr: Expr,
+}
+
+/// A table-level `UNIQUE` constraint, which no two rows may match on all of its columns.
+/// Rows with `NULL` in any of them never conflict.
+#[derive(PartialEq, Debug, Clone, Eq, Hash, Serialize, Deserialize)]
+pub struct UniqueConstraint {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
 #[derive(PartialEq, Debug, Clone, Eq, Hash, Serialize, Deserialize, Display)]
 pub enum ReferentialAction {
     #[strum(to_string = "NO ACTION")]
     NoAction,
+    #[strum(to_string = "RESTRICT")]
+    Restrict,
+    #[strum(to_string = "CASCADE")]
+    Cascade,
+    #[strum(to_string = "SET NULL")]
+    SetNull,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum TriggerTiming {
+    Before,
+    After,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum TriggerEvent {
+    Insert,
+    Update,
+    Delete,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -60,6 +119,10 @@ pub enum Statement {
         columns: Vec<String>,
         /// A SQL query that specifies what to insert
         source: Query,
+        /// ON CONFLICT
+        on_conflict: Option<OnConflict>,
+        /// RETURNING
+        returning: Vec<SelectItem>,
     },
     /// UPDATE
     Update {
@@ -69,6 +132,8 @@ pub enum Statement {
         assignments: Vec<Assignment>,
         /// WHERE
         selection: Option<Expr>,
+        /// RETURNING
+        returning: Vec<SelectItem>,
     },
     /// DELETE
     Delete {
@@ -76,6 +141,8 @@ pub enum Statement {
         table_name: String,
         /// WHERE
         selection: Option<Expr>,
+        /// RETURNING
+        returning: Vec<SelectItem>,
     },
     /// CREATE TABLE
     CreateTable {
@@ -87,8 +154,29 @@ pub enum Statement {
         source: Option<Box<Query>>,
         engine: Option<String>,
         foreign_keys: Vec<ForeignKey>,
+        checks: Vec<CheckConstraint>,
+        uniques: Vec<UniqueConstraint>,
         comment: Option<String>,
     },
+    /// CREATE VIEW, CREATE MATERIALIZED VIEW
+    CreateView {
+        or_replace: bool,
+        name: String,
+        /// The query the view names, expanded wherever the view is read
+        query: Box<Query>,
+        /// Whether the rows of the query are stored, until `REFRESH MATERIALIZED VIEW`
+        materialized: bool,
+    },
+    /// REFRESH MATERIALIZED VIEW
+    RefreshMaterializedView(String),
+    /// DROP VIEW
+    DropView {
+        /// An optional `IF EXISTS` clause. (Non-standard.)
+        if_exists: bool,
+        names: Vec<String>,
+        /// An optional `CASCADE` clause, dropping the views reading the dropped ones.
+        cascade: bool,
+    },
     /// CREATE FUNCTION
     CreateFunction {
         or_replace: bool,
@@ -119,17 +207,82 @@ pub enum Statement {
         /// One or more objects to drop. (ANSI SQL requires exactly one.)
         names: Vec<String>,
     },
+    /// CREATE TRIGGER
+    CreateTrigger {
+        or_replace: bool,
+        name: String,
+        table_name: String,
+        timing: TriggerTiming,
+        events: Vec<TriggerEvent>,
+        /// `WHEN` condition, reading the changed row as `NEW` and `OLD`
+        condition: Option<Expr>,
+        /// SQL of the statements run for each changed row, reading it as `NEW` and `OLD`
+        body: Vec<String>,
+    },
+    /// DROP TRIGGER
+    DropTrigger {
+        /// An optional `IF EXISTS` clause. (Non-standard.)
+        if_exists: bool,
+        name: String,
+        table_name: String,
+    },
+    /// CREATE SEQUENCE
+    CreateSequence {
+        if_not_exists: bool,
+        name: String,
+        /// `INCREMENT BY`, added to the last value on each `NEXTVAL`
+        increment: i64,
+        /// `START WITH`, the value the first `NEXTVAL` returns
+        start: i64,
+    },
+    /// DROP SEQUENCE
+    DropSequence {
+        /// An optional `IF EXISTS` clause. (Non-standard.)
+        if_exists: bool,
+        names: Vec<String>,
+    },
     /// CREATE INDEX
     CreateIndex {
         name: String,
         table_name: String,
-        column: OrderByExpr,
+        /// columns of the index, more than one for a composite index
+        columns: Vec<OrderByExpr>,
+        /// `WHERE` of a partial index, which holds only the rows it is true for
+        predicate: Option<Expr>,
+        /// `USING FULLTEXT`, indexing the rows by the terms of their text
+        fulltext: bool,
     },
     /// DROP INDEX
     DropIndex {
         name: String,
         table_name: String,
     },
+    /// CREATE USER, CREATE ROLE
+    CreateUser {
+        if_not_exists: bool,
+        name: String,
+        password: Option<String>,
+        /// `SUPERUSER`, bypassing every privilege check
+        superuser: bool,
+    },
+    /// DROP USER, DROP ROLE
+    DropUser {
+        /// An optional `IF EXISTS` clause. (Non-standard.)
+        if_exists: bool,
+        names: Vec<String>,
+    },
+    /// GRANT
+    Grant {
+        privileges: Vec<Privilege>,
+        table_names: Vec<String>,
+        grantees: Vec<String>,
+    },
+    /// REVOKE
+    Revoke {
+        privileges: Vec<Privilege>,
+        table_names: Vec<String>,
+        grantees: Vec<String>,
+    },
     /// START TRANSACTION, BEGIN
     StartTransaction,
     /// COMMIT
@@ -139,6 +292,26 @@ pub enum Statement {
     /// SHOW VARIABLE
     ShowVariable(Variable),
     ShowIndexes(String),
+    /// SHOW CREATE VIEW
+    ShowCreateView(String),
+    /// EXPLAIN, EXPLAIN ANALYZE
+    Explain {
+        /// `ANALYZE`, running the query to count the rows of each operator
+        analyze: bool,
+        query: Query,
+    },
+    /// CHECK DATABASE, CHECK TABLE
+    Check {
+        /// `None` checks every table
+        table_name: Option<String>,
+    },
+    /// ANALYZE
+    Analyze {
+        /// `None` analyzes every table
+        table_name: Option<String>,
+    },
+    /// SET
+    SetVariable(SetVariable),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -154,6 +327,14 @@ pub enum Variable {
     Version,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SetVariable {
+    /// `SET audit = { ON | OFF }`
+    Audit(bool),
+    /// `SET audit_table = 'name'`
+    AuditTable(String),
+}
+
 impl ToSql for Assignment {
     fn to_sql(&self) -> String {
         format!(r#""{}" = {}"#, self.id, self.value.to_sql())
@@ -177,6 +358,27 @@ impl ToSql for ForeignKey {
     }
 }
 
+impl ToSql for CheckConstraint {
+    fn to_sql(&self) -> String {
+        let CheckConstraint { name, expr } = self;
+
+        format!(r#"CONSTRAINT "{name}" CHECK ({})"#, expr.to_sql())
+    }
+}
+
+impl ToSql for UniqueConstraint {
+    fn to_sql(&self) -> String {
+        let UniqueConstraint { name, columns } = self;
+        let columns = columns
+            .iter()
+            .map(|column| format!(r#""{column}""#))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!(r#"CONSTRAINT "{name}" UNIQUE ({columns})"#)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Array {
     pub elem: Vec<Expr>,
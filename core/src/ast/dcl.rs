@@ -0,0 +1,23 @@
+use {
+    serde::{Deserialize, Serialize},
+    strum_macros::Display,
+};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Display,
+)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum Action {
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+/// Privilege on a table, given with `GRANT` and taken away with `REVOKE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Privilege {
+    pub action: Action,
+    /// Columns the privilege is limited to, `None` for every column of the table.
+    pub columns: Option<Vec<String>>,
+}
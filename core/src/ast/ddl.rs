@@ -20,6 +20,12 @@ pub enum AlterTableOperation {
     },
     /// `RENAME TO <table_name>`
     RenameTable { table_name: String },
+    /// `ALTER [ COLUMN ] <column_name> [ SET DATA ] TYPE <data_type> [ USING <expr> ]`
+    AlterColumnType {
+        column_name: String,
+        data_type: DataType,
+        using: Option<Expr>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -67,6 +73,17 @@ impl ToSql for AlterTableOperation {
             AlterTableOperation::RenameTable { table_name } => {
                 format!(r#"RENAME TO "{table_name}""#)
             }
+            AlterTableOperation::AlterColumnType {
+                column_name,
+                data_type,
+                using,
+            } => match using {
+                Some(using) => format!(
+                    r#"ALTER COLUMN "{column_name}" TYPE {data_type} USING {}"#,
+                    using.to_sql()
+                ),
+                None => format!(r#"ALTER COLUMN "{column_name}" TYPE {data_type}"#),
+            },
         }
     }
 }
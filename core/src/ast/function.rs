@@ -1,5 +1,5 @@
 use {
-    super::{DataType, DateTimeField, Expr, ast_literal::TrimWhereField},
+    super::{DataType, DateTimeField, Expr, OrderByExpr, ast_literal::TrimWhereField},
     crate::ast::ToSql,
     serde::{Deserialize, Serialize},
     strum_macros::Display,
@@ -218,6 +218,49 @@ pub enum Function {
         values: Option<Expr>,
     },
     Dedup(Expr),
+    /// `MATCH(exprs) AGAINST(query)`, the relevance of the text of `exprs` to the terms of
+    /// `query`, see [`crate::data::fulltext::relevance`]
+    Match {
+        exprs: Vec<Expr>,
+        query: Expr,
+    },
+    RegexpMatches {
+        expr: Expr,
+        pattern: Expr,
+    },
+    RegexpReplace {
+        expr: Expr,
+        pattern: Expr,
+        replacement: Expr,
+    },
+    RegexpExtract {
+        expr: Expr,
+        pattern: Expr,
+        group: Option<Expr>,
+    },
+    JsonExtract {
+        expr: Expr,
+        path: Expr,
+    },
+    JsonSet {
+        expr: Expr,
+        path: Expr,
+        value: Expr,
+    },
+    JsonRemove {
+        expr: Expr,
+        path: Expr,
+    },
+    /// Sequence functions, which `INSERT` and `SELECT` resolve to values before they run
+    #[strum(to_string = "NEXTVAL")]
+    NextVal(Expr),
+    #[strum(to_string = "CURRVAL")]
+    CurrVal(Expr),
+    #[strum(to_string = "SETVAL")]
+    SetVal {
+        name: Expr,
+        value: Expr,
+    },
 }
 
 impl ToSql for Function {
@@ -507,6 +550,57 @@ impl ToSql for Function {
                 ),
             },
             Function::Dedup(list) => format!("DEDUP({})", list.to_sql()),
+            Function::Match { exprs, query } => {
+                let exprs = exprs
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("MATCH({exprs}) AGAINST({})", query.to_sql())
+            }
+            Function::RegexpMatches { expr, pattern } => {
+                format!("REGEXP_MATCHES({}, {})", expr.to_sql(), pattern.to_sql())
+            }
+            Function::RegexpReplace {
+                expr,
+                pattern,
+                replacement,
+            } => format!(
+                "REGEXP_REPLACE({}, {}, {})",
+                expr.to_sql(),
+                pattern.to_sql(),
+                replacement.to_sql()
+            ),
+            Function::RegexpExtract {
+                expr,
+                pattern,
+                group,
+            } => match group {
+                None => format!("REGEXP_EXTRACT({}, {})", expr.to_sql(), pattern.to_sql()),
+                Some(group) => format!(
+                    "REGEXP_EXTRACT({}, {}, {})",
+                    expr.to_sql(),
+                    pattern.to_sql(),
+                    group.to_sql()
+                ),
+            },
+            Function::JsonExtract { expr, path } => {
+                format!("JSON_EXTRACT({}, {})", expr.to_sql(), path.to_sql())
+            }
+            Function::JsonSet { expr, path, value } => format!(
+                "JSON_SET({}, {}, {})",
+                expr.to_sql(),
+                path.to_sql(),
+                value.to_sql()
+            ),
+            Function::JsonRemove { expr, path } => {
+                format!("JSON_REMOVE({}, {})", expr.to_sql(), path.to_sql())
+            }
+            Function::NextVal(name) => format!("NEXTVAL({})", name.to_sql()),
+            Function::CurrVal(name) => format!("CURRVAL({})", name.to_sql()),
+            Function::SetVal { name, value } => {
+                format!("SETVAL({}, {})", name.to_sql(), value.to_sql())
+            }
         }
     }
 }
@@ -520,17 +614,51 @@ pub enum AggregateFunction {
     Avg(Expr),
     Variance(Expr),
     Stdev(Expr),
+    /// Window functions, valid only with `OVER`
+    RowNumber,
+    Rank,
+    DenseRank,
+    Lag {
+        expr: Expr,
+        offset: Option<Expr>,
+        default: Option<Expr>,
+    },
+    Lead {
+        expr: Expr,
+        offset: Option<Expr>,
+        default: Option<Expr>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Aggregate {
     pub func: AggregateFunction,
     pub distinct: bool,
+    /// `OVER` clause, computing the function per row over a window of rows instead of per group
+    pub over: Option<WindowSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct WindowSpec {
+    pub partition_by: Vec<Expr>,
+    pub order_by: Vec<OrderByExpr>,
 }
 
 impl Aggregate {
     pub fn new(func: AggregateFunction, distinct: bool) -> Self {
-        Self { func, distinct }
+        Self {
+            func,
+            distinct,
+            over: None,
+        }
+    }
+
+    pub fn window(func: AggregateFunction, over: WindowSpec) -> Self {
+        Self {
+            func,
+            distinct: false,
+            over: Some(over),
+        }
     }
 
     pub fn count(expr: CountArgExpr, distinct: bool) -> Self {
@@ -564,6 +692,18 @@ impl Aggregate {
 
 impl AggregateFunction {
     fn to_sql_with_distinct(&self, distinct: bool) -> String {
+        let lag_or_lead =
+            |name: &str, expr: &Expr, offset: &Option<Expr>, default: &Option<Expr>| {
+                let args = std::iter::once(expr)
+                    .chain(offset)
+                    .chain(default)
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                format!("{name}({args})")
+            };
+
         let (name, arg) = match self {
             AggregateFunction::Count(expr) => ("COUNT", expr.to_sql()),
             AggregateFunction::Sum(expr) => ("SUM", expr.to_sql()),
@@ -572,6 +712,19 @@ impl AggregateFunction {
             AggregateFunction::Avg(expr) => ("AVG", expr.to_sql()),
             AggregateFunction::Variance(expr) => ("VARIANCE", expr.to_sql()),
             AggregateFunction::Stdev(expr) => ("STDEV", expr.to_sql()),
+            AggregateFunction::RowNumber => return "ROW_NUMBER()".to_owned(),
+            AggregateFunction::Rank => return "RANK()".to_owned(),
+            AggregateFunction::DenseRank => return "DENSE_RANK()".to_owned(),
+            AggregateFunction::Lag {
+                expr,
+                offset,
+                default,
+            } => return lag_or_lead("LAG", expr, offset, default),
+            AggregateFunction::Lead {
+                expr,
+                offset,
+                default,
+            } => return lag_or_lead("LEAD", expr, offset, default),
         };
         if distinct {
             format!("{name}(DISTINCT {arg})")
@@ -583,7 +736,46 @@ impl AggregateFunction {
 
 impl ToSql for Aggregate {
     fn to_sql(&self) -> String {
-        self.func.to_sql_with_distinct(self.distinct)
+        let func = self.func.to_sql_with_distinct(self.distinct);
+
+        match &self.over {
+            Some(over) => format!("{func} OVER ({})", over.to_sql()),
+            None => func,
+        }
+    }
+}
+
+impl ToSql for WindowSpec {
+    fn to_sql(&self) -> String {
+        let WindowSpec {
+            partition_by,
+            order_by,
+        } = self;
+
+        let partition_by = (!partition_by.is_empty()).then(|| {
+            let exprs = partition_by
+                .iter()
+                .map(ToSql::to_sql)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("PARTITION BY {exprs}")
+        });
+        let order_by = (!order_by.is_empty()).then(|| {
+            let exprs = order_by
+                .iter()
+                .map(ToSql::to_sql)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("ORDER BY {exprs}")
+        });
+
+        partition_by
+            .into_iter()
+            .chain(order_by)
+            .collect::<Vec<_>>()
+            .join(" ")
     }
 }
 
@@ -606,8 +798,8 @@ impl ToSql for CountArgExpr {
 mod tests {
     use {
         crate::ast::{
-            Aggregate, AstLiteral, CountArgExpr, DataType, DateTimeField, Expr, Function, ToSql,
-            TrimWhereField,
+            Aggregate, AggregateFunction, AstLiteral, CountArgExpr, DataType, DateTimeField, Expr,
+            Function, OrderByExpr, ToSql, TrimWhereField, WindowSpec,
         },
         bigdecimal::BigDecimal,
         std::str::FromStr,
@@ -1468,6 +1660,75 @@ mod tests {
                 "list".to_owned()
             ))))
             .to_sql(),
+        );
+
+        assert_eq!(
+            r#"MATCH("title", "body") AGAINST('rust engine')"#,
+            &Expr::Function(Box::new(Function::Match {
+                exprs: vec![
+                    Expr::Identifier("title".to_owned()),
+                    Expr::Identifier("body".to_owned())
+                ],
+                query: Expr::Literal(AstLiteral::QuotedString("rust engine".to_owned()))
+            }))
+            .to_sql(),
+        );
+
+        assert_eq!(
+            r#"REGEXP_MATCHES("body", '[0-9]+')"#,
+            &Expr::Function(Box::new(Function::RegexpMatches {
+                expr: Expr::Identifier("body".to_owned()),
+                pattern: Expr::Literal(AstLiteral::QuotedString("[0-9]+".to_owned()))
+            }))
+            .to_sql(),
+        );
+
+        assert_eq!(
+            r#"REGEXP_REPLACE("body", '\s+', ' ')"#,
+            &Expr::Function(Box::new(Function::RegexpReplace {
+                expr: Expr::Identifier("body".to_owned()),
+                pattern: Expr::Literal(AstLiteral::QuotedString(r"\s+".to_owned())),
+                replacement: Expr::Literal(AstLiteral::QuotedString(" ".to_owned()))
+            }))
+            .to_sql(),
+        );
+
+        assert_eq!(
+            r#"REGEXP_EXTRACT("body", '(\w+)@', 1)"#,
+            &Expr::Function(Box::new(Function::RegexpExtract {
+                expr: Expr::Identifier("body".to_owned()),
+                pattern: Expr::Literal(AstLiteral::QuotedString(r"(\w+)@".to_owned())),
+                group: Some(Expr::Literal(AstLiteral::Number(BigDecimal::from(1))))
+            }))
+            .to_sql(),
+        );
+
+        assert_eq!(
+            r#"JSON_EXTRACT("doc", '$.items[0]')"#,
+            &Expr::Function(Box::new(Function::JsonExtract {
+                expr: Expr::Identifier("doc".to_owned()),
+                path: Expr::Literal(AstLiteral::QuotedString("$.items[0]".to_owned()))
+            }))
+            .to_sql(),
+        );
+
+        assert_eq!(
+            r#"JSON_SET("doc", '$.name', 'Glue')"#,
+            &Expr::Function(Box::new(Function::JsonSet {
+                expr: Expr::Identifier("doc".to_owned()),
+                path: Expr::Literal(AstLiteral::QuotedString("$.name".to_owned())),
+                value: Expr::Literal(AstLiteral::QuotedString("Glue".to_owned()))
+            }))
+            .to_sql(),
+        );
+
+        assert_eq!(
+            r#"JSON_REMOVE("doc", '$.name')"#,
+            &Expr::Function(Box::new(Function::JsonRemove {
+                expr: Expr::Identifier("doc".to_owned()),
+                path: Expr::Literal(AstLiteral::QuotedString("$.name".to_owned()))
+            }))
+            .to_sql(),
         )
     }
 
@@ -1600,4 +1861,58 @@ mod tests {
             .to_sql()
         );
     }
+
+    #[test]
+    fn to_sql_window() {
+        let id = || Expr::Identifier("id".to_owned());
+
+        assert_eq!(
+            "ROW_NUMBER() OVER ()",
+            Expr::Aggregate(Box::new(Aggregate::window(
+                AggregateFunction::RowNumber,
+                WindowSpec {
+                    partition_by: Vec::new(),
+                    order_by: Vec::new(),
+                },
+            )))
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"SUM("id") OVER (PARTITION BY "name", "city" ORDER BY "id" DESC)"#,
+            Expr::Aggregate(Box::new(Aggregate::window(
+                AggregateFunction::Sum(id()),
+                WindowSpec {
+                    partition_by: vec![
+                        Expr::Identifier("name".to_owned()),
+                        Expr::Identifier("city".to_owned()),
+                    ],
+                    order_by: vec![OrderByExpr {
+                        expr: id(),
+                        asc: Some(false),
+                    }],
+                },
+            )))
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#"LAG("id", 2, 0) OVER (ORDER BY "id")"#,
+            Expr::Aggregate(Box::new(Aggregate::window(
+                AggregateFunction::Lag {
+                    expr: id(),
+                    offset: Some(Expr::Literal(AstLiteral::Number(2.into()))),
+                    default: Some(Expr::Literal(AstLiteral::Number(0.into()))),
+                },
+                WindowSpec {
+                    partition_by: Vec::new(),
+                    order_by: vec![OrderByExpr {
+                        expr: id(),
+                        asc: None,
+                    }],
+                },
+            )))
+            .to_sql()
+        );
+    }
 }
@@ -44,6 +44,9 @@ pub enum BinaryOperator {
     BitwiseAnd,
     BitwiseShiftLeft,
     BitwiseShiftRight,
+    RegexMatch,
+    Arrow,
+    LongArrow,
 }
 
 impl ToSql for BinaryOperator {
@@ -67,6 +70,9 @@ impl ToSql for BinaryOperator {
             BinaryOperator::BitwiseAnd => "&".to_owned(),
             BinaryOperator::BitwiseShiftLeft => "<<".to_owned(),
             BinaryOperator::BitwiseShiftRight => ">>".to_owned(),
+            BinaryOperator::RegexMatch => "~".to_owned(),
+            BinaryOperator::Arrow => "->".to_owned(),
+            BinaryOperator::LongArrow => "->>".to_owned(),
         }
     }
 }
@@ -325,6 +331,30 @@ mod tests {
                 expr: Box::new(Expr::Literal(AstLiteral::Number(BigDecimal::from(1)))),
             }
             .to_sql(),
+        );
+
+        assert_eq!(
+            r#""name" ~ '^G'"#,
+            &Expr::BinaryOp {
+                left: Box::new(Expr::Identifier("name".to_owned())),
+                op: BinaryOperator::RegexMatch,
+                right: Box::new(Expr::Literal(AstLiteral::QuotedString("^G".to_owned())))
+            }
+            .to_sql()
+        );
+
+        assert_eq!(
+            r#""doc" -> 'user' ->> 'name'"#,
+            &Expr::BinaryOp {
+                left: Box::new(Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier("doc".to_owned())),
+                    op: BinaryOperator::Arrow,
+                    right: Box::new(Expr::Literal(AstLiteral::QuotedString("user".to_owned())))
+                }),
+                op: BinaryOperator::LongArrow,
+                right: Box::new(Expr::Literal(AstLiteral::QuotedString("name".to_owned())))
+            }
+            .to_sql()
         )
     }
 }
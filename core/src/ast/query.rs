@@ -1,8 +1,10 @@
 use {
     super::{Expr, IndexOperator, ToSqlUnquoted},
     crate::ast::ToSql,
+    chrono::NaiveDate,
     itertools::Itertools,
     serde::{Deserialize, Serialize},
+    std::fmt,
     strum_macros::Display,
 };
 
@@ -18,6 +20,22 @@ pub struct Query {
 pub enum SetExpr {
     Select(Box<Select>),
     Values(Values),
+    /// `left UNION | EXCEPT | INTERSECT [ALL] right`
+    SetOperation {
+        op: SetOperator,
+        /// `ALL`, keeping the duplicate rows
+        all: bool,
+        left: Box<SetExpr>,
+        right: Box<SetExpr>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum SetOperator {
+    Union,
+    Except,
+    Intersect,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -55,6 +73,11 @@ pub enum IndexItem {
         asc: Option<bool>,
         cmp_expr: Option<(IndexOperator, Expr)>,
     },
+    /// Scan of a full-text index for the rows with any of the terms of `query`
+    FullText {
+        name: String,
+        query: Expr,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -69,16 +92,64 @@ pub enum TableFactor {
         subquery: Query,
         alias: TableAlias,
     },
+    /// Table `name` of `WITH RECURSIVE`: the rows of `anchor`, then the rows `recursive` makes
+    /// of the rows added last, which it reads as `name`, until it adds none.
+    Recursive {
+        name: String,
+        columns: Vec<String>,
+        anchor: Box<Query>,
+        recursive: Box<Query>,
+        /// `UNION ALL`, keeping the duplicate rows
+        all: bool,
+        alias: TableAlias,
+        /// Query planner result, the default depth limit when `None`
+        max_depth: Option<usize>,
+    },
     Series {
         alias: TableAlias,
         size: Expr,
     },
+    Fake {
+        alias: TableAlias,
+        size: Expr,
+        columns: Vec<FakeColumn>,
+        seed: Option<Expr>,
+    },
     Dictionary {
         dict: Dictionary,
         alias: TableAlias,
     },
 }
 
+/// Column of the rows generated by `FAKE`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FakeColumn {
+    pub name: String,
+    pub kind: FakeKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FakeKind {
+    /// Row number, starting from 1.
+    Sequence,
+    Name,
+    Email,
+    Integer {
+        min: i64,
+        max: i64,
+    },
+    Float {
+        min: i64,
+        max: i64,
+    },
+    Boolean,
+    Date {
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    Uuid,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Display)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
 pub enum Dictionary {
@@ -109,6 +180,13 @@ pub enum JoinExecutor {
         value_expr: Expr,
         where_clause: Option<Expr>,
     },
+    /// Joins rows read in the order of `key_expr` with rows coming in the order of
+    /// `value_expr`, both ascending through the indexes of the relations.
+    Merge {
+        key_expr: Expr,
+        value_expr: Expr,
+        where_clause: Option<Expr>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -212,6 +290,23 @@ impl SetExpr {
             (SetExpr::Select(select), false) => select.to_sql_unquoted(),
             (SetExpr::Values(values), true) => format!("VALUES {}", values.to_sql()),
             (SetExpr::Values(values), false) => format!("VALUES {}", values.to_sql_unquoted()),
+            (
+                SetExpr::SetOperation {
+                    op,
+                    all,
+                    left,
+                    right,
+                },
+                _,
+            ) => {
+                let all = if *all { " ALL" } else { "" };
+                let operand = |set_expr: &SetExpr| match set_expr {
+                    SetExpr::SetOperation { .. } => format!("({})", set_expr.to_sql_with(quoted)),
+                    _ => set_expr.to_sql_with(quoted),
+                };
+
+                format!("{} {op}{all} {}", operand(left), operand(right))
+            }
         }
     }
 }
@@ -387,9 +482,61 @@ impl TableFactor {
                     alias.to_sql_with(quoted)
                 )
             }
+            (
+                TableFactor::Recursive {
+                    name,
+                    columns,
+                    anchor,
+                    recursive,
+                    all,
+                    alias,
+                    ..
+                },
+                _,
+            ) => {
+                let quote = |ident: &String| match quoted {
+                    true => format!(r#""{ident}""#),
+                    false => ident.to_owned(),
+                };
+                let columns = match columns.is_empty() {
+                    true => "".to_owned(),
+                    false => format!("({})", columns.iter().map(quote).join(", ")),
+                };
+                let union = if *all { "UNION ALL" } else { "UNION" };
+
+                format!(
+                    "(WITH RECURSIVE {}{columns} AS ({} {union} {}) SELECT * FROM {}) {}",
+                    quote(name),
+                    anchor.to_sql_with(quoted),
+                    recursive.to_sql_with(quoted),
+                    quote(name),
+                    alias.to_sql_with(quoted)
+                )
+            }
             (TableFactor::Series { alias, size }, _) => {
                 format!("SERIES({}) {}", to_sql(size), alias.to_sql_with(quoted))
             }
+            (
+                TableFactor::Fake {
+                    alias,
+                    size,
+                    columns,
+                    seed,
+                },
+                _,
+            ) => {
+                let columns = columns.iter().map(FakeColumn::to_string).join(", ");
+                let seed = seed
+                    .as_ref()
+                    .map(|seed| format!(", {}", to_sql(seed)))
+                    .unwrap_or_default();
+
+                format!(
+                    "FAKE({}, '{columns}'{seed}) {}",
+                    to_sql(size),
+                    alias.to_sql_with(quoted)
+                )
+            }
             (TableFactor::Dictionary { dict, alias }, true) => {
                 format!(r#""{dict}" {}"#, alias.to_sql_with(quoted))
             }
@@ -400,6 +547,27 @@ impl TableFactor {
     }
 }
 
+impl fmt::Display for FakeColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.name, self.kind)
+    }
+}
+
+impl fmt::Display for FakeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FakeKind::Sequence => write!(f, "SEQUENCE"),
+            FakeKind::Name => write!(f, "NAME"),
+            FakeKind::Email => write!(f, "EMAIL"),
+            FakeKind::Integer { min, max } => write!(f, "INTEGER({min}, {max})"),
+            FakeKind::Float { min, max } => write!(f, "FLOAT({min}, {max})"),
+            FakeKind::Boolean => write!(f, "BOOLEAN"),
+            FakeKind::Date { from, to } => write!(f, "DATE({from}, {to})"),
+            FakeKind::Uuid => write!(f, "UUID"),
+        }
+    }
+}
+
 impl ToSql for TableAlias {
     fn to_sql(&self) -> String {
         self.to_sql_with(true)
@@ -414,12 +582,26 @@ impl ToSqlUnquoted for TableAlias {
 
 impl TableAlias {
     fn to_sql_with(&self, quoted: bool) -> String {
-        let TableAlias { name, .. } = self;
+        let TableAlias { name, columns } = self;
 
-        match quoted {
+        let alias = match quoted {
             true => format!(r#"AS "{name}""#),
             false => format!("AS {name}"),
+        };
+        if columns.is_empty() {
+            return alias;
         }
+
+        let columns = columns
+            .iter()
+            .map(|column| match quoted {
+                true => format!(r#""{column}""#),
+                false => column.to_owned(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("{alias} ({columns})")
     }
 }
 
@@ -497,6 +679,11 @@ impl JoinExecutor {
                 key_expr,
                 value_expr,
                 where_clause,
+            }
+            | JoinExecutor::Merge {
+                key_expr,
+                value_expr,
+                where_clause,
             } => {
                 let key_value = format!("{} = {}", to_sql(key_expr), to_sql(value_expr));
                 match where_clause {
@@ -596,9 +783,10 @@ mod tests {
     use {
         crate::{
             ast::{
-                AstLiteral, BinaryOperator, Dictionary, Expr, Join, JoinConstraint, JoinExecutor,
-                JoinOperator, OrderByExpr, Query, Select, SelectItem, SetExpr, TableAlias,
-                TableFactor, TableWithJoins, ToSql, ToSqlUnquoted, Values,
+                AstLiteral, BinaryOperator, Dictionary, Expr, FakeColumn, FakeKind, Join,
+                JoinConstraint, JoinExecutor, JoinOperator, OrderByExpr, Query, Select, SelectItem,
+                SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, ToSql,
+                ToSqlUnquoted, Values,
             },
             parse_sql::parse_expr,
             translate::translate_expr,
@@ -737,6 +925,26 @@ mod tests {
         ]))
         .to_sql();
         assert_eq!(actual, expected);
+
+        let values = |n: &str| {
+            Box::new(SetExpr::Values(Values(vec![vec![Expr::Literal(
+                AstLiteral::Number(BigDecimal::from_str(n).unwrap()),
+            )]])))
+        };
+        let actual = "VALUES (1) EXCEPT ALL (VALUES (2) INTERSECT VALUES (3))".to_owned();
+        let expected = SetExpr::SetOperation {
+            op: SetOperator::Except,
+            all: true,
+            left: values("1"),
+            right: Box::new(SetExpr::SetOperation {
+                op: SetOperator::Intersect,
+                all: false,
+                left: values("2"),
+                right: values("3"),
+            }),
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
     }
 
     #[test]
@@ -1021,6 +1229,48 @@ mod tests {
         .to_sql();
         assert_eq!(actual, expected);
 
+        let actual = r#"(WITH RECURSIVE "t"("n") AS (VALUES (1) UNION ALL SELECT * FROM "t") SELECT * FROM "t") AS "T""#;
+        let expected = TableFactor::Recursive {
+            name: "t".to_owned(),
+            columns: vec!["n".to_owned()],
+            anchor: Box::new(Query {
+                body: SetExpr::Values(Values(vec![vec![Expr::Literal(AstLiteral::Number(
+                    BigDecimal::from_str("1").unwrap(),
+                ))]])),
+                order_by: Vec::new(),
+                limit: None,
+                offset: None,
+            }),
+            recursive: Box::new(Query {
+                body: SetExpr::Select(Box::new(Select {
+                    distinct: false,
+                    projection: vec![SelectItem::Wildcard],
+                    from: TableWithJoins {
+                        relation: TableFactor::Table {
+                            name: "t".to_owned(),
+                            alias: None,
+                            index: None,
+                        },
+                        joins: Vec::new(),
+                    },
+                    selection: None,
+                    group_by: Vec::new(),
+                    having: None,
+                })),
+                order_by: Vec::new(),
+                limit: None,
+                offset: None,
+            }),
+            all: true,
+            alias: TableAlias {
+                name: "T".to_owned(),
+                columns: Vec::new(),
+            },
+            max_depth: None,
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
         let actual = r#"SERIES(3) AS "S""#;
         let expected = TableFactor::Series {
             alias: TableAlias {
@@ -1032,6 +1282,30 @@ mod tests {
         .to_sql();
         assert_eq!(actual, expected);
 
+        let actual = r#"FAKE(3, 'id SEQUENCE, age INTEGER(18, 65)', 42) AS "F""#;
+        let expected = TableFactor::Fake {
+            alias: TableAlias {
+                name: "F".to_owned(),
+                columns: Vec::new(),
+            },
+            size: Expr::Literal(AstLiteral::Number(BigDecimal::from_str("3").unwrap())),
+            columns: vec![
+                FakeColumn {
+                    name: "id".to_owned(),
+                    kind: FakeKind::Sequence,
+                },
+                FakeColumn {
+                    name: "age".to_owned(),
+                    kind: FakeKind::Integer { min: 18, max: 65 },
+                },
+            ],
+            seed: Some(Expr::Literal(AstLiteral::Number(
+                BigDecimal::from_str("42").unwrap(),
+            ))),
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
+
         let actual = r#""GLUE_TABLES" AS "glue""#;
         let expected = TableFactor::Dictionary {
             dict: Dictionary::GlueTables,
@@ -1120,6 +1394,14 @@ mod tests {
         }
         .to_sql();
         assert_eq!(actual, expected);
+
+        let actual = r#"AS "F" ("a", "b")"#;
+        let expected = TableAlias {
+            name: "F".to_owned(),
+            columns: vec!["a".to_owned(), "b".to_owned()],
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
     }
 
     #[test]
@@ -1131,6 +1413,14 @@ mod tests {
         }
         .to_sql_unquoted();
         assert_eq!(actual, expected);
+
+        let actual = "AS F (a, b)";
+        let expected = TableAlias {
+            name: "F".to_owned(),
+            columns: vec!["a".to_owned(), "b".to_owned()],
+        }
+        .to_sql_unquoted();
+        assert_eq!(actual, expected);
     }
 
     #[test]
@@ -1213,6 +1503,23 @@ mod tests {
         }
         .to_sql();
         assert_eq!(actual, expected);
+
+        let actual = r#"INNER JOIN "PlayerItem" ON "PlayerItem"."user_id" = "Player"."id" AND "PlayerItem"."amount" > 10"#;
+        let expected = Join {
+            relation: TableFactor::Table {
+                name: "PlayerItem".to_owned(),
+                alias: None,
+                index: None,
+            },
+            join_operator: JoinOperator::Inner(JoinConstraint::None),
+            join_executor: JoinExecutor::Merge {
+                key_expr: expr("PlayerItem.user_id"),
+                value_expr: expr("Player.id"),
+                where_clause: Some(expr(r#""PlayerItem"."amount" > 10"#)),
+            },
+        }
+        .to_sql();
+        assert_eq!(actual, expected);
     }
 
     #[test]
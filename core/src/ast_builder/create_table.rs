@@ -53,6 +53,8 @@ impl Build for CreateTableNode {
             source: None,
             engine: None,
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            uniques: Vec::new(),
             comment: None,
         })
     }
@@ -80,6 +80,18 @@ impl<'a> ExprNode<'a> {
     pub fn bitwise_shift_right<T: Into<Self>>(self, other: T) -> Self {
         self.binary_op(BinaryOperator::BitwiseShiftRight, other)
     }
+
+    pub fn regex_match<T: Into<Self>>(self, other: T) -> Self {
+        self.binary_op(BinaryOperator::RegexMatch, other)
+    }
+
+    pub fn arrow<T: Into<Self>>(self, other: T) -> Self {
+        self.binary_op(BinaryOperator::Arrow, other)
+    }
+
+    pub fn long_arrow<T: Into<Self>>(self, other: T) -> Self {
+        self.binary_op(BinaryOperator::LongArrow, other)
+    }
 }
 
 #[cfg(test)]
@@ -155,5 +167,13 @@ mod tests {
         let actual = col("id").bitwise_shift_right(num(1));
         let expected = "id >> 1";
         test_expr(actual, expected);
+
+        let actual = col("name").regex_match(text("^G"));
+        let expected = "name ~ '^G'";
+        test_expr(actual, expected);
+
+        let actual = col("doc").arrow(text("user")).long_arrow(text("name"));
+        let expected = "doc -> 'user' ->> 'name'";
+        test_expr(actual, expected);
     }
 }
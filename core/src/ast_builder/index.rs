@@ -1,6 +1,9 @@
 use {
-    super::{Build, OrderByExprNode},
-    crate::{ast::Statement, result::Result},
+    super::{Build, ExprNode, OrderByExprNode},
+    crate::{
+        ast::{Expr, Statement},
+        result::Result,
+    },
 };
 
 #[derive(Clone, Debug)]
@@ -8,6 +11,8 @@ pub struct CreateIndexNode<'a> {
     name: String,
     table_name: String,
     column: OrderByExprNode<'a>,
+    filter_expr: Option<ExprNode<'a>>,
+    fulltext: bool,
 }
 
 impl<'a> CreateIndexNode<'a> {
@@ -16,20 +21,37 @@ impl<'a> CreateIndexNode<'a> {
             table_name,
             name,
             column,
+            filter_expr: None,
+            fulltext: false,
         }
     }
+
+    pub fn filter<T: Into<ExprNode<'a>>>(mut self, expr: T) -> Self {
+        self.filter_expr = Some(expr.into());
+
+        self
+    }
+
+    pub fn fulltext(mut self) -> Self {
+        self.fulltext = true;
+
+        self
+    }
 }
 
 impl<'a> Build for CreateIndexNode<'a> {
     fn build(self) -> Result<Statement> {
         let table_name = self.table_name;
         let name = self.name;
-        let column = self.column.try_into()?;
+        let columns = vec![self.column.try_into()?];
+        let predicate = self.filter_expr.map(Expr::try_from).transpose()?;
 
         Ok(Statement::CreateIndex {
             name,
             table_name,
-            column,
+            columns,
+            predicate,
+            fulltext: self.fulltext,
         })
     }
 }
@@ -68,6 +90,20 @@ mod tests {
         let actual = table("Foo").create_index("nameIndex", "name desc").build();
         let expected = "CREATE INDEX nameIndex ON Foo (name Desc)";
         test(actual, expected);
+
+        let actual = table("Foo")
+            .create_index("nameIndex", "name")
+            .filter("active = TRUE")
+            .build();
+        let expected = "CREATE INDEX nameIndex ON Foo (name) WHERE active = TRUE";
+        test(actual, expected);
+
+        let actual = table("Foo")
+            .create_index("bodyIndex", "body")
+            .fulltext()
+            .build();
+        let expected = "CREATE INDEX bodyIndex ON Foo USING FULLTEXT (body)";
+        test(actual, expected);
     }
 
     #[test]
@@ -0,0 +1,222 @@
+//! Audit log of the statements run through [`crate::prelude::Glue`].
+//!
+//! Once a sink is set, with [`crate::prelude::Glue::with_audit`] or `SET audit_table`, every
+//! statement is recorded whether it succeeds or not. `SET audit = OFF` pauses the recording
+//! and `SET audit = ON` resumes it.
+
+use {
+    crate::{
+        ast::{SetVariable, Statement},
+        ast_builder::{Build, null, num, table, text, timestamp},
+        chrono::{NaiveDateTime, Utc},
+        executor::{Payload, execute, table_names},
+        result::Result,
+        store::{GStore, GStoreMut},
+    },
+    serde::Serialize,
+    std::{
+        fmt::{self, Debug},
+        fs::OpenOptions,
+        io::Write,
+        path::PathBuf,
+    },
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum AuditError {
+    #[error("no audit sink to enable, SET audit_table first")]
+    SinkNotFound,
+
+    #[error("failed to write audit record: {0}")]
+    WriteFailed(String),
+}
+
+/// A statement run through `Glue`, as handed to the [`AuditSink`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AuditRecord {
+    /// User set with `Glue::set_user`, `None` for the embedding application itself.
+    pub user: Option<String>,
+    pub executed_at: NaiveDateTime,
+    /// Kind of the statement, such as `SELECT` or `CREATE TABLE`.
+    pub statement: String,
+    /// Tables the statement reads or writes.
+    pub tables: Vec<String>,
+    /// Rows selected, inserted, updated or deleted.
+    pub rows: Option<usize>,
+    /// Error of a failed statement.
+    pub error: Option<String>,
+}
+
+impl AuditRecord {
+    pub(crate) fn new(user: Option<&str>, statement: &Statement, result: &Result<Payload>) -> Self {
+        Self {
+            user: user.map(ToOwned::to_owned),
+            executed_at: Utc::now().naive_utc(),
+            statement: kind(statement).to_owned(),
+            tables: tables(statement),
+            rows: result.as_ref().ok().and_then(Payload::rows),
+            error: result.as_ref().err().map(ToString::to_string),
+        }
+    }
+}
+
+pub enum AuditSink {
+    /// Inserts a row per statement into the table of the audited storage, creating it if
+    /// needed. Rows inserted inside a transaction which rolls back are lost with it.
+    Table(String),
+    /// Appends a JSON line per statement to the file.
+    File(PathBuf),
+    Callback(Box<dyn FnMut(&AuditRecord) + Send + Sync>),
+}
+
+impl Debug for AuditSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Table(table_name) => f.debug_tuple("Table").field(table_name).finish(),
+            Self::File(path) => f.debug_tuple("File").field(path).finish(),
+            Self::Callback(_) => f.write_str("Callback"),
+        }
+    }
+}
+
+impl AuditSink {
+    pub(crate) async fn write<T: GStore + GStoreMut>(
+        &mut self,
+        storage: &mut T,
+        record: &AuditRecord,
+    ) -> Result<()> {
+        match self {
+            Self::Table(table_name) => {
+                let create_table = table(table_name)
+                    .create_table_if_not_exists()
+                    .add_column("user_name TEXT NULL")
+                    .add_column("executed_at TIMESTAMP")
+                    .add_column("statement TEXT")
+                    .add_column("tables TEXT")
+                    .add_column("row_count INTEGER NULL")
+                    .add_column("error TEXT NULL")
+                    .build()?;
+                execute(storage, &create_table).await?;
+
+                let insert = table(table_name)
+                    .insert()
+                    .values(vec![vec![
+                        record.user.clone().map(text).unwrap_or(null()),
+                        timestamp(record.executed_at.to_string()),
+                        text(record.statement.clone()),
+                        text(record.tables.join(", ")),
+                        record.rows.map(|rows| num(rows as u64)).unwrap_or(null()),
+                        record.error.clone().map(text).unwrap_or(null()),
+                    ]])
+                    .build()?;
+                execute(storage, &insert).await.map(|_| ())
+            }
+            Self::File(path) => {
+                let mut line = serde_json::to_string(record)
+                    .map_err(|error| AuditError::WriteFailed(error.to_string()))?;
+                line.push('\n');
+
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .and_then(|mut file| file.write_all(line.as_bytes()))
+                    .map_err(|error| AuditError::WriteFailed(error.to_string()))?;
+
+                Ok(())
+            }
+            Self::Callback(callback) => {
+                callback(record);
+
+                Ok(())
+            }
+        }
+    }
+}
+
+fn kind(statement: &Statement) -> &'static str {
+    match statement {
+        Statement::ShowColumns { .. } => "SHOW COLUMNS",
+        Statement::Query(_) => "SELECT",
+        Statement::Insert { .. } => "INSERT",
+        Statement::Update { .. } => "UPDATE",
+        Statement::Delete { .. } => "DELETE",
+        Statement::CreateTable { .. } => "CREATE TABLE",
+        Statement::CreateView {
+            materialized: false,
+            ..
+        } => "CREATE VIEW",
+        Statement::CreateView {
+            materialized: true, ..
+        } => "CREATE MATERIALIZED VIEW",
+        Statement::RefreshMaterializedView(_) => "REFRESH MATERIALIZED VIEW",
+        Statement::CreateFunction { .. } => "CREATE FUNCTION",
+        Statement::AlterTable { .. } => "ALTER TABLE",
+        Statement::DropTable { .. } => "DROP TABLE",
+        Statement::DropView { .. } => "DROP VIEW",
+        Statement::DropFunction { .. } => "DROP FUNCTION",
+        Statement::CreateTrigger { .. } => "CREATE TRIGGER",
+        Statement::DropTrigger { .. } => "DROP TRIGGER",
+        Statement::CreateSequence { .. } => "CREATE SEQUENCE",
+        Statement::DropSequence { .. } => "DROP SEQUENCE",
+        Statement::CreateIndex { .. } => "CREATE INDEX",
+        Statement::DropIndex { .. } => "DROP INDEX",
+        Statement::CreateUser { .. } => "CREATE USER",
+        Statement::DropUser { .. } => "DROP USER",
+        Statement::Grant { .. } => "GRANT",
+        Statement::Revoke { .. } => "REVOKE",
+        Statement::StartTransaction => "START TRANSACTION",
+        Statement::Commit => "COMMIT",
+        Statement::Rollback => "ROLLBACK",
+        Statement::ShowVariable(_) => "SHOW",
+        Statement::ShowIndexes(_) => "SHOW INDEXES",
+        Statement::ShowCreateView(_) => "SHOW CREATE VIEW",
+        Statement::Explain { analyze: false, .. } => "EXPLAIN",
+        Statement::Explain { analyze: true, .. } => "EXPLAIN ANALYZE",
+        Statement::Check {
+            table_name: Some(_),
+        } => "CHECK TABLE",
+        Statement::Check { table_name: None } => "CHECK DATABASE",
+        Statement::Analyze { .. } => "ANALYZE",
+        Statement::SetVariable(_) => "SET",
+    }
+}
+
+fn tables(statement: &Statement) -> Vec<String> {
+    match statement {
+        Statement::ShowColumns { table_name }
+        | Statement::AlterTable {
+            name: table_name, ..
+        }
+        | Statement::CreateTrigger { table_name, .. }
+        | Statement::DropTrigger { table_name, .. }
+        | Statement::CreateIndex { table_name, .. }
+        | Statement::DropIndex { table_name, .. }
+        | Statement::ShowIndexes(table_name)
+        | Statement::ShowCreateView(table_name)
+        | Statement::RefreshMaterializedView(table_name)
+        | Statement::Check {
+            table_name: Some(table_name),
+        }
+        | Statement::Analyze {
+            table_name: Some(table_name),
+        }
+        | Statement::SetVariable(SetVariable::AuditTable(table_name)) => {
+            vec![table_name.clone()]
+        }
+        Statement::CreateTable { name, .. } => std::iter::once(name.clone())
+            .chain(table_names(statement))
+            .collect(),
+        Statement::CreateView { name, .. } | Statement::CreateSequence { name, .. } => {
+            vec![name.clone()]
+        }
+        Statement::DropTable { names, .. }
+        | Statement::DropView { names, .. }
+        | Statement::DropSequence { names, .. } => names.clone(),
+        Statement::Grant { table_names, .. } | Statement::Revoke { table_names, .. } => {
+            table_names.clone()
+        }
+        _ => table_names(statement).into_iter().collect(),
+    }
+}
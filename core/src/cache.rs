@@ -0,0 +1,337 @@
+//! Result cache of the queries run through [`crate::prelude::Glue`].
+//!
+//! Results are keyed by the normalized SQL of the planned query, so queries differing only in
+//! whitespace or keyword case share an entry while queries with different parameters do not.
+//! Each `INSERT`, `UPDATE` or `DELETE` run through the same `Glue` drops the entries reading
+//! its table, and schema changes, rollbacks, queries calling sequence functions and writes
+//! firing triggers or cascading through foreign keys drop every entry. Writes made to the
+//! storage directly, or through another `Glue`, are not seen by the cache.
+//!
+//! [`StatementCache`] caches the statements of the SQL run instead, skipping its parsing.
+
+mod statement;
+
+pub use statement::{CacheStats, StatementCache};
+
+use {
+    crate::{
+        ast::{
+            Expr, Function, JoinConstraint, JoinExecutor, JoinOperator, Query, Select, SelectItem,
+            SetExpr, Statement, TableFactor, ToSql, Values,
+        },
+        chrono::{NaiveDateTime, TimeDelta, Utc},
+        executor::{Payload, calls_sequence_functions, table_names},
+        plan::PlanExpr,
+    },
+    std::{
+        collections::{BTreeMap, BTreeSet, HashMap},
+        time::Duration,
+    },
+};
+
+#[derive(Debug)]
+pub struct QueryCache {
+    capacity: usize,
+    ttl: Option<TimeDelta>,
+    max_rows: Option<usize>,
+    entries: HashMap<String, Entry>,
+    /// Keys by their last use, the least recently used first.
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    payload: Payload,
+    tables: BTreeSet<String>,
+    cached_at: NaiveDateTime,
+    tick: u64,
+}
+
+impl QueryCache {
+    /// Creates a cache holding the results of up to `capacity` queries, evicting the least
+    /// recently used one when full.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            ttl: None,
+            max_rows: None,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Expires the results once they have been cached for `ttl`.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = TimeDelta::from_std(ttl).ok();
+        self
+    }
+
+    /// Leaves the results of more than `max_rows` rows out of the cache.
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    /// Drops the results read from the table.
+    pub fn invalidate(&mut self, table_name: &str) {
+        let keys = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.tables.contains(table_name))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        for key in keys {
+            self.remove(&key);
+        }
+    }
+
+    /// Cache key of the statement, `None` unless it is a query whose result depends only on
+    /// the tables it reads.
+    pub(crate) fn key(statement: &Statement) -> Option<String> {
+        match statement {
+            Statement::Query(query) if !volatile_query(query) => Some(query.to_sql()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn get(&mut self, key: &str) -> Option<Payload> {
+        let expired = {
+            let entry = self.entries.get(key)?;
+
+            self.ttl
+                .is_some_and(|ttl| Utc::now().naive_utc() - entry.cached_at >= ttl)
+        };
+        if expired {
+            self.remove(key);
+            return None;
+        }
+
+        self.tick += 1;
+        let entry = self.entries.get_mut(key)?;
+        self.recency.remove(&entry.tick);
+        self.recency.insert(self.tick, key.to_owned());
+        entry.tick = self.tick;
+
+        Some(entry.payload.clone())
+    }
+
+    pub(crate) fn insert(&mut self, key: String, statement: &Statement, payload: &Payload) {
+        let rows = payload.rows().unwrap_or_default();
+        if self.capacity == 0 || self.max_rows.is_some_and(|max_rows| rows > max_rows) {
+            return;
+        }
+
+        self.remove(&key);
+        while self.entries.len() >= self.capacity {
+            let Some((_, key)) = self.recency.pop_first() else {
+                break;
+            };
+
+            self.entries.remove(&key);
+        }
+
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                payload: payload.clone(),
+                tables: table_names(statement),
+                cached_at: Utc::now().naive_utc(),
+                tick: self.tick,
+            },
+        );
+    }
+
+    /// Drops the results a statement which ran, successfully or not, may have changed.
+    pub(crate) fn invalidate_by(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Query(query)
+            | Statement::Explain {
+                analyze: true,
+                query,
+            } if calls_sequence_functions(query) => self.clear(),
+            Statement::Query(_)
+            | Statement::Explain { .. }
+            | Statement::ShowColumns { .. }
+            | Statement::ShowVariable(_)
+            | Statement::ShowIndexes(_)
+            | Statement::ShowCreateView(_)
+            | Statement::Check { .. }
+            | Statement::SetVariable(_)
+            | Statement::StartTransaction
+            | Statement::Commit => {}
+            Statement::Insert { table_name, .. }
+            | Statement::Update { table_name, .. }
+            | Statement::Delete { table_name, .. } => self.invalidate(table_name),
+            _ => self.clear(),
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.tick);
+        }
+    }
+}
+
+/// Whether the query calls a function whose result changes between runs.
+fn volatile_query(query: &Query) -> bool {
+    let Query {
+        body,
+        order_by,
+        limit,
+        offset,
+    } = query;
+
+    volatile_set_expr(body)
+        || order_by.iter().any(|order_by| volatile(&order_by.expr))
+        || limit.iter().chain(offset).any(volatile)
+}
+
+fn volatile_set_expr(set_expr: &SetExpr) -> bool {
+    match set_expr {
+        SetExpr::Select(select) => volatile_select(select),
+        SetExpr::Values(Values(rows)) => rows.iter().flatten().any(volatile),
+        SetExpr::SetOperation { left, right, .. } => {
+            volatile_set_expr(left) || volatile_set_expr(right)
+        }
+    }
+}
+
+fn volatile_select(select: &Select) -> bool {
+    let Select {
+        projection,
+        from,
+        selection,
+        group_by,
+        having,
+        ..
+    } = select;
+
+    let relations = std::iter::once(&from.relation)
+        .chain(from.joins.iter().map(|join| &join.relation))
+        .any(|table_factor| match table_factor {
+            TableFactor::Derived { subquery, .. } => volatile_query(subquery),
+            TableFactor::Recursive {
+                anchor, recursive, ..
+            } => volatile_query(anchor) || volatile_query(recursive),
+            TableFactor::Series { size, .. } => volatile(size),
+            TableFactor::Fake { size, seed, .. } => {
+                seed.as_ref().is_none_or(volatile) || volatile(size)
+            }
+            TableFactor::Table { .. } | TableFactor::Dictionary { .. } => false,
+        });
+    let joins = from.joins.iter().any(|join| {
+        let (JoinOperator::Inner(constraint) | JoinOperator::LeftOuter(constraint)) =
+            &join.join_operator;
+        let constraint = matches!(constraint, JoinConstraint::On(expr) if volatile(expr));
+        let executor = match &join.join_executor {
+            JoinExecutor::NestedLoop => false,
+            JoinExecutor::Hash {
+                key_expr,
+                value_expr,
+                where_clause,
+            }
+            | JoinExecutor::Merge {
+                key_expr,
+                value_expr,
+                where_clause,
+            } => [key_expr, value_expr]
+                .into_iter()
+                .chain(where_clause)
+                .any(volatile),
+        };
+
+        constraint || executor
+    });
+    let projection = projection
+        .iter()
+        .any(|item| matches!(item, SelectItem::Expr { expr, .. } if volatile(expr)));
+
+    relations || joins || projection || selection.iter().chain(group_by).chain(having).any(volatile)
+}
+
+fn volatile(expr: &Expr) -> bool {
+    if let Expr::Function(function) = expr {
+        if matches!(
+            **function,
+            Function::Now()
+                | Function::CurrentDate()
+                | Function::CurrentTime()
+                | Function::CurrentTimestamp()
+                | Function::Rand(None)
+                | Function::GenerateUuid()
+                | Function::Custom { .. }
+                | Function::NextVal(_)
+                | Function::CurrVal(_)
+                | Function::SetVal { .. }
+        ) {
+            return true;
+        }
+    }
+
+    match PlanExpr::from(expr) {
+        PlanExpr::None | PlanExpr::Identifier(_) | PlanExpr::CompoundIdentifier { .. } => false,
+        PlanExpr::Expr(expr) => volatile(expr),
+        PlanExpr::TwoExprs(expr, expr2) => volatile(expr) || volatile(expr2),
+        PlanExpr::ThreeExprs(expr, expr2, expr3) => {
+            volatile(expr) || volatile(expr2) || volatile(expr3)
+        }
+        PlanExpr::MultiExprs(exprs) => exprs.into_iter().any(volatile),
+        PlanExpr::Query(query) => volatile_query(query),
+        PlanExpr::QueryAndExpr { query, expr } => volatile(expr) || volatile_query(query),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::QueryCache,
+        crate::{parse_sql::parse, translate::translate},
+    };
+
+    fn key(sql: &str) -> Option<String> {
+        let parsed = parse(sql).expect(sql);
+        let statement = translate(&parsed[0]).expect(sql);
+
+        QueryCache::key(&statement)
+    }
+
+    #[test]
+    fn cache_key() {
+        assert_eq!(
+            key("select id FROM Item  where id = 1"),
+            key("SELECT id FROM Item WHERE id = 1")
+        );
+        assert_ne!(
+            key("SELECT id FROM Item WHERE id = 1"),
+            key("SELECT id FROM Item WHERE id = 2")
+        );
+
+        assert!(key("SELECT RAND(1) FROM Item").is_some());
+        assert!(key("INSERT INTO Item VALUES (1)").is_none());
+        assert!(key("SELECT NOW()").is_none());
+        assert!(key("SELECT id FROM Item WHERE id > RAND()").is_none());
+        assert!(key("SELECT * FROM Item WHERE id IN (SELECT GENERATE_UUID())").is_none());
+        assert!(key("SELECT * FROM (SELECT CURRENT_DATE AS d) AS Today").is_none());
+        assert!(key("SELECT * FROM FAKE(3, 'id SEQUENCE', 1)").is_some());
+        assert!(key("SELECT * FROM FAKE(3, 'id SEQUENCE')").is_none());
+    }
+}
@@ -0,0 +1,215 @@
+use {
+    crate::ast::Statement,
+    std::collections::{BTreeMap, HashMap},
+};
+
+/// Statements of the SQL run through [`crate::prelude::Glue`], keyed by the text of the SQL, so
+/// SQL run again is not parsed and translated again.
+///
+/// With [`StatementCache::with_plans`], the planned statements are cached instead, which skips
+/// planning as well. Plans read the schemas and statistics of the tables, so every statement
+/// run through the same `Glue` which may change them, such as `CREATE INDEX` or `ANALYZE`,
+/// drops the cached plans. Schemas changed by other means are not seen by the cache.
+#[derive(Debug)]
+pub struct StatementCache {
+    capacity: usize,
+    plans: bool,
+    entries: HashMap<String, Entry>,
+    /// Keys by their last use, the least recently used first.
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+    stats: CacheStats,
+}
+
+#[derive(Debug)]
+struct Entry {
+    statements: Vec<Statement>,
+    tick: u64,
+}
+
+/// Lookups of SQL in a [`StatementCache`] since it was created.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// SQL whose statements were cached.
+    pub hits: u64,
+    /// SQL parsed, as its statements were not cached.
+    pub misses: u64,
+}
+
+impl StatementCache {
+    /// Creates a cache holding the statements of up to `capacity` SQL texts, evicting the
+    /// least recently used one when full.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            plans: false,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Caches the planned statements rather than the translated ones.
+    pub fn with_plans(mut self) -> Self {
+        self.plans = true;
+        self
+    }
+
+    pub fn plans(&self) -> bool {
+        self.plans
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    pub(crate) fn get(&mut self, sql: &str) -> Option<Vec<Statement>> {
+        let Some(entry) = self.entries.get_mut(sql) else {
+            self.stats.misses += 1;
+            return None;
+        };
+
+        self.stats.hits += 1;
+        self.tick += 1;
+        self.recency.remove(&entry.tick);
+        self.recency.insert(self.tick, sql.to_owned());
+        entry.tick = self.tick;
+
+        Some(entry.statements.clone())
+    }
+
+    pub(crate) fn insert(&mut self, sql: &str, statements: Vec<Statement>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if let Some(entry) = self.entries.remove(sql) {
+            self.recency.remove(&entry.tick);
+        }
+        while self.entries.len() >= self.capacity {
+            let Some((_, sql)) = self.recency.pop_first() else {
+                break;
+            };
+
+            self.entries.remove(&sql);
+        }
+
+        self.tick += 1;
+        self.recency.insert(self.tick, sql.to_owned());
+        self.entries.insert(
+            sql.to_owned(),
+            Entry {
+                statements,
+                tick: self.tick,
+            },
+        );
+    }
+
+    /// Drops the cached plans when the statement, which ran successfully or not, may have
+    /// changed the schemas or statistics they were planned with.
+    pub(crate) fn invalidate_by(&mut self, statement: &Statement) {
+        if !self.plans {
+            return;
+        }
+
+        match statement {
+            Statement::Query(_)
+            | Statement::Explain { .. }
+            | Statement::Insert { .. }
+            | Statement::Update { .. }
+            | Statement::Delete { .. }
+            | Statement::ShowColumns { .. }
+            | Statement::ShowVariable(_)
+            | Statement::ShowIndexes(_)
+            | Statement::ShowCreateView(_)
+            | Statement::Check { .. }
+            | Statement::SetVariable(_)
+            | Statement::StartTransaction
+            | Statement::Commit => {}
+            _ => self.clear(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{CacheStats, StatementCache},
+        crate::{ast::Statement, parse_sql::parse, translate::translate},
+    };
+
+    fn statements(sql: &str) -> Vec<Statement> {
+        parse(sql)
+            .unwrap()
+            .iter()
+            .map(|statement| translate(statement).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn lru() {
+        let mut cache = StatementCache::new(2);
+        let sqls = ["SELECT 1", "SELECT 2", "SELECT 3"];
+
+        assert_eq!(cache.get(sqls[0]), None);
+        cache.insert(sqls[0], statements(sqls[0]));
+        cache.insert(sqls[1], statements(sqls[1]));
+        assert_eq!(cache.get(sqls[0]), Some(statements(sqls[0])));
+
+        // the least recently used SQL is evicted once the cache is full
+        cache.insert(sqls[2], statements(sqls[2]));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(sqls[1]), None);
+        assert_eq!(cache.get(sqls[0]), Some(statements(sqls[0])));
+        assert_eq!(cache.get(sqls[2]), Some(statements(sqls[2])));
+        assert_eq!(cache.get("select 1"), None);
+
+        assert_eq!(cache.stats(), CacheStats { hits: 3, misses: 3 });
+
+        let mut cache = StatementCache::new(0);
+        cache.insert(sqls[0], statements(sqls[0]));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn invalidate_by() {
+        let sql = "SELECT * FROM Item";
+        let mut translated = StatementCache::new(8);
+        let mut planned = StatementCache::new(8).with_plans();
+
+        for statement in statements(
+            "
+            INSERT INTO Item VALUES (1);
+            SELECT * FROM Item;
+            START TRANSACTION;
+            COMMIT;
+            CREATE INDEX idx_id ON Item (id);
+            ",
+        ) {
+            for cache in [&mut translated, &mut planned] {
+                if cache.is_empty() {
+                    cache.insert(sql, statements(sql));
+                }
+                cache.invalidate_by(&statement);
+            }
+
+            let index = matches!(statement, Statement::CreateIndex { .. });
+            assert_eq!(planned.is_empty(), index, "{statement:?}");
+            assert_eq!(translated.len(), 1, "{statement:?}");
+        }
+    }
+}
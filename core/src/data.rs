@@ -3,11 +3,15 @@ mod function;
 mod interval;
 mod key;
 mod literal;
+mod password;
 mod point;
 mod row;
+mod statistics;
 mod string_ext;
 mod table;
+mod user;
 
+pub mod fulltext;
 pub mod schema;
 pub mod value;
 
@@ -17,10 +21,16 @@ pub use {
     interval::{Interval, IntervalError},
     key::{Key, KeyError},
     literal::{Literal, LiteralError},
+    password::{Password, native_password_hash, verify_native_password},
     point::Point,
     row::{Row, RowError},
-    schema::{Schema, SchemaIndex, SchemaIndexOrd, SchemaParseError},
+    schema::{
+        LegacySchema, LegacySchemaIndex, Schema, SchemaIndex, SchemaIndexOrd, SchemaParseError,
+        SchemaSequence, SchemaTrigger, SchemaView,
+    },
+    statistics::{ColumnStatistics, TableStatistics},
     string_ext::{StringExt, StringExtError},
     table::{TableError, get_alias, get_index},
+    user::User,
     value::{BTreeMapJsonExt, ConvertError, NumericBinaryOperator, Value, ValueError},
 };
@@ -0,0 +1,59 @@
+//! Terms of text for full-text indexes and `MATCH ... AGAINST`.
+//!
+//! Text is split into terms at every character which is neither a letter nor a digit, and the
+//! terms are lowercased, so `Rust-based engine` has the terms `rust`, `based` and `engine`.
+
+use std::collections::BTreeSet;
+
+/// Terms of the text in the order they appear, repeated as often as they appear.
+pub fn terms(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Terms of the text without the repeated ones, the keys a full-text index holds a row by.
+pub fn distinct_terms(text: &str) -> BTreeSet<String> {
+    terms(text).into_iter().collect()
+}
+
+/// Relevance of the text to the terms of the query, 0 when the text has none of them.
+///
+/// Each term of the query found in the text adds `n / (n + 1)` for the `n` times the text has
+/// it, so a text with more of the terms ranks above one repeating a single term. The relevance
+/// reads the text alone, with no statistics of the other rows of the table.
+pub fn relevance(text: &str, query: &str) -> f64 {
+    let terms = terms(text);
+
+    distinct_terms(query)
+        .iter()
+        .map(|term| terms.iter().filter(|found| *found == term).count() as f64)
+        .map(|n| n / (n + 1.0))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{relevance, terms};
+
+    #[test]
+    fn split_terms() {
+        assert_eq!(
+            terms("Rust-based SQL engine, v0.16!"),
+            vec!["rust", "based", "sql", "engine", "v0", "16"]
+        );
+        assert_eq!(terms("  ,. "), Vec::<String>::new());
+        assert_eq!(terms("Ärger über CAFÉ"), vec!["ärger", "über", "café"]);
+    }
+
+    #[test]
+    fn rank() {
+        assert_eq!(relevance("rust engine", "python"), 0.0);
+        assert_eq!(relevance("rust engine", "RUST"), 0.5);
+        assert_eq!(relevance("rust engine", "rust rust"), 0.5);
+        assert_eq!(relevance("rust engine", "rust engine"), 1.0);
+        assert!(relevance("rust, rust and rust", "rust") > relevance("rust engine", "rust"));
+        assert!(relevance("rust engine", "rust engine") > relevance("rust rust rust", "rust"));
+    }
+}
@@ -1,5 +1,6 @@
 use {
     crate::{
+        ast::{ColumnDef, ColumnUniqueOption},
         data::{Interval, Value},
         result::{Error, Result},
     },
@@ -51,6 +52,8 @@ pub enum Key {
     Uuid(u128),
     Inet(IpAddr),
     None,
+    /// Key of a composite primary key, with the key of each of its columns
+    Composite(Vec<Key>),
 }
 
 impl Ord for Key {
@@ -81,6 +84,7 @@ impl Ord for Key {
             }),
             (Key::Uuid(l), Key::Uuid(r)) => l.cmp(r),
             (Key::Inet(l), Key::Inet(r)) => l.cmp(r),
+            (Key::Composite(l), Key::Composite(r)) => l.cmp(r),
             (Key::None, Key::None) => Ordering::Equal,
             (Key::None, _) => Ordering::Greater,
             (_, Key::None) => Ordering::Less,
@@ -173,6 +177,7 @@ impl From<Key> for Value {
             Key::Interval(v) => Value::Interval(v),
             Key::Uuid(v) => Value::Uuid(v),
             Key::None => Value::Null,
+            Key::Composite(keys) => Value::List(keys.into_iter().map(Value::from).collect()),
         }
     }
 }
@@ -337,6 +342,42 @@ impl Key {
                 .copied()
                 .collect::<Vec<_>>(),
             Key::None => vec![NONE],
+            Key::Composite(keys) => {
+                // Zeros of each key are escaped and each key ends with two zeros, so a key
+                // sorts before the longer keys it prefixes.
+                let mut bytes = vec![VALUE];
+                for key in keys {
+                    for byte in key.to_cmp_be_bytes()? {
+                        bytes.push(byte);
+                        if byte == 0 {
+                            bytes.push(u8::MAX);
+                        }
+                    }
+
+                    bytes.extend([0, 0]);
+                }
+
+                bytes
+            }
+        })
+    }
+
+    /// Key of a row by its primary key, `None` when the table has none. Rows of a composite
+    /// primary key are keyed by the values of its columns, in the order of the table.
+    pub fn primary(column_defs: &[ColumnDef], values: &[Value]) -> Result<Option<Self>> {
+        let mut keys = column_defs
+            .iter()
+            .zip(values)
+            .filter(|(ColumnDef { unique, .. }, _)| {
+                unique == &Some(ColumnUniqueOption { is_primary: true })
+            })
+            .map(|(_, value)| Key::try_from(value))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(match keys.len() {
+            0 => None,
+            1 => keys.pop(),
+            _ => Some(Key::Composite(keys)),
         })
     }
 
@@ -365,6 +406,7 @@ impl Key {
             Key::Uuid(_) => 21,
             Key::Inet(_) => 22,
             Key::None => 23,
+            Key::Composite(_) => 24,
         }
     }
 }
@@ -817,6 +859,23 @@ mod tests {
         assert_eq!(cmp(&n2, &n1), Ordering::Greater);
         assert_eq!(cmp(&n1, &null), Ordering::Less);
 
+        let composite = |l: &str, r: &str| {
+            Composite(vec![Str(l.to_owned()), Str(r.to_owned())]).to_cmp_be_bytes()
+        };
+        let n1 = composite("a", "bc");
+        let n2 = composite("ab", "c");
+        let n3 = composite("ab", "");
+        let n4 = composite("a\0", "c");
+        let n5 = Composite(vec![I64(1), None]).to_cmp_be_bytes();
+        let n6 = Composite(vec![I64(1), I64(2)]).to_cmp_be_bytes();
+
+        assert_eq!(cmp(&n1, &n1), Ordering::Equal);
+        assert_eq!(cmp(&n1, &n2), Ordering::Less);
+        assert_eq!(cmp(&n3, &n2), Ordering::Less);
+        assert_eq!(cmp(&n1, &n4), Ordering::Less);
+        assert_eq!(cmp(&n4, &n3), Ordering::Less);
+        assert_eq!(cmp(&n6, &n5), Ordering::Less);
+
         assert_eq!(
             F64(12.34.into()).to_cmp_be_bytes(),
             Err(KeyError::FloatToCmpBigEndianNotSupported.into())
@@ -0,0 +1,202 @@
+use {
+    hmac::{Hmac, Mac},
+    serde::{Deserialize, Serialize},
+    sha1::Sha1,
+    sha2::{Digest, Sha256},
+};
+
+const SCRAM_ITERATIONS: u32 = 4096;
+const SCRAM_SALT_LEN: usize = 16;
+
+/// Verifiers of the password of a [`crate::data::User`], derived from it when the user is
+/// created. The password itself is not kept.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Password {
+    /// SCRAM-SHA-256 verifier with a random salt, as PostgreSQL keeps it:
+    /// `SCRAM-SHA-256$<iterations>:<salt>$<stored key>:<server key>` with the keys in hex.
+    pub scram: String,
+    /// `SHA1(SHA1(password))` in hex, as MySQL keeps it for `mysql_native_password` logins,
+    /// which cannot be checked against a salted hash. Empty for an empty password.
+    pub native: String,
+}
+
+impl Password {
+    pub fn new(password: &str) -> Self {
+        let salt = rand::random::<[u8; SCRAM_SALT_LEN]>();
+        let salted = salted_password(password, &salt, SCRAM_ITERATIONS);
+        let stored_key = Sha256::digest(hmac(&salted, b"Client Key"));
+        let server_key = hmac(&salted, b"Server Key");
+
+        Self {
+            scram: format!(
+                "SCRAM-SHA-256${SCRAM_ITERATIONS}:{}${}:{}",
+                hex::encode(salt),
+                hex::encode(stored_key),
+                hex::encode(server_key),
+            ),
+            native: hex::encode(native_password_hash(password)),
+        }
+    }
+
+    /// Checks a password given in clear text against the SCRAM verifier.
+    pub fn verify(&self, password: &str) -> bool {
+        let Some((iterations, salt, stored_key)) = self.scram_parts() else {
+            return false;
+        };
+
+        let salted = salted_password(password, &salt, iterations);
+        let client_key = hmac(&salted, b"Client Key");
+
+        equals(&Sha256::digest(client_key), &stored_key)
+    }
+
+    /// Checks the `response` of a `mysql_native_password` login to `scramble`, see
+    /// [`verify_native_password`].
+    pub fn verify_native(&self, scramble: &[u8], response: &[u8]) -> bool {
+        hex::decode(&self.native)
+            .is_ok_and(|hash| verify_native_password(&hash, scramble, response))
+    }
+
+    fn scram_parts(&self) -> Option<(u32, Vec<u8>, Vec<u8>)> {
+        let (params, keys) = self.scram.strip_prefix("SCRAM-SHA-256$")?.split_once('$')?;
+        let (iterations, salt) = params.split_once(':')?;
+        let (stored_key, _) = keys.split_once(':')?;
+
+        Some((
+            iterations.parse().ok()?,
+            hex::decode(salt).ok()?,
+            hex::decode(stored_key).ok()?,
+        ))
+    }
+}
+
+/// `SHA1(SHA1(password))`, empty for an empty password.
+pub fn native_password_hash(password: &str) -> Vec<u8> {
+    if password.is_empty() {
+        return Vec::new();
+    }
+
+    Sha1::digest(Sha1::digest(password.as_bytes())).to_vec()
+}
+
+/// Checks the `response` of a `mysql_native_password` login, which is
+/// `SHA1(password) XOR SHA1(scramble + SHA1(SHA1(password)))`, against `hash`, the
+/// [`native_password_hash`] of the password. An empty password is answered with an empty
+/// response.
+pub fn verify_native_password(hash: &[u8], scramble: &[u8], response: &[u8]) -> bool {
+    if hash.is_empty() || response.is_empty() {
+        return hash.is_empty() && response.is_empty();
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update(scramble);
+    hasher.update(hash);
+    let stage1 = hasher
+        .finalize()
+        .iter()
+        .zip(response)
+        .map(|(a, b)| a ^ b)
+        .collect::<Vec<_>>();
+
+    response.len() == stage1.len() && equals(&Sha1::digest(stage1), hash)
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let code = Hmac::<Sha256>::new_from_slice(key)
+        .expect("HMAC takes keys of any length")
+        .chain_update(data)
+        .finalize()
+        .into_bytes();
+
+    let mut bytes = [0; 32];
+    bytes.copy_from_slice(&code);
+
+    bytes
+}
+
+/// `Hi(password, salt, iterations)` of SCRAM, which is PBKDF2 with HMAC-SHA-256.
+fn salted_password(password: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut block = hmac(
+        password.as_bytes(),
+        &[salt, &1_u32.to_be_bytes()[..]].concat(),
+    );
+    let mut salted = block;
+    for _ in 1..iterations {
+        block = hmac(password.as_bytes(), &block);
+        salted.iter_mut().zip(block).for_each(|(a, b)| *a ^= b);
+    }
+
+    salted
+}
+
+/// Compares in time independent of where the bytes differ.
+fn equals(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Password, native_password_hash, verify_native_password};
+
+    /// The response a `mysql_native_password` client sends.
+    fn native_response(password: &str, scramble: &[u8]) -> Vec<u8> {
+        use sha1::{Digest, Sha1};
+
+        if password.is_empty() {
+            return Vec::new();
+        }
+
+        let stage1 = Sha1::digest(password.as_bytes());
+        let mut hasher = Sha1::new();
+        hasher.update(scramble);
+        hasher.update(Sha1::digest(stage1));
+
+        hasher
+            .finalize()
+            .iter()
+            .zip(stage1)
+            .map(|(a, b)| a ^ b)
+            .collect()
+    }
+
+    #[test]
+    fn verify() {
+        let password = Password::new("sticky");
+
+        assert!(password.scram.starts_with("SCRAM-SHA-256$4096:"));
+        assert!(!password.scram.contains("sticky"));
+        assert!(password.verify("sticky"));
+        assert!(!password.verify("wrong"));
+        assert!(!password.verify(""));
+        assert_ne!(password.scram, Password::new("sticky").scram);
+
+        let broken = Password {
+            scram: "sticky".to_owned(),
+            native: String::new(),
+        };
+        assert!(!broken.verify("sticky"));
+    }
+
+    #[test]
+    fn verify_native() {
+        let scramble = b"01234567890123456789";
+        let password = Password::new("sticky");
+
+        assert_eq!(password.native, hex::encode(native_password_hash("sticky")));
+        assert!(password.verify_native(scramble, &native_response("sticky", scramble)));
+        assert!(!password.verify_native(scramble, &native_response("wrong", scramble)));
+        assert!(!password.verify_native(
+            b"98765432109876543210",
+            &native_response("sticky", scramble)
+        ));
+        assert!(!password.verify_native(scramble, &[]));
+
+        let empty = native_password_hash("");
+        assert!(verify_native_password(&empty, scramble, &[]));
+        assert!(!verify_native_password(
+            &empty,
+            scramble,
+            &native_response("x", scramble)
+        ));
+    }
+}
@@ -1,6 +1,9 @@
 use {
     crate::{
-        ast::{ColumnDef, Expr, ForeignKey, OrderByExpr, Statement, ToSql},
+        ast::{
+            CheckConstraint, ColumnDef, ColumnUniqueOption, DataType, Expr, ForeignKey,
+            OrderByExpr, Query, Statement, ToSql, TriggerEvent, TriggerTiming, UniqueConstraint,
+        },
         prelude::{parse, translate},
         result::Result,
     },
@@ -22,11 +25,62 @@ pub enum SchemaIndexOrd {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct SchemaIndex {
     pub name: String,
-    pub expr: Expr,
+    /// expressions of the index columns, more than one for a composite index
+    pub exprs: Vec<Expr>,
     pub order: SchemaIndexOrd,
+    /// `WHERE` of a partial index, which holds only the rows it is true for
+    pub predicate: Option<Expr>,
+    /// Full-text index of `USING FULLTEXT`, which holds each row by the terms of its text
+    /// rather than by its value
+    pub fulltext: bool,
     pub created: NaiveDateTime,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SchemaView {
+    pub query: Query,
+    /// Materialized views keep the rows of their query, recomputed on `REFRESH`
+    pub materialized: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SchemaTrigger {
+    pub name: String,
+    pub timing: TriggerTiming,
+    pub events: Vec<TriggerEvent>,
+    pub condition: Option<Expr>,
+    /// SQL of the statements run for each changed row
+    pub body: Vec<String>,
+}
+
+/// A sequence keeps the last value `NEXTVAL` returned as the single row of its schema.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SchemaSequence {
+    pub increment: i64,
+    pub start: i64,
+}
+
+impl SchemaSequence {
+    pub const COLUMN_NAME: &'static str = "last_value";
+
+    pub fn column_defs() -> Vec<ColumnDef> {
+        vec![ColumnDef {
+            name: Self::COLUMN_NAME.to_owned(),
+            data_type: DataType::Int,
+            nullable: false,
+            default: None,
+            unique: None,
+            comment: None,
+        }]
+    }
+
+    /// Name of the sequence a `SERIAL` column draws its values from, created and dropped
+    /// along with its table.
+    pub fn serial_name(table_name: &str, column_name: &str) -> String {
+        format!("{table_name}_{column_name}_seq")
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Schema {
     pub table_name: String,
@@ -34,7 +88,13 @@ pub struct Schema {
     pub indexes: Vec<SchemaIndex>,
     pub engine: Option<String>,
     pub foreign_keys: Vec<ForeignKey>,
+    pub checks: Vec<CheckConstraint>,
+    pub uniques: Vec<UniqueConstraint>,
     pub comment: Option<String>,
+    /// The query of a view, which has no rows of its own unless materialized
+    pub view: Option<SchemaView>,
+    pub triggers: Vec<SchemaTrigger>,
+    pub sequence: Option<SchemaSequence>,
 }
 
 impl Schema {
@@ -45,15 +105,63 @@ impl Schema {
             indexes,
             engine,
             foreign_keys,
+            checks,
+            uniques,
             comment,
+            view,
+            triggers,
+            sequence,
         } = self;
 
+        if let Some(SchemaSequence { increment, start }) = sequence {
+            return format!(
+                r#"CREATE SEQUENCE "{table_name}" INCREMENT BY {increment} START WITH {start};"#
+            );
+        }
+
+        if let Some(SchemaView {
+            materialized: false,
+            ..
+        }) = view
+        {
+            return self.view_ddl().unwrap_or_default();
+        }
+
         let columns = column_defs.as_ref().map(|column_defs| {
+            let primary_key = column_defs
+                .iter()
+                .filter(|column_def| {
+                    column_def.unique == Some(ColumnUniqueOption { is_primary: true })
+                })
+                .map(|ColumnDef { name, .. }| format!(r#""{name}""#))
+                .collect::<Vec<_>>();
+            let (column_defs, primary_key) = match primary_key.len() {
+                0 | 1 => (column_defs.iter().map(ToSql::to_sql).collect(), None),
+                _ => (
+                    column_defs
+                        .iter()
+                        .map(|column_def| {
+                            let unique = column_def.unique.filter(|unique| !unique.is_primary);
+
+                            ColumnDef {
+                                unique,
+                                ..column_def.clone()
+                            }
+                            .to_sql()
+                        })
+                        .collect::<Vec<_>>(),
+                    Some(format!("PRIMARY KEY ({})", primary_key.join(", "))),
+                ),
+            };
             let foreign_keys = foreign_keys.iter().map(ToSql::to_sql);
+            let checks = checks.iter().map(ToSql::to_sql);
+            let uniques = uniques.iter().map(ToSql::to_sql);
             let body = column_defs
-                .iter()
-                .map(ToSql::to_sql)
+                .into_iter()
+                .chain(primary_key)
+                .chain(uniques)
                 .chain(foreign_keys)
+                .chain(checks)
                 .collect::<Vec<_>>()
                 .join(", ");
 
@@ -76,48 +184,152 @@ impl Schema {
         .join(" ")
             + ";";
 
-        let create_indexes = indexes.iter().map(|SchemaIndex { name, expr, .. }| {
-            let expr = expr.to_sql();
+        let create_indexes = indexes.iter().map(
+            |SchemaIndex {
+                 name,
+                 exprs,
+                 predicate,
+                 fulltext,
+                 ..
+             }| {
+                let exprs = exprs
+                    .iter()
+                    .map(ToSql::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let predicate = predicate
+                    .as_ref()
+                    .map(|predicate| format!(" WHERE {}", predicate.to_sql()))
+                    .unwrap_or_default();
+
+                let using = if *fulltext { " USING FULLTEXT" } else { "" };
+
+                format!(r#"CREATE INDEX "{name}" ON "{table_name}"{using} ({exprs}){predicate};"#)
+            },
+        );
+
+        let create_triggers = triggers.iter().map(|trigger| {
+            let SchemaTrigger {
+                name,
+                timing,
+                events,
+                condition,
+                body,
+            } = trigger;
+            let events = events
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" OR ");
+            let condition = condition
+                .as_ref()
+                .map(|condition| format!(" WHEN {}", condition.to_sql()))
+                .unwrap_or_default();
+            let body = body
+                .iter()
+                .map(|sql| format!("{sql}; "))
+                .collect::<String>();
 
-            format!(r#"CREATE INDEX "{name}" ON "{table_name}" ({expr});"#)
+            format!(
+                r#"CREATE TRIGGER "{name}" {timing} {events} ON "{table_name}" FOR EACH ROW{condition} BEGIN {body}END;"#
+            )
         });
 
         iter::once(create_table)
             .chain(create_indexes)
+            .chain(self.view_ddl())
+            .chain(create_triggers)
             .collect::<Vec<_>>()
             .join("\n")
     }
 
+    /// Returns the `CREATE VIEW` or `CREATE MATERIALIZED VIEW` statement of a view.
+    pub fn view_ddl(&self) -> Option<String> {
+        let SchemaView {
+            query,
+            materialized,
+        } = self.view.as_ref()?;
+        let materialized = if *materialized { "MATERIALIZED " } else { "" };
+
+        Some(format!(
+            r#"CREATE {materialized}VIEW "{}" AS {};"#,
+            self.table_name,
+            query.to_sql()
+        ))
+    }
+
     pub fn from_ddl(ddl: &str) -> Result<Schema> {
         let created = Utc::now().naive_utc();
         let statements = parse(ddl)?;
 
+        let mut view = None;
+        let mut triggers = Vec::new();
         let indexes = statements
             .iter()
             .skip(1)
-            .map(|create_index| {
-                let create_index = translate(create_index)?;
-                match create_index {
-                    Statement::CreateIndex {
+            .filter_map(|statement| match translate(statement) {
+                Ok(Statement::CreateView {
+                    query,
+                    materialized: true,
+                    ..
+                }) => {
+                    view = Some(SchemaView {
+                        query: *query,
+                        materialized: true,
+                    });
+
+                    None
+                }
+                Ok(Statement::CreateTrigger {
+                    name,
+                    timing,
+                    events,
+                    condition,
+                    body,
+                    ..
+                }) => {
+                    triggers.push(SchemaTrigger {
+                        name,
+                        timing,
+                        events,
+                        condition,
+                        body,
+                    });
+
+                    None
+                }
+                create_index => Some(create_index),
+            })
+            .map(|create_index| match create_index? {
+                Statement::CreateIndex {
+                    name,
+                    columns,
+                    predicate,
+                    fulltext,
+                    ..
+                } => {
+                    let order = columns
+                        .first()
+                        .and_then(|OrderByExpr { asc, .. }| *asc)
+                        .and_then(|bool| bool.then_some(SchemaIndexOrd::Asc))
+                        .unwrap_or(SchemaIndexOrd::Both);
+                    let exprs = columns
+                        .into_iter()
+                        .map(|OrderByExpr { expr, .. }| expr)
+                        .collect();
+
+                    let index = SchemaIndex {
                         name,
-                        column: OrderByExpr { expr, asc },
-                        ..
-                    } => {
-                        let order = asc
-                            .and_then(|bool| bool.then_some(SchemaIndexOrd::Asc))
-                            .unwrap_or(SchemaIndexOrd::Both);
-
-                        let index = SchemaIndex {
-                            name,
-                            expr,
-                            order,
-                            created,
-                        };
-
-                        Ok(index)
-                    }
-                    _ => Err(SchemaParseError::CannotParseDDL.into()),
+                        exprs,
+                        order,
+                        predicate,
+                        fulltext,
+                        created,
+                    };
+
+                    Ok(index)
                 }
+                _ => Err(SchemaParseError::CannotParseDDL.into()),
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -125,11 +337,52 @@ impl Schema {
         let create_table = translate(create_table)?;
 
         match create_table {
+            Statement::CreateView {
+                name,
+                query,
+                materialized: false,
+                ..
+            } if statements.len() == 1 => Ok(Schema {
+                table_name: name,
+                column_defs: None,
+                indexes,
+                engine: None,
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                uniques: Vec::new(),
+                comment: None,
+                view: Some(SchemaView {
+                    query: *query,
+                    materialized: false,
+                }),
+                triggers: Vec::new(),
+                sequence: None,
+            }),
+            Statement::CreateSequence {
+                name,
+                increment,
+                start,
+                ..
+            } if statements.len() == 1 => Ok(Schema {
+                table_name: name,
+                column_defs: Some(SchemaSequence::column_defs()),
+                indexes,
+                engine: None,
+                foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                uniques: Vec::new(),
+                comment: None,
+                view: None,
+                triggers: Vec::new(),
+                sequence: Some(SchemaSequence { increment, start }),
+            }),
             Statement::CreateTable {
                 name,
                 columns,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
                 ..
             } => Ok(Schema {
@@ -138,13 +391,86 @@ impl Schema {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view,
+                triggers,
+                sequence: None,
             }),
             _ => Err(SchemaParseError::CannotParseDDL.into()),
         }
     }
 }
 
+/// Layout of [`Schema`] written by storages before tables had checks, unique constraints,
+/// views, triggers and sequences, and before indexes took more than one expression. Storages
+/// which persist schemas in a format without field names read their old schemas with it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LegacySchema {
+    pub table_name: String,
+    pub column_defs: Option<Vec<ColumnDef>>,
+    pub indexes: Vec<LegacySchemaIndex>,
+    pub engine: Option<String>,
+    pub foreign_keys: Vec<ForeignKey>,
+    pub comment: Option<String>,
+}
+
+/// Layout of [`SchemaIndex`] in a [`LegacySchema`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LegacySchemaIndex {
+    pub name: String,
+    pub expr: Expr,
+    pub order: SchemaIndexOrd,
+    pub created: NaiveDateTime,
+}
+
+impl From<LegacySchema> for Schema {
+    fn from(schema: LegacySchema) -> Self {
+        let LegacySchema {
+            table_name,
+            column_defs,
+            indexes,
+            engine,
+            foreign_keys,
+            comment,
+        } = schema;
+
+        let indexes = indexes
+            .into_iter()
+            .map(
+                |LegacySchemaIndex {
+                     name,
+                     expr,
+                     order,
+                     created,
+                 }| SchemaIndex {
+                    name,
+                    exprs: vec![expr],
+                    order,
+                    predicate: None,
+                    fulltext: false,
+                    created,
+                },
+            )
+            .collect();
+
+        Schema {
+            table_name,
+            column_defs,
+            indexes,
+            engine,
+            foreign_keys,
+            checks: Vec::new(),
+            uniques: Vec::new(),
+            comment,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
+        }
+    }
+}
+
 #[derive(ThisError, Debug, PartialEq, Serialize)]
 pub enum SchemaParseError {
     #[error("cannot parse ddl")]
@@ -156,9 +482,12 @@ mod tests {
     use {
         super::SchemaParseError,
         crate::{
-            ast::{AstLiteral, ColumnDef, ColumnUniqueOption, Expr},
+            ast::{
+                AstLiteral, BinaryOperator, CheckConstraint, ColumnDef, ColumnUniqueOption, Expr,
+                TriggerEvent, TriggerTiming, UniqueConstraint,
+            },
             chrono::Utc,
-            data::{Schema, SchemaIndex, SchemaIndexOrd},
+            data::{Schema, SchemaIndex, SchemaIndexOrd, SchemaSequence, SchemaTrigger},
             prelude::DataType,
         },
     };
@@ -170,7 +499,12 @@ mod tests {
             indexes,
             engine,
             foreign_keys,
+            checks,
+            uniques,
             comment,
+            view,
+            triggers,
+            sequence,
         } = actual;
 
         let Schema {
@@ -179,14 +513,24 @@ mod tests {
             indexes: indexes_e,
             engine: engine_e,
             foreign_keys: foreign_keys_e,
+            checks: checks_e,
+            uniques: uniques_e,
             comment: comment_e,
+            view: view_e,
+            triggers: triggers_e,
+            sequence: sequence_e,
         } = expected;
 
         assert_eq!(table_name, table_name_e);
         assert_eq!(column_defs, column_defs_e);
         assert_eq!(engine, engine_e);
         assert_eq!(foreign_keys, foreign_keys_e);
+        assert_eq!(checks, checks_e);
+        assert_eq!(uniques, uniques_e);
         assert_eq!(comment, comment_e);
+        assert_eq!(view, view_e);
+        assert_eq!(triggers, triggers_e);
+        assert_eq!(sequence, sequence_e);
         indexes
             .into_iter()
             .zip(indexes_e)
@@ -195,18 +539,27 @@ mod tests {
 
     fn assert_index(actual: SchemaIndex, expected: SchemaIndex) {
         let SchemaIndex {
-            name, expr, order, ..
+            name,
+            exprs,
+            order,
+            predicate,
+            fulltext,
+            ..
         } = actual;
         let SchemaIndex {
             name: name_e,
-            expr: expr_e,
+            exprs: exprs_e,
             order: order_e,
+            predicate: predicate_e,
+            fulltext: fulltext_e,
             ..
         } = expected;
 
         assert_eq!(name, name_e);
-        assert_eq!(expr, expr_e);
+        assert_eq!(exprs, exprs_e);
         assert_eq!(order, order_e);
+        assert_eq!(predicate, predicate_e);
+        assert_eq!(fulltext, fulltext_e);
     }
 
     #[test]
@@ -234,7 +587,12 @@ mod tests {
             indexes: Vec::new(),
             engine: None,
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            uniques: Vec::new(),
             comment: None,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
         };
 
         let ddl = r#"CREATE TABLE "User" ("id" INT NOT NULL, "name" TEXT NULL DEFAULT 'glue');"#;
@@ -249,7 +607,12 @@ mod tests {
             indexes: Vec::new(),
             engine: None,
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            uniques: Vec::new(),
             comment: None,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
         };
         let ddl = r#"CREATE TABLE "Test";"#;
         assert_eq!(schema.to_ddl(), ddl);
@@ -273,7 +636,12 @@ mod tests {
             indexes: Vec::new(),
             engine: None,
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            uniques: Vec::new(),
             comment: None,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
         };
 
         let ddl = r#"CREATE TABLE "User" ("id" INT NOT NULL PRIMARY KEY);"#;
@@ -283,14 +651,204 @@ mod tests {
         assert_schema(actual, schema);
     }
 
+    #[test]
+    fn table_composite_primary() {
+        let column_def = |name: &str| ColumnDef {
+            name: name.to_owned(),
+            data_type: DataType::Int,
+            nullable: false,
+            default: None,
+            unique: Some(ColumnUniqueOption { is_primary: true }),
+            comment: None,
+        };
+        let schema = Schema {
+            table_name: "UserRole".to_owned(),
+            column_defs: Some(vec![
+                column_def("user_id"),
+                column_def("role_id"),
+                ColumnDef {
+                    name: "note".to_owned(),
+                    data_type: DataType::Text,
+                    nullable: true,
+                    default: None,
+                    unique: None,
+                    comment: None,
+                },
+            ]),
+            indexes: Vec::new(),
+            engine: None,
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            uniques: Vec::new(),
+            comment: None,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
+        };
+
+        let ddl = r#"CREATE TABLE "UserRole" ("user_id" INT NOT NULL, "role_id" INT NOT NULL, "note" TEXT NULL, PRIMARY KEY ("user_id", "role_id"));"#;
+        assert_eq!(schema.to_ddl(), ddl);
+
+        let actual = Schema::from_ddl(ddl).unwrap();
+        assert_schema(actual, schema);
+    }
+
+    #[test]
+    fn table_with_unique() {
+        let column_def = |name: &str| ColumnDef {
+            name: name.to_owned(),
+            data_type: DataType::Int,
+            nullable: true,
+            default: None,
+            unique: None,
+            comment: None,
+        };
+        let schema = Schema {
+            table_name: "Seat".to_owned(),
+            column_defs: Some(vec![column_def("row"), column_def("number")]),
+            indexes: Vec::new(),
+            engine: None,
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            uniques: vec![UniqueConstraint {
+                name: "Seat_row_number_key".to_owned(),
+                columns: vec!["row".to_owned(), "number".to_owned()],
+            }],
+            comment: None,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
+        };
+
+        let ddl = r#"CREATE TABLE "Seat" ("row" INT NULL, "number" INT NULL, CONSTRAINT "Seat_row_number_key" UNIQUE ("row", "number"));"#;
+        assert_eq!(schema.to_ddl(), ddl);
+
+        let actual = Schema::from_ddl(ddl).unwrap();
+        assert_schema(actual, schema);
+    }
+
+    #[test]
+    fn table_with_check() {
+        let schema = Schema {
+            table_name: "Item".to_owned(),
+            column_defs: Some(vec![ColumnDef {
+                name: "price".to_owned(),
+                data_type: DataType::Int,
+                nullable: true,
+                default: None,
+                unique: None,
+                comment: None,
+            }]),
+            indexes: Vec::new(),
+            engine: None,
+            foreign_keys: Vec::new(),
+            checks: vec![CheckConstraint {
+                name: "Item_price_check".to_owned(),
+                expr: Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier("price".to_owned())),
+                    op: BinaryOperator::Gt,
+                    right: Box::new(Expr::Literal(AstLiteral::Number(0.into()))),
+                },
+            }],
+            uniques: Vec::new(),
+            comment: None,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
+        };
+
+        let ddl = r#"CREATE TABLE "Item" ("price" INT NULL, CONSTRAINT "Item_price_check" CHECK ("price" > 0));"#;
+        assert_eq!(schema.to_ddl(), ddl);
+
+        let actual = Schema::from_ddl(ddl).unwrap();
+        assert_schema(actual, schema);
+    }
+
     #[test]
     fn invalid_ddl() {
-        // Only Statement::CreateTable is supported
+        // Only Statement::CreateTable, Statement::CreateView and Statement::CreateSequence are supported
         let invalid_ddl = r#"DROP TABLE "Users";"#;
         let actual = Schema::from_ddl(invalid_ddl);
         assert_eq!(actual, Err(SchemaParseError::CannotParseDDL.into()));
     }
 
+    #[test]
+    fn view() {
+        let ddl =
+            r#"CREATE VIEW "Adult" AS SELECT "name" AS "name" FROM "User" WHERE "age" >= 20;"#;
+        let schema = Schema::from_ddl(ddl).unwrap();
+        assert_eq!(schema.table_name, "Adult");
+        assert_eq!(schema.column_defs, None);
+        assert!(schema.view.is_some());
+        assert_eq!(schema.to_ddl(), ddl);
+    }
+
+    #[test]
+    fn materialized_view() {
+        let ddl = r#"CREATE TABLE "Adult" ("name" TEXT NULL);
+CREATE MATERIALIZED VIEW "Adult" AS SELECT "name" AS "name" FROM "User" WHERE "age" >= 20;"#;
+        let schema = Schema::from_ddl(ddl).unwrap();
+        assert_eq!(schema.table_name, "Adult");
+        assert!(schema.column_defs.is_some());
+        assert!(schema.view.as_ref().is_some_and(|view| view.materialized));
+        assert_eq!(schema.to_ddl(), ddl);
+        assert_eq!(schema.view_ddl().as_deref(), ddl.lines().nth(1),);
+    }
+
+    #[test]
+    fn trigger() {
+        let ddl = r#"CREATE TABLE "User" ("id" INT NOT NULL, "name" TEXT NULL);
+CREATE TRIGGER "log_user" AFTER INSERT OR UPDATE ON "User" FOR EACH ROW WHEN "NEW"."id" > 1 BEGIN INSERT INTO "Log" VALUES (NEW.id, 'it''s'); DELETE FROM "Cache" WHERE id = NEW.id; END;
+CREATE TRIGGER "forget_user" BEFORE DELETE ON "User" FOR EACH ROW BEGIN DELETE FROM "Log" WHERE id = OLD.id; END;"#;
+        let schema = Schema::from_ddl(ddl).unwrap();
+        assert_eq!(
+            schema.triggers,
+            vec![
+                SchemaTrigger {
+                    name: "log_user".to_owned(),
+                    timing: TriggerTiming::After,
+                    events: vec![TriggerEvent::Insert, TriggerEvent::Update],
+                    condition: Some(Expr::BinaryOp {
+                        left: Box::new(Expr::CompoundIdentifier {
+                            alias: "NEW".to_owned(),
+                            ident: "id".to_owned(),
+                        }),
+                        op: BinaryOperator::Gt,
+                        right: Box::new(Expr::Literal(AstLiteral::Number(1.into()))),
+                    }),
+                    body: vec![
+                        r#"INSERT INTO "Log" VALUES (NEW.id, 'it''s')"#.to_owned(),
+                        r#"DELETE FROM "Cache" WHERE id = NEW.id"#.to_owned(),
+                    ],
+                },
+                SchemaTrigger {
+                    name: "forget_user".to_owned(),
+                    timing: TriggerTiming::Before,
+                    events: vec![TriggerEvent::Delete],
+                    condition: None,
+                    body: vec![r#"DELETE FROM "Log" WHERE id = OLD.id"#.to_owned()],
+                },
+            ]
+        );
+        assert_eq!(schema.to_ddl(), ddl);
+    }
+
+    #[test]
+    fn sequence() {
+        let ddl = r#"CREATE SEQUENCE "Countdown" INCREMENT BY -2 START WITH 10;"#;
+        let schema = Schema::from_ddl(ddl).unwrap();
+        assert_eq!(schema.table_name, "Countdown");
+        assert_eq!(schema.column_defs, Some(SchemaSequence::column_defs()));
+        assert_eq!(
+            schema.sequence,
+            Some(SchemaSequence {
+                increment: -2,
+                start: 10,
+            })
+        );
+        assert_eq!(schema.to_ddl(), ddl);
+    }
+
     #[test]
     fn table_with_index() {
         let schema = Schema {
@@ -316,24 +874,58 @@ mod tests {
             indexes: vec![
                 SchemaIndex {
                     name: "User_id".to_owned(),
-                    expr: Expr::Identifier("id".to_owned()),
+                    exprs: vec![Expr::Identifier("id".to_owned())],
                     order: SchemaIndexOrd::Both,
+                    predicate: None,
+                    fulltext: false,
                     created: Utc::now().naive_utc(),
                 },
                 SchemaIndex {
                     name: "User_name".to_owned(),
-                    expr: Expr::Identifier("name".to_owned()),
+                    exprs: vec![Expr::Identifier("name".to_owned())],
+                    order: SchemaIndexOrd::Both,
+                    predicate: Some(Expr::BinaryOp {
+                        left: Box::new(Expr::Identifier("id".to_owned())),
+                        op: BinaryOperator::Gt,
+                        right: Box::new(Expr::Literal(AstLiteral::Number(0.into()))),
+                    }),
+                    fulltext: false,
+                    created: Utc::now().naive_utc(),
+                },
+                SchemaIndex {
+                    name: "User_name_id".to_owned(),
+                    exprs: vec![
+                        Expr::Identifier("name".to_owned()),
+                        Expr::Identifier("id".to_owned()),
+                    ],
+                    order: SchemaIndexOrd::Both,
+                    predicate: None,
+                    fulltext: false,
+                    created: Utc::now().naive_utc(),
+                },
+                SchemaIndex {
+                    name: "User_name_text".to_owned(),
+                    exprs: vec![Expr::Identifier("name".to_owned())],
                     order: SchemaIndexOrd::Both,
+                    predicate: None,
+                    fulltext: true,
                     created: Utc::now().naive_utc(),
                 },
             ],
             engine: None,
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            uniques: Vec::new(),
             comment: None,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
         };
         let ddl = r#"CREATE TABLE "User" ("id" INT NOT NULL, "name" TEXT NOT NULL);
 CREATE INDEX "User_id" ON "User" ("id");
-CREATE INDEX "User_name" ON "User" ("name");"#;
+CREATE INDEX "User_name" ON "User" ("name") WHERE "id" > 0;
+CREATE INDEX "User_name_id" ON "User" ("name", "id");
+CREATE INDEX "User_name_text" ON "User" USING FULLTEXT ("name");"#;
         assert_eq!(schema.to_ddl(), ddl);
 
         let actual = Schema::from_ddl(ddl).unwrap();
@@ -369,13 +961,20 @@ CREATE TABLE "User" ("id" INT NOT NULL, "name" TEXT NOT NULL);"#;
             ]),
             indexes: vec![SchemaIndex {
                 name: ".".to_owned(),
-                expr: Expr::Identifier(";".to_owned()),
+                exprs: vec![Expr::Identifier(";".to_owned())],
                 order: SchemaIndexOrd::Both,
+                predicate: None,
+                fulltext: false,
                 created: Utc::now().naive_utc(),
             }],
             engine: None,
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            uniques: Vec::new(),
             comment: None,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
         };
         let ddl = r#"CREATE TABLE "1" ("2" INT NULL, ";" INT NULL);
 CREATE INDEX "." ON "1" (";");"#;
@@ -0,0 +1,34 @@
+use {
+    super::Value,
+    chrono::NaiveDateTime,
+    serde::{Deserialize, Serialize},
+};
+
+/// Statistics of a table collected by `ANALYZE`, which the planner estimates how many rows
+/// a condition matches with.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableStatistics {
+    pub table_name: String,
+    pub row_count: usize,
+    /// Empty for a schemaless table
+    pub columns: Vec<ColumnStatistics>,
+    pub analyzed: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColumnStatistics {
+    pub name: String,
+    /// Number of the distinct values other than `NULL`
+    pub distinct_count: usize,
+    pub null_count: usize,
+    /// `None` when the column holds no value other than `NULL`, or only values which cannot
+    /// be compared such as `MAP` and `LIST`
+    pub min: Option<Value>,
+    pub max: Option<Value>,
+}
+
+impl TableStatistics {
+    pub fn column(&self, name: &str) -> Option<&ColumnStatistics> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+}
@@ -4,10 +4,19 @@ use {crate::result::Result, regex::Regex, serde::Serialize, thiserror::Error};
 pub enum StringExtError {
     #[error("unreachable literal unary operation")]
     UnreachablePatternParsing,
+
+    #[error("invalid regular expression: {0}")]
+    InvalidRegexPattern(String),
 }
 
 pub trait StringExt {
     fn like(&self, pattern: &str, case_sensitive: bool) -> Result<bool>;
+
+    /// Compiles the string as a regular expression.
+    fn to_regex(&self) -> Result<Regex>;
+
+    /// Whether the regular expression `pattern` matches any part of the string.
+    fn regex_match(&self, pattern: &str) -> Result<bool>;
 }
 
 impl StringExt for str {
@@ -31,4 +40,12 @@ impl StringExt for str {
         .map_err(|_| StringExtError::UnreachablePatternParsing)?
         .is_match(match_string.as_str()))
     }
+
+    fn to_regex(&self) -> Result<Regex> {
+        Regex::new(self).map_err(|_| StringExtError::InvalidRegexPattern(self.to_owned()).into())
+    }
+
+    fn regex_match(&self, pattern: &str) -> Result<bool> {
+        pattern.to_regex().map(|regex| regex.is_match(self))
+    }
 }
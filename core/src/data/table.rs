@@ -24,10 +24,18 @@ pub fn get_alias(table_factor: &TableFactor) -> &String {
             alias: TableAlias { name, .. },
             ..
         }
+        | TableFactor::Recursive {
+            alias: TableAlias { name, .. },
+            ..
+        }
         | TableFactor::Series {
             alias: TableAlias { name, .. },
             ..
         }
+        | TableFactor::Fake {
+            alias: TableAlias { name, .. },
+            ..
+        }
         | TableFactor::Dictionary {
             alias: TableAlias { name, .. },
             ..
@@ -39,7 +47,9 @@ pub fn get_index(table_factor: &TableFactor) -> Option<&IndexItem> {
     match table_factor {
         TableFactor::Table { index, .. } => index.as_ref(),
         TableFactor::Derived { .. }
+        | TableFactor::Recursive { .. }
         | TableFactor::Series { .. }
+        | TableFactor::Fake { .. }
         | TableFactor::Dictionary { .. } => None,
     }
 }
@@ -0,0 +1,105 @@
+use {
+    super::Password,
+    crate::ast::{Action, Privilege},
+    serde::{Deserialize, Serialize},
+    std::collections::BTreeMap,
+};
+
+/// Entry of the user catalog, created with `CREATE USER` and kept by storages implementing
+/// [`crate::store::User`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub name: String,
+    /// Verifiers servers check the password given by the user against when authenticating.
+    pub password: Option<Password>,
+    pub superuser: bool,
+    /// Privileges granted to the user, by table name.
+    pub privileges: BTreeMap<String, Vec<Privilege>>,
+}
+
+impl User {
+    pub fn new(name: String, password: Option<Password>, superuser: bool) -> Self {
+        Self {
+            name,
+            password,
+            superuser,
+            privileges: BTreeMap::new(),
+        }
+    }
+
+    pub fn grant(&mut self, table_name: &str, privilege: &Privilege) {
+        let privileges = self.privileges.entry(table_name.to_owned()).or_default();
+
+        match privileges.iter_mut().find(|p| p.action == privilege.action) {
+            Some(granted) => {
+                granted.columns = match (granted.columns.take(), &privilege.columns) {
+                    (Some(mut columns), Some(added)) => {
+                        for column in added {
+                            if !columns.contains(column) {
+                                columns.push(column.clone());
+                            }
+                        }
+
+                        Some(columns)
+                    }
+                    _ => None,
+                };
+            }
+            None => privileges.push(privilege.clone()),
+        }
+    }
+
+    /// Takes `privilege` away. Revoking columns keeps a privilege granted on the whole table,
+    /// as in PostgreSQL.
+    pub fn revoke(&mut self, table_name: &str, privilege: &Privilege) {
+        let Some(privileges) = self.privileges.get_mut(table_name) else {
+            return;
+        };
+
+        privileges.retain_mut(|granted| {
+            if granted.action != privilege.action {
+                return true;
+            }
+
+            match (&mut granted.columns, &privilege.columns) {
+                (_, None) => false,
+                (None, Some(_)) => true,
+                (Some(columns), Some(revoked)) => {
+                    columns.retain(|column| !revoked.contains(column));
+
+                    !columns.is_empty()
+                }
+            }
+        });
+
+        if privileges.is_empty() {
+            self.privileges.remove(table_name);
+        }
+    }
+
+    /// Checks whether `action` is granted on every column in `columns` of the table, where
+    /// `None` stands for the whole table. An empty list asks for the privilege on any column.
+    pub fn is_granted(&self, table_name: &str, action: Action, columns: Option<&[String]>) -> bool {
+        if self.superuser {
+            return true;
+        }
+
+        let Some(privileges) = self.privileges.get(table_name) else {
+            return false;
+        };
+        let mut privileges = privileges.iter().filter(|p| p.action == action).peekable();
+        if privileges.peek().is_none() {
+            return false;
+        }
+
+        let mut granted = Vec::new();
+        for privilege in privileges {
+            match &privilege.columns {
+                None => return true,
+                Some(columns) => granted.extend(columns),
+            }
+        }
+
+        columns.is_some_and(|columns| columns.iter().all(|column| granted.contains(&column)))
+    }
+}
@@ -27,6 +27,7 @@ mod date;
 mod error;
 mod expr;
 mod json;
+mod json_path;
 mod literal;
 mod selector;
 mod uuid;
@@ -780,6 +781,19 @@ impl Value {
         }
     }
 
+    pub fn regex_match(&self, other: &Value) -> Result<Value> {
+        use Value::*;
+
+        match (self, other) {
+            (Str(a), Str(b)) => a.regex_match(b).map(Bool),
+            _ => Err(ValueError::RegexMatchOnNonString {
+                base: self.clone(),
+                pattern: other.clone(),
+            }
+            .into()),
+        }
+    }
+
     pub fn extract(&self, date_type: &DateTimeField) -> Result<Value> {
         let value = match (self, date_type) {
             (Value::Date(v), DateTimeField::Year) => v.year().into(),
@@ -992,7 +1006,10 @@ fn str_position(from_str: &str, sub_str: &str) -> usize {
 mod tests {
     use {
         super::{Interval, Value::*},
-        crate::data::{NumericBinaryOperator, ValueError, point::Point, value::uuid::parse_uuid},
+        crate::data::{
+            NumericBinaryOperator, StringExtError, ValueError, point::Point,
+            value::uuid::parse_uuid,
+        },
         chrono::{NaiveDate, NaiveTime},
         rust_decimal::Decimal,
         std::{collections::HashMap, net::IpAddr, str::FromStr},
@@ -2903,6 +2920,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn regex_match() {
+        let text = Str("error 404: not found".to_owned());
+
+        assert_eq!(text.regex_match(&Str(r"\d{3}".to_owned())), Ok(Bool(true)));
+        assert_eq!(text.regex_match(&Str("^not".to_owned())), Ok(Bool(false)));
+        assert_eq!(
+            text.regex_match(&Str("(".to_owned())),
+            Err(StringExtError::InvalidRegexPattern("(".to_owned()).into())
+        );
+        assert_eq!(
+            text.regex_match(&I64(404)),
+            Err(ValueError::RegexMatchOnNonString {
+                base: text,
+                pattern: I64(404)
+            }
+            .into())
+        );
+    }
+
     #[test]
     fn get_type() {
         use {
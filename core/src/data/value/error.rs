@@ -176,6 +176,9 @@ pub enum ValueError {
         case_sensitive: bool,
     },
 
+    #[error("operator doesn't exist: {base:?} ~ {pattern:?}")]
+    RegexMatchOnNonString { base: Value, pattern: Value },
+
     #[error("extract format not matched: {value:?} FROM {field:?})")]
     ExtractFormatNotMatched { value: Value, field: DateTimeField },
 
@@ -197,6 +200,12 @@ pub enum ValueError {
     #[error("selector requires MAP or LIST types")]
     SelectorRequiresMapOrListTypes,
 
+    #[error("invalid JSON path, expected $ followed by .key and [index]: {0}")]
+    InvalidJsonPath(String),
+
+    #[error("JSON path $ removes the whole document")]
+    JsonRemoveDocument,
+
     #[error("overflow occurred: {lhs:?} {operator} {rhs:?}")]
     BinaryOperationOverflow {
         lhs: Value,
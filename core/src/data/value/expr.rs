@@ -56,10 +56,10 @@ impl TryFrom<Value> for Expr {
             Value::F64(v) => Expr::Literal(AstLiteral::Number(
                 BigDecimal::from_f64(v).ok_or(ValueToExprConversionFailure)?,
             )),
-            Value::Decimal(v) => Expr::Literal(AstLiteral::Number(
-                BigDecimal::from_f64(v.try_into().map_err(|_| ValueToExprConversionFailure)?)
-                    .ok_or(ValueToExprConversionFailure)?,
-            )),
+            Value::Decimal(v) => Expr::TypedString {
+                data_type: DataType::Decimal,
+                value: v.to_string(),
+            },
             Value::Str(v) => Expr::Literal(AstLiteral::QuotedString(v)),
             Value::Bytea(v) => Expr::Literal(AstLiteral::HexString(hex::encode(v))),
             Value::Inet(v) => Expr::Literal(AstLiteral::QuotedString(v.to_string())),
@@ -133,7 +133,7 @@ mod tests {
         bigdecimal::{BigDecimal, FromPrimitive},
         chrono::{NaiveDate, NaiveTime},
         rust_decimal::Decimal,
-        std::collections::BTreeMap,
+        std::{collections::BTreeMap, str::FromStr},
     };
 
     #[test]
@@ -218,9 +218,17 @@ mod tests {
         );
         assert_eq!(
             Value::Decimal(Decimal::new(315, 2)).try_into(),
-            Ok(Expr::Literal(AstLiteral::Number(
-                BigDecimal::from_f64(3.15).unwrap()
-            )))
+            Ok(Expr::TypedString {
+                data_type: DataType::Decimal,
+                value: "3.15".to_owned(),
+            })
+        );
+        assert_eq!(
+            Value::Decimal(Decimal::from_str("12345678901234.567890123456789").unwrap()).try_into(),
+            Ok(Expr::TypedString {
+                data_type: DataType::Decimal,
+                value: "12345678901234.567890123456789".to_owned(),
+            })
         );
         assert_eq!(
             Value::Str("data".to_owned()).try_into(),
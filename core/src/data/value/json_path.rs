@@ -0,0 +1,262 @@
+use {
+    super::{Value, ValueError},
+    crate::result::Result,
+};
+
+/// A step of a JSON path such as `$.items[0].name`, which starts at the document with `$` and
+/// steps into the value of a MAP by `.key` and into the item of a LIST by `[index]`.
+enum PathItem {
+    Key(String),
+    Index(usize),
+}
+
+impl PathItem {
+    fn get<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        match (self, value) {
+            (PathItem::Key(key), Value::Map(map)) => map.get(key),
+            (PathItem::Index(index), Value::List(list)) => list.get(*index),
+            _ => None,
+        }
+    }
+
+    fn get_mut<'a>(&self, value: &'a mut Value) -> Option<&'a mut Value> {
+        match (self, value) {
+            (PathItem::Key(key), Value::Map(map)) => map.get_mut(key),
+            (PathItem::Index(index), Value::List(list)) => list.get_mut(*index),
+            _ => None,
+        }
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<PathItem>> {
+    let invalid = || ValueError::InvalidJsonPath(path.to_owned());
+    let mut rest = path.trim().strip_prefix('$').ok_or_else(invalid)?;
+    let mut items = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(key) = rest.strip_prefix('.') {
+            let end = key.find(['.', '[']).unwrap_or(key.len());
+            let (key, next) = key.split_at(end);
+            if key.is_empty() {
+                return Err(invalid().into());
+            }
+
+            items.push(PathItem::Key(key.to_owned()));
+            rest = next;
+        } else if let Some(index) = rest.strip_prefix('[') {
+            let (index, next) = index.split_once(']').ok_or_else(invalid)?;
+            let index = index.trim().parse::<usize>().map_err(|_| invalid())?;
+
+            items.push(PathItem::Index(index));
+            rest = next;
+        } else {
+            return Err(invalid().into());
+        }
+    }
+
+    Ok(items)
+}
+
+impl Value {
+    fn validate_json_document(&self) -> Result<()> {
+        match self {
+            Value::Map(_) | Value::List(_) => Ok(()),
+            _ => Err(ValueError::SelectorRequiresMapOrListTypes.into()),
+        }
+    }
+
+    /// Value of the MAP or LIST at the JSON path, NULL when the path leads nowhere.
+    pub fn json_extract(&self, path: &str) -> Result<Value> {
+        self.validate_json_document()?;
+
+        let value = parse_path(path)?
+            .iter()
+            .try_fold(self, |value, item| item.get(value))
+            .cloned()
+            .unwrap_or(Value::Null);
+
+        Ok(value)
+    }
+
+    /// The MAP or LIST with `value` at the JSON path, adding the key to a MAP which has not got
+    /// it and appending `value` to a LIST shorter than the index. A path whose parent is missing
+    /// leaves the document as it is, and `$` gives `value` itself.
+    pub fn json_set(mut self, path: &str, value: Value) -> Result<Value> {
+        self.validate_json_document()?;
+
+        let mut items = parse_path(path)?;
+        let Some(last) = items.pop() else {
+            return Ok(value);
+        };
+        let parent = items
+            .iter()
+            .try_fold(&mut self, |parent, item| item.get_mut(parent));
+
+        match (last, parent) {
+            (PathItem::Key(key), Some(Value::Map(map))) => {
+                map.insert(key, value);
+            }
+            (PathItem::Index(index), Some(Value::List(list))) if index < list.len() => {
+                list[index] = value;
+            }
+            (PathItem::Index(_), Some(Value::List(list))) => {
+                list.push(value);
+            }
+            _ => {}
+        }
+
+        Ok(self)
+    }
+
+    /// The MAP or LIST without the value at the JSON path, as it is when the path leads nowhere.
+    pub fn json_remove(mut self, path: &str) -> Result<Value> {
+        self.validate_json_document()?;
+
+        let mut items = parse_path(path)?;
+        let Some(last) = items.pop() else {
+            return Err(ValueError::JsonRemoveDocument.into());
+        };
+        let parent = items
+            .iter()
+            .try_fold(&mut self, |parent, item| item.get_mut(parent));
+
+        match (last, parent) {
+            (PathItem::Key(key), Some(Value::Map(map))) => {
+                map.remove(&key);
+            }
+            (PathItem::Index(index), Some(Value::List(list))) if index < list.len() => {
+                list.remove(index);
+            }
+            _ => {}
+        }
+
+        Ok(self)
+    }
+
+    /// `doc -> key`, the value of the MAP at the key or of the LIST at the index.
+    pub fn json_field(&self, key: &Value) -> Result<Value> {
+        self.selector_by_index(std::slice::from_ref(key))
+    }
+
+    /// `doc ->> key`, the text of [`Value::json_field`], a MAP or LIST as its JSON.
+    pub fn json_field_text(&self, key: &Value) -> Result<Value> {
+        let value = match self.json_field(key)? {
+            Value::Null => Value::Null,
+            value => Value::Str(String::from(value)),
+        };
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        crate::data::{Value, ValueError},
+        serde_json::json,
+    };
+
+    fn doc(json: serde_json::Value) -> Value {
+        Value::try_from(json).unwrap()
+    }
+
+    #[test]
+    fn json_extract() {
+        let value = doc(json!({ "items": [{ "name": "Glue" }, 2], "id": 1 }));
+
+        assert_eq!(value.json_extract("$.id"), Ok(Value::I64(1)));
+        assert_eq!(value.json_extract(" $.items[1] "), Ok(Value::I64(2)));
+        assert_eq!(
+            value.json_extract("$.items[0].name"),
+            Ok(Value::Str("Glue".to_owned()))
+        );
+        assert_eq!(value.json_extract("$"), Ok(value.clone()));
+        assert_eq!(value.json_extract("$.items[2]"), Ok(Value::Null));
+        assert_eq!(value.json_extract("$.id.name"), Ok(Value::Null));
+        assert_eq!(value.json_extract("$[0]"), Ok(Value::Null));
+
+        for path in ["id", "$.", "$..id", "$.items[", "$.items[-1]", "$id"] {
+            assert_eq!(
+                value.json_extract(path),
+                Err(ValueError::InvalidJsonPath(path.to_owned()).into()),
+                "{path}"
+            );
+        }
+        assert_eq!(
+            Value::I64(1).json_extract("$"),
+            Err(ValueError::SelectorRequiresMapOrListTypes.into())
+        );
+    }
+
+    #[test]
+    fn json_set() {
+        let value = doc(json!({ "tags": ["a", "b"], "user": { "id": 1 } }));
+        let set = |path, new| value.clone().json_set(path, doc(new));
+
+        assert_eq!(
+            set("$.user.id", json!(2)),
+            Ok(doc(json!({ "tags": ["a", "b"], "user": { "id": 2 } })))
+        );
+        assert_eq!(
+            set("$.user.name", json!("Glue")),
+            Ok(doc(
+                json!({ "tags": ["a", "b"], "user": { "id": 1, "name": "Glue" } })
+            ))
+        );
+        assert_eq!(
+            set("$.tags[0]", json!("c")),
+            Ok(doc(json!({ "tags": ["c", "b"], "user": { "id": 1 } })))
+        );
+        assert_eq!(
+            set("$.tags[5]", json!("c")),
+            Ok(doc(json!({ "tags": ["a", "b", "c"], "user": { "id": 1 } })))
+        );
+        assert_eq!(set("$.missing.id", json!(2)), Ok(value.clone()));
+        assert_eq!(set("$", json!([1])), Ok(doc(json!([1]))));
+    }
+
+    #[test]
+    fn json_remove() {
+        let value = doc(json!({ "tags": ["a", "b"], "user": { "id": 1 } }));
+
+        assert_eq!(
+            value.clone().json_remove("$.user.id"),
+            Ok(doc(json!({ "tags": ["a", "b"], "user": {} })))
+        );
+        assert_eq!(
+            value.clone().json_remove("$.tags[0]"),
+            Ok(doc(json!({ "tags": ["b"], "user": { "id": 1 } })))
+        );
+        assert_eq!(value.clone().json_remove("$.tags[2]"), Ok(value.clone()));
+        assert_eq!(
+            value.json_remove("$"),
+            Err(ValueError::JsonRemoveDocument.into())
+        );
+    }
+
+    #[test]
+    fn json_field() {
+        let value = doc(json!({ "user": { "id": 1 }, "name": "Glue" }));
+
+        assert_eq!(
+            value.json_field(&Value::Str("user".to_owned())),
+            Ok(doc(json!({ "id": 1 })))
+        );
+        assert_eq!(
+            value.json_field_text(&Value::Str("user".to_owned())),
+            Ok(Value::Str(r#"{"id":1}"#.to_owned()))
+        );
+        assert_eq!(
+            value.json_field_text(&Value::Str("name".to_owned())),
+            Ok(Value::Str("Glue".to_owned()))
+        );
+        assert_eq!(
+            value.json_field_text(&Value::Str("missing".to_owned())),
+            Ok(Value::Null)
+        );
+        assert_eq!(
+            doc(json!([10, 20])).json_field(&Value::I64(1)),
+            Ok(Value::I64(20))
+        );
+    }
+}
@@ -246,6 +246,10 @@ impl Value {
                 .parse::<Decimal>()
                 .map(Value::Decimal)
                 .map_err(|_| ValueError::FailedToParseDecimal(v.to_string()).into()),
+            (DataType::Decimal, Literal::Text(v)) => v
+                .parse::<Decimal>()
+                .map(Value::Decimal)
+                .map_err(|_| ValueError::FailedToParseDecimal(v.to_string()).into()),
             (_, Literal::Null) => Ok(Value::Null),
             _ => Err(ValueError::IncompatibleLiteralForDataType {
                 data_type: data_type.clone(),
@@ -926,6 +930,11 @@ mod tests {
             num!("200"),
             Value::Decimal(Decimal::new(200, 0))
         );
+        test!(
+            DataType::Decimal,
+            text!("12345678901234.567890123456789"),
+            Value::Decimal(Decimal::from_str("12345678901234.567890123456789").unwrap())
+        );
     }
 
     #[test]
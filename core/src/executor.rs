@@ -1,29 +1,46 @@
 mod aggregate;
 mod alter;
+mod analyze;
+mod authorize;
+mod check;
 mod context;
 mod delete;
 mod evaluate;
 mod execute;
+mod explain;
+mod fake;
 mod fetch;
 mod filter;
 mod insert;
 mod join;
 mod limit;
+mod pushdown;
+mod recursive;
+mod returning;
 mod select;
+mod sequence;
 mod sort;
+mod trigger;
 mod update;
 mod validate;
 
 pub use {
     alter::{AlterError, Referencing},
+    authorize::{PrivilegeError, authorize},
     context::RowContext,
     delete::DeleteError,
     evaluate::{EvaluateError, evaluate_stateless},
-    execute::{ExecuteError, Payload, PayloadVariable, execute},
+    execute::{ExecuteError, Payload, PayloadVariable, execute, execute_into, execute_parallel},
     fetch::FetchError,
     insert::InsertError,
+    recursive::DEFAULT_MAX_RECURSION_DEPTH,
     select::SelectError,
     sort::SortError,
     update::UpdateError,
     validate::ValidateError,
 };
+
+pub(crate) use {
+    authorize::table_names,
+    sequence::{calls_sequence_functions, is_sequence_function},
+};
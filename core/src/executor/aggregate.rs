@@ -1,16 +1,21 @@
+mod parallel;
 mod state;
+mod window;
+
+pub use self::{parallel::Parallel, window::apply as window};
 
 use {
-    self::state::State,
+    self::state::{State, ValuesMap},
     super::{
         context::{AggregateContext, RowContext},
         evaluate::{Evaluated, evaluate},
         filter::check_expr,
     },
     crate::{
-        ast::{Expr, SelectItem},
-        data::Key,
-        result::Result,
+        ast::{Expr, IndexItem, SelectItem, TableAlias, TableFactor, TableWithJoins},
+        data::{Key, get_alias},
+        plan::column_type,
+        result::{Error, Result},
         store::GStore,
     },
     futures::{
@@ -21,12 +26,13 @@ use {
 };
 
 #[derive(futures_enum::Stream)]
-enum S<T1, T2> {
+enum S<T1, T2, T3> {
     NonAggregate(T1),
     Aggregate(T2),
+    Ordered(T3),
 }
 
-fn check_aggregate<'a>(fields: &'a [SelectItem], group_by: &'a [Expr]) -> bool {
+pub(super) fn check_aggregate<'a>(fields: &'a [SelectItem], group_by: &'a [Expr]) -> bool {
     if !group_by.is_empty() {
         return true;
     }
@@ -37,12 +43,71 @@ fn check_aggregate<'a>(fields: &'a [SelectItem], group_by: &'a [Expr]) -> bool {
     })
 }
 
+/// Position of the `GROUP BY` expression the rows of the query come grouped by, a column of
+/// its first table the index planner took an index of the column for the scan of. Joins keep
+/// the order of the rows of the table, so the rows of each value of the column come one after
+/// another.
+pub async fn ordered_by<T: GStore>(
+    storage: &T,
+    table_with_joins: &TableWithJoins,
+    group_by: &[Expr],
+) -> Result<Option<usize>> {
+    let TableWithJoins { relation, joins } = table_with_joins;
+    let TableFactor::Table {
+        name,
+        alias,
+        index: Some(IndexItem::NonClustered {
+            name: index_name, ..
+        }),
+    } = relation
+    else {
+        return Ok(None);
+    };
+
+    if group_by.is_empty()
+        || matches!(alias, Some(TableAlias { columns, .. }) if !columns.is_empty())
+    {
+        return Ok(None);
+    }
+
+    let Some(schema) = storage.fetch_schema(name).await? else {
+        return Ok(None);
+    };
+    let column = schema
+        .indexes
+        .iter()
+        .find(|index| &index.name == index_name)
+        .and_then(|index| match index.exprs.first() {
+            Some(Expr::Identifier(column)) => Some(column),
+            _ => None,
+        })
+        .filter(|column| column_type(&schema, column).is_some());
+    let Some(column) = column else {
+        return Ok(None);
+    };
+
+    let position = group_by.iter().position(|expr| match expr {
+        Expr::Identifier(ident) => joins.is_empty() && ident == column,
+        Expr::CompoundIdentifier { alias, ident } => {
+            alias == get_alias(relation) && ident == column
+        }
+        _ => false,
+    });
+
+    Ok(position)
+}
+
+/// Aggregates the rows into their groups, in the order of the first rows of the groups. When
+/// the rows come grouped by the `GROUP BY` expression at `ordered`, see [`ordered_by`], the
+/// groups of each of its values are given once its rows end, holding the groups of a single
+/// value at a time rather than those of all the rows.
 pub async fn apply<'a, T: GStore, U: Stream<Item = Result<Arc<RowContext<'a>>>> + 'a>(
     storage: &'a T,
     fields: &'a [SelectItem],
     group_by: &'a [Expr],
     having: Option<&'a Expr>,
     filter_context: Option<Arc<RowContext<'a>>>,
+    ordered: Option<usize>,
     rows: U,
 ) -> Result<impl Stream<Item = Result<AggregateContext<'a>>> + use<'a, T, U>> {
     if !check_aggregate(fields, group_by) {
@@ -53,74 +118,163 @@ pub async fn apply<'a, T: GStore, U: Stream<Item = Result<Arc<RowContext<'a>>>>
         return Ok(S::NonAggregate(rows));
     }
 
-    let state = rows
-        .into_stream()
-        .enumerate()
-        .map(|(i, row)| row.map(|row| (i, row)))
-        .try_fold(State::new(storage), |state, (index, project_context)| {
+    let rows = {
+        let filter_context = filter_context.clone();
+
+        rows.into_stream()
+            .enumerate()
+            .map(|(i, row)| row.map(|row| (i, row)))
+            .and_then(move |(index, project_context)| {
+                let filter_context = filter_context.clone();
+
+                group(storage, group_by, filter_context, index, project_context)
+            })
+    };
+
+    let Some(position) = ordered else {
+        let state = rows
+            .try_fold(State::new(storage), |state, row| {
+                accumulate(state, fields, row)
+            })
+            .await?;
+        let rows = export(state).await?;
+
+        return group_by_having(storage, filter_context, having, rows)
+            .await
+            .map(S::Aggregate);
+    };
+
+    let rows = stream::try_unfold(
+        (Box::pin(rows.fuse()), None),
+        move |(mut rows, next): (_, Option<Grouped<'a>>)| {
             let filter_context = filter_context.clone();
 
             async move {
-                let filter_context = match filter_context {
-                    Some(filter_context) => Arc::new(RowContext::concat(
-                        Arc::clone(&project_context),
-                        filter_context,
-                    )),
-                    None => Arc::clone(&project_context),
+                let first = match next {
+                    Some(row) => row,
+                    None => match rows.try_next().await? {
+                        Some(row) => row,
+                        None => return Ok::<_, Error>(None),
+                    },
                 };
-                let filter_context = Some(filter_context);
+                let key = first.group[position].clone();
+                let mut state = accumulate(State::new(storage), fields, first).await?;
 
-                let evaluated: Vec<Evaluated<'_>> = stream::iter(group_by.iter())
-                    .then(|expr| {
-                        let filter_clone = filter_context.as_ref().map(Arc::clone);
-                        async move { evaluate(storage, filter_clone, None, expr).await }
-                    })
-                    .try_collect::<Vec<_>>()
-                    .await?;
-
-                let group = evaluated
-                    .iter()
-                    .map(Key::try_from)
-                    .collect::<Result<Vec<Key>>>()?;
-
-                let state = state.apply(index, group, Arc::clone(&project_context));
-                let state = stream::iter(fields)
-                    .map(Ok)
-                    .try_fold(state, |state, field| {
-                        let filter_clone = filter_context.as_ref().map(Arc::clone);
-
-                        async move {
-                            match field {
-                                SelectItem::Expr { expr, .. } => {
-                                    aggregate(state, filter_clone, expr).await
-                                }
-                                _ => Ok(state),
-                            }
+                let next = loop {
+                    match rows.try_next().await? {
+                        Some(row) if row.group[position] == key => {
+                            state = accumulate(state, fields, row).await?;
                         }
-                    })
-                    .await?;
+                        next => break next,
+                    }
+                };
 
-                Ok(state)
+                let groups = export(state).await?;
+                let groups = group_by_having(storage, filter_context, having, groups).await?;
+
+                Ok(Some((groups, (rows, next))))
             }
+        },
+    )
+    .try_flatten();
+
+    Ok(S::Ordered(rows))
+}
+
+/// Row with the values of `GROUP BY` it is grouped by.
+struct Grouped<'a> {
+    index: usize,
+    group: Vec<Key>,
+    project_context: Arc<RowContext<'a>>,
+    /// Context `GROUP BY` and the aggregates evaluate in, the row with the outer rows.
+    filter_context: Arc<RowContext<'a>>,
+}
+
+async fn group<'a, T: GStore>(
+    storage: &'a T,
+    group_by: &'a [Expr],
+    filter_context: Option<Arc<RowContext<'a>>>,
+    index: usize,
+    project_context: Arc<RowContext<'a>>,
+) -> Result<Grouped<'a>> {
+    let filter_context = match filter_context {
+        Some(filter_context) => Arc::new(RowContext::concat(
+            Arc::clone(&project_context),
+            filter_context,
+        )),
+        None => Arc::clone(&project_context),
+    };
+
+    let evaluated: Vec<Evaluated<'_>> = stream::iter(group_by.iter())
+        .then(|expr| {
+            let filter_clone = Some(Arc::clone(&filter_context));
+            async move { evaluate(storage, filter_clone, None, expr).await }
         })
+        .try_collect::<Vec<_>>()
         .await?;
 
-    group_by_having(storage, filter_context, having, state)
+    let group = evaluated
+        .iter()
+        .map(Key::try_from)
+        .collect::<Result<Vec<Key>>>()?;
+
+    Ok(Grouped {
+        index,
+        group,
+        project_context,
+        filter_context,
+    })
+}
+
+async fn accumulate<'a, T: GStore>(
+    state: State<'a, T>,
+    fields: &'a [SelectItem],
+    row: Grouped<'a>,
+) -> Result<State<'a, T>> {
+    let Grouped {
+        index,
+        group,
+        project_context,
+        filter_context,
+    } = row;
+
+    let state = state.apply(index, group, project_context);
+    stream::iter(fields)
+        .map(Ok)
+        .try_fold(state, |state, field| {
+            let filter_clone = Some(Arc::clone(&filter_context));
+
+            async move {
+                match field {
+                    SelectItem::Expr { expr, .. } => aggregate(state, filter_clone, expr).await,
+                    _ => Ok(state),
+                }
+            }
+        })
         .await
-        .map(S::Aggregate)
 }
 
-async fn group_by_having<'a, T: GStore>(
-    storage: &'a T,
-    filter_context: Option<Arc<RowContext<'a>>>,
-    having: Option<&'a Expr>,
+async fn export<'a, T: GStore>(
     state: State<'a, T>,
-) -> Result<impl Stream<Item = Result<AggregateContext<'a>>>> {
+) -> Result<Vec<(Option<ValuesMap<'a>>, Arc<RowContext<'a>>)>> {
     let rows = state
         .export()
         .await?
         .into_iter()
-        .filter_map(|(aggregated, next)| next.map(|next| (aggregated, next)));
+        .filter_map(|(aggregated, next)| next.map(|next| (aggregated, next)))
+        .collect();
+
+    Ok(rows)
+}
+
+/// Filters the groups, each the aggregated values and the first row of the group, by
+/// `HAVING`.
+async fn group_by_having<'a, T: GStore>(
+    storage: &'a T,
+    filter_context: Option<Arc<RowContext<'a>>>,
+    having: Option<&'a Expr>,
+    rows: Vec<(Option<ValuesMap<'a>>, Arc<RowContext<'a>>)>,
+) -> Result<impl Stream<Item = Result<AggregateContext<'a>>>> {
     let rows = stream::iter(rows)
         .filter_map(move |(aggregated, next)| {
             let filter_context = filter_context.as_ref().map(Arc::clone);
@@ -195,6 +349,14 @@ where
 
                 Ok(state)
             }
+            Expr::Aggregate(aggr_expr) if aggr_expr.over.is_some() => {
+                let mut state = state;
+                for expr in aggr_expr.as_exprs() {
+                    state = aggregate(state, filter_context.clone(), expr).await?;
+                }
+
+                Ok(state)
+            }
             Expr::Aggregate(aggr_expr) => {
                 state.accumulate(filter_context, aggr_expr.as_ref()).await
             }
@@ -225,6 +387,7 @@ fn check(expr: &Expr) -> bool {
                     .map(|expr| check(expr))
                     .unwrap_or(false)
         }
+        Expr::Aggregate(aggr) if aggr.over.is_some() => aggr.as_exprs().any(check),
         Expr::Aggregate(_) => true,
         _ => false,
     }
@@ -0,0 +1,405 @@
+use {
+    super::{check_aggregate, group_by_having, state::AggrValue},
+    crate::{
+        ast::{Aggregate, Expr, Function, Select, SelectItem, TableFactor, TableWithJoins},
+        data::{Key, Row, Value},
+        executor::{
+            context::{AggregateContext, RowContext},
+            evaluate::evaluate_stateless,
+        },
+        plan::PlanExpr,
+        result::Result,
+        store::GStore,
+    },
+    futures::{
+        future::try_join_all,
+        stream::{Stream, TryChunksError, TryStreamExt},
+    },
+    std::{borrow::Cow, collections::HashMap, sync::Arc},
+};
+
+/// Rows a task aggregates at a time.
+const BATCH_SIZE: usize = 1024;
+
+/// Aggregation of a `SELECT` reading the partitions the storage splits the scan of its table
+/// into side by side, and splitting the rows of each into batches, which tasks aggregate while
+/// the storage reads the next rows. The partial results of the batches are merged in the order
+/// of the rows, giving what aggregating the rows one by one gives.
+pub struct Parallel<'a> {
+    parallelism: usize,
+    aggregates: Vec<&'a Aggregate>,
+    exprs: Arc<Exprs>,
+}
+
+/// Expressions the tasks evaluate for each row, owned to move into the tasks.
+struct Exprs {
+    where_clause: Option<Expr>,
+    group_by: Vec<Expr>,
+    aggregates: Vec<Aggregate>,
+}
+
+impl<'a> Parallel<'a> {
+    /// `None` unless `parallelism` is above 1 and the `SELECT` aggregates the rows of a
+    /// single table, evaluating `WHERE`, `GROUP BY` and the arguments of its aggregates
+    /// without the storage, which rules out subqueries and custom functions.
+    pub fn new(select: &'a Select, parallelism: usize) -> Option<Self> {
+        let Select {
+            from:
+                TableWithJoins {
+                    relation: TableFactor::Table { .. },
+                    joins,
+                },
+            selection,
+            projection,
+            group_by,
+            ..
+        } = select
+        else {
+            return None;
+        };
+
+        if parallelism <= 1 || !joins.is_empty() || !check_aggregate(projection, group_by) {
+            return None;
+        }
+
+        let mut aggregates = Vec::new();
+        let collected = projection.iter().all(|item| match item {
+            SelectItem::Expr { expr, .. } => collect(expr, &mut aggregates),
+            _ => true,
+        });
+        let stateless = selection
+            .iter()
+            .chain(group_by)
+            .chain(aggregates.iter().filter_map(|aggr| aggr.as_expr()))
+            .all(stateless);
+
+        if !collected || !stateless {
+            return None;
+        }
+
+        let exprs = Exprs {
+            where_clause: selection.clone(),
+            group_by: group_by.clone(),
+            aggregates: aggregates.iter().map(|&aggr| aggr.clone()).collect(),
+        };
+
+        Some(Self {
+            parallelism,
+            aggregates,
+            exprs: Arc::new(exprs),
+        })
+    }
+
+    pub async fn apply<T: GStore, U: Stream<Item = Result<Row>> + 'a>(
+        self,
+        storage: &'a T,
+        table_alias: &'a str,
+        having: Option<&'a Expr>,
+        partitions: Vec<U>,
+    ) -> Result<impl Stream<Item = Result<AggregateContext<'a>>> + use<'a, T, U>> {
+        let Self {
+            parallelism,
+            aggregates,
+            exprs,
+        } = self;
+
+        // the tasks are shared among the partitions, a single one taking all of them
+        let buffered = parallelism.div_ceil(partitions.len().max(1));
+        let partials = partitions
+            .into_iter()
+            .map(|rows| aggregate_partition(Arc::clone(&exprs), table_alias, buffered, rows));
+        let partial = try_join_all(partials)
+            .await?
+            .into_iter()
+            .try_fold(Partial::default(), Partial::merge)?;
+
+        let mut rows = Vec::with_capacity(partial.groups.len());
+        for Group { row, values, .. } in partial.groups {
+            let aggregated = if aggregates.is_empty() {
+                None
+            } else {
+                let mut aggregated = im::HashMap::new();
+                for (&aggr, PartialValue { value, .. }) in aggregates.iter().zip(values) {
+                    aggregated.insert(aggr, value.export().await?);
+                }
+
+                Some(aggregated)
+            };
+            let next = Arc::new(RowContext::new(table_alias, Cow::Owned(row), None));
+
+            rows.push((aggregated, next));
+        }
+
+        group_by_having(storage, None, having, rows).await
+    }
+}
+
+/// Aggregates the rows of a partition, up to `buffered` batches at a time.
+async fn aggregate_partition<U: Stream<Item = Result<Row>>>(
+    exprs: Arc<Exprs>,
+    table_alias: &str,
+    buffered: usize,
+    rows: U,
+) -> Result<Partial> {
+    rows.try_chunks(BATCH_SIZE)
+        .map_err(|TryChunksError(_, error)| error)
+        .map_ok(|rows| spawn(Arc::clone(&exprs), table_alias.to_owned(), rows))
+        .try_buffered(buffered)
+        .try_fold(Partial::default(), |partial, batch| async move {
+            partial.merge(batch)
+        })
+        .await
+}
+
+/// Aggregates the batch on a blocking thread of the Tokio runtime running the query, or in
+/// place without the runtime or without the `parallel` feature.
+async fn spawn(exprs: Arc<Exprs>, table_alias: String, rows: Vec<Row>) -> Result<Partial> {
+    #[cfg(feature = "parallel")]
+    if let Ok(handle) = tokio::runtime::Handle::try_current() {
+        return handle
+            .spawn_blocking(move || {
+                futures::executor::block_on(aggregate_batch(&exprs, &table_alias, rows))
+            })
+            .await
+            .unwrap_or_else(|error| std::panic::resume_unwind(error.into_panic()));
+    }
+
+    aggregate_batch(&exprs, &table_alias, rows).await
+}
+
+async fn aggregate_batch(exprs: &Exprs, table_alias: &str, rows: Vec<Row>) -> Result<Partial> {
+    let mut partial = Partial::default();
+
+    for row in rows {
+        let context = || Some(RowContext::new(table_alias, Cow::Borrowed(&row), None));
+
+        if let Some(expr) = &exprs.where_clause {
+            let pass = match evaluate_stateless(context(), expr).await? {
+                evaluated if evaluated.is_null() => false,
+                evaluated => evaluated.try_into()?,
+            };
+
+            if !pass {
+                continue;
+            }
+        }
+
+        let mut keys = Vec::with_capacity(exprs.group_by.len());
+        for expr in &exprs.group_by {
+            let evaluated = evaluate_stateless(context(), expr).await?;
+
+            keys.push(Key::try_from(evaluated)?);
+        }
+
+        let mut values = Vec::with_capacity(exprs.aggregates.len());
+        for aggr in &exprs.aggregates {
+            let value = match aggr.as_expr() {
+                Some(expr) => Value::try_from(evaluate_stateless(context(), expr).await?)?,
+                None if aggr.distinct => {
+                    let entries = RowContext::new(table_alias, Cow::Borrowed(&row), None)
+                        .get_all_entries()
+                        .into_iter()
+                        .map(|(_, value)| value)
+                        .collect();
+
+                    Value::List(entries)
+                }
+                None => Value::Null,
+            };
+
+            values.push(value);
+        }
+
+        partial.accumulate(&exprs.aggregates, keys, row, values)?;
+    }
+
+    Ok(partial)
+}
+
+/// Groups of a batch, or of the batches merged so far, in the order of their first rows.
+#[derive(Default)]
+struct Partial {
+    indexes: HashMap<Vec<Key>, usize>,
+    groups: Vec<Group>,
+}
+
+struct Group {
+    keys: Vec<Key>,
+    /// First row of the group, which `HAVING` and the projection read.
+    row: Row,
+    /// Aggregate of each of [`Exprs::aggregates`].
+    values: Vec<PartialValue>,
+}
+
+impl Partial {
+    fn accumulate(
+        &mut self,
+        aggregates: &[Aggregate],
+        keys: Vec<Key>,
+        row: Row,
+        values: Vec<Value>,
+    ) -> Result<()> {
+        if let Some(&index) = self.indexes.get(&keys) {
+            let group = &mut self.groups[index];
+            group.values = std::mem::take(&mut group.values)
+                .into_iter()
+                .zip(aggregates.iter().zip(&values))
+                .map(|(partial, (aggr, value))| partial.accumulate(aggr, value))
+                .collect::<Result<_>>()?;
+
+            return Ok(());
+        }
+
+        let values = aggregates
+            .iter()
+            .zip(&values)
+            .map(|(aggr, value)| PartialValue::new(aggr, value))
+            .collect::<Result<_>>()?;
+
+        self.push(Group { keys, row, values });
+
+        Ok(())
+    }
+
+    fn merge(mut self, batch: Partial) -> Result<Self> {
+        for other in batch.groups {
+            let Some(&index) = self.indexes.get(&other.keys) else {
+                self.push(other);
+                continue;
+            };
+
+            let group = &mut self.groups[index];
+            group.values = std::mem::take(&mut group.values)
+                .into_iter()
+                .zip(other.values)
+                .map(|(partial, other)| partial.merge(other))
+                .collect::<Result<_>>()?;
+        }
+
+        Ok(self)
+    }
+
+    fn push(&mut self, group: Group) {
+        self.indexes.insert(group.keys.clone(), self.groups.len());
+        self.groups.push(group);
+    }
+}
+
+/// Aggregate of the rows of a group in a batch.
+struct PartialValue {
+    value: AggrValue,
+    /// Aggregate of the rows after the first while `value` [leads with
+    /// `NULL`](AggrValue::leads_with_null), which rows of earlier batches would have skipped.
+    rest: Option<AggrValue>,
+}
+
+impl PartialValue {
+    fn new(aggr: &Aggregate, value: &Value) -> Result<Self> {
+        AggrValue::new(aggr, value).map(|value| Self { value, rest: None })
+    }
+
+    fn accumulate(self, aggr: &Aggregate, value: &Value) -> Result<Self> {
+        let Self {
+            value: aggr_value,
+            rest,
+        } = self;
+        let rest = match rest {
+            Some(rest) => Some(accumulate(rest, value)?),
+            None if aggr_value.leads_with_null() && !value.is_null() => {
+                Some(AggrValue::new(aggr, value)?)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            value: accumulate(aggr_value, value)?,
+            rest,
+        })
+    }
+
+    fn merge(self, other: Self) -> Result<Self> {
+        let other = match other {
+            Self {
+                rest: Some(rest), ..
+            } => rest,
+            Self { value, .. } if value.leads_with_null() => return Ok(self),
+            Self { value, .. } => value,
+        };
+
+        Ok(Self {
+            value: self.value.merge(other)?,
+            rest: None,
+        })
+    }
+}
+
+fn accumulate(aggr_value: AggrValue, value: &Value) -> Result<AggrValue> {
+    aggr_value
+        .accumulate(value)
+        .map(|accumulated| accumulated.unwrap_or(aggr_value))
+}
+
+/// Collects the aggregates [`super::aggregate`] accumulates for the expression, false when it
+/// has a window function without `OVER`, which fails to aggregate.
+fn collect<'a>(expr: &'a Expr, aggregates: &mut Vec<&'a Aggregate>) -> bool {
+    match expr {
+        Expr::Between {
+            expr, low, high, ..
+        } => [expr, low, high]
+            .into_iter()
+            .all(|expr| collect(expr, aggregates)),
+        Expr::BinaryOp { left, right, .. } => {
+            collect(left, aggregates) && collect(right, aggregates)
+        }
+        Expr::UnaryOp { expr, .. } | Expr::Nested(expr) => collect(expr, aggregates),
+        Expr::Case {
+            operand,
+            when_then,
+            else_result,
+        } => operand
+            .as_deref()
+            .into_iter()
+            .chain(when_then.iter().flat_map(|(when, then)| [when, then]))
+            .chain(else_result.as_deref())
+            .all(|expr| collect(expr, aggregates)),
+        Expr::Aggregate(aggr) if aggr.over.is_some() => {
+            aggr.as_exprs().all(|expr| collect(expr, aggregates))
+        }
+        Expr::Aggregate(aggr) if aggr.is_window_function() => false,
+        Expr::Aggregate(aggr) => {
+            let aggr: &Aggregate = aggr;
+            if !aggregates.contains(&aggr) {
+                aggregates.push(aggr);
+            }
+
+            true
+        }
+        _ => true,
+    }
+}
+
+/// Whether the expression evaluates without the storage, which the tasks do not have.
+fn stateless(expr: &Expr) -> bool {
+    if let Expr::Function(function) = expr {
+        if matches!(
+            **function,
+            Function::Custom { .. }
+                | Function::NextVal(_)
+                | Function::CurrVal(_)
+                | Function::SetVal { .. }
+        ) {
+            return false;
+        }
+    }
+
+    match PlanExpr::from(expr) {
+        PlanExpr::None | PlanExpr::Identifier(_) | PlanExpr::CompoundIdentifier { .. } => true,
+        PlanExpr::Expr(expr) => stateless(expr),
+        PlanExpr::TwoExprs(expr, expr2) => stateless(expr) && stateless(expr2),
+        PlanExpr::ThreeExprs(expr, expr2, expr3) => {
+            stateless(expr) && stateless(expr2) && stateless(expr3)
+        }
+        PlanExpr::MultiExprs(exprs) => exprs.into_iter().all(stateless),
+        PlanExpr::Query(_) | PlanExpr::QueryAndExpr { .. } => false,
+    }
+}
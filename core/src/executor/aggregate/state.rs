@@ -1,6 +1,6 @@
 use {
     crate::{
-        ast::{Aggregate, AggregateFunction, CountArgExpr, DataType},
+        ast::{Aggregate, AggregateFunction, CountArgExpr, DataType, ToSql},
         data::{Key, Value},
         executor::{
             context::RowContext,
@@ -16,11 +16,11 @@ use {
 };
 
 type Group = Arc<Vec<Key>>;
-type ValuesMap<'a> = HashMap<&'a Aggregate, Value>;
+pub(super) type ValuesMap<'a> = HashMap<&'a Aggregate, Value>;
 type Context<'a> = Arc<RowContext<'a>>;
 
 #[derive(Clone)]
-enum AggrValue {
+pub(super) enum AggrValue {
     Count {
         wildcard: bool,
         count: i64,
@@ -77,7 +77,7 @@ impl AggrValue {
         }
     }
 
-    fn new(aggr: &Aggregate, value: &Value) -> Result<Self> {
+    pub(super) fn new(aggr: &Aggregate, value: &Value) -> Result<Self> {
         let value = value.clone();
 
         Ok(match &aggr.func {
@@ -210,10 +210,17 @@ impl AggrValue {
                     distinct_values,
                 }
             }
+            AggregateFunction::RowNumber
+            | AggregateFunction::Rank
+            | AggregateFunction::DenseRank
+            | AggregateFunction::Lag { .. }
+            | AggregateFunction::Lead { .. } => {
+                return Err(EvaluateError::WindowFunctionNotAllowed(aggr.to_sql()).into());
+            }
         })
     }
 
-    fn accumulate(&self, new_value: &Value) -> Result<Option<Self>> {
+    pub(super) fn accumulate(&self, new_value: &Value) -> Result<Option<Self>> {
         match self {
             Self::Count {
                 wildcard,
@@ -357,7 +364,148 @@ impl AggrValue {
         }
     }
 
-    async fn export(self) -> Result<Value> {
+    /// Adds the aggregate of the rows following the rows of `self`, giving what accumulating
+    /// them one by one gives. Values of `DISTINCT` aggregates are accumulated again unless
+    /// `self` has them.
+    pub(super) fn merge(self, other: Self) -> Result<Self> {
+        let other = match other {
+            Self::Count {
+                distinct_values: Some(values),
+                ..
+            }
+            | Self::Sum {
+                distinct_values: Some(values),
+                ..
+            }
+            | Self::Min {
+                distinct_values: Some(values),
+                ..
+            }
+            | Self::Max {
+                distinct_values: Some(values),
+                ..
+            }
+            | Self::Avg {
+                distinct_values: Some(values),
+                ..
+            }
+            | Self::Variance {
+                distinct_values: Some(values),
+                ..
+            }
+            | Self::Stdev {
+                distinct_values: Some(values),
+                ..
+            } => {
+                return values.into_iter().try_fold(self, |aggr_value, value| {
+                    aggr_value
+                        .accumulate(&value)
+                        .map(|accumulated| accumulated.unwrap_or(aggr_value))
+                });
+            }
+            other => other,
+        };
+
+        Ok(match (self, other) {
+            (
+                Self::Count {
+                    wildcard,
+                    count,
+                    distinct_values,
+                },
+                Self::Count { count: other, .. },
+            ) => Self::Count {
+                wildcard,
+                count: count + other,
+                distinct_values,
+            },
+            (
+                Self::Sum {
+                    value,
+                    distinct_values,
+                },
+                Self::Sum { value: other, .. },
+            ) => Self::Sum {
+                value: value.add(&other)?,
+                distinct_values,
+            },
+            (
+                Self::Avg {
+                    sum,
+                    count,
+                    distinct_values,
+                },
+                Self::Avg {
+                    sum: other_sum,
+                    count: other_count,
+                    ..
+                },
+            ) => Self::Avg {
+                sum: sum.add(&other_sum)?,
+                count: count + other_count,
+                distinct_values,
+            },
+            (
+                Self::Variance {
+                    sum_square,
+                    sum,
+                    count,
+                    distinct_values,
+                },
+                Self::Variance {
+                    sum_square: other_sum_square,
+                    sum: other_sum,
+                    count: other_count,
+                    ..
+                },
+            ) => Self::Variance {
+                sum_square: sum_square.add(&other_sum_square)?,
+                sum: sum.add(&other_sum)?,
+                count: count + other_count,
+                distinct_values,
+            },
+            (
+                Self::Stdev {
+                    sum_square,
+                    sum,
+                    count,
+                    distinct_values,
+                },
+                Self::Stdev {
+                    sum_square: other_sum_square,
+                    sum: other_sum,
+                    count: other_count,
+                    ..
+                },
+            ) => Self::Stdev {
+                sum_square: sum_square.add(&other_sum_square)?,
+                sum: sum.add(&other_sum)?,
+                count: count + other_count,
+                distinct_values,
+            },
+            (aggr_value, Self::Min { value: other, .. } | Self::Max { value: other, .. }) => {
+                aggr_value.accumulate(&other)?.unwrap_or(aggr_value)
+            }
+            (aggr_value, _) => aggr_value,
+        })
+    }
+
+    /// Whether it is `MIN` or `MAX` without `DISTINCT` whose first value was `NULL`, which it
+    /// keeps whatever values follow.
+    pub(super) fn leads_with_null(&self) -> bool {
+        matches!(
+            self,
+            Self::Min {
+                value: Value::Null,
+                distinct_values: None,
+            } | Self::Max {
+                value: Value::Null,
+                distinct_values: None,
+            }
+        )
+    }
+
+    pub(super) async fn export(self) -> Result<Value> {
         let variance = |sum_square: Value, sum: Value, count: i64| async move {
             let count = Value::I64(count);
             let sum_expr1 = sum_square.multiply(&count)?;
@@ -486,7 +634,7 @@ impl<'a, T: GStore> State<'a, T> {
             AggregateFunction::Count(CountArgExpr::Wildcard) => {
                 if aggr.distinct {
                     let context = filter_context.as_ref().ok_or_else(|| {
-                        EvaluateError::FilterContextRequiredForAggregate(aggr.clone())
+                        EvaluateError::FilterContextRequiredForAggregate(Box::new(aggr.clone()))
                     })?;
                     let entries = context.get_all_entries();
                     let values: Vec<Value> = entries.into_iter().map(|(_, v)| v).collect();
@@ -504,6 +652,13 @@ impl<'a, T: GStore> State<'a, T> {
             | AggregateFunction::Stdev(expr) => evaluate(self.storage, filter_context, None, expr)
                 .await?
                 .try_into()?,
+            AggregateFunction::RowNumber
+            | AggregateFunction::Rank
+            | AggregateFunction::DenseRank
+            | AggregateFunction::Lag { .. }
+            | AggregateFunction::Lead { .. } => {
+                return Err(EvaluateError::WindowFunctionNotAllowed(aggr.to_sql()).into());
+            }
         };
         let aggr_value = match self.get(aggr) {
             Some((index, _)) if self.index <= *index => None,
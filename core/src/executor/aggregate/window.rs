@@ -0,0 +1,257 @@
+use {
+    super::state::AggrValue,
+    crate::{
+        ast::{Aggregate, AggregateFunction, Expr, OrderByExpr, SelectItem, WindowSpec},
+        data::{Key, Value},
+        executor::{
+            context::{AggregateContext, RowContext},
+            evaluate::evaluate,
+            sort::sort_by,
+        },
+        plan::PlanExpr,
+        result::Result,
+        store::GStore,
+    },
+    futures::stream::{self, Stream, TryStreamExt},
+    im::HashMap,
+    std::{cmp::Ordering, collections::BTreeMap, ops::Range, sync::Arc},
+};
+
+#[derive(futures_enum::Stream)]
+enum S<T1, T2> {
+    NonWindow(T1),
+    Window(T2),
+}
+
+type Partition = Vec<(Vec<(Key, Option<bool>)>, usize)>;
+
+/// Computes the window functions of the projection and of `ORDER BY` for every row, after
+/// grouping, and stores them with the aggregated values of the row.
+pub async fn apply<'a, T: GStore, U: Stream<Item = Result<AggregateContext<'a>>> + 'a>(
+    storage: &'a T,
+    fields: &'a [SelectItem],
+    order_by: &'a [OrderByExpr],
+    filter_context: Option<Arc<RowContext<'a>>>,
+    rows: U,
+) -> Result<impl Stream<Item = Result<AggregateContext<'a>>> + use<'a, T, U>> {
+    let mut windows = Vec::new();
+    fields
+        .iter()
+        .filter_map(|field| match field {
+            SelectItem::Expr { expr, .. } => Some(expr),
+            _ => None,
+        })
+        .chain(order_by.iter().map(|OrderByExpr { expr, .. }| expr))
+        .for_each(|expr| collect(expr, &mut windows));
+
+    if windows.is_empty() {
+        return Ok(S::NonWindow(rows));
+    }
+
+    let mut rows = rows.try_collect::<Vec<_>>().await?;
+    for (window, over) in windows {
+        let values = compute(storage, filter_context.as_ref(), window, over, &rows).await?;
+
+        for (row, value) in rows.iter_mut().zip(values) {
+            row.aggregated
+                .get_or_insert_with(HashMap::new)
+                .insert(window, value);
+        }
+    }
+
+    Ok(S::Window(stream::iter(rows.into_iter().map(Ok))))
+}
+
+/// Collects the window functions of the expression, inner ones first, leaving subqueries out.
+fn collect<'a>(expr: &'a Expr, windows: &mut Vec<(&'a Aggregate, &'a WindowSpec)>) {
+    match PlanExpr::from(expr) {
+        PlanExpr::None
+        | PlanExpr::Identifier(_)
+        | PlanExpr::CompoundIdentifier { .. }
+        | PlanExpr::Query(_) => {}
+        PlanExpr::Expr(expr) | PlanExpr::QueryAndExpr { expr, .. } => collect(expr, windows),
+        PlanExpr::TwoExprs(expr, expr2) => {
+            collect(expr, windows);
+            collect(expr2, windows);
+        }
+        PlanExpr::ThreeExprs(expr, expr2, expr3) => {
+            collect(expr, windows);
+            collect(expr2, windows);
+            collect(expr3, windows);
+        }
+        PlanExpr::MultiExprs(exprs) => exprs.into_iter().for_each(|expr| collect(expr, windows)),
+    }
+
+    if let Expr::Aggregate(aggr) = expr
+        && let Some(over) = &aggr.over
+        && !windows.iter().any(|(window, _)| *window == aggr.as_ref())
+    {
+        windows.push((aggr, over));
+    }
+}
+
+async fn compute<'a, T: GStore>(
+    storage: &'a T,
+    filter_context: Option<&Arc<RowContext<'a>>>,
+    window: &'a Aggregate,
+    over: &'a WindowSpec,
+    rows: &[AggregateContext<'a>],
+) -> Result<Vec<Value>> {
+    let WindowSpec {
+        partition_by,
+        order_by,
+    } = over;
+
+    let mut partitions = BTreeMap::<Vec<Key>, Partition>::new();
+    for (i, row) in rows.iter().enumerate() {
+        let mut partition = Vec::with_capacity(partition_by.len());
+        for expr in partition_by {
+            let value = evaluate_value(storage, filter_context, row, expr).await?;
+            partition.push(Key::try_from(value)?);
+        }
+
+        let mut keys = Vec::with_capacity(order_by.len());
+        for OrderByExpr { expr, asc } in order_by {
+            let value = evaluate_value(storage, filter_context, row, expr).await?;
+            keys.push((Key::try_from(value)?, *asc));
+        }
+
+        partitions.entry(partition).or_default().push((keys, i));
+    }
+
+    let mut values = vec![Value::Null; rows.len()];
+    for mut partition in partitions.into_values() {
+        partition.sort_by(|(keys_a, _), (keys_b, _)| sort_by(keys_a, keys_b));
+
+        match &window.func {
+            AggregateFunction::RowNumber => {
+                for (position, (_, i)) in partition.iter().enumerate() {
+                    values[*i] = Value::I64(position as i64 + 1);
+                }
+            }
+            AggregateFunction::Rank | AggregateFunction::DenseRank => {
+                let dense = matches!(window.func, AggregateFunction::DenseRank);
+
+                for (n, peers) in peer_groups(&partition).into_iter().enumerate() {
+                    let rank = if dense { n + 1 } else { peers.start + 1 };
+
+                    for (_, i) in &partition[peers] {
+                        values[*i] = Value::I64(rank as i64);
+                    }
+                }
+            }
+            AggregateFunction::Lag {
+                expr,
+                offset,
+                default,
+            }
+            | AggregateFunction::Lead {
+                expr,
+                offset,
+                default,
+            } => {
+                let lead = matches!(window.func, AggregateFunction::Lead { .. });
+
+                for (position, (_, i)) in partition.iter().enumerate() {
+                    let row = &rows[*i];
+                    let offset = match offset {
+                        Some(offset) => {
+                            evaluate_value(storage, filter_context, row, offset).await?
+                        }
+                        None => Value::I64(1),
+                    };
+                    if offset.is_null() {
+                        continue;
+                    }
+
+                    let offset = i64::try_from(&offset)?;
+                    let target = match lead {
+                        true => position as i64 + offset,
+                        false => position as i64 - offset,
+                    };
+                    let target = usize::try_from(target)
+                        .ok()
+                        .and_then(|target| partition.get(target));
+
+                    values[*i] = match (target, default) {
+                        (Some((_, j)), _) => {
+                            evaluate_value(storage, filter_context, &rows[*j], expr).await?
+                        }
+                        (None, Some(default)) => {
+                            evaluate_value(storage, filter_context, row, default).await?
+                        }
+                        (None, None) => Value::Null,
+                    };
+                }
+            }
+            _ => {
+                // With ORDER BY the frame ends at the last peer of the row, so the aggregate is
+                // a running one; without, every row of the partition is a peer.
+                let mut state: Option<AggrValue> = None;
+
+                for peers in peer_groups(&partition) {
+                    for (_, i) in &partition[peers.clone()] {
+                        let value = match window.as_expr() {
+                            Some(expr) => {
+                                evaluate_value(storage, filter_context, &rows[*i], expr).await?
+                            }
+                            None => Value::Null,
+                        };
+
+                        state = match state {
+                            Some(state) => Some(state.accumulate(&value)?.unwrap_or(state)),
+                            None => Some(AggrValue::new(window, &value)?),
+                        };
+                    }
+
+                    let value = match state.clone() {
+                        Some(state) => state.export().await?,
+                        None => Value::Null,
+                    };
+                    for (_, i) in &partition[peers] {
+                        values[*i] = value.clone();
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(values)
+}
+
+/// Splits the sorted partition into runs of rows with equal `ORDER BY` keys.
+fn peer_groups(partition: &Partition) -> Vec<Range<usize>> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+
+    for end in 1..=partition.len() {
+        if end == partition.len()
+            || sort_by(&partition[start].0, &partition[end].0) != Ordering::Equal
+        {
+            groups.push(start..end);
+            start = end;
+        }
+    }
+
+    groups
+}
+
+async fn evaluate_value<'a, T: GStore>(
+    storage: &'a T,
+    filter_context: Option<&Arc<RowContext<'a>>>,
+    row: &AggregateContext<'a>,
+    expr: &'a Expr,
+) -> Result<Value> {
+    let context = match filter_context {
+        Some(filter_context) => Arc::new(RowContext::concat(
+            Arc::clone(&row.next),
+            Arc::clone(filter_context),
+        )),
+        None => Arc::clone(&row.next),
+    };
+    let aggregated = row.aggregated.clone().map(Arc::new);
+
+    evaluate(storage, Some(context), aggregated, expr)
+        .await?
+        .try_into()
+}
@@ -2,15 +2,26 @@ mod alter_table;
 mod error;
 mod function;
 mod index;
+mod sequence;
 mod table;
+mod trigger;
+mod user;
 mod validate;
+mod view;
 
-use validate::{validate, validate_arg_names, validate_column_names, validate_default_args};
+use validate::{
+    check_columns, validate, validate_arg_names, validate_check, validate_column_names,
+    validate_default_args, validate_unique,
+};
 
 pub use {
     alter_table::alter_table,
     error::AlterError,
     function::{delete_function, insert_function},
     index::create_index,
+    sequence::{create_sequence, drop_sequence},
     table::{CreateTableOptions, Referencing, create_table, drop_table},
+    trigger::{create_trigger, drop_trigger},
+    user::{create_user, drop_users, grant, revoke},
+    view::{create_view, drop_view, refresh_materialized_view},
 };
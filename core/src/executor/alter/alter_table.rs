@@ -1,8 +1,9 @@
 use {
-    super::{AlterError, Referencing, validate},
+    super::{AlterError, Referencing, check_columns, validate},
     crate::{
-        ast::{AlterTableOperation, Expr, Function},
+        ast::{AlterTableOperation, ColumnDef, Expr},
         data::{Schema, SchemaIndex},
+        executor::{evaluate_stateless, is_sequence_function},
         result::Result,
         store::{GStore, GStoreMut},
     },
@@ -17,7 +18,8 @@ pub async fn alter_table<T: GStore + GStoreMut>(
         old_column_name: column_name,
         ..
     }
-    | AlterTableOperation::DropColumn { column_name, .. } = operation
+    | AlterTableOperation::DropColumn { column_name, .. }
+    | AlterTableOperation::AlterColumnType { column_name, .. } = operation
     {
         if let Some(schema) = storage.fetch_schema(table_name).await? {
             let referencing_foreign_key = schema
@@ -34,6 +36,34 @@ pub async fn alter_table<T: GStore + GStoreMut>(
                 }
                 .into());
             }
+
+            let checked = schema.checks.into_iter().find(|check| {
+                let mut columns = Vec::new();
+                check_columns(&check.expr, &mut columns);
+
+                columns.contains(&column_name.as_str())
+            });
+
+            if let Some(check) = checked {
+                return Err(AlterError::CannotAlterCheckedColumn {
+                    check: check.name,
+                    column: column_name.to_owned(),
+                }
+                .into());
+            }
+
+            let unique = schema
+                .uniques
+                .into_iter()
+                .find(|unique| unique.columns.contains(column_name));
+
+            if let Some(unique) = unique {
+                return Err(AlterError::CannotAlterUniqueColumn {
+                    unique: unique.name,
+                    column: column_name.to_owned(),
+                }
+                .into());
+            }
         }
 
         let referencings = storage.fetch_referencings(table_name).await?;
@@ -78,7 +108,7 @@ pub async fn alter_table<T: GStore + GStoreMut>(
 
             let indexes = indexes
                 .iter()
-                .filter(|SchemaIndex { expr, .. }| find_column(expr, column_name))
+                .filter(|index| reads_column(index, column_name))
                 .map(|SchemaIndex { name, .. }| name);
 
             for index_name in indexes {
@@ -89,9 +119,62 @@ pub async fn alter_table<T: GStore + GStoreMut>(
                 .drop_column(table_name, column_name, *if_exists)
                 .await
         }
+        AlterTableOperation::AlterColumnType {
+            column_name,
+            data_type,
+            using,
+        } => {
+            let column_def = storage
+                .fetch_schema(table_name)
+                .await?
+                .and_then(|schema| schema.column_defs)
+                .and_then(|column_defs| {
+                    column_defs
+                        .into_iter()
+                        .find(|column_def| &column_def.name == column_name)
+                });
+
+            if let Some(column_def) = column_def {
+                let column_def = ColumnDef {
+                    data_type: data_type.clone(),
+                    ..column_def
+                };
+                validate(&column_def).await?;
+
+                if let Some(default) = column_def
+                    .default
+                    .as_ref()
+                    .filter(|expr| !is_sequence_function(expr))
+                {
+                    evaluate_stateless(None, default)
+                        .await?
+                        .try_into_value(data_type, column_def.nullable)?
+                        .cast(data_type)?;
+                }
+            }
+
+            storage
+                .alter_column_type(table_name, column_name, data_type, using.as_ref())
+                .await
+        }
     }
 }
 
+/// Whether the index expression or the predicate of a partial index reads the column
+fn reads_column(index: &SchemaIndex, column_name: &str) -> bool {
+    let SchemaIndex {
+        exprs, predicate, ..
+    } = index;
+
+    exprs.iter().any(|expr| find_column(expr, column_name))
+        || predicate.as_ref().is_some_and(|predicate| {
+            let mut columns = Vec::new();
+            check_columns(predicate, &mut columns);
+
+            columns.contains(&column_name)
+        })
+}
+
 fn find_column(expr: &Expr, column_name: &str) -> bool {
     let find = |expr| find_column(expr, column_name);
 
@@ -100,10 +183,7 @@ fn find_column(expr: &Expr, column_name: &str) -> bool {
         Expr::Nested(expr) => find(expr),
         Expr::BinaryOp { left, right, .. } => find(left) || find(right),
         Expr::UnaryOp { expr, .. } => find(expr),
-        Expr::Function(func) => match func.as_ref() {
-            Function::Cast { expr, .. } => find(expr),
-            _ => false,
-        },
+        Expr::Function(func) => func.as_exprs().any(find),
         _ => false,
     }
 }
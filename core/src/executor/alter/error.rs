@@ -22,6 +22,53 @@ pub enum AlterError {
     #[error("table does not exist: {0}")]
     TableNotFound(String),
 
+    // CREATE USER, DROP USER, GRANT, REVOKE
+    #[error("user already exists: {0}")]
+    UserAlreadyExists(String),
+
+    #[error("user does not exist: {0}")]
+    UserNotFound(String),
+
+    #[error("column does not exist: {0}")]
+    ColumnNotFound(String),
+
+    // CREATE VIEW, DROP VIEW, SHOW CREATE VIEW
+    #[error("view does not exist: {0}")]
+    ViewNotFound(String),
+
+    #[error("view reads itself: {0}")]
+    ViewReadsItself(String),
+
+    #[error("'{0}' is a view, use DROP VIEW to drop it")]
+    DropTableOnView(String),
+
+    #[error("cannot drop '{name}' due to dependent views: '{}'", views.join(", "))]
+    CannotDropWithDependentViews { name: String, views: Vec<String> },
+
+    // REFRESH MATERIALIZED VIEW
+    #[error("materialized view does not exist: {0}")]
+    MaterializedViewNotFound(String),
+
+    // CREATE TRIGGER, DROP TRIGGER
+    #[error("trigger already exists: {0}")]
+    TriggerAlreadyExists(String),
+
+    #[error("trigger does not exist: {0}")]
+    TriggerNotFound(String),
+
+    #[error("'{0}' is a view, triggers can only be created on tables")]
+    TriggerOnView(String),
+
+    #[error("triggers on '{0}' would fire themselves")]
+    TriggerFiresItself(String),
+
+    // CREATE SEQUENCE, DROP SEQUENCE, NEXTVAL
+    #[error("sequence does not exist: {0}")]
+    SequenceNotFound(String),
+
+    #[error("'{0}' is a sequence, use DROP SEQUENCE to drop it")]
+    DropTableOnSequence(String),
+
     #[error("CTAS source table does not exist: {0}")]
     CtasSourceTableNotFound(String),
 
@@ -33,6 +80,15 @@ pub enum AlterError {
     #[error("unsupported index expr: {0:#?}")]
     UnsupportedIndexExpr(Expr),
 
+    #[error("unsupported partial index predicate: {0:#?}")]
+    UnsupportedIndexPredicate(Expr),
+
+    #[error("column '{0}' of partial index predicate not found")]
+    IndexPredicateColumnNotFound(String),
+
+    #[error("full-text index takes a single expression, found {0}")]
+    FullTextIndexExprCount(usize),
+
     // validate index expr
     #[error("unsupported unnamed argument")]
     UnsupportedUnnamedArg,
@@ -76,6 +132,26 @@ pub enum AlterError {
         referenced_column: String,
     },
 
+    #[error("referencing column '{0}' is NOT NULL, cannot be set to NULL by its foreign key")]
+    SetNullOnNotNullColumn(String),
+
+    // CHECK constraints
+    #[error("CHECK constraint '{0}' can only read the columns of its row")]
+    UnsupportedCheckExpr(String),
+
+    #[error("column '{column}' of CHECK constraint '{check}' not found")]
+    CheckColumnNotFound { check: String, column: String },
+
+    #[error("cannot alter column '{column}' read by CHECK constraint '{check}'")]
+    CannotAlterCheckedColumn { check: String, column: String },
+
+    // UNIQUE constraints
+    #[error("column '{column}' of UNIQUE constraint '{unique}' not found")]
+    UniqueColumnNotFound { unique: String, column: String },
+
+    #[error("cannot alter column '{column}' of UNIQUE constraint '{unique}'")]
+    CannotAlterUniqueColumn { unique: String, column: String },
+
     #[error("cannot drop table '{referenced_table_name}' due to referencing tables: '{}'", referencings.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
     CannotDropTableWithReferencing {
         referenced_table_name: String,
@@ -1,5 +1,5 @@
 use {
-    super::AlterError,
+    super::{AlterError, check_columns},
     crate::{
         ast::{ColumnDef, Expr, Function, OrderByExpr},
         data::Schema,
@@ -12,9 +12,10 @@ pub async fn create_index<T: GStore + GStoreMut>(
     storage: &mut T,
     table_name: &str,
     index_name: &str,
-    column: &OrderByExpr,
+    index_columns: &[OrderByExpr],
+    predicate: Option<&Expr>,
+    fulltext: bool,
 ) -> Result<()> {
-    let expr = &column.expr;
     let Schema { column_defs, .. } = storage
         .fetch_schema(table_name)
         .await?
@@ -25,16 +26,40 @@ pub async fn create_index<T: GStore + GStoreMut>(
         .map(|ColumnDef { name, .. }| name)
         .collect::<Vec<_>>();
 
-    let (valid, has_ident) = validate_index_expr(&columns, expr);
-    if !valid {
-        return Err(AlterError::UnsupportedIndexExpr(expr.clone()).into());
-    } else if !has_ident {
-        return Err(AlterError::IdentifierNotFound(expr.clone()).into());
+    if fulltext && index_columns.len() != 1 {
+        return Err(AlterError::FullTextIndexExprCount(index_columns.len()).into());
     }
 
-    storage.create_index(table_name, index_name, column).await
+    for OrderByExpr { expr, .. } in index_columns {
+        let (valid, has_ident) = validate_index_expr(&columns, expr);
+        if !valid {
+            return Err(AlterError::UnsupportedIndexExpr(expr.clone()).into());
+        } else if !has_ident {
+            return Err(AlterError::IdentifierNotFound(expr.clone()).into());
+        }
+    }
+
+    if let Some(predicate) = predicate {
+        let mut predicate_columns = Vec::new();
+        if !check_columns(predicate, &mut predicate_columns) {
+            return Err(AlterError::UnsupportedIndexPredicate(predicate.clone()).into());
+        }
+
+        if let Some(column) = predicate_columns
+            .into_iter()
+            .find(|column| !columns.iter().any(|name| name == column))
+        {
+            return Err(AlterError::IndexPredicateColumnNotFound(column.to_owned()).into());
+        }
+    }
+
+    storage
+        .create_index(table_name, index_name, index_columns, predicate, fulltext)
+        .await
 }
 
+/// Validates the index expression, returning whether it is valid and whether it reads a column.
+/// Functions are valid when they give the same value for the same row every time.
 fn validate_index_expr(columns: &[String], expr: &Expr) -> (bool, bool) {
     let validate = |expr| validate_index_expr(columns, expr);
 
@@ -50,8 +75,22 @@ fn validate_index_expr(columns: &[String], expr: &Expr) -> (bool, bool) {
         }
         Expr::UnaryOp { expr, .. } => validate(expr),
         Expr::Function(func) => match func.as_ref() {
-            Function::Cast { expr, .. } => validate(expr),
-            _ => (false, false),
+            Function::Custom { .. }
+            | Function::Rand(_)
+            | Function::Now()
+            | Function::CurrentDate()
+            | Function::CurrentTime()
+            | Function::CurrentTimestamp()
+            | Function::GenerateUuid()
+            | Function::NextVal(_)
+            | Function::CurrVal(_)
+            | Function::SetVal { .. } => (false, false),
+            func => func.as_exprs().map(validate).fold(
+                (true, false),
+                |(valid, has_ident), (valid2, has_ident2)| {
+                    (valid && valid2, has_ident || has_ident2)
+                },
+            ),
         },
         _ => (false, false),
     }
@@ -0,0 +1,85 @@
+use {
+    super::AlterError,
+    crate::{
+        ast::{AstLiteral, ColumnDef, Expr, Function},
+        data::{Schema, SchemaSequence},
+        result::Result,
+        store::{GStore, GStoreMut},
+    },
+};
+
+pub async fn create_sequence<T: GStore + GStoreMut>(
+    storage: &mut T,
+    name: &str,
+    sequence: SchemaSequence,
+    if_not_exists: bool,
+) -> Result<()> {
+    if storage.fetch_schema(name).await?.is_some() {
+        return match if_not_exists {
+            true => Ok(()),
+            false => Err(AlterError::TableAlreadyExists(name.to_owned()).into()),
+        };
+    }
+
+    let schema = Schema {
+        table_name: name.to_owned(),
+        column_defs: Some(SchemaSequence::column_defs()),
+        indexes: Vec::new(),
+        engine: None,
+        foreign_keys: Vec::new(),
+        checks: Vec::new(),
+        uniques: Vec::new(),
+        comment: None,
+        view: None,
+        triggers: Vec::new(),
+        sequence: Some(sequence),
+    };
+
+    storage.insert_schema(&schema).await
+}
+
+pub async fn drop_sequence<T: GStore + GStoreMut>(
+    storage: &mut T,
+    names: &[String],
+    if_exists: bool,
+) -> Result<usize> {
+    let mut n = 0;
+
+    for name in names {
+        match storage.fetch_schema(name).await? {
+            Some(Schema {
+                sequence: Some(_), ..
+            }) => {}
+            _ if if_exists => continue,
+            _ => return Err(AlterError::SequenceNotFound(name.to_owned()).into()),
+        }
+
+        storage.delete_schema(name).await?;
+
+        n += 1;
+    }
+
+    Ok(n)
+}
+
+/// Returns the sequences the `SERIAL` columns of the table draw their values from.
+pub fn serial_sequence_names(table_name: &str, column_defs: &[ColumnDef]) -> Vec<String> {
+    column_defs
+        .iter()
+        .filter_map(|ColumnDef { name, default, .. }| {
+            let serial_name = SchemaSequence::serial_name(table_name, name);
+            let Some(Expr::Function(function)) = default else {
+                return None;
+            };
+
+            match function.as_ref() {
+                Function::NextVal(Expr::Literal(AstLiteral::QuotedString(name)))
+                    if *name == serial_name =>
+                {
+                    Some(serial_name)
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
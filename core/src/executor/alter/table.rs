@@ -1,10 +1,16 @@
 use {
-    super::{AlterError, validate, validate_column_names},
+    super::{
+        AlterError,
+        sequence::{create_sequence, serial_sequence_names},
+        validate, validate_check, validate_column_names, validate_unique,
+        view::drop_dependent_views,
+    },
     crate::{
         ast::{
-            ColumnDef, ColumnUniqueOption, ForeignKey, Query, SetExpr, TableFactor, ToSql, Values,
+            CheckConstraint, ColumnDef, ColumnUniqueOption, FakeColumn, FakeKind, ForeignKey,
+            Query, ReferentialAction, SetExpr, TableFactor, ToSql, UniqueConstraint, Values,
         },
-        data::Schema,
+        data::{Schema, SchemaSequence},
         executor::{evaluate_stateless, select::select},
         prelude::{DataType, Value},
         result::Result,
@@ -22,6 +28,8 @@ pub struct CreateTableOptions<'a> {
     pub source: &'a Option<Box<Query>>,
     pub engine: &'a Option<String>,
     pub foreign_keys: &'a Vec<ForeignKey>,
+    pub checks: &'a Vec<CheckConstraint>,
+    pub uniques: &'a Vec<UniqueConstraint>,
     pub comment: &'a Option<String>,
 }
 
@@ -34,11 +42,18 @@ pub async fn create_table<T: GStore + GStoreMut>(
         source,
         engine,
         foreign_keys,
+        checks,
+        uniques,
         comment,
     }: CreateTableOptions<'_>,
 ) -> Result<()> {
-    let target_columns_defs = match source.as_deref() {
-        Some(Query { body, .. }) => match body {
+    let mut body = source.as_deref().map(|Query { body, .. }| body);
+    while let Some(SetExpr::SetOperation { left, .. }) = body {
+        body = Some(left);
+    }
+
+    let target_columns_defs = match body {
+        Some(body) => match body {
             SetExpr::Select(select_query) => match &select_query.from.relation {
                 TableFactor::Table { name, .. } => {
                     let schema = storage.fetch_schema(name).await?;
@@ -62,6 +77,28 @@ pub async fn create_table<T: GStore + GStoreMut>(
 
                     Some(vec![column_def])
                 }
+                TableFactor::Fake { columns, .. } => {
+                    let column_defs = columns
+                        .iter()
+                        .map(|FakeColumn { name, kind }| ColumnDef {
+                            name: name.clone(),
+                            data_type: match kind {
+                                FakeKind::Sequence | FakeKind::Integer { .. } => DataType::Int,
+                                FakeKind::Name | FakeKind::Email => DataType::Text,
+                                FakeKind::Float { .. } => DataType::Float,
+                                FakeKind::Boolean => DataType::Boolean,
+                                FakeKind::Date { .. } => DataType::Date,
+                                FakeKind::Uuid => DataType::Uuid,
+                            },
+                            nullable: false,
+                            default: None,
+                            unique: None,
+                            comment: None,
+                        })
+                        .collect();
+
+                    Some(column_defs)
+                }
                 _ => {
                     return Err(AlterError::Unreachable.into());
                 }
@@ -106,6 +143,9 @@ pub async fn create_table<T: GStore + GStoreMut>(
 
                 Some(column_defs)
             }
+            SetExpr::SetOperation { .. } => {
+                return Err(AlterError::Unreachable.into());
+            }
         },
         None if column_defs.is_some() => column_defs.map(<[ColumnDef]>::to_vec),
         None => None,
@@ -119,11 +159,21 @@ pub async fn create_table<T: GStore + GStoreMut>(
         }
     }
 
+    for check in checks {
+        validate_check(check, target_columns_defs.as_deref())?;
+    }
+
+    for unique in uniques {
+        validate_unique(unique, target_columns_defs.as_deref())?;
+    }
+
     for foreign_key in foreign_keys {
         let ForeignKey {
             referencing_column_name,
             referenced_table_name,
             referenced_column_name,
+            on_delete,
+            on_update,
             ..
         } = foreign_key;
 
@@ -140,14 +190,17 @@ pub async fn create_table<T: GStore + GStoreMut>(
             referenced_schema.column_defs
         };
 
+        let column_defs = column_defs.unwrap_or_default();
         let referenced_column_def = column_defs
-            .and_then(|column_defs| {
-                column_defs
-                    .into_iter()
-                    .find(|column_def| column_def.name == *referenced_column_name)
-            })
+            .iter()
+            .find(|column_def| column_def.name == *referenced_column_name)
             .ok_or_else(|| AlterError::ReferencedColumnNotFound(referenced_column_name.to_owned()))?
             .to_owned();
+        let is_composite_primary_key = column_defs
+            .iter()
+            .filter(|column_def| column_def.unique == Some(ColumnUniqueOption { is_primary: true }))
+            .nth(1)
+            .is_some();
 
         let referencing_column_def = target_columns_defs
             .as_deref()
@@ -170,23 +223,48 @@ pub async fn create_table<T: GStore + GStoreMut>(
             .into());
         }
 
-        if referenced_column_def.unique != Some(ColumnUniqueOption { is_primary: true }) {
+        if referenced_column_def.unique != Some(ColumnUniqueOption { is_primary: true })
+            || is_composite_primary_key
+        {
             return Err(AlterError::ReferencingNonPKColumn {
                 referenced_table: referenced_table_name.to_owned(),
                 referenced_column: referenced_column_name.to_owned(),
             }
             .into());
         }
+
+        let set_null = [on_delete, on_update]
+            .into_iter()
+            .any(|action| action == &ReferentialAction::SetNull);
+        if set_null && !referencing_column_def.nullable {
+            return Err(
+                AlterError::SetNullOnNotNullColumn(referencing_column_name.to_owned()).into(),
+            );
+        }
     }
 
     if storage.fetch_schema(target_table_name).await?.is_none() {
+        let sequence = SchemaSequence {
+            increment: 1,
+            start: 1,
+        };
+        let column_defs = target_columns_defs.as_deref().unwrap_or_default();
+        for name in serial_sequence_names(target_table_name, column_defs) {
+            create_sequence(storage, &name, sequence, false).await?;
+        }
+
         let schema = Schema {
             table_name: target_table_name.to_owned(),
             column_defs: target_columns_defs,
             indexes: vec![],
             engine: engine.clone(),
             foreign_keys: foreign_keys.clone(),
+            checks: checks.clone(),
+            uniques: uniques.clone(),
             comment: comment.clone(),
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
         };
 
         storage.insert_schema(&schema).await?;
@@ -222,15 +300,28 @@ pub async fn drop_table<T: GStore + GStoreMut>(
     for table_name in table_names {
         let schema = storage.fetch_schema(table_name).await?;
 
-        match (schema, if_exists) {
+        let column_defs = match (schema, if_exists) {
             (None, true) => {
                 continue;
             }
             (None, false) => {
                 return Err(AlterError::TableNotFound(table_name.to_owned()).into());
             }
-            _ => {}
-        }
+            (Some(Schema { view: Some(_), .. }), _) => {
+                return Err(AlterError::DropTableOnView(table_name.to_owned()).into());
+            }
+            (
+                Some(Schema {
+                    sequence: Some(_), ..
+                }),
+                _,
+            ) => {
+                return Err(AlterError::DropTableOnSequence(table_name.to_owned()).into());
+            }
+            (Some(Schema { column_defs, .. }), _) => column_defs.unwrap_or_default(),
+        };
+
+        drop_dependent_views(storage, table_name, cascade).await?;
 
         let referencings = storage.fetch_referencings(table_name).await?;
 
@@ -258,6 +349,10 @@ pub async fn drop_table<T: GStore + GStoreMut>(
         }
         storage.delete_schema(table_name).await?;
 
+        for name in serial_sequence_names(table_name, &column_defs) {
+            storage.delete_schema(&name).await?;
+        }
+
         n += 1;
     }
 
@@ -283,7 +378,7 @@ impl fmt::Display for Referencing {
 
 #[cfg(test)]
 mod tests {
-    use {super::*, crate::ast::ReferentialAction};
+    use super::*;
 
     #[test]
     fn test_referencing_display() {
@@ -0,0 +1,115 @@
+use {
+    super::AlterError,
+    crate::{
+        ast::Statement,
+        data::{Schema, SchemaTrigger},
+        parse_sql::parse,
+        result::Result,
+        store::{GStore, GStoreMut},
+        translate::translate,
+    },
+    futures::stream::TryStreamExt,
+    std::collections::{BTreeSet, HashMap},
+};
+
+pub async fn create_trigger<T: GStore + GStoreMut>(
+    storage: &mut T,
+    table_name: &str,
+    trigger: SchemaTrigger,
+    or_replace: bool,
+) -> Result<()> {
+    let mut schema = fetch_table_schema(storage, table_name).await?;
+
+    match schema.triggers.iter().position(|t| t.name == trigger.name) {
+        Some(_) if !or_replace => {
+            return Err(AlterError::TriggerAlreadyExists(trigger.name).into());
+        }
+        Some(i) => schema.triggers[i] = trigger,
+        None => schema.triggers.push(trigger),
+    }
+
+    let mut written_tables = storage
+        .fetch_all_schemas()
+        .await?
+        .into_iter()
+        .filter(|schema| schema.table_name != table_name)
+        .chain(std::iter::once(schema.clone()))
+        .map(|schema| Ok((schema.table_name, written_table_names(&schema.triggers)?)))
+        .collect::<Result<HashMap<_, _>>>()?;
+
+    let mut table_names = written_tables.remove(table_name).unwrap_or_default();
+    let mut visited = BTreeSet::new();
+    while let Some(written_table_name) = table_names.pop() {
+        if written_table_name == table_name {
+            return Err(AlterError::TriggerFiresItself(table_name.to_owned()).into());
+        }
+        if !visited.insert(written_table_name.clone()) {
+            continue;
+        }
+
+        if let Some(next) = written_tables.get(&written_table_name) {
+            table_names.extend(next.iter().cloned());
+        }
+    }
+
+    replace_schema(storage, &schema).await
+}
+
+pub async fn drop_trigger<T: GStore + GStoreMut>(
+    storage: &mut T,
+    table_name: &str,
+    trigger_name: &str,
+    if_exists: bool,
+) -> Result<()> {
+    let mut schema = fetch_table_schema(storage, table_name).await?;
+
+    match schema.triggers.iter().position(|t| t.name == trigger_name) {
+        Some(i) => {
+            schema.triggers.remove(i);
+        }
+        None if if_exists => return Ok(()),
+        None => return Err(AlterError::TriggerNotFound(trigger_name.to_owned()).into()),
+    }
+
+    replace_schema(storage, &schema).await
+}
+
+async fn fetch_table_schema<T: GStore>(storage: &T, table_name: &str) -> Result<Schema> {
+    match storage.fetch_schema(table_name).await? {
+        Some(Schema { view: Some(_), .. }) => {
+            Err(AlterError::TriggerOnView(table_name.to_owned()).into())
+        }
+        Some(schema) => Ok(schema),
+        None => Err(AlterError::TableNotFound(table_name.to_owned()).into()),
+    }
+}
+
+/// Returns the tables the bodies of the triggers modify.
+fn written_table_names(triggers: &[SchemaTrigger]) -> Result<Vec<String>> {
+    let mut table_names = Vec::new();
+    for sql in triggers.iter().flat_map(|trigger| trigger.body.iter()) {
+        for sql_statement in parse(sql)? {
+            match translate(&sql_statement)? {
+                Statement::Insert { table_name, .. }
+                | Statement::Update { table_name, .. }
+                | Statement::Delete { table_name, .. } => table_names.push(table_name),
+                _ => {}
+            }
+        }
+    }
+
+    Ok(table_names)
+}
+
+/// Stores the schema of an existing table, keeping its rows which some storages drop when
+/// the schema is inserted again.
+async fn replace_schema<T: GStore + GStoreMut>(storage: &mut T, schema: &Schema) -> Result<()> {
+    let rows = storage
+        .scan_data(&schema.table_name)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    storage.insert_schema(schema).await?;
+    storage.insert_data(&schema.table_name, rows).await
+}
@@ -0,0 +1,140 @@
+use {
+    super::AlterError,
+    crate::{
+        ast::Privilege,
+        data::{Password, User},
+        result::Result,
+        store::{GStore, GStoreMut},
+    },
+};
+
+pub async fn create_user<T: GStore + GStoreMut>(
+    storage: &mut T,
+    name: &str,
+    password: Option<&str>,
+    superuser: bool,
+    if_not_exists: bool,
+) -> Result<()> {
+    if storage.fetch_user(name).await?.is_some() {
+        return match if_not_exists {
+            true => Ok(()),
+            false => Err(AlterError::UserAlreadyExists(name.to_owned()).into()),
+        };
+    }
+
+    let user = User::new(name.to_owned(), password.map(Password::new), superuser);
+
+    storage.insert_user(user).await
+}
+
+pub async fn drop_users<T: GStore + GStoreMut>(
+    storage: &mut T,
+    names: &[String],
+    if_exists: bool,
+) -> Result<()> {
+    for name in names {
+        let user = storage.fetch_user(name).await?;
+
+        if !if_exists {
+            user.ok_or_else(|| AlterError::UserNotFound(name.to_owned()))?;
+        }
+
+        storage.delete_user(name).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn grant<T: GStore + GStoreMut>(
+    storage: &mut T,
+    privileges: &[Privilege],
+    table_names: &[String],
+    grantees: &[String],
+) -> Result<()> {
+    let mut users = fetch_grantees(storage, grantees).await?;
+    validate_privileges(storage, privileges, table_names).await?;
+
+    for user in users.iter_mut() {
+        for table_name in table_names {
+            for privilege in privileges {
+                user.grant(table_name, privilege);
+            }
+        }
+    }
+
+    for user in users {
+        storage.insert_user(user).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn revoke<T: GStore + GStoreMut>(
+    storage: &mut T,
+    privileges: &[Privilege],
+    table_names: &[String],
+    grantees: &[String],
+) -> Result<()> {
+    let mut users = fetch_grantees(storage, grantees).await?;
+    validate_privileges(storage, privileges, table_names).await?;
+
+    for user in users.iter_mut() {
+        for table_name in table_names {
+            for privilege in privileges {
+                user.revoke(table_name, privilege);
+            }
+        }
+    }
+
+    for user in users {
+        storage.insert_user(user).await?;
+    }
+
+    Ok(())
+}
+
+async fn fetch_grantees<T: GStore>(storage: &T, grantees: &[String]) -> Result<Vec<User>> {
+    let mut users = Vec::with_capacity(grantees.len());
+
+    for name in grantees {
+        let user = storage
+            .fetch_user(name)
+            .await?
+            .ok_or_else(|| AlterError::UserNotFound(name.to_owned()))?;
+
+        users.push(user);
+    }
+
+    Ok(users)
+}
+
+async fn validate_privileges<T: GStore>(
+    storage: &T,
+    privileges: &[Privilege],
+    table_names: &[String],
+) -> Result<()> {
+    for table_name in table_names {
+        let schema = storage
+            .fetch_schema(table_name)
+            .await?
+            .ok_or_else(|| AlterError::TableNotFound(table_name.to_owned()))?;
+        let Some(column_defs) = schema.column_defs else {
+            continue;
+        };
+
+        let columns = privileges
+            .iter()
+            .filter_map(|privilege| privilege.columns.as_ref())
+            .flatten();
+        for column in columns {
+            if !column_defs
+                .iter()
+                .any(|column_def| &column_def.name == column)
+            {
+                return Err(AlterError::ColumnNotFound(format!("{table_name}.{column}")).into());
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,8 +1,12 @@
 use {
     super::AlterError,
     crate::{
-        ast::{ColumnDef, ColumnUniqueOption, DataType, OperateFunctionArg},
-        executor::evaluate_stateless,
+        ast::{
+            CheckConstraint, ColumnDef, ColumnUniqueOption, DataType, Expr, OperateFunctionArg,
+            UniqueConstraint,
+        },
+        executor::{evaluate_stateless, is_sequence_function},
+        plan::PlanExpr,
         result::Result,
     },
 };
@@ -27,7 +31,7 @@ pub async fn validate(column_def: &ColumnDef) -> Result<()> {
         .into());
     }
 
-    if let Some(expr) = default {
+    if let Some(expr) = default.as_ref().filter(|expr| !is_sequence_function(expr)) {
         evaluate_stateless(None, expr).await?;
     }
 
@@ -52,6 +56,79 @@ pub fn validate_column_names(column_defs: &[ColumnDef]) -> Result<()> {
     }
 }
 
+/// Validates the `CHECK` constraint reads only columns of the table, without subqueries,
+/// aggregates or sequence functions, so it can be evaluated against each row by itself.
+pub fn validate_check(check: &CheckConstraint, column_defs: Option<&[ColumnDef]>) -> Result<()> {
+    let CheckConstraint { name, expr } = check;
+    let mut columns = Vec::new();
+    if !check_columns(expr, &mut columns) {
+        return Err(AlterError::UnsupportedCheckExpr(name.to_owned()).into());
+    }
+
+    let column_defs = column_defs.unwrap_or_default();
+    match columns.into_iter().find(|column| {
+        column_defs
+            .iter()
+            .all(|column_def| &column_def.name != column)
+    }) {
+        Some(column) => Err(AlterError::CheckColumnNotFound {
+            check: name.to_owned(),
+            column: column.to_owned(),
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Validates the columns of the `UNIQUE` constraint are columns of the table.
+pub fn validate_unique(unique: &UniqueConstraint, column_defs: Option<&[ColumnDef]>) -> Result<()> {
+    let UniqueConstraint { name, columns } = unique;
+    let column_defs = column_defs.unwrap_or_default();
+
+    match columns.iter().find(|column| {
+        column_defs
+            .iter()
+            .all(|column_def| &column_def.name != *column)
+    }) {
+        Some(column) => Err(AlterError::UniqueColumnNotFound {
+            unique: name.to_owned(),
+            column: column.to_owned(),
+        }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Collects the columns the `CHECK` expression reads, returning false on what cannot be
+/// evaluated against a single row.
+pub fn check_columns<'a>(expr: &'a Expr, columns: &mut Vec<&'a str>) -> bool {
+    if matches!(expr, Expr::Aggregate(_)) || is_sequence_function(expr) {
+        return false;
+    }
+
+    match PlanExpr::from(expr) {
+        PlanExpr::None => true,
+        PlanExpr::Identifier(ident) => {
+            columns.push(ident);
+
+            true
+        }
+        PlanExpr::Expr(expr) => check_columns(expr, columns),
+        PlanExpr::TwoExprs(expr, expr2) => {
+            check_columns(expr, columns) && check_columns(expr2, columns)
+        }
+        PlanExpr::ThreeExprs(expr, expr2, expr3) => {
+            check_columns(expr, columns)
+                && check_columns(expr2, columns)
+                && check_columns(expr3, columns)
+        }
+        PlanExpr::MultiExprs(exprs) => exprs.into_iter().all(|expr| check_columns(expr, columns)),
+        PlanExpr::CompoundIdentifier { .. }
+        | PlanExpr::Query(_)
+        | PlanExpr::QueryAndExpr { .. } => false,
+    }
+}
+
 pub fn validate_arg_names(args: &[OperateFunctionArg]) -> Result<()> {
     let duplicate_arg_name = args
         .iter()
@@ -0,0 +1,250 @@
+use {
+    super::{AlterError, validate_column_names},
+    crate::{
+        ast::{ColumnDef, Query, TableFactor},
+        data::{Schema, SchemaView, Value},
+        executor::select::select_with_labels,
+        plan::expand_views,
+        prelude::DataType,
+        result::Result,
+        store::{DataRow, GStore, GStoreMut},
+        translate::visit_query,
+    },
+    futures::stream::TryStreamExt,
+    std::collections::BTreeSet,
+};
+
+pub async fn create_view<T: GStore + GStoreMut>(
+    storage: &mut T,
+    view_name: &str,
+    query: &Query,
+    or_replace: bool,
+    materialized: bool,
+) -> Result<()> {
+    let replaced = match storage.fetch_schema(view_name).await? {
+        Some(Schema { view: Some(_), .. }) if or_replace => true,
+        Some(_) => return Err(AlterError::TableAlreadyExists(view_name.to_owned()).into()),
+        None => false,
+    };
+
+    let mut table_names = read_table_names(query);
+    let mut visited = BTreeSet::new();
+    while let Some(table_name) = table_names.pop() {
+        if table_name == view_name {
+            return Err(AlterError::ViewReadsItself(view_name.to_owned()).into());
+        }
+        if !visited.insert(table_name.clone()) {
+            continue;
+        }
+
+        let schema = storage
+            .fetch_schema(&table_name)
+            .await?
+            .ok_or_else(|| AlterError::TableNotFound(table_name.clone()))?;
+        if let Some(SchemaView { query, .. }) = schema.view {
+            table_names.extend(read_table_names(&query));
+        }
+    }
+
+    let (column_defs, rows) = match materialized {
+        true => {
+            let (column_defs, rows) = materialize(storage, query).await?;
+            (column_defs, Some(rows))
+        }
+        false => (None, None),
+    };
+
+    if replaced {
+        storage.delete_schema(view_name).await?;
+    }
+
+    let schema = Schema {
+        table_name: view_name.to_owned(),
+        column_defs,
+        indexes: Vec::new(),
+        engine: None,
+        foreign_keys: Vec::new(),
+        checks: Vec::new(),
+        uniques: Vec::new(),
+        comment: None,
+        view: Some(SchemaView {
+            query: query.clone(),
+            materialized,
+        }),
+        triggers: Vec::new(),
+        sequence: None,
+    };
+    storage.insert_schema(&schema).await?;
+
+    match rows {
+        Some(rows) => storage.append_data(view_name, rows).await,
+        None => Ok(()),
+    }
+}
+
+/// Replaces the rows of the materialized view with the current rows of its query, returning
+/// their number.
+pub async fn refresh_materialized_view<T: GStore + GStoreMut>(
+    storage: &mut T,
+    view_name: &str,
+) -> Result<usize> {
+    let not_found = || AlterError::MaterializedViewNotFound(view_name.to_owned());
+    let Some(mut schema) = storage.fetch_schema(view_name).await? else {
+        return Err(not_found().into());
+    };
+    let Some(SchemaView {
+        query,
+        materialized: true,
+    }) = &schema.view
+    else {
+        return Err(not_found().into());
+    };
+
+    let (column_defs, rows) = materialize(storage, query).await?;
+
+    let keys = storage
+        .scan_data(view_name)
+        .await?
+        .map_ok(|(key, _)| key)
+        .try_collect::<Vec<_>>()
+        .await?;
+    storage.delete_data(view_name, keys).await?;
+
+    if schema.column_defs != column_defs {
+        schema.column_defs = column_defs;
+        storage.insert_schema(&schema).await?;
+    }
+
+    let n = rows.len();
+    storage.append_data(view_name, rows).await?;
+
+    Ok(n)
+}
+
+/// Runs the query of a materialized view, typing each column after its first non-null value.
+async fn materialize<T: GStore>(
+    storage: &T,
+    query: &Query,
+) -> Result<(Option<Vec<ColumnDef>>, Vec<DataRow>)> {
+    let mut query = query.clone();
+    expand_views(storage, &mut query).await?;
+
+    let (labels, rows) = select_with_labels(storage, &query, None).await?;
+    let rows = rows.map_ok(DataRow::from).try_collect::<Vec<_>>().await?;
+
+    let column_defs = labels
+        .map(|labels| {
+            let column_defs = labels
+                .into_iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    let data_type = rows
+                        .iter()
+                        .find_map(|row| match row {
+                            DataRow::Vec(values) => values.get(i).and_then(Value::get_type),
+                            DataRow::Map(_) => None,
+                        })
+                        .unwrap_or(DataType::Text);
+
+                    ColumnDef {
+                        name,
+                        data_type,
+                        nullable: true,
+                        default: None,
+                        unique: None,
+                        comment: None,
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            validate_column_names(&column_defs).map(|_| column_defs)
+        })
+        .transpose()?;
+
+    Ok((column_defs, rows))
+}
+
+pub async fn drop_view<T: GStore + GStoreMut>(
+    storage: &mut T,
+    view_names: &[String],
+    if_exists: bool,
+    cascade: bool,
+) -> Result<usize> {
+    let mut n = 0;
+
+    for view_name in view_names {
+        match storage.fetch_schema(view_name).await? {
+            Some(Schema { view: Some(_), .. }) => {}
+            _ if if_exists => continue,
+            _ => return Err(AlterError::ViewNotFound(view_name.to_owned()).into()),
+        }
+
+        drop_dependent_views(storage, view_name, cascade).await?;
+        storage.delete_schema(view_name).await?;
+
+        n += 1;
+    }
+
+    Ok(n)
+}
+
+/// Drops the views reading the table or view, directly or through other views, failing
+/// unless `cascade` when there are any.
+pub async fn drop_dependent_views<T: GStore + GStoreMut>(
+    storage: &mut T,
+    table_name: &str,
+    cascade: bool,
+) -> Result<()> {
+    let mut dependents = dependent_views(storage, table_name).await?;
+    if dependents.is_empty() {
+        return Ok(());
+    } else if !cascade {
+        return Err(AlterError::CannotDropWithDependentViews {
+            name: table_name.to_owned(),
+            views: dependents,
+        }
+        .into());
+    }
+
+    let mut views = BTreeSet::new();
+    while let Some(view_name) = dependents.pop() {
+        if views.insert(view_name.clone()) {
+            dependents.extend(dependent_views(storage, &view_name).await?);
+        }
+    }
+
+    for view_name in views {
+        storage.delete_schema(&view_name).await?;
+    }
+
+    Ok(())
+}
+
+async fn dependent_views<T: GStore>(storage: &T, table_name: &str) -> Result<Vec<String>> {
+    Ok(storage
+        .fetch_all_schemas()
+        .await?
+        .into_iter()
+        .filter_map(|schema| {
+            let SchemaView { query, .. } = schema.view?;
+
+            read_table_names(&query)
+                .iter()
+                .any(|name| name == table_name)
+                .then_some(schema.table_name)
+        })
+        .collect())
+}
+
+/// Returns the tables and views the query reads, without expanding the views.
+fn read_table_names(query: &Query) -> Vec<String> {
+    let mut query = query.clone();
+    let mut table_names = Vec::new();
+    visit_query(&mut query, &mut |table_factor| {
+        if let TableFactor::Table { name, .. } = table_factor {
+            table_names.push(name.clone());
+        }
+    });
+
+    table_names
+}
@@ -0,0 +1,128 @@
+use {
+    super::ExecuteError,
+    crate::{
+        chrono::Utc,
+        data::{ColumnStatistics, Key, Schema, TableStatistics, Value},
+        executor::Payload,
+        result::Result,
+        store::{DataRow, GStore, GStoreMut},
+    },
+    futures::stream::TryStreamExt,
+    std::collections::BTreeSet,
+};
+
+/// Collects the statistics of `ANALYZE` for the planner and stores them, one row per column
+/// of each analyzed table.
+pub async fn analyze<T: GStore + GStoreMut>(
+    storage: &mut T,
+    table_name: Option<&str>,
+) -> Result<Payload> {
+    let schemas = match table_name {
+        Some(table_name) => vec![
+            storage
+                .fetch_schema(table_name)
+                .await?
+                .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?,
+        ],
+        None => storage.fetch_all_schemas().await?,
+    };
+
+    let mut statistics = Vec::new();
+    for schema in &schemas {
+        let has_rows =
+            schema.sequence.is_none() && schema.view.as_ref().is_none_or(|view| view.materialized);
+
+        if has_rows {
+            statistics.push(analyze_table(storage, schema).await?);
+        }
+    }
+
+    let mut rows = Vec::new();
+    for table in statistics {
+        let row = |column: Option<&ColumnStatistics>| {
+            let name = column.map(|column| Value::Str(column.name.to_owned()));
+            let count = |count: usize| Value::I64(count as i64);
+            let value = |value: Option<&Value>| value.cloned().unwrap_or(Value::Null);
+
+            vec![
+                Value::Str(table.table_name.to_owned()),
+                count(table.row_count),
+                name.unwrap_or(Value::Null),
+                column.map_or(Value::Null, |column| count(column.distinct_count)),
+                column.map_or(Value::Null, |column| count(column.null_count)),
+                value(column.and_then(|column| column.min.as_ref())),
+                value(column.and_then(|column| column.max.as_ref())),
+            ]
+        };
+
+        match table.columns.is_empty() {
+            true => rows.push(row(None)),
+            false => rows.extend(table.columns.iter().map(Some).map(row)),
+        }
+
+        storage.insert_statistics(table).await?;
+    }
+
+    Ok(Payload::Select {
+        labels: [
+            "TABLE_NAME",
+            "ROW_COUNT",
+            "COLUMN_NAME",
+            "DISTINCT_COUNT",
+            "NULL_COUNT",
+            "MIN",
+            "MAX",
+        ]
+        .into_iter()
+        .map(ToOwned::to_owned)
+        .collect(),
+        rows,
+    })
+}
+
+async fn analyze_table<T: GStore>(storage: &T, schema: &Schema) -> Result<TableStatistics> {
+    let column_defs = schema.column_defs.as_deref().unwrap_or_default();
+    let mut distinct = vec![BTreeSet::<Key>::new(); column_defs.len()];
+    let mut null_counts = vec![0; column_defs.len()];
+    let mut row_count = 0;
+
+    let mut rows = storage.scan_data(&schema.table_name).await?;
+    while let Some((_, row)) = rows.try_next().await? {
+        row_count += 1;
+
+        let DataRow::Vec(values) = row else {
+            continue;
+        };
+
+        for (i, value) in values.iter().enumerate().take(column_defs.len()) {
+            match value {
+                Value::Null => null_counts[i] += 1,
+                value => {
+                    if let Ok(key) = Key::try_from(value) {
+                        distinct[i].insert(key);
+                    }
+                }
+            }
+        }
+    }
+
+    let columns = column_defs
+        .iter()
+        .zip(distinct)
+        .zip(null_counts)
+        .map(|((column_def, keys), null_count)| ColumnStatistics {
+            name: column_def.name.to_owned(),
+            distinct_count: keys.len(),
+            null_count,
+            min: keys.first().cloned().map(Value::from),
+            max: keys.last().cloned().map(Value::from),
+        })
+        .collect();
+
+    Ok(TableStatistics {
+        table_name: schema.table_name.to_owned(),
+        row_count,
+        columns,
+        analyzed: Utc::now().naive_utc(),
+    })
+}
@@ -0,0 +1,605 @@
+use {
+    crate::{
+        ast::{
+            Action, Assignment, ColumnDef, Expr, IndexItem, JoinConstraint, JoinExecutor,
+            JoinOperator, OnConflict, OnConflictAction, Query, Select, SelectItem, SetExpr,
+            Statement, TableFactor, Values,
+        },
+        data::Schema,
+        plan::PlanExpr,
+        result::Result,
+        store::GStore,
+    },
+    serde::Serialize,
+    std::{
+        collections::{BTreeMap, BTreeSet, HashMap},
+        fmt::Debug,
+    },
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum PrivilegeError {
+    #[error("user does not exist: {0}")]
+    UserNotFound(String),
+
+    #[error("permission denied for user '{user}': {action} on table '{table_name}'")]
+    PermissionDenied {
+        user: String,
+        action: Action,
+        table_name: String,
+    },
+
+    #[error("only a superuser can run this statement, '{0}' is not one")]
+    SuperuserRequired(String),
+}
+
+/// Checks that the user may run `statement`, which may already be planned.
+///
+/// Queries and data modifications need the privileges granted on the tables and columns they
+/// read or write, while schema changes and user management are reserved to superusers.
+pub async fn authorize<T: GStore>(
+    storage: &T,
+    user_name: &str,
+    statement: &Statement,
+) -> Result<()> {
+    let user = storage
+        .fetch_user(user_name)
+        .await?
+        .ok_or_else(|| PrivilegeError::UserNotFound(user_name.to_owned()))?;
+    if user.superuser {
+        return Ok(());
+    }
+
+    let permission_denied = |action: Action, table_name: &str| PrivilegeError::PermissionDenied {
+        user: user_name.to_owned(),
+        action,
+        table_name: table_name.to_owned(),
+    };
+
+    match statement {
+        Statement::Query(_)
+        | Statement::Explain { .. }
+        | Statement::Insert { .. }
+        | Statement::Update { .. }
+        | Statement::Delete { .. } => {}
+        Statement::ShowColumns { table_name }
+        | Statement::ShowIndexes(table_name)
+        | Statement::ShowCreateView(table_name) => {
+            return match user.privileges.contains_key(table_name) {
+                true => Ok(()),
+                false => Err(permission_denied(Action::Select, table_name).into()),
+            };
+        }
+        Statement::StartTransaction
+        | Statement::Commit
+        | Statement::Rollback
+        | Statement::ShowVariable(_) => return Ok(()),
+        _ => return Err(PrivilegeError::SuperuserRequired(user_name.to_owned()).into()),
+    }
+
+    let mut schemas = HashMap::new();
+    for table_name in table_names(statement) {
+        if let Some(schema) = storage.fetch_schema(&table_name).await? {
+            schemas.insert(table_name, schema);
+        }
+    }
+
+    for ((table_name, action), columns) in Required::collect(&schemas, statement).privileges {
+        let columns = columns.map(|columns| {
+            columns
+                .into_iter()
+                .map(ToOwned::to_owned)
+                .collect::<Vec<_>>()
+        });
+
+        if !user.is_granted(table_name, action, columns.as_deref()) {
+            return Err(permission_denied(action, table_name).into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the tables a query, data modification or `CREATE TABLE AS SELECT` reads or writes.
+pub(crate) fn table_names(statement: &Statement) -> BTreeSet<String> {
+    Required::collect(&HashMap::new(), statement).table_names()
+}
+
+/// Relation a column of a query can be read from.
+struct Relation<'a> {
+    alias: &'a str,
+    /// `None` for derived tables, series and dictionaries, which need no privilege.
+    table_name: Option<&'a str>,
+    schema: Option<&'a Schema>,
+}
+
+impl Relation<'_> {
+    fn has_column(&self, column: &str) -> bool {
+        self.schema
+            .and_then(|schema| schema.column_defs.as_ref())
+            .is_some_and(|column_defs| column_defs.iter().any(|def| def.name == column))
+    }
+}
+
+/// Privileges a statement needs, by table and action. The columns are `None` when the
+/// privilege is needed on the whole table, as for schemaless tables whose columns are unknown.
+struct Required<'a> {
+    schemas: &'a HashMap<String, Schema>,
+    scopes: Vec<Vec<Relation<'a>>>,
+    /// Tables of `WITH RECURSIVE` read by their own recursive query, which need no privilege.
+    recursive: Vec<&'a str>,
+    privileges: BTreeMap<(&'a str, Action), Option<BTreeSet<&'a str>>>,
+}
+
+impl<'a> Required<'a> {
+    fn collect(schemas: &'a HashMap<String, Schema>, statement: &'a Statement) -> Self {
+        let mut required = Self {
+            schemas,
+            scopes: Vec::new(),
+            recursive: Vec::new(),
+            privileges: BTreeMap::new(),
+        };
+
+        match statement {
+            Statement::Query(query) | Statement::Explain { query, .. } => required.query(query),
+            Statement::Insert {
+                table_name,
+                columns,
+                source,
+                on_conflict,
+                returning,
+            } => {
+                required.require_any(table_name, Action::Insert);
+                if !columns.is_empty() && required.columns(table_name).is_some() {
+                    for column in columns {
+                        required.require(table_name, Action::Insert, column);
+                    }
+                } else {
+                    required.require_columns(table_name, Action::Insert);
+                }
+
+                required.query(source);
+
+                if let Some(OnConflict {
+                    action:
+                        OnConflictAction::DoUpdate {
+                            assignments,
+                            selection,
+                        },
+                    ..
+                }) = on_conflict
+                {
+                    required.update(table_name, selection.as_ref(), assignments);
+                }
+
+                required.returning(table_name, returning);
+            }
+            Statement::Update {
+                table_name,
+                selection,
+                assignments,
+                returning,
+            } => {
+                required.update(table_name, selection.as_ref(), assignments);
+                required.returning(table_name, returning);
+            }
+            Statement::Delete {
+                table_name,
+                selection,
+                returning,
+            } => {
+                required
+                    .privileges
+                    .insert((table_name, Action::Delete), None);
+                required.modified_table(table_name, selection.as_ref(), |_| {});
+                required.returning(table_name, returning);
+            }
+            Statement::CreateTable {
+                source: Some(source),
+                ..
+            } => required.query(source),
+            _ => {}
+        }
+
+        required
+    }
+
+    fn table_names(&self) -> BTreeSet<String> {
+        self.privileges
+            .keys()
+            .map(|(table_name, _)| table_name.to_string())
+            .collect()
+    }
+
+    fn columns(&self, table_name: &str) -> Option<&'a [ColumnDef]> {
+        self.schemas
+            .get(table_name)
+            .and_then(|schema| schema.column_defs.as_deref())
+    }
+
+    /// Registers the table, needing the privilege on any of its columns unless more are added.
+    fn require_any(&mut self, table_name: &'a str, action: Action) {
+        self.privileges
+            .entry((table_name, action))
+            .or_insert_with(|| Some(BTreeSet::new()));
+    }
+
+    fn require(&mut self, table_name: &'a str, action: Action, column: &'a str) {
+        let Some(columns) = self
+            .privileges
+            .entry((table_name, action))
+            .or_insert_with(|| Some(BTreeSet::new()))
+        else {
+            return;
+        };
+
+        columns.insert(column);
+    }
+
+    /// Needs the privilege on every column of the table, or on the whole table if schemaless.
+    fn require_columns(&mut self, table_name: &'a str, action: Action) {
+        match self.columns(table_name) {
+            Some(column_defs) => {
+                for column_def in column_defs {
+                    self.require(table_name, action, &column_def.name);
+                }
+            }
+            None => {
+                self.privileges.insert((table_name, action), None);
+            }
+        }
+    }
+
+    /// Walks the expressions of `UPDATE` and `DELETE`, whose own table is read without needing
+    /// `SELECT` unless its columns are referenced.
+    fn update(
+        &mut self,
+        table_name: &'a str,
+        selection: Option<&'a Expr>,
+        assignments: &'a [Assignment],
+    ) {
+        self.require_any(table_name, Action::Update);
+        if self.columns(table_name).is_some() {
+            for assignment in assignments {
+                self.require(table_name, Action::Update, &assignment.id);
+            }
+        } else {
+            self.require_columns(table_name, Action::Update);
+        }
+
+        self.modified_table(table_name, selection, |required| {
+            for assignment in assignments {
+                required.expr(&assignment.value);
+            }
+        });
+    }
+
+    /// Walks `RETURNING`, which reads the rows written to the table as `SELECT` would.
+    fn returning(&mut self, table_name: &'a str, returning: &'a [SelectItem]) {
+        if returning.is_empty() {
+            return;
+        }
+
+        if self.columns(table_name).is_none() {
+            self.privileges.insert((table_name, Action::Select), None);
+        }
+
+        self.modified_table(table_name, None, |required| {
+            returning.iter().for_each(|item| required.select_item(item));
+        });
+    }
+
+    fn modified_table(
+        &mut self,
+        table_name: &'a str,
+        selection: Option<&'a Expr>,
+        walk: impl FnOnce(&mut Self),
+    ) {
+        let schema = self.schemas.get(table_name);
+        if selection.is_some() && self.columns(table_name).is_none() {
+            self.privileges.insert((table_name, Action::Select), None);
+        }
+
+        self.scopes.push(vec![Relation {
+            alias: table_name,
+            table_name: Some(table_name),
+            schema,
+        }]);
+        if let Some(selection) = selection {
+            self.expr(selection);
+        }
+        walk(self);
+        self.scopes.pop();
+    }
+
+    fn query(&mut self, query: &'a Query) {
+        let Query {
+            body,
+            order_by,
+            limit,
+            offset,
+        } = query;
+
+        self.set_expr(body);
+
+        for order_by in order_by {
+            self.expr(&order_by.expr);
+        }
+        limit.iter().chain(offset).for_each(|expr| self.expr(expr));
+        self.scopes.pop();
+    }
+
+    /// Walks the body of a query, leaving the relations it reads from in scope.
+    fn set_expr(&mut self, set_expr: &'a SetExpr) {
+        match set_expr {
+            SetExpr::Select(select) => self.select(select),
+            SetExpr::Values(Values(rows)) => {
+                self.scopes.push(Vec::new());
+                rows.iter().flatten().for_each(|expr| self.expr(expr));
+            }
+            SetExpr::SetOperation { left, right, .. } => {
+                for set_expr in [left, right] {
+                    self.set_expr(set_expr);
+                    self.scopes.pop();
+                }
+                self.scopes.push(Vec::new());
+            }
+        }
+    }
+
+    /// Walks the select, leaving its relations in scope for the rest of the query.
+    fn select(&mut self, select: &'a Select) {
+        let Select {
+            projection,
+            from,
+            selection,
+            group_by,
+            having,
+            ..
+        } = select;
+
+        let table_factors = std::iter::once(&from.relation)
+            .chain(from.joins.iter().map(|join| &join.relation))
+            .collect::<Vec<_>>();
+        let relations = table_factors
+            .iter()
+            .map(|table_factor| self.relation(table_factor))
+            .collect();
+        self.scopes.push(relations);
+
+        for table_factor in table_factors {
+            if let TableFactor::Table {
+                name,
+                index: Some(index),
+                ..
+            } = table_factor
+            {
+                self.index(name, index);
+            }
+        }
+
+        for item in projection {
+            self.select_item(item);
+        }
+
+        for join in &from.joins {
+            let (JoinOperator::Inner(constraint) | JoinOperator::LeftOuter(constraint)) =
+                &join.join_operator;
+            if let JoinConstraint::On(expr) = constraint {
+                self.expr(expr);
+            }
+
+            if let JoinExecutor::Hash {
+                key_expr,
+                value_expr,
+                where_clause,
+            }
+            | JoinExecutor::Merge {
+                key_expr,
+                value_expr,
+                where_clause,
+            } = &join.join_executor
+            {
+                self.expr(key_expr);
+                self.expr(value_expr);
+                where_clause.iter().for_each(|expr| self.expr(expr));
+            }
+        }
+
+        selection
+            .iter()
+            .chain(group_by)
+            .chain(having)
+            .for_each(|expr| self.expr(expr));
+    }
+
+    fn relation(&mut self, table_factor: &'a TableFactor) -> Relation<'a> {
+        match table_factor {
+            TableFactor::Table { name, alias, .. } if self.recursive.contains(&name.as_str()) => {
+                Relation {
+                    alias: alias.as_ref().map_or(name, |alias| &alias.name),
+                    table_name: None,
+                    schema: None,
+                }
+            }
+            TableFactor::Table { name, alias, .. } => {
+                self.require_any(name, Action::Select);
+                if self.columns(name).is_none() {
+                    self.privileges.insert((name, Action::Select), None);
+                }
+
+                Relation {
+                    alias: alias.as_ref().map_or(name, |alias| &alias.name),
+                    table_name: Some(name),
+                    schema: self.schemas.get(name),
+                }
+            }
+            TableFactor::Recursive {
+                name,
+                anchor,
+                recursive,
+                alias,
+                ..
+            } => {
+                self.query(anchor);
+                self.recursive.push(name);
+                self.query(recursive);
+                self.recursive.pop();
+
+                Relation {
+                    alias: &alias.name,
+                    table_name: None,
+                    schema: None,
+                }
+            }
+            TableFactor::Derived { subquery, alias } => {
+                self.query(subquery);
+
+                Relation {
+                    alias: &alias.name,
+                    table_name: None,
+                    schema: None,
+                }
+            }
+            TableFactor::Series { alias, size } => {
+                self.expr(size);
+
+                Relation {
+                    alias: &alias.name,
+                    table_name: None,
+                    schema: None,
+                }
+            }
+            TableFactor::Fake {
+                alias, size, seed, ..
+            } => {
+                self.expr(size);
+                if let Some(seed) = seed {
+                    self.expr(seed);
+                }
+
+                Relation {
+                    alias: &alias.name,
+                    table_name: None,
+                    schema: None,
+                }
+            }
+            TableFactor::Dictionary { alias, .. } => Relation {
+                alias: &alias.name,
+                table_name: None,
+                schema: None,
+            },
+        }
+    }
+
+    /// Follows a planned index back to the columns it replaced in the `WHERE` clause.
+    fn index(&mut self, table_name: &'a str, index: &'a IndexItem) {
+        let Some(schema) = self.schemas.get(table_name) else {
+            return;
+        };
+
+        match index {
+            IndexItem::PrimaryKey(expr) => {
+                let primary_keys = schema.column_defs.iter().flatten().filter(|column_def| {
+                    column_def
+                        .unique
+                        .as_ref()
+                        .is_some_and(|unique| unique.is_primary)
+                });
+                for column_def in primary_keys {
+                    self.require(table_name, Action::Select, &column_def.name);
+                }
+
+                self.expr(expr);
+            }
+            IndexItem::NonClustered { name, .. } | IndexItem::FullText { name, .. } => {
+                if let Some(schema_index) = schema.indexes.iter().find(|index| &index.name == name)
+                {
+                    self.scopes.push(vec![Relation {
+                        alias: table_name,
+                        table_name: Some(table_name),
+                        schema: Some(schema),
+                    }]);
+                    for expr in &schema_index.exprs {
+                        self.expr(expr);
+                    }
+                    self.scopes.pop();
+                }
+
+                match index {
+                    IndexItem::NonClustered {
+                        cmp_expr: Some((_, expr)),
+                        ..
+                    }
+                    | IndexItem::FullText { query: expr, .. } => self.expr(expr),
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn select_item(&mut self, item: &'a SelectItem) {
+        match item {
+            SelectItem::Expr { expr, .. } => self.expr(expr),
+            SelectItem::QualifiedWildcard(alias) => self.wildcard(Some(alias)),
+            SelectItem::Wildcard => self.wildcard(None),
+        }
+    }
+
+    fn wildcard(&mut self, alias: Option<&str>) {
+        let Some(scope) = self.scopes.last() else {
+            return;
+        };
+
+        let table_names = scope
+            .iter()
+            .filter(|relation| alias.is_none_or(|alias| relation.alias == alias))
+            .filter_map(|relation| relation.table_name)
+            .collect::<Vec<_>>();
+        for table_name in table_names {
+            self.require_columns(table_name, Action::Select);
+        }
+    }
+
+    fn expr(&mut self, expr: &'a Expr) {
+        match PlanExpr::from(expr) {
+            PlanExpr::None => {}
+            PlanExpr::Identifier(ident) => self.column(None, ident),
+            PlanExpr::CompoundIdentifier { alias, ident } => self.column(Some(alias), ident),
+            PlanExpr::Expr(expr) => self.expr(expr),
+            PlanExpr::TwoExprs(expr, expr2) => {
+                self.expr(expr);
+                self.expr(expr2);
+            }
+            PlanExpr::ThreeExprs(expr, expr2, expr3) => {
+                self.expr(expr);
+                self.expr(expr2);
+                self.expr(expr3);
+            }
+            PlanExpr::MultiExprs(exprs) => exprs.into_iter().for_each(|expr| self.expr(expr)),
+            PlanExpr::Query(query) => self.query(query),
+            PlanExpr::QueryAndExpr { query, expr } => {
+                self.expr(expr);
+                self.query(query);
+            }
+        }
+    }
+
+    /// Resolves the column to the innermost relation providing it. Columns of derived tables
+    /// and schemaless tables are left alone, being covered by their own privileges.
+    fn column(&mut self, alias: Option<&'a str>, ident: &'a str) {
+        let table_name = self.scopes.iter().rev().flatten().find_map(|relation| {
+            let found = match alias {
+                Some(alias) => relation.alias == alias,
+                None => relation.has_column(ident),
+            };
+
+            found.then_some(relation.table_name)
+        });
+
+        if let Some(Some(table_name)) = table_name {
+            self.require(table_name, Action::Select, ident);
+        }
+    }
+}
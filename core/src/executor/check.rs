@@ -0,0 +1,250 @@
+use {
+    super::ExecuteError,
+    crate::{
+        ast::{ColumnDef, ColumnUniqueOption},
+        data::{Key, Schema, Value},
+        executor::Payload,
+        result::Result,
+        store::{DataRow, GStore, RowIter},
+    },
+    futures::stream::TryStreamExt,
+    md5::{Digest, Md5},
+    std::collections::{BTreeMap, HashSet},
+};
+
+/// Runs the checks of `CHECK DATABASE` and `CHECK TABLE`, one row per passed check and per
+/// problem found.
+pub async fn check<T: GStore>(storage: &T, table_name: Option<&str>) -> Result<Payload> {
+    let schemas = match table_name {
+        Some(table_name) => vec![
+            storage
+                .fetch_schema(table_name)
+                .await?
+                .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?,
+        ],
+        None => storage.fetch_all_schemas().await?,
+    };
+
+    let mut rows = Vec::new();
+    for schema in &schemas {
+        check_table(storage, schema, &mut rows).await?;
+    }
+
+    Ok(Payload::Select {
+        labels: ["TABLE_NAME", "CHECK", "STATUS", "DETAIL"]
+            .into_iter()
+            .map(ToOwned::to_owned)
+            .collect(),
+        rows,
+    })
+}
+
+async fn check_table<T: GStore>(
+    storage: &T,
+    schema: &Schema,
+    rows: &mut Vec<Vec<Value>>,
+) -> Result<()> {
+    let table_name = schema.table_name.as_str();
+    let mut report = |check: &str, problems: Vec<String>, detail: Option<String>| {
+        let row = |status: &str, detail: Option<String>| {
+            vec![
+                Value::Str(table_name.to_owned()),
+                Value::Str(check.to_owned()),
+                Value::Str(status.to_owned()),
+                detail.map(Value::Str).unwrap_or(Value::Null),
+            ]
+        };
+
+        match problems.is_empty() {
+            true => rows.push(row("OK", detail)),
+            false => rows.extend(
+                problems
+                    .into_iter()
+                    .map(|problem| row("ERROR", Some(problem))),
+            ),
+        }
+    };
+
+    let data = match scan(storage.scan_data(table_name).await).await {
+        Ok(data) => data,
+        Err(error) => {
+            report("SCAN", vec![error.to_string()], None);
+            return Ok(());
+        }
+    };
+
+    report("ROW_TYPE", check_row_types(schema, &data), None);
+
+    let column_defs = schema.column_defs.as_deref().unwrap_or_default();
+    let primary_key = column_defs
+        .iter()
+        .enumerate()
+        .filter(|(_, ColumnDef { unique, .. })| {
+            unique == &Some(ColumnUniqueOption { is_primary: true })
+        })
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    if !primary_key.is_empty() {
+        report("PRIMARY_KEY", check_primary_key(&primary_key, &data), None);
+    }
+
+    let encoded = data
+        .iter()
+        .map(|(_, row)| encode(row))
+        .collect::<Result<Vec<_>>>()?;
+    for index in &schema.indexes {
+        let indexed = storage
+            .scan_indexed_data(table_name, &index.name, None, None)
+            .await;
+        let problems = match scan(indexed).await {
+            Ok(indexed) => {
+                // a full-text index holds a row once for each of its terms, and not at all
+                // without any
+                let mut keys = HashSet::new();
+                let indexed = indexed
+                    .iter()
+                    .filter(|(key, _)| !index.fulltext || keys.insert(key))
+                    .map(|(_, row)| encode(row))
+                    .collect::<Result<Vec<_>>>()?;
+
+                check_index(&encoded, &indexed, !index.fulltext)
+            }
+            Err(error) => vec![error.to_string()],
+        };
+
+        report(&format!("INDEX {}", index.name), problems, None);
+    }
+
+    report("CHECKSUM", Vec::new(), Some(checksum(encoded)));
+
+    Ok(())
+}
+
+async fn scan(rows: Result<RowIter<'_>>) -> Result<Vec<(Key, DataRow)>> {
+    rows?.try_collect().await
+}
+
+fn check_row_types(schema: &Schema, data: &[(Key, DataRow)]) -> Vec<String> {
+    data.iter()
+        .filter_map(|(key, row)| {
+            let problem = match (&schema.column_defs, row) {
+                (None, DataRow::Map(_)) => return None,
+                (None, DataRow::Vec(_)) => "row with columns in a schemaless table".to_owned(),
+                (Some(_), DataRow::Map(_)) => "schemaless row in a table with columns".to_owned(),
+                (Some(column_defs), DataRow::Vec(values)) if column_defs.len() != values.len() => {
+                    format!("{} values for {} columns", values.len(), column_defs.len())
+                }
+                (Some(column_defs), DataRow::Vec(values)) => column_defs
+                    .iter()
+                    .zip(values)
+                    .find_map(|(column_def, value)| {
+                        let ColumnDef {
+                            name,
+                            data_type,
+                            nullable,
+                            ..
+                        } = column_def;
+
+                        match value.get_type() {
+                            None if !nullable => Some(format!(r#"column "{name}" is NULL"#)),
+                            Some(found) if &found != data_type => Some(format!(
+                                r#"column "{name}" holds {found} instead of {data_type}"#
+                            )),
+                            _ => None,
+                        }
+                    })?,
+            };
+
+            Some(format!("row {}: {problem}", display(key)))
+        })
+        .collect()
+}
+
+fn check_primary_key(columns: &[usize], data: &[(Key, DataRow)]) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut counts = BTreeMap::<Key, usize>::new();
+    for (key, row) in data {
+        let DataRow::Vec(values) = row else {
+            continue;
+        };
+
+        let keys = columns
+            .iter()
+            .map(|i| values.get(*i).map(Key::try_from))
+            .collect::<Option<Result<Vec<_>>>>();
+
+        match keys {
+            None => problems.push(format!("row {}: primary key is NULL", display(key))),
+            Some(Ok(keys)) if keys.contains(&Key::None) => {
+                problems.push(format!("row {}: primary key is NULL", display(key)));
+            }
+            Some(Err(error)) => problems.push(format!("row {}: {error}", display(key))),
+            Some(Ok(mut keys)) => {
+                let primary_key = match keys.len() {
+                    1 => keys.remove(0),
+                    _ => Key::Composite(keys),
+                };
+
+                *counts.entry(primary_key).or_default() += 1;
+            }
+        }
+    }
+
+    problems.extend(
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(key, count)| format!("primary key {} is used by {count} rows", display(&key))),
+    );
+
+    problems
+}
+
+/// Reconciles the rows read through the index with the rows of the table.
+/// Compares the rows of the table with those of the index, which holds them all when
+/// `complete`.
+fn check_index(encoded: &[String], indexed: &[String], complete: bool) -> Vec<String> {
+    let mut counts = BTreeMap::<&str, i64>::new();
+    for row in encoded {
+        *counts.entry(row).or_default() += 1;
+    }
+    for row in indexed {
+        *counts.entry(row).or_default() -= 1;
+    }
+
+    let missing = counts.values().filter(|n| **n > 0).sum::<i64>();
+    let orphaned = -counts.values().filter(|n| **n < 0).sum::<i64>();
+
+    let mut problems = Vec::new();
+    if complete && missing > 0 {
+        problems.push(format!("{missing} rows missing from the index"));
+    }
+    if orphaned > 0 {
+        problems.push(format!("{orphaned} index entries without a row"));
+    }
+
+    problems
+}
+
+/// MD5 of the rows in a storage independent order, so that the checksums of copies of a table
+/// in different storages match.
+fn checksum(mut encoded: Vec<String>) -> String {
+    encoded.sort_unstable();
+
+    let mut hasher = Md5::new();
+    for row in encoded {
+        hasher.update(row.as_bytes());
+        hasher.update(b"\n");
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+fn encode(row: &DataRow) -> Result<String> {
+    serde_json::to_string(row)
+        .map_err(|error| ExecuteError::RowEncodingFailed(error.to_string()).into())
+}
+
+fn display(key: &Key) -> String {
+    String::from(&Value::from(key.clone()))
+}
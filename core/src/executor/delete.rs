@@ -1,16 +1,24 @@
 use {
     super::{
         Payload, Referencing,
+        execute::execute_inner,
         fetch::{fetch, fetch_columns},
+        returning::Returning,
+        trigger::{RowChange, Triggers},
     },
     crate::{
-        ast::{BinaryOperator, Expr, ForeignKey, ReferentialAction},
-        result::{Error, Result},
+        ast::{
+            Assignment, AstLiteral, BinaryOperator, Expr, ForeignKey, ReferentialAction,
+            SelectItem, Statement, TriggerEvent, TriggerTiming,
+        },
+        data::{Key, Row},
+        result::Result,
         store::{GStore, GStoreMut},
     },
-    futures::stream::{StreamExt, TryStreamExt},
+    async_recursion::async_recursion,
+    futures::stream::TryStreamExt,
     serde::Serialize,
-    std::sync::Arc,
+    std::{collections::HashSet, sync::Arc},
     thiserror::Error as ThisError,
 };
 
@@ -23,62 +31,173 @@ pub enum DeleteError {
     ValueNotFound(String),
 }
 
-pub async fn delete<T: GStore + GStoreMut>(
+/// Deletes the rows matching `selection`.
+///
+/// Rows referencing a deleted row through a foreign key make the deletion fail with
+/// `NO ACTION` or `RESTRICT`. With `CASCADE` they are deleted as well, and with `SET NULL` their
+/// referencing column is set to `NULL`, once the rows of this table are deleted.
+#[async_recursion]
+pub async fn delete<T>(
     storage: &mut T,
     table_name: &str,
     selection: &Option<Expr>,
-) -> Result<Payload> {
+    returning: &[SelectItem],
+) -> Result<Payload>
+where
+    T: GStore + GStoreMut,
+{
+    let returning = Returning::new(storage, table_name, returning).await?;
     let columns = fetch_columns(storage, table_name).await?.map(Arc::from);
-    let referencings = storage.fetch_referencings(table_name).await?;
-    let keys = fetch(storage, table_name, columns, selection.as_ref())
+    let triggers = storage
+        .fetch_schema(table_name)
         .await?
-        .into_stream()
-        .then(|item| async {
-            let (key, row) = item?;
-
-            for Referencing {
-                table_name: referencing_table_name,
-                foreign_key:
-                    ForeignKey {
-                        referencing_column_name,
-                        referenced_column_name,
-                        on_delete,
-                        ..
-                    },
-            } in &referencings
-            {
-                let value = row
-                    .get_value(referenced_column_name)
-                    .ok_or(DeleteError::ValueNotFound(referenced_column_name.clone()))?
-                    .clone();
-
-                let expr = &Expr::BinaryOp {
-                    left: Box::new(Expr::Identifier(referencing_column_name.clone())),
-                    op: BinaryOperator::Eq,
-                    right: Box::new(Expr::try_from(value)?),
-                };
-
-                let columns = Some(Arc::from(Vec::new()));
-                let referencing_rows =
-                    fetch(storage, referencing_table_name, columns, Some(expr)).await?;
-
-                let referencing_row_exists = Box::pin(referencing_rows).next().await.is_some();
-                if referencing_row_exists && on_delete == &ReferentialAction::NoAction {
+        .map(|schema| schema.triggers)
+        .unwrap_or_default();
+    let triggers = Triggers::new(triggers, TriggerEvent::Delete);
+    let rows = fetch(storage, table_name, columns, selection.as_ref())
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let statements =
+        referential_statements(storage, table_name, &rows, &mut HashSet::new()).await?;
+
+    let (keys, rows): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+    let returned = returning.map(|returning| (returning, rows.clone()));
+    let changes = match triggers.is_empty() {
+        true => Vec::new(),
+        false => rows
+            .into_iter()
+            .map(|row| RowChange {
+                old: Some(row),
+                new: None,
+            })
+            .collect(),
+    };
+    let num_keys = keys.len();
+
+    triggers
+        .fire(storage, TriggerTiming::Before, &changes)
+        .await?;
+    storage.delete_data(table_name, keys).await?;
+    for statement in &statements {
+        execute_inner(storage, statement, 1).await?;
+    }
+    triggers
+        .fire(storage, TriggerTiming::After, &changes)
+        .await?;
+
+    match returned {
+        Some((returning, rows)) => returning.project(storage, rows).await,
+        None => Ok(Payload::Delete(num_keys)),
+    }
+}
+
+/// Returns the statements carrying out the `CASCADE` and `SET NULL` actions of the rows
+/// referencing the deleted rows.
+///
+/// Fails on rows referencing them with `NO ACTION` or `RESTRICT`, following `CASCADE` down to
+/// the rows it would delete in turn, so the deletion fails before any row is deleted.
+#[async_recursion]
+async fn referential_statements<T>(
+    storage: &T,
+    table_name: &str,
+    rows: &[(Key, Row)],
+    visited: &mut HashSet<(String, Key)>,
+) -> Result<Vec<Statement>>
+where
+    T: GStore,
+{
+    let mut referencings = storage.fetch_referencings(table_name).await?;
+    let self_referencings = storage
+        .fetch_schema(table_name)
+        .await?
+        .map(|schema| schema.foreign_keys)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|foreign_key| foreign_key.referenced_table_name == table_name)
+        .map(|foreign_key| Referencing {
+            table_name: table_name.to_owned(),
+            foreign_key,
+        });
+    referencings.extend(self_referencings);
+
+    let mut statements = Vec::new();
+
+    for (key, row) in rows {
+        if !visited.insert((table_name.to_owned(), key.clone())) {
+            continue;
+        }
+
+        for Referencing {
+            table_name: referencing_table_name,
+            foreign_key:
+                ForeignKey {
+                    referencing_column_name,
+                    referenced_column_name,
+                    on_delete,
+                    ..
+                },
+        } in &referencings
+        {
+            let value = row
+                .get_value(referenced_column_name)
+                .ok_or(DeleteError::ValueNotFound(referenced_column_name.clone()))?
+                .clone();
+
+            let expr = Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(referencing_column_name.clone())),
+                op: BinaryOperator::Eq,
+                right: Box::new(Expr::try_from(value)?),
+            };
+
+            let columns = fetch_columns(storage, referencing_table_name)
+                .await?
+                .map(Arc::from);
+            let referencing_rows = fetch(storage, referencing_table_name, columns, Some(&expr))
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?;
+
+            if referencing_rows.is_empty() {
+                continue;
+            }
+
+            let statement = match on_delete {
+                ReferentialAction::NoAction | ReferentialAction::Restrict => {
                     return Err(DeleteError::ReferencingColumnExists(format!(
                         "{referencing_table_name}.{referencing_column_name}"
                     ))
                     .into());
                 }
-            }
+                ReferentialAction::Cascade => {
+                    referential_statements(
+                        storage,
+                        referencing_table_name,
+                        &referencing_rows,
+                        visited,
+                    )
+                    .await?;
 
-            Ok::<_, Error>(key)
-        })
-        .try_collect::<Vec<_>>()
-        .await?;
-    let num_keys = keys.len();
+                    Statement::Delete {
+                        table_name: referencing_table_name.clone(),
+                        selection: Some(expr),
+                        returning: Vec::new(),
+                    }
+                }
+                ReferentialAction::SetNull => Statement::Update {
+                    table_name: referencing_table_name.clone(),
+                    assignments: vec![Assignment {
+                        id: referencing_column_name.clone(),
+                        value: Expr::Literal(AstLiteral::Null),
+                    }],
+                    selection: Some(expr),
+                    returning: Vec::new(),
+                },
+            };
+
+            statements.push(statement);
+        }
+    }
 
-    storage
-        .delete_data(table_name, keys)
-        .await
-        .map(|_| Payload::Delete(num_keys))
+    Ok(statements)
 }
@@ -7,7 +7,7 @@ use {
     self::function::BreakCase,
     super::{context::RowContext, select::select},
     crate::{
-        ast::{Aggregate, Expr, Function},
+        ast::{Aggregate, Expr, Function, ToSql},
         data::{CustomFunction, Interval, Literal, Row, Value},
         mock::MockStorage,
         result::{Error, Result},
@@ -153,7 +153,10 @@ where
             .and_then(|aggregated| aggregated.get(aggr.as_ref()))
         {
             Some(value) => Ok(Evaluated::Value(value.clone())),
-            None => Err(EvaluateError::UnreachableEmptyAggregateValue(*aggr.clone()).into()),
+            None if aggr.over.is_some() => {
+                Err(EvaluateError::WindowFunctionNotAllowed(aggr.to_sql()).into())
+            }
+            None => Err(EvaluateError::UnreachableEmptyAggregateValue(aggr.clone()).into()),
         },
         Expr::Function(func) => {
             let context = context.as_ref().map(Arc::clone);
@@ -723,6 +726,64 @@ async fn evaluate_function<'a, 'b: 'a, 'c: 'a, T: GStore>(
             f::splice(name, list_data, begin_index, end_index, values)
         }
         Function::Dedup(list) => f::dedup(eval(list).await?),
+        Function::Match { exprs, query } => {
+            let exprs = stream::iter(exprs).then(eval).try_collect().await?;
+            let query = eval(query).await?;
+            f::match_against(name, exprs, query)
+        }
+        Function::RegexpMatches { expr, pattern } => {
+            let expr = eval(expr).await?;
+            let pattern = eval(pattern).await?;
+
+            f::regexp_matches(name, expr, pattern)
+        }
+        Function::RegexpReplace {
+            expr,
+            pattern,
+            replacement,
+        } => {
+            let expr = eval(expr).await?;
+            let pattern = eval(pattern).await?;
+            let replacement = eval(replacement).await?;
+
+            f::regexp_replace(name, expr, pattern, replacement)
+        }
+        Function::RegexpExtract {
+            expr,
+            pattern,
+            group,
+        } => {
+            let expr = eval(expr).await?;
+            let pattern = eval(pattern).await?;
+            let group = match group {
+                Some(group) => Some(eval(group).await?),
+                None => None,
+            };
+
+            f::regexp_extract(name, expr, pattern, group)
+        }
+        Function::JsonExtract { expr, path } => {
+            let expr = eval(expr).await?;
+            let path = eval(path).await?;
+
+            f::json_extract(name, expr, path)
+        }
+        Function::JsonSet { expr, path, value } => {
+            let expr = eval(expr).await?;
+            let path = eval(path).await?;
+            let value = eval(value).await?;
+
+            f::json_set(name, expr, path, value)
+        }
+        Function::JsonRemove { expr, path } => {
+            let expr = eval(expr).await?;
+            let path = eval(path).await?;
+
+            f::json_remove(name, expr, path)
+        }
+        Function::NextVal(_) | Function::CurrVal(_) | Function::SetVal { .. } => {
+            return Err(EvaluateError::SequenceFunctionNotAllowed(name).into());
+        }
     };
 
     match result {
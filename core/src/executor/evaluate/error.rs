@@ -87,10 +87,13 @@ pub enum EvaluateError {
     ContextRequiredForIdentEvaluation(Expr),
 
     #[error("unreachable empty aggregate value: {0:?}")]
-    UnreachableEmptyAggregateValue(Aggregate),
+    UnreachableEmptyAggregateValue(Box<Aggregate>),
+
+    #[error("window function is only allowed in SELECT list and ORDER BY: {0}")]
+    WindowFunctionNotAllowed(String),
 
     #[error("filter context is required for aggregate function: {0:?}")]
-    FilterContextRequiredForAggregate(Aggregate),
+    FilterContextRequiredForAggregate(Box<Aggregate>),
 
     #[error("incompatible bit operation between {0} and {1}")]
     IncompatibleBitOperation(String, String),
@@ -184,6 +187,16 @@ pub enum EvaluateError {
 
     #[error("failed to convert Value to u32: {0}")]
     I64ToU32ConversionFailure(String),
+
+    #[error("{0} is only allowed as a whole value of VALUES or of SELECT without FROM")]
+    SequenceFunctionNotAllowed(String),
+
+    #[error("{name} reads group {group} of a pattern with {groups} group(s)")]
+    RegexGroupOutOfRange {
+        name: String,
+        group: usize,
+        groups: usize,
+    },
 }
 
 fn error_serialize<S>(error: &chrono::format::ParseError, serializer: S) -> Result<S::Ok, S::Error>
@@ -68,6 +68,24 @@ pub fn binary_op<'a>(
         BinaryOperator::BitwiseAnd => l.bitwise_and(&r),
         BinaryOperator::BitwiseShiftLeft => l.bitwise_shift_left(&r),
         BinaryOperator::BitwiseShiftRight => l.bitwise_shift_right(&r),
+        BinaryOperator::RegexMatch => {
+            let l: Value = l.try_into()?;
+            let r: Value = r.try_into()?;
+
+            l.regex_match(&r).map(Evaluated::Value)
+        }
+        BinaryOperator::Arrow => {
+            let l: Value = l.try_into()?;
+            let r: Value = r.try_into()?;
+
+            l.json_field(&r).map(Evaluated::Value)
+        }
+        BinaryOperator::LongArrow => {
+            let l: Value = l.try_into()?;
+            let r: Value = r.try_into()?;
+
+            l.json_field_text(&r).map(Evaluated::Value)
+        }
     }
 }
 
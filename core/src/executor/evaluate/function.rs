@@ -2,12 +2,13 @@ use {
     super::{EvaluateError, Evaluated},
     crate::{
         ast::DateTimeField,
-        data::{Key, Point, Value},
+        data::{Key, Point, StringExt, Value, fulltext},
         result::{Error, Result},
     },
     chrono::{Datelike, Duration, Months},
     md5::{Digest, Md5},
     rand::{Rng, SeedableRng, rngs::StdRng},
+    regex::Regex,
     std::ops::ControlFlow::{self as StdControlFlow, Break, Continue},
     uuid::Uuid,
 };
@@ -115,6 +116,10 @@ fn eval_to_str(name: &str, evaluated: Evaluated<'_>) -> ControlFlow<String> {
     }
 }
 
+fn eval_to_regex(name: &str, evaluated: Evaluated<'_>) -> ControlFlow<Regex> {
+    eval_to_str(name, evaluated)?.to_regex().into_control_flow()
+}
+
 fn eval_to_int(name: &str, evaluated: Evaluated<'_>) -> ControlFlow<i64> {
     match evaluated.try_into().break_if_null()? {
         Value::I64(num) => Continue(num),
@@ -1098,3 +1103,145 @@ pub fn dedup<'a>(list: Evaluated<'_>) -> ControlFlow<Evaluated<'a>> {
         _ => Err(EvaluateError::ListTypeRequired.into()).into_control_flow(),
     }
 }
+
+pub fn match_against<'a>(
+    name: String,
+    exprs: Vec<Evaluated<'_>>,
+    query: Evaluated<'_>,
+) -> ControlFlow<Evaluated<'a>> {
+    let query = eval_to_str(&name, query)?;
+    let mut texts = Vec::with_capacity(exprs.len());
+    for expr in exprs.into_iter().filter(|expr| !expr.is_null()) {
+        texts.push(eval_to_str(&name, expr)?);
+    }
+
+    let relevance = fulltext::relevance(&texts.join(" "), &query);
+
+    Continue(Evaluated::Value(Value::F64(relevance)))
+}
+
+pub fn regexp_matches<'a>(
+    name: String,
+    expr: Evaluated<'_>,
+    pattern: Evaluated<'_>,
+) -> ControlFlow<Evaluated<'a>> {
+    let string = eval_to_str(&name, expr)?;
+    let regex = eval_to_regex(&name, pattern)?;
+
+    let matches = if regex.captures_len() == 1 {
+        regex
+            .find_iter(&string)
+            .map(|found| Value::Str(found.as_str().to_owned()))
+            .collect()
+    } else {
+        regex
+            .captures_iter(&string)
+            .map(|captures| {
+                let groups = captures
+                    .iter()
+                    .skip(1)
+                    .map(|group| group.map_or(Value::Null, |g| Value::Str(g.as_str().to_owned())))
+                    .collect();
+
+                Value::List(groups)
+            })
+            .collect()
+    };
+
+    Continue(Evaluated::Value(Value::List(matches)))
+}
+
+pub fn regexp_replace<'a>(
+    name: String,
+    expr: Evaluated<'_>,
+    pattern: Evaluated<'_>,
+    replacement: Evaluated<'_>,
+) -> ControlFlow<Evaluated<'a>> {
+    let string = eval_to_str(&name, expr)?;
+    let regex = eval_to_regex(&name, pattern)?;
+    let replacement = eval_to_str(&name, replacement)?;
+    let value = regex
+        .replace_all(&string, replacement.as_str())
+        .into_owned();
+
+    Continue(Evaluated::Value(Value::Str(value)))
+}
+
+pub fn regexp_extract<'a>(
+    name: String,
+    expr: Evaluated<'_>,
+    pattern: Evaluated<'_>,
+    group: Option<Evaluated<'_>>,
+) -> ControlFlow<Evaluated<'a>> {
+    let string = eval_to_str(&name, expr)?;
+    let regex = eval_to_regex(&name, pattern)?;
+    let group = match group {
+        Some(group) => eval_to_int(&name, group)
+            .map(usize::try_from)?
+            .map_err(|_| EvaluateError::FunctionRequiresUSizeValue(name.clone()).into())
+            .into_control_flow()?,
+        None => 0,
+    };
+
+    if group >= regex.captures_len() {
+        return Err(EvaluateError::RegexGroupOutOfRange {
+            name,
+            group,
+            groups: regex.captures_len() - 1,
+        }
+        .into())
+        .into_control_flow();
+    }
+
+    match regex
+        .captures(&string)
+        .and_then(|captures| captures.get(group))
+    {
+        Some(found) => Continue(Evaluated::Value(Value::Str(found.as_str().to_owned()))),
+        None => Break(BreakCase::Null),
+    }
+}
+
+pub fn json_extract<'a>(
+    name: String,
+    expr: Evaluated<'_>,
+    path: Evaluated<'_>,
+) -> ControlFlow<Evaluated<'a>> {
+    let document = Value::try_from(expr).break_if_null()?;
+    let path = eval_to_str(&name, path)?;
+
+    document
+        .json_extract(&path)
+        .map(Evaluated::Value)
+        .into_control_flow()
+}
+
+pub fn json_set<'a>(
+    name: String,
+    expr: Evaluated<'_>,
+    path: Evaluated<'_>,
+    value: Evaluated<'_>,
+) -> ControlFlow<Evaluated<'a>> {
+    let document = Value::try_from(expr).break_if_null()?;
+    let path = eval_to_str(&name, path)?;
+    let value = Value::try_from(value).into_control_flow()?;
+
+    document
+        .json_set(&path, value)
+        .map(Evaluated::Value)
+        .into_control_flow()
+}
+
+pub fn json_remove<'a>(
+    name: String,
+    expr: Evaluated<'_>,
+    path: Evaluated<'_>,
+) -> ControlFlow<Evaluated<'a>> {
+    let document = Value::try_from(expr).break_if_null()?;
+    let path = eval_to_str(&name, path)?;
+
+    document
+        .json_remove(&path)
+        .map(Evaluated::Value)
+        .into_control_flow()
+}
@@ -1,32 +1,40 @@
 use {
     super::{
         alter::{
-            CreateTableOptions, alter_table, create_index, create_table, delete_function,
-            drop_table, insert_function,
+            AlterError, CreateTableOptions, alter_table, create_index, create_sequence,
+            create_table, create_trigger, create_user, create_view, delete_function, drop_sequence,
+            drop_table, drop_trigger, drop_users, drop_view, grant, insert_function,
+            refresh_materialized_view, revoke,
         },
+        analyze::analyze,
+        check::check,
         delete::delete,
-        fetch::fetch,
+        explain::explain,
         insert::insert,
-        select::{select, select_with_labels},
-        update::Update,
-        validate::{ColumnValidation, validate_unique},
+        select::{select, select_analyzed, select_with_labels},
+        sequence::{calls_sequence_functions, resolve, resolve_query},
+        update::update,
     },
     crate::{
         ast::{
             AstLiteral, BinaryOperator, DataType, Dictionary, Expr, Query, SelectItem, SetExpr,
             Statement, TableAlias, TableFactor, TableWithJoins, Variable,
         },
-        data::{Key, Row, Schema, Value},
+        data::{Row, Schema, SchemaSequence, SchemaTrigger, Value},
         result::Result,
         store::{GStore, GStoreMut},
     },
-    futures::stream::{StreamExt, TryStreamExt},
+    futures::{
+        Sink, SinkExt,
+        stream::{StreamExt, TryStreamExt},
+    },
     serde::{Deserialize, Serialize},
     std::{
+        borrow::Cow,
         collections::{BTreeMap, HashMap},
         env::var,
         fmt::Debug,
-        sync::Arc,
+        pin::pin,
     },
     thiserror::Error as ThisError,
 };
@@ -35,6 +43,21 @@ use {
 pub enum ExecuteError {
     #[error("table not found: {0}")]
     TableNotFound(String),
+
+    #[error("SET can only run through Glue")]
+    SetVariableOutsideGlue,
+
+    #[error("failed to encode row: {0}")]
+    RowEncodingFailed(String),
+
+    #[error("CURRVAL of sequence {0} is not defined before NEXTVAL")]
+    SequenceValueNotDefined(String),
+
+    #[error("sequence {0} reached the limit of INT")]
+    SequenceOverflow(String),
+
+    #[error("RETURNING * is not supported for schemaless table: {0}")]
+    ReturningWildcardOnSchemalessTable(String),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -47,17 +70,30 @@ pub enum Payload {
         rows: Vec<Vec<Value>>,
     },
     SelectMap(Vec<BTreeMap<String, Value>>),
+    Returning {
+        labels: Vec<String>,
+        rows: Vec<Vec<Value>>,
+    },
     Delete(usize),
     Update(usize),
     DropTable(usize),
+    DropView(usize),
+    DropSequence(usize),
+    RefreshMaterializedView(usize),
     DropFunction,
+    DropUser,
+    Grant,
+    Revoke,
     AlterTable,
     CreateIndex,
     DropIndex,
+    CreateTrigger,
+    DropTrigger,
     StartTransaction,
     Commit,
     Rollback,
     ShowVariable(PayloadVariable),
+    SetVariable,
 }
 
 impl Payload {
@@ -66,7 +102,7 @@ impl Payload {
     /// The items of the Iterator are `HashMap<Column, Value>`, and they are borrowed by default.
     /// If ownership is required, you need to acquire them directly.
     ///
-    /// - Some: [`Payload::Select`], [`Payload::SelectMap`]
+    /// - Some: [`Payload::Select`], [`Payload::SelectMap`], [`Payload::Returning`]
     /// - None: otherwise
     pub fn select(&self) -> Option<impl Iterator<Item = HashMap<&str, &Value>>> {
         #[derive(iter_enum::Iterator)]
@@ -76,13 +112,15 @@ impl Payload {
         }
 
         Some(match self {
-            Payload::Select { labels, rows } => Iter::Schema(rows.iter().map(move |row| {
-                labels
-                    .iter()
-                    .zip(row.iter())
-                    .map(|(label, value)| (label.as_str(), value))
-                    .collect::<HashMap<_, _>>()
-            })),
+            Payload::Select { labels, rows } | Payload::Returning { labels, rows } => {
+                Iter::Schema(rows.iter().map(move |row| {
+                    labels
+                        .iter()
+                        .zip(row.iter())
+                        .map(|(label, value)| (label.as_str(), value))
+                        .collect::<HashMap<_, _>>()
+                }))
+            }
             Payload::SelectMap(rows) => Iter::Schemaless(rows.iter().map(|row| {
                 row.iter()
                     .map(|(k, v)| (k.as_str(), v))
@@ -91,6 +129,16 @@ impl Payload {
             _ => return None,
         })
     }
+
+    /// Returns the number of rows selected, inserted, updated or deleted.
+    pub(crate) fn rows(&self) -> Option<usize> {
+        match self {
+            Payload::Select { rows, .. } | Payload::Returning { rows, .. } => Some(rows.len()),
+            Payload::SelectMap(rows) => Some(rows.len()),
+            Payload::Insert(n) | Payload::Update(n) | Payload::Delete(n) => Some(*n),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -103,16 +151,112 @@ pub enum PayloadVariable {
 pub async fn execute<T: GStore + GStoreMut>(
     storage: &mut T,
     statement: &Statement,
+) -> Result<Payload> {
+    execute_parallel(storage, statement, 1).await
+}
+
+/// Executes the statement as [`execute`] does, splitting the aggregation of its query across
+/// `parallelism` tasks, see [`crate::glue::Glue::with_parallelism`].
+#[tracing::instrument(skip_all, fields(rows), err)]
+pub async fn execute_parallel<T: GStore + GStoreMut>(
+    storage: &mut T,
+    statement: &Statement,
+    parallelism: usize,
+) -> Result<Payload> {
+    let payload = execute_autocommit(storage, statement, parallelism).await?;
+    if let Some(rows) = payload.rows() {
+        tracing::Span::current().record("rows", rows);
+    }
+
+    Ok(payload)
+}
+
+/// Runs the query as [`execute_parallel`] does, except that its rows are sent into `sink` as
+/// they are read instead of being collected. Returns the payload without rows and the number
+/// of rows sent; reading stops once `sink` fails, as when its receiver is gone.
+pub async fn execute_into<T, S>(
+    storage: &mut T,
+    query: &Query,
+    parallelism: usize,
+    sink: &mut S,
+) -> Result<(Payload, usize)>
+where
+    T: GStore + GStoreMut,
+    S: Sink<Row> + Unpin,
+{
+    let autocommit = storage.begin(true).await?;
+    let result = send_rows(storage, query, parallelism, sink).await;
+
+    if !autocommit {
+        return result;
+    }
+
+    match result {
+        Ok(sent) => storage.commit().await.map(|_| sent),
+        Err(error) => {
+            storage.rollback().await?;
+
+            Err(error)
+        }
+    }
+}
+
+async fn send_rows<T, S>(
+    storage: &mut T,
+    query: &Query,
+    parallelism: usize,
+    sink: &mut S,
+) -> Result<(Payload, usize)>
+where
+    T: GStore + GStoreMut,
+    S: Sink<Row> + Unpin,
+{
+    let query = match calls_sequence_functions(query) {
+        true => {
+            let mut query = query.clone();
+            resolve_query(storage, &mut query).await?;
+
+            Cow::Owned(query)
+        }
+        false => Cow::Borrowed(query),
+    };
+
+    let (labels, rows) = select_analyzed(storage, &query, None, None, parallelism).await?;
+    let mut rows = pin!(rows);
+    let mut sent = 0;
+    while let Some(row) = rows.try_next().await? {
+        if sink.send(row).await.is_err() {
+            break;
+        }
+
+        sent += 1;
+    }
+
+    let payload = match labels {
+        Some(labels) => Payload::Select {
+            labels,
+            rows: Vec::new(),
+        },
+        None => Payload::SelectMap(Vec::new()),
+    };
+
+    Ok((payload, sent))
+}
+
+async fn execute_autocommit<T: GStore + GStoreMut>(
+    storage: &mut T,
+    statement: &Statement,
+    parallelism: usize,
 ) -> Result<Payload> {
     if matches!(
         statement,
         Statement::StartTransaction | Statement::Rollback | Statement::Commit
     ) {
-        return execute_inner(storage, statement).await;
+        return execute_inner(storage, statement, parallelism).await;
     }
 
     let autocommit = storage.begin(true).await?;
-    let result = execute_inner(storage, statement).await;
+    let result = execute_inner(storage, statement, parallelism).await;
 
     if !autocommit {
         return result;
@@ -128,11 +272,14 @@ pub async fn execute<T: GStore + GStoreMut>(
     }
 }
 
-async fn execute_inner<T: GStore + GStoreMut>(
+pub(super) async fn execute_inner<T: GStore + GStoreMut>(
     storage: &mut T,
     statement: &Statement,
+    parallelism: usize,
 ) -> Result<Payload> {
-    match statement {
+    let statement = resolve(storage, statement).await?;
+
+    match statement.as_ref() {
         //- Modification
         //-- Tables
         Statement::CreateTable {
@@ -142,6 +289,8 @@ async fn execute_inner<T: GStore + GStoreMut>(
             source,
             engine,
             foreign_keys,
+            checks,
+            uniques,
             comment,
         } => {
             let options = CreateTableOptions {
@@ -151,6 +300,8 @@ async fn execute_inner<T: GStore + GStoreMut>(
                 source,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
             };
 
@@ -166,20 +317,96 @@ async fn execute_inner<T: GStore + GStoreMut>(
         } => drop_table(storage, names, *if_exists, *cascade)
             .await
             .map(Payload::DropTable),
+        //-- Views
+        Statement::CreateView {
+            or_replace,
+            name,
+            query,
+            materialized,
+        } => create_view(storage, name, query, *or_replace, *materialized)
+            .await
+            .map(|_| Payload::Create),
+        Statement::DropView {
+            if_exists,
+            names,
+            cascade,
+        } => drop_view(storage, names, *if_exists, *cascade)
+            .await
+            .map(Payload::DropView),
+        Statement::RefreshMaterializedView(name) => refresh_materialized_view(storage, name)
+            .await
+            .map(Payload::RefreshMaterializedView),
         Statement::AlterTable { name, operation } => alter_table(storage, name, operation)
             .await
             .map(|_| Payload::AlterTable),
         Statement::CreateIndex {
             name,
             table_name,
-            column,
-        } => create_index(storage, table_name, name, column)
-            .await
-            .map(|_| Payload::CreateIndex),
+            columns,
+            predicate,
+            fulltext,
+        } => create_index(
+            storage,
+            table_name,
+            name,
+            columns,
+            predicate.as_ref(),
+            *fulltext,
+        )
+        .await
+        .map(|_| Payload::CreateIndex),
         Statement::DropIndex { name, table_name } => storage
             .drop_index(table_name, name)
             .await
             .map(|_| Payload::DropIndex),
+        //-- Triggers
+        Statement::CreateTrigger {
+            or_replace,
+            name,
+            table_name,
+            timing,
+            events,
+            condition,
+            body,
+        } => {
+            let trigger = SchemaTrigger {
+                name: name.to_owned(),
+                timing: *timing,
+                events: events.clone(),
+                condition: condition.clone(),
+                body: body.clone(),
+            };
+
+            create_trigger(storage, table_name, trigger, *or_replace)
+                .await
+                .map(|_| Payload::CreateTrigger)
+        }
+        Statement::DropTrigger {
+            if_exists,
+            name,
+            table_name,
+        } => drop_trigger(storage, table_name, name, *if_exists)
+            .await
+            .map(|_| Payload::DropTrigger),
+        //-- Sequences
+        Statement::CreateSequence {
+            if_not_exists,
+            name,
+            increment,
+            start,
+        } => {
+            let sequence = SchemaSequence {
+                increment: *increment,
+                start: *start,
+            };
+
+            create_sequence(storage, name, sequence, *if_not_exists)
+                .await
+                .map(|_| Payload::Create)
+        }
+        Statement::DropSequence { if_exists, names } => drop_sequence(storage, names, *if_exists)
+            .await
+            .map(Payload::DropSequence),
         //- Transaction
         Statement::StartTransaction => storage
             .begin(false)
@@ -192,84 +419,43 @@ async fn execute_inner<T: GStore + GStoreMut>(
             table_name,
             columns,
             source,
-        } => insert(storage, table_name, columns, source)
+            on_conflict,
+            returning,
+        } => {
+            insert(
+                storage,
+                table_name,
+                columns,
+                source,
+                on_conflict.as_ref(),
+                returning,
+            )
             .await
-            .map(Payload::Insert),
+        }
         Statement::Update {
             table_name,
             selection,
             assignments,
+            returning,
         } => {
-            let Schema {
-                column_defs,
-                foreign_keys,
-                ..
-            } = storage
-                .fetch_schema(table_name)
-                .await?
-                .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?;
-
-            let all_columns = column_defs.as_deref().map(|columns| {
-                columns
-                    .iter()
-                    .map(|col_def| col_def.name.to_owned())
-                    .collect()
-            });
-            let columns_to_update: Vec<String> = assignments
-                .iter()
-                .map(|assignment| assignment.id.to_owned())
-                .collect();
-
-            let update = Update::new(storage, table_name, assignments, column_defs.as_deref())?;
-
-            let foreign_keys = Arc::new(foreign_keys);
-
-            let rows = fetch(storage, table_name, all_columns, selection.as_ref())
-                .await?
-                .and_then(|item| {
-                    let update = &update;
-                    let (key, row) = item;
-
-                    let foreign_keys = Arc::clone(&foreign_keys);
-                    async move {
-                        let row = update.apply(row, foreign_keys.as_ref()).await?;
-
-                        Ok((key, row))
-                    }
-                })
-                .try_collect::<Vec<(Key, Row)>>()
-                .await?;
-
-            if let Some(column_defs) = column_defs {
-                let column_validation =
-                    ColumnValidation::SpecifiedColumns(&column_defs, columns_to_update);
-                let rows = rows.iter().filter_map(|(_, row)| match row {
-                    Row::Vec { values, .. } => Some(values.as_slice()),
-                    Row::Map(_) => None,
-                });
-
-                validate_unique(storage, table_name, column_validation, rows).await?;
-            }
-
-            let num_rows = rows.len();
-            let rows = rows
-                .into_iter()
-                .map(|(key, row)| (key, row.into()))
-                .collect();
-
-            storage
-                .insert_data(table_name, rows)
-                .await
-                .map(|_| Payload::Update(num_rows))
+            update(
+                storage,
+                table_name,
+                selection.as_ref(),
+                assignments,
+                returning,
+            )
+            .await
         }
         Statement::Delete {
             table_name,
             selection,
-        } => delete(storage, table_name, selection).await,
+            returning,
+        } => delete(storage, table_name, selection, returning).await,
 
         //- Selection
         Statement::Query(query) => {
-            let (labels, rows) = select_with_labels(storage, query, None).await?;
+            let (labels, rows) = select_analyzed(storage, query, None, None, parallelism).await?;
 
             match labels {
                 Some(labels) => rows
@@ -341,6 +527,21 @@ async fn execute_inner<T: GStore + GStoreMut>(
 
             Ok(Payload::Select { labels, rows })
         }
+        Statement::ShowCreateView(view_name) => {
+            let view_ddl = storage
+                .fetch_schema(view_name)
+                .await?
+                .and_then(|schema| schema.view_ddl())
+                .ok_or_else(|| AlterError::ViewNotFound(view_name.to_owned()))?;
+
+            Ok(Payload::Select {
+                labels: vec!["VIEW_NAME".to_owned(), "CREATE_VIEW".to_owned()],
+                rows: vec![vec![Value::Str(view_name.to_owned()), Value::Str(view_ddl)]],
+            })
+        }
+        Statement::Check { table_name } => check(storage, table_name.as_deref()).await,
+        Statement::Analyze { table_name } => analyze(storage, table_name.as_deref()).await,
+        Statement::Explain { analyze, query } => explain(storage, query, *analyze).await,
         Statement::ShowVariable(variable) => match variable {
             Variable::Tables => {
                 let query = Query {
@@ -411,5 +612,39 @@ async fn execute_inner<T: GStore + GStoreMut>(
         Statement::DropFunction { if_exists, names } => delete_function(storage, names, *if_exists)
             .await
             .map(|_| Payload::DropFunction),
+        //- Users
+        Statement::CreateUser {
+            if_not_exists,
+            name,
+            password,
+            superuser,
+        } => create_user(
+            storage,
+            name,
+            password.as_deref(),
+            *superuser,
+            *if_not_exists,
+        )
+        .await
+        .map(|_| Payload::Create),
+        Statement::DropUser { if_exists, names } => drop_users(storage, names, *if_exists)
+            .await
+            .map(|_| Payload::DropUser),
+        Statement::Grant {
+            privileges,
+            table_names,
+            grantees,
+        } => grant(storage, privileges, table_names, grantees)
+            .await
+            .map(|_| Payload::Grant),
+        Statement::Revoke {
+            privileges,
+            table_names,
+            grantees,
+        } => revoke(storage, privileges, table_names, grantees)
+            .await
+            .map(|_| Payload::Revoke),
+        //- Variables, which only Glue keeps
+        Statement::SetVariable(_) => Err(ExecuteError::SetVariableOutsideGlue.into()),
     }
 }
@@ -0,0 +1,388 @@
+use {
+    super::{aggregate::check_aggregate, select::select_analyzed},
+    crate::{
+        ast::{
+            BinaryOperator, IndexItem, Join, JoinExecutor, Query, Select, SelectItem, SetExpr,
+            TableFactor, TableWithJoins, ToSql, ToSqlUnquoted, Values,
+        },
+        chrono::{NaiveDateTime, Utc},
+        data::{Interval, Value},
+        executor::Payload,
+        result::Result,
+        store::GStore,
+    },
+    futures::{future::ready, stream::TryStreamExt},
+    itertools::Itertools,
+    std::sync::atomic::{AtomicI64, AtomicUsize, Ordering},
+};
+
+/// Operators of a `SELECT` whose rows `EXPLAIN ANALYZE` counts, with `Output` for the rows of
+/// the whole query.
+#[derive(Clone, Copy, Debug)]
+pub(super) enum Stage {
+    Scan,
+    Join,
+    Filter,
+    Aggregate,
+    Project,
+    Sort,
+    Distinct,
+    Output,
+}
+
+#[derive(Default)]
+struct Stats {
+    rows: AtomicUsize,
+    /// microseconds from the start of the query to the last row
+    elapsed: AtomicI64,
+}
+
+/// Row counts and timings of the operators of the query `EXPLAIN ANALYZE` runs.
+pub(super) struct Analyze {
+    started: NaiveDateTime,
+    stats: [Stats; 8],
+}
+
+impl Analyze {
+    fn new() -> Self {
+        Self {
+            started: Utc::now().naive_utc(),
+            stats: Default::default(),
+        }
+    }
+
+    /// Counts a row the operator gave, taking the time since the query started.
+    pub(super) fn record(&self, stage: Stage) {
+        let stats = &self.stats[stage as usize];
+        let elapsed = (Utc::now().naive_utc() - self.started)
+            .num_microseconds()
+            .unwrap_or(i64::MAX);
+
+        stats.rows.fetch_add(1, Ordering::Relaxed);
+        stats.elapsed.fetch_max(elapsed, Ordering::Relaxed);
+    }
+
+    fn values(&self, stage: Stage) -> [Value; 2] {
+        let stats = &self.stats[stage as usize];
+        let rows = stats.rows.load(Ordering::Relaxed);
+        let elapsed = match rows {
+            0 => Value::Null,
+            _ => Value::Interval(Interval::Microsecond(stats.elapsed.load(Ordering::Relaxed))),
+        };
+
+        [Value::I64(rows as i64), elapsed]
+    }
+}
+
+/// Operator of the plan, one row of the result of `EXPLAIN`.
+struct Node {
+    operator: &'static str,
+    detail: Option<String>,
+    /// `None` unless `EXPLAIN ANALYZE` counts its rows
+    stage: Option<Stage>,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn new(operator: &'static str, detail: Option<String>) -> Self {
+        Self {
+            operator,
+            detail,
+            stage: None,
+            children: Vec::new(),
+        }
+    }
+
+    fn stage(mut self, stage: Option<Stage>) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    fn child(mut self, child: Node) -> Self {
+        self.children.push(child);
+        self
+    }
+}
+
+/// Shows the plan of the query, one row per operator with the id of the operator taking its
+/// rows. `EXPLAIN ANALYZE` runs the query and adds the number of rows each operator gave and
+/// the time from the start of the query to its last row.
+pub async fn explain<T: GStore>(storage: &T, query: &Query, analyze: bool) -> Result<Payload> {
+    let mut labels = vec!["ID", "PARENT_ID", "OPERATOR", "DETAIL"];
+    let mut root = query_node(query, analyze);
+
+    let analyzed = match analyze {
+        true => {
+            let analyze = Analyze::new();
+            let (_, rows) = select_analyzed(storage, query, None, Some(&analyze), 1).await?;
+            rows.try_for_each(|_| {
+                analyze.record(Stage::Output);
+
+                ready(Ok(()))
+            })
+            .await?;
+
+            labels.extend(["ROWS", "ELAPSED"]);
+            root.stage = Some(Stage::Output);
+
+            Some(analyze)
+        }
+        false => None,
+    };
+
+    let mut rows = Vec::new();
+    flatten(root, None, analyzed.as_ref(), &mut rows);
+
+    Ok(Payload::Select {
+        labels: labels.into_iter().map(ToOwned::to_owned).collect(),
+        rows,
+    })
+}
+
+fn flatten(
+    node: Node,
+    parent_id: Option<i64>,
+    analyze: Option<&Analyze>,
+    rows: &mut Vec<Vec<Value>>,
+) {
+    let Node {
+        operator,
+        detail,
+        stage,
+        children,
+    } = node;
+
+    let id = rows.len() as i64 + 1;
+    let mut row = vec![
+        Value::I64(id),
+        parent_id.map(Value::I64).unwrap_or(Value::Null),
+        Value::Str(operator.to_owned()),
+        detail.map(Value::Str).unwrap_or(Value::Null),
+    ];
+    if let Some(analyze) = analyze {
+        row.extend(match stage {
+            Some(stage) => analyze.values(stage),
+            None => [Value::Null, Value::Null],
+        });
+    }
+    rows.push(row);
+
+    for child in children {
+        flatten(child, Some(id), analyze, rows);
+    }
+}
+
+/// Plan of the query, counting the rows of the operators of its `SELECT` when `analyzed`.
+fn query_node(query: &Query, analyzed: bool) -> Node {
+    let Query {
+        body,
+        order_by,
+        limit,
+        offset,
+    } = query;
+
+    let node = match body {
+        SetExpr::Select(select) => select_node(select, analyzed),
+        _ => set_expr_node(body),
+    };
+    let node = match order_by.is_empty() {
+        true => node,
+        false => {
+            let detail = order_by
+                .iter()
+                .map(ToSqlUnquoted::to_sql_unquoted)
+                .join(", ");
+
+            let sorted = analyzed && matches!(body, SetExpr::Select(_));
+
+            Node::new("SORT", Some(detail))
+                .stage(sorted.then_some(Stage::Sort))
+                .child(node)
+        }
+    };
+
+    match (limit, offset) {
+        (None, None) => node,
+        _ => {
+            let detail = [("LIMIT", limit), ("OFFSET", offset)]
+                .into_iter()
+                .filter_map(|(keyword, expr)| {
+                    expr.as_ref()
+                        .map(|expr| format!("{keyword} {}", expr.to_sql_unquoted()))
+                })
+                .join(" ");
+
+            Node::new("LIMIT", Some(detail)).child(node)
+        }
+    }
+}
+
+fn set_expr_node(set_expr: &SetExpr) -> Node {
+    match set_expr {
+        SetExpr::Select(select) => select_node(select, false),
+        SetExpr::Values(Values(rows)) => Node::new("VALUES", Some(format!("{} rows", rows.len()))),
+        SetExpr::SetOperation {
+            op,
+            all,
+            left,
+            right,
+        } => {
+            let detail = match all {
+                true => format!("{op} ALL"),
+                false => op.to_string(),
+            };
+
+            Node::new("SET OPERATION", Some(detail))
+                .child(set_expr_node(left))
+                .child(set_expr_node(right))
+        }
+    }
+}
+
+fn select_node(select: &Select, analyzed: bool) -> Node {
+    let Select {
+        distinct,
+        projection,
+        from: TableWithJoins { relation, joins },
+        selection,
+        group_by,
+        having,
+    } = select;
+    let stage = |stage| analyzed.then_some(stage);
+
+    let node = relation_node(relation).stage(stage(Stage::Scan));
+    let node = joins.iter().fold(node, |node, join| {
+        let Join {
+            relation,
+            join_executor,
+            ..
+        } = join;
+        let operator = match join_executor {
+            JoinExecutor::NestedLoop => "NESTED LOOP JOIN",
+            JoinExecutor::Hash { .. } => "HASH JOIN",
+            JoinExecutor::Merge { .. } => "MERGE JOIN",
+        };
+
+        Node::new(operator, Some(join.to_sql_unquoted()))
+            .child(node)
+            .child(relation_node(relation))
+    });
+    let node = match joins.is_empty() {
+        true => node,
+        false => node.stage(stage(Stage::Join)),
+    };
+
+    let node = match selection {
+        Some(expr) => Node::new("FILTER", Some(expr.to_sql_unquoted()))
+            .stage(stage(Stage::Filter))
+            .child(node),
+        None => node,
+    };
+
+    let node = match check_aggregate(projection, group_by) {
+        true => {
+            let group_by = (!group_by.is_empty()).then(|| {
+                let exprs = group_by
+                    .iter()
+                    .map(ToSqlUnquoted::to_sql_unquoted)
+                    .join(", ");
+
+                format!("GROUP BY {exprs}")
+            });
+            let having = having
+                .as_ref()
+                .map(|expr| format!("HAVING {}", expr.to_sql_unquoted()));
+            let detail = group_by.into_iter().chain(having).join(" ");
+            let detail = (!detail.is_empty()).then_some(detail);
+
+            Node::new("AGGREGATE", detail)
+                .stage(stage(Stage::Aggregate))
+                .child(node)
+        }
+        false => node,
+    };
+
+    let detail = projection.iter().map(select_item_sql).join(", ");
+    let node = Node::new("PROJECT", Some(detail))
+        .stage(stage(Stage::Project))
+        .child(node);
+
+    match distinct {
+        true => Node::new("DISTINCT", None)
+            .stage(stage(Stage::Distinct))
+            .child(node),
+        false => node,
+    }
+}
+
+/// Select item without the label when it is the expression itself.
+fn select_item_sql(select_item: &SelectItem) -> String {
+    match select_item {
+        SelectItem::Expr { expr, label } if &expr.to_sql_unquoted() == label => label.to_owned(),
+        _ => select_item.to_sql_unquoted(),
+    }
+}
+
+fn relation_node(relation: &TableFactor) -> Node {
+    let detail = relation.to_sql_unquoted();
+
+    match relation {
+        TableFactor::Table { index: None, .. } => Node::new("SCAN", Some(detail)),
+        TableFactor::Table {
+            index: Some(IndexItem::PrimaryKey(expr)),
+            ..
+        } => {
+            let detail = format!("{detail} USING PRIMARY KEY = {}", expr.to_sql_unquoted());
+
+            Node::new("PRIMARY KEY LOOKUP", Some(detail))
+        }
+        TableFactor::Table {
+            index:
+                Some(IndexItem::NonClustered {
+                    name,
+                    asc,
+                    cmp_expr,
+                }),
+            ..
+        } => {
+            let cmp = cmp_expr.iter().map(|(index_op, expr)| {
+                let op = BinaryOperator::from(index_op.clone()).to_sql();
+
+                format!("{op} {}", expr.to_sql_unquoted())
+            });
+            let order = asc.map(|asc| match asc {
+                true => "ASC".to_owned(),
+                false => "DESC".to_owned(),
+            });
+            let detail = [format!("{detail} USING {name}")]
+                .into_iter()
+                .chain(cmp)
+                .chain(order)
+                .join(" ");
+
+            Node::new("INDEX SCAN", Some(detail))
+        }
+        TableFactor::Table {
+            index: Some(IndexItem::FullText { name, query }),
+            ..
+        } => {
+            let detail = format!("{detail} USING {name} MATCH {}", query.to_sql_unquoted());
+
+            Node::new("FULLTEXT SCAN", Some(detail))
+        }
+        TableFactor::Derived { subquery, alias } => {
+            Node::new("SUBQUERY", Some(alias.name.to_owned())).child(query_node(subquery, false))
+        }
+        TableFactor::Recursive {
+            name,
+            anchor,
+            recursive,
+            ..
+        } => Node::new("RECURSIVE", Some(name.to_owned()))
+            .child(query_node(anchor, false))
+            .child(query_node(recursive, false)),
+        TableFactor::Series { .. } => Node::new("SERIES", Some(detail)),
+        TableFactor::Fake { .. } => Node::new("FAKE", Some(detail)),
+        TableFactor::Dictionary { .. } => Node::new("DICTIONARY", Some(detail)),
+    }
+}
@@ -0,0 +1,78 @@
+use {
+    crate::{
+        ast::{FakeColumn, FakeKind},
+        data::Value,
+    },
+    chrono::TimeDelta,
+    rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom},
+};
+
+const FIRST_NAMES: [&str; 16] = [
+    "Alice", "Bob", "Carol", "David", "Emma", "Frank", "Grace", "Henry", "Isla", "Jack", "Kate",
+    "Liam", "Mia", "Noah", "Olivia", "Paul",
+];
+
+const LAST_NAMES: [&str; 16] = [
+    "Anderson", "Brown", "Clark", "Davis", "Evans", "Garcia", "Harris", "Jones", "Kim", "Lee",
+    "Martin", "Nguyen", "Park", "Smith", "Taylor", "Wilson",
+];
+
+/// Rows of `FAKE`, generated the same way again for the same seed.
+pub fn fake_rows(
+    columns: &[FakeColumn],
+    size: i64,
+    seed: u64,
+) -> impl Iterator<Item = Vec<Value>> + '_ {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    (1..=size).map(move |n| {
+        columns
+            .iter()
+            .map(|column| fake_value(&column.kind, n, &mut rng))
+            .collect()
+    })
+}
+
+fn fake_value(kind: &FakeKind, n: i64, rng: &mut StdRng) -> Value {
+    let mut name = || {
+        let first = FIRST_NAMES.choose(rng).copied().unwrap_or_default();
+        let last = LAST_NAMES.choose(rng).copied().unwrap_or_default();
+
+        (first, last)
+    };
+
+    match kind {
+        FakeKind::Sequence => Value::I64(n),
+        FakeKind::Name => {
+            let (first, last) = name();
+
+            Value::Str(format!("{first} {last}"))
+        }
+        FakeKind::Email => {
+            let (first, last) = name();
+
+            Value::Str(format!(
+                "{}.{}{n}@example.com",
+                first.to_lowercase(),
+                last.to_lowercase()
+            ))
+        }
+        FakeKind::Integer { min, max } => Value::I64(rng.gen_range(*min..=*max)),
+        FakeKind::Float { min, max } => Value::F64(rng.gen_range(*min as f64..=*max as f64)),
+        FakeKind::Boolean => Value::Bool(rng.r#gen()),
+        FakeKind::Date { from, to } => {
+            let days = rng.gen_range(0..=(*to - *from).num_days());
+
+            Value::Date(*from + TimeDelta::days(days))
+        }
+        FakeKind::Uuid => {
+            let bytes = rng.r#gen();
+
+            Value::Uuid(
+                uuid::Builder::from_random_bytes(bytes)
+                    .into_uuid()
+                    .as_u128(),
+            )
+        }
+    }
+}
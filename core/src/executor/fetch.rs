@@ -1,26 +1,35 @@
 use {
-    super::{context::RowContext, evaluate::evaluate_stateless, filter::check_expr},
+    super::{
+        context::RowContext, evaluate::evaluate_stateless, fake::fake_rows, filter::check_expr,
+        pushdown::fetch_predicate, recursive::recursive_rows,
+    },
     crate::{
         ast::{
-            ColumnDef, ColumnUniqueOption, Dictionary, Expr, IndexItem, Join, Query, Select,
-            SelectItem, SetExpr, TableAlias, TableFactor, TableWithJoins, ToSql, ToSqlUnquoted,
-            Values,
+            ColumnDef, ColumnUniqueOption, Dictionary, Expr, IndexItem, IndexOperator, Join, Query,
+            Select, SelectItem, SetExpr, TableAlias, TableFactor, TableWithJoins, ToSql,
+            ToSqlUnquoted, Values,
         },
-        data::{Key, Row, Value, get_alias, get_index},
+        data::{Key, Row, Value, fulltext, get_alias, get_index},
         executor::{
             evaluate::{Evaluated, evaluate},
             select::select,
         },
         result::Result,
-        store::{DataRow, GStore},
+        store::{DataRow, GStore, Predicate},
     },
     async_recursion::async_recursion,
     futures::{
         future,
-        stream::{self, Stream, TryStreamExt},
+        stream::{self, Stream, StreamExt, TryStreamExt},
     },
     serde::Serialize,
-    std::{borrow::Cow, collections::BTreeMap, fmt::Debug, iter, sync::Arc},
+    std::{
+        borrow::Cow,
+        collections::{BTreeMap, BTreeSet, HashSet},
+        fmt::Debug,
+        iter,
+        sync::Arc,
+    },
     thiserror::Error as ThisError,
 };
 
@@ -35,9 +44,15 @@ pub enum FetchError {
     #[error("SERIES has wrong size: {0}")]
     SeriesSizeWrong(i64),
 
+    #[error("FAKE has wrong size: {0}")]
+    FakeSizeWrong(i64),
+
     #[error("table '{0}' has {1} columns available but {2} column aliases specified")]
     TooManyColumnAliases(String, usize, usize),
 
+    #[error("recursive table '{0}' is still adding rows after {1} recursions")]
+    RecursionDepthExceeded(String, usize),
+
     #[error("unreachable")]
     Unreachable,
 }
@@ -49,50 +64,59 @@ pub async fn fetch<'a, T: GStore>(
     where_clause: Option<&'a Expr>,
 ) -> Result<impl Stream<Item = Result<(Key, Row)>> + 'a> {
     let columns = columns.unwrap_or_else(|| Arc::from([]));
-    let rows = storage
-        .scan_data(table_name)
-        .await?
-        .try_filter_map(move |(key, data_row)| {
-            let row = match data_row {
-                DataRow::Vec(values) => Row::Vec {
-                    columns: Arc::clone(&columns),
-                    values,
-                },
-                DataRow::Map(values) => Row::Map(values),
+    let relation = TableFactor::Table {
+        name: table_name.to_owned(),
+        alias: None,
+        index: None,
+    };
+    let rows = match fetch_predicate(storage, &relation, where_clause, true).await? {
+        Some(predicate) => storage.scan_data_filtered(table_name, &predicate).await?,
+        None => storage.scan_data(table_name).await?,
+    };
+    let rows = rows.try_filter_map(move |(key, data_row)| {
+        let row = match data_row {
+            DataRow::Vec(values) => Row::Vec {
+                columns: Arc::clone(&columns),
+                values,
+            },
+            DataRow::Map(values) => Row::Map(values),
+        };
+
+        async move {
+            let expr = match where_clause {
+                None => {
+                    return Ok(Some((key, row)));
+                }
+                Some(expr) => expr,
             };
 
-            async move {
-                let expr = match where_clause {
-                    None => {
-                        return Ok(Some((key, row)));
-                    }
-                    Some(expr) => expr,
-                };
-
-                let context = RowContext::new(table_name, Cow::Borrowed(&row), None);
+            let context = RowContext::new(table_name, Cow::Borrowed(&row), None);
 
-                check_expr(storage, Some(Arc::new(context)), None, expr)
-                    .await
-                    .map(|pass| pass.then_some((key, row)))
-            }
-        });
+            check_expr(storage, Some(Arc::new(context)), None, expr)
+                .await
+                .map(|pass| pass.then_some((key, row)))
+        }
+    });
 
     Ok(rows)
 }
 
 #[derive(futures_enum::Stream)]
-pub enum Rows<I1, I2, I3, I4> {
+pub enum Rows<I1, I2, I3, I4, I5, I6> {
     Derived(I1),
-    Table(I2),
-    Series(I3),
-    Dictionary(I4),
+    Recursive(I2),
+    Table(I3),
+    Series(I4),
+    Fake(I5),
+    Dictionary(I6),
 }
 
 pub async fn fetch_relation_rows<'a, T: GStore>(
     storage: &'a T,
     table_factor: &'a TableFactor,
     filter_context: &Option<Arc<RowContext<'a>>>,
-) -> Result<impl Stream<Item = Result<Row>> + 'a> {
+    predicate: Option<Predicate>,
+) -> Result<impl Stream<Item = Result<Row>> + 'a + use<'a, T>> {
     let columns = Arc::from(
         fetch_relation_columns(storage, table_factor)
             .await?
@@ -115,14 +139,29 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
 
             Ok(Rows::Derived(rows))
         }
+        TableFactor::Recursive { .. } => {
+            let filter_context = filter_context.as_ref().map(Arc::clone);
+            let rows = recursive_rows(storage, table_factor, filter_context)
+                .await?
+                .into_iter()
+                .map(move |values| {
+                    Ok(Row::Vec {
+                        columns: Arc::clone(&columns),
+                        values,
+                    })
+                });
+
+            Ok(Rows::Recursive(stream::iter(rows)))
+        }
         TableFactor::Table { name, .. } => {
             let rows = {
                 #[derive(futures_enum::Stream)]
-                enum Rows<I1, I2, I3, I4> {
+                enum Rows<I1, I2, I3, I4, I5> {
                     Indexed(I1),
                     PrimaryKey(I2),
                     PrimaryKeyEmpty(I3),
-                    FullScan(I4),
+                    FullText(I4),
+                    FullScan(I5),
                 }
 
                 match get_index(table_factor) {
@@ -196,15 +235,49 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
                             None => Rows::PrimaryKeyEmpty(stream::empty()),
                         }
                     }
-                    _ => {
-                        let rows = storage.scan_data(name).await?.map_ok(move |(_, data_row)| {
-                            match data_row {
+                    Some(IndexItem::FullText {
+                        name: index_name,
+                        query,
+                    }) => {
+                        let query: Value =
+                            evaluate(storage, None, None, query).await?.try_into()?;
+                        let terms = match query {
+                            Value::Null => BTreeSet::new(),
+                            query => fulltext::distinct_terms(&String::from(query)),
+                        };
+
+                        // the index holds a row by each of its terms, so a row with more than
+                        // one of the terms is scanned more than once
+                        let mut scanned = HashSet::new();
+                        let rows = stream::iter(terms)
+                            .then(move |term| {
+                                let cmp_value = Some((&IndexOperator::Eq, Value::Str(term)));
+
+                                storage.scan_indexed_data(name, index_name, None, cmp_value)
+                            })
+                            .try_flatten()
+                            .try_filter(move |(key, _)| future::ready(scanned.insert(key.clone())))
+                            .map_ok(move |(_, data_row)| match data_row {
                                 DataRow::Vec(values) => Row::Vec {
                                     columns: Arc::clone(&columns),
                                     values,
                                 },
                                 DataRow::Map(values) => Row::Map(values),
-                            }
+                            });
+
+                        Rows::FullText(rows)
+                    }
+                    _ => {
+                        let rows = match &predicate {
+                            Some(predicate) => storage.scan_data_filtered(name, predicate).await?,
+                            None => storage.scan_data(name).await?,
+                        };
+                        let rows = rows.map_ok(move |(_, data_row)| match data_row {
+                            DataRow::Vec(values) => Row::Vec {
+                                columns: Arc::clone(&columns),
+                                values,
+                            },
+                            DataRow::Map(values) => Row::Map(values),
                         });
 
                         Rows::FullScan(rows)
@@ -232,6 +305,37 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
 
             Ok(Rows::Series(stream::iter(rows)))
         }
+        TableFactor::Fake {
+            size,
+            columns: fake_columns,
+            seed,
+            ..
+        } => {
+            let value: Value = evaluate_stateless(None, size).await?.try_into()?;
+            let size: i64 = value.try_into()?;
+            if size < 0 {
+                return Err(FetchError::FakeSizeWrong(size).into());
+            }
+
+            let seed = match seed {
+                Some(seed) => {
+                    let value: Value = evaluate_stateless(None, seed).await?.try_into()?;
+                    let seed: i64 = value.try_into()?;
+
+                    seed as u64
+                }
+                None => rand::random(),
+            };
+
+            let rows = fake_rows(fake_columns, size, seed).map(move |values| {
+                Ok(Row::Vec {
+                    columns: Arc::clone(&columns),
+                    values,
+                })
+            });
+
+            Ok(Rows::Fake(stream::iter(rows)))
+        }
         TableFactor::Dictionary { dict, .. } => {
             let rows = {
                 #[derive(futures_enum::Stream)]
@@ -334,20 +438,23 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
                         let schemas = storage.fetch_all_schemas().await?;
                         let rows = schemas.into_iter().flat_map(move |schema| {
                             let column_defs = schema.column_defs.unwrap_or_default();
-                            let primary_column = column_defs.iter().find_map(|column_def| {
-                                let ColumnDef { name, unique, .. } = column_def;
+                            let primary_columns = column_defs
+                                .iter()
+                                .filter_map(|column_def| {
+                                    let ColumnDef { name, unique, .. } = column_def;
 
-                                (unique == &Some(ColumnUniqueOption { is_primary: true }))
-                                    .then_some(name)
-                            });
+                                    (unique == &Some(ColumnUniqueOption { is_primary: true }))
+                                        .then_some(name.as_str())
+                                })
+                                .collect::<Vec<_>>();
 
-                            let clustered = match primary_column {
-                                Some(column_name) => {
+                            let clustered = match primary_columns.is_empty() {
+                                false => {
                                     let values = vec![
                                         Value::Str(schema.table_name.clone()),
                                         Value::Str("PRIMARY".to_owned()),
                                         Value::Str("BOTH".to_owned()),
-                                        Value::Str(column_name.to_owned()),
+                                        Value::Str(primary_columns.join(", ")),
                                         Value::Bool(true),
                                     ];
 
@@ -358,7 +465,7 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
 
                                     vec![Ok(row)]
                                 }
-                                None => Vec::new(),
+                                true => Vec::new(),
                             };
 
                             let columns = Arc::clone(&columns);
@@ -367,7 +474,14 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
                                     Value::Str(schema.table_name.clone()),
                                     Value::Str(index.name),
                                     Value::Str(index.order.to_string()),
-                                    Value::Str(index.expr.to_sql_unquoted()),
+                                    Value::Str(
+                                        index
+                                            .exprs
+                                            .iter()
+                                            .map(ToSqlUnquoted::to_sql_unquoted)
+                                            .collect::<Vec<_>>()
+                                            .join(", "),
+                                    ),
                                     Value::Bool(false),
                                 ];
 
@@ -390,6 +504,56 @@ pub async fn fetch_relation_rows<'a, T: GStore>(
     }
 }
 
+/// Rows of the relation as [`fetch_relation_rows`] gives them, split into up to `partitions`
+/// streams by [`crate::store::Store::scan_data_partitioned`] when it scans a table without an
+/// index, and a single stream otherwise.
+pub async fn fetch_relation_partitions<'a, T: GStore>(
+    storage: &'a T,
+    table_factor: &'a TableFactor,
+    predicate: Option<Predicate>,
+    partitions: usize,
+) -> Result<Vec<impl Stream<Item = Result<Row>> + 'a + use<'a, T>>> {
+    #[derive(futures_enum::Stream)]
+    enum Partition<I1, I2> {
+        Scanned(I1),
+        Fetched(I2),
+    }
+
+    let TableFactor::Table {
+        name, index: None, ..
+    } = table_factor
+    else {
+        let rows = fetch_relation_rows(storage, table_factor, &None, predicate).await?;
+
+        return Ok(vec![Partition::Fetched(rows)]);
+    };
+
+    let columns: Arc<[String]> = Arc::from(
+        fetch_relation_columns(storage, table_factor)
+            .await?
+            .unwrap_or_default(),
+    );
+    let partitions = storage
+        .scan_data_partitioned(name, predicate.as_ref(), partitions)
+        .await?
+        .into_iter()
+        .map(|rows| {
+            let columns = Arc::clone(&columns);
+            let rows = rows.map_ok(move |(_, data_row)| match data_row {
+                DataRow::Vec(values) => Row::Vec {
+                    columns: Arc::clone(&columns),
+                    values,
+                },
+                DataRow::Map(values) => Row::Map(values),
+            });
+
+            Partition::Scanned(rows)
+        })
+        .collect();
+
+    Ok(partitions)
+}
+
 pub async fn fetch_columns<T: GStore>(
     storage: &T,
     table_name: &str,
@@ -442,6 +606,9 @@ where
             }
         }
         TableFactor::Series { .. } => Ok(Some(vec!["N".to_owned()])),
+        TableFactor::Fake { columns, .. } => Ok(Some(
+            columns.iter().map(|column| column.name.clone()).collect(),
+        )),
         TableFactor::Dictionary { dict, .. } => Ok(Some(match dict {
             Dictionary::GlueObjects => vec![
                 "OBJECT_NAME".to_owned(),
@@ -467,66 +634,77 @@ where
             ],
         })),
         TableFactor::Derived {
-            subquery: Query { body, .. },
-            alias:
-                TableAlias {
-                    columns: alias_columns,
-                    name,
-                },
-        } => match body {
-            SetExpr::Select(statement) => {
-                let Select {
-                    from:
-                        TableWithJoins {
-                            relation, joins, ..
-                        },
-                    projection,
-                    ..
-                } = statement.as_ref();
+            subquery,
+            alias: TableAlias { name, columns },
+        } => fetch_query_labels(storage, subquery)
+            .await?
+            .map(|labels| alias_labels(name, columns, labels))
+            .transpose(),
+        TableFactor::Recursive {
+            name,
+            columns,
+            anchor,
+            alias,
+            ..
+        } => fetch_query_labels(storage, anchor)
+            .await?
+            .map(|labels| {
+                let labels = alias_labels(name, columns, labels)?;
 
-                let labels = fetch_labels(storage, relation, joins, projection).await?;
-                match labels {
-                    None => Ok(None),
-                    Some(labels) if alias_columns.is_empty() => Ok(Some(labels)),
-                    Some(labels) if alias_columns.len() > labels.len() => {
-                        Err(FetchError::TooManyColumnAliases(
-                            name.to_string(),
-                            labels.len(),
-                            alias_columns.len(),
-                        )
-                        .into())
-                    }
-                    Some(labels) => Ok(Some(
-                        alias_columns
-                            .iter()
-                            .cloned()
-                            .chain(labels[alias_columns.len()..labels.len()].to_vec())
-                            .collect(),
-                    )),
-                }
-            }
-            SetExpr::Values(Values(values_list)) => {
-                let total_len = values_list[0].len();
-                let alias_len = alias_columns.len();
-                if alias_len > total_len {
-                    return Err(FetchError::TooManyColumnAliases(
-                        name.into(),
-                        total_len,
-                        alias_len,
-                    )
-                    .into());
-                }
-                let labels = (alias_len + 1..=total_len).map(|i| format!("column{i}"));
-                let labels = alias_columns
-                    .iter()
-                    .cloned()
-                    .chain(labels)
-                    .collect::<Vec<_>>();
+                alias_labels(&alias.name, &alias.columns, labels)
+            })
+            .transpose(),
+    }
+}
 
-                Ok(Some(labels))
-            }
-        },
+async fn fetch_query_labels<T: GStore>(storage: &T, query: &Query) -> Result<Option<Vec<String>>> {
+    fetch_set_expr_labels(storage, &query.body).await
+}
+
+#[async_recursion]
+async fn fetch_set_expr_labels<T>(storage: &T, set_expr: &SetExpr) -> Result<Option<Vec<String>>>
+where
+    T: GStore,
+{
+    match set_expr {
+        SetExpr::Select(statement) => {
+            let Select {
+                from: TableWithJoins { relation, joins },
+                projection,
+                ..
+            } = statement.as_ref();
+
+            fetch_labels(storage, relation, joins, projection).await
+        }
+        SetExpr::Values(Values(values_list)) => Ok(Some(
+            (1..=values_list[0].len())
+                .map(|i| format!("column{i}"))
+                .collect(),
+        )),
+        SetExpr::SetOperation { left, .. } => fetch_set_expr_labels(storage, left).await,
+    }
+}
+
+/// Renames the first labels to the column aliases of table `name`.
+pub(super) fn alias_labels(
+    name: &str,
+    alias_columns: &[String],
+    labels: Vec<String>,
+) -> Result<Vec<String>> {
+    if alias_columns.len() > labels.len() {
+        return Err(FetchError::TooManyColumnAliases(
+            name.to_owned(),
+            labels.len(),
+            alias_columns.len(),
+        )
+        .into());
     }
+
+    Ok(alias_columns
+        .iter()
+        .cloned()
+        .chain(labels.into_iter().skip(alias_columns.len()))
+        .collect())
 }
 
 async fn fetch_join_columns<'a, T: GStore>(
@@ -1,10 +1,19 @@
 use {
     super::{
+        Payload,
+        filter::check_expr,
+        returning::Returning,
         select::select,
-        validate::{ColumnValidation, validate_unique},
+        trigger::{RowChange, Triggers},
+        update::{UpdatedRows, update_context},
+        validate::{ColumnValidation, ValidateError, validate_checks, validate_unique},
     },
     crate::{
-        ast::{ColumnDef, ColumnUniqueOption, Expr, ForeignKey, Query, SetExpr, Values},
+        ast::{
+            CheckConstraint, ColumnDef, ColumnUniqueOption, Expr, ForeignKey, OnConflict,
+            OnConflictAction, Query, SelectItem, SetExpr, TriggerEvent, TriggerTiming,
+            UniqueConstraint, Values,
+        },
         data::{Key, Row, Schema, Value},
         executor::{evaluate::evaluate_stateless, limit::Limit},
         result::Result,
@@ -12,7 +21,11 @@ use {
     },
     futures::stream::{self, StreamExt, TryStreamExt},
     serde::Serialize,
-    std::{fmt::Debug, sync::Arc},
+    std::{
+        collections::{HashMap, HashSet},
+        fmt::Debug,
+        sync::Arc,
+    },
     thiserror::Error as ThisError,
 };
 
@@ -50,6 +63,18 @@ pub enum InsertError {
 
     #[error("unreachable referencing column name: {0}")]
     ConflictReferencingColumnName(String),
+
+    #[error("ON CONFLICT is not supported for schemaless table: {0}")]
+    OnConflictOnSchemalessTable(String),
+
+    #[error("ON CONFLICT target column is neither primary key nor unique: {0}")]
+    ConflictTargetNotUnique(String),
+
+    #[error("ON CONFLICT DO UPDATE requires a conflict target")]
+    ConflictTargetRequired,
+
+    #[error("ON CONFLICT DO UPDATE cannot affect a row a second time")]
+    ConflictRowAffectedTwice,
 }
 
 enum RowsData {
@@ -57,73 +82,203 @@ enum RowsData {
     Insert(Vec<(Key, DataRow)>),
 }
 
+/// Constraints of the table the inserted rows are validated against.
+struct Constraints {
+    foreign_keys: Vec<ForeignKey>,
+    checks: Vec<CheckConstraint>,
+    uniques: Vec<UniqueConstraint>,
+}
+
 pub async fn insert<T: GStore + GStoreMut>(
     storage: &mut T,
     table_name: &str,
     columns: &[String],
     source: &Query,
-) -> Result<usize> {
+    on_conflict: Option<&OnConflict>,
+    returning: &[SelectItem],
+) -> Result<Payload> {
     let Schema {
         column_defs,
         foreign_keys,
+        checks,
+        uniques,
+        triggers,
         ..
     } = storage
         .fetch_schema(table_name)
         .await?
         .ok_or_else(|| InsertError::TableNotFound(table_name.to_owned()))?;
 
-    let rows = match column_defs {
+    let triggers = Triggers::new(triggers, TriggerEvent::Insert);
+    let labels = column_defs
+        .iter()
+        .flatten()
+        .map(|column_def| column_def.name.to_owned())
+        .collect::<Arc<[_]>>();
+
+    let constraints = Constraints {
+        foreign_keys,
+        checks,
+        uniques,
+    };
+
+    let (rows, conflicts) = match column_defs {
         Some(column_defs) => {
-            fetch_vec_rows(
+            let column_defs = Arc::from(column_defs);
+            let rows = fetch_vec_rows(storage, &column_defs, &labels, columns, source).await?;
+            let (rows, conflicts) = match on_conflict {
+                Some(on_conflict) => {
+                    split_conflicts(
+                        storage,
+                        table_name,
+                        &column_defs,
+                        &constraints.uniques,
+                        on_conflict,
+                        rows,
+                    )
+                    .await?
+                }
+                None => (rows, Vec::new()),
+            };
+            let rows = validate_vec_rows(
                 storage,
                 table_name,
-                column_defs,
-                columns,
-                source,
-                foreign_keys,
+                &column_defs,
+                &labels,
+                constraints,
+                rows,
             )
-            .await
+            .await?;
+
+            (rows, conflicts)
+        }
+        None if on_conflict.is_some() => {
+            return Err(InsertError::OnConflictOnSchemalessTable(table_name.to_owned()).into());
         }
-        None => fetch_map_rows(storage, source).await.map(RowsData::Append),
-    }?;
+        None => (
+            RowsData::Append(fetch_map_rows(storage, source).await?),
+            Vec::new(),
+        ),
+    };
+
+    let updated = match on_conflict {
+        Some(OnConflict {
+            action:
+                OnConflictAction::DoUpdate {
+                    assignments,
+                    selection,
+                },
+            ..
+        }) if !conflicts.is_empty() => {
+            let mut rows = Vec::new();
+            for (key, values, excluded) in conflicts {
+                let row = Row::Vec {
+                    columns: Arc::clone(&labels),
+                    values,
+                };
+                let excluded = Row::Vec {
+                    columns: Arc::clone(&labels),
+                    values: excluded,
+                };
+
+                if let Some(selection) = selection {
+                    let context = update_context(table_name, &row, Some(&excluded));
+
+                    if !check_expr(storage, Some(context), None, selection).await? {
+                        continue;
+                    }
+                }
+
+                rows.push((key, row, Some(excluded)));
+            }
+
+            let schema = storage
+                .fetch_schema(table_name)
+                .await?
+                .ok_or_else(|| InsertError::TableNotFound(table_name.to_owned()))?;
+
+            Some(UpdatedRows::new(storage, table_name, schema, assignments, rows).await?)
+        }
+        _ => None,
+    };
+
+    let returning = Returning::new(storage, table_name, returning).await?;
+    let returned = returning.map(|returning| {
+        let inserted_rows: Vec<_> = match &rows {
+            RowsData::Append(rows) => rows.iter().collect(),
+            RowsData::Insert(rows) => rows.iter().map(|(_, row)| row).collect(),
+        };
+        let rows = inserted_rows
+            .into_iter()
+            .map(|row| into_row(&labels, row))
+            .chain(updated.iter().flat_map(UpdatedRows::rows).cloned())
+            .collect::<Vec<_>>();
+
+        (returning, rows)
+    });
+    let changes = match &rows {
+        _ if triggers.is_empty() => Vec::new(),
+        RowsData::Append(rows) => rows.iter().map(|row| inserted(&labels, row)).collect(),
+        RowsData::Insert(rows) => rows.iter().map(|(_, row)| inserted(&labels, row)).collect(),
+    };
+    triggers
+        .fire(storage, TriggerTiming::Before, &changes)
+        .await?;
 
-    match rows {
+    let num_rows = match rows {
         RowsData::Append(rows) => {
             let num_rows = rows.len();
 
-            storage
-                .append_data(table_name, rows)
-                .await
-                .map(|_| num_rows)
+            storage.append_data(table_name, rows).await?;
+            num_rows
         }
         RowsData::Insert(rows) => {
             let num_rows = rows.len();
 
-            storage
-                .insert_data(table_name, rows)
-                .await
-                .map(|_| num_rows)
+            storage.insert_data(table_name, rows).await?;
+            num_rows
         }
+    };
+
+    triggers
+        .fire(storage, TriggerTiming::After, &changes)
+        .await?;
+
+    let num_updated = match updated {
+        Some(updated) => updated.write(storage).await?,
+        None => 0,
+    };
+
+    match returned {
+        Some((returning, rows)) => returning.project(storage, rows).await,
+        None => Ok(Payload::Insert(num_rows + num_updated)),
+    }
+}
+
+fn into_row(labels: &Arc<[String]>, row: &DataRow) -> Row {
+    match row {
+        DataRow::Vec(values) => Row::Vec {
+            columns: Arc::clone(labels),
+            values: values.clone(),
+        },
+        DataRow::Map(values) => Row::Map(values.clone()),
+    }
+}
+
+fn inserted(labels: &Arc<[String]>, row: &DataRow) -> RowChange {
+    RowChange {
+        old: None,
+        new: Some(into_row(labels, row)),
     }
 }
 
 async fn fetch_vec_rows<T: GStore>(
     storage: &T,
-    table_name: &str,
-    column_defs: Vec<ColumnDef>,
+    column_defs: &Arc<[ColumnDef]>,
+    labels: &Arc<[String]>,
     columns: &[String],
     source: &Query,
-    foreign_keys: Vec<ForeignKey>,
-) -> Result<RowsData> {
-    let labels = Arc::from(
-        column_defs
-            .iter()
-            .map(|column_def| column_def.name.to_owned())
-            .collect::<Vec<_>>(),
-    );
-    let column_defs = Arc::from(column_defs);
-    let column_validation = ColumnValidation::All(&column_defs);
-
+) -> Result<Vec<Vec<Value>>> {
     #[derive(futures_enum::Stream)]
     enum Rows<I1, I2> {
         Values(I1),
@@ -134,8 +289,8 @@ async fn fetch_vec_rows<T: GStore>(
         SetExpr::Values(Values(values_list)) => {
             let limit = Limit::new(source.limit.as_ref(), source.offset.as_ref()).await?;
             let rows = stream::iter(values_list).then(|values| {
-                let column_defs = Arc::clone(&column_defs);
-                let labels = Arc::clone(&labels);
+                let column_defs = Arc::clone(column_defs);
+                let labels = Arc::clone(labels);
 
                 async move {
                     Ok(Row::Vec {
@@ -149,7 +304,7 @@ async fn fetch_vec_rows<T: GStore>(
 
             Rows::Values(rows)
         }
-        SetExpr::Select(_) => {
+        SetExpr::Select(_) | SetExpr::SetOperation { .. } => {
             let rows = select(storage, source, None).await?.map(|row| {
                 let values = row?.try_into_vec()?;
 
@@ -176,32 +331,252 @@ async fn fetch_vec_rows<T: GStore>(
     .try_collect::<Vec<Vec<Value>>>()
     .await?;
 
+    Ok(rows)
+}
+
+/// Splits the rows into the rows to insert and the rows conflicting on the unique columns
+/// `ON CONFLICT` looks at, paired with the key and values of the row they conflict with.
+///
+/// A row conflicting with a row inserted by the same statement is skipped by `DO NOTHING`,
+/// while `DO UPDATE` fails rather than update a row twice.
+async fn split_conflicts<T: GStore>(
+    storage: &T,
+    table_name: &str,
+    column_defs: &[ColumnDef],
+    uniques: &[UniqueConstraint],
+    on_conflict: &OnConflict,
+    rows: Vec<Vec<Value>>,
+) -> Result<(Vec<Vec<Value>>, Vec<(Key, Vec<Value>, Vec<Value>)>)> {
+    let OnConflict { target, action } = on_conflict;
+    let do_update = matches!(action, OnConflictAction::DoUpdate { .. });
+    if do_update && target.is_empty() {
+        return Err(InsertError::ConflictTargetRequired.into());
+    }
+
+    let arbiters = arbiter_columns(column_defs, uniques, target)?;
+    let arbiter_keys = |values: &[Value]| {
+        arbiters
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (columns, _))| {
+                let values = columns
+                    .iter()
+                    .map(|column| values.get(*column).filter(|value| !value.is_null()))
+                    .collect::<Option<Vec<_>>>()?;
+
+                let key = match values.as_slice() {
+                    [value] => Key::try_from(*value),
+                    _ => values
+                        .into_iter()
+                        .map(Key::try_from)
+                        .collect::<Result<Vec<_>>>()
+                        .map(Key::Composite),
+                };
+
+                Some(key.map(|key| (i, key)))
+            })
+            .collect::<Result<Vec<_>>>()
+    };
+
+    let mut proposed = HashSet::new();
+    for values in rows.iter() {
+        proposed.extend(arbiter_keys(values)?);
+    }
+
+    let mut existing = HashMap::new();
+    for (i, key) in proposed.iter() {
+        if !arbiters[*i].1 {
+            continue;
+        }
+
+        match storage.fetch_data(table_name, key).await? {
+            Some(DataRow::Vec(values)) => {
+                existing.insert((*i, key.clone()), (key.clone(), values));
+            }
+            Some(DataRow::Map(_)) => {
+                return Err(ValidateError::ConflictOnUnexpectedSchemalessRowFound.into());
+            }
+            None => {}
+        }
+    }
+
+    if arbiters.iter().any(|(_, is_primary)| !is_primary) {
+        let stored = storage
+            .scan_data(table_name)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        for (key, row) in stored {
+            let DataRow::Vec(values) = row else {
+                return Err(ValidateError::ConflictOnUnexpectedSchemalessRowFound.into());
+            };
+
+            for arbiter_key in arbiter_keys(&values)? {
+                if proposed.contains(&arbiter_key) {
+                    existing.insert(arbiter_key, (key.clone(), values.clone()));
+                }
+            }
+        }
+    }
+
+    let mut inserted = HashSet::new();
+    let mut updated = HashSet::new();
+    let mut rows_to_insert = Vec::new();
+    let mut conflicts = Vec::new();
+
+    for values in rows {
+        let keys = arbiter_keys(&values)?;
+
+        if keys.iter().any(|key| inserted.contains(key)) {
+            if do_update {
+                return Err(InsertError::ConflictRowAffectedTwice.into());
+            }
+
+            continue;
+        }
+
+        match keys.iter().find_map(|key| existing.get(key)) {
+            Some((key, stored)) if do_update => {
+                if !updated.insert(key.clone()) {
+                    return Err(InsertError::ConflictRowAffectedTwice.into());
+                }
+
+                conflicts.push((key.clone(), stored.clone(), values));
+            }
+            Some(_) => {}
+            None => {
+                inserted.extend(keys);
+                rows_to_insert.push(values);
+            }
+        }
+    }
+
+    Ok((rows_to_insert, conflicts))
+}
+
+/// Returns the column indexes of each unique key `ON CONFLICT` looks at, and whether it is the
+/// primary key, taking all unique keys when no target is given.
+///
+/// The columns of a composite primary key or of a `UNIQUE` constraint are looked at together,
+/// only when the target names all of them.
+fn arbiter_columns(
+    column_defs: &[ColumnDef],
+    uniques: &[UniqueConstraint],
+    target: &[String],
+) -> Result<Vec<(Vec<usize>, bool)>> {
+    let position = |column_name: &String| {
+        column_defs
+            .iter()
+            .position(|column_def| &column_def.name == column_name)
+            .ok_or_else(|| InsertError::WrongColumnName(column_name.to_owned()).into())
+    };
+
+    let primary_key = column_defs
+        .iter()
+        .enumerate()
+        .filter(|(_, ColumnDef { unique, .. })| {
+            unique == &Some(ColumnUniqueOption { is_primary: true })
+        })
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let groups = (!primary_key.is_empty())
+        .then_some(Ok((primary_key, true)))
+        .into_iter()
+        .chain(uniques.iter().map(|UniqueConstraint { columns, .. }| {
+            columns
+                .iter()
+                .map(position)
+                .collect::<Result<Vec<_>>>()
+                .map(|columns| (columns, false))
+        }))
+        .collect::<Result<Vec<_>>>()?;
+    let unique_columns = column_defs
+        .iter()
+        .enumerate()
+        .filter(|(_, ColumnDef { unique, .. })| {
+            unique == &Some(ColumnUniqueOption { is_primary: false })
+        })
+        .map(|(i, _)| (vec![i], false));
+
+    if target.is_empty() {
+        return Ok(groups.into_iter().chain(unique_columns).collect());
+    }
+
+    let target = target.iter().map(position).collect::<Result<Vec<_>>>()?;
+    let groups = groups
+        .into_iter()
+        .filter(|(columns, _)| columns.iter().all(|i| target.contains(i)))
+        .collect::<Vec<_>>();
+
+    let mut arbiters = groups.clone();
+    for i in target {
+        if groups.iter().any(|(columns, _)| columns.contains(&i)) {
+            continue;
+        }
+
+        match column_defs[i].unique {
+            Some(ColumnUniqueOption { is_primary: false }) => arbiters.push((vec![i], false)),
+            _ => {
+                return Err(
+                    InsertError::ConflictTargetNotUnique(column_defs[i].name.to_owned()).into(),
+                );
+            }
+        }
+    }
+
+    Ok(arbiters)
+}
+
+async fn validate_vec_rows<T: GStore>(
+    storage: &T,
+    table_name: &str,
+    column_defs: &Arc<[ColumnDef]>,
+    labels: &Arc<[String]>,
+    constraints: Constraints,
+    rows: Vec<Vec<Value>>,
+) -> Result<RowsData> {
+    let Constraints {
+        foreign_keys,
+        checks,
+        uniques,
+    } = constraints;
+    let column_validation = ColumnValidation::All(column_defs);
+
     validate_unique(
         storage,
         table_name,
         column_validation,
+        &uniques,
         rows.iter().map(|values| values.as_slice()),
     )
     .await?;
 
-    validate_foreign_key(storage, &column_defs, foreign_keys, &rows).await?;
+    validate_foreign_key(storage, column_defs, foreign_keys, &rows).await?;
+    validate_checks(
+        storage,
+        table_name,
+        &checks,
+        labels,
+        rows.iter().map(|values| values.as_slice()),
+    )
+    .await?;
 
-    let primary_key = column_defs.iter().position(|ColumnDef { unique, .. }| {
-        unique == &Some(ColumnUniqueOption { is_primary: true })
-    });
+    let has_primary_key = column_defs
+        .iter()
+        .any(|ColumnDef { unique, .. }| unique == &Some(ColumnUniqueOption { is_primary: true }));
 
-    match primary_key {
-        Some(i) => rows
+    match has_primary_key {
+        true => rows
             .into_iter()
             .filter_map(|values| {
-                values
-                    .get(i)
-                    .map(Key::try_from)
+                Key::primary(column_defs, &values)
+                    .transpose()
                     .map(|result| result.map(|key| (key, values.into())))
             })
             .collect::<Result<Vec<_>>>()
             .map(RowsData::Insert),
-        None => Ok(RowsData::Append(rows.into_iter().map(Into::into).collect())),
+        false => Ok(RowsData::Append(rows.into_iter().map(Into::into).collect())),
     }
 }
 
@@ -282,7 +657,7 @@ async fn fetch_map_rows<T: GStore>(storage: &T, source: &Query) -> Result<Vec<Da
 
             Rows::Values(rows)
         }
-        SetExpr::Select(_) => {
+        SetExpr::Select(_) | SetExpr::SetOperation { .. } => {
             let rows = select(storage, source, None).await?.map(|row| {
                 let row = row?;
 
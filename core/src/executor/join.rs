@@ -6,17 +6,21 @@ use {
             JoinOperator as AstJoinOperator, TableFactor,
         },
         data::{Key, Row, Value, get_alias},
-        executor::{context::RowContext, evaluate::evaluate, filter::check_expr},
-        result::Result,
+        executor::{
+            context::RowContext, evaluate::evaluate, filter::check_expr, select::SelectError,
+        },
+        result::{Error, Result},
         store::GStore,
     },
     futures::{
         future,
+        lock::Mutex,
         stream::{self, Stream, StreamExt, TryStreamExt, empty, once},
     },
     itertools::Itertools,
     std::{
         borrow::Cow,
+        cmp::Ordering,
         collections::{BTreeMap, HashMap},
         pin::Pin,
         sync::Arc,
@@ -129,14 +133,15 @@ async fn join<'a, T: GStore>(
             let filter_context = Some(filter_context);
 
             #[derive(futures_enum::Stream)]
-            enum Rows<I1, I2, I3> {
+            enum Rows<I1, I2, I3, I4> {
                 NestedLoop(I1),
                 Hash(I2),
-                Empty(I3),
+                Merge(I3),
+                Empty(I4),
             }
             let rows = match join_executor.as_ref() {
                 JoinExecutor::NestedLoop => {
-                    let rows = fetch_relation_rows(storage, relation, &filter_context)
+                    let rows = fetch_relation_rows(storage, relation, &filter_context, None)
                         .await?
                         .and_then(|row| future::ok(Cow::Owned(row)))
                         .try_filter_map(move |row| {
@@ -193,6 +198,44 @@ async fn join<'a, T: GStore>(
                         }
                     }
                 }
+                JoinExecutor::Merge { rows, value_expr } => {
+                    let key = evaluate(
+                        storage,
+                        filter_context.as_ref().map(Arc::clone),
+                        None,
+                        value_expr,
+                    )
+                    .await
+                    .map(Key::try_from)??;
+
+                    if matches!(key, Key::None) {
+                        Rows::Empty(empty())
+                    } else {
+                        let group = rows.lock().await.get(key).await?;
+                        let rows = stream::iter(group.iter())
+                            .filter_map(|row| {
+                                let filter_context = filter_context.as_ref().map(Arc::clone);
+                                let project_context = Some(Arc::clone(&project_context));
+
+                                async {
+                                    check_where_clause(
+                                        storage,
+                                        table_alias,
+                                        filter_context,
+                                        project_context,
+                                        where_clause,
+                                        Cow::Borrowed(row),
+                                    )
+                                    .await
+                                    .transpose()
+                                }
+                            })
+                            .collect::<Vec<_>>()
+                            .await;
+
+                        Rows::Merge(stream::iter(rows))
+                    }
+                }
             };
 
             let rows: Joined = match join_operator {
@@ -223,61 +266,179 @@ enum JoinExecutor<'a> {
         rows_map: HashMap<Key, Vec<Row>>,
         value_expr: &'a Expr,
     },
+    Merge {
+        rows: Box<Mutex<MergeRows<'a>>>,
+        value_expr: &'a Expr,
+    },
 }
 
 impl<'a> JoinExecutor<'a> {
     async fn new<T: GStore>(
         storage: &'a T,
-        relation: &TableFactor,
+        relation: &'a TableFactor,
         filter_context: Option<Arc<RowContext<'a>>>,
         ast_join_executor: &'a AstJoinExecutor,
     ) -> Result<JoinExecutor<'a>> {
-        let (key_expr, value_expr, where_clause) = match ast_join_executor {
-            AstJoinExecutor::NestedLoop => return Ok(Self::NestedLoop),
+        match ast_join_executor {
+            AstJoinExecutor::NestedLoop => Ok(Self::NestedLoop),
             AstJoinExecutor::Hash {
                 key_expr,
                 value_expr,
                 where_clause,
-            } => (key_expr, value_expr, where_clause),
-        };
+            } => {
+                let rows_map = fetch_keyed_rows(
+                    storage,
+                    relation,
+                    filter_context,
+                    key_expr,
+                    where_clause.as_ref(),
+                )
+                .await?
+                .try_collect::<Vec<_>>()
+                .await?
+                .into_iter()
+                .into_group_map();
+
+                Ok(Self::Hash {
+                    rows_map,
+                    value_expr,
+                })
+            }
+            AstJoinExecutor::Merge {
+                key_expr,
+                value_expr,
+                where_clause,
+            } => {
+                let rows = fetch_keyed_rows(
+                    storage,
+                    relation,
+                    filter_context,
+                    key_expr,
+                    where_clause.as_ref(),
+                )
+                .await?;
+                let rows = MergeRows {
+                    table_alias: get_alias(relation),
+                    rows: Box::pin(rows),
+                    read: None,
+                    next: None,
+                    key: None,
+                    group: Arc::new(Vec::new()),
+                };
+
+                Ok(Self::Merge {
+                    rows: Box::new(Mutex::new(rows)),
+                    value_expr,
+                })
+            }
+        }
+    }
+}
+
+/// Rows of the joined relation paired with their keys of `key_expr`, leaving out the rows of
+/// `NULL` keys and those `where_clause` does not pass.
+async fn fetch_keyed_rows<'a, T: GStore>(
+    storage: &'a T,
+    relation: &'a TableFactor,
+    filter_context: Option<Arc<RowContext<'a>>>,
+    key_expr: &'a Expr,
+    where_clause: Option<&'a Expr>,
+) -> Result<impl Stream<Item = Result<(Key, Row)>> + Send + 'a> {
+    let rows = fetch_relation_rows(storage, relation, &filter_context, None)
+        .await?
+        .try_filter_map(move |row| {
+            let filter_context = filter_context.as_ref().map(Arc::clone);
+
+            async move {
+                let filter_context = Arc::new(RowContext::new(
+                    get_alias(relation),
+                    Cow::Borrowed(&row),
+                    filter_context,
+                ));
+
+                let key: Key = evaluate(storage, Some(Arc::clone(&filter_context)), None, key_expr)
+                    .await?
+                    .try_into()?;
+
+                if matches!(key, Key::None) {
+                    return Ok(None);
+                }
 
-        let rows_map = fetch_relation_rows(storage, relation, &filter_context)
-            .await?
-            .try_filter_map(|row| {
-                let filter_context = filter_context.as_ref().map(Arc::clone);
-
-                async move {
-                    let filter_context = Arc::new(RowContext::new(
-                        get_alias(relation),
-                        Cow::Borrowed(&row),
-                        filter_context,
-                    ));
-
-                    let hash_key: Key =
-                        evaluate(storage, Some(Arc::clone(&filter_context)), None, key_expr)
-                            .await?
-                            .try_into()?;
-
-                    if matches!(hash_key, Key::None) {
-                        return Ok(None);
+                match where_clause {
+                    Some(expr) => check_expr(storage, Some(filter_context), None, expr)
+                        .await
+                        .map(|pass| pass.then_some((key, row))),
+                    None => Ok(Some((key, row))),
+                }
+            }
+        });
+
+    Ok(rows)
+}
+
+type KeyedRows<'a> = Pin<Box<dyn Stream<Item = Result<(Key, Row)>> + Send + 'a>>;
+
+/// Rows of the joined relation of a merge join, read in the order of their keys as the rows
+/// they are joined with come in the same order.
+struct MergeRows<'a> {
+    table_alias: &'a str,
+    rows: KeyedRows<'a>,
+    /// Key of the row last read from `rows`.
+    read: Option<Key>,
+    /// Row read ahead, of the first key greater than `key`.
+    next: Option<(Key, Row)>,
+    /// Key last looked up, of which `group` holds the rows.
+    key: Option<Key>,
+    group: Arc<Vec<Row>>,
+}
+
+impl MergeRows<'_> {
+    /// Rows of the key, skipping the rows of the keys less than it. Keys are looked up in
+    /// ascending order, the same key repeatedly for consecutive rows of it.
+    async fn get(&mut self, key: Key) -> Result<Arc<Vec<Row>>> {
+        match self.key.as_ref().map(|current| key.cmp(current)) {
+            Some(Ordering::Equal) => return Ok(Arc::clone(&self.group)),
+            Some(Ordering::Less) => return Err(self.unordered()),
+            Some(Ordering::Greater) | None => {}
+        }
+
+        let mut group = Vec::new();
+        loop {
+            let (row_key, row) = match self.next.take() {
+                Some(next) => next,
+                None => match self.rows.try_next().await? {
+                    Some((row_key, _))
+                        if self.read.as_ref().is_some_and(|read| &row_key < read) =>
+                    {
+                        return Err(self.unordered());
                     }
+                    Some((row_key, row)) => {
+                        self.read = Some(row_key.clone());
 
-                    match where_clause {
-                        Some(expr) => check_expr(storage, Some(filter_context), None, expr)
-                            .await
-                            .map(|pass| pass.then_some((hash_key, row))),
-                        None => Ok(Some((hash_key, row))),
+                        (row_key, row)
                     }
+                    None => break,
+                },
+            };
+
+            match row_key.cmp(&key) {
+                Ordering::Less => continue,
+                Ordering::Equal => group.push(row),
+                Ordering::Greater => {
+                    self.next = Some((row_key, row));
+                    break;
                 }
-            })
-            .try_collect::<Vec<_>>()
-            .await?
-            .into_iter()
-            .into_group_map();
-        Ok(Self::Hash {
-            rows_map,
-            value_expr,
-        })
+            }
+        }
+
+        self.key = Some(key);
+        self.group = Arc::new(group);
+
+        Ok(Arc::clone(&self.group))
+    }
+
+    fn unordered(&self) -> Error {
+        SelectError::MergeJoinRowsUnordered(self.table_alias.to_owned()).into()
     }
 }
 
@@ -33,6 +33,13 @@ impl Limit {
         Ok(Self { limit, offset })
     }
 
+    /// Rows the limit reads of its input, those `OFFSET` skips and those `LIMIT` takes, `None`
+    /// without `LIMIT`.
+    pub fn rows(&self) -> Option<usize> {
+        self.limit
+            .map(|limit| limit.saturating_add(self.offset.unwrap_or(0)))
+    }
+
     pub fn apply<'a, T: Stream<Item = Result<Row>> + 'a>(
         &self,
         rows: T,
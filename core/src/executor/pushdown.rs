@@ -0,0 +1,373 @@
+use crate::{
+    ast::{AstLiteral, BinaryOperator, ColumnDef, DataType, Expr, TableAlias, TableFactor},
+    data::{Literal, Value},
+    result::Result,
+    store::{CompareOperator, GStore, Predicate},
+};
+
+/// Predicate of the `WHERE` clause for the storage to check while scanning the table, `None`
+/// when the relation is not a table scanned in full or no condition translates.
+///
+/// Conditions joined by `AND` translate one by one and the others are left to the executor,
+/// which still checks the whole `WHERE` clause. A condition compares a column of the table
+/// with a literal, and `OR` translates only when all of its conditions do. Unqualified columns
+/// are read from the table only when `unqualified`, which callers set when the query joins no
+/// other relation.
+pub(super) async fn fetch_predicate<T: GStore>(
+    storage: &T,
+    relation: &TableFactor,
+    where_clause: Option<&Expr>,
+    unqualified: bool,
+) -> Result<Option<Predicate>> {
+    let (
+        TableFactor::Table {
+            name,
+            alias,
+            index: None,
+        },
+        Some(where_clause),
+    ) = (relation, where_clause)
+    else {
+        return Ok(None);
+    };
+
+    let alias = match alias {
+        Some(TableAlias { columns, .. }) if !columns.is_empty() => return Ok(None),
+        Some(TableAlias { name, .. }) => name,
+        None => name,
+    };
+
+    let Some(column_defs) = storage
+        .fetch_schema(name)
+        .await?
+        .and_then(|schema| schema.column_defs)
+    else {
+        return Ok(None);
+    };
+
+    let translator = Translator {
+        alias,
+        unqualified,
+        column_defs: &column_defs,
+    };
+    let mut predicates = Vec::new();
+    translator.conjuncts(where_clause, &mut predicates);
+
+    let predicate = match predicates.len() {
+        0 => None,
+        1 => predicates.pop(),
+        _ => Some(Predicate::And(predicates)),
+    };
+
+    Ok(predicate)
+}
+
+struct Translator<'a> {
+    alias: &'a str,
+    unqualified: bool,
+    column_defs: &'a [ColumnDef],
+}
+
+impl Translator<'_> {
+    fn conjuncts(&self, expr: &Expr, predicates: &mut Vec<Predicate>) {
+        match expr {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => {
+                self.conjuncts(left, predicates);
+                self.conjuncts(right, predicates);
+            }
+            Expr::Nested(expr) => self.conjuncts(expr, predicates),
+            _ => predicates.extend(self.translate(expr)),
+        }
+    }
+
+    /// Predicate matching exactly the rows the condition matches.
+    fn translate(&self, expr: &Expr) -> Option<Predicate> {
+        match expr {
+            Expr::Nested(expr) => self.translate(expr),
+            Expr::BinaryOp {
+                left,
+                op: op @ (BinaryOperator::And | BinaryOperator::Or),
+                right,
+            } => {
+                let predicates = vec![self.translate(left)?, self.translate(right)?];
+
+                Some(match op {
+                    BinaryOperator::And => Predicate::And(predicates),
+                    _ => Predicate::Or(predicates),
+                })
+            }
+            Expr::BinaryOp { left, op, right } => {
+                let op = match op {
+                    BinaryOperator::Eq => CompareOperator::Eq,
+                    BinaryOperator::NotEq => CompareOperator::NotEq,
+                    BinaryOperator::Lt => CompareOperator::Lt,
+                    BinaryOperator::LtEq => CompareOperator::LtEq,
+                    BinaryOperator::Gt => CompareOperator::Gt,
+                    BinaryOperator::GtEq => CompareOperator::GtEq,
+                    _ => return None,
+                };
+
+                match (self.column(left), self.column(right)) {
+                    (Some(column_def), None) => self.compare(column_def, op, right),
+                    (None, Some(column_def)) => self.compare(column_def, op.flip(), left),
+                    _ => None,
+                }
+            }
+            Expr::IsNull(expr) => self
+                .column(expr)
+                .map(|column_def| Predicate::IsNull(column_def.name.to_owned())),
+            Expr::IsNotNull(expr) => self
+                .column(expr)
+                .map(|column_def| Predicate::IsNotNull(column_def.name.to_owned())),
+            Expr::InList {
+                expr,
+                list,
+                negated: false,
+            } => {
+                let column_def = self.column(expr)?;
+                let values = list
+                    .iter()
+                    .filter(|expr| !matches!(expr, Expr::Literal(AstLiteral::Null)))
+                    .map(|expr| self.value(column_def, expr))
+                    .collect::<Option<Vec<_>>>()?;
+
+                Some(Predicate::In {
+                    column: column_def.name.to_owned(),
+                    values,
+                })
+            }
+            Expr::Between {
+                expr,
+                negated: false,
+                low,
+                high,
+            } => {
+                let column_def = self.column(expr)?;
+
+                Some(Predicate::And(vec![
+                    self.compare(column_def, CompareOperator::GtEq, low)?,
+                    self.compare(column_def, CompareOperator::LtEq, high)?,
+                ]))
+            }
+            _ => None,
+        }
+    }
+
+    fn compare(
+        &self,
+        column_def: &ColumnDef,
+        op: CompareOperator,
+        expr: &Expr,
+    ) -> Option<Predicate> {
+        let ordered = !matches!(column_def.data_type, DataType::Boolean);
+        if !ordered && !matches!(op, CompareOperator::Eq | CompareOperator::NotEq) {
+            return None;
+        }
+
+        Some(Predicate::Compare {
+            column: column_def.name.to_owned(),
+            op,
+            value: self.value(column_def, expr)?,
+        })
+    }
+
+    fn column(&self, expr: &Expr) -> Option<&ColumnDef> {
+        let name = match expr {
+            Expr::Identifier(ident) if self.unqualified => ident,
+            Expr::CompoundIdentifier { alias, ident } if alias == self.alias => ident,
+            _ => return None,
+        };
+
+        self.column_defs
+            .iter()
+            .find(|column_def| &column_def.name == name)
+    }
+
+    /// Literal converted to the type of the column, the way the executor converts it to
+    /// compare with the values of the column.
+    fn value(&self, column_def: &ColumnDef, expr: &Expr) -> Option<Value> {
+        let Expr::Literal(ast_literal) = expr else {
+            return None;
+        };
+
+        let literal = Literal::try_from(ast_literal).ok()?;
+        let convertible = matches!(
+            (&column_def.data_type, &literal),
+            (DataType::Boolean, Literal::Boolean(_))
+                | (DataType::Text, Literal::Text(_))
+                | (
+                    DataType::Int8
+                        | DataType::Int16
+                        | DataType::Int32
+                        | DataType::Int
+                        | DataType::Int128
+                        | DataType::Uint8
+                        | DataType::Uint16
+                        | DataType::Uint32
+                        | DataType::Uint64
+                        | DataType::Uint128
+                        | DataType::Float32
+                        | DataType::Float,
+                    Literal::Number(_),
+                )
+        );
+
+        if !convertible {
+            return None;
+        }
+
+        Value::try_from_literal(&column_def.data_type, &literal).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::fetch_predicate,
+        crate::{
+            ast::{TableAlias, TableFactor},
+            data::Value,
+            mock::{MockStorage, run},
+            parse_sql::parse_expr,
+            store::{
+                CompareOperator::{self, *},
+                Predicate,
+            },
+            translate::translate_expr,
+        },
+        futures::executor::block_on,
+    };
+
+    fn table(alias: Option<&str>) -> TableFactor {
+        TableFactor::Table {
+            name: "Item".to_owned(),
+            alias: alias.map(|name| TableAlias {
+                name: name.to_owned(),
+                columns: Vec::new(),
+            }),
+            index: None,
+        }
+    }
+
+    fn predicate(
+        storage: &MockStorage,
+        relation: &TableFactor,
+        sql: &str,
+        unqualified: bool,
+    ) -> Option<Predicate> {
+        let expr = translate_expr(&parse_expr(sql).unwrap()).unwrap();
+
+        block_on(fetch_predicate(storage, relation, Some(&expr), unqualified)).unwrap()
+    }
+
+    fn compare(column: &str, op: CompareOperator, value: Value) -> Predicate {
+        Predicate::Compare {
+            column: column.to_owned(),
+            op,
+            value,
+        }
+    }
+
+    #[test]
+    fn translate() {
+        let storage = run("CREATE TABLE Item (id INTEGER, name TEXT, rate FLOAT, flag BOOLEAN)");
+        let test = |sql, expected| {
+            assert_eq!(
+                predicate(&storage, &table(None), sql, true),
+                expected,
+                "{sql}"
+            )
+        };
+
+        test("id = 1", Some(compare("id", Eq, Value::I64(1))));
+        test("1 < id", Some(compare("id", Gt, Value::I64(1))));
+        test(
+            "Item.rate <= 1.5",
+            Some(compare("rate", LtEq, Value::F64(1.5))),
+        );
+        test(
+            "name <> 'a'",
+            Some(compare("name", NotEq, Value::Str("a".to_owned()))),
+        );
+        test("flag = TRUE", Some(compare("flag", Eq, Value::Bool(true))));
+        test("name IS NULL", Some(Predicate::IsNull("name".to_owned())));
+        test(
+            "name IS NOT NULL",
+            Some(Predicate::IsNotNull("name".to_owned())),
+        );
+        test(
+            "id IN (1, NULL, 3)",
+            Some(Predicate::In {
+                column: "id".to_owned(),
+                values: vec![Value::I64(1), Value::I64(3)],
+            }),
+        );
+        test(
+            "id BETWEEN 1 AND 3",
+            Some(Predicate::And(vec![
+                compare("id", GtEq, Value::I64(1)),
+                compare("id", LtEq, Value::I64(3)),
+            ])),
+        );
+        test(
+            "id = 1 OR (name = 'a' AND rate > 0)",
+            Some(Predicate::Or(vec![
+                compare("id", Eq, Value::I64(1)),
+                Predicate::And(vec![
+                    compare("name", Eq, Value::Str("a".to_owned())),
+                    compare("rate", Gt, Value::F64(0.0)),
+                ]),
+            ])),
+        );
+
+        // conditions not translating are left to the executor
+        test(
+            "id > 1 AND id + 1 = 3 AND (name = 'a')",
+            Some(Predicate::And(vec![
+                compare("id", Gt, Value::I64(1)),
+                compare("name", Eq, Value::Str("a".to_owned())),
+            ])),
+        );
+        test("id = 1 OR id + 1 = 3", None);
+        test("NOT id = 1", None);
+        test("id NOT IN (1, 2)", None);
+        test("id = name", None);
+        test("id = '1'", None);
+        test("flag < TRUE", None);
+        test("missing = 1", None);
+        test("Other.id = 1", None);
+    }
+
+    #[test]
+    fn relation() {
+        let storage = run("CREATE TABLE Item (id INTEGER, name TEXT)");
+        let expected = Some(compare("id", Eq, Value::I64(1)));
+
+        assert_eq!(
+            predicate(&storage, &table(Some("i")), "i.id = 1", false),
+            expected
+        );
+        assert_eq!(
+            predicate(&storage, &table(Some("i")), "Item.id = 1", true),
+            None
+        );
+        assert_eq!(predicate(&storage, &table(None), "id = 1", false), None);
+
+        let aliased = TableFactor::Table {
+            name: "Item".to_owned(),
+            alias: Some(TableAlias {
+                name: "i".to_owned(),
+                columns: vec!["a".to_owned(), "b".to_owned()],
+            }),
+            index: None,
+        };
+        assert_eq!(predicate(&storage, &aliased, "i.a = 1", true), None);
+
+        let storage = run("CREATE TABLE Item");
+        assert_eq!(predicate(&storage, &table(None), "id = 1", true), None);
+    }
+}
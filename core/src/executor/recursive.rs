@@ -0,0 +1,128 @@
+use {
+    super::{
+        context::RowContext,
+        fetch::{FetchError, alias_labels},
+        select::{select, select_with_labels},
+    },
+    crate::{
+        ast::{Expr, Query, SetExpr, TableAlias, TableFactor, TableWithJoins, Values},
+        data::{Row, Value},
+        result::Result,
+        store::GStore,
+    },
+    futures::stream::{StreamExt, TryStreamExt},
+    std::{collections::HashSet, iter, sync::Arc},
+};
+
+/// Recursions a `WITH RECURSIVE` table may take unless the planner sets another limit.
+pub const DEFAULT_MAX_RECURSION_DEPTH: usize = 1000;
+
+/// Rows of a `WITH RECURSIVE` table, running its recursive query on the rows added last until
+/// it adds none.
+pub async fn recursive_rows<'a, T: GStore>(
+    storage: &'a T,
+    table_factor: &'a TableFactor,
+    filter_context: Option<Arc<RowContext<'a>>>,
+) -> Result<Vec<Vec<Value>>> {
+    let TableFactor::Recursive {
+        name,
+        columns,
+        anchor,
+        recursive,
+        all,
+        max_depth,
+        ..
+    } = table_factor
+    else {
+        return Err(FetchError::Unreachable.into());
+    };
+    let max_depth = max_depth.unwrap_or(DEFAULT_MAX_RECURSION_DEPTH);
+
+    let (labels, rows) =
+        select_with_labels(storage, anchor, filter_context.as_ref().map(Arc::clone)).await?;
+    let rows = rows
+        .map(|row| row.and_then(Row::try_into_vec))
+        .try_collect::<Vec<_>>()
+        .await?;
+    let columns = alias_labels(name, columns, labels.unwrap_or_default())?;
+
+    let mut seen = HashSet::new();
+    let mut added = |rows: Vec<Vec<Value>>| match all {
+        true => rows,
+        false => rows
+            .into_iter()
+            .filter(|row| seen.insert(row.clone()))
+            .collect(),
+    };
+
+    let mut working = added(rows);
+    let mut result = working.clone();
+    let mut depth = 0;
+    while !working.is_empty() {
+        if depth == max_depth {
+            return Err(FetchError::RecursionDepthExceeded(name.to_owned(), max_depth).into());
+        }
+        depth += 1;
+
+        let values = working
+            .into_iter()
+            .map(|row| row.into_iter().map(Expr::try_from).collect())
+            .collect::<Result<Vec<_>>>()?;
+        let query = with_working_table(recursive, name, &columns, values);
+        let rows = select(storage, &query, filter_context.as_ref().map(Arc::clone))
+            .await?
+            .map(|row| row.and_then(Row::try_into_vec))
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        working = added(rows);
+        result.extend(working.iter().cloned());
+    }
+
+    Ok(result)
+}
+
+/// Replaces the references to table `name` in the `FROM` clause of the recursive query with
+/// the rows added last.
+fn with_working_table(
+    recursive: &Query,
+    name: &str,
+    columns: &[String],
+    values: Vec<Vec<Expr>>,
+) -> Query {
+    let mut query = recursive.clone();
+    let SetExpr::Select(select) = &mut query.body else {
+        return query;
+    };
+
+    let TableWithJoins { relation, joins } = &mut select.from;
+    for relation in iter::once(relation).chain(joins.iter_mut().map(|join| &mut join.relation)) {
+        let TableFactor::Table {
+            name: table_name,
+            alias,
+            ..
+        } = relation
+        else {
+            continue;
+        };
+        if table_name != name {
+            continue;
+        }
+
+        let alias = TableAlias {
+            name: alias.as_ref().map_or(name, |alias| &alias.name).to_owned(),
+            columns: columns.to_vec(),
+        };
+        *relation = TableFactor::Derived {
+            subquery: Query {
+                body: SetExpr::Values(Values(values.clone())),
+                order_by: Vec::new(),
+                limit: None,
+                offset: None,
+            },
+            alias,
+        };
+    }
+
+    query
+}
@@ -0,0 +1,82 @@
+use {
+    super::{
+        Payload,
+        context::RowContext,
+        execute::ExecuteError,
+        fetch::{FetchError, fetch_columns},
+        select::Project,
+    },
+    crate::{ast::SelectItem, data::Row, result::Result, store::GStore},
+    std::{borrow::Cow, sync::Arc},
+};
+
+/// `RETURNING` of `INSERT`, `UPDATE` or `DELETE`, projecting the rows they write as `SELECT`
+/// would project the rows of the table.
+pub(super) struct Returning<'a> {
+    table_name: &'a str,
+    items: &'a [SelectItem],
+    labels: Vec<String>,
+}
+
+impl<'a> Returning<'a> {
+    /// Resolves the labels of the items, returning `None` without any. The labels are resolved
+    /// before the statement writes its rows, so unknown tables fail it before anything changes.
+    pub async fn new<T: GStore>(
+        storage: &T,
+        table_name: &'a str,
+        items: &'a [SelectItem],
+    ) -> Result<Option<Self>> {
+        if items.is_empty() {
+            return Ok(None);
+        }
+
+        let columns = fetch_columns(storage, table_name).await?;
+        let labels = items
+            .iter()
+            .map(|item| match (item, &columns) {
+                (SelectItem::Expr { label, .. }, _) => Ok(vec![label.to_owned()]),
+                (SelectItem::QualifiedWildcard(alias), _) if alias != table_name => {
+                    Err(FetchError::TableAliasNotFound(alias.to_owned()).into())
+                }
+                (SelectItem::Wildcard | SelectItem::QualifiedWildcard(_), Some(columns)) => {
+                    Ok(columns.clone())
+                }
+                (SelectItem::Wildcard | SelectItem::QualifiedWildcard(_), None) => Err(
+                    ExecuteError::ReturningWildcardOnSchemalessTable(table_name.to_owned()).into(),
+                ),
+            })
+            .collect::<Result<Vec<_>>>()?
+            .concat();
+
+        Ok(Some(Self {
+            table_name,
+            items,
+            labels,
+        }))
+    }
+
+    pub async fn project<T: GStore>(self, storage: &T, rows: Vec<Row>) -> Result<Payload> {
+        let Self {
+            table_name,
+            items,
+            labels,
+        } = self;
+        let columns = Arc::<[String]>::from(labels.clone());
+
+        let mut values = Vec::with_capacity(rows.len());
+        for row in rows {
+            let context = RowContext::new(table_name, Cow::Borrowed(&row), None);
+            let row = Project::new(storage, None, items)
+                .apply(None, Some(Arc::clone(&columns)), Arc::new(context))
+                .await?
+                .try_into_vec()?;
+
+            values.push(row);
+        }
+
+        Ok(Payload::Returning {
+            labels,
+            rows: values,
+        })
+    }
+}
@@ -1,17 +1,21 @@
 mod error;
 mod project;
+mod set_operation;
 
 pub use error::SelectError;
+pub(super) use project::Project;
 use {
-    self::project::Project,
+    self::set_operation::set_operation,
     super::{
         aggregate,
         context::{AggregateContext, RowContext},
         evaluate::evaluate_stateless,
-        fetch::{fetch_labels, fetch_relation_rows},
+        explain::{Analyze, Stage},
+        fetch::{fetch_labels, fetch_relation_partitions, fetch_relation_rows},
         filter::Filter,
         join::Join,
         limit::Limit,
+        pushdown::fetch_predicate,
         sort::Sort,
     },
     crate::{
@@ -21,7 +25,10 @@ use {
         store::GStore,
     },
     async_recursion::async_recursion,
-    futures::stream::{self, Stream, StreamExt, TryStreamExt},
+    futures::{
+        future,
+        stream::{self, Stream, StreamExt, TryStreamExt},
+    },
     std::{
         borrow::Cow,
         collections::{BTreeMap, HashSet},
@@ -30,21 +37,24 @@ use {
     utils::Vector,
 };
 
-fn apply_distinct(rows: Vec<Row>) -> Vec<Row> {
+/// Rows of `SELECT DISTINCT`, given as they come unless an earlier row has the same values,
+/// which holds the values of the distinct rows rather than the rows.
+fn distinct<'a>(
+    rows: impl Stream<Item = Result<Row>> + 'a,
+) -> impl Stream<Item = Result<Row>> + 'a {
     let mut seen = HashSet::new();
 
-    rows.into_iter()
-        .filter(|row| {
-            let key = match row {
-                Row::Vec { values, .. } => values.clone(),
-                Row::Map(map) => {
-                    let sorted_map: BTreeMap<_, _> = map.iter().collect();
-                    sorted_map.into_values().cloned().collect()
-                }
-            };
-            seen.insert(key)
-        })
-        .collect()
+    rows.try_filter(move |row| {
+        let key = match row {
+            Row::Vec { values, .. } => values.clone(),
+            Row::Map(map) => {
+                let sorted_map: BTreeMap<_, _> = map.iter().collect();
+                sorted_map.into_values().cloned().collect()
+            }
+        };
+
+        future::ready(seen.insert(key))
+    })
 }
 
 async fn rows_with_labels(exprs_list: &[Vec<Expr>]) -> Result<(Vec<Row>, Vec<String>)> {
@@ -119,7 +129,6 @@ async fn sort_stateless(rows: Vec<Row>, order_by: &[OrderByExpr]) -> Result<Vec<
     Ok(sorted)
 }
 
-#[async_recursion]
 pub async fn select_with_labels<'a, T>(
     storage: &'a T,
     query: &'a Query,
@@ -128,6 +137,27 @@ pub async fn select_with_labels<'a, T>(
     Option<Vec<String>>,
     impl Stream<Item = Result<Row>> + Send + 'a,
 )>
+where
+    T: GStore,
+{
+    select_analyzed(storage, query, filter_context, None, 1).await
+}
+
+/// Runs the query as [`select_with_labels`] does, counting the rows each operator of its
+/// `SELECT` gives into `analyze` for `EXPLAIN ANALYZE`. The scan and the aggregation of its
+/// `SELECT` are split across `parallelism` tasks when it is above 1, see
+/// [`aggregate::Parallel`].
+#[async_recursion]
+pub(super) async fn select_analyzed<'a, T>(
+    storage: &'a T,
+    query: &'a Query,
+    filter_context: Option<Arc<RowContext<'a>>>,
+    analyze: Option<&'a Analyze>,
+    parallelism: usize,
+) -> Result<(
+    Option<Vec<String>>,
+    impl Stream<Item = Result<Row>> + Send + 'a,
+)>
 where
     T: GStore,
 {
@@ -137,14 +167,13 @@ where
         Values(S1),
     }
 
-    let Select {
-        distinct,
-        from: table_with_joins,
-        selection: where_clause,
-        projection,
-        group_by,
-        having,
-    } = match &query.body {
+    #[derive(futures_enum::Stream)]
+    enum Aggregated<S1, S2> {
+        Sequential(S1),
+        Parallel(S2),
+    }
+
+    let select = match &query.body {
         SetExpr::Select(statement) => statement.as_ref(),
         SetExpr::Values(Values(values_list)) => {
             let limit = Limit::new(query.limit.as_ref(), query.offset.as_ref()).await?;
@@ -155,53 +184,125 @@ where
 
             return Ok((Some(labels), Row::Values(rows)));
         }
+        SetExpr::SetOperation { .. } => {
+            let limit = Limit::new(query.limit.as_ref(), query.offset.as_ref()).await?;
+            let (labels, rows) = set_operation(storage, &query.body, filter_context).await?;
+            let columns = Arc::from(labels.clone());
+            let rows = rows
+                .into_iter()
+                .map(|values| crate::data::Row::Vec {
+                    columns: Arc::clone(&columns),
+                    values,
+                })
+                .collect();
+            let rows = sort_stateless(rows, &query.order_by).await?;
+            let rows = stream::iter(rows.into_iter().map(Ok));
+            let rows = limit.apply(rows);
+
+            return Ok((Some(labels), Row::Values(rows)));
+        }
+    };
+    let Select {
+        distinct: is_distinct,
+        from: table_with_joins,
+        selection: where_clause,
+        projection,
+        group_by,
+        having,
+    } = select;
+
+    let record = move |stage| {
+        if let Some(analyze) = analyze {
+            analyze.record(stage);
+        }
     };
 
     let TableWithJoins { relation, joins } = &table_with_joins;
-    let rows = fetch_relation_rows(storage, relation, &None)
-        .await?
-        .map(move |row| {
-            let row = row?;
-            let alias = get_alias(relation);
-
-            Ok(RowContext::new(alias, Cow::Owned(row), None))
-        });
+    let predicate =
+        fetch_predicate(storage, relation, where_clause.as_ref(), joins.is_empty()).await?;
+    let parallel = match &filter_context {
+        Some(_) => None,
+        None => aggregate::Parallel::new(select, parallelism),
+    };
 
-    let join = Join::new(storage, joins, filter_context.as_ref().map(Arc::clone));
-    let filter = Arc::new(Filter::new(
-        storage,
-        where_clause.as_ref(),
-        filter_context.as_ref().map(Arc::clone),
-        None,
-    ));
     let limit = Limit::new(query.limit.as_ref(), query.offset.as_ref()).await?;
     let sort = Sort::new(
         storage,
         filter_context.as_ref().map(Arc::clone),
         &query.order_by,
+        limit.rows().filter(|_| !is_distinct),
     );
 
-    let rows = join.apply(rows).await?;
-    let rows = rows.try_filter_map(move |project_context| {
-        let filter = Arc::clone(&filter);
+    let rows = match parallel {
+        Some(parallel) => {
+            let partitions = fetch_relation_partitions(storage, relation, predicate, parallelism)
+                .await?
+                .into_iter()
+                .map(|rows| rows.inspect_ok(move |_| record(Stage::Scan)))
+                .collect();
+            let rows = parallel
+                .apply(storage, get_alias(relation), having.as_ref(), partitions)
+                .await?;
 
-        async move {
-            filter
-                .check(Arc::clone(&project_context))
-                .await
-                .map(|pass| pass.then_some(project_context))
+            Aggregated::Parallel(rows)
         }
-    });
+        None => {
+            let rows = fetch_relation_rows(storage, relation, &None, predicate)
+                .await?
+                .inspect_ok(move |_| record(Stage::Scan));
+            let rows = rows.map_ok(move |row| {
+                let alias = get_alias(relation);
+
+                RowContext::new(alias, Cow::Owned(row), None)
+            });
+
+            let join = Join::new(storage, joins, filter_context.as_ref().map(Arc::clone));
+            let filter = Arc::new(Filter::new(
+                storage,
+                where_clause.as_ref(),
+                filter_context.as_ref().map(Arc::clone),
+                None,
+            ));
+
+            let rows = join.apply(rows).await?;
+            let rows = rows
+                .inspect_ok(move |_| record(Stage::Join))
+                .try_filter_map(move |project_context| {
+                    let filter = Arc::clone(&filter);
 
-    let rows = aggregate::apply(
+                    async move {
+                        filter
+                            .check(Arc::clone(&project_context))
+                            .await
+                            .map(|pass| pass.then_some(project_context))
+                    }
+                })
+                .inspect_ok(move |_| record(Stage::Filter));
+
+            let ordered = aggregate::ordered_by(storage, table_with_joins, group_by).await?;
+            let rows = aggregate::apply(
+                storage,
+                projection,
+                group_by,
+                having.as_ref(),
+                filter_context.as_ref().map(Arc::clone),
+                ordered,
+                rows,
+            )
+            .await?;
+
+            Aggregated::Sequential(rows)
+        }
+    };
+    let rows = aggregate::window(
         storage,
         projection,
-        group_by,
-        having.as_ref(),
+        &query.order_by,
         filter_context.as_ref().map(Arc::clone),
         rows,
     )
-    .await?;
+    .await?
+    .inspect_ok(move |_| record(Stage::Aggregate));
 
     let labels = fetch_labels(storage, relation, joins, projection)
         .await?
@@ -227,14 +328,16 @@ where
             Ok((aggregated, next, row))
         }
     });
+    let rows = rows.inspect_ok(move |_| record(Stage::Project));
 
-    let rows = sort.apply(rows, get_alias(relation)).await?;
+    let rows = sort
+        .apply(rows, get_alias(relation))
+        .await?
+        .inspect_ok(move |_| record(Stage::Sort));
 
-    let rows: Box<dyn Stream<Item = Result<crate::data::Row>> + Unpin + Send> = if *distinct {
-        let all_rows: Vec<crate::data::Row> = rows.try_collect().await?;
-        let unique_rows = apply_distinct(all_rows);
-        let unique_stream = stream::iter(unique_rows.into_iter().map(Ok));
-        Box::new(limit.apply(unique_stream))
+    let rows: Box<dyn Stream<Item = Result<crate::data::Row>> + Unpin + Send> = if *is_distinct {
+        let rows = distinct(rows).inspect_ok(move |_| record(Stage::Distinct));
+        Box::new(limit.apply(rows))
     } else {
         Box::new(limit.apply(rows))
     };
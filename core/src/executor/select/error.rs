@@ -1,7 +1,25 @@
-use {serde::Serialize, std::fmt::Debug, thiserror::Error};
+use {
+    crate::ast::{DataType, SetOperator},
+    serde::Serialize,
+    std::fmt::Debug,
+    thiserror::Error,
+};
 
 #[derive(Error, Serialize, Debug, PartialEq, Eq)]
 pub enum SelectError {
     #[error("VALUES lists must all be the same length")]
     NumberOfValuesDifferent,
+
+    #[error("each {0} query must have the same number of columns")]
+    SetOperationColumnsDifferent(SetOperator),
+
+    #[error("{op} types {left} and {right} cannot be matched")]
+    SetOperationTypesDifferent {
+        op: SetOperator,
+        left: DataType,
+        right: DataType,
+    },
+
+    #[error("rows of merge join with '{0}' are not in the order of the join key")]
+    MergeJoinRowsUnordered(String),
 }
@@ -0,0 +1,152 @@
+use {
+    super::{SelectError, select_with_labels},
+    crate::{
+        ast::{DataType, Query, SetExpr, SetOperator},
+        data::{Row, Value},
+        executor::context::RowContext,
+        result::Result,
+        store::GStore,
+    },
+    async_recursion::async_recursion,
+    futures::stream::{StreamExt, TryStreamExt},
+    std::{
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    },
+};
+
+/// Labels and rows of the query body, those of `left` labeling the rows of a set operation.
+#[async_recursion]
+pub async fn set_operation<'a, T>(
+    storage: &'a T,
+    set_expr: &'a SetExpr,
+    filter_context: Option<Arc<RowContext<'a>>>,
+) -> Result<(Vec<String>, Vec<Vec<Value>>)>
+where
+    T: GStore,
+{
+    let SetExpr::SetOperation {
+        op,
+        all,
+        left,
+        right,
+    } = set_expr
+    else {
+        let query = Query {
+            body: set_expr.clone(),
+            order_by: Vec::new(),
+            limit: None,
+            offset: None,
+        };
+        let (labels, rows) = select_with_labels(storage, &query, filter_context).await?;
+        let rows = rows
+            .map(|row| row.and_then(Row::try_into_vec))
+            .try_collect()
+            .await?;
+
+        return Ok((labels.unwrap_or_default(), rows));
+    };
+
+    let (labels, mut left_rows) =
+        set_operation(storage, left, filter_context.as_ref().map(Arc::clone)).await?;
+    let (right_labels, mut right_rows) = set_operation(storage, right, filter_context).await?;
+    if labels.len() != right_labels.len() {
+        return Err(SelectError::SetOperationColumnsDifferent(*op).into());
+    }
+
+    for i in 0..labels.len() {
+        let data_type = |rows: &[Vec<Value>]| rows.iter().find_map(|row| row[i].get_type());
+        let (Some(left_type), Some(right_type)) = (data_type(&left_rows), data_type(&right_rows))
+        else {
+            continue;
+        };
+        if left_type == right_type {
+            continue;
+        }
+
+        let data_type = common_type(&left_type, &right_type).ok_or_else(|| {
+            SelectError::SetOperationTypesDifferent {
+                op: *op,
+                left: left_type.clone(),
+                right: right_type.clone(),
+            }
+        })?;
+        for row in left_rows.iter_mut().chain(right_rows.iter_mut()) {
+            if row[i].get_type().is_some_and(|found| found != data_type) {
+                row[i] = row[i].cast(&data_type)?;
+            }
+        }
+    }
+
+    let rows = match (op, all) {
+        (SetOperator::Union, true) => {
+            left_rows.extend(right_rows);
+            left_rows
+        }
+        (SetOperator::Union, false) => {
+            let mut seen = HashSet::new();
+
+            left_rows
+                .into_iter()
+                .chain(right_rows)
+                .filter(|row| seen.insert(row.clone()))
+                .collect()
+        }
+        (SetOperator::Except | SetOperator::Intersect, _) => {
+            let intersect = matches!(op, SetOperator::Intersect);
+            let mut counts = HashMap::<Vec<Value>, usize>::new();
+            for row in right_rows {
+                *counts.entry(row).or_default() += 1;
+            }
+
+            // Each row of `right` matches a single row of `left`, so with `ALL` a row kept m
+            // times in `left` and n times in `right` is in min(m, n) rows of `INTERSECT` and in
+            // max(m - n, 0) rows of `EXCEPT`.
+            let mut seen = HashSet::new();
+            left_rows
+                .into_iter()
+                .filter(|row| *all || seen.insert(row.clone()))
+                .filter(|row| {
+                    let matched = counts
+                        .get_mut(row)
+                        .filter(|count| **count > 0)
+                        .map(|count| *count -= 1)
+                        .is_some();
+
+                    matched == intersect
+                })
+                .collect()
+        }
+    };
+
+    Ok((labels, rows))
+}
+
+/// Type both columns are cast to when a set operation matches columns of different types.
+fn common_type(left: &DataType, right: &DataType) -> Option<DataType> {
+    const INTEGERS: [DataType; 10] = [
+        DataType::Int8,
+        DataType::Uint8,
+        DataType::Int16,
+        DataType::Uint16,
+        DataType::Int32,
+        DataType::Uint32,
+        DataType::Int,
+        DataType::Uint64,
+        DataType::Int128,
+        DataType::Uint128,
+    ];
+
+    let rank = |data_type: &DataType| INTEGERS.iter().position(|integer| integer == data_type);
+    let is_float = |data_type: &DataType| matches!(data_type, DataType::Float32 | DataType::Float);
+    let is_numeric = |data_type: &DataType| {
+        rank(data_type).is_some() || is_float(data_type) || data_type == &DataType::Decimal
+    };
+
+    match (left, right) {
+        _ if !is_numeric(left) || !is_numeric(right) => None,
+        _ if is_float(left) || is_float(right) => Some(DataType::Float),
+        (DataType::Decimal, _) | (_, DataType::Decimal) => Some(DataType::Decimal),
+        _ => rank(left).max(rank(right)).map(|i| INTEGERS[i].clone()),
+    }
+}
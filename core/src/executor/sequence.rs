@@ -0,0 +1,310 @@
+use {
+    super::{
+        alter::AlterError,
+        evaluate::{EvaluateError, evaluate_stateless},
+        execute::ExecuteError,
+    },
+    crate::{
+        ast::{
+            AstLiteral, ColumnDef, DataType, Expr, Function, Query, Select, SelectItem, SetExpr,
+            Statement, TableFactor, Values,
+        },
+        data::{Key, Schema, SchemaSequence, Value},
+        result::Result,
+        store::{DataRow, GStore, GStoreMut},
+    },
+    futures::stream::TryStreamExt,
+    std::borrow::Cow,
+};
+
+/// Whether the expression is a call of `NEXTVAL`, `CURRVAL` or `SETVAL`.
+pub(crate) fn is_sequence_function(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Function(function) if matches!(
+            **function,
+            Function::NextVal(_) | Function::CurrVal(_) | Function::SetVal { .. }
+        )
+    )
+}
+
+/// Whether the query calls sequence functions where [`resolve`] replaces them.
+pub(crate) fn calls_sequence_functions(query: &Query) -> bool {
+    match &query.body {
+        SetExpr::Values(Values(rows)) => rows.iter().flatten().any(is_sequence_function),
+        SetExpr::Select(select) if single_row(select) => select.projection.iter().any(
+            |item| matches!(item, SelectItem::Expr { expr, .. } if is_sequence_function(expr)),
+        ),
+        _ => false,
+    }
+}
+
+/// Replaces the sequence functions the statement calls with the values they return, one call
+/// at a time in the order they are written.
+///
+/// Sequences change on `NEXTVAL` and `SETVAL`, which expressions cannot do while they are
+/// evaluated, so the functions are only resolved as whole values of `VALUES` rows or of the
+/// projection of a `SELECT` without `FROM`. Column defaults calling them are written into the
+/// `VALUES` rows of `INSERT` first, giving each inserted row its own value.
+pub(super) async fn resolve<'a, T: GStore + GStoreMut>(
+    storage: &mut T,
+    statement: &'a Statement,
+) -> Result<Cow<'a, Statement>> {
+    match statement {
+        Statement::Query(query) if calls_sequence_functions(query) => {
+            let mut query = query.clone();
+            resolve_query(storage, &mut query).await?;
+
+            Ok(Cow::Owned(Statement::Query(query)))
+        }
+        Statement::Explain {
+            analyze: true,
+            query,
+        } if calls_sequence_functions(query) => {
+            let mut query = query.clone();
+            resolve_query(storage, &mut query).await?;
+
+            Ok(Cow::Owned(Statement::Explain {
+                analyze: true,
+                query,
+            }))
+        }
+        Statement::Insert {
+            table_name,
+            columns,
+            source,
+            on_conflict,
+            returning,
+        } => {
+            let column_defs = storage
+                .fetch_schema(table_name)
+                .await?
+                .and_then(|schema| schema.column_defs)
+                .unwrap_or_default();
+            let defaults = column_defs.iter().any(|column_def| {
+                column_def
+                    .default
+                    .as_ref()
+                    .is_some_and(is_sequence_function)
+            });
+
+            if !defaults && !calls_sequence_functions(source) {
+                return Ok(Cow::Borrowed(statement));
+            }
+
+            let mut columns = columns.clone();
+            let mut source = source.clone();
+            fill_defaults(&column_defs, &mut columns, &mut source);
+            resolve_query(storage, &mut source).await?;
+
+            Ok(Cow::Owned(Statement::Insert {
+                table_name: table_name.clone(),
+                columns,
+                source,
+                on_conflict: on_conflict.clone(),
+                returning: returning.clone(),
+            }))
+        }
+        _ => Ok(Cow::Borrowed(statement)),
+    }
+}
+
+/// Whether the select reads a single row of no table, as `SELECT` without `FROM` does.
+fn single_row(select: &Select) -> bool {
+    let Select {
+        from,
+        selection,
+        group_by,
+        having,
+        ..
+    } = select;
+
+    matches!(
+        &from.relation,
+        TableFactor::Series {
+            size: Expr::Literal(AstLiteral::Number(size)),
+            ..
+        } if *size == 1.into()
+    ) && from.joins.is_empty()
+        && selection.is_none()
+        && group_by.is_empty()
+        && having.is_none()
+}
+
+/// Writes the column defaults calling sequence functions into the `VALUES` rows. Rows which
+/// do not match the columns are left for `INSERT` to reject.
+fn fill_defaults(column_defs: &[ColumnDef], columns: &mut Vec<String>, source: &mut Query) {
+    let SetExpr::Values(Values(rows)) = &mut source.body else {
+        return;
+    };
+    let sequence_default = |column_def: &ColumnDef| {
+        column_def
+            .default
+            .as_ref()
+            .filter(|default| is_sequence_function(default))
+            .cloned()
+    };
+
+    if !columns.is_empty() {
+        if rows.iter().any(|row| row.len() != columns.len()) {
+            return;
+        }
+
+        for column_def in column_defs {
+            let Some(default) = sequence_default(column_def) else {
+                continue;
+            };
+            if columns.contains(&column_def.name) {
+                continue;
+            }
+
+            columns.push(column_def.name.clone());
+            rows.iter_mut().for_each(|row| row.push(default.clone()));
+        }
+
+        return;
+    }
+
+    let Some(last) = column_defs
+        .iter()
+        .rposition(|column_def| sequence_default(column_def).is_some())
+    else {
+        return;
+    };
+
+    for row in rows.iter_mut() {
+        for column_def in column_defs.iter().take(last + 1).skip(row.len()) {
+            let expr = match (&column_def.default, column_def.nullable) {
+                (Some(default), _) => default.clone(),
+                (None, true) => Expr::Literal(AstLiteral::Null),
+                (None, false) => break,
+            };
+
+            row.push(expr);
+        }
+    }
+}
+
+pub(super) async fn resolve_query<T: GStore + GStoreMut>(
+    storage: &mut T,
+    query: &mut Query,
+) -> Result<()> {
+    let exprs = match &mut query.body {
+        SetExpr::Values(Values(rows)) => rows.iter_mut().flatten().collect(),
+        SetExpr::Select(select) if single_row(select) => select
+            .projection
+            .iter_mut()
+            .filter_map(|item| match item {
+                SelectItem::Expr { expr, .. } => Some(expr),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    for expr in exprs {
+        let Expr::Function(function) = expr else {
+            continue;
+        };
+
+        if let Some(value) = call(storage, function).await? {
+            *expr = Expr::try_from(value)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the sequence function, returning `None` for other functions.
+async fn call<T: GStore + GStoreMut>(
+    storage: &mut T,
+    function: &Function,
+) -> Result<Option<Value>> {
+    let value = match function {
+        Function::NextVal(name) => {
+            let name = sequence_name(function, name).await?;
+            let (SchemaSequence { increment, start }, last) = fetch(storage, &name).await?;
+            let value = match &last {
+                Some((_, value)) => value
+                    .checked_add(increment)
+                    .ok_or_else(|| ExecuteError::SequenceOverflow(name.clone()))?,
+                None => start,
+            };
+
+            store(storage, &name, last.map(|(key, _)| key), value).await?
+        }
+        Function::CurrVal(name) => {
+            let name = sequence_name(function, name).await?;
+            let (_, last) = fetch(storage, &name).await?;
+
+            last.map(|(_, value)| Value::I64(value))
+                .ok_or(ExecuteError::SequenceValueNotDefined(name))?
+        }
+        Function::SetVal { name, value } => {
+            let name = sequence_name(function, name).await?;
+            let Value::I64(value) = evaluate_stateless(None, value)
+                .await?
+                .try_into_value(&DataType::Int, false)?
+            else {
+                return Err(
+                    EvaluateError::FunctionRequiresIntegerValue(function.to_string()).into(),
+                );
+            };
+            let (_, last) = fetch(storage, &name).await?;
+
+            store(storage, &name, last.map(|(key, _)| key), value).await?
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(value))
+}
+
+async fn sequence_name(function: &Function, expr: &Expr) -> Result<String> {
+    match evaluate_stateless(None, expr).await?.try_into()? {
+        Value::Str(name) => Ok(name),
+        _ => Err(EvaluateError::FunctionRequiresStringValue(function.to_string()).into()),
+    }
+}
+
+/// Returns the sequence and the key and value of its last value, if `NEXTVAL` or `SETVAL` has
+/// set one.
+async fn fetch<T: GStore>(storage: &T, name: &str) -> Result<(SchemaSequence, Option<(Key, i64)>)> {
+    let Some(Schema {
+        sequence: Some(sequence),
+        ..
+    }) = storage.fetch_schema(name).await?
+    else {
+        return Err(AlterError::SequenceNotFound(name.to_owned()).into());
+    };
+
+    let last = storage
+        .scan_data(name)
+        .await?
+        .try_next()
+        .await?
+        .and_then(|(key, row)| match row {
+            DataRow::Vec(values) => match values.first() {
+                Some(Value::I64(value)) => Some((key, *value)),
+                _ => None,
+            },
+            DataRow::Map(_) => None,
+        });
+
+    Ok((sequence, last))
+}
+
+async fn store<T: GStore + GStoreMut>(
+    storage: &mut T,
+    name: &str,
+    key: Option<Key>,
+    value: i64,
+) -> Result<Value> {
+    let row = DataRow::Vec(vec![Value::I64(value)]);
+    match key {
+        Some(key) => storage.insert_data(name, vec![(key, row)]).await?,
+        None => storage.append_data(name, vec![row]).await?,
+    }
+
+    Ok(Value::I64(value))
+}
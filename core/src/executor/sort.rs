@@ -1,3 +1,6 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod spill;
+
 use {
     super::{context::RowContext, evaluate::evaluate},
     crate::{
@@ -10,9 +13,8 @@ use {
     futures::stream::{self, Stream, StreamExt, TryStreamExt},
     im::HashMap,
     serde::Serialize,
-    std::{borrow::Cow, cmp::Ordering, fmt::Debug, sync::Arc},
+    std::{borrow::Cow, cmp::Ordering, collections::BinaryHeap, fmt::Debug, sync::Arc},
     thiserror::Error as ThisError,
-    utils::Vector,
 };
 
 #[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
@@ -21,12 +23,17 @@ pub enum SortError {
     ColumnIndexOutOfRange(usize),
     #[error("Unreachable ORDER BY Clause")]
     Unreachable,
+    #[error("failed to spill the rows of ORDER BY: {0}")]
+    Spill(String),
 }
 
 pub struct Sort<'a, T: GStore> {
     storage: &'a T,
     context: Option<Arc<RowContext<'a>>>,
     order_by: &'a [OrderByExpr],
+    /// Rows `LIMIT` and `OFFSET` read of the sorted rows, which the sort keeps only the first
+    /// of rather than sorting all the rows.
+    limit: Option<usize>,
 }
 
 impl<'a, T: GStore> Sort<'a, T> {
@@ -34,11 +41,13 @@ impl<'a, T: GStore> Sort<'a, T> {
         storage: &'a T,
         context: Option<Arc<RowContext<'a>>>,
         order_by: &'a [OrderByExpr],
+        limit: Option<usize>,
     ) -> Self {
         Self {
             storage,
             context,
             order_by,
+            limit,
         }
     }
 
@@ -56,9 +65,10 @@ impl<'a, T: GStore> Sort<'a, T> {
         table_alias: &'a str,
     ) -> Result<impl Stream<Item = Result<Row>> + 'a + use<'a, T, U>> {
         #[derive(futures_enum::Stream)]
-        enum Rows<I1, I2> {
+        enum Rows<I1, I2, I3> {
             NonOrderBy(I1),
-            OrderBy(I2),
+            Top(I2),
+            OrderBy(I3),
         }
 
         if self.order_by.is_empty() {
@@ -67,102 +77,185 @@ impl<'a, T: GStore> Sort<'a, T> {
             return Ok(Rows::NonOrderBy(Box::pin(rows)));
         }
 
-        let rows = rows
-            .and_then(|(aggregated, next, row)| {
-                enum SortType<'a> {
-                    Value(Value),
-                    Expr(&'a Expr),
-                }
+        let rows = rows.and_then(|(aggregated, next, row)| {
+            enum SortType<'a> {
+                Value(Value),
+                Expr(&'a Expr),
+            }
 
-                let order_by = self.order_by;
-                let order_by = order_by
-                    .iter()
-                    .map(|OrderByExpr { expr, asc }| -> Result<_> {
-                        let big_decimal = match expr {
+            let order_by = self.order_by;
+            let order_by = order_by
+                .iter()
+                .map(|OrderByExpr { expr, asc }| -> Result<_> {
+                    let big_decimal = match expr {
+                        Expr::Literal(AstLiteral::Number(n)) => Some(n),
+                        Expr::UnaryOp {
+                            op: UnaryOperator::Plus,
+                            expr,
+                        } => match expr.as_ref() {
                             Expr::Literal(AstLiteral::Number(n)) => Some(n),
-                            Expr::UnaryOp {
-                                op: UnaryOperator::Plus,
-                                expr,
-                            } => match expr.as_ref() {
-                                Expr::Literal(AstLiteral::Number(n)) => Some(n),
-                                _ => None,
-                            },
                             _ => None,
-                        };
-
-                        match (big_decimal, &row) {
-                            (Some(n), Row::Vec { values, .. }) => {
-                                let index = n
-                                    .to_usize()
-                                    .ok_or_else(|| -> Error { SortError::Unreachable.into() })?;
-                                let zero_based = index.checked_sub(1).ok_or_else(|| -> Error {
-                                    SortError::ColumnIndexOutOfRange(index).into()
-                                })?;
-                                let value = values.get(zero_based).ok_or_else(|| -> Error {
-                                    SortError::ColumnIndexOutOfRange(index).into()
-                                })?;
-
-                                Ok((SortType::Value(value.clone()), *asc))
-                            }
-                            _ => Ok((SortType::Expr(expr), *asc)),
-                        }
-                    })
-                    .collect::<Result<Vec<_>>>();
+                        },
+                        _ => None,
+                    };
 
-                let filter_context = match &self.context {
-                    Some(context) => {
-                        Arc::new(RowContext::concat(Arc::clone(&next), Arc::clone(context)))
+                    match (big_decimal, &row) {
+                        (Some(n), Row::Vec { values, .. }) => {
+                            let index = n
+                                .to_usize()
+                                .ok_or_else(|| -> Error { SortError::Unreachable.into() })?;
+                            let zero_based = index.checked_sub(1).ok_or_else(|| -> Error {
+                                SortError::ColumnIndexOutOfRange(index).into()
+                            })?;
+                            let value = values.get(zero_based).ok_or_else(|| -> Error {
+                                SortError::ColumnIndexOutOfRange(index).into()
+                            })?;
+
+                            Ok((SortType::Value(value.clone()), *asc))
+                        }
+                        _ => Ok((SortType::Expr(expr), *asc)),
                     }
-                    None => Arc::clone(&next),
-                };
-
-                async move {
-                    let context = RowContext::new(table_alias, Cow::Borrowed(&row), None);
-                    let label_context = Arc::new(context);
-                    let filter_context = Arc::new(RowContext::concat(
-                        filter_context,
-                        Arc::clone(&label_context),
-                    ));
-
-                    let keys = order_by
-                        .map(stream::iter)?
-                        .then(|(sort_type, asc)| {
-                            let context = Some(Arc::clone(&filter_context));
-                            let aggregated = aggregated.as_ref().map(Arc::clone);
-
-                            async move {
-                                match sort_type {
-                                    SortType::Value(value) => value,
-                                    SortType::Expr(expr) => {
-                                        evaluate(self.storage, context, aggregated, expr)
-                                            .await?
-                                            .try_into()?
-                                    }
+                })
+                .collect::<Result<Vec<_>>>();
+
+            let filter_context = match &self.context {
+                Some(context) => {
+                    Arc::new(RowContext::concat(Arc::clone(&next), Arc::clone(context)))
+                }
+                None => Arc::clone(&next),
+            };
+
+            async move {
+                let context = RowContext::new(table_alias, Cow::Borrowed(&row), None);
+                let label_context = Arc::new(context);
+                let filter_context = Arc::new(RowContext::concat(
+                    filter_context,
+                    Arc::clone(&label_context),
+                ));
+
+                let keys = order_by
+                    .map(stream::iter)?
+                    .then(|(sort_type, asc)| {
+                        let context = Some(Arc::clone(&filter_context));
+                        let aggregated = aggregated.as_ref().map(Arc::clone);
+
+                        async move {
+                            match sort_type {
+                                SortType::Value(value) => value,
+                                SortType::Expr(expr) => {
+                                    evaluate(self.storage, context, aggregated, expr)
+                                        .await?
+                                        .try_into()?
                                 }
-                                .try_into()
-                                .map(|key| (key, asc))
                             }
-                        })
-                        .try_collect::<Vec<_>>()
-                        .await?;
+                            .try_into()
+                            .map(|key| (key, asc))
+                        }
+                    })
+                    .try_collect::<Vec<_>>()
+                    .await?;
 
-                    drop(label_context);
-                    drop(filter_context);
+                drop(label_context);
+                drop(filter_context);
 
-                    Ok((keys, row))
-                }
-            })
-            .try_collect::<Vec<(Vec<(Key, Option<bool>)>, Row)>>()
-            .await
-            .map(Vector::from)?
-            .sort_by(|(keys_a, ..), (keys_b, ..)| sort_by(keys_a, keys_b))
-            .into_iter()
-            .map(|(.., row)| Ok(row));
-
-        Ok(Rows::OrderBy(stream::iter(rows)))
+                Ok((keys, row))
+            }
+        });
+        match self.limit {
+            Some(limit) => {
+                let rows = top(rows, limit).await?;
+
+                Ok(Rows::Top(stream::iter(rows.into_iter().map(Ok))))
+            }
+            None => sort(rows).await.map(stream::iter).map(Rows::OrderBy),
+        }
     }
 }
 
+/// All the rows by their keys, spilling them to temporary files past
+/// [`spill::RUN_ROWS`] rows, see [`spill::sort`].
+#[cfg(not(target_arch = "wasm32"))]
+async fn sort(
+    rows: impl Stream<Item = Result<(Vec<(Key, Option<bool>)>, Row)>>,
+) -> Result<spill::Sorted> {
+    spill::sort(rows, spill::RUN_ROWS).await
+}
+
+/// All the rows by their keys, sorted in memory as WebAssembly has no files to spill them to.
+#[cfg(target_arch = "wasm32")]
+async fn sort(
+    rows: impl Stream<Item = Result<(Vec<(Key, Option<bool>)>, Row)>>,
+) -> Result<std::vec::IntoIter<Result<Row>>> {
+    let rows = rows
+        .try_collect::<Vec<(Vec<(Key, Option<bool>)>, Row)>>()
+        .await
+        .map(utils::Vector::from)?
+        .sort_by(|(keys_a, ..), (keys_b, ..)| sort_by(keys_a, keys_b))
+        .into_iter()
+        .map(|(.., row)| Ok(row))
+        .collect::<Vec<_>>();
+
+    Ok(rows.into_iter())
+}
+
+/// First `limit` rows by their keys, holding no more than `limit` rows in a heap while the
+/// rows are read.
+async fn top(
+    rows: impl Stream<Item = Result<(Vec<(Key, Option<bool>)>, Row)>>,
+    limit: usize,
+) -> Result<Vec<Row>> {
+    let heap = rows
+        .into_stream()
+        .enumerate()
+        .map(|(index, row)| row.map(|(keys, row)| Ranked { keys, index, row }))
+        .try_fold(BinaryHeap::new(), |mut heap, ranked| async move {
+            if heap.len() < limit {
+                heap.push(ranked);
+            } else if let Some(mut last) = heap.peek_mut().filter(|last| ranked < **last) {
+                *last = ranked;
+            }
+
+            Ok(heap)
+        })
+        .await?;
+
+    let rows = heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Ranked { row, .. }| row)
+        .collect();
+
+    Ok(rows)
+}
+
+/// Row of a sort, ordered by its keys and then by the order it came in, which keeps rows of
+/// equal keys in the order the stable sort of all the rows gives.
+struct Ranked {
+    keys: Vec<(Key, Option<bool>)>,
+    index: usize,
+    row: Row,
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        sort_by(&self.keys, &other.keys).then(self.index.cmp(&other.index))
+    }
+}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
 pub fn sort_by(keys_a: &[(Key, Option<bool>)], keys_b: &[(Key, Option<bool>)]) -> Ordering {
     let pairs = keys_a
         .iter()
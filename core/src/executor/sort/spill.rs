@@ -0,0 +1,317 @@
+use {
+    super::{SortError, sort_by},
+    crate::{
+        data::{Key, Row, Value},
+        result::{Error, Result},
+    },
+    futures::stream::{Stream, TryStreamExt},
+    serde::{Deserialize, Serialize},
+    std::{
+        cmp::Ordering,
+        collections::{BTreeMap, BinaryHeap},
+        fmt::Display,
+        fs::{self, File, OpenOptions},
+        io::{self, BufReader, BufWriter, Seek, SeekFrom},
+        path::PathBuf,
+        sync::Arc,
+    },
+};
+
+/// Rows sorted in memory at a time, past which the rows are sorted in runs of this many rows
+/// written to temporary files.
+pub const RUN_ROWS: usize = 65_536;
+
+type Keys = Vec<(Key, Option<bool>)>;
+
+/// Sorts the rows by their keys, in memory when there are no more than `run_rows` of them.
+/// More rows are sorted in runs of `run_rows` rows, each written to a temporary file, which
+/// are merged as the sorted rows are read, holding one row of each run in memory. Rows of equal
+/// keys keep the order they came in.
+pub async fn sort(
+    rows: impl Stream<Item = Result<(Keys, Row)>>,
+    run_rows: usize,
+) -> Result<Sorted> {
+    let mut spill = Spill::default();
+    let mut run = Vec::new();
+    let mut rows = Box::pin(rows.into_stream());
+
+    while let Some(row) = rows.try_next().await? {
+        run.push(row);
+
+        if run.len() >= run_rows {
+            spill.write(std::mem::take(&mut run))?;
+        }
+    }
+
+    if spill.runs.is_empty() {
+        run.sort_by(|(keys_a, _), (keys_b, _)| sort_by(keys_a, keys_b));
+
+        return Ok(Sorted::Memory(run.into_iter()));
+    }
+
+    if !run.is_empty() {
+        spill.write(run)?;
+    }
+
+    spill.merge().map(Sorted::Merge)
+}
+
+/// Sorted rows, each either held in memory or read from the runs it was spilled to.
+pub enum Sorted {
+    Memory(std::vec::IntoIter<(Keys, Row)>),
+    Merge(Merge),
+}
+
+impl Iterator for Sorted {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Memory(rows) => rows.next().map(|(_, row)| Ok(row)),
+            Self::Merge(merge) => merge.next(),
+        }
+    }
+}
+
+/// Row as written to a run, with the columns of [`Row::Vec`] kept in [`Spill::columns`].
+#[derive(Serialize, Deserialize)]
+enum Spilled {
+    Vec { columns: usize, values: Vec<Value> },
+    Map(BTreeMap<String, Value>),
+}
+
+#[derive(Default)]
+struct Spill {
+    /// Columns of the rows spilled, shared by most of them.
+    columns: Vec<Arc<[String]>>,
+    runs: Vec<Run>,
+}
+
+impl Spill {
+    fn write(&mut self, mut rows: Vec<(Keys, Row)>) -> Result<()> {
+        rows.sort_by(|(keys_a, _), (keys_b, _)| sort_by(keys_a, keys_b));
+
+        let mut run = Run::create()?;
+        let mut writer = BufWriter::new(run.file.take().ok_or(SortError::Unreachable)?);
+        for (keys, row) in rows {
+            let row = match row {
+                Row::Vec { columns, values } => Spilled::Vec {
+                    columns: self.columns_position(columns),
+                    values,
+                },
+                Row::Map(values) => Spilled::Map(values),
+            };
+
+            bincode::serialize_into(&mut writer, &(keys, row)).map_err(spill_error)?;
+            run.remaining += 1;
+        }
+
+        let mut file = writer
+            .into_inner()
+            .map_err(|error| spill_error(error.into_error()))?;
+        file.seek(SeekFrom::Start(0)).map_err(spill_error)?;
+        run.file = Some(file);
+        self.runs.push(run);
+
+        Ok(())
+    }
+
+    fn columns_position(&mut self, columns: Arc<[String]>) -> usize {
+        let position = self
+            .columns
+            .iter()
+            .position(|spilled| Arc::ptr_eq(spilled, &columns) || *spilled == columns);
+
+        position.unwrap_or_else(|| {
+            self.columns.push(columns);
+            self.columns.len() - 1
+        })
+    }
+
+    fn merge(self) -> Result<Merge> {
+        let Self { columns, runs } = self;
+        let mut merge = Merge {
+            columns,
+            readers: Vec::with_capacity(runs.len()),
+            heads: BinaryHeap::with_capacity(runs.len()),
+        };
+
+        for mut run in runs {
+            let file = run.file.take().ok_or(SortError::Unreachable)?;
+            merge.readers.push(Reader {
+                reader: Some(BufReader::new(file)),
+                run,
+            });
+
+            merge.read(merge.readers.len() - 1)?;
+        }
+
+        Ok(merge)
+    }
+}
+
+/// Rows of the runs merged by their keys, reading the next row of a run once its last row
+/// read is given.
+pub struct Merge {
+    columns: Vec<Arc<[String]>>,
+    readers: Vec<Reader>,
+    heads: BinaryHeap<Head>,
+}
+
+impl Merge {
+    fn read(&mut self, run: usize) -> Result<()> {
+        let reader = &mut self.readers[run];
+        if reader.run.remaining == 0 {
+            reader.reader = None;
+
+            return Ok(());
+        }
+
+        let file = reader.reader.as_mut().ok_or(SortError::Unreachable)?;
+        let (keys, row) = bincode::deserialize_from(file).map_err(spill_error)?;
+        reader.run.remaining -= 1;
+        self.heads.push(Head { keys, run, row });
+
+        Ok(())
+    }
+}
+
+impl Iterator for Merge {
+    type Item = Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Head { run, row, .. } = self.heads.pop()?;
+        let row = match row {
+            Spilled::Vec { columns, values } => match self.columns.get(columns) {
+                Some(columns) => Row::Vec {
+                    columns: Arc::clone(columns),
+                    values,
+                },
+                None => return Some(Err(SortError::Unreachable.into())),
+            },
+            Spilled::Map(values) => Row::Map(values),
+        };
+
+        Some(self.read(run).map(|()| row))
+    }
+}
+
+struct Reader {
+    reader: Option<BufReader<File>>,
+    run: Run,
+}
+
+/// Next row of a run, the rows of earlier runs coming first among rows of equal keys.
+struct Head {
+    keys: Keys,
+    run: usize,
+    row: Spilled,
+}
+
+impl Ord for Head {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed, as the heap gives its greatest item first
+        sort_by(&other.keys, &self.keys).then(other.run.cmp(&self.run))
+    }
+}
+
+impl PartialOrd for Head {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Head {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Head {}
+
+/// Temporary file of a run, removed once the run is dropped.
+struct Run {
+    path: PathBuf,
+    file: Option<File>,
+    /// Rows written to the file and not read yet.
+    remaining: usize,
+}
+
+impl Run {
+    fn create() -> Result<Self> {
+        loop {
+            let name = format!("gluesql-sort-{:016x}", rand::random::<u64>());
+            let path = std::env::temp_dir().join(name);
+            let file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create_new(true)
+                .open(&path);
+
+            match file {
+                Ok(file) => {
+                    return Ok(Self {
+                        path,
+                        file: Some(file),
+                        remaining: 0,
+                    });
+                }
+                Err(error) if error.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(error) => return Err(spill_error(error)),
+            }
+        }
+    }
+}
+
+impl Drop for Run {
+    fn drop(&mut self) {
+        self.file = None;
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn spill_error(error: impl Display) -> Error {
+    SortError::Spill(error.to_string()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::sort,
+        crate::{
+            data::{Key, Row, Value},
+            result::Result,
+        },
+        futures::{executor::block_on, stream},
+        std::sync::Arc,
+    };
+
+    #[test]
+    fn spill() {
+        let columns: Arc<[String]> = Arc::from(vec!["id".to_owned(), "kind".to_owned()]);
+        let rows = (0..10).map(|id| {
+            let keys = vec![(Key::I64(id % 3), Some(false))];
+            let row = Row::Vec {
+                columns: Arc::clone(&columns),
+                values: vec![Value::I64(id), Value::I64(id % 3)],
+            };
+
+            Ok((keys, row))
+        });
+        let ids = |run_rows| {
+            block_on(sort(stream::iter(rows.clone()), run_rows))
+                .unwrap()
+                .map(|row| match row? {
+                    Row::Vec { values, .. } => Ok(values[0].clone()),
+                    Row::Map(_) => unreachable!(),
+                })
+                .collect::<Result<Vec<_>>>()
+                .unwrap()
+        };
+
+        let expected = [2, 5, 8, 1, 4, 7, 0, 3, 6, 9].map(Value::I64).to_vec();
+        assert_eq!(ids(100), expected);
+        assert_eq!(ids(3), expected);
+        assert_eq!(ids(1), expected);
+    }
+}
@@ -0,0 +1,192 @@
+use {
+    super::{
+        context::RowContext, evaluate::EvaluateError, execute::execute_inner, filter::check_expr,
+    },
+    crate::{
+        ast::{AstLiteral, DataType, DateTimeField, Expr, TriggerEvent, TriggerTiming},
+        data::{Row, SchemaTrigger, Value, ValueError},
+        parse_sql::parse,
+        plan::plan,
+        result::{Error, Result},
+        store::{GStore, GStoreMut},
+        translate::translate,
+    },
+    async_recursion::async_recursion,
+    sqlparser::ast::{
+        DataType as SqlDataType, DateTimeField as SqlDateTimeField, ExactNumberInfo,
+        Expr as SqlExpr, Interval as SqlInterval, Statement as SqlStatement, TimezoneInfo,
+        Value as SqlValue, visit_expressions_mut,
+    },
+    std::{borrow::Cow, collections::BTreeMap, ops::ControlFlow, sync::Arc},
+};
+
+/// A row changed by `INSERT`, `UPDATE` or `DELETE`, read by triggers as `OLD` and `NEW`.
+pub(super) struct RowChange {
+    pub old: Option<Row>,
+    pub new: Option<Row>,
+}
+
+/// The triggers of a table set off by one kind of change.
+pub(super) struct Triggers(Vec<SchemaTrigger>);
+
+impl Triggers {
+    pub fn new(triggers: Vec<SchemaTrigger>, event: TriggerEvent) -> Self {
+        let triggers = triggers
+            .into_iter()
+            .filter(|trigger| trigger.events.contains(&event))
+            .collect();
+
+        Self(triggers)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Runs the body of each trigger at `timing` once for every changed row its `WHEN`
+    /// condition holds for.
+    ///
+    /// `BEFORE` triggers run for all the rows before any of them is written, and `AFTER`
+    /// triggers once all of them are.
+    #[async_recursion]
+    pub async fn fire<T>(
+        &self,
+        storage: &mut T,
+        timing: TriggerTiming,
+        changes: &[RowChange],
+    ) -> Result<()>
+    where
+        T: GStore + GStoreMut,
+    {
+        let empty = Row::Map(BTreeMap::new());
+
+        for trigger in self.0.iter().filter(|trigger| trigger.timing == timing) {
+            let body = trigger
+                .body
+                .iter()
+                .map(parse)
+                .collect::<Result<Vec<_>>>()?
+                .concat();
+
+            for RowChange { old, new } in changes {
+                let old =
+                    RowContext::new("OLD", Cow::Borrowed(old.as_ref().unwrap_or(&empty)), None);
+                let context = RowContext::new(
+                    "NEW",
+                    Cow::Borrowed(new.as_ref().unwrap_or(&empty)),
+                    Some(Arc::new(old)),
+                );
+                let context = Arc::new(context);
+
+                if let Some(condition) = &trigger.condition {
+                    let context = Some(Arc::clone(&context));
+                    if !check_expr(storage, context, None, condition).await? {
+                        continue;
+                    }
+                }
+
+                for sql_statement in bind_row(&body, &context)? {
+                    let statement = translate(&sql_statement)?;
+                    let statement = plan(storage, statement).await?;
+
+                    execute_inner(storage, &statement, 1).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The statements of a trigger body with each `NEW.column` and `OLD.column` they read,
+/// in any case, replaced by the value of the column in the changed row.
+fn bind_row(body: &[SqlStatement], context: &RowContext<'_>) -> Result<Vec<SqlStatement>> {
+    let mut body = body.to_vec();
+    let bound = visit_expressions_mut(&mut body, |expr| {
+        let (alias, column) = match expr {
+            SqlExpr::CompoundIdentifier(idents) => match idents.as_slice() {
+                [alias, column]
+                    if alias.value.eq_ignore_ascii_case("NEW")
+                        || alias.value.eq_ignore_ascii_case("OLD") =>
+                {
+                    (alias.value.to_ascii_uppercase(), &column.value)
+                }
+                _ => return ControlFlow::Continue(()),
+            },
+            _ => return ControlFlow::Continue(()),
+        };
+
+        let value = context.get_alias_value(&alias, column).ok_or_else(|| {
+            EvaluateError::CompoundIdentifierNotFound {
+                table_alias: alias.clone(),
+                column_name: column.to_owned(),
+            }
+            .into()
+        });
+
+        match value.and_then(|value| sql_expr(value.clone())) {
+            Ok(value) => {
+                *expr = SqlExpr::Nested(Box::new(value));
+
+                ControlFlow::Continue(())
+            }
+            Err(error) => ControlFlow::Break(error),
+        }
+    });
+
+    match bound {
+        ControlFlow::Continue(()) => Ok(body),
+        ControlFlow::Break(error) => Err(error),
+    }
+}
+
+/// The SQL expression [`translate`] reads back as `value`.
+fn sql_expr(value: Value) -> Result<SqlExpr> {
+    let conversion_failure = || -> Error { ValueError::ValueToExprConversionFailure.into() };
+    let sql_value = |literal| match literal {
+        AstLiteral::Boolean(v) => SqlValue::Boolean(v),
+        AstLiteral::Number(v) => SqlValue::Number(v, false),
+        AstLiteral::QuotedString(v) => SqlValue::SingleQuotedString(v),
+        AstLiteral::HexString(v) => SqlValue::HexStringLiteral(v),
+        AstLiteral::Null => SqlValue::Null,
+    };
+
+    match Expr::try_from(value)? {
+        Expr::Literal(literal) => Ok(SqlExpr::Value(sql_value(literal))),
+        Expr::TypedString { data_type, value } => {
+            let data_type = match data_type {
+                DataType::Date => SqlDataType::Date,
+                DataType::Timestamp => SqlDataType::Timestamp(None, TimezoneInfo::None),
+                DataType::Time => SqlDataType::Time(None, TimezoneInfo::None),
+                DataType::Decimal => SqlDataType::Decimal(ExactNumberInfo::None),
+                _ => return Err(conversion_failure()),
+            };
+
+            Ok(SqlExpr::TypedString { data_type, value })
+        }
+        Expr::Interval {
+            expr,
+            leading_field,
+            last_field: None,
+        } => {
+            let value = match *expr {
+                Expr::Literal(literal) => SqlExpr::Value(sql_value(literal)),
+                _ => return Err(conversion_failure()),
+            };
+            let leading_field = match leading_field {
+                Some(DateTimeField::Month) => SqlDateTimeField::Month,
+                Some(DateTimeField::Second) => SqlDateTimeField::Second,
+                _ => return Err(conversion_failure()),
+            };
+
+            Ok(SqlExpr::Interval(SqlInterval {
+                value: Box::new(value),
+                leading_field: Some(leading_field),
+                leading_precision: None,
+                last_field: None,
+                fractional_seconds_precision: None,
+            }))
+        }
+        _ => Err(conversion_failure()),
+    }
+}
@@ -1,13 +1,22 @@
 use {
     super::{
+        Payload,
         context::RowContext,
         evaluate::{Evaluated, evaluate},
+        execute::ExecuteError,
+        fetch::fetch,
+        returning::Returning,
+        trigger::{RowChange, Triggers},
+        validate::{ColumnValidation, validate_checks, validate_unique},
     },
     crate::{
-        ast::{Assignment, ColumnDef, ColumnUniqueOption, ForeignKey},
-        data::{Key, Row, Value},
+        ast::{
+            Assignment, ColumnDef, ColumnUniqueOption, Expr, ForeignKey, SelectItem, TriggerEvent,
+            TriggerTiming,
+        },
+        data::{Key, Row, Schema, Value},
         result::{Error, Result},
-        store::GStore,
+        store::{GStore, GStoreMut},
     },
     futures::stream::{self, StreamExt, TryStreamExt},
     serde::Serialize,
@@ -36,6 +45,175 @@ pub enum UpdateError {
     },
 }
 
+/// Updates the rows matching `selection`.
+pub async fn update<T: GStore + GStoreMut>(
+    storage: &mut T,
+    table_name: &str,
+    selection: Option<&Expr>,
+    assignments: &[Assignment],
+    returning: &[SelectItem],
+) -> Result<Payload> {
+    let schema = storage
+        .fetch_schema(table_name)
+        .await?
+        .ok_or_else(|| ExecuteError::TableNotFound(table_name.to_owned()))?;
+    let all_columns = schema.column_defs.as_deref().map(|columns| {
+        columns
+            .iter()
+            .map(|col_def| col_def.name.to_owned())
+            .collect()
+    });
+
+    let rows = fetch(storage, table_name, all_columns, selection)
+        .await?
+        .map_ok(|(key, row)| (key, row, None))
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let returning = Returning::new(storage, table_name, returning).await?;
+    let updated = UpdatedRows::new(storage, table_name, schema, assignments, rows).await?;
+    let returned = returning.map(|returning| (returning, updated.rows().cloned().collect()));
+    let num_rows = updated.write(storage).await?;
+
+    match returned {
+        Some((returning, rows)) => returning.project(storage, rows).await,
+        None => Ok(Payload::Update(num_rows)),
+    }
+}
+
+/// The context assignments are evaluated in, reading the row being updated and, for
+/// `INSERT ... ON CONFLICT DO UPDATE`, the row proposed for insertion as `EXCLUDED`.
+pub(super) fn update_context<'a>(
+    table_name: &'a str,
+    row: &'a Row,
+    excluded: Option<&'a Row>,
+) -> Arc<RowContext<'a>> {
+    let excluded = excluded
+        .map(|excluded| Arc::new(RowContext::new("EXCLUDED", Cow::Borrowed(excluded), None)));
+
+    Arc::new(RowContext::new(table_name, Cow::Borrowed(row), excluded))
+}
+
+/// Rows with their assignments applied and validated, waiting to be written.
+pub(super) struct UpdatedRows<'a> {
+    table_name: &'a str,
+    triggers: Triggers,
+    rows: Vec<(Key, Option<Row>, Row)>,
+}
+
+impl<'a> UpdatedRows<'a> {
+    /// Applies the assignments to each row, with the row proposed for insertion it conflicted
+    /// with for `ON CONFLICT DO UPDATE`.
+    pub async fn new<T: GStore>(
+        storage: &T,
+        table_name: &'a str,
+        schema: Schema,
+        assignments: &[Assignment],
+        rows: Vec<(Key, Row, Option<Row>)>,
+    ) -> Result<Self> {
+        let Schema {
+            column_defs,
+            foreign_keys,
+            checks,
+            uniques,
+            triggers,
+            ..
+        } = schema;
+
+        let columns_to_update: Vec<String> = assignments
+            .iter()
+            .map(|assignment| assignment.id.to_owned())
+            .collect();
+
+        let update = Update::new(storage, table_name, assignments, column_defs.as_deref())?;
+        let triggers = Triggers::new(triggers, TriggerEvent::Update);
+
+        let rows = stream::iter(rows)
+            .then(|(key, row, excluded)| {
+                let update = &update;
+                let foreign_keys = &foreign_keys;
+                let old = (!triggers.is_empty()).then(|| row.clone());
+
+                async move {
+                    let row = update.apply(row, excluded.as_ref(), foreign_keys).await?;
+
+                    Ok::<_, Error>((key, old, row))
+                }
+            })
+            .try_collect::<Vec<(Key, Option<Row>, Row)>>()
+            .await?;
+
+        if let Some(column_defs) = column_defs {
+            let keys = rows.iter().map(|(key, _, _)| key.clone()).collect();
+            let column_validation =
+                ColumnValidation::SpecifiedColumns(&column_defs, columns_to_update, keys);
+            let rows = rows.iter().filter_map(|(_, _, row)| match row {
+                Row::Vec { values, .. } => Some(values.as_slice()),
+                Row::Map(_) => None,
+            });
+
+            validate_unique(
+                storage,
+                table_name,
+                column_validation,
+                &uniques,
+                rows.clone(),
+            )
+            .await?;
+
+            let labels = column_defs
+                .iter()
+                .map(|column_def| column_def.name.to_owned())
+                .collect::<Arc<[_]>>();
+            validate_checks(storage, table_name, &checks, &labels, rows).await?;
+        }
+
+        Ok(Self {
+            table_name,
+            triggers,
+            rows,
+        })
+    }
+
+    /// The rows as they are after the update.
+    pub fn rows(&self) -> impl Iterator<Item = &Row> {
+        self.rows.iter().map(|(_, _, row)| row)
+    }
+
+    pub async fn write<T: GStore + GStoreMut>(self, storage: &mut T) -> Result<usize> {
+        let Self {
+            table_name,
+            triggers,
+            rows,
+        } = self;
+
+        let changes = rows
+            .iter()
+            .filter_map(|(_, old, new)| {
+                old.clone().map(|old| RowChange {
+                    old: Some(old),
+                    new: Some(new.clone()),
+                })
+            })
+            .collect::<Vec<_>>();
+        triggers
+            .fire(storage, TriggerTiming::Before, &changes)
+            .await?;
+
+        let num_rows = rows.len();
+        let rows = rows
+            .into_iter()
+            .map(|(key, _, row)| (key, row.into()))
+            .collect();
+
+        storage.insert_data(table_name, rows).await?;
+        triggers
+            .fire(storage, TriggerTiming::After, &changes)
+            .await
+            .map(|_| num_rows)
+    }
+}
+
 pub struct Update<'a, T: GStore> {
     storage: &'a T,
     table_name: &'a str,
@@ -72,9 +250,13 @@ impl<'a, T: GStore> Update<'a, T> {
         })
     }
 
-    pub async fn apply(&self, row: Row, foreign_keys: &[ForeignKey]) -> Result<Row> {
-        let context = RowContext::new(self.table_name, Cow::Borrowed(&row), None);
-        let context = Some(Arc::new(context));
+    pub async fn apply(
+        &self,
+        row: Row,
+        excluded: Option<&Row>,
+        foreign_keys: &[ForeignKey],
+    ) -> Result<Row> {
+        let context = Some(update_context(self.table_name, &row, excluded));
 
         let assignments = stream::iter(self.fields.iter())
             .then(|assignment| {
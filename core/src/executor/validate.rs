@@ -1,14 +1,15 @@
 use {
+    super::{context::RowContext, evaluate::evaluate},
     crate::{
-        ast::{ColumnDef, ColumnUniqueOption},
-        data::{Key, Value},
+        ast::{CheckConstraint, ColumnDef, ColumnUniqueOption, UniqueConstraint},
+        data::{Key, Row, Value},
         result::Result,
-        store::{DataRow, Store},
+        store::{DataRow, GStore, Store},
     },
     futures::stream::TryStreamExt,
     im::HashSet,
     serde::Serialize,
-    std::fmt::Debug,
+    std::{borrow::Cow, fmt::Debug, sync::Arc},
     thiserror::Error as ThisError,
     utils::Vector,
 };
@@ -26,33 +27,50 @@ pub enum ValidateError {
 
     #[error("duplicate entry '{0:?}' for primary_key field")]
     DuplicateEntryOnPrimaryKeyField(Key),
+
+    #[error("duplicate entry '{}' for unique constraint '{1}'", .0.iter().map(String::from).collect::<Vec<_>>().join(", "))]
+    DuplicateEntryOnUniqueConstraint(Vec<Value>, String),
+
+    #[error("row violates CHECK constraint '{0}'")]
+    CheckConstraintViolated(String),
 }
 
 pub enum ColumnValidation<'column_def> {
     /// `INSERT`
     All(&'column_def [ColumnDef]),
-    /// `UPDATE`
-    SpecifiedColumns(&'column_def [ColumnDef], Vec<String>),
+    /// `UPDATE`, with the keys of the rows being updated, whose stored values are replaced
+    SpecifiedColumns(&'column_def [ColumnDef], Vec<String>, HashSet<Key>),
+}
+
+/// What makes a group of columns unique, telling the error a duplicate entry raises
+#[derive(Debug, Clone)]
+enum Unique {
+    /// `UNIQUE` or sole `PRIMARY KEY` column, with its name
+    Column(String),
+    /// columns of a composite primary key
+    PrimaryKey,
+    /// table-level `UNIQUE` constraint, with its name
+    Constraint(String),
 }
 
 #[derive(Debug)]
-struct UniqueConstraint {
-    column_index: usize,
-    column_name: String,
+struct UniqueKeys {
+    column_indexes: Vec<usize>,
+    unique: Unique,
     keys: HashSet<Key>,
 }
 
-impl UniqueConstraint {
-    fn new(column_index: usize, column_name: String) -> Self {
+impl UniqueKeys {
+    fn new(column_indexes: Vec<usize>, unique: Unique) -> Self {
         Self {
-            column_index,
-            column_name,
+            column_indexes,
+            unique,
             keys: HashSet::new(),
         }
     }
 
-    fn add(self, value: &Value) -> Result<Self> {
-        let new_key = self.check(value)?;
+    fn add(self, row: &[Value]) -> Result<Self> {
+        let new_key = self.check(row)?;
 
         if matches!(new_key, Key::None) {
             return Ok(self);
@@ -61,24 +79,56 @@ impl UniqueConstraint {
         let keys = self.keys.update(new_key);
 
         Ok(Self {
-            column_index: self.column_index,
-            column_name: self.column_name,
+            column_indexes: self.column_indexes,
+            unique: self.unique,
             keys,
         })
     }
 
-    fn check(&self, value: &Value) -> Result<Key> {
-        let key = Key::try_from(value)?;
+    /// Key of the row on the columns of the group, `Key::None` when any of them is `NULL`
+    fn key(&self, row: &[Value]) -> Result<Key> {
+        let values = self
+            .column_indexes
+            .iter()
+            .map(|i| {
+                row.get(*i)
+                    .ok_or(ValidateError::ConflictOnStorageColumnIndex(*i).into())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match values.as_slice() {
+            [value] => Key::try_from(*value),
+            _ if values.iter().any(|value| value.is_null()) => Ok(Key::None),
+            _ => values
+                .into_iter()
+                .map(Key::try_from)
+                .collect::<Result<Vec<_>>>()
+                .map(Key::Composite),
+        }
+    }
+
+    fn check(&self, row: &[Value]) -> Result<Key> {
+        let key = self.key(row)?;
 
         if !self.keys.contains(&key) {
-            Ok(key)
-        } else {
-            Err(ValidateError::DuplicateEntryOnUniqueField(
-                value.clone(),
-                self.column_name.to_owned(),
-            )
-            .into())
+            return Ok(key);
         }
+
+        Err(match &self.unique {
+            Unique::Column(column_name) => ValidateError::DuplicateEntryOnUniqueField(
+                row[self.column_indexes[0]].clone(),
+                column_name.to_owned(),
+            ),
+            Unique::PrimaryKey => ValidateError::DuplicateEntryOnPrimaryKeyField(key),
+            Unique::Constraint(name) => ValidateError::DuplicateEntryOnUniqueConstraint(
+                self.column_indexes
+                    .iter()
+                    .map(|i| row[*i].clone())
+                    .collect(),
+                name.to_owned(),
+            ),
+        }
+        .into())
     }
 }
 
@@ -86,24 +136,21 @@ pub async fn validate_unique<T: Store>(
     storage: &T,
     table_name: &str,
     column_validation: ColumnValidation<'_>,
+    uniques: &[UniqueConstraint],
     row_iter: impl Iterator<Item = &[Value]> + Clone,
 ) -> Result<()> {
-    enum Columns {
-        /// key index
-        PrimaryKeyOnly(usize),
-        /// `[(key_index, table_name)]`
-        All(Vec<(usize, String)>),
+    enum Columns<'a> {
+        /// the table has no unique column but its primary key
+        PrimaryKeyOnly(&'a [ColumnDef]),
+        /// `[(key_indexes, unique)]`
+        All(Vec<(Vec<usize>, Unique)>),
     }
 
     let columns = match &column_validation {
         ColumnValidation::All(column_defs) => {
-            let primary_key_index = column_defs
-                .iter()
-                .enumerate()
-                .find(|(_, ColumnDef { unique, .. })| {
-                    unique == &Some(ColumnUniqueOption { is_primary: true })
-                })
-                .map(|(i, _)| i);
+            let has_primary_key = column_defs.iter().any(|ColumnDef { unique, .. }| {
+                unique == &Some(ColumnUniqueOption { is_primary: true })
+            });
             let other_unique_column_def_count = column_defs
                 .iter()
                 .filter(|ColumnDef { unique, .. }| {
@@ -111,22 +158,26 @@ pub async fn validate_unique<T: Store>(
                 })
                 .count();
 
-            match (primary_key_index, other_unique_column_def_count) {
-                (Some(primary_key_index), 0) => Columns::PrimaryKeyOnly(primary_key_index),
-                _ => Columns::All(fetch_all_unique_columns(column_defs)),
+            match (has_primary_key, other_unique_column_def_count, uniques) {
+                (true, 0, []) => Columns::PrimaryKeyOnly(column_defs),
+                _ => Columns::All(fetch_all_unique_columns(column_defs, uniques)),
             }
         }
-        ColumnValidation::SpecifiedColumns(column_defs, specified_columns) => Columns::All(
-            fetch_specified_unique_columns(column_defs, specified_columns),
+        ColumnValidation::SpecifiedColumns(column_defs, specified_columns, _) => Columns::All(
+            fetch_specified_unique_columns(column_defs, uniques, specified_columns),
         ),
     };
+    let updated_keys = match &column_validation {
+        ColumnValidation::SpecifiedColumns(_, _, keys) => Some(keys),
+        ColumnValidation::All(_) => None,
+    };
 
     match columns {
-        Columns::PrimaryKeyOnly(primary_key_index) => {
-            for primary_key in
-                row_iter.filter_map(|row| row.get(primary_key_index).map(Key::try_from))
-            {
-                let key = primary_key?;
+        Columns::PrimaryKeyOnly(column_defs) => {
+            for primary_key in row_iter.map(|row| Key::primary(column_defs, row)) {
+                let Some(key) = primary_key? else {
+                    continue;
+                };
 
                 if storage.fetch_data(table_name, &key).await?.is_some() {
                     return Err(ValidateError::DuplicateEntryOnPrimaryKeyField(key).into());
@@ -145,7 +196,11 @@ pub async fn validate_unique<T: Store>(
             storage
                 .scan_data(table_name)
                 .await?
-                .try_for_each(|(_, data_row)| async {
+                .try_for_each(|(key, data_row)| async move {
+                    if updated_keys.is_some_and(|keys| keys.contains(&key)) {
+                        return Ok(());
+                    }
+
                     let values = match data_row {
                         DataRow::Vec(values) => values,
                         DataRow::Map(_) => {
@@ -156,12 +211,7 @@ pub async fn validate_unique<T: Store>(
                     };
 
                     unique_constraints.iter().try_for_each(|constraint| {
-                        let col_idx = constraint.column_index;
-                        let val = values
-                            .get(col_idx)
-                            .ok_or(ValidateError::ConflictOnStorageColumnIndex(col_idx))?;
-
-                        constraint.check(val)?;
+                        constraint.check(&values)?;
 
                         Ok(())
                     })
@@ -171,47 +221,114 @@ pub async fn validate_unique<T: Store>(
     }
 }
 
+/// Validates the rows against the `CHECK` constraints of the table. A check evaluating to
+/// `NULL` passes, as only false fails it.
+pub async fn validate_checks<'a, T: GStore>(
+    storage: &T,
+    table_name: &str,
+    checks: &[CheckConstraint],
+    labels: &Arc<[String]>,
+    row_iter: impl Iterator<Item = &'a [Value]>,
+) -> Result<()> {
+    if checks.is_empty() {
+        return Ok(());
+    }
+
+    for values in row_iter {
+        let row = Row::Vec {
+            columns: Arc::clone(labels),
+            values: values.to_vec(),
+        };
+        let context = Arc::new(RowContext::new(table_name, Cow::Borrowed(&row), None));
+
+        for CheckConstraint { name, expr } in checks {
+            let evaluated = evaluate(storage, Some(Arc::clone(&context)), None, expr).await?;
+            if evaluated.is_null() {
+                continue;
+            }
+
+            if !bool::try_from(evaluated)? {
+                return Err(ValidateError::CheckConstraintViolated(name.to_owned()).into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
 fn create_unique_constraints<'a>(
-    unique_columns: Vec<(usize, String)>,
+    unique_columns: Vec<(Vec<usize>, Unique)>,
     row_iter: impl Iterator<Item = &'a [Value]> + Clone,
-) -> Result<Vector<UniqueConstraint>> {
+) -> Result<Vector<UniqueKeys>> {
     unique_columns
         .into_iter()
         .try_fold(Vector::new(), |constraints, col| {
-            let (col_idx, col_name) = col;
-            let new_constraint = UniqueConstraint::new(col_idx, col_name);
+            let (col_indexes, unique) = col;
+            let new_constraint = UniqueKeys::new(col_indexes, unique);
             let new_constraint = row_iter
                 .clone()
-                .try_fold(new_constraint, |constraint, row| {
-                    let val = row
-                        .get(col_idx)
-                        .ok_or(ValidateError::ConflictOnStorageColumnIndex(col_idx))?;
-
-                    constraint.add(val)
-                })?;
+                .try_fold(new_constraint, |constraint, row| constraint.add(row))?;
             Ok(constraints.push(new_constraint))
         })
 }
 
-fn fetch_all_unique_columns(column_defs: &[ColumnDef]) -> Vec<(usize, String)> {
-    column_defs
+/// Unique columns of the table, with the columns of a composite primary key and of each
+/// `UNIQUE` constraint as a single group
+fn fetch_all_unique_columns(
+    column_defs: &[ColumnDef],
+    uniques: &[UniqueConstraint],
+) -> Vec<(Vec<usize>, Unique)> {
+    let primary_key = column_defs
         .iter()
         .enumerate()
-        .filter_map(|(i, table_col)| table_col.unique.map(|_| (i, table_col.name.to_owned())))
-        .collect()
+        .filter(|(_, ColumnDef { unique, .. })| {
+            unique == &Some(ColumnUniqueOption { is_primary: true })
+        })
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    let sole_primary_key = match primary_key.as_slice() {
+        [i] => Some(*i),
+        _ => None,
+    };
+    let unique_columns = column_defs
+        .iter()
+        .enumerate()
+        .filter(|(i, table_col)| {
+            table_col.unique == Some(ColumnUniqueOption { is_primary: false })
+                || sole_primary_key == Some(*i)
+        })
+        .map(|(i, table_col)| (vec![i], Unique::Column(table_col.name.to_owned())));
+    let primary_key = (primary_key.len() > 1).then_some((primary_key, Unique::PrimaryKey));
+    let uniques = uniques.iter().map(|UniqueConstraint { name, columns }| {
+        let column_indexes = columns
+            .iter()
+            .filter_map(|column| {
+                column_defs
+                    .iter()
+                    .position(|column_def| &column_def.name == column)
+            })
+            .collect();
+
+        (column_indexes, Unique::Constraint(name.to_owned()))
+    });
+
+    unique_columns.chain(primary_key).chain(uniques).collect()
 }
 
 fn fetch_specified_unique_columns(
     all_column_defs: &[ColumnDef],
+    uniques: &[UniqueConstraint],
     specified_columns: &[String],
-) -> Vec<(usize, String)> {
-    all_column_defs
-        .iter()
-        .enumerate()
-        .filter_map(|(i, table_col)| {
-            (table_col.unique.is_some()
-                && specified_columns.iter().any(|col| col == &table_col.name))
-            .then_some((i, table_col.name.to_owned()))
+) -> Vec<(Vec<usize>, Unique)> {
+    fetch_all_unique_columns(all_column_defs, uniques)
+        .into_iter()
+        .filter(|(col_indexes, _)| {
+            col_indexes.iter().any(|i| {
+                specified_columns
+                    .iter()
+                    .any(|col| col == &all_column_defs[*i].name)
+            })
         })
         .collect()
 }
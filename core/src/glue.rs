@@ -1,45 +1,324 @@
 use {
     crate::{
-        ast::Statement,
-        executor::{Payload, execute},
+        ast::{ReferentialAction, SetVariable, Statement},
+        audit::{AuditError, AuditRecord, AuditSink},
+        cache::{QueryCache, StatementCache},
+        data::{Row, Value},
+        executor::{Payload, authorize, execute_into, execute_parallel},
         parse_sql::parse,
-        plan::plan,
+        plan::{plan, plan_recursion_depth},
+        prepared::PreparedStatement,
         result::Result,
         store::{GStore, GStoreMut},
         translate::translate,
     },
     futures::{
-        TryStreamExt,
+        Sink, TryStreamExt,
         stream::{self, StreamExt},
     },
+    sqlparser::ast::Statement as SqlStatement,
+    std::path::Path,
 };
 
 #[derive(Debug)]
 pub struct Glue<T: GStore + GStoreMut> {
     pub storage: T,
+    user: Option<String>,
+    audit: Option<AuditSink>,
+    audit_enabled: bool,
+    cache: Option<QueryCache>,
+    statement_cache: Option<StatementCache>,
+    max_recursion_depth: Option<usize>,
+    parallelism: usize,
 }
 
 impl<T: GStore + GStoreMut> Glue<T> {
     pub fn new(storage: T) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            user: None,
+            audit: None,
+            audit_enabled: false,
+            cache: None,
+            statement_cache: None,
+            max_recursion_depth: None,
+            parallelism: 1,
+        }
+    }
+
+    /// Records every statement into the sink, see [`crate::audit`].
+    pub fn with_audit(mut self, sink: AuditSink) -> Self {
+        self.audit = Some(sink);
+        self.audit_enabled = true;
+        self
+    }
+
+    /// Caches the results of the queries, see [`crate::cache`].
+    pub fn with_cache(mut self, cache: QueryCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Caches the statements of the SQL run, see [`StatementCache`].
+    pub fn with_statement_cache(mut self, cache: StatementCache) -> Self {
+        self.statement_cache = Some(cache);
+        self
+    }
+
+    /// Fails `WITH RECURSIVE` queries still adding rows after `depth` recursions, instead of
+    /// after [`crate::executor::DEFAULT_MAX_RECURSION_DEPTH`].
+    pub fn with_max_recursion_depth(mut self, depth: usize) -> Self {
+        self.max_recursion_depth = Some(depth);
+        self
+    }
+
+    /// Splits the aggregation of queries reading a single table across up to `degree` tasks,
+    /// each aggregating a batch of its rows, instead of aggregating the rows one by one. The
+    /// scan of the table is split into up to `degree` partitions read side by side when the
+    /// storage supports it, see [`crate::store::Store::scan_data_partitioned`]. The tasks run
+    /// on blocking threads of the Tokio runtime executing the query when the `parallel`
+    /// feature is enabled, and one after another otherwise. `degree` of 0 or 1, the default,
+    /// turns it off.
+    pub fn with_parallelism(mut self, degree: usize) -> Self {
+        self.parallelism = degree.max(1);
+        self
+    }
+
+    pub fn cache(&self) -> Option<&QueryCache> {
+        self.cache.as_ref()
+    }
+
+    pub fn cache_mut(&mut self) -> Option<&mut QueryCache> {
+        self.cache.as_mut()
+    }
+
+    pub fn statement_cache(&self) -> Option<&StatementCache> {
+        self.statement_cache.as_ref()
+    }
+
+    pub fn statement_cache_mut(&mut self) -> Option<&mut StatementCache> {
+        self.statement_cache.as_mut()
+    }
+
+    /// Runs the following statements as the user, checking the privileges granted to them.
+    /// Without a user, which is the default, every statement is allowed.
+    pub fn set_user(&mut self, user: Option<&str>) {
+        self.user = user.map(ToOwned::to_owned);
+    }
+
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
     }
 
     pub async fn plan<Sql: AsRef<str>>(&mut self, sql: Sql) -> Result<Vec<Statement>> {
-        let parsed = parse(sql)?;
+        let sql = sql.as_ref();
+        let Some(cache) = self.statement_cache.as_mut() else {
+            let parsed = parse(sql)?;
+
+            return self.plan_parsed(parsed).await;
+        };
+
+        let plans = cache.plans();
+        if let Some(statements) = cache.get(sql) {
+            return match plans {
+                true => Ok(statements),
+                false => self.plan_translated(statements).await,
+            };
+        }
+
+        let translated = parse(sql)?
+            .iter()
+            .map(translate)
+            .collect::<Result<Vec<_>>>()?;
+        let (cached, planned) = match plans {
+            true => {
+                let planned = self.plan_translated(translated).await?;
+
+                (planned.clone(), planned)
+            }
+            false => (translated.clone(), self.plan_translated(translated).await?),
+        };
+
+        if let Some(cache) = self.statement_cache.as_mut() {
+            cache.insert(sql, cached);
+        }
+
+        Ok(planned)
+    }
+
+    async fn plan_parsed(&self, parsed: Vec<SqlStatement>) -> Result<Vec<Statement>> {
+        let translated = parsed.iter().map(translate).collect::<Result<Vec<_>>>()?;
+
+        self.plan_translated(translated).await
+    }
+
+    async fn plan_translated(&self, statements: Vec<Statement>) -> Result<Vec<Statement>> {
         let storage = &self.storage;
-        stream::iter(parsed)
-            .map(|p| translate(&p))
-            .then(|statement| async move { plan(storage, statement?).await })
+        let max_recursion_depth = self.max_recursion_depth;
+        stream::iter(statements)
+            .then(|statement| async move { plan(storage, statement).await })
+            .map_ok(|statement| match max_recursion_depth {
+                Some(depth) => plan_recursion_depth(statement, depth),
+                None => statement,
+            })
             .try_collect()
             .await
     }
 
     pub async fn execute_stmt(&mut self, statement: &Statement) -> Result<Payload> {
-        execute(&mut self.storage, statement).await
+        let audited = self.audit_enabled;
+        let result = self.execute_unaudited(statement).await;
+        self.record(audited, statement, &result, None).await?;
+
+        result
+    }
+
+    /// Runs the statement as [`Self::execute_stmt`] does, except that the rows of a query are
+    /// sent into `sink` as the executor reads them, instead of being collected into its payload,
+    /// which then comes without rows. Such queries neither read nor fill the result cache.
+    pub async fn execute_stmt_into<S>(
+        &mut self,
+        statement: &Statement,
+        sink: &mut S,
+    ) -> Result<Payload>
+    where
+        S: Sink<Row> + Unpin,
+    {
+        let Statement::Query(query) = statement else {
+            return self.execute_stmt(statement).await;
+        };
+
+        let audited = self.audit_enabled;
+        let result = async {
+            if let Some(user) = &self.user {
+                authorize(&self.storage, user, statement).await?;
+            }
+
+            execute_into(&mut self.storage, query, self.parallelism, sink).await
+        }
+        .await;
+        let sent = result.as_ref().ok().map(|(_, sent)| *sent);
+        let result = result.map(|(payload, _)| payload);
+        self.record(audited, statement, &result, sent).await?;
+
+        result
+    }
+
+    /// Drops the cached statements the statement invalidates and writes it to the audit sink,
+    /// with `rows` as the rows of a query whose rows were sent instead of collected.
+    async fn record(
+        &mut self,
+        audited: bool,
+        statement: &Statement,
+        result: &Result<Payload>,
+        rows: Option<usize>,
+    ) -> Result<()> {
+        if let Some(cache) = self.statement_cache.as_mut() {
+            cache.invalidate_by(statement);
+        }
+
+        if let Some(sink) = self
+            .audit
+            .as_mut()
+            .filter(|_| audited || self.audit_enabled)
+        {
+            let mut record = AuditRecord::new(self.user.as_deref(), statement, result);
+            record.rows = rows.or(record.rows);
+            sink.write(&mut self.storage, &record).await?;
+
+            if let (AuditSink::Table(table_name), Some(cache)) = (sink, self.cache.as_mut()) {
+                cache.invalidate(table_name);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn execute_unaudited(&mut self, statement: &Statement) -> Result<Payload> {
+        if let Some(user) = &self.user {
+            authorize(&self.storage, user, statement).await?;
+        }
+
+        if let Statement::SetVariable(variable) = statement {
+            return self.set(variable).map(|_| Payload::SetVariable);
+        }
+
+        let Some(cache) = self.cache.as_mut() else {
+            return execute_parallel(&mut self.storage, statement, self.parallelism).await;
+        };
+
+        let Some(key) = QueryCache::key(statement) else {
+            let writes_other_tables = fires_triggers(&self.storage, statement).await?
+                || cascades(&self.storage, statement).await?;
+            let result = execute_parallel(&mut self.storage, statement, self.parallelism).await;
+            match writes_other_tables {
+                true => cache.clear(),
+                false => cache.invalidate_by(statement),
+            }
+
+            return result;
+        };
+
+        if let Some(payload) = cache.get(&key) {
+            return Ok(payload);
+        }
+
+        let payload = execute_parallel(&mut self.storage, statement, self.parallelism).await?;
+        cache.insert(key, statement, &payload);
+
+        Ok(payload)
     }
 
+    fn set(&mut self, variable: &SetVariable) -> Result<()> {
+        match variable {
+            SetVariable::Audit(true) if self.audit.is_none() => {
+                return Err(AuditError::SinkNotFound.into());
+            }
+            SetVariable::Audit(enabled) => self.audit_enabled = *enabled,
+            SetVariable::AuditTable(table_name) => {
+                self.audit = Some(AuditSink::Table(table_name.clone()));
+                self.audit_enabled = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copies the storage to `target_path` while it stays available, see
+    /// [`crate::store::Backup`].
+    pub async fn backup<P: AsRef<Path>>(&self, target_path: P) -> Result<()> {
+        self.storage.backup(target_path.as_ref()).await
+    }
+
+    #[tracing::instrument(name = "query", skip_all)]
     pub async fn execute<Sql: AsRef<str>>(&mut self, sql: Sql) -> Result<Vec<Payload>> {
         let statements = self.plan(sql).await?;
+
+        self.execute_planned(statements).await
+    }
+
+    /// Parses `sql`, whose placeholders [`Self::execute_with_params`] reads as the values of
+    /// its parameters, see [`crate::prepared`].
+    pub fn prepare<Sql: AsRef<str>>(&self, sql: Sql) -> Result<PreparedStatement> {
+        PreparedStatement::new(sql)
+    }
+
+    /// Runs the prepared statement with `params` as the values of its placeholders, `params[0]`
+    /// for `$1` and the first `?`. The statement is planned for the values, as the SQL with
+    /// the values in place of the placeholders is.
+    #[tracing::instrument(name = "query", skip_all)]
+    pub async fn execute_with_params(
+        &mut self,
+        statement: &PreparedStatement,
+        params: &[Value],
+    ) -> Result<Vec<Payload>> {
+        let parsed = statement.bind(params)?;
+        let statements = self.plan_parsed(parsed).await?;
+
+        self.execute_planned(statements).await
+    }
+
+    async fn execute_planned(&mut self, statements: Vec<Statement>) -> Result<Vec<Payload>> {
         let mut payloads = Vec::<Payload>::new();
         for statement in statements.iter() {
             let payload = self.execute_stmt(statement).await?;
@@ -49,3 +328,36 @@ impl<T: GStore + GStoreMut> Glue<T> {
         Ok(payloads)
     }
 }
+
+/// Whether the statement modifies a table with triggers, whose bodies may modify any table.
+async fn fires_triggers<T: GStore>(storage: &T, statement: &Statement) -> Result<bool> {
+    match statement {
+        Statement::Insert { table_name, .. }
+        | Statement::Update { table_name, .. }
+        | Statement::Delete { table_name, .. } => Ok(storage
+            .fetch_schema(table_name)
+            .await?
+            .is_some_and(|schema| !schema.triggers.is_empty())),
+        _ => Ok(false),
+    }
+}
+
+/// Whether the statement deletes from a table referenced by foreign keys with `ON DELETE
+/// CASCADE` or `SET NULL`, which write to the referencing tables and to the tables those
+/// cascade to in turn.
+async fn cascades<T: GStore>(storage: &T, statement: &Statement) -> Result<bool> {
+    let Statement::Delete { table_name, .. } = statement else {
+        return Ok(false);
+    };
+
+    Ok(storage
+        .fetch_referencings(table_name)
+        .await?
+        .iter()
+        .any(|referencing| {
+            matches!(
+                referencing.foreign_key.on_delete,
+                ReferentialAction::Cascade | ReferentialAction::SetNull
+            )
+        }))
+}
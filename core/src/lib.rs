@@ -9,11 +9,16 @@ mod result;
 
 pub mod ast;
 pub mod ast_builder;
+pub mod audit;
+pub mod cache;
 pub mod data;
 pub mod executor;
+pub mod migration;
 pub mod parse_sql;
 pub mod plan;
+pub mod prepared;
 pub mod store;
+pub mod telemetry;
 pub mod translate;
 
 pub mod prelude {
@@ -24,6 +29,7 @@ pub mod prelude {
         glue::Glue,
         parse_sql::parse,
         plan::plan,
+        prepared::PreparedStatement,
         result::{Error, Result},
         translate::translate,
     };
@@ -0,0 +1,345 @@
+//! Versioned schema migrations.
+//!
+//! A [`Migrator`] applies SQL scripts in version order and records every applied version in a
+//! tracking table, `__migrations` by default, of the migrated storage. Each script runs within
+//! a transaction on storages which support them; elsewhere a failing script leaves the
+//! statements before it applied.
+
+use {
+    crate::{
+        ast::Statement,
+        ast_builder::{Build, col, num, table, text, timestamp},
+        chrono::{NaiveDateTime, Utc},
+        data::Value,
+        executor::Payload,
+        parse_sql::parse,
+        plan::plan,
+        prelude::Glue,
+        result::Result,
+        store::{GStore, GStoreMut},
+        translate::translate,
+    },
+    serde::Serialize,
+    std::{collections::BTreeMap, fs, path::Path},
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum MigrationError {
+    #[error("migration file name should be <version>_<name>.up.sql or .down.sql: {0}")]
+    InvalidFileName(String),
+
+    #[error("failed to read migrations: {0}")]
+    ReadFailed(String),
+
+    #[error("duplicate migration version: {0}")]
+    DuplicateVersion(i64),
+
+    #[error("migration {0} has a down script but no up script")]
+    UpNotFound(i64),
+
+    #[error("migration {0} cannot be reverted, it has no down script")]
+    DownNotFound(i64),
+
+    #[error("applied migration {0} is not among the migrations")]
+    MigrationNotFound(i64),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Migration {
+    pub version: i64,
+    pub name: String,
+    pub up: String,
+    pub down: Option<String>,
+}
+
+impl Migration {
+    pub fn new(version: i64, name: &str, up: &str) -> Self {
+        Self {
+            version,
+            name: name.to_owned(),
+            up: up.to_owned(),
+            down: None,
+        }
+    }
+
+    /// Sets the script reverting the migration, without which it cannot be reverted.
+    pub fn with_down(mut self, down: &str) -> Self {
+        self.down = Some(down.to_owned());
+        self
+    }
+}
+
+/// A migration, or a version found only in the tracking table, with its applied time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub name: String,
+    pub applied_at: Option<NaiveDateTime>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Migrator {
+    migrations: BTreeMap<i64, Migration>,
+    table_name: String,
+}
+
+impl Migrator {
+    pub fn new(migrations: Vec<Migration>) -> Result<Self> {
+        let mut by_version = BTreeMap::new();
+        for migration in migrations {
+            let version = migration.version;
+
+            if by_version.insert(version, migration).is_some() {
+                return Err(MigrationError::DuplicateVersion(version).into());
+            }
+        }
+
+        Ok(Self {
+            migrations: by_version,
+            table_name: "__migrations".to_owned(),
+        })
+    }
+
+    /// Reads the migrations of a directory, named `<version>_<name>.up.sql` and
+    /// `<version>_<name>.down.sql`. Files without the `.sql` extension are ignored.
+    pub fn from_dir<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let read_failed = |error: std::io::Error| MigrationError::ReadFailed(error.to_string());
+
+        let mut ups = BTreeMap::new();
+        let mut downs = BTreeMap::new();
+        for entry in fs::read_dir(path).map_err(read_failed)? {
+            let path = entry.map_err(read_failed)?.path();
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            if !file_name.ends_with(".sql") {
+                continue;
+            }
+
+            let (version, name, direction) = parse_file_name(file_name)
+                .ok_or_else(|| MigrationError::InvalidFileName(file_name.to_owned()))?;
+            let script = fs::read_to_string(&path).map_err(read_failed)?;
+            let scripts = match direction {
+                Direction::Up => &mut ups,
+                Direction::Down => &mut downs,
+            };
+
+            if scripts.insert(version, (name.to_owned(), script)).is_some() {
+                return Err(MigrationError::DuplicateVersion(version).into());
+            }
+        }
+
+        if let Some(version) = downs.keys().find(|version| !ups.contains_key(version)) {
+            return Err(MigrationError::UpNotFound(*version).into());
+        }
+
+        let migrations = ups
+            .into_iter()
+            .map(|(version, (name, up))| Migration {
+                version,
+                name,
+                up,
+                down: downs.remove(&version).map(|(_, down)| down),
+            })
+            .collect();
+
+        Self::new(migrations)
+    }
+
+    /// Records the applied versions in the table instead of `__migrations`.
+    pub fn with_table(mut self, table_name: &str) -> Self {
+        self.table_name = table_name.to_owned();
+        self
+    }
+
+    pub async fn status<T: GStore + GStoreMut>(
+        &self,
+        glue: &mut Glue<T>,
+    ) -> Result<Vec<MigrationStatus>> {
+        let mut applied = self.applied(glue).await?;
+        let mut statuses = self
+            .migrations
+            .values()
+            .map(|migration| MigrationStatus {
+                version: migration.version,
+                name: migration.name.clone(),
+                applied_at: applied
+                    .remove(&migration.version)
+                    .map(|(_, applied_at)| applied_at),
+            })
+            .collect::<Vec<_>>();
+
+        statuses.extend(
+            applied
+                .into_iter()
+                .map(|(version, (name, applied_at))| MigrationStatus {
+                    version,
+                    name,
+                    applied_at: Some(applied_at),
+                }),
+        );
+        statuses.sort_by_key(|status| status.version);
+
+        Ok(statuses)
+    }
+
+    /// Applies every migration not applied yet in version order, returning their versions.
+    pub async fn up<T: GStore + GStoreMut>(&self, glue: &mut Glue<T>) -> Result<Vec<i64>> {
+        let applied = self.applied(glue).await?;
+        let mut versions = Vec::new();
+
+        for migration in self
+            .migrations
+            .values()
+            .filter(|migration| !applied.contains_key(&migration.version))
+        {
+            let record = table(self.table_name.as_str())
+                .insert()
+                .values(vec![vec![
+                    num(migration.version),
+                    text(migration.name.clone()),
+                    timestamp(Utc::now().naive_utc().to_string()),
+                ]])
+                .build()?;
+
+            run(glue, &migration.up, &record).await?;
+            versions.push(migration.version);
+        }
+
+        Ok(versions)
+    }
+
+    /// Reverts the last `steps` applied migrations, latest first, returning their versions.
+    pub async fn down<T: GStore + GStoreMut>(
+        &self,
+        glue: &mut Glue<T>,
+        steps: usize,
+    ) -> Result<Vec<i64>> {
+        let applied = self.applied(glue).await?;
+        let mut versions = Vec::new();
+
+        for version in applied.into_keys().rev().take(steps) {
+            let migration = self
+                .migrations
+                .get(&version)
+                .ok_or(MigrationError::MigrationNotFound(version))?;
+            let down = migration
+                .down
+                .as_deref()
+                .ok_or(MigrationError::DownNotFound(version))?;
+            let record = table(self.table_name.as_str())
+                .delete()
+                .filter(col("version").eq(num(version)))
+                .build()?;
+
+            run(glue, down, &record).await?;
+            versions.push(version);
+        }
+
+        Ok(versions)
+    }
+
+    /// Reads the tracking table, creating it if needed.
+    async fn applied<T: GStore + GStoreMut>(
+        &self,
+        glue: &mut Glue<T>,
+    ) -> Result<BTreeMap<i64, (String, NaiveDateTime)>> {
+        let create_table = table(self.table_name.as_str())
+            .create_table_if_not_exists()
+            .add_column("version INTEGER PRIMARY KEY")
+            .add_column("name TEXT")
+            .add_column("applied_at TIMESTAMP")
+            .build()?;
+        glue.execute_stmt(&create_table).await?;
+
+        let select = table(self.table_name.as_str())
+            .select()
+            .project("version, name, applied_at")
+            .build()?;
+        let Payload::Select { rows, .. } = glue.execute_stmt(&select).await? else {
+            return Ok(BTreeMap::new());
+        };
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| match row.as_slice() {
+                [
+                    Value::I64(version),
+                    Value::Str(name),
+                    Value::Timestamp(applied_at),
+                ] => Some((*version, (name.clone(), *applied_at))),
+                _ => None,
+            })
+            .collect())
+    }
+}
+
+/// Runs the script and then `record`, within a single transaction when the storage has them.
+async fn run<T: GStore + GStoreMut>(
+    glue: &mut Glue<T>,
+    script: &str,
+    record: &Statement,
+) -> Result<()> {
+    let transaction = glue.storage.begin(false).await.is_ok();
+    let result = execute_script(glue, script, record).await;
+
+    match (transaction, &result) {
+        (false, _) => {}
+        (true, Ok(_)) => glue.storage.commit().await?,
+        (true, Err(_)) => glue.storage.rollback().await?,
+    }
+
+    result
+}
+
+async fn execute_script<T: GStore + GStoreMut>(
+    glue: &mut Glue<T>,
+    script: &str,
+    record: &Statement,
+) -> Result<()> {
+    for statement in parse(script)? {
+        let statement = translate(&statement)?;
+        let statement = plan(&glue.storage, statement).await?;
+
+        glue.execute_stmt(&statement).await?;
+    }
+
+    glue.execute_stmt(record).await.map(|_| ())
+}
+
+#[derive(Debug, PartialEq)]
+enum Direction {
+    Up,
+    Down,
+}
+
+fn parse_file_name(file_name: &str) -> Option<(i64, &str, Direction)> {
+    let (stem, direction) = match file_name.strip_suffix(".up.sql") {
+        Some(stem) => (stem, Direction::Up),
+        None => (file_name.strip_suffix(".down.sql")?, Direction::Down),
+    };
+    let (version, name) = stem.split_once('_')?;
+
+    Some((version.parse().ok()?, name, direction))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, parse_file_name};
+
+    #[test]
+    fn file_name() {
+        assert_eq!(
+            parse_file_name("1_create_item.up.sql"),
+            Some((1, "create_item", Direction::Up))
+        );
+        assert_eq!(
+            parse_file_name("20240101_add_index.down.sql"),
+            Some((20240101, "add_index", Direction::Down))
+        );
+        assert_eq!(parse_file_name("create_item.up.sql"), None);
+        assert_eq!(parse_file_name("1_create_item.sql"), None);
+        assert_eq!(parse_file_name("one_create_item.up.sql"), None);
+    }
+}
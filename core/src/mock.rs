@@ -3,8 +3,8 @@ use {
         data::{Key, Schema},
         result::{Error, Result},
         store::{
-            AlterTable, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut, Metadata,
-            RowIter, Store, StoreMut, Transaction,
+            AlterTable, Backup, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut,
+            Metadata, RowIter, Store, StoreMut, Transaction, User, UserMut,
         },
     },
     async_trait::async_trait,
@@ -41,6 +41,15 @@ impl CustomFunction for MockStorage {}
 #[async_trait]
 impl CustomFunctionMut for MockStorage {}
 
+#[async_trait]
+impl User for MockStorage {}
+
+#[async_trait]
+impl UserMut for MockStorage {}
+
+#[async_trait]
+impl Backup for MockStorage {}
+
 #[async_trait]
 impl Store for MockStorage {
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
@@ -144,13 +153,15 @@ mod tests {
             block_on(storage.create_index(
                 "Foo",
                 "idx_col",
-                &OrderByExpr {
+                &[OrderByExpr {
                     expr: Expr::TypedString {
                         data_type: DataType::Boolean,
                         value: "true".to_owned(),
                     },
                     asc: None,
-                },
+                }],
+                None,
+                false,
             ))
             .is_err()
         );
@@ -1,32 +1,498 @@
 use {
-    crate::result::{Error, Result},
+    crate::{
+        prepared::PreparedError,
+        result::{Error, Result},
+        telemetry,
+    },
     sqlparser::{
         ast::{
             Assignment as SqlAssignment, ColumnDef as SqlColumnDef, DataType as SqlDataType,
-            Expr as SqlExpr, Ident as SqlIdent, OrderByExpr as SqlOrderByExpr, Query as SqlQuery,
-            SelectItem as SqlSelectItem, Statement as SqlStatement,
+            Expr as SqlExpr, FunctionDesc, Ident as SqlIdent, ObjectName, OperateFunctionArg,
+            OrderByExpr as SqlOrderByExpr, Query as SqlQuery, SelectItem as SqlSelectItem,
+            Statement as SqlStatement, TriggerExecBody, TriggerExecBodyType, TriggerObject,
+            Value as SqlValue, visit_expressions_mut,
         },
         dialect::PostgreSqlDialect,
-        parser::Parser,
-        tokenizer::Tokenizer,
+        keywords::Keyword,
+        parser::{Parser, ParserError},
+        tokenizer::{Location, Token, TokenWithLocation, Tokenizer, TokenizerError, Whitespace},
     },
+    std::ops::ControlFlow,
 };
 
 const DIALECT: PostgreSqlDialect = PostgreSqlDialect {};
 
 pub fn parse<Sql: AsRef<str>>(sql: Sql) -> Result<Vec<SqlStatement>> {
-    Parser::parse_sql(&DIALECT, sql.as_ref()).map_err(|e| Error::Parser(format!("{e:#?}")))
+    let sql = sql.as_ref();
+    let span = tracing::info_span!(
+        "parse",
+        query = telemetry::query_text(sql),
+        statements = tracing::field::Empty,
+    )
+    .entered();
+
+    let parse = || {
+        let tokens = Tokenizer::new(&DIALECT, sql).tokenize_with_location()?;
+
+        let tokens = match_against(user_as_role(tokens));
+        let parser = Parser::new(&DIALECT).with_tokens_with_locations(tokens);
+
+        parse_statements(parser)
+    };
+    let statements = parse().map_err(|e: ParserError| Error::Parser(format!("{e:#?}")))?;
+    span.record("statements", statements.len());
+
+    Ok(statements)
+}
+
+/// Same as [`Parser::parse_statements`], but also reads the statements only GlueSQL knows.
+fn parse_statements(mut parser: Parser) -> Result<Vec<SqlStatement>, ParserError> {
+    let mut statements = Vec::new();
+    loop {
+        while parser.consume_token(&Token::SemiColon) {}
+        if parser.peek_token().token == Token::EOF {
+            break;
+        }
+
+        let statement = match parse_check(&mut parser)? {
+            Some(statement) => statement,
+            None => match parse_create_trigger(&mut parser)? {
+                Some(statement) => statement,
+                None => match parse_delete_returning(&mut parser)? {
+                    Some(statement) => statement,
+                    None => parser.parse_statement()?,
+                },
+            },
+        };
+        statements.push(statement);
+
+        if !parser.consume_token(&Token::SemiColon) && parser.peek_token().token != Token::EOF {
+            return parser.expected("end of statement", parser.peek_token());
+        }
+    }
+
+    Ok(statements)
+}
+
+/// Reads `CHECK DATABASE`, `CHECK TABLE name`, `REFRESH MATERIALIZED VIEW name` and
+/// `ANALYZE [TABLE] [name]` as a `SHOW` of their words, the way `SHOW INDEXES FROM name` is
+/// read, and `DROP MATERIALIZED VIEW` as `DROP VIEW`.
+fn parse_check(parser: &mut Parser) -> Result<Option<SqlStatement>, ParserError> {
+    let check = || SqlIdent::new("CHECK");
+    let variable = if parser.parse_keywords(&[Keyword::CHECK, Keyword::DATABASE]) {
+        vec![check(), SqlIdent::new("DATABASE")]
+    } else if parser.parse_keywords(&[Keyword::CHECK, Keyword::TABLE]) {
+        vec![
+            check(),
+            SqlIdent::new("TABLE"),
+            parser.parse_identifier(false)?,
+        ]
+    } else if parse_refresh(parser) {
+        vec![
+            SqlIdent::new("REFRESH"),
+            SqlIdent::new("MATERIALIZED"),
+            SqlIdent::new("VIEW"),
+            parser.parse_identifier(false)?,
+        ]
+    } else if parser.parse_keyword(Keyword::ANALYZE) {
+        let table = parser.parse_keyword(Keyword::TABLE);
+        let ended = matches!(parser.peek_token().token, Token::SemiColon | Token::EOF);
+
+        match table || !ended {
+            true => vec![SqlIdent::new("ANALYZE"), parser.parse_identifier(false)?],
+            false => vec![SqlIdent::new("ANALYZE")],
+        }
+    } else if parser.parse_keywords(&[Keyword::DROP, Keyword::MATERIALIZED, Keyword::VIEW]) {
+        parser.prev_token();
+        return parser.parse_drop().map(Some);
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(SqlStatement::ShowVariable { variable }))
+}
+
+/// Consumes `REFRESH MATERIALIZED VIEW`, whose first word the parser has no keyword for.
+fn parse_refresh(parser: &mut Parser) -> bool {
+    let refresh = matches!(
+        &parser.peek_token().token,
+        Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case("REFRESH")
+    );
+    if !refresh {
+        return false;
+    }
+
+    parser.next_token();
+    if parser.parse_keywords(&[Keyword::MATERIALIZED, Keyword::VIEW]) {
+        return true;
+    }
+
+    parser.prev_token();
+    false
+}
+
+/// Reads `DELETE FROM table RETURNING ...`, whose `RETURNING` the parser takes for an alias of
+/// the table when no `WHERE` comes between them.
+fn parse_delete_returning(parser: &mut Parser) -> Result<Option<SqlStatement>, ParserError> {
+    let start = parser.peek_token().location;
+    if !parser.parse_keywords(&[Keyword::DELETE, Keyword::FROM]) {
+        return Ok(None);
+    }
+
+    let table_name = parser.parse_object_name(false)?;
+    if !parser.parse_keyword(Keyword::RETURNING) {
+        while parser.peek_token().location != start {
+            parser.prev_token();
+        }
+
+        return Ok(None);
+    }
+
+    let returning = parser.parse_comma_separated(Parser::parse_select_item)?;
+    let mut statement = Parser::new(&DIALECT)
+        .try_with_sql(&format!("DELETE FROM {table_name}"))?
+        .parse_statement()?;
+    if let SqlStatement::Delete(delete) = &mut statement {
+        delete.returning = Some(returning);
+    }
+
+    Ok(Some(statement))
+}
+
+/// Reads `CREATE [OR REPLACE] TRIGGER name {BEFORE | AFTER} event [OR event ...] ON table
+/// [FOR EACH ROW] [WHEN condition] BEGIN statement; ... END`.
+///
+/// The parser only knows triggers executing a function, so the SQL of each statement of the
+/// body is kept as a string argument of the procedure the trigger executes. `NEW` and `OLD` in
+/// the condition are read in any case.
+fn parse_create_trigger(parser: &mut Parser) -> Result<Option<SqlStatement>, ParserError> {
+    let or_replace = if parser.parse_keywords(&[Keyword::CREATE, Keyword::TRIGGER]) {
+        false
+    } else if parser.parse_keywords(&[
+        Keyword::CREATE,
+        Keyword::OR,
+        Keyword::REPLACE,
+        Keyword::TRIGGER,
+    ]) {
+        true
+    } else {
+        return Ok(None);
+    };
+
+    let name = parser.parse_object_name(false)?;
+    let period = parser.parse_trigger_period()?;
+    let events = parser.parse_keyword_separated(Keyword::OR, Parser::parse_trigger_event)?;
+    parser.expect_keyword(Keyword::ON)?;
+    let table_name = parser.parse_object_name(false)?;
+    let _ = parser.parse_keywords(&[Keyword::FOR, Keyword::EACH, Keyword::ROW]);
+    let mut condition = parser
+        .parse_keyword(Keyword::WHEN)
+        .then(|| parser.parse_expr())
+        .transpose()?;
+    if let Some(condition) = condition.as_mut() {
+        let _ = visit_expressions_mut(condition, |expr| {
+            if let SqlExpr::CompoundIdentifier(idents) = expr {
+                if let [alias, _] = idents.as_mut_slice() {
+                    if alias.value.eq_ignore_ascii_case("NEW")
+                        || alias.value.eq_ignore_ascii_case("OLD")
+                    {
+                        alias.value.make_ascii_uppercase();
+                    }
+                }
+            }
+
+            ControlFlow::<()>::Continue(())
+        });
+    }
+
+    parser.expect_keyword(Keyword::BEGIN)?;
+    let mut body = Vec::new();
+    while !parser.parse_keyword(Keyword::END) {
+        let statement = parser.parse_statement()?;
+        parser.expect_token(&Token::SemiColon)?;
+
+        body.push(OperateFunctionArg {
+            mode: None,
+            name: None,
+            data_type: SqlDataType::Text,
+            default_expr: Some(SqlExpr::Value(SqlValue::SingleQuotedString(
+                statement.to_string(),
+            ))),
+        });
+    }
+
+    Ok(Some(SqlStatement::CreateTrigger {
+        or_replace,
+        is_constraint: false,
+        name,
+        period,
+        events,
+        table_name,
+        referenced_table_name: None,
+        referencing: Vec::new(),
+        trigger_object: TriggerObject::Row,
+        include_each: true,
+        condition,
+        exec_body: TriggerExecBody {
+            exec_type: TriggerExecBodyType::Procedure,
+            func_desc: FunctionDesc {
+                name: ObjectName(Vec::new()),
+                args: Some(body),
+            },
+        },
+        characteristics: None,
+    }))
+}
+
+/// Tokens of `sql`, checked to parse, with each `?` placeholder numbered as `$1`, `$2`, ... by
+/// its position among the `?` placeholders, and the number of parameters the placeholders
+/// read, the highest of their numbers.
+pub(crate) fn tokenize_prepared(sql: &str) -> Result<(Vec<TokenWithLocation>, usize)> {
+    let parser_error = |e: ParserError| Error::Parser(format!("{e:#?}"));
+    let mut tokens = tokenize_questions(sql).map_err(|e| parser_error(e.into()))?;
+
+    let mut questions = 0;
+    let mut params = 0;
+    for TokenWithLocation { token, .. } in tokens.iter_mut() {
+        let n = match token {
+            Token::Placeholder(placeholder) if placeholder.starts_with('$') => placeholder[1..]
+                .parse::<usize>()
+                .ok()
+                .filter(|n| *n > 0)
+                .ok_or_else(|| PreparedError::InvalidPlaceholder(placeholder.clone()))?,
+            token if token.to_string() == "?" => {
+                questions += 1;
+                *token = Token::Placeholder(format!("${questions}"));
+
+                questions
+            }
+            _ => continue,
+        };
+
+        params = params.max(n);
+    }
+
+    let tokens = match_against(user_as_role(tokens));
+    parse_statements(Parser::new(&DIALECT).with_tokens_with_locations(tokens.clone()))
+        .map_err(parser_error)?;
+
+    Ok((tokens, params))
+}
+
+/// Same as [`Tokenizer::tokenize_with_location`], but keeps the character after each `?`, which
+/// the tokenizer of PostgreSQL drops along with the `?` unless they make `?|` or `?&`. The SQL
+/// after each `?` is tokenized again, its tokens moved to their locations in the whole SQL.
+fn tokenize_questions(sql: &str) -> Result<Vec<TokenWithLocation>, TokenizerError> {
+    let mut tokens = Vec::new();
+    let mut rest = sql;
+    let mut start = Location { line: 1, column: 1 };
+
+    loop {
+        let mut scanned = Tokenizer::new(&DIALECT, rest).tokenize_with_location()?;
+        let question = scanned
+            .iter()
+            .position(|TokenWithLocation { token, .. }| token == &Token::Question);
+        let end = question.map_or(scanned.len(), |i| i + 1);
+        let location = question.map(|i| scanned[i].location);
+
+        tokens.extend(scanned.drain(..end).map(|mut token| {
+            token.location = shift(start, token.location);
+            token
+        }));
+
+        let Some(Location { line, column }) = location else {
+            return Ok(tokens);
+        };
+
+        let line_start = rest
+            .split_inclusive('\n')
+            .take(line as usize - 1)
+            .map(str::len)
+            .sum::<usize>();
+        let offset = rest[line_start..]
+            .char_indices()
+            .nth(column as usize - 1)
+            .map_or(rest.len(), |(offset, _)| line_start + offset);
+
+        rest = &rest[(offset + 1).min(rest.len())..];
+        start = shift(
+            start,
+            Location {
+                line,
+                column: column + 1,
+            },
+        );
+    }
+}
+
+/// Location in the whole SQL of a location in the part of it starting at `start`.
+fn shift(start: Location, location: Location) -> Location {
+    match location.line {
+        0 => location,
+        1 => Location {
+            line: start.line,
+            column: start.column + location.column - 1,
+        },
+        line => Location {
+            line: start.line + line - 1,
+            column: location.column,
+        },
+    }
+}
+
+/// Same as [`parse`], but reads each placeholder `$n` of the tokens [`tokenize_prepared`] gives
+/// as the value `params[n - 1]` is the SQL of.
+pub(crate) fn parse_prepared(
+    tokens: &[TokenWithLocation],
+    params: &[String],
+) -> Result<Vec<SqlStatement>> {
+    let parser_error = |e: ParserError| Error::Parser(format!("{e:#?}"));
+
+    let mut bound = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let param = match &token.token {
+            Token::Placeholder(placeholder) => placeholder
+                .strip_prefix('$')
+                .and_then(|n| n.parse::<usize>().ok())
+                .and_then(|n| params.get(n.checked_sub(1)?)),
+            _ => None,
+        };
+
+        match param {
+            Some(param) => bound.extend(
+                Tokenizer::new(&DIALECT, &format!("({param})"))
+                    .tokenize_with_location()
+                    .map_err(|e| parser_error(e.into()))?,
+            ),
+            None => bound.push(token.clone()),
+        }
+    }
+
+    parse_statements(Parser::new(&DIALECT).with_tokens_with_locations(bound)).map_err(parser_error)
+}
+
+/// Rewrites `CREATE USER` and `DROP USER` to their `ROLE` forms, the only ones the parser
+/// knows; GlueSQL does not tell users and roles apart.
+fn user_as_role(mut tokens: Vec<TokenWithLocation>) -> Vec<TokenWithLocation> {
+    let is_keyword = |token: &Token, keywords: &[Keyword]| matches!(token, Token::Word(word) if word.quote_style.is_none() && keywords.contains(&word.keyword));
+
+    let words = (0..tokens.len())
+        .filter(|i| !matches!(tokens[*i].token, Token::Whitespace(_)))
+        .collect::<Vec<_>>();
+    for (n, i) in words.iter().copied().enumerate() {
+        let statement_start = match n.checked_sub(2) {
+            None => n == 1,
+            Some(before) => tokens[words[before]].token == Token::SemiColon,
+        };
+        if statement_start
+            && is_keyword(
+                &tokens[words[n - 1]].token,
+                &[Keyword::CREATE, Keyword::DROP],
+            )
+            && is_keyword(&tokens[i].token, &[Keyword::USER])
+        {
+            tokens[i].token = Token::make_keyword("ROLE");
+        }
+    }
+
+    tokens
+}
+
+/// Rewrites `MATCH(exprs) AGAINST(query)` as `MATCH(exprs, query)`, the function call the parser
+/// reads it as; the parser knows `AGAINST` in the MySQL dialect only.
+fn match_against(mut tokens: Vec<TokenWithLocation>) -> Vec<TokenWithLocation> {
+    let is_word = |token: &Token, value: &str| matches!(token, Token::Word(word) if word.quote_style.is_none() && word.value.eq_ignore_ascii_case(value));
+
+    let words = (0..tokens.len())
+        .filter(|i| !matches!(tokens[*i].token, Token::Whitespace(_)))
+        .collect::<Vec<_>>();
+    let mut dropped = vec![false; tokens.len()];
+    for n in 0..words.len() {
+        let opened = words
+            .get(n + 1)
+            .is_some_and(|i| tokens[*i].token == Token::LParen);
+        if !is_word(&tokens[words[n]].token, "MATCH") || !opened {
+            continue;
+        }
+
+        let mut depth = 0;
+        let closed = words.iter().enumerate().skip(n + 1).find(|(_, i)| {
+            match tokens[**i].token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth -= 1,
+                _ => {}
+            }
+
+            depth == 0
+        });
+        let Some((m, &close)) = closed else {
+            continue;
+        };
+
+        match (words.get(m + 1), words.get(m + 2)) {
+            (Some(&against), Some(&open))
+                if is_word(&tokens[against].token, "AGAINST")
+                    && tokens[open].token == Token::LParen =>
+            {
+                tokens[close].token = Token::Comma;
+                dropped[close + 1..=open].fill(true);
+            }
+            _ => {}
+        }
+    }
+
+    tokens
+        .into_iter()
+        .zip(dropped)
+        .filter_map(|(token, dropped)| (!dropped).then_some(token))
+        .collect()
+}
+
+/// Replaces the literals of `sql` with `?` and drops its comments, keeping the shape of the
+/// query. SQL which cannot be tokenized is redacted entirely.
+pub fn redact<Sql: AsRef<str>>(sql: Sql) -> String {
+    let Ok(tokens) = Tokenizer::new(&DIALECT, sql.as_ref()).tokenize() else {
+        return "?".to_owned();
+    };
+
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            Token::Number(..)
+            | Token::SingleQuotedString(_)
+            | Token::DoubleQuotedString(_)
+            | Token::TripleSingleQuotedString(_)
+            | Token::TripleDoubleQuotedString(_)
+            | Token::DollarQuotedString(_)
+            | Token::SingleQuotedByteStringLiteral(_)
+            | Token::DoubleQuotedByteStringLiteral(_)
+            | Token::TripleSingleQuotedByteStringLiteral(_)
+            | Token::TripleDoubleQuotedByteStringLiteral(_)
+            | Token::SingleQuotedRawStringLiteral(_)
+            | Token::DoubleQuotedRawStringLiteral(_)
+            | Token::TripleSingleQuotedRawStringLiteral(_)
+            | Token::TripleDoubleQuotedRawStringLiteral(_)
+            | Token::NationalStringLiteral(_)
+            | Token::EscapedStringLiteral(_)
+            | Token::UnicodeStringLiteral(_)
+            | Token::HexStringLiteral(_) => "?".to_owned(),
+            Token::Whitespace(
+                Whitespace::SingleLineComment { .. } | Whitespace::MultiLineComment(_),
+            ) => " ".to_owned(),
+            token => token.to_string(),
+        })
+        .collect()
 }
 
 macro_rules! generate_parse_fn {
     ($fn_name: ident, $output_type: ty) => {
         pub fn $fn_name<Sql: AsRef<str>>(sql_expr: Sql) -> Result<$output_type> {
             let tokens = Tokenizer::new(&DIALECT, sql_expr.as_ref())
-                .tokenize()
+                .tokenize_with_location()
                 .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
 
             Parser::new(&DIALECT)
-                .with_tokens(tokens)
+                .with_tokens_with_locations(match_against(tokens))
                 .$fn_name()
                 .map_err(|e| Error::Parser(format!("{:#?}", e)))
         }
@@ -34,11 +500,11 @@ macro_rules! generate_parse_fn {
     ($fn_name: ident, $parse_fn_name: ident, $parse_fn_arg: ident, $output_type: ty) => {
         pub fn $fn_name<Sql: AsRef<str>>(sql_expr: Sql) -> Result<$output_type> {
             let tokens = Tokenizer::new(&DIALECT, sql_expr.as_ref())
-                .tokenize()
+                .tokenize_with_location()
                 .map_err(|e| Error::Parser(format!("{:#?}", e)))?;
 
             Parser::new(&DIALECT)
-                .with_tokens(tokens)
+                .with_tokens_with_locations(match_against(tokens))
                 .$parse_fn_name(Parser::$parse_fn_arg)
                 .map_err(|e| Error::Parser(format!("{:#?}", e)))
         }
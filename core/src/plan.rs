@@ -1,4 +1,5 @@
 mod context;
+mod decorrelate;
 mod error;
 mod evaluable;
 mod expr;
@@ -6,22 +7,39 @@ mod index;
 mod join;
 mod planner;
 mod primary_key;
+mod recursive;
 mod schema;
 mod validate;
+mod view;
 
 use crate::{ast::Statement, result::Result, store::Store};
 
+pub(crate) use {expr::PlanExpr, join::column_type, view::expand_views};
+
 pub use {
-    self::validate::validate, error::*, index::plan as plan_index, join::plan as plan_join,
-    primary_key::plan as plan_primary_key, schema::fetch_schema_map,
+    self::validate::validate, decorrelate::plan as plan_decorrelate, error::*,
+    index::plan as plan_index, join::plan as plan_join, primary_key::plan as plan_primary_key,
+    recursive::plan as plan_recursion_depth, schema::fetch_schema_map,
+    schema::fetch_statistics_map, view::plan as plan_view,
 };
 
+#[tracing::instrument(skip_all)]
 pub async fn plan<T: Store>(storage: &T, statement: Statement) -> Result<Statement> {
+    if let Statement::Explain { analyze, query } = statement {
+        return match Box::pin(plan(storage, Statement::Query(query))).await? {
+            Statement::Query(query) => Ok(Statement::Explain { analyze, query }),
+            statement => Ok(statement),
+        };
+    }
+
+    let statement = plan_view(storage, statement).await?;
     let schema_map = fetch_schema_map(storage, &statement).await?;
     validate(&schema_map, &statement)?;
+    let statement = plan_decorrelate(&schema_map, statement);
     let statement = plan_primary_key(&schema_map, statement);
-    let statement = plan_index(&schema_map, statement)?;
-    let statement = plan_join(&schema_map, statement);
+    let statistics_map = fetch_statistics_map(storage, &schema_map).await?;
+    let statement = plan_index(&schema_map, &statistics_map, statement)?;
+    let statement = plan_join(&schema_map, &statistics_map, statement);
 
     Ok(statement)
 }
@@ -0,0 +1,400 @@
+use {
+    super::PlanExpr,
+    crate::{
+        ast::{
+            AggregateFunction, BinaryOperator, Expr, Join, JoinConstraint, JoinExecutor,
+            JoinOperator, Query, Select, SelectItem, SetExpr, Statement, TableAlias, TableFactor,
+            TableWithJoins,
+        },
+        data::Schema,
+    },
+    std::collections::HashMap,
+};
+
+/// Turns the correlated aggregate subqueries of the projection, such as
+/// `(SELECT MAX(score) FROM Score WHERE Score.user_id = User.id)`, into a hash join on the
+/// aggregates grouped by the correlated column, so the subquery runs once instead of once per
+/// row.
+pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> Statement {
+    match statement {
+        Statement::Query(Query {
+            body: SetExpr::Select(select),
+            order_by,
+            limit,
+            offset,
+        }) => Statement::Query(Query {
+            body: SetExpr::Select(Box::new(plan_select(schema_map, *select))),
+            order_by,
+            limit,
+            offset,
+        }),
+        _ => statement,
+    }
+}
+
+fn plan_select(schema_map: &HashMap<String, Schema>, mut select: Select) -> Select {
+    let wildcard = select
+        .projection
+        .iter()
+        .any(|item| matches!(item, SelectItem::Wildcard));
+    let aggregated = select
+        .projection
+        .iter()
+        .any(|item| matches!(item, SelectItem::Expr { expr, .. } if contains_aggregate(expr)));
+    if wildcard || aggregated || !select.group_by.is_empty() || select.having.is_some() {
+        return select;
+    }
+
+    for (i, item) in select.projection.iter_mut().enumerate() {
+        let SelectItem::Expr {
+            expr: expr @ Expr::Subquery(_),
+            ..
+        } = item
+        else {
+            continue;
+        };
+        let Expr::Subquery(query) = &*expr else {
+            continue;
+        };
+
+        let alias = format!("__subquery_{i}");
+        if let Some(relation) = decorrelate(schema_map, query, &alias) {
+            select.from.joins.push(relation);
+            *expr = Expr::CompoundIdentifier {
+                alias,
+                ident: "value".to_owned(),
+            };
+        }
+    }
+
+    select
+}
+
+/// Derived table of `SELECT key, aggregate GROUP BY key` joined on `key = outer expression`,
+/// when the subquery aggregates a single table filtered by a single such equality.
+fn decorrelate(schema_map: &HashMap<String, Schema>, query: &Query, alias: &str) -> Option<Join> {
+    let Query {
+        body: SetExpr::Select(select),
+        order_by,
+        limit: None,
+        offset: None,
+    } = query
+    else {
+        return None;
+    };
+    let Select {
+        distinct: false,
+        projection,
+        from:
+            TableWithJoins {
+                relation:
+                    relation @ TableFactor::Table {
+                        name,
+                        alias: table_alias,
+                        index: None,
+                    },
+                joins,
+            },
+        selection: Some(selection),
+        group_by,
+        having: None,
+    } = select.as_ref()
+    else {
+        return None;
+    };
+    let [
+        SelectItem::Expr {
+            expr: aggregate @ Expr::Aggregate(aggr),
+            ..
+        },
+    ] = projection.as_slice()
+    else {
+        return None;
+    };
+    let aggregates = matches!(
+        aggr.func,
+        AggregateFunction::Count(_)
+            | AggregateFunction::Sum(_)
+            | AggregateFunction::Max(_)
+            | AggregateFunction::Min(_)
+            | AggregateFunction::Avg(_)
+            | AggregateFunction::Variance(_)
+            | AggregateFunction::Stdev(_)
+    );
+    if !aggregates
+        || aggr.over.is_some()
+        || !order_by.is_empty()
+        || !joins.is_empty()
+        || !group_by.is_empty()
+    {
+        return None;
+    }
+
+    let inner = Inner {
+        alias: table_alias
+            .as_ref()
+            .map_or(name, |TableAlias { name, .. }| name),
+        columns: schema_map
+            .get(name)?
+            .column_defs
+            .as_ref()?
+            .iter()
+            .map(|column_def| column_def.name.as_str())
+            .collect(),
+    };
+    if !matches!(inner.refs(aggregate), Refs::None | Refs::Inner) {
+        return None;
+    }
+
+    let mut correlated = None;
+    let mut filters = Vec::new();
+    for expr in conjuncts(selection) {
+        match inner.refs(expr) {
+            Refs::None | Refs::Inner => filters.push(expr.clone()),
+            Refs::Both if correlated.is_none() => {
+                correlated = Some(inner.correlation(expr)?);
+            }
+            Refs::Outer | Refs::Both | Refs::Query => return None,
+        }
+    }
+    let (key, value) = correlated?;
+
+    filters.push(Expr::IsNotNull(Box::new(key.clone())));
+    let selection = filters.into_iter().reduce(|left, right| Expr::BinaryOp {
+        left: Box::new(left),
+        op: BinaryOperator::And,
+        right: Box::new(right),
+    });
+    let subquery = Query {
+        body: SetExpr::Select(Box::new(Select {
+            distinct: false,
+            projection: vec![
+                SelectItem::Expr {
+                    expr: key.clone(),
+                    label: "key".to_owned(),
+                },
+                SelectItem::Expr {
+                    expr: aggregate.clone(),
+                    label: "value".to_owned(),
+                },
+            ],
+            from: TableWithJoins {
+                relation: relation.clone(),
+                joins: Vec::new(),
+            },
+            selection,
+            group_by: vec![key],
+            having: None,
+        })),
+        order_by: Vec::new(),
+        limit: None,
+        offset: None,
+    };
+
+    Some(Join {
+        relation: TableFactor::Derived {
+            subquery,
+            alias: TableAlias {
+                name: alias.to_owned(),
+                columns: Vec::new(),
+            },
+        },
+        join_operator: JoinOperator::LeftOuter(JoinConstraint::None),
+        join_executor: JoinExecutor::Hash {
+            key_expr: Expr::CompoundIdentifier {
+                alias: alias.to_owned(),
+                ident: "key".to_owned(),
+            },
+            value_expr: value,
+            where_clause: None,
+        },
+    })
+}
+
+fn conjuncts(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut exprs = conjuncts(left);
+            exprs.extend(conjuncts(right));
+            exprs
+        }
+        Expr::Nested(expr) => conjuncts(expr),
+        _ => vec![expr],
+    }
+}
+
+fn contains_aggregate(expr: &Expr) -> bool {
+    if matches!(expr, Expr::Aggregate(_)) {
+        return true;
+    }
+
+    match PlanExpr::from(expr) {
+        PlanExpr::None
+        | PlanExpr::Identifier(_)
+        | PlanExpr::CompoundIdentifier { .. }
+        | PlanExpr::Query(_) => false,
+        PlanExpr::Expr(expr) | PlanExpr::QueryAndExpr { expr, .. } => contains_aggregate(expr),
+        PlanExpr::TwoExprs(expr, expr2) => contains_aggregate(expr) || contains_aggregate(expr2),
+        PlanExpr::ThreeExprs(expr, expr2, expr3) => {
+            contains_aggregate(expr) || contains_aggregate(expr2) || contains_aggregate(expr3)
+        }
+        PlanExpr::MultiExprs(exprs) => exprs.into_iter().any(contains_aggregate),
+    }
+}
+
+/// Tables an expression of the subquery reads from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Refs {
+    None,
+    Inner,
+    Outer,
+    Both,
+    Query,
+}
+
+impl Refs {
+    fn merge(self, other: Refs) -> Refs {
+        match (self, other) {
+            (Refs::Query, _) | (_, Refs::Query) => Refs::Query,
+            (Refs::None, refs) | (refs, Refs::None) => refs,
+            (Refs::Inner, Refs::Inner) => Refs::Inner,
+            (Refs::Outer, Refs::Outer) => Refs::Outer,
+            _ => Refs::Both,
+        }
+    }
+}
+
+/// Table of the subquery, whose columns take precedence over those of the outer query.
+struct Inner<'a> {
+    alias: &'a str,
+    columns: Vec<&'a str>,
+}
+
+impl Inner<'_> {
+    fn refs(&self, expr: &Expr) -> Refs {
+        match PlanExpr::from(expr) {
+            PlanExpr::None => Refs::None,
+            PlanExpr::Identifier(ident) if self.columns.contains(&ident) => Refs::Inner,
+            PlanExpr::CompoundIdentifier { alias, .. } if alias == self.alias => Refs::Inner,
+            PlanExpr::Identifier(_) | PlanExpr::CompoundIdentifier { .. } => Refs::Outer,
+            PlanExpr::Expr(expr) => self.refs(expr),
+            PlanExpr::TwoExprs(expr, expr2) => self.refs(expr).merge(self.refs(expr2)),
+            PlanExpr::ThreeExprs(expr, expr2, expr3) => self
+                .refs(expr)
+                .merge(self.refs(expr2))
+                .merge(self.refs(expr3)),
+            PlanExpr::MultiExprs(exprs) => exprs
+                .into_iter()
+                .fold(Refs::None, |refs, expr| refs.merge(self.refs(expr))),
+            PlanExpr::Query(_) | PlanExpr::QueryAndExpr { .. } => Refs::Query,
+        }
+    }
+
+    /// Splits `inner expression = outer expression` into its inner and outer sides.
+    fn correlation(&self, expr: &Expr) -> Option<(Expr, Expr)> {
+        let Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        } = expr
+        else {
+            return None;
+        };
+
+        match (self.refs(left), self.refs(right)) {
+            (Refs::Inner, Refs::Outer) => Some((*left.clone(), *right.clone())),
+            (Refs::Outer, Refs::Inner) => Some((*right.clone(), *left.clone())),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::plan as plan_decorrelate,
+        crate::{
+            ast::{Expr, SelectItem, SetExpr, Statement, TableFactor},
+            mock::{MockStorage, run},
+            parse_sql::parse,
+            plan::fetch_schema_map,
+            translate::translate,
+        },
+        futures::executor::block_on,
+    };
+
+    fn plan(storage: &MockStorage, sql: &str) -> Statement {
+        let parsed = parse(sql).expect(sql).into_iter().next().unwrap();
+        let statement = translate(&parsed).unwrap();
+        let schema_map = block_on(fetch_schema_map(storage, &statement)).unwrap();
+
+        plan_decorrelate(&schema_map, statement)
+    }
+
+    fn joined(statement: &Statement) -> Vec<String> {
+        let Statement::Query(query) = statement else {
+            return Vec::new();
+        };
+        let SetExpr::Select(select) = &query.body else {
+            return Vec::new();
+        };
+
+        select
+            .from
+            .joins
+            .iter()
+            .filter_map(|join| match &join.relation {
+                TableFactor::Derived { alias, .. } => Some(alias.name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn correlated_aggregate() {
+        let storage = run("
+            CREATE TABLE Users (id INTEGER, name TEXT);
+            CREATE TABLE Score (user_id INTEGER, score INTEGER);
+        ");
+
+        let sql = "
+            SELECT name, (SELECT MAX(score) FROM Score s WHERE s.user_id = u.id AND score > 0)
+            FROM Users u
+        ";
+        let statement = plan(&storage, sql);
+        assert_eq!(joined(&statement), vec!["__subquery_1"], "{sql}");
+
+        let Statement::Query(query) = &statement else {
+            unreachable!();
+        };
+        let SetExpr::Select(select) = &query.body else {
+            unreachable!();
+        };
+        assert!(
+            matches!(
+                &select.projection[1],
+                SelectItem::Expr { expr: Expr::CompoundIdentifier { alias, ident }, .. }
+                    if alias == "__subquery_1" && ident == "value"
+            ),
+            "{sql}"
+        );
+
+        let test_cases = [
+            "SELECT *, (SELECT MAX(score) FROM Score WHERE user_id = id) FROM Users",
+            "SELECT COUNT(*), (SELECT MAX(score) FROM Score WHERE user_id = id) FROM Users",
+            "SELECT (SELECT score FROM Score WHERE user_id = id) FROM Users",
+            "SELECT (SELECT MAX(score) FROM Score WHERE user_id = id OR score > 0) FROM Users",
+            "SELECT (SELECT MAX(score) FROM Score WHERE user_id > id) FROM Users",
+            "SELECT (SELECT MAX(score) FROM Score WHERE user_id = id AND score = id) FROM Users",
+            "SELECT (SELECT MAX(score) FROM Score WHERE score > 0) FROM Users",
+            "SELECT (SELECT MAX(id) FROM Score WHERE user_id = Users.id) FROM Users",
+        ];
+        for sql in test_cases {
+            assert_eq!(joined(&plan(&storage, sql)), Vec::<String>::new(), "{sql}");
+        }
+    }
+}
@@ -7,6 +7,12 @@ pub enum PlanError {
     #[error("column reference {0} is ambiguous, please specify the table name")]
     ColumnReferenceAmbiguous(String),
 
+    #[error("cannot insert into, update or delete from view: {0}")]
+    ViewNotWritable(String),
+
+    #[error("cannot insert into, update or delete from sequence: {0}, use NEXTVAL or SETVAL")]
+    SequenceNotWritable(String),
+
     #[error("unreachable")]
     Unreachable,
 }
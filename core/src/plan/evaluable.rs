@@ -46,15 +46,7 @@ fn check_query(context: Option<Arc<Context<'_>>>, query: &Query) -> bool {
         offset,
     } = query;
 
-    let body = match body {
-        SetExpr::Select(select) => check_select(context.as_ref().map(Arc::clone), select),
-        SetExpr::Values(Values(rows)) => rows
-            .iter()
-            .flatten()
-            .all(|expr| check_expr(context.as_ref().map(Arc::clone), expr)),
-    };
-
-    if !body {
+    if !check_set_expr(context.as_ref().map(Arc::clone), body) {
         return false;
     }
 
@@ -72,6 +64,19 @@ fn check_query(context: Option<Arc<Context<'_>>>, query: &Query) -> bool {
         .all(|expr| check_expr(context.as_ref().map(Arc::clone), expr))
 }
 
+fn check_set_expr(context: Option<Arc<Context<'_>>>, set_expr: &SetExpr) -> bool {
+    match set_expr {
+        SetExpr::Select(select) => check_select(context, select),
+        SetExpr::Values(Values(rows)) => rows
+            .iter()
+            .flatten()
+            .all(|expr| check_expr(context.as_ref().map(Arc::clone), expr)),
+        SetExpr::SetOperation { left, right, .. } => {
+            check_set_expr(context.as_ref().map(Arc::clone), left) && check_set_expr(context, right)
+        }
+    }
+}
+
 fn check_select(context: Option<Arc<Context<'_>>>, select: &Select) -> bool {
     let Select {
         distinct: _,
@@ -132,7 +137,9 @@ fn check_table_factor(context: Option<Arc<Context<'_>>>, table_factor: &TableFac
             .map(|TableAlias { name, .. }| name)
             .unwrap_or_else(|| name),
         TableFactor::Derived { alias, .. }
+        | TableFactor::Recursive { alias, .. }
         | TableFactor::Series { alias, .. }
+        | TableFactor::Fake { alias, .. }
         | TableFactor::Dictionary { alias, .. } => &alias.name,
     };
 
@@ -32,6 +32,9 @@ impl<'a> From<&'a Expr> for PlanExpr<'a> {
             | Expr::IsNull(expr)
             | Expr::IsNotNull(expr)
             | Expr::Interval { expr, .. } => PlanExpr::Expr(expr),
+            Expr::Aggregate(aggregate) if aggregate.over.is_some() => {
+                PlanExpr::MultiExprs(aggregate.as_exprs().collect())
+            }
             Expr::Aggregate(aggregate) => match aggregate.as_expr() {
                 Some(expr) => PlanExpr::Expr(expr),
                 None => PlanExpr::None,
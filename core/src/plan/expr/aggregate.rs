@@ -1,18 +1,65 @@
-use crate::ast::{Aggregate, AggregateFunction, CountArgExpr, Expr};
+use crate::ast::{Aggregate, AggregateFunction, CountArgExpr, Expr, WindowSpec};
 
 impl Aggregate {
     pub fn as_expr(&self) -> Option<&Expr> {
         match &self.func {
-            AggregateFunction::Count(CountArgExpr::Wildcard) => None,
+            AggregateFunction::Count(CountArgExpr::Wildcard)
+            | AggregateFunction::RowNumber
+            | AggregateFunction::Rank
+            | AggregateFunction::DenseRank => None,
             AggregateFunction::Count(CountArgExpr::Expr(expr))
             | AggregateFunction::Sum(expr)
             | AggregateFunction::Max(expr)
             | AggregateFunction::Min(expr)
             | AggregateFunction::Avg(expr)
             | AggregateFunction::Variance(expr)
-            | AggregateFunction::Stdev(expr) => Some(expr),
+            | AggregateFunction::Stdev(expr)
+            | AggregateFunction::Lag { expr, .. }
+            | AggregateFunction::Lead { expr, .. } => Some(expr),
         }
     }
+
+    /// Whether it is one of the functions valid only with `OVER`, such as `ROW_NUMBER`.
+    pub fn is_window_function(&self) -> bool {
+        matches!(
+            self.func,
+            AggregateFunction::RowNumber
+                | AggregateFunction::Rank
+                | AggregateFunction::DenseRank
+                | AggregateFunction::Lag { .. }
+                | AggregateFunction::Lead { .. }
+        )
+    }
+
+    /// Every expression the aggregate reads, including the arguments of `LAG` and `LEAD` and
+    /// the `PARTITION BY` and `ORDER BY` expressions of its window.
+    pub fn as_exprs(&self) -> impl Iterator<Item = &Expr> {
+        let (offset, default) = match &self.func {
+            AggregateFunction::Lag {
+                offset, default, ..
+            }
+            | AggregateFunction::Lead {
+                offset, default, ..
+            } => (offset.as_ref(), default.as_ref()),
+            _ => (None, None),
+        };
+        let window = self.over.iter().flat_map(
+            |WindowSpec {
+                 partition_by,
+                 order_by,
+             }| {
+                partition_by
+                    .iter()
+                    .chain(order_by.iter().map(|order_by| &order_by.expr))
+            },
+        );
+
+        self.as_expr()
+            .into_iter()
+            .chain(offset)
+            .chain(default)
+            .chain(window)
+    }
 }
 
 #[cfg(test)]
@@ -60,5 +107,31 @@ mod tests {
         let actual = parse("VARIANCE(id)");
         let expected = Expr::Identifier("id".to_owned());
         assert_eq!(actual.as_expr(), Some(&expected));
+
+        assert_eq!(parse("ROW_NUMBER() OVER ()").as_expr(), None);
+    }
+
+    #[test]
+    fn as_exprs() {
+        let exprs = |sql| {
+            parse(sql)
+                .as_exprs()
+                .map(|expr| match expr {
+                    Expr::Identifier(ident) => ident.to_owned(),
+                    _ => unreachable!("only identifiers in as_exprs tests"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(exprs("COUNT(*)"), Vec::<String>::new());
+        assert_eq!(exprs("SUM(id)"), vec!["id"]);
+        assert_eq!(
+            exprs("RANK() OVER (PARTITION BY city ORDER BY score DESC)"),
+            vec!["city", "score"]
+        );
+        assert_eq!(
+            exprs("LEAD(id, n, fallback) OVER (ORDER BY id)"),
+            vec!["id", "n", "fallback", "id"]
+        );
     }
 }
@@ -67,6 +67,8 @@ impl Function {
             | Self::IsEmpty(expr)
             | Self::Sort { expr, order: None }
             | Self::Dedup(expr)
+            | Self::NextVal(expr)
+            | Self::CurrVal(expr)
             | Self::Entries(expr)
             | Self::Keys(expr)
             | Self::Values(expr) => Exprs::Single([expr].into_iter()),
@@ -166,7 +168,22 @@ impl Function {
                 geometry1: expr,
                 geometry2: expr2,
             }
-            | Self::AddMonth { expr, size: expr2 } => Exprs::Double([expr, expr2].into_iter()),
+            | Self::AddMonth { expr, size: expr2 }
+            | Self::RegexpMatches {
+                expr,
+                pattern: expr2,
+            }
+            | Self::RegexpExtract {
+                expr,
+                pattern: expr2,
+                group: None,
+            }
+            | Self::JsonExtract { expr, path: expr2 }
+            | Self::JsonRemove { expr, path: expr2 }
+            | Self::SetVal {
+                name: expr,
+                value: expr2,
+            } => Exprs::Double([expr, expr2].into_iter()),
 
             Self::Lpad {
                 expr,
@@ -203,6 +220,21 @@ impl Function {
                 begin_index: expr2,
                 end_index: expr3,
                 values: None,
+            }
+            | Self::RegexpReplace {
+                expr,
+                pattern: expr2,
+                replacement: expr3,
+            }
+            | Self::RegexpExtract {
+                expr,
+                pattern: expr2,
+                group: Some(expr3),
+            }
+            | Self::JsonSet {
+                expr,
+                path: expr2,
+                value: expr3,
             } => Exprs::Triple([expr, expr2, expr3].into_iter()),
             Self::Custom { name: _, exprs } => Exprs::VariableArgs(exprs.iter()),
             Self::Coalesce(exprs) => Exprs::VariableArgs(exprs.iter()),
@@ -211,6 +243,9 @@ impl Function {
                 Exprs::VariableArgsWithSingle(once(separator).chain(exprs.iter()))
             }
             Self::Greatest(exprs) => Exprs::VariableArgs(exprs.iter()),
+            Self::Match { exprs, query } => {
+                Exprs::VariableArgsWithSingle(once(query).chain(exprs.iter()))
+            }
             Self::Splice {
                 list_data: expr,
                 begin_index: expr2,
@@ -328,6 +363,10 @@ mod tests {
         test("REPEAT(column, 2)", &["column", "2"]);
         test(r#"UNWRAP(field, "foo.1")"#, &["field", r#""foo.1""#]);
         test(r#"SKIP(list, 2)"#, &[r#""list""#, r#"2"#]);
+        test("REGEXP_MATCHES(body, '[0-9]+')", &["body", "'[0-9]+'"]);
+        test("REGEXP_EXTRACT(body, '[0-9]+')", &["body", "'[0-9]+'"]);
+        test("JSON_EXTRACT(doc, '$.id')", &["doc", "'$.id'"]);
+        test("JSON_REMOVE(doc, '$.id')", &["doc", "'$.id'"]);
 
         // Triple
         test(
@@ -343,6 +382,9 @@ mod tests {
             &[r#"'   >++++("<   '"#, "3", "11"],
         );
         test(r#"SPLICE(list, 2, 4)"#, &["list", "2", "4"]);
+        test("REGEXP_REPLACE(body, 'a+', 'b')", &["body", "'a+'", "'b'"]);
+        test("REGEXP_EXTRACT(body, '(a)+', 1)", &["body", "'(a)+'", "1"]);
+        test("JSON_SET(doc, '$.id', 1)", &["doc", "'$.id'", "1"]);
 
         // Quadruple
         test(
@@ -5,16 +5,27 @@ use {
             AstLiteral, BinaryOperator, Expr, Function, IndexItem, IndexOperator, OrderByExpr,
             Query, Select, SetExpr, Statement, TableAlias, TableFactor, TableWithJoins,
         },
-        data::{Schema, SchemaIndex, SchemaIndexOrd},
+        data::{ColumnStatistics, Schema, SchemaIndex, SchemaIndexOrd, TableStatistics, Value},
         result::Result,
     },
+    bigdecimal::ToPrimitive,
     std::collections::HashMap,
     utils::Vector,
 };
 
-pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> Result<Statement> {
+/// Fraction of the rows of a table above which the statistics of `ANALYZE` take scanning the
+/// table for cheaper than an index scan.
+const INDEX_SCAN_LIMIT: f64 = 0.3;
+
+pub fn plan(
+    schema_map: &HashMap<String, Schema>,
+    statistics_map: &HashMap<String, TableStatistics>,
+    statement: Statement,
+) -> Result<Statement> {
     match statement {
-        Statement::Query(query) => plan_query(schema_map, query).map(Statement::Query),
+        Statement::Query(query) => {
+            plan_query(schema_map, statistics_map, query).map(Statement::Query)
+        }
         _ => Ok(statement),
     }
 }
@@ -22,18 +33,36 @@ pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> Resul
 struct Indexes(Vec<SchemaIndex>);
 
 impl Indexes {
+    /// Takes the indexes a query of the `WHERE` can scan by value, leaving out the full-text
+    /// indexes, see [`fulltext_scan`], and the partial indexes whose predicate it does not imply.
+    fn new(indexes: &[SchemaIndex], selection: Option<&Expr>) -> Self {
+        let indexes = implied(indexes, selection)
+            .into_iter()
+            .filter(|SchemaIndex { fulltext, .. }| !fulltext)
+            .cloned()
+            .collect();
+
+        Self(indexes)
+    }
+
     fn find(&self, target: &Expr) -> Option<String> {
         self.0
             .iter()
-            .find(|SchemaIndex { expr, .. }| expr == target)
+            .find(|SchemaIndex { exprs, .. }| {
+                matches!(exprs.as_slice(), [expr] if unnested(expr) == unnested(target))
+            })
             .map(|SchemaIndex { name, .. }| name.to_owned())
     }
 
     fn find_ordered(&self, target: &OrderByExpr) -> Option<String> {
         self.0
             .iter()
-            .find(|SchemaIndex { expr, order, .. }| {
-                if expr != &target.expr {
+            .find(|SchemaIndex { exprs, order, .. }| {
+                let [expr] = exprs.as_slice() else {
+                    return false;
+                };
+
+                if unnested(expr) != unnested(&target.expr) {
                     return false;
                 }
 
@@ -47,9 +76,161 @@ impl Indexes {
             })
             .map(|SchemaIndex { name, .. }| name.to_owned())
     }
+
+    /// Finds the composite index a full scan of which gives the rows in the order of
+    /// `ORDER BY`, with the direction of the scan.
+    fn find_composite_ordered(&self, order_by: &[OrderByExpr]) -> Option<(String, bool)> {
+        self.0
+            .iter()
+            .filter(|SchemaIndex { exprs, .. }| exprs.len() > 1)
+            .find_map(|index| {
+                composite_order(index, 0, order_by).map(|asc| (index.name.to_owned(), asc))
+            })
+    }
+
+    /// Finds the composite index of which the `WHERE` compares the most leading columns, with
+    /// `=` but for the last one which any comparison can be used for.
+    fn find_composite(&self, conjuncts: &[&Expr]) -> Option<CompositeScan> {
+        let comparisons = conjuncts
+            .iter()
+            .enumerate()
+            .filter_map(|(i, expr)| {
+                comparison(expr).map(|(target, index_op, value)| (i, target, index_op, value))
+            })
+            .collect::<Vec<_>>();
+
+        self.0
+            .iter()
+            .filter(|SchemaIndex { exprs, .. }| exprs.len() > 1)
+            .filter_map(|SchemaIndex { name, exprs, .. }| {
+                let mut scan = CompositeScan {
+                    index_name: name.to_owned(),
+                    index_op: IndexOperator::Eq,
+                    values: Vec::new(),
+                    conjuncts: Vec::new(),
+                };
+
+                for expr in exprs {
+                    let find = |eq: bool| {
+                        comparisons.iter().find(|(_, target, index_op, _)| {
+                            unnested(target) == unnested(expr)
+                                && (index_op == &IndexOperator::Eq) == eq
+                        })
+                    };
+
+                    if let Some((i, _, _, value)) = find(true) {
+                        scan.values.push((*value).clone());
+                        scan.conjuncts.push(*i);
+
+                        continue;
+                    } else if let Some((i, _, index_op, value)) = find(false) {
+                        scan.index_op = index_op.clone();
+                        scan.values.push((*value).clone());
+                        scan.conjuncts.push(*i);
+                    }
+
+                    break;
+                }
+
+                (!scan.values.is_empty()).then_some(scan)
+            })
+            .reduce(|best, scan| match scan.values.len() > best.values.len() {
+                true => scan,
+                false => best,
+            })
+    }
+}
+
+/// Indexes whose predicate the `WHERE` implies, all but the partial indexes of which the
+/// `WHERE` lacks any of the predicate's conjuncts.
+fn implied<'a>(indexes: &'a [SchemaIndex], selection: Option<&Expr>) -> Vec<&'a SchemaIndex> {
+    let selection = selection.map(conjuncts).unwrap_or_default();
+
+    indexes
+        .iter()
+        .filter(|SchemaIndex { predicate, .. }| match predicate {
+            Some(predicate) => conjuncts(predicate)
+                .into_iter()
+                .all(|expr| selection.contains(&expr)),
+            None => true,
+        })
+        .collect()
+}
+
+/// Scan of the full-text index of the expression a `MATCH ... AGAINST` conjunct of the `WHERE`
+/// keeps the rows of a positive relevance for, as `MATCH(body) AGAINST('rust engine') > 0` does.
+/// Only the rows with a term of the query have a positive relevance, and those are the rows
+/// the index holds by the terms. The conjunct is left in the `WHERE` to filter them.
+fn fulltext_scan(indexes: &[SchemaIndex], selection: Option<&Expr>) -> Option<IndexItem> {
+    let fulltext = implied(indexes, selection)
+        .into_iter()
+        .filter(|SchemaIndex { fulltext, .. }| *fulltext)
+        .collect::<Vec<_>>();
+    if fulltext.is_empty() {
+        return None;
+    }
+
+    conjuncts(selection?).into_iter().find_map(|expr| {
+        let (target, index_op, value) = comparison(expr)?;
+        let Expr::Function(function) = unnested(target) else {
+            return None;
+        };
+        let Function::Match { exprs, query } = function.as_ref() else {
+            return None;
+        };
+        let ([target], true) = (exprs.as_slice(), is_stateless(query)) else {
+            return None;
+        };
+        let threshold = match unnested(value) {
+            Expr::Literal(AstLiteral::Number(value)) => value.to_f64()?,
+            _ => return None,
+        };
+        let positive = match index_op {
+            IndexOperator::Gt => threshold >= 0.0,
+            IndexOperator::GtEq => threshold > 0.0,
+            _ => false,
+        };
+        if !positive {
+            return None;
+        }
+
+        fulltext
+            .iter()
+            .find(|SchemaIndex { exprs, .. }| {
+                matches!(exprs.as_slice(), [expr] if unnested(expr) == unnested(target))
+            })
+            .map(|SchemaIndex { name, .. }| IndexItem::FullText {
+                name: name.to_owned(),
+                query: query.clone(),
+            })
+    })
+}
+
+/// Scan of a composite index by the values of its leading columns, compared with `=` but the
+/// last one compared with `index_op`.
+struct CompositeScan {
+    index_name: String,
+    index_op: IndexOperator,
+    values: Vec<Expr>,
+    /// positions of the conjuncts of the `WHERE` the scan replaces
+    conjuncts: Vec<usize>,
 }
 
-fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Query> {
+impl CompositeScan {
+    /// Number of the leading columns equal in all rows the scan gives
+    fn bound(&self) -> usize {
+        match self.index_op {
+            IndexOperator::Eq => self.values.len(),
+            _ => self.values.len() - 1,
+        }
+    }
+}
+
+fn plan_query(
+    schema_map: &HashMap<String, Schema>,
+    statistics_map: &HashMap<String, TableStatistics>,
+    query: Query,
+) -> Result<Query> {
     let Query {
         body,
         order_by,
@@ -67,12 +248,41 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
                 offset,
             });
         }
+        SetExpr::SetOperation {
+            op,
+            all,
+            left,
+            right,
+        } => {
+            let plan_operand = |set_expr: Box<SetExpr>| {
+                let query = Query {
+                    body: *set_expr,
+                    order_by: Vec::new(),
+                    limit: None,
+                    offset: None,
+                };
+
+                plan_query(schema_map, statistics_map, query).map(|query| Box::new(query.body))
+            };
+
+            return Ok(Query {
+                body: SetExpr::SetOperation {
+                    op,
+                    all,
+                    left: plan_operand(left)?,
+                    right: plan_operand(right)?,
+                },
+                order_by,
+                limit,
+                offset,
+            });
+        }
     };
 
     let TableWithJoins { relation, .. } = &select.from;
     let table_name = match relation {
         TableFactor::Table { name, .. } => name,
-        TableFactor::Derived { .. } => {
+        TableFactor::Derived { .. } | TableFactor::Recursive { .. } => {
             return Ok(Query {
                 body: SetExpr::Select(select),
                 order_by,
@@ -83,6 +293,10 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         TableFactor::Series {
             alias: TableAlias { name, .. },
             ..
+        }
+        | TableFactor::Fake {
+            alias: TableAlias { name, .. },
+            ..
         } => name,
         TableFactor::Dictionary {
             alias: TableAlias { name, .. },
@@ -90,8 +304,17 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         } => name,
     };
 
-    let indexes = match schema_map.get(table_name) {
-        Some(Schema { indexes, .. }) => Indexes(indexes.clone()),
+    let (indexes, fulltext) = match schema_map.get(table_name) {
+        Some(Schema { indexes, .. }) => {
+            let selection = select.selection.as_ref();
+            // a primary key lookup has taken the conjunct it looks up out of the `WHERE`
+            let looked_up = matches!(relation, TableFactor::Table { index: Some(_), .. });
+
+            (
+                Indexes::new(indexes, selection),
+                fulltext_scan(indexes, selection).filter(|_| !looked_up),
+            )
+        }
         None => {
             return Ok(Query {
                 body: SetExpr::Select(select),
@@ -111,6 +334,22 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
                 cmp_expr: None,
             })
     });
+    let (index, order_by) = match (fulltext, index) {
+        (Some(fulltext), _) => (Some(fulltext), order_by),
+        (None, Some(index)) => (Some(index), Vector::from(order_by).pop().0.into()),
+        (None, None) => match indexes.find_composite_ordered(&order_by) {
+            Some((name, asc)) => {
+                let index = IndexItem::NonClustered {
+                    name,
+                    asc: Some(asc),
+                    cmp_expr: None,
+                };
+
+                (Some(index), Vec::new())
+            }
+            None => (None, order_by),
+        },
+    };
 
     if index.is_some() {
         let Select {
@@ -126,7 +365,9 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
         let (name, alias) = match relation {
             TableFactor::Table { name, alias, .. } => (name, alias),
             TableFactor::Derived { .. }
+            | TableFactor::Recursive { .. }
             | TableFactor::Series { .. }
+            | TableFactor::Fake { .. }
             | TableFactor::Dictionary { .. } => {
                 return Err(PlanError::Unreachable.into());
             }
@@ -148,12 +389,17 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
 
         Ok(Query {
             body: SetExpr::Select(Box::new(select)),
-            order_by: Vector::from(order_by).pop().0.into(),
+            order_by,
             limit,
             offset,
         })
     } else {
-        let select = plan_select(schema_map, &indexes, *select)?;
+        let statistics = statistics_map.get(table_name);
+        let planned = plan_select(schema_map, statistics_map, &indexes, statistics, *select)?;
+        let (select, order_by) = match planned {
+            (select, Some(scan)) => order_by_composite(&indexes, select, scan, order_by),
+            (select, None) => (select, order_by),
+        };
         let body = SetExpr::Select(Box::new(select));
         let query = Query {
             body,
@@ -166,11 +412,14 @@ fn plan_query(schema_map: &HashMap<String, Schema>, query: Query) -> Result<Quer
     }
 }
 
+/// Plans the index scan of the `WHERE`, returning the scan of a composite index when taken.
 fn plan_select(
     schema_map: &HashMap<String, Schema>,
+    statistics_map: &HashMap<String, TableStatistics>,
     indexes: &Indexes,
+    statistics: Option<&TableStatistics>,
     select: Select,
-) -> Result<Select> {
+) -> Result<(Select, Option<CompositeScan>)> {
     let Select {
         distinct,
         projection,
@@ -183,37 +432,70 @@ fn plan_select(
     let selection = match selection {
         Some(expr) => expr,
         None => {
-            return Ok(Select {
+            let select = Select {
                 distinct,
                 projection,
                 from,
                 selection,
                 group_by,
                 having,
-            });
+            };
+
+            return Ok((select, None));
         }
     };
 
-    match plan_index(schema_map, indexes, selection)? {
-        Planned::Expr(selection) => Ok(Select {
-            distinct,
-            projection,
-            from,
-            selection: Some(selection),
-            group_by,
-            having,
-        }),
-        Planned::IndexedExpr {
-            index_name,
-            index_op,
-            index_value_expr,
-            selection,
-        } => {
+    let (selection, selective) = match statistics {
+        Some(statistics) => by_selectivity(indexes, statistics, selection),
+        None => (selection, None),
+    };
+    let indexes = selective.as_ref().unwrap_or(indexes);
+
+    let composite = indexes.find_composite(&conjuncts(&selection));
+    let planned = match composite {
+        Some(scan) if scan.values.len() > 1 => {
+            plan_composite(schema_map, statistics_map, scan, &selection)?
+        }
+        composite => match (
+            plan_index(schema_map, statistics_map, indexes, selection.clone())?,
+            composite,
+        ) {
+            (Planned::Expr(_), Some(scan)) => {
+                plan_composite(schema_map, statistics_map, scan, &selection)?
+            }
+            (planned, _) => (planned, None),
+        },
+    };
+
+    match planned {
+        (Planned::Expr(selection), _) => {
+            let select = Select {
+                distinct,
+                projection,
+                from,
+                selection: Some(selection),
+                group_by,
+                having,
+            };
+
+            Ok((select, None))
+        }
+        (
+            Planned::IndexedExpr {
+                index_name,
+                index_op,
+                index_value_expr,
+                selection,
+            },
+            composite,
+        ) => {
             let TableWithJoins { relation, joins } = from;
             let (name, alias) = match relation {
                 TableFactor::Table { name, alias, .. } => (name, alias),
                 TableFactor::Derived { .. }
+                | TableFactor::Recursive { .. }
                 | TableFactor::Series { .. }
+                | TableFactor::Fake { .. }
                 | TableFactor::Dictionary { .. } => {
                     return Err(PlanError::Unreachable.into());
                 }
@@ -229,16 +511,292 @@ fn plan_select(
                 joins,
             };
 
-            Ok(Select {
+            let select = Select {
                 distinct,
                 projection,
                 from,
                 selection,
                 group_by,
                 having,
+            };
+
+            Ok((select, composite))
+        }
+    }
+}
+
+/// Plans the scan of a composite index, leaving the conjuncts of the `WHERE` it does not
+/// replace as the selection.
+fn plan_composite(
+    schema_map: &HashMap<String, Schema>,
+    statistics_map: &HashMap<String, TableStatistics>,
+    scan: CompositeScan,
+    selection: &Expr,
+) -> Result<(Planned, Option<CompositeScan>)> {
+    let no_indexes = Indexes(Vec::new());
+    let selection = conjuncts(selection)
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| !scan.conjuncts.contains(i))
+        .map(
+            |(_, expr)| match plan_index(schema_map, statistics_map, &no_indexes, expr.clone())? {
+                Planned::Expr(expr) => Ok(expr),
+                Planned::IndexedExpr { .. } => Err(PlanError::Unreachable.into()),
+            },
+        )
+        .reduce(|left: Result<Expr>, right| {
+            Ok(Expr::BinaryOp {
+                left: Box::new(left?),
+                op: BinaryOperator::And,
+                right: Box::new(right?),
+            })
+        })
+        .transpose()?;
+
+    let planned = Planned::IndexedExpr {
+        index_name: scan.index_name.clone(),
+        index_op: scan.index_op.clone(),
+        index_value_expr: Expr::Array {
+            elem: scan.values.clone(),
+        },
+        selection,
+    };
+
+    Ok((planned, Some(scan)))
+}
+
+/// Puts the conjuncts of the `WHERE` first which the statistics of `ANALYZE` estimate an index
+/// scan of to give the fewest rows, so the most selective one is scanned, and leaves out the
+/// indexes of the conjuncts estimated to give too many rows for an index scan to pay off.
+fn by_selectivity(
+    indexes: &Indexes,
+    statistics: &TableStatistics,
+    selection: Expr,
+) -> (Expr, Option<Indexes>) {
+    if statistics.row_count == 0 {
+        return (selection, None);
+    }
+
+    let estimates = conjuncts(&selection)
+        .into_iter()
+        .map(|expr| {
+            let estimate = selectivity(indexes, statistics, expr);
+
+            (expr, estimate)
+        })
+        .collect::<Vec<_>>();
+    let (selective, unselective): (Vec<_>, Vec<_>) = estimates
+        .iter()
+        .filter_map(|(expr, estimate)| estimate.map(|estimate| (*expr, estimate)))
+        .partition(|(_, estimate)| *estimate <= INDEX_SCAN_LIMIT);
+    if selective.is_empty() && unselective.is_empty() {
+        return (selection, None);
+    }
+
+    let index_name = |expr: &Expr| match unnested(expr) {
+        Expr::IsNull(target) | Expr::IsNotNull(target) => indexes.find(target),
+        expr => comparison(expr).and_then(|(target, _, _)| indexes.find(target)),
+    };
+    let kept = estimates
+        .iter()
+        .filter(|(_, estimate)| estimate.is_none_or(|estimate| estimate <= INDEX_SCAN_LIMIT))
+        .filter_map(|(expr, _)| index_name(expr))
+        .collect::<Vec<_>>();
+    let left_out = unselective
+        .iter()
+        .filter_map(|(expr, _)| index_name(expr))
+        .filter(|name| !kept.contains(name))
+        .collect::<Vec<_>>();
+    let indexes = indexes
+        .0
+        .iter()
+        .filter(|SchemaIndex { name, .. }| !left_out.contains(name))
+        .cloned()
+        .collect();
+
+    let mut ordered = selective;
+    ordered.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+    let ordered = ordered
+        .into_iter()
+        .map(|(expr, _)| expr)
+        .chain(
+            estimates
+                .iter()
+                .filter(|(_, estimate)| estimate.is_none())
+                .map(|(expr, _)| *expr),
+        )
+        .chain(unselective.into_iter().map(|(expr, _)| expr))
+        .collect::<Vec<_>>();
+    let unchanged = ordered
+        .iter()
+        .zip(&estimates)
+        .all(|(expr, (conjunct, _))| expr == conjunct);
+    let selection = match unchanged {
+        true => selection,
+        false => ordered
+            .into_iter()
+            .map(|expr| match expr {
+                Expr::BinaryOp {
+                    op: BinaryOperator::Or,
+                    ..
+                } => Expr::Nested(Box::new(expr.clone())),
+                _ => expr.clone(),
             })
+            .reduce(|left, right| Expr::BinaryOp {
+                left: Box::new(left),
+                op: BinaryOperator::And,
+                right: Box::new(right),
+            })
+            .unwrap_or(selection),
+    };
+
+    (selection, Some(Indexes(indexes)))
+}
+
+/// Estimated fraction of the rows of the table an index scan of the conjunct gives, `None`
+/// when no index scans it or the statistics cannot tell.
+fn selectivity(indexes: &Indexes, statistics: &TableStatistics, expr: &Expr) -> Option<f64> {
+    let column = |target: &Expr| {
+        indexes.find(target)?;
+
+        match unnested(target) {
+            Expr::Identifier(name) | Expr::CompoundIdentifier { ident: name, .. } => {
+                statistics.column(name)
+            }
+            _ => None,
         }
+    };
+    let row_count = statistics.row_count as f64;
+    let nulls = |column: &ColumnStatistics| column.null_count as f64 / row_count;
+
+    let (target, index_op, value) = match unnested(expr) {
+        Expr::IsNull(target) => return column(target).map(nulls),
+        Expr::IsNotNull(target) => return column(target).map(|column| 1.0 - nulls(column)),
+        expr => comparison(expr)?,
+    };
+    let column = column(target)?;
+    let non_null = 1.0 - nulls(column);
+
+    if index_op == IndexOperator::Eq {
+        return match column.distinct_count {
+            0 => Some(0.0),
+            distinct_count => Some(non_null / distinct_count as f64),
+        };
+    }
+
+    let number = |value: &Value| match value {
+        Value::Bool(_) | Value::Str(_) => None,
+        value => f64::try_from(value).ok(),
+    };
+    let value = match unnested(value) {
+        Expr::Literal(AstLiteral::Number(value)) => value.to_f64()?,
+        _ => return None,
+    };
+    let min = number(column.min.as_ref()?)?;
+    let max = number(column.max.as_ref()?)?;
+    let below = match max > min {
+        true => ((value - min) / (max - min)).clamp(0.0, 1.0),
+        false if value > min => 1.0,
+        false => 0.0,
+    };
+
+    match index_op {
+        IndexOperator::Lt | IndexOperator::LtEq => Some(non_null * below),
+        _ => Some(non_null * (1.0 - below)),
+    }
+}
+
+/// Takes the order of the rows of a composite index scan for `ORDER BY` when they are in it,
+/// scanning the index backwards for a descending order.
+fn order_by_composite(
+    indexes: &Indexes,
+    select: Select,
+    scan: CompositeScan,
+    order_by: Vec<OrderByExpr>,
+) -> (Select, Vec<OrderByExpr>) {
+    let asc = indexes
+        .0
+        .iter()
+        .find(|SchemaIndex { name, .. }| name == &scan.index_name)
+        .and_then(|index| composite_order(index, scan.bound(), &order_by));
+    let (Some(asc), false) = (asc, order_by.is_empty()) else {
+        return (select, order_by);
+    };
+
+    let Select {
+        distinct,
+        projection,
+        from,
+        selection,
+        group_by,
+        having,
+    } = select;
+    let TableWithJoins { relation, joins } = from;
+    let relation = match relation {
+        TableFactor::Table {
+            name,
+            alias,
+            index:
+                Some(IndexItem::NonClustered {
+                    name: index_name,
+                    cmp_expr,
+                    ..
+                }),
+        } => TableFactor::Table {
+            name,
+            alias,
+            index: Some(IndexItem::NonClustered {
+                name: index_name,
+                asc: Some(asc),
+                cmp_expr,
+            }),
+        },
+        relation => relation,
+    };
+    let select = Select {
+        distinct,
+        projection,
+        from: TableWithJoins { relation, joins },
+        selection,
+        group_by,
+        having,
+    };
+
+    (select, Vec::new())
+}
+
+/// Direction of the scan of the composite index giving the rows in the order of `ORDER BY`,
+/// where the leading columns up to `bound` are equal in all rows the scan gives, so the
+/// `ORDER BY` can start from any of them.
+fn composite_order(index: &SchemaIndex, bound: usize, order_by: &[OrderByExpr]) -> Option<bool> {
+    let SchemaIndex { exprs, order, .. } = index;
+    let (first, rest) = order_by.split_first()?;
+    let asc = first.asc.unwrap_or(true);
+    if rest
+        .iter()
+        .any(|order_by_expr| order_by_expr.asc.unwrap_or(true) != asc)
+    {
+        return None;
     }
+
+    let ordered = matches!(
+        (asc, order),
+        (_, SchemaIndexOrd::Both) | (true, SchemaIndexOrd::Asc) | (false, SchemaIndexOrd::Desc)
+    );
+    let matched = (0..=bound).any(|start| {
+        exprs
+            .get(start..start + order_by.len())
+            .is_some_and(|exprs| {
+                exprs
+                    .iter()
+                    .zip(order_by)
+                    .all(|(expr, OrderByExpr { expr: target, .. })| {
+                        unnested(expr) == unnested(target)
+                    })
+            })
+    });
+
+    (ordered && matched).then_some(asc)
 }
 
 enum Planned {
@@ -253,18 +811,19 @@ enum Planned {
 
 fn plan_index(
     schema_map: &HashMap<String, Schema>,
+    statistics_map: &HashMap<String, TableStatistics>,
     indexes: &Indexes,
     selection: Expr,
 ) -> Result<Planned> {
     match selection {
-        Expr::Nested(expr) => plan_index(schema_map, indexes, *expr),
+        Expr::Nested(expr) => plan_index(schema_map, statistics_map, indexes, *expr),
         Expr::IsNull(expr) => Ok(search_is_null(indexes, true, expr)),
         Expr::IsNotNull(expr) => Ok(search_is_null(indexes, false, expr)),
-        Expr::Subquery(query) => plan_query(schema_map, *query)
+        Expr::Subquery(query) => plan_query(schema_map, statistics_map, *query)
             .map(Box::new)
             .map(Expr::Subquery)
             .map(Planned::Expr),
-        Expr::Exists { subquery, negated } => plan_query(schema_map, *subquery)
+        Expr::Exists { subquery, negated } => plan_query(schema_map, statistics_map, *subquery)
             .map(Box::new)
             .map(|subquery| Expr::Exists { subquery, negated })
             .map(Planned::Expr),
@@ -272,7 +831,7 @@ fn plan_index(
             expr,
             subquery,
             negated,
-        } => plan_query(schema_map, *subquery)
+        } => plan_query(schema_map, statistics_map, *subquery)
             .map(Box::new)
             .map(|subquery| Expr::InSubquery {
                 expr,
@@ -285,7 +844,7 @@ fn plan_index(
             op: BinaryOperator::And,
             right,
         } => {
-            let left = match plan_index(schema_map, indexes, *left)? {
+            let left = match plan_index(schema_map, statistics_map, indexes, *left)? {
                 Planned::Expr(selection) => selection,
                 Planned::IndexedExpr {
                     index_name,
@@ -311,7 +870,7 @@ fn plan_index(
                 }
             };
 
-            match plan_index(schema_map, indexes, *right)? {
+            match plan_index(schema_map, statistics_map, indexes, *right)? {
                 Planned::Expr(expr) => Ok(Planned::Expr(Expr::BinaryOp {
                     left: Box::new(left),
                     op: BinaryOperator::And,
@@ -437,6 +996,51 @@ fn search_index_op(
     }
 }
 
+/// The expression without its enclosing parentheses, as `CREATE INDEX` takes an expression
+/// other than a column name in them
+fn unnested(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::Nested(expr) => unnested(expr),
+        _ => expr,
+    }
+}
+
+/// Column compared by the comparison with a stateless value, with the operator and the value
+fn comparison(expr: &Expr) -> Option<(&Expr, IndexOperator, &Expr)> {
+    let Expr::BinaryOp { left, op, right } = unnested(expr) else {
+        return None;
+    };
+
+    let index_op = match op {
+        BinaryOperator::Eq => IndexOperator::Eq,
+        BinaryOperator::Gt => IndexOperator::Gt,
+        BinaryOperator::GtEq => IndexOperator::GtEq,
+        BinaryOperator::Lt => IndexOperator::Lt,
+        BinaryOperator::LtEq => IndexOperator::LtEq,
+        _ => return None,
+    };
+
+    if is_stateless(right) {
+        Some((left, index_op, right))
+    } else if is_stateless(left) {
+        Some((right, index_op.reverse(), left))
+    } else {
+        None
+    }
+}
+
+fn conjuncts(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::Nested(expr) => conjuncts(expr),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => [conjuncts(left), conjuncts(right)].concat(),
+        _ => vec![expr],
+    }
+}
+
 fn is_stateless(expr: &Expr) -> bool {
     match expr {
         Expr::Literal(AstLiteral::Null) => false,
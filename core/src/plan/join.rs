@@ -2,17 +2,29 @@ use {
     super::{context::Context, evaluable::check_expr as check_evaluable, planner::Planner},
     crate::{
         ast::{
-            BinaryOperator, Expr, Join, JoinConstraint, JoinExecutor, JoinOperator, Query, Select,
-            SetExpr, Statement, TableWithJoins,
+            BinaryOperator, DataType, Expr, IndexItem, Join, JoinConstraint, JoinExecutor,
+            JoinOperator, Query, Select, SelectItem, SetExpr, Statement, TableAlias, TableFactor,
+            TableWithJoins,
         },
-        data::Schema,
+        data::{Schema, SchemaIndex, SchemaIndexOrd, TableStatistics, get_alias},
     },
     std::{collections::HashMap, sync::Arc},
     utils::Vector,
 };
 
-pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> Statement {
-    let planner = JoinPlanner { schema_map };
+/// Number of row pairs below which joins run as nested loops, reading the rows of the joined
+/// table as they go, rather than building a hash table of them first.
+const HASH_JOIN_THRESHOLD: usize = 100;
+
+pub fn plan(
+    schema_map: &HashMap<String, Schema>,
+    statistics_map: &HashMap<String, TableStatistics>,
+    statement: Statement,
+) -> Statement {
+    let planner = JoinPlanner {
+        schema_map,
+        statistics_map,
+    };
 
     match statement {
         Statement::Query(query) => {
@@ -26,6 +38,7 @@ pub fn plan(schema_map: &HashMap<String, Schema>, statement: Statement) -> State
 
 struct JoinPlanner<'a> {
     schema_map: &'a HashMap<String, Schema>,
+    statistics_map: &'a HashMap<String, TableStatistics>,
 }
 
 impl<'a> Planner<'a> for JoinPlanner<'a> {
@@ -37,14 +50,7 @@ impl<'a> Planner<'a> for JoinPlanner<'a> {
             offset,
         } = query;
 
-        let body = match body {
-            SetExpr::Select(select) => {
-                let select = self.select(outer_context, *select);
-
-                SetExpr::Select(Box::new(select))
-            }
-            SetExpr::Values(_) => body,
-        };
+        let body = self.set_expr(outer_context, body);
 
         Query {
             body,
@@ -60,6 +66,28 @@ impl<'a> Planner<'a> for JoinPlanner<'a> {
 }
 
 impl<'a> JoinPlanner<'a> {
+    fn set_expr(&self, outer_context: Option<Arc<Context<'a>>>, set_expr: SetExpr) -> SetExpr {
+        match set_expr {
+            SetExpr::Select(select) => {
+                let select = self.select(outer_context, *select);
+
+                SetExpr::Select(Box::new(select))
+            }
+            SetExpr::Values(_) => set_expr,
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => SetExpr::SetOperation {
+                op,
+                all,
+                left: Box::new(self.set_expr(outer_context.as_ref().map(Arc::clone), *left)),
+                right: Box::new(self.set_expr(outer_context, *right)),
+            },
+        }
+    }
+
     fn select(&self, outer_context: Option<Arc<Context<'a>>>, select: Select) -> Select {
         let Select {
             distinct,
@@ -70,6 +98,14 @@ impl<'a> JoinPlanner<'a> {
             having,
         } = select;
 
+        let from = self.reorder(
+            outer_context.as_ref().map(Arc::clone),
+            &projection,
+            selection.as_ref(),
+            from,
+        );
+        let (from, selection) =
+            self.push_join_conditions(outer_context.as_ref().map(Arc::clone), from, selection);
         let (outer_context, from) = self.table_with_joins(outer_context, from);
         let selection = selection.map(|expr| self.subquery_expr(outer_context, expr));
 
@@ -83,6 +119,350 @@ impl<'a> JoinPlanner<'a> {
         }
     }
 
+    /// Joins the tables of a query of three or more tables joined by inner joins smallest first,
+    /// by the row counts `ANALYZE` collected, taking next the smallest table the conditions
+    /// of `ON` and `WHERE` link to the tables joined before it, so no cross product is taken
+    /// where a join condition could be. The conditions of `ON` move to the first join all the
+    /// tables they read are joined by. Queries of `*`, whose columns come in the order of the
+    /// tables, keep the order they are written in.
+    fn reorder(
+        &self,
+        outer_context: Option<Arc<Context<'a>>>,
+        projection: &[SelectItem],
+        selection: Option<&Expr>,
+        from: TableWithJoins,
+    ) -> TableWithJoins {
+        let TableWithJoins { relation, joins } = &from;
+        let reorderable = joins.len() > 1
+            && !projection.contains(&SelectItem::Wildcard)
+            && joins.iter().all(|join| {
+                matches!(
+                    join,
+                    Join {
+                        join_operator: JoinOperator::Inner(_),
+                        join_executor: JoinExecutor::NestedLoop,
+                        ..
+                    }
+                )
+            });
+        if !reorderable {
+            return from;
+        }
+
+        let relations = std::iter::once(relation)
+            .chain(joins.iter().map(|Join { relation, .. }| relation))
+            .collect::<Vec<_>>();
+        let row_counts = relations
+            .iter()
+            .map(|relation| self.row_count(relation))
+            .collect::<Option<Vec<_>>>();
+        let Some(row_counts) = row_counts else {
+            return from;
+        };
+
+        let links = joins
+            .iter()
+            .filter_map(|join| match &join.join_operator {
+                JoinOperator::Inner(JoinConstraint::On(expr)) => Some(expr),
+                _ => None,
+            })
+            .chain(selection)
+            .flat_map(conjuncts)
+            .filter(|expr| is_equi_join(expr))
+            .collect::<Vec<_>>();
+        let evaluable = |context: &Option<Arc<Context<'a>>>, expr: &Expr| {
+            let context = Context::concat(
+                context.as_ref().map(Arc::clone),
+                outer_context.as_ref().map(Arc::clone),
+            );
+
+            check_evaluable(context, expr)
+        };
+
+        let mut order: Vec<usize> = Vec::new();
+        let mut context = None;
+        while order.len() < relations.len() {
+            let remaining = (0..relations.len()).filter(|i| !order.contains(i));
+            let linked = remaining.clone().filter(|i| {
+                let next = self.update_context(context.as_ref().map(Arc::clone), relations[*i]);
+
+                links
+                    .iter()
+                    .any(|expr| evaluable(&next, expr) && !evaluable(&context, expr))
+            });
+            let next = match order.is_empty() {
+                true => None,
+                false => linked.min_by_key(|i| row_counts[*i]),
+            }
+            .or_else(|| remaining.min_by_key(|i| row_counts[*i]))
+            .unwrap_or_default();
+
+            context = self.update_context(context, relations[next]);
+            order.push(next);
+        }
+
+        if order.iter().copied().eq(0..relations.len()) {
+            return from;
+        }
+
+        let TableWithJoins { relation, joins } = from;
+        let mut conditions = Vec::new();
+        let mut relations = vec![Some(relation)];
+        for Join {
+            relation,
+            join_operator,
+            ..
+        } in joins
+        {
+            if let JoinOperator::Inner(JoinConstraint::On(expr)) = join_operator {
+                conditions.extend(into_conjuncts(expr));
+            }
+
+            relations.push(Some(relation));
+        }
+        let mut relations = order
+            .into_iter()
+            .filter_map(|i| relations[i].take())
+            .collect::<Vec<_>>();
+        let relation = relations.remove(0);
+        let last = relations.len() - 1;
+
+        let mut context = self.update_context(None, &relation);
+        let mut joins = Vec::new();
+        for (i, relation) in relations.into_iter().enumerate() {
+            context = self.update_context(context, &relation);
+
+            let (on, rest): (Vec<_>, Vec<_>) = conditions
+                .into_iter()
+                .partition(|expr| i == last || evaluable(&context, expr));
+            conditions = rest;
+
+            let join_operator = match on.into_iter().reduce(and) {
+                Some(expr) => JoinOperator::Inner(JoinConstraint::On(expr)),
+                None => JoinOperator::Inner(JoinConstraint::None),
+            };
+            joins.push(Join {
+                relation,
+                join_operator,
+                join_executor: JoinExecutor::NestedLoop,
+            });
+        }
+
+        TableWithJoins { relation, joins }
+    }
+
+    /// Moves the equi-join conditions of `WHERE` comparing the columns of two tables to the
+    /// `ON` of the first inner join after which both are joined, where they can run as hash
+    /// joins.
+    fn push_join_conditions(
+        &self,
+        outer_context: Option<Arc<Context<'a>>>,
+        from: TableWithJoins,
+        selection: Option<Expr>,
+    ) -> (TableWithJoins, Option<Expr>) {
+        let Some(selection) = selection else {
+            return (from, None);
+        };
+
+        let mut contexts = vec![self.update_context(None, &from.relation)];
+        for Join { relation, .. } in &from.joins {
+            let context = contexts
+                .last()
+                .and_then(|context| context.as_ref().map(Arc::clone));
+
+            contexts.push(self.update_context(context, relation));
+        }
+        let evaluable = |context: &Option<Arc<Context<'a>>>, expr: &Expr| {
+            let context = Context::concat(
+                context.as_ref().map(Arc::clone),
+                outer_context.as_ref().map(Arc::clone),
+            );
+
+            check_evaluable(context, expr)
+        };
+        let target = |expr: &Expr| {
+            if !is_equi_join(expr) || evaluable(&contexts[0], expr) {
+                return None;
+            }
+
+            let joined = (1..contexts.len()).find(|i| evaluable(&contexts[*i], expr))?;
+            let relation = self.update_context(None, &from.joins[joined - 1].relation);
+            if evaluable(&relation, expr) {
+                return None;
+            }
+
+            from.joins[joined - 1..]
+                .iter()
+                .position(|join| {
+                    matches!(
+                        join,
+                        Join {
+                            relation: TableFactor::Table { .. },
+                            join_operator: JoinOperator::Inner(_),
+                            join_executor: JoinExecutor::NestedLoop,
+                        }
+                    )
+                })
+                .map(|i| joined - 1 + i)
+        };
+        let targets = conjuncts(&selection)
+            .into_iter()
+            .map(target)
+            .collect::<Vec<_>>();
+        if targets.iter().all(Option::is_none) {
+            return (from, Some(selection));
+        }
+
+        let TableWithJoins { relation, joins } = from;
+        let mut pushed = vec![Vec::new(); joins.len()];
+        let mut remainder = Vec::new();
+        for (expr, target) in into_conjuncts(selection).into_iter().zip(targets) {
+            match target {
+                Some(i) => pushed[i].push(expr),
+                None => remainder.push(expr),
+            }
+        }
+
+        let joins = joins
+            .into_iter()
+            .zip(pushed)
+            .map(|(join, pushed)| {
+                let Join {
+                    relation,
+                    join_operator,
+                    join_executor,
+                } = join;
+                let join_operator = match join_operator {
+                    JoinOperator::Inner(constraint) if !pushed.is_empty() => {
+                        let on = match constraint {
+                            JoinConstraint::On(expr) => Some(expr),
+                            JoinConstraint::None => None,
+                        };
+                        let expr = on.into_iter().chain(pushed).reduce(and);
+
+                        JoinOperator::Inner(expr.map_or(JoinConstraint::None, JoinConstraint::On))
+                    }
+                    join_operator => join_operator,
+                };
+
+                Join {
+                    relation,
+                    join_operator,
+                    join_executor,
+                }
+            })
+            .collect();
+
+        (
+            TableWithJoins { relation, joins },
+            remainder.into_iter().reduce(and),
+        )
+    }
+
+    /// Rows `ANALYZE` counted in the table, `None` for other relations and tables not
+    /// analyzed.
+    fn row_count(&self, relation: &TableFactor) -> Option<usize> {
+        let TableFactor::Table { name, .. } = relation else {
+            return None;
+        };
+
+        let columns = self
+            .get_schema(name)
+            .is_some_and(|schema| schema.column_defs.is_some());
+
+        self.statistics_map
+            .get(name)
+            .filter(|_| columns)
+            .map(|statistics| statistics.row_count)
+    }
+
+    /// Column the scan of the relation gives the rows in the ascending order of, with its data
+    /// type, when the index planner took an index of the column for the scan. Joins keep the
+    /// order of the rows they are joined with, so the rows of every join of the query come in
+    /// the order of the column.
+    fn scan_order(&self, relation: &TableFactor) -> Option<(Expr, &'a DataType)> {
+        let TableFactor::Table {
+            name,
+            alias,
+            index:
+                Some(IndexItem::NonClustered {
+                    name: index_name,
+                    asc: None | Some(true),
+                    ..
+                }),
+        } = relation
+        else {
+            return None;
+        };
+
+        if matches!(alias, Some(TableAlias { columns, .. }) if !columns.is_empty()) {
+            return None;
+        }
+
+        let schema = self.get_schema(name)?;
+        let column = schema
+            .indexes
+            .iter()
+            .find(|SchemaIndex { name, .. }| name == index_name)
+            .and_then(index_column)?;
+        let data_type = column_type(schema, column)?;
+        let expr = Expr::CompoundIdentifier {
+            alias: get_alias(relation).to_owned(),
+            ident: column.to_owned(),
+        };
+
+        Some((expr, data_type))
+    }
+
+    /// Index of the joined table a full scan of which gives its rows in the ascending order of
+    /// `key_expr`, a column of the table of `data_type`.
+    fn merge_index(
+        &self,
+        relation: &TableFactor,
+        key_expr: &Expr,
+        data_type: &DataType,
+    ) -> Option<IndexItem> {
+        let (
+            TableFactor::Table {
+                name,
+                alias,
+                index: None,
+            },
+            Expr::CompoundIdentifier {
+                alias: target,
+                ident,
+            },
+        ) = (relation, key_expr)
+        else {
+            return None;
+        };
+
+        if target != get_alias(relation)
+            || matches!(alias, Some(TableAlias { columns, .. }) if !columns.is_empty())
+        {
+            return None;
+        }
+
+        let schema = self.get_schema(name)?;
+        if column_type(schema, ident) != Some(data_type) {
+            return None;
+        }
+
+        schema
+            .indexes
+            .iter()
+            .find(|index| {
+                index.predicate.is_none()
+                    && !matches!(index.order, SchemaIndexOrd::Desc)
+                    && index_column(index) == Some(ident.as_str())
+            })
+            .map(|SchemaIndex { name, .. }| IndexItem::NonClustered {
+                name: name.to_owned(),
+                asc: None,
+                cmp_expr: None,
+            })
+    }
+
     fn table_with_joins(
         &self,
         outer_context: Option<Arc<Context<'a>>>,
@@ -90,35 +470,55 @@ impl<'a> JoinPlanner<'a> {
     ) -> (Option<Arc<Context<'a>>>, TableWithJoins) {
         let TableWithJoins { relation, joins } = table_with_joins;
         let init_context = self.update_context(None, &relation);
-        let (context, joins) =
-            joins
-                .into_iter()
-                .fold((init_context, Vector::new()), |(context, joins), join| {
-                    let outer_context = outer_context.as_ref().map(Arc::clone);
-                    let (context, join) = self.join(outer_context, context, join);
-                    let joins = joins.push(join);
-
-                    (context, joins)
-                });
+        let init_row_count = self.row_count(&relation);
+        let order = self.scan_order(&relation);
+        let (context, _, joins) = joins.into_iter().fold(
+            (init_context, init_row_count, Vector::new()),
+            |(context, row_count, joins), join| {
+                let outer_context = outer_context.as_ref().map(Arc::clone);
+                let row_count = row_count
+                    .zip(self.row_count(&join.relation))
+                    .map(|(left, right)| left.saturating_mul(right));
+                let (context, join) =
+                    self.join(outer_context, context, row_count, order.as_ref(), join);
+                let joins = joins.push(join);
+
+                (context, row_count, joins)
+            },
+        );
         let joins = joins.into();
         let context = Context::concat(context, outer_context);
 
         (context, TableWithJoins { relation, joins })
     }
 
+    /// Plans the join as a hash join when an equality of its `ON` compares the joined
+    /// relation with the rows it is joined with. `pairs`, the number of row pairs the join
+    /// compares by the row counts of the tables, keeps joins below `HASH_JOIN_THRESHOLD` as
+    /// nested loops. A hash join becomes a merge join when the rows it is joined with come in
+    /// the `order` of its value, and an index of the joined table gives its rows in the order
+    /// of its key.
     fn join(
         &self,
         outer_context: Option<Arc<Context<'a>>>,
         inner_context: Option<Arc<Context<'a>>>,
+        pairs: Option<usize>,
+        order: Option<&(Expr, &DataType)>,
         join: Join,
     ) -> (Option<Arc<Context<'a>>>, Join) {
         let Join {
-            relation,
+            mut relation,
             join_operator,
             join_executor,
         } = join;
 
-        if matches!(join_executor, JoinExecutor::Hash { .. }) {
+        // Derived tables may read the columns of the rows they are joined with, as `LATERAL`
+        // subqueries do, so their rows cannot be hashed once for all of them.
+        if matches!(
+            join_executor,
+            JoinExecutor::Hash { .. } | JoinExecutor::Merge { .. }
+        ) || matches!(relation, TableFactor::Derived { .. })
+        {
             let context = self.update_context(inner_context, &relation);
             let join = Join {
                 relation,
@@ -157,6 +557,54 @@ impl<'a> JoinPlanner<'a> {
             current_context,
             expr,
         );
+        let (join_executor, expr) = match join_executor {
+            JoinExecutor::Hash {
+                key_expr,
+                value_expr,
+                where_clause,
+            } if pairs.is_some_and(|pairs| pairs < HASH_JOIN_THRESHOLD) => {
+                let equality = Expr::BinaryOp {
+                    left: Box::new(value_expr),
+                    op: BinaryOperator::Eq,
+                    right: Box::new(key_expr),
+                };
+                let expr = std::iter::once(equality)
+                    .chain(where_clause)
+                    .chain(expr)
+                    .reduce(and);
+
+                (JoinExecutor::NestedLoop, expr)
+            }
+            _ => (join_executor, expr),
+        };
+        let join_executor = match (join_executor, order) {
+            (
+                JoinExecutor::Hash {
+                    key_expr,
+                    value_expr,
+                    where_clause,
+                },
+                Some((order, data_type)),
+            ) if &value_expr == order => match self.merge_index(&relation, &key_expr, data_type) {
+                Some(index) => {
+                    if let TableFactor::Table { index: scan, .. } = &mut relation {
+                        *scan = Some(index);
+                    }
+
+                    JoinExecutor::Merge {
+                        key_expr,
+                        value_expr,
+                        where_clause,
+                    }
+                }
+                None => JoinExecutor::Hash {
+                    key_expr,
+                    value_expr,
+                    where_clause,
+                },
+            },
+            (join_executor, _) => join_executor,
+        };
 
         let join_operator = match (join_op, expr) {
             (JoinOp::Inner, Some(expr)) => JoinOperator::Inner(JoinConstraint::On(expr)),
@@ -290,7 +738,7 @@ impl<'a> JoinPlanner<'a> {
                     return (join_executor, expr);
                 }
 
-                let (join_executor, right) = self.join_expr(
+                let (mut join_executor, right) = self.join_expr(
                     outer_context.as_ref().map(Arc::clone),
                     inner_context,
                     current_context.as_ref().map(Arc::clone),
@@ -314,16 +762,19 @@ impl<'a> JoinPlanner<'a> {
                 match join_executor {
                     JoinExecutor::NestedLoop => (join_executor, expr),
                     JoinExecutor::Hash {
-                        key_expr,
-                        value_expr,
-                        where_clause,
+                        ref mut where_clause,
+                        ..
+                    }
+                    | JoinExecutor::Merge {
+                        ref mut where_clause,
+                        ..
                     } => {
                         let context = Context::concat(current_context, outer_context);
                         let (evaluable_expr, expr) = expr
                             .map(|expr| find_evaluable(context, expr))
                             .unwrap_or((None, None));
 
-                        let where_clause = match (where_clause, evaluable_expr) {
+                        *where_clause = match (where_clause.take(), evaluable_expr) {
                             (Some(expr), Some(expr2)) => Some(Expr::BinaryOp {
                                 left: Box::new(expr),
                                 op: BinaryOperator::And,
@@ -333,12 +784,6 @@ impl<'a> JoinPlanner<'a> {
                             (None, None) => None,
                         };
 
-                        let join_executor = JoinExecutor::Hash {
-                            key_expr,
-                            value_expr,
-                            where_clause,
-                        };
-
                         (join_executor, expr)
                     }
                 }
@@ -387,6 +832,105 @@ impl<'a> JoinPlanner<'a> {
     }
 }
 
+/// Column of a single-column index, `None` for composite indexes, indexes of expressions and
+/// full-text indexes, which do not order the rows by the column.
+fn index_column(index: &SchemaIndex) -> Option<&str> {
+    match index.exprs.as_slice() {
+        [Expr::Identifier(column)] if !index.fulltext => Some(column),
+        _ => None,
+    }
+}
+
+/// Type of the column, limited to the integer and text types, whose values an index orders the
+/// way merge joins and ordered aggregation compare their keys.
+pub(crate) fn column_type<'a>(schema: &'a Schema, column: &str) -> Option<&'a DataType> {
+    schema
+        .column_defs
+        .as_ref()?
+        .iter()
+        .find(|column_def| column_def.name == column)
+        .map(|column_def| &column_def.data_type)
+        .filter(|data_type| {
+            matches!(
+                data_type,
+                DataType::Int8
+                    | DataType::Int16
+                    | DataType::Int32
+                    | DataType::Int
+                    | DataType::Int128
+                    | DataType::Uint8
+                    | DataType::Uint16
+                    | DataType::Uint32
+                    | DataType::Uint64
+                    | DataType::Uint128
+                    | DataType::Text
+            )
+        })
+}
+
+fn and(left: Expr, right: Expr) -> Expr {
+    Expr::BinaryOp {
+        left: Box::new(left),
+        op: BinaryOperator::And,
+        right: Box::new(right),
+    }
+}
+
+fn conjuncts(expr: &Expr) -> Vec<&Expr> {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => [conjuncts(left), conjuncts(right)].concat(),
+        Expr::Nested(nested)
+            if matches!(
+                nested.as_ref(),
+                Expr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                }
+            ) =>
+        {
+            conjuncts(nested)
+        }
+        _ => vec![expr],
+    }
+}
+
+fn into_conjuncts(expr: Expr) -> Vec<Expr> {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => [into_conjuncts(*left), into_conjuncts(*right)].concat(),
+        Expr::Nested(nested)
+            if matches!(
+                nested.as_ref(),
+                Expr::BinaryOp {
+                    op: BinaryOperator::And,
+                    ..
+                }
+            ) =>
+        {
+            into_conjuncts(*nested)
+        }
+        _ => vec![expr],
+    }
+}
+
+/// Condition comparing two columns with `=`, as joins are written in `WHERE`.
+fn is_equi_join(expr: &Expr) -> bool {
+    let column =
+        |expr: &Expr| matches!(expr, Expr::Identifier(_) | Expr::CompoundIdentifier { .. });
+
+    matches!(
+        expr,
+        Expr::BinaryOp { left, op: BinaryOperator::Eq, right } if column(left) && column(right)
+    )
+}
+
 type EvaluableExpr = Option<Expr>;
 type RemainderExpr = Option<Expr>;
 
@@ -425,14 +969,19 @@ mod tests {
     use {
         super::plan,
         crate::{
-            ast::{DateTimeField, Statement},
+            ast::{
+                DateTimeField, Expr, IndexItem, Join, JoinExecutor, SetExpr, Statement, TableFactor,
+            },
             ast_builder::{Build, QueryNode, col, exists, num, subquery, table},
+            chrono::NaiveDateTime,
+            data::{SchemaIndex, SchemaIndexOrd, TableStatistics},
             mock::{MockStorage, run},
-            parse_sql::parse,
-            plan::fetch_schema_map,
-            translate::translate,
+            parse_sql::{parse, parse_expr},
+            plan::{fetch_schema_map, plan_index},
+            translate::{translate, translate_expr},
         },
         futures::executor::block_on,
+        std::collections::HashMap,
     };
 
     fn plan_join(storage: &MockStorage, sql: &str) -> Statement {
@@ -440,7 +989,32 @@ mod tests {
         let statement = translate(&parsed).unwrap();
         let schema_map = block_on(fetch_schema_map(storage, &statement)).unwrap();
 
-        plan(&schema_map, statement)
+        plan(&schema_map, &HashMap::new(), statement)
+    }
+
+    fn plan_join_analyzed(
+        storage: &MockStorage,
+        sql: &str,
+        row_counts: &[(&str, usize)],
+    ) -> Statement {
+        let parsed = parse(sql).expect(sql).into_iter().next().unwrap();
+        let statement = translate(&parsed).unwrap();
+        let schema_map = block_on(fetch_schema_map(storage, &statement)).unwrap();
+        let statistics_map = row_counts
+            .iter()
+            .map(|(table_name, row_count)| {
+                let statistics = TableStatistics {
+                    table_name: table_name.to_string(),
+                    row_count: *row_count,
+                    columns: Vec::new(),
+                    analyzed: NaiveDateTime::default(),
+                };
+
+                (table_name.to_string(), statistics)
+            })
+            .collect();
+
+        plan(&schema_map, &statistics_map, statement)
     }
 
     macro_rules! test {
@@ -533,7 +1107,7 @@ mod tests {
         let actual = {
             let schema_map = block_on(fetch_schema_map(&storage, &actual)).unwrap();
 
-            plan(&schema_map, actual)
+            plan(&schema_map, &HashMap::new(), actual)
         };
         let expected = table("Player")
             .select()
@@ -949,4 +1523,326 @@ mod tests {
         );
         test!(actual, expected, "case expr:\n{sql}");
     }
+
+    #[test]
+    fn push_join_conditions() {
+        let storage = run("
+            CREATE TABLE Player (
+                id INTEGER,
+                name TEXT
+            );
+            CREATE TABLE PlayerItem (
+                user_id INTEGER,
+                item_id INTEGER,
+                amount INTEGER
+            );
+        ");
+
+        let sql = "
+            SELECT *
+            FROM Player
+            CROSS JOIN PlayerItem
+            WHERE PlayerItem.user_id = Player.id AND PlayerItem.amount > 10;
+        ";
+        let actual = plan_join(&storage, sql);
+        let expected = table("Player")
+            .select()
+            .join("PlayerItem")
+            .hash_executor("PlayerItem.user_id", "Player.id")
+            .filter("PlayerItem.amount > 10");
+        test!(actual, expected, "equi-join condition of where:\n{sql}");
+
+        let sql = "
+            SELECT *
+            FROM Player
+            JOIN PlayerItem ON PlayerItem.amount > 10
+            WHERE Player.id = PlayerItem.user_id;
+        ";
+        let actual = plan_join(&storage, sql);
+        let expected = table("Player")
+            .select()
+            .join("PlayerItem")
+            .hash_executor("PlayerItem.user_id", "Player.id")
+            .hash_filter("PlayerItem.amount > 10");
+        test!(actual, expected, "equi-join condition added to on:\n{sql}");
+
+        let sql = "
+            SELECT *
+            FROM Player
+            LEFT JOIN PlayerItem ON PlayerItem.amount > 10
+            WHERE Player.id = PlayerItem.user_id;
+        ";
+        let actual = plan_join(&storage, sql);
+        let expected = table("Player")
+            .select()
+            .left_join("PlayerItem")
+            .on("PlayerItem.amount > 10")
+            .filter("Player.id = PlayerItem.user_id");
+        test!(actual, expected, "left outer join keeps where:\n{sql}");
+
+        let sql = "
+            SELECT *
+            FROM Player
+            CROSS JOIN PlayerItem
+            WHERE Player.id = Player.name OR PlayerItem.user_id = Player.id;
+        ";
+        let actual = plan_join(&storage, sql);
+        let expected = table("Player")
+            .select()
+            .join("PlayerItem")
+            .filter("Player.id = Player.name OR PlayerItem.user_id = Player.id");
+        test!(actual, expected, "disjunction stays in where:\n{sql}");
+    }
+
+    #[test]
+    fn reorder() {
+        let storage = run("
+            CREATE TABLE Player (
+                id INTEGER,
+                name TEXT
+            );
+            CREATE TABLE Item (
+                id INTEGER,
+                name TEXT
+            );
+            CREATE TABLE PlayerItem (
+                user_id INTEGER,
+                item_id INTEGER,
+                amount INTEGER
+            );
+        ");
+        let row_counts = [("Player", 1000), ("Item", 10), ("PlayerItem", 10000)];
+
+        let sql = "
+            SELECT Player.name, Item.name
+            FROM PlayerItem
+            JOIN Player ON Player.id = PlayerItem.user_id
+            JOIN Item ON Item.id = PlayerItem.item_id
+            WHERE PlayerItem.amount > 10;
+        ";
+        let actual = plan_join_analyzed(&storage, sql, &row_counts);
+        let expected = table("Item")
+            .select()
+            .join("PlayerItem")
+            .hash_executor("PlayerItem.item_id", "Item.id")
+            .join("Player")
+            .hash_executor("Player.id", "PlayerItem.user_id")
+            .filter("PlayerItem.amount > 10")
+            .project("Player.name, Item.name");
+        test!(actual, expected, "smallest linked table first:\n{sql}");
+
+        let sql = "
+            SELECT Player.name, Item.name
+            FROM Player
+            CROSS JOIN Item
+            CROSS JOIN PlayerItem
+            WHERE Player.id = PlayerItem.user_id AND Item.id = PlayerItem.item_id;
+        ";
+        let actual = plan_join_analyzed(&storage, sql, &row_counts);
+        let expected = table("Item")
+            .select()
+            .join("PlayerItem")
+            .hash_executor("PlayerItem.item_id", "Item.id")
+            .join("Player")
+            .hash_executor("Player.id", "PlayerItem.user_id")
+            .project("Player.name, Item.name");
+        test!(actual, expected, "cross joins linked by where:\n{sql}");
+
+        let sql = "
+            SELECT *
+            FROM PlayerItem
+            JOIN Player ON Player.id = PlayerItem.user_id
+            JOIN Item ON Item.id = PlayerItem.item_id;
+        ";
+        let actual = plan_join_analyzed(&storage, sql, &row_counts);
+        let expected = table("PlayerItem")
+            .select()
+            .join("Player")
+            .hash_executor("Player.id", "PlayerItem.user_id")
+            .join("Item")
+            .hash_executor("Item.id", "PlayerItem.item_id");
+        test!(actual, expected, "wildcard keeps the order:\n{sql}");
+
+        let sql = "
+            SELECT Player.name, Item.name
+            FROM PlayerItem
+            JOIN Player ON Player.id = PlayerItem.user_id
+            JOIN Item ON Item.id = PlayerItem.item_id;
+        ";
+        let actual = plan_join(&storage, sql);
+        let expected = table("PlayerItem")
+            .select()
+            .join("Player")
+            .hash_executor("Player.id", "PlayerItem.user_id")
+            .join("Item")
+            .hash_executor("Item.id", "PlayerItem.item_id")
+            .project("Player.name, Item.name");
+        test!(
+            actual,
+            expected,
+            "tables not analyzed keep the order:\n{sql}"
+        );
+    }
+
+    #[test]
+    fn hash_join_threshold() {
+        let storage = run("
+            CREATE TABLE Player (
+                id INTEGER,
+                name TEXT
+            );
+            CREATE TABLE PlayerItem (
+                user_id INTEGER,
+                item_id INTEGER,
+                amount INTEGER
+            );
+        ");
+
+        let sql = "
+            SELECT Player.name
+            FROM Player
+            JOIN PlayerItem ON Player.id = PlayerItem.user_id AND PlayerItem.amount > 10
+        ";
+        let actual = plan_join_analyzed(&storage, sql, &[("Player", 5), ("PlayerItem", 10)]);
+        let expected = table("Player")
+            .select()
+            .join("PlayerItem")
+            .on("Player.id = PlayerItem.user_id AND PlayerItem.amount > 10")
+            .project("Player.name");
+        test!(actual, expected, "small join runs as nested loop:\n{sql}");
+
+        let actual = plan_join_analyzed(&storage, sql, &[("Player", 10), ("PlayerItem", 10)]);
+        let expected = table("Player")
+            .select()
+            .join("PlayerItem")
+            .hash_executor("PlayerItem.user_id", "Player.id")
+            .hash_filter("PlayerItem.amount > 10")
+            .project("Player.name");
+        test!(
+            actual,
+            expected,
+            "join over the threshold runs as hash join:\n{sql}"
+        );
+
+        let actual = plan_join_analyzed(&storage, sql, &[("Player", 5)]);
+        let expected = table("Player")
+            .select()
+            .join("PlayerItem")
+            .hash_executor("PlayerItem.user_id", "Player.id")
+            .hash_filter("PlayerItem.amount > 10")
+            .project("Player.name");
+        test!(
+            actual,
+            expected,
+            "join of a table not analyzed runs as hash join:\n{sql}"
+        );
+    }
+
+    #[test]
+    fn merge_join() {
+        let storage = run("
+            CREATE TABLE Player (
+                id INTEGER,
+                name TEXT
+            );
+            CREATE TABLE PlayerItem (
+                user_id INTEGER,
+                amount INTEGER
+            );
+        ");
+        let expr = |sql: &str| translate_expr(&parse_expr(sql).unwrap()).unwrap();
+        let plan_merge_join = |sql: &str, indexes: &[(&str, &str, &str)]| {
+            let parsed = parse(sql).expect(sql).into_iter().next().unwrap();
+            let statement = translate(&parsed).unwrap();
+            let mut schema_map = block_on(fetch_schema_map(&storage, &statement)).unwrap();
+            for (table_name, name, column) in indexes {
+                let index = SchemaIndex {
+                    name: name.to_string(),
+                    exprs: vec![Expr::Identifier(column.to_string())],
+                    order: SchemaIndexOrd::Both,
+                    predicate: None,
+                    fulltext: false,
+                    created: NaiveDateTime::default(),
+                };
+
+                schema_map.get_mut(*table_name).unwrap().indexes.push(index);
+            }
+
+            let statistics_map = HashMap::new();
+            let statement = plan_index(&schema_map, &statistics_map, statement).unwrap();
+            let Statement::Query(query) = plan(&schema_map, &statistics_map, statement) else {
+                panic!("not a query: {sql}");
+            };
+            let SetExpr::Select(select) = query.body else {
+                panic!("not a select: {sql}");
+            };
+            let Join {
+                relation,
+                join_executor,
+                ..
+            } = select.from.joins.into_iter().next().unwrap();
+
+            (relation, join_executor)
+        };
+        let indexes = [
+            ("Player", "idx_id", "id"),
+            ("PlayerItem", "idx_user_id", "user_id"),
+        ];
+        let merge_index = Some(IndexItem::NonClustered {
+            name: "idx_user_id".to_owned(),
+            asc: None,
+            cmp_expr: None,
+        });
+        let executor = |merge| {
+            let key_expr = expr("PlayerItem.user_id");
+            let value_expr = expr("Player.id");
+            let where_clause = Some(expr("PlayerItem.amount > 10"));
+
+            match merge {
+                true => JoinExecutor::Merge {
+                    key_expr,
+                    value_expr,
+                    where_clause,
+                },
+                false => JoinExecutor::Hash {
+                    key_expr,
+                    value_expr,
+                    where_clause,
+                },
+            }
+        };
+        let join = "
+            SELECT Player.name
+            FROM Player
+            JOIN PlayerItem ON Player.id = PlayerItem.user_id AND PlayerItem.amount > 10
+        ";
+
+        let sql = format!("{join} ORDER BY id");
+        let (relation, join_executor) = plan_merge_join(&sql, &indexes);
+        assert_eq!(join_executor, executor(true), "ordered by index:\n{sql}");
+        assert!(
+            matches!(relation, TableFactor::Table { index, .. } if index == merge_index),
+            "joined table read by index:\n{sql}"
+        );
+
+        let sql = format!("{join} WHERE id > 1");
+        let (_, join_executor) = plan_merge_join(&sql, &indexes);
+        assert_eq!(join_executor, executor(true), "filtered by index:\n{sql}");
+
+        let (relation, join_executor) = plan_merge_join(join, &indexes);
+        assert_eq!(join_executor, executor(false), "full scan:\n{join}");
+        assert!(matches!(relation, TableFactor::Table { index: None, .. }));
+
+        let sql = format!("{join} ORDER BY id DESC");
+        let (_, join_executor) = plan_merge_join(&sql, &indexes);
+        assert_eq!(join_executor, executor(false), "descending order:\n{sql}");
+
+        let sql = format!("{join} ORDER BY id");
+        let (_, join_executor) = plan_merge_join(&sql, &indexes[..1]);
+        assert_eq!(
+            join_executor,
+            executor(false),
+            "joined table not indexed:\n{sql}"
+        );
+    }
 }
@@ -197,7 +197,9 @@ pub trait Planner<'a> {
                 (name, alias)
             }
             TableFactor::Derived { .. }
+            | TableFactor::Recursive { .. }
             | TableFactor::Series { .. }
+            | TableFactor::Fake { .. }
             | TableFactor::Dictionary { .. } => return next,
         };
 
@@ -218,9 +220,15 @@ pub trait Planner<'a> {
 
         let primary_key = column_defs
             .iter()
-            .find_map(|ColumnDef { name, unique, .. }| {
+            .filter_map(|ColumnDef { name, unique, .. }| {
                 (unique == &Some(ColumnUniqueOption { is_primary: true })).then_some(name.as_str())
-            });
+            })
+            .collect::<Vec<_>>();
+        // rows of a composite primary key cannot be fetched by a single column
+        let primary_key = match primary_key.as_slice() {
+            [primary_key] => Some(*primary_key),
+            _ => None,
+        };
 
         let context = Context::new(
             alias.unwrap_or_else(|| name.to_owned()),
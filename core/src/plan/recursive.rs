@@ -0,0 +1,70 @@
+use crate::{
+    ast::{Statement, TableFactor},
+    translate::visit_query,
+};
+
+/// Limits the recursions of every `WITH RECURSIVE` table of the statement to `max_depth`.
+pub fn plan(mut statement: Statement, max_depth: usize) -> Statement {
+    let query = match &mut statement {
+        Statement::Query(query)
+        | Statement::Explain { query, .. }
+        | Statement::Insert { source: query, .. } => query,
+        Statement::CreateTable {
+            source: Some(query),
+            ..
+        } => query.as_mut(),
+        _ => return statement,
+    };
+
+    visit_query(query, &mut |table_factor| {
+        if let TableFactor::Recursive {
+            max_depth: depth, ..
+        } = table_factor
+        {
+            *depth = Some(max_depth);
+        }
+    });
+
+    statement
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::plan,
+        crate::{
+            ast::{Statement, TableFactor},
+            parse_sql::parse,
+            translate::{translate, visit_query},
+        },
+    };
+
+    fn max_depths(sql: &str, max_depth: usize) -> Vec<Option<usize>> {
+        let parsed = parse(sql).expect(sql).into_iter().next().unwrap();
+        let statement = plan(translate(&parsed).expect(sql), max_depth);
+        let Statement::Query(mut query) = statement else {
+            panic!("not a query: {sql}");
+        };
+
+        let mut max_depths = Vec::new();
+        visit_query(&mut query, &mut |table_factor| {
+            if let TableFactor::Recursive { max_depth, .. } = table_factor {
+                max_depths.push(*max_depth);
+            }
+        });
+
+        max_depths
+    }
+
+    #[test]
+    fn recursive_tables() {
+        let sql = "
+            WITH RECURSIVE t(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM t WHERE n < 3)
+            SELECT * FROM t WHERE n IN (SELECT * FROM t)
+        ";
+        assert_eq!(max_depths(sql, 10), vec![Some(10), Some(10)]);
+
+        let sql = "WITH t(n) AS (SELECT 1) SELECT * FROM t";
+        assert_eq!(max_depths(sql, 10), Vec::new());
+    }
+}
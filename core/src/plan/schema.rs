@@ -5,7 +5,7 @@ use {
             Expr, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem, SetExpr,
             Statement, TableFactor, TableWithJoins,
         },
-        data::Schema,
+        data::{Schema, TableStatistics},
         result::Result,
         store::Store,
     },
@@ -63,6 +63,22 @@ pub async fn fetch_schema_map<T: Store>(
     }
 }
 
+/// Fetches the statistics `ANALYZE` collected of the tables of the schema map, the planner
+/// choosing among the index scans and ordering the joins with them.
+pub async fn fetch_statistics_map<T: Store>(
+    storage: &T,
+    schema_map: &HashMap<String, Schema>,
+) -> Result<HashMap<String, TableStatistics>> {
+    let mut statistics_map = HashMap::new();
+    for table_name in schema_map.keys() {
+        if let Some(statistics) = storage.fetch_statistics(table_name).await? {
+            statistics_map.insert(table_name.to_owned(), statistics);
+        }
+    }
+
+    Ok(statistics_map)
+}
+
 async fn scan_query<T: Store>(storage: &T, query: &Query) -> Result<HashMap<String, Schema>> {
     let Query {
         body,
@@ -71,10 +87,7 @@ async fn scan_query<T: Store>(storage: &T, query: &Query) -> Result<HashMap<Stri
         ..
     } = query;
 
-    let schema_list = match body {
-        SetExpr::Select(select) => scan_select(storage, select).await?,
-        SetExpr::Values(_) => HashMap::new(),
-    };
+    let schema_list = scan_set_expr(storage, body).await?;
 
     let schema_list = match (limit, offset) {
         (Some(limit), Some(offset)) => schema_list
@@ -92,6 +105,25 @@ async fn scan_query<T: Store>(storage: &T, query: &Query) -> Result<HashMap<Stri
     Ok(schema_list)
 }
 
+#[async_recursion]
+async fn scan_set_expr<T>(storage: &T, set_expr: &SetExpr) -> Result<HashMap<String, Schema>>
+where
+    T: Store,
+{
+    match set_expr {
+        SetExpr::Select(select) => scan_select(storage, select).await,
+        SetExpr::Values(_) => Ok(HashMap::new()),
+        SetExpr::SetOperation { left, right, .. } => {
+            let schema_list = scan_set_expr(storage, left).await?;
+
+            Ok(schema_list
+                .into_iter()
+                .chain(scan_set_expr(storage, right).await?)
+                .collect())
+        }
+    }
+}
+
 async fn scan_select<T: Store>(storage: &T, select: &Select) -> Result<HashMap<String, Schema>> {
     let Select {
         distinct: _,
@@ -186,7 +218,19 @@ where
             Ok(schema_list)
         }
         TableFactor::Derived { subquery, .. } => scan_query(storage, subquery).await,
-        TableFactor::Series { .. } | TableFactor::Dictionary { .. } => Ok(HashMap::new()),
+        TableFactor::Recursive {
+            anchor, recursive, ..
+        } => {
+            let schema_list = scan_query(storage, anchor).await?;
+
+            Ok(schema_list
+                .into_iter()
+                .chain(scan_query(storage, recursive).await?)
+                .collect())
+        }
+        TableFactor::Series { .. } | TableFactor::Fake { .. } | TableFactor::Dictionary { .. } => {
+            Ok(HashMap::new())
+        }
     }
 }
 
@@ -128,7 +128,7 @@ fn contextualize_query<'a>(
 
             Context::concat(by_table, by_joins)
         }
-        SetExpr::Values(_) => None,
+        SetExpr::Values(_) | SetExpr::SetOperation { .. } => None,
     }
 }
 
@@ -142,7 +142,10 @@ fn contextualize_table_factor<'a>(
             schema.map(|schema| Arc::from(Context::new(get_labels(schema), None)))
         }
         TableFactor::Derived { subquery, .. } => contextualize_query(schema_map, subquery),
-        TableFactor::Series { .. } | TableFactor::Dictionary { .. } => None,
+        TableFactor::Recursive { anchor, .. } => contextualize_query(schema_map, anchor),
+        TableFactor::Series { .. } | TableFactor::Fake { .. } | TableFactor::Dictionary { .. } => {
+            None
+        }
     }
 }
 
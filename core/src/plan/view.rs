@@ -0,0 +1,193 @@
+use {
+    super::PlanError,
+    crate::{
+        ast::{Query, Statement, TableAlias, TableFactor},
+        data::{Schema, SchemaView},
+        result::Result,
+        store::Store,
+        translate::visit_query,
+    },
+    std::collections::{BTreeSet, HashMap},
+};
+
+/// Replaces the views read by the statement with the queries they name.
+pub async fn plan<T: Store>(storage: &T, mut statement: Statement) -> Result<Statement> {
+    match &mut statement {
+        Statement::Query(query) => expand_views(storage, query).await?,
+        Statement::Insert {
+            table_name, source, ..
+        } => {
+            check_writable(storage, table_name).await?;
+            expand_views(storage, source).await?;
+        }
+        Statement::CreateTable {
+            source: Some(query),
+            ..
+        } => expand_views(storage, query).await?,
+        Statement::Update { table_name, .. } | Statement::Delete { table_name, .. } => {
+            check_writable(storage, table_name).await?;
+        }
+        _ => {}
+    }
+
+    Ok(statement)
+}
+
+async fn check_writable<T: Store>(storage: &T, table_name: &str) -> Result<()> {
+    match storage.fetch_schema(table_name).await? {
+        Some(Schema { view: Some(_), .. }) => {
+            Err(PlanError::ViewNotWritable(table_name.to_owned()).into())
+        }
+        Some(Schema {
+            sequence: Some(_), ..
+        }) => Err(PlanError::SequenceNotWritable(table_name.to_owned()).into()),
+        _ => Ok(()),
+    }
+}
+
+/// Expands the views of the query a level at a time, until it reads tables only.
+/// Materialized views are read like tables, from the rows they keep.
+///
+/// Views never read themselves, which `CREATE VIEW` checks, so the expansion ends.
+pub(crate) async fn expand_views<T: Store>(storage: &T, query: &mut Query) -> Result<()> {
+    loop {
+        let mut table_names = BTreeSet::new();
+        visit_query(query, &mut |table_factor| {
+            if let TableFactor::Table { name, .. } = table_factor {
+                table_names.insert(name.clone());
+            }
+        });
+
+        let mut views = HashMap::new();
+        for table_name in table_names {
+            if let Some(Schema {
+                view:
+                    Some(SchemaView {
+                        query,
+                        materialized: false,
+                    }),
+                ..
+            }) = storage.fetch_schema(&table_name).await?
+            {
+                views.insert(table_name, query);
+            }
+        }
+
+        if views.is_empty() {
+            return Ok(());
+        }
+
+        visit_query(query, &mut |table_factor| {
+            let TableFactor::Table { name, alias, .. } = table_factor else {
+                return;
+            };
+            let Some(view) = views.get(name.as_str()) else {
+                return;
+            };
+
+            let alias = alias.take().unwrap_or_else(|| TableAlias {
+                name: name.clone(),
+                columns: Vec::new(),
+            });
+
+            *table_factor = TableFactor::Derived {
+                subquery: view.clone(),
+                alias,
+            };
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::plan,
+        crate::{
+            ast::{Statement, TableFactor},
+            data::Schema,
+            mock::{MockStorage, run},
+            parse_sql::parse,
+            plan::PlanError,
+            result::Result,
+            store::StoreMut,
+            translate::{translate, visit_query},
+        },
+        futures::executor::block_on,
+    };
+
+    fn plan_sql(storage: &MockStorage, sql: &str) -> Result<Statement> {
+        let parsed = parse(sql).expect(sql).into_iter().next().unwrap();
+        let statement = translate(&parsed).expect(sql);
+
+        block_on(plan(storage, statement))
+    }
+
+    fn table_names(storage: &MockStorage, sql: &str) -> Vec<String> {
+        let Statement::Query(mut query) = plan_sql(storage, sql).expect(sql) else {
+            panic!("not a query: {sql}");
+        };
+
+        let mut table_names = Vec::new();
+        visit_query(&mut query, &mut |table_factor| {
+            if let TableFactor::Table { name, .. } = table_factor {
+                table_names.push(name.clone());
+            }
+        });
+
+        table_names
+    }
+
+    #[test]
+    fn expand() {
+        let mut storage = run("
+            CREATE TABLE Foo (id INTEGER);
+            CREATE TABLE Bar (id INTEGER);
+            CREATE VIEW FooView AS SELECT * FROM Foo;
+            CREATE VIEW BothView AS SELECT * FROM FooView JOIN Bar ON FooView.id = Bar.id;
+        ");
+
+        assert_eq!(table_names(&storage, "SELECT * FROM Foo"), vec!["Foo"]);
+        assert_eq!(table_names(&storage, "SELECT * FROM FooView"), vec!["Foo"]);
+        assert_eq!(
+            table_names(&storage, "SELECT * FROM BothView"),
+            vec!["Foo", "Bar"]
+        );
+        assert_eq!(
+            table_names(
+                &storage,
+                "SELECT * FROM Bar WHERE id IN (SELECT id FROM FooView)"
+            ),
+            vec!["Bar", "Foo"]
+        );
+
+        let ddl = r#"CREATE TABLE "FooCopy" ("id" INT NULL);
+CREATE MATERIALIZED VIEW "FooCopy" AS SELECT * FROM "FooView";"#;
+        block_on(storage.insert_schema(&Schema::from_ddl(ddl).unwrap())).unwrap();
+        assert_eq!(
+            table_names(&storage, "SELECT * FROM FooCopy"),
+            vec!["FooCopy"]
+        );
+    }
+
+    #[test]
+    fn not_writable() {
+        let storage = run("
+            CREATE TABLE Foo (id INTEGER);
+            CREATE VIEW FooView AS SELECT * FROM Foo;
+        ");
+
+        let error =
+            |table_name: &str| Err(PlanError::ViewNotWritable(table_name.to_owned()).into());
+
+        assert_eq!(
+            plan_sql(&storage, "INSERT INTO FooView VALUES (1)"),
+            error("FooView")
+        );
+        assert_eq!(
+            plan_sql(&storage, "UPDATE FooView SET id = 2"),
+            error("FooView")
+        );
+        assert_eq!(plan_sql(&storage, "DELETE FROM FooView"), error("FooView"));
+        assert!(plan_sql(&storage, "INSERT INTO Foo SELECT * FROM FooView").is_ok());
+    }
+}
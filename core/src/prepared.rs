@@ -0,0 +1,155 @@
+//! Prepared statements of [`crate::prelude::Glue`].
+//!
+//! [`crate::prelude::Glue::prepare`] parses SQL with placeholders once, `$1`, `$2`, ... for the
+//! parameters by their number and `?` for the parameter after the one the `?` before it read.
+//! [`crate::prelude::Glue::execute_with_params`] then runs it with the values of the
+//! parameters, which are read as values wherever they are placed, never as SQL, so they need
+//! no quoting or escaping.
+
+use {
+    crate::{
+        ast::{Expr, ToSql},
+        data::Value,
+        parse_sql::{parse_prepared, tokenize_prepared},
+        result::Result,
+    },
+    serde::Serialize,
+    sqlparser::{ast::Statement as SqlStatement, tokenizer::TokenWithLocation},
+    std::fmt::Debug,
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum PreparedError {
+    #[error("invalid placeholder, expected $1, $2, ... or ?: {0}")]
+    InvalidPlaceholder(String),
+
+    #[error("the statement reads {expected} parameters but {found} were given")]
+    ParamCountMismatch { expected: usize, found: usize },
+}
+
+#[derive(Clone, Debug)]
+pub struct PreparedStatement {
+    tokens: Vec<TokenWithLocation>,
+    params: usize,
+}
+
+impl PreparedStatement {
+    pub fn new<Sql: AsRef<str>>(sql: Sql) -> Result<Self> {
+        let (tokens, params) = tokenize_prepared(sql.as_ref())?;
+
+        Ok(Self { tokens, params })
+    }
+
+    /// Number of parameters the statement reads.
+    pub fn params(&self) -> usize {
+        self.params
+    }
+
+    /// Statements with their placeholders read as the values of `params`, `params[0]` for
+    /// `$1` and the first `?`.
+    pub fn bind(&self, params: &[Value]) -> Result<Vec<SqlStatement>> {
+        if params.len() != self.params {
+            return Err(PreparedError::ParamCountMismatch {
+                expected: self.params,
+                found: params.len(),
+            }
+            .into());
+        }
+
+        let params = params
+            .iter()
+            .map(|value| Expr::try_from(value.clone()).map(|expr| expr.to_sql()))
+            .collect::<Result<Vec<_>>>()?;
+
+        parse_prepared(&self.tokens, &params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{PreparedError, PreparedStatement},
+        crate::{
+            data::Value::{self, *},
+            parse_sql::parse,
+            result::Error,
+            translate::translate,
+        },
+    };
+
+    fn bind(sql: &str, params: &[Value]) -> Result<Vec<String>, Error> {
+        PreparedStatement::new(sql)?
+            .bind(params)?
+            .iter()
+            .map(|statement| translate(statement).map(|statement| format!("{statement:?}")))
+            .collect()
+    }
+
+    fn inline(sql: &str) -> Vec<String> {
+        parse(sql)
+            .unwrap()
+            .iter()
+            .map(|statement| format!("{:?}", translate(statement).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn params() {
+        let params = |sql| PreparedStatement::new(sql).unwrap().params();
+
+        assert_eq!(params("SELECT * FROM Item"), 0);
+        assert_eq!(params("SELECT * FROM Item WHERE id = $1 OR id = $3"), 3);
+        assert_eq!(params("SELECT * FROM Item WHERE id = ? OR name = ?"), 2);
+        assert_eq!(params("SELECT '$1', '?' FROM Item"), 0);
+        assert_eq!(
+            params("SELECT * FROM Item WHERE id IN (?,?)\nAND name = ?"),
+            3
+        );
+        assert_eq!(
+            PreparedStatement::new("SELECT * FROM Item WHERE id = $0").unwrap_err(),
+            PreparedError::InvalidPlaceholder("$0".to_owned()).into()
+        );
+        assert!(matches!(
+            PreparedStatement::new("SELECT * FROM WHERE id = $1"),
+            Err(Error::Parser(_))
+        ));
+    }
+
+    #[test]
+    fn bind_params() {
+        assert_eq!(
+            bind(
+                "SELECT * FROM Item WHERE id = $1 AND name = $2",
+                &[I64(1), Str("Glue".to_owned())]
+            ),
+            Ok(inline(
+                "SELECT * FROM Item WHERE id = (1) AND name = ('Glue')"
+            ))
+        );
+        assert_eq!(
+            bind(
+                "INSERT INTO Item VALUES (?, ?); DELETE FROM Item WHERE id = ?",
+                &[I64(-1), Str("it's".to_owned()), Null]
+            ),
+            Ok(inline(
+                "INSERT INTO Item VALUES ((-1), ('it''s')); DELETE FROM Item WHERE id = (NULL)"
+            ))
+        );
+        assert_eq!(
+            bind(
+                "SELECT * FROM Item WHERE name = $1",
+                &[Str("' OR TRUE --".to_owned())]
+            ),
+            Ok(inline("SELECT * FROM Item WHERE name = (''' OR TRUE --')"))
+        );
+        assert_eq!(
+            bind("SELECT * FROM Item WHERE id = $1 OR id = $2", &[I64(1)]),
+            Err(PreparedError::ParamCountMismatch {
+                expected: 2,
+                found: 1
+            }
+            .into())
+        );
+    }
+}
@@ -2,16 +2,19 @@ use {serde::Serialize, std::fmt::Debug, thiserror::Error as ThisError};
 
 pub use crate::{
     ast_builder::AstBuilderError,
+    audit::AuditError,
     data::{
         ConvertError, IntervalError, KeyError, LiteralError, RowError, SchemaParseError,
         StringExtError, TableError, ValueError,
     },
     executor::{
-        AlterError, DeleteError, EvaluateError, ExecuteError, FetchError, InsertError, SelectError,
-        SortError, UpdateError, ValidateError,
+        AlterError, DeleteError, EvaluateError, ExecuteError, FetchError, InsertError,
+        PrivilegeError, SelectError, SortError, UpdateError, ValidateError,
     },
+    migration::MigrationError,
     plan::PlanError,
-    store::{AlterTableError, IndexError},
+    prepared::PreparedError,
+    store::{AlterTableError, BackupError, IndexError},
     translate::TranslateError,
 };
 
@@ -73,6 +76,16 @@ pub enum Error {
     Plan(#[from] PlanError),
     #[error("schema-parse: {0}")]
     Schema(#[from] SchemaParseError),
+    #[error("privilege: {0}")]
+    Privilege(#[from] PrivilegeError),
+    #[error("audit: {0}")]
+    Audit(#[from] AuditError),
+    #[error("migration: {0}")]
+    Migration(#[from] MigrationError),
+    #[error("backup: {0}")]
+    Backup(#[from] BackupError),
+    #[error("prepared: {0}")]
+    Prepared(#[from] PreparedError),
 }
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
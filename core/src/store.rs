@@ -1,34 +1,41 @@
 mod alter_table;
+mod backup;
 mod data_row;
 mod function;
 mod index;
 mod metadata;
+mod predicate;
 mod transaction;
+mod user;
 
-pub trait GStore: Store + Index + Metadata + CustomFunction {}
-impl<S: Store + Index + Metadata + CustomFunction> GStore for S {}
+pub trait GStore: Store + Index + Metadata + CustomFunction + User + Backup {}
+impl<S: Store + Index + Metadata + CustomFunction + User + Backup> GStore for S {}
 
 pub trait GStoreMut:
-    StoreMut + IndexMut + AlterTable + Transaction + CustomFunction + CustomFunctionMut
+    StoreMut + IndexMut + AlterTable + Transaction + CustomFunction + CustomFunctionMut + UserMut
 {
 }
-impl<S: StoreMut + IndexMut + AlterTable + Transaction + CustomFunction + CustomFunctionMut>
-    GStoreMut for S
+impl<
+    S: StoreMut + IndexMut + AlterTable + Transaction + CustomFunction + CustomFunctionMut + UserMut,
+> GStoreMut for S
 {
 }
 
 pub use {
     alter_table::{AlterTable, AlterTableError},
+    backup::{Backup, BackupError},
     data_row::DataRow,
     function::{CustomFunction, CustomFunctionMut},
     index::{Index, IndexError, IndexMut},
     metadata::{MetaIter, Metadata},
+    predicate::{CompareOperator, Predicate},
     transaction::Transaction,
+    user::{User, UserMut},
 };
 
 use {
     crate::{
-        data::{Key, Schema},
+        data::{Key, Schema, TableStatistics},
         executor::Referencing,
         result::{Error, Result},
     },
@@ -50,6 +57,42 @@ pub trait Store: Send + Sync {
 
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>>;
 
+    /// Scans the rows of the table which can match the predicate, translated from the
+    /// `WHERE` clause of the query. The executor still checks the whole `WHERE` clause on
+    /// the rows returned, so a storage may check all, part or none of the predicate, but
+    /// must not leave out a row matching it. Scans every row by default.
+    async fn scan_data_filtered<'a>(
+        &'a self,
+        table_name: &str,
+        _predicate: &Predicate,
+    ) -> Result<RowIter<'a>> {
+        self.scan_data(table_name).await
+    }
+
+    /// Scans the rows of the table as [`Store::scan_data_filtered`] does, or as
+    /// [`Store::scan_data`] does without a predicate, split into up to `partitions` streams
+    /// which the executor reads side by side to aggregate them in parallel. The streams, one
+    /// after another, must give the rows in the order of a single scan. Gives a single scan
+    /// by default.
+    async fn scan_data_partitioned<'a>(
+        &'a self,
+        table_name: &str,
+        predicate: Option<&Predicate>,
+        _partitions: usize,
+    ) -> Result<Vec<RowIter<'a>>> {
+        let rows = match predicate {
+            Some(predicate) => self.scan_data_filtered(table_name, predicate).await?,
+            None => self.scan_data(table_name).await?,
+        };
+
+        Ok(vec![rows])
+    }
+
+    /// Statistics `ANALYZE` collected last for the table, `None` when it has none.
+    async fn fetch_statistics(&self, _table_name: &str) -> Result<Option<TableStatistics>> {
+        Ok(None)
+    }
+
     async fn fetch_referencings(&self, table_name: &str) -> Result<Vec<Referencing>> {
         let schemas = self.fetch_all_schemas().await?;
 
@@ -108,4 +151,12 @@ pub trait StoreMut: Send + Sync {
 
         Err(Error::StorageMsg(msg))
     }
+
+    /// Keeps the statistics of the table, replacing the ones it had. Storages implementing
+    /// this drop the statistics along with the table in `delete_schema`.
+    async fn insert_statistics(&mut self, _statistics: TableStatistics) -> Result<()> {
+        let msg = "[Storage] StoreMut::insert_statistics is not supported".to_owned();
+
+        Err(Error::StorageMsg(msg))
+    }
 }
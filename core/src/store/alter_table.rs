@@ -1,10 +1,15 @@
 use {
     super::{DataRow, Store, StoreMut},
-    crate::{ast::ColumnDef, data::Value, executor::evaluate_stateless, result::Result},
+    crate::{
+        ast::{ColumnDef, ColumnUniqueOption, DataType, Expr},
+        data::{Key, Row, Value},
+        executor::{RowContext, ValidateError, evaluate_stateless},
+        result::Result,
+    },
     async_trait::async_trait,
     futures::TryStreamExt,
     serde::Serialize,
-    std::fmt::Debug,
+    std::{borrow::Cow, collections::HashSet, fmt::Debug, sync::Arc},
     thiserror::Error,
 };
 
@@ -25,6 +30,9 @@ pub enum AlterTableError {
     #[error("Dropping column not found: {0}")]
     DroppingColumnNotFound(String),
 
+    #[error("Altering column not found: {0}")]
+    AlteringColumnNotFound(String),
+
     #[error("Schemaless table does not support ALTER TABLE: {0}")]
     SchemalessTableFound(String),
 
@@ -190,4 +198,112 @@ pub trait AlterTable: Store + StoreMut {
         self.insert_schema(&schema).await?;
         self.insert_data(table_name, rows).await
     }
+
+    /// Converts the column to `data_type`, evaluating `using` against each row or casting the
+    /// current value without it. Every row is converted before any is written, so a value that
+    /// cannot be converted leaves the table as it was.
+    async fn alter_column_type(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+        using: Option<&Expr>,
+    ) -> Result<()> {
+        let mut schema = self
+            .fetch_schema(table_name)
+            .await?
+            .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_owned()))?;
+
+        let column_defs = schema
+            .column_defs
+            .as_mut()
+            .ok_or_else(|| AlterTableError::SchemalessTableFound(table_name.to_owned()))?;
+
+        let columns = column_defs
+            .iter()
+            .map(|column_def| column_def.name.clone())
+            .collect::<Arc<[String]>>();
+        let (i, column_def) = column_defs
+            .iter_mut()
+            .enumerate()
+            .find(|(_, column_def)| column_def.name == column_name)
+            .ok_or_else(|| AlterTableError::AlteringColumnNotFound(column_name.to_owned()))?;
+
+        data_type.clone_into(&mut column_def.data_type);
+        let ColumnDef {
+            nullable, unique, ..
+        } = column_def.clone();
+        let column_defs = column_defs.clone();
+
+        let column = Expr::Identifier(column_name.to_owned());
+        let expr = using.unwrap_or(&column);
+
+        let rows = self
+            .scan_data(table_name)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut keys = HashSet::new();
+        let mut converted = Vec::with_capacity(rows.len());
+        for (key, data_row) in rows {
+            let values = match data_row {
+                DataRow::Vec(values) => values,
+                DataRow::Map(_) => {
+                    return Err(AlterTableError::ConflictOnUnexpectedMapRowFound.into());
+                }
+            };
+
+            let row = Row::Vec {
+                columns: Arc::clone(&columns),
+                values,
+            };
+            let context = RowContext::new(table_name, Cow::Borrowed(&row), None);
+            let value = evaluate_stateless(Some(context), expr)
+                .await?
+                .try_into_value(data_type, nullable)?
+                .cast(data_type)?;
+
+            if let Some(ColumnUniqueOption { is_primary: false }) = unique
+                && !value.is_null()
+                && !keys.insert(Key::try_from(&value)?)
+            {
+                return Err(ValidateError::DuplicateEntryOnUniqueField(
+                    value,
+                    column_name.to_owned(),
+                )
+                .into());
+            }
+
+            let mut values = row.try_into_vec()?;
+            values[i] = value;
+
+            let new_key = match unique {
+                Some(ColumnUniqueOption { is_primary: true }) => {
+                    let new_key = Key::primary(&column_defs, &values)?.unwrap_or(Key::None);
+                    if !keys.insert(new_key.clone()) {
+                        return Err(ValidateError::DuplicateEntryOnPrimaryKeyField(new_key).into());
+                    }
+
+                    Some(new_key)
+                }
+                _ => None,
+            };
+
+            converted.push((key, new_key, DataRow::Vec(values)));
+        }
+
+        let rekeyed = converted
+            .iter()
+            .filter_map(|(key, new_key, _)| new_key.as_ref().map(|_| key.clone()))
+            .collect::<Vec<_>>();
+        let rows = converted
+            .into_iter()
+            .map(|(key, new_key, row)| (new_key.unwrap_or(key), row))
+            .collect();
+
+        self.insert_schema(&schema).await?;
+        self.delete_data(table_name, rekeyed).await?;
+        self.insert_data(table_name, rows).await
+    }
 }
@@ -0,0 +1,26 @@
+use {
+    crate::result::{Error, Result},
+    async_trait::async_trait,
+    serde::Serialize,
+    std::path::Path,
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Serialize, Debug, PartialEq, Eq)]
+pub enum BackupError {
+    #[error("backup target already exists: {0}")]
+    TargetAlreadyExists(String),
+}
+
+/// By implementing `Backup` trait, you can copy a running storage with
+/// [`crate::prelude::Glue::backup`].
+#[async_trait]
+pub trait Backup {
+    /// Writes a consistent copy of the storage to `target_path`, which must not exist yet.
+    /// Queries may keep running meanwhile; the copy holds the data committed when it started.
+    async fn backup(&self, _target_path: &Path) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] Backup::backup is not supported".to_owned(),
+        ))
+    }
+}
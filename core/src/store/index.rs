@@ -1,7 +1,7 @@
 use {
     super::RowIter,
     crate::{
-        ast::{IndexOperator, OrderByExpr},
+        ast::{Expr, IndexOperator, OrderByExpr},
         data::Value,
         result::{Error, Result},
     },
@@ -59,7 +59,9 @@ pub trait IndexMut {
         &mut self,
         _table_name: &str,
         _index_name: &str,
-        _column: &OrderByExpr,
+        _columns: &[OrderByExpr],
+        _predicate: Option<&Expr>,
+        _fulltext: bool,
     ) -> Result<()> {
         let msg = "[Storage] Index::create_index is not supported".to_owned();
 
@@ -0,0 +1,194 @@
+use {
+    super::DataRow,
+    crate::{ast::ColumnDef, data::Value},
+    serde::{Deserialize, Serialize},
+    std::cmp::Ordering,
+};
+
+/// Conditions of the `WHERE` clause a storage can check while scanning a table, given to
+/// `Store::scan_data_filtered`. Values are already converted to the types of their columns.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Predicate {
+    Compare {
+        column: String,
+        op: CompareOperator,
+        value: Value,
+    },
+    IsNull(String),
+    IsNotNull(String),
+    In {
+        column: String,
+        values: Vec<Value>,
+    },
+    And(Vec<Predicate>),
+    Or(Vec<Predicate>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareOperator {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl CompareOperator {
+    /// Operator of the same comparison with the operands swapped, `1 < id` into `id > 1`.
+    pub fn flip(self) -> Self {
+        match self {
+            Self::Eq => Self::Eq,
+            Self::NotEq => Self::NotEq,
+            Self::Lt => Self::Gt,
+            Self::LtEq => Self::GtEq,
+            Self::Gt => Self::Lt,
+            Self::GtEq => Self::LtEq,
+        }
+    }
+}
+
+impl Predicate {
+    /// Whether the row can match the predicate, `column_defs` locating the values of a row
+    /// which is a `DataRow::Vec`. A comparison with `NULL` never matches, and rows holding a
+    /// column the predicate cannot read or a value it cannot compare are kept, leaving them to
+    /// the executor.
+    pub fn check(&self, column_defs: Option<&[ColumnDef]>, row: &DataRow) -> bool {
+        let get_value = |column: &str| match row {
+            DataRow::Vec(values) => column_defs?
+                .iter()
+                .position(|column_def| column_def.name == column)
+                .and_then(|i| values.get(i)),
+            DataRow::Map(values) => values.get(column),
+        };
+
+        match self {
+            Self::Compare { column, op, value } => {
+                let Some(target) = get_value(column) else {
+                    return true;
+                };
+
+                compare(target, *op, value)
+            }
+            Self::IsNull(column) => get_value(column).is_none_or(Value::is_null),
+            Self::IsNotNull(column) => get_value(column).is_none_or(|value| !value.is_null()),
+            Self::In { column, values } => {
+                let Some(target) = get_value(column) else {
+                    return true;
+                };
+
+                values
+                    .iter()
+                    .any(|value| compare(target, CompareOperator::Eq, value))
+            }
+            Self::And(predicates) => predicates
+                .iter()
+                .all(|predicate| predicate.check(column_defs, row)),
+            Self::Or(predicates) => predicates
+                .iter()
+                .any(|predicate| predicate.check(column_defs, row)),
+        }
+    }
+}
+
+fn compare(target: &Value, op: CompareOperator, value: &Value) -> bool {
+    if target.is_null() || value.is_null() {
+        return false;
+    }
+
+    let ordering = match op {
+        CompareOperator::Eq => return target.evaluate_eq(value).is_true(),
+        CompareOperator::NotEq => return target.evaluate_eq(value).is_false(),
+        _ => target.evaluate_cmp(value),
+    };
+
+    match (op, ordering) {
+        (_, None) => true,
+        (CompareOperator::Lt, Some(ordering)) => ordering == Ordering::Less,
+        (CompareOperator::LtEq, Some(ordering)) => ordering != Ordering::Greater,
+        (CompareOperator::Gt, Some(ordering)) => ordering == Ordering::Greater,
+        (_, Some(ordering)) => ordering != Ordering::Less,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{
+            CompareOperator::{self, *},
+            Predicate,
+        },
+        crate::{
+            ast::{ColumnDef, DataType},
+            data::Value,
+            store::DataRow,
+        },
+        std::collections::BTreeMap,
+    };
+
+    fn column_defs() -> Vec<ColumnDef> {
+        ["id", "name"]
+            .into_iter()
+            .zip([DataType::Int, DataType::Text])
+            .map(|(name, data_type)| ColumnDef {
+                name: name.to_owned(),
+                data_type,
+                nullable: true,
+                default: None,
+                unique: None,
+                comment: None,
+            })
+            .collect()
+    }
+
+    fn compare(column: &str, op: CompareOperator, value: Value) -> Predicate {
+        Predicate::Compare {
+            column: column.to_owned(),
+            op,
+            value,
+        }
+    }
+
+    #[test]
+    fn check() {
+        let column_defs = column_defs();
+        let column_defs = Some(column_defs.as_slice());
+        let row = DataRow::Vec(vec![Value::I64(3), Value::Null]);
+
+        assert!(compare("id", Eq, Value::I64(3)).check(column_defs, &row));
+        assert!(!compare("id", NotEq, Value::I64(3)).check(column_defs, &row));
+        assert!(compare("id", Lt, Value::I64(5)).check(column_defs, &row));
+        assert!(!compare("id", Gt, Value::I64(5)).check(column_defs, &row));
+        assert!(compare("id", GtEq, Value::F64(3.0)).check(column_defs, &row));
+        assert!(!compare("name", Eq, Value::Str("a".to_owned())).check(column_defs, &row));
+        assert!(Predicate::IsNull("name".to_owned()).check(column_defs, &row));
+        assert!(!Predicate::IsNotNull("name".to_owned()).check(column_defs, &row));
+
+        let predicate = Predicate::In {
+            column: "id".to_owned(),
+            values: vec![Value::I64(1), Value::I64(3)],
+        };
+        assert!(predicate.check(column_defs, &row));
+
+        let predicate = Predicate::Or(vec![
+            compare("id", Eq, Value::I64(1)),
+            compare("id", Eq, Value::I64(2)),
+        ]);
+        assert!(!predicate.check(column_defs, &row));
+
+        let predicate = Predicate::And(vec![
+            compare("id", Gt, Value::I64(1)),
+            Predicate::IsNull("name".to_owned()),
+        ]);
+        assert!(predicate.check(column_defs, &row));
+
+        // rows the predicate cannot read or compare are left to the executor
+        assert!(compare("missing", Eq, Value::I64(1)).check(column_defs, &row));
+        assert!(compare("id", Lt, Value::Str("a".to_owned())).check(column_defs, &row));
+        assert!(compare("id", Eq, Value::I64(1)).check(None, &row));
+
+        let row = DataRow::Map(BTreeMap::from([("id".to_owned(), Value::I64(3))]));
+        assert!(compare("id", Eq, Value::I64(3)).check(None, &row));
+        assert!(!compare("id", Eq, Value::I64(4)).check(None, &row));
+    }
+}
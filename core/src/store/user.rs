@@ -0,0 +1,38 @@
+use {
+    crate::{
+        data::User as StructUser,
+        result::{Error, Result},
+    },
+    async_trait::async_trait,
+};
+
+#[async_trait]
+pub trait User {
+    async fn fetch_user(&self, _name: &str) -> Result<Option<StructUser>> {
+        Err(Error::StorageMsg(
+            "[Storage] User is not supported".to_owned(),
+        ))
+    }
+
+    async fn fetch_all_users(&self) -> Result<Vec<StructUser>> {
+        Err(Error::StorageMsg(
+            "[Storage] User is not supported".to_owned(),
+        ))
+    }
+}
+
+#[async_trait]
+pub trait UserMut {
+    /// Inserts the user, replacing a user of the same name.
+    async fn insert_user(&mut self, _user: StructUser) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] User is not supported".to_owned(),
+        ))
+    }
+
+    async fn delete_user(&mut self, _name: &str) -> Result<()> {
+        Err(Error::StorageMsg(
+            "[Storage] User is not supported".to_owned(),
+        ))
+    }
+}
@@ -0,0 +1,61 @@
+//! Settings of the [`tracing`] spans emitted while statements run.
+//!
+//! Parsing, translation, planning and execution each open a span, and so do the storage
+//! trait implementations of the bundled storages. Nothing is collected unless the application
+//! installs a `tracing` subscriber, such as the OTLP exporter of the servers' `otlp` feature.
+
+use {
+    crate::parse_sql::redact,
+    std::sync::atomic::{AtomicU8, Ordering},
+};
+
+/// How the SQL text is recorded on the `query` field of the `parse` span.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum QueryText {
+    Full,
+    /// Literals replaced with `?`, see [`redact`].
+    Redacted,
+    Hidden,
+}
+
+static QUERY_TEXT: AtomicU8 = AtomicU8::new(QueryText::Full as u8);
+
+/// Sets how the query text is recorded, process wide. The default is [`QueryText::Full`].
+pub fn set_query_text(query_text: QueryText) {
+    QUERY_TEXT.store(query_text as u8, Ordering::Relaxed);
+}
+
+pub fn get_query_text() -> QueryText {
+    match QUERY_TEXT.load(Ordering::Relaxed) {
+        0 => QueryText::Full,
+        1 => QueryText::Redacted,
+        _ => QueryText::Hidden,
+    }
+}
+
+pub(crate) fn query_text(sql: &str) -> Option<String> {
+    match get_query_text() {
+        QueryText::Full => Some(sql.to_owned()),
+        QueryText::Redacted => Some(redact(sql)),
+        QueryText::Hidden => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_sql::redact;
+
+    #[test]
+    fn redact_literals() {
+        assert_eq!(
+            redact("SELECT * FROM Foo WHERE name = 'secret' AND id > 10"),
+            "SELECT * FROM Foo WHERE name = ? AND id > ?"
+        );
+        assert_eq!(
+            redact("INSERT INTO \"Foo\" VALUES (1, X'AB', E'a\\nb') -- note"),
+            "INSERT INTO \"Foo\" VALUES (?, ?, ?)  "
+        );
+        assert_eq!(redact("SELECT 'unterminated"), "?");
+    }
+}
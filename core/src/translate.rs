@@ -1,37 +1,58 @@
 mod ast_literal;
+mod cte;
 mod data_type;
+mod dcl;
 mod ddl;
 mod error;
 mod expr;
+mod fake;
 mod function;
 mod operator;
 mod query;
 
 pub use self::{
     data_type::translate_data_type,
-    ddl::{translate_column_def, translate_operate_function_arg},
+    ddl::{translate_column_def, translate_operate_function_arg, translate_table_column_def},
     error::TranslateError,
     expr::{translate_expr, translate_order_by_expr},
     query::{alias_or_name, translate_query, translate_select_item},
 };
 
+pub(crate) use cte::visit_query;
+
 use {
     crate::{
-        ast::{Assignment, ForeignKey, ReferentialAction, Statement, Variable},
+        ast::{
+            Assignment, ForeignKey, OnConflict, OnConflictAction, Query, ReferentialAction, Select,
+            SelectItem, SetExpr, SetVariable, Statement, TableAlias, TableFactor, TableWithJoins,
+            TriggerEvent, TriggerTiming, Variable,
+        },
+        parse_sql::parse,
         result::Result,
     },
-    ddl::translate_alter_table_operation,
+    dcl::{translate_grant_objects, translate_password, translate_privileges},
+    ddl::{
+        translate_alter_table_operation, translate_checks, translate_index_name,
+        translate_primary_key, translate_uniques,
+    },
     sqlparser::ast::{
         Assignment as SqlAssignment, AssignmentTarget as SqlAssignmentTarget,
-        CommentDef as SqlCommentDef, CreateFunctionBody as SqlCreateFunctionBody,
-        CreateIndex as SqlCreateIndex, CreateTable as SqlCreateTable, Delete as SqlDelete,
-        FromTable as SqlFromTable, Ident as SqlIdent, Insert as SqlInsert,
-        ObjectName as SqlObjectName, ObjectType as SqlObjectType,
-        ReferentialAction as SqlReferentialAction, Statement as SqlStatement,
-        TableConstraint as SqlTableConstraint, TableFactor, TableWithJoins,
+        CommentDef as SqlCommentDef, ConflictTarget as SqlConflictTarget,
+        CreateFunctionBody as SqlCreateFunctionBody, CreateIndex as SqlCreateIndex,
+        CreateTable as SqlCreateTable, Delete as SqlDelete, DoUpdate as SqlDoUpdate,
+        Expr as SqlExpr, FromTable as SqlFromTable, Ident as SqlIdent, Insert as SqlInsert,
+        ObjectName as SqlObjectName, ObjectType as SqlObjectType, OnConflict as SqlOnConflict,
+        OnConflictAction as SqlOnConflictAction, OnInsert as SqlOnInsert, Query as SqlQuery,
+        ReferentialAction as SqlReferentialAction, SelectItem as SqlSelectItem,
+        SequenceOptions as SqlSequenceOptions, ShowCreateObject as SqlShowCreateObject,
+        Statement as SqlStatement, TableConstraint as SqlTableConstraint,
+        TableFactor as SqlTableFactor, TableWithJoins as SqlTableWithJoins,
+        TriggerEvent as SqlTriggerEvent, TriggerExecBody as SqlTriggerExecBody,
+        TriggerPeriod as SqlTriggerPeriod, Value as SqlValue, ViewColumnDef as SqlViewColumnDef,
     },
 };
 
+#[tracing::instrument(skip_all)]
 pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
     match sql_statement {
         SqlStatement::Query(query) => translate_query(query).map(Statement::Query),
@@ -39,6 +60,8 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
             table_name,
             columns,
             source,
+            on,
+            returning,
             ..
         }) => {
             let table_name = translate_object_name(table_name)?;
@@ -49,17 +72,21 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                     TranslateError::DefaultValuesOnInsertNotSupported(table_name.clone()).into()
                 })
                 .and_then(translate_query)?;
+            let on_conflict = on.as_ref().map(translate_on_conflict).transpose()?;
 
             Ok(Statement::Insert {
                 table_name,
                 columns,
                 source,
+                on_conflict,
+                returning: translate_returning(returning.as_deref())?,
             })
         }
         SqlStatement::Update {
             table,
             assignments,
             selection,
+            returning,
             ..
         } => Ok(Statement::Update {
             table_name: translate_table_with_join(table)?,
@@ -68,9 +95,13 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                 .map(translate_assignment)
                 .collect::<Result<_>>()?,
             selection: selection.as_ref().map(translate_expr).transpose()?,
+            returning: translate_returning(returning.as_deref())?,
         }),
         SqlStatement::Delete(SqlDelete {
-            from, selection, ..
+            from,
+            selection,
+            returning,
+            ..
         }) => {
             let from = match from {
                 SqlFromTable::WithFromKeyword(from) => from,
@@ -87,6 +118,7 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
             Ok(Statement::Delete {
                 table_name,
                 selection: selection.as_ref().map(translate_expr).transpose()?,
+                returning: translate_returning(returning.as_deref())?,
             })
         }
         SqlStatement::CreateTable(SqlCreateTable {
@@ -99,17 +131,28 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
             comment,
             ..
         }) => {
-            let columns = columns
+            let name = translate_object_name(name)?;
+            let checks = translate_checks(&name, columns, constraints)?;
+            let uniques = translate_uniques(&name, constraints)?;
+
+            let mut columns = columns
                 .iter()
-                .map(translate_column_def)
+                .map(|column_def| translate_table_column_def(&name, column_def))
                 .collect::<Result<Vec<_>>>()?;
+            translate_primary_key(&name, &mut columns, constraints)?;
 
             let columns = (!columns.is_empty()).then_some(columns);
 
-            let name = translate_object_name(name)?;
-
             let foreign_keys = constraints
                 .iter()
+                .filter(|constraint| {
+                    !matches!(
+                        constraint,
+                        SqlTableConstraint::Check { .. }
+                            | SqlTableConstraint::PrimaryKey { .. }
+                            | SqlTableConstraint::Unique { .. }
+                    )
+                })
                 .map(translate_foreign_key)
                 .collect::<Result<Vec<_>>>()?;
 
@@ -125,6 +168,8 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                     .as_ref()
                     .map(|table_engine| table_engine.name.to_owned()),
                 foreign_keys,
+                checks,
+                uniques,
                 comment: comment.as_ref().map(|comment| match comment {
                     SqlCommentDef::WithEq(comment)
                     | SqlCommentDef::WithoutEq(comment)
@@ -132,6 +177,24 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                 }),
             })
         }
+        SqlStatement::CreateView {
+            or_replace,
+            materialized,
+            name,
+            columns,
+            query,
+            ..
+        } => {
+            let name = translate_object_name(name)?;
+            let query = translate_view_query(&name, columns, query)?;
+
+            Ok(Statement::CreateView {
+                or_replace: *or_replace,
+                name,
+                query: Box::new(query),
+                materialized: *materialized,
+            })
+        }
         SqlStatement::AlterTable {
             name, operations, ..
         } => {
@@ -163,6 +226,20 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                 .collect::<Result<Vec<_>>>()?,
             cascade: *cascade,
         }),
+        SqlStatement::Drop {
+            object_type: SqlObjectType::View,
+            if_exists,
+            names,
+            cascade,
+            ..
+        } => Ok(Statement::DropView {
+            if_exists: *if_exists,
+            names: names
+                .iter()
+                .map(translate_object_name)
+                .collect::<Result<Vec<_>>>()?,
+            cascade: *cascade,
+        }),
         SqlStatement::DropFunction {
             if_exists,
             func_desc,
@@ -174,21 +251,89 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                 .map(|v| translate_object_name(&v.name))
                 .collect::<Result<Vec<_>>>()?,
         }),
+        SqlStatement::CreateTrigger {
+            or_replace,
+            name,
+            period,
+            events,
+            table_name,
+            condition,
+            exec_body,
+            ..
+        } => Ok(Statement::CreateTrigger {
+            or_replace: *or_replace,
+            name: translate_object_name(name)?,
+            table_name: translate_object_name(table_name)?,
+            timing: translate_trigger_period(period)?,
+            events: events
+                .iter()
+                .map(translate_trigger_event)
+                .collect::<Result<Vec<_>>>()?,
+            condition: condition.as_ref().map(translate_expr).transpose()?,
+            body: translate_trigger_body(exec_body)?,
+        }),
+        SqlStatement::DropTrigger {
+            if_exists,
+            trigger_name,
+            table_name,
+            ..
+        } => Ok(Statement::DropTrigger {
+            if_exists: *if_exists,
+            name: translate_object_name(trigger_name)?,
+            table_name: translate_object_name(table_name)?,
+        }),
+        SqlStatement::CreateSequence {
+            temporary: false,
+            if_not_exists,
+            name,
+            data_type: None,
+            sequence_options,
+            owned_by: None,
+        } => {
+            let (increment, start) = translate_sequence_options(sequence_options)?;
+
+            Ok(Statement::CreateSequence {
+                if_not_exists: *if_not_exists,
+                name: translate_object_name(name)?,
+                increment,
+                start,
+            })
+        }
+        SqlStatement::Drop {
+            object_type: SqlObjectType::Sequence,
+            if_exists,
+            names,
+            ..
+        } => Ok(Statement::DropSequence {
+            if_exists: *if_exists,
+            names: names
+                .iter()
+                .map(translate_object_name)
+                .collect::<Result<Vec<_>>>()?,
+        }),
         SqlStatement::CreateIndex(SqlCreateIndex {
             name,
             table_name,
+            using,
             columns,
+            predicate,
             ..
         }) => {
-            if columns.len() > 1 {
-                return Err(TranslateError::CompositeIndexNotSupported.into());
-            }
-
-            let Some(name) = name else {
-                return Err(TranslateError::UnsupportedUnnamedIndex.into());
+            let table_name = translate_object_name(table_name)?;
+            let fulltext = match using.as_ref().map(|using| using.value.to_uppercase()) {
+                None => false,
+                Some(method) if method == "BTREE" => false,
+                Some(method) if method == "FULLTEXT" => true,
+                Some(method) => return Err(TranslateError::UnsupportedIndexMethod(method).into()),
+            };
+            let columns = columns
+                .iter()
+                .map(translate_order_by_expr)
+                .collect::<Result<Vec<_>>>()?;
+            let name = match name {
+                Some(name) => translate_object_name(name)?,
+                None => translate_index_name(&table_name, &columns),
             };
-
-            let name = translate_object_name(name)?;
 
             if name.to_uppercase() == "PRIMARY" {
                 return Err(TranslateError::ReservedIndexName(name).into());
@@ -196,8 +341,10 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
 
             Ok(Statement::CreateIndex {
                 name,
-                table_name: translate_object_name(table_name)?,
-                column: translate_order_by_expr(&columns[0])?,
+                table_name,
+                columns,
+                predicate: predicate.as_ref().map(translate_expr).transpose()?,
+                fulltext,
             })
         }
         SqlStatement::Drop {
@@ -223,6 +370,111 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
 
             Ok(Statement::DropIndex { name, table_name })
         }
+        SqlStatement::CreateRole {
+            names,
+            if_not_exists,
+            login,
+            inherit,
+            bypassrls,
+            password,
+            superuser,
+            create_db,
+            create_role,
+            replication,
+            connection_limit,
+            valid_until,
+            in_role,
+            in_group,
+            role,
+            user,
+            admin,
+            authorization_owner,
+        } => {
+            let unsupported = *login == Some(false)
+                || inherit.is_some()
+                || bypassrls.is_some()
+                || create_db.is_some()
+                || create_role.is_some()
+                || replication.is_some()
+                || connection_limit.is_some()
+                || valid_until.is_some()
+                || !in_role.is_empty()
+                || !in_group.is_empty()
+                || !role.is_empty()
+                || !user.is_empty()
+                || !admin.is_empty()
+                || authorization_owner.is_some();
+            if unsupported {
+                return Err(
+                    TranslateError::UnsupportedCreateUserOption(sql_statement.to_string()).into(),
+                );
+            }
+
+            let [name] = names.as_slice() else {
+                return Err(TranslateError::TooManyUsersInCreateUser.into());
+            };
+
+            Ok(Statement::CreateUser {
+                if_not_exists: *if_not_exists,
+                name: translate_object_name(name)?,
+                password: password
+                    .as_ref()
+                    .map(translate_password)
+                    .transpose()?
+                    .flatten(),
+                superuser: superuser.unwrap_or(false),
+            })
+        }
+        SqlStatement::Drop {
+            object_type: SqlObjectType::Role,
+            if_exists,
+            names,
+            ..
+        } => Ok(Statement::DropUser {
+            if_exists: *if_exists,
+            names: names
+                .iter()
+                .map(translate_object_name)
+                .collect::<Result<Vec<_>>>()?,
+        }),
+        SqlStatement::Grant {
+            privileges,
+            objects,
+            grantees,
+            with_grant_option,
+            granted_by,
+        } => {
+            if *with_grant_option || granted_by.is_some() {
+                return Err(
+                    TranslateError::UnsupportedGrantOption(sql_statement.to_string()).into(),
+                );
+            }
+
+            Ok(Statement::Grant {
+                privileges: translate_privileges(privileges)?,
+                table_names: translate_grant_objects(objects)?,
+                grantees: translate_idents(grantees),
+            })
+        }
+        SqlStatement::Revoke {
+            privileges,
+            objects,
+            grantees,
+            granted_by,
+            ..
+        } => {
+            if granted_by.is_some() {
+                return Err(
+                    TranslateError::UnsupportedGrantOption(sql_statement.to_string()).into(),
+                );
+            }
+
+            Ok(Statement::Revoke {
+                privileges: translate_privileges(privileges)?,
+                table_names: translate_grant_objects(objects)?,
+                grantees: translate_idents(grantees),
+            })
+        }
         SqlStatement::StartTransaction { .. } => Ok(Statement::StartTransaction),
         SqlStatement::Commit { .. } => Ok(Statement::Commit),
         SqlStatement::Rollback { .. } => Ok(Statement::Rollback),
@@ -237,8 +489,20 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
         SqlStatement::ShowVariable { variable } => match (variable.len(), variable.first()) {
             (1, Some(keyword)) => match keyword.value.to_uppercase().as_str() {
                 "VERSION" => Ok(Statement::ShowVariable(Variable::Version)),
+                "ANALYZE" => Ok(Statement::Analyze { table_name: None }),
                 v => Err(TranslateError::UnsupportedShowVariableKeyword(v.to_owned()).into()),
             },
+            (2, Some(keyword))
+                if keyword.value.eq_ignore_ascii_case("CHECK")
+                    && variable[1].value.eq_ignore_ascii_case("DATABASE") =>
+            {
+                Ok(Statement::Check { table_name: None })
+            }
+            (2, Some(keyword)) if keyword.value.eq_ignore_ascii_case("ANALYZE") => {
+                Ok(Statement::Analyze {
+                    table_name: Some(variable[1].value.to_owned()),
+                })
+            }
             (3, Some(keyword)) => match keyword.value.to_uppercase().as_str() {
                 "INDEXES" => match variable.get(2) {
                     Some(tablename) => Ok(Statement::ShowIndexes(tablename.value.to_owned())),
@@ -247,18 +511,47 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
                     )
                     .into()),
                 },
+                "CHECK" if variable[1].value.eq_ignore_ascii_case("TABLE") => {
+                    Ok(Statement::Check {
+                        table_name: Some(variable[2].value.to_owned()),
+                    })
+                }
                 _ => Err(TranslateError::UnsupportedShowVariableStatement(
                     sql_statement.to_string(),
                 )
                 .into()),
             },
+            (4, Some(keyword)) if keyword.value.eq_ignore_ascii_case("REFRESH") => Ok(
+                Statement::RefreshMaterializedView(variable[3].value.to_owned()),
+            ),
             _ => Err(
                 TranslateError::UnsupportedShowVariableStatement(sql_statement.to_string()).into(),
             ),
         },
+        SqlStatement::ShowCreate {
+            obj_type: SqlShowCreateObject::View,
+            obj_name,
+        } => translate_object_name(obj_name).map(Statement::ShowCreateView),
+        SqlStatement::Explain {
+            analyze, statement, ..
+        } => match statement.as_ref() {
+            SqlStatement::Query(query) => Ok(Statement::Explain {
+                analyze: *analyze,
+                query: translate_query(query)?,
+            }),
+            statement => {
+                Err(TranslateError::UnsupportedExplainStatement(statement.to_string()).into())
+            }
+        },
         SqlStatement::ShowColumns { table_name, .. } => Ok(Statement::ShowColumns {
             table_name: translate_object_name(table_name)?,
         }),
+        SqlStatement::SetVariable {
+            local: false,
+            hivevar: false,
+            variables,
+            value,
+        } => translate_set_variable(variables, value).map(Statement::SetVariable),
         SqlStatement::CreateFunction {
             or_replace,
             name,
@@ -288,6 +581,48 @@ pub fn translate(sql_statement: &SqlStatement) -> Result<Statement> {
     }
 }
 
+fn translate_returning(returning: Option<&[SqlSelectItem]>) -> Result<Vec<SelectItem>> {
+    returning
+        .unwrap_or_default()
+        .iter()
+        .map(translate_select_item)
+        .collect()
+}
+
+fn translate_on_conflict(on_insert: &SqlOnInsert) -> Result<OnConflict> {
+    let unsupported =
+        || TranslateError::UnsupportedOnInsert(on_insert.to_string().trim().to_owned());
+
+    let SqlOnInsert::OnConflict(SqlOnConflict {
+        conflict_target,
+        action,
+    }) = on_insert
+    else {
+        return Err(unsupported().into());
+    };
+
+    let target = match conflict_target {
+        Some(SqlConflictTarget::Columns(columns)) => translate_idents(columns),
+        Some(SqlConflictTarget::OnConstraint(_)) => return Err(unsupported().into()),
+        None => Vec::new(),
+    };
+    let action = match action {
+        SqlOnConflictAction::DoNothing => OnConflictAction::DoNothing,
+        SqlOnConflictAction::DoUpdate(SqlDoUpdate {
+            assignments,
+            selection,
+        }) => OnConflictAction::DoUpdate {
+            assignments: assignments
+                .iter()
+                .map(translate_assignment)
+                .collect::<Result<_>>()?,
+            selection: selection.as_ref().map(translate_expr).transpose()?,
+        },
+    };
+
+    Ok(OnConflict { target, action })
+}
+
 pub fn translate_assignment(sql_assignment: &SqlAssignment) -> Result<Assignment> {
     let SqlAssignment { target, value } = sql_assignment;
 
@@ -317,16 +652,93 @@ pub fn translate_assignment(sql_assignment: &SqlAssignment) -> Result<Assignment
     })
 }
 
-fn translate_table_with_join(table: &TableWithJoins) -> Result<String> {
+fn translate_set_variable(variables: &[SqlObjectName], value: &[SqlExpr]) -> Result<SetVariable> {
+    let ([name], [value]) = (variables, value) else {
+        let variables = variables
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        return Err(TranslateError::UnsupportedSetVariable(variables.join(", ")).into());
+    };
+
+    let text = match value {
+        SqlExpr::Identifier(ident) => Some(ident.value.as_str()),
+        SqlExpr::Value(SqlValue::SingleQuotedString(value)) => Some(value.as_str()),
+        _ => None,
+    };
+    let invalid_value = || TranslateError::InvalidSetVariableValue {
+        name: name.to_string(),
+        value: value.to_string(),
+    };
+
+    match name.to_string().to_lowercase().as_str() {
+        "audit" => match value {
+            SqlExpr::Value(SqlValue::Boolean(enabled)) => Ok(SetVariable::Audit(*enabled)),
+            _ => match text.map(str::to_uppercase).as_deref() {
+                Some("ON") => Ok(SetVariable::Audit(true)),
+                Some("OFF") => Ok(SetVariable::Audit(false)),
+                _ => Err(invalid_value().into()),
+            },
+        },
+        "audit_table" => text
+            .map(|table_name| SetVariable::AuditTable(table_name.to_owned()))
+            .ok_or_else(|| invalid_value().into()),
+        _ => Err(TranslateError::UnsupportedSetVariable(name.to_string()).into()),
+    }
+}
+
+fn translate_table_with_join(table: &SqlTableWithJoins) -> Result<String> {
     if !table.joins.is_empty() {
         return Err(TranslateError::JoinOnUpdateNotSupported.into());
     }
     match &table.relation {
-        TableFactor::Table { name, .. } => translate_object_name(name),
+        SqlTableFactor::Table { name, .. } => translate_object_name(name),
         t => Err(TranslateError::UnsupportedTableFactor(t.to_string()).into()),
     }
 }
 
+/// Names the columns of the view query with the column list of `CREATE VIEW`, if any.
+fn translate_view_query(
+    name: &str,
+    columns: &[SqlViewColumnDef],
+    sql_query: &SqlQuery,
+) -> Result<Query> {
+    let query = translate_query(sql_query)?;
+    if columns.is_empty() {
+        return Ok(query);
+    }
+
+    let columns = columns
+        .iter()
+        .map(|column| column.name.value.to_owned())
+        .collect();
+    let select = Select {
+        distinct: false,
+        projection: vec![SelectItem::Wildcard],
+        from: TableWithJoins {
+            relation: TableFactor::Derived {
+                subquery: query,
+                alias: TableAlias {
+                    name: name.to_owned(),
+                    columns,
+                },
+            },
+            joins: Vec::new(),
+        },
+        selection: None,
+        group_by: Vec::new(),
+        having: None,
+    };
+
+    Ok(Query {
+        body: SetExpr::Select(Box::new(select)),
+        order_by: Vec::new(),
+        limit: None,
+        offset: None,
+    })
+}
+
 fn translate_object_name(sql_object_name: &SqlObjectName) -> Result<String> {
     let sql_object_name = &sql_object_name.0;
     if sql_object_name.len() > 1 {
@@ -344,6 +756,87 @@ pub fn translate_idents(idents: &[SqlIdent]) -> Vec<String> {
     idents.iter().map(|v| v.value.to_owned()).collect()
 }
 
+fn translate_trigger_period(period: &SqlTriggerPeriod) -> Result<TriggerTiming> {
+    match period {
+        SqlTriggerPeriod::Before => Ok(TriggerTiming::Before),
+        SqlTriggerPeriod::After => Ok(TriggerTiming::After),
+        SqlTriggerPeriod::InsteadOf => {
+            Err(TranslateError::UnsupportedTriggerTiming(period.to_string()).into())
+        }
+    }
+}
+
+fn translate_trigger_event(event: &SqlTriggerEvent) -> Result<TriggerEvent> {
+    match event {
+        SqlTriggerEvent::Insert => Ok(TriggerEvent::Insert),
+        SqlTriggerEvent::Update(columns) if columns.is_empty() => Ok(TriggerEvent::Update),
+        SqlTriggerEvent::Delete => Ok(TriggerEvent::Delete),
+        _ => Err(TranslateError::UnsupportedTriggerEvent(event.to_string()).into()),
+    }
+}
+
+/// Returns the increment and the start of a sequence, starting ascending sequences at 1 and
+/// descending ones at -1 unless `START WITH` is given.
+fn translate_sequence_options(options: &[SqlSequenceOptions]) -> Result<(i64, i64)> {
+    let value = |expr: &SqlExpr| {
+        expr.to_string()
+            .parse::<i64>()
+            .map_err(|_| TranslateError::InvalidSequenceValue(expr.to_string()))
+    };
+
+    let mut increment = 1;
+    let mut start = None;
+    for option in options {
+        match option {
+            SqlSequenceOptions::IncrementBy(expr, _) => increment = value(expr)?,
+            SqlSequenceOptions::StartWith(expr, _) => start = Some(value(expr)?),
+            _ => {
+                return Err(TranslateError::UnsupportedSequenceOption(
+                    option.to_string().trim().to_owned(),
+                )
+                .into());
+            }
+        }
+    }
+
+    if increment == 0 {
+        return Err(TranslateError::ZeroSequenceIncrement.into());
+    }
+
+    let start = start.unwrap_or(increment.signum());
+
+    Ok((increment, start))
+}
+
+/// Reads the SQL of the statements `parse_sql` keeps as the arguments of the trigger body,
+/// checking that each one modifies a table.
+fn translate_trigger_body(exec_body: &SqlTriggerExecBody) -> Result<Vec<String>> {
+    let args = exec_body.func_desc.args.as_deref().unwrap_or_default();
+
+    args.iter()
+        .map(|arg| {
+            let Some(SqlExpr::Value(SqlValue::SingleQuotedString(sql))) = &arg.default_expr else {
+                return Err(TranslateError::UnsupportedTriggerStatement(arg.to_string()).into());
+            };
+
+            for sql_statement in parse(sql)? {
+                match translate(&sql_statement)? {
+                    Statement::Insert { .. }
+                    | Statement::Update { .. }
+                    | Statement::Delete { .. } => {}
+                    _ => {
+                        return Err(
+                            TranslateError::UnsupportedTriggerStatement(sql.to_owned()).into()
+                        );
+                    }
+                }
+            }
+
+            Ok(sql.to_owned())
+        })
+        .collect()
+}
+
 pub fn translate_referential_action(
     action: &Option<SqlReferentialAction>,
 ) -> Result<ReferentialAction> {
@@ -352,7 +845,10 @@ pub fn translate_referential_action(
     let action = action.unwrap_or(NoAction);
 
     match action {
-        NoAction | Restrict => Ok(ReferentialAction::NoAction),
+        NoAction => Ok(ReferentialAction::NoAction),
+        Restrict => Ok(ReferentialAction::Restrict),
+        Cascade => Ok(ReferentialAction::Cascade),
+        SetNull => Ok(ReferentialAction::SetNull),
         _ => Err(TranslateError::UnsupportedConstraint(action.to_string()).into()),
     }
 }
@@ -0,0 +1,315 @@
+use {
+    super::{TranslateError, query::translate_set_expr, translate_idents, translate_query},
+    crate::{
+        ast::{
+            Expr, Join, JoinConstraint, JoinOperator, Query, Select, SelectItem, SetExpr,
+            TableAlias, TableFactor, Values,
+        },
+        result::Result,
+    },
+    sqlparser::ast::{
+        Cte as SqlCte, Query as SqlQuery, SetExpr as SqlSetExpr, SetOperator as SqlSetOperator,
+        SetQuantifier as SqlSetQuantifier, TableAlias as SqlTableAlias, With as SqlWith,
+    },
+    std::collections::HashMap,
+};
+
+/// Replaces the references to the tables of the `WITH` clause, in the `FROM` and `JOIN` clauses
+/// of the query and of its subqueries, with the tables they name.
+pub fn translate_with(sql_with: &SqlWith, mut query: Query) -> Result<Query> {
+    let SqlWith {
+        recursive,
+        cte_tables,
+    } = sql_with;
+
+    let mut ctes = HashMap::new();
+    for SqlCte {
+        alias: SqlTableAlias { name, columns },
+        query: sql_query,
+        ..
+    } in cte_tables
+    {
+        let name = name.value.to_owned();
+        if ctes.contains_key(&name) {
+            return Err(TranslateError::DuplicateCteName(name).into());
+        }
+
+        let columns = translate_idents(columns);
+        let table_factor = match translate_recursive(*recursive, &name, sql_query)? {
+            Some((mut anchor, mut recursive, all)) => {
+                bind_query(&mut anchor, &ctes);
+                bind_query(&mut recursive, &ctes);
+
+                TableFactor::Recursive {
+                    name: name.clone(),
+                    columns,
+                    anchor: Box::new(anchor),
+                    recursive: Box::new(recursive),
+                    all,
+                    alias: TableAlias {
+                        name: name.clone(),
+                        columns: Vec::new(),
+                    },
+                    max_depth: None,
+                }
+            }
+            None => {
+                let mut subquery = translate_query(sql_query)?;
+                bind_query(&mut subquery, &ctes);
+
+                TableFactor::Derived {
+                    subquery,
+                    alias: TableAlias {
+                        name: name.clone(),
+                        columns,
+                    },
+                }
+            }
+        };
+
+        ctes.insert(name, table_factor);
+    }
+
+    bind_query(&mut query, &ctes);
+
+    Ok(query)
+}
+
+/// Splits `anchor UNION [ALL] recursive` when the recursive query reads the table `name`.
+fn translate_recursive(
+    recursive: bool,
+    name: &str,
+    sql_query: &SqlQuery,
+) -> Result<Option<(Query, Query, bool)>> {
+    let SqlQuery {
+        body,
+        order_by,
+        limit,
+        offset,
+        ..
+    } = sql_query;
+
+    if !recursive || order_by.is_some() || limit.is_some() || offset.is_some() {
+        return Ok(None);
+    }
+
+    let SqlSetExpr::SetOperation {
+        op: SqlSetOperator::Union,
+        set_quantifier,
+        left,
+        right,
+    } = body.as_ref()
+    else {
+        return Ok(None);
+    };
+
+    let all = match set_quantifier {
+        SqlSetQuantifier::All => true,
+        SqlSetQuantifier::None | SqlSetQuantifier::Distinct => false,
+        _ => return Ok(None),
+    };
+
+    let query = |body| Query {
+        body,
+        order_by: Vec::new(),
+        limit: None,
+        offset: None,
+    };
+    let anchor = query(translate_set_expr(left)?);
+    let mut recursive = query(translate_set_expr(right)?);
+
+    let mut reads_itself = false;
+    visit_query(&mut recursive, &mut |table_factor| {
+        if let TableFactor::Table {
+            name: table_name, ..
+        } = table_factor
+        {
+            reads_itself |= table_name == name;
+        }
+    });
+
+    Ok(reads_itself.then_some((anchor, recursive, all)))
+}
+
+fn bind_query(query: &mut Query, ctes: &HashMap<String, TableFactor>) {
+    if ctes.is_empty() {
+        return;
+    }
+
+    visit_query(query, &mut |table_factor| {
+        let TableFactor::Table {
+            name,
+            alias: reference,
+            ..
+        } = table_factor
+        else {
+            return;
+        };
+        let Some(cte) = ctes.get(name.as_str()) else {
+            return;
+        };
+
+        let mut cte = cte.clone();
+        if let (
+            Some(reference),
+            TableFactor::Derived { alias, .. } | TableFactor::Recursive { alias, .. },
+        ) = (reference, &mut cte)
+        {
+            alias.name = reference.name.clone();
+            if !reference.columns.is_empty() {
+                alias.columns = reference.columns.clone();
+            }
+        }
+
+        *table_factor = cte;
+    });
+}
+
+/// Calls `f` on each table factor of the query and of its subqueries, inner ones first.
+pub(crate) fn visit_query(query: &mut Query, f: &mut impl FnMut(&mut TableFactor)) {
+    let Query {
+        body,
+        order_by,
+        limit,
+        offset,
+    } = query;
+
+    visit_set_expr(body, f);
+
+    order_by
+        .iter_mut()
+        .map(|order_by| &mut order_by.expr)
+        .chain(limit)
+        .chain(offset)
+        .for_each(|expr| visit_expr(expr, f));
+}
+
+fn visit_set_expr(set_expr: &mut SetExpr, f: &mut impl FnMut(&mut TableFactor)) {
+    match set_expr {
+        SetExpr::Select(select) => {
+            let Select {
+                projection,
+                from,
+                selection,
+                group_by,
+                having,
+                ..
+            } = select.as_mut();
+
+            visit_table_factor(&mut from.relation, f);
+            for Join {
+                relation,
+                join_operator,
+                ..
+            } in &mut from.joins
+            {
+                visit_table_factor(relation, f);
+
+                let (JoinOperator::Inner(constraint) | JoinOperator::LeftOuter(constraint)) =
+                    join_operator;
+                if let JoinConstraint::On(expr) = constraint {
+                    visit_expr(expr, f);
+                }
+            }
+
+            for item in projection {
+                if let SelectItem::Expr { expr, .. } = item {
+                    visit_expr(expr, f);
+                }
+            }
+            selection
+                .iter_mut()
+                .chain(group_by)
+                .chain(having)
+                .for_each(|expr| visit_expr(expr, f));
+        }
+        SetExpr::Values(Values(rows)) => rows
+            .iter_mut()
+            .flatten()
+            .for_each(|expr| visit_expr(expr, f)),
+        SetExpr::SetOperation { left, right, .. } => {
+            visit_set_expr(left, f);
+            visit_set_expr(right, f);
+        }
+    }
+}
+
+fn visit_table_factor(table_factor: &mut TableFactor, f: &mut impl FnMut(&mut TableFactor)) {
+    match table_factor {
+        TableFactor::Derived { subquery, .. } => visit_query(subquery, f),
+        TableFactor::Recursive {
+            anchor, recursive, ..
+        } => {
+            visit_query(anchor, f);
+            visit_query(recursive, f);
+        }
+        TableFactor::Table { .. }
+        | TableFactor::Series { .. }
+        | TableFactor::Fake { .. }
+        | TableFactor::Dictionary { .. } => {}
+    }
+
+    f(table_factor);
+}
+
+fn visit_expr(expr: &mut Expr, f: &mut impl FnMut(&mut TableFactor)) {
+    match expr {
+        Expr::Subquery(query)
+        | Expr::Exists {
+            subquery: query, ..
+        } => visit_query(query, f),
+        Expr::InSubquery { expr, subquery, .. } => {
+            visit_expr(expr, f);
+            visit_query(subquery, f);
+        }
+        Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::UnaryOp { expr, .. }
+        | Expr::Nested(expr)
+        | Expr::Interval { expr, .. } => visit_expr(expr, f),
+        Expr::Between {
+            expr, low, high, ..
+        } => {
+            visit_expr(expr, f);
+            visit_expr(low, f);
+            visit_expr(high, f);
+        }
+        Expr::Like { expr, pattern, .. } | Expr::ILike { expr, pattern, .. } => {
+            visit_expr(expr, f);
+            visit_expr(pattern, f);
+        }
+        Expr::BinaryOp { left, right, .. } => {
+            visit_expr(left, f);
+            visit_expr(right, f);
+        }
+        Expr::InList { expr, list, .. } => {
+            visit_expr(expr, f);
+            list.iter_mut().for_each(|expr| visit_expr(expr, f));
+        }
+        Expr::Case {
+            operand,
+            when_then,
+            else_result,
+        } => {
+            operand
+                .iter_mut()
+                .chain(else_result)
+                .for_each(|expr| visit_expr(expr, f));
+            for (when, then) in when_then {
+                visit_expr(when, f);
+                visit_expr(then, f);
+            }
+        }
+        Expr::ArrayIndex { obj, indexes } => {
+            visit_expr(obj, f);
+            indexes.iter_mut().for_each(|expr| visit_expr(expr, f));
+        }
+        Expr::Array { elem } => elem.iter_mut().for_each(|expr| visit_expr(expr, f)),
+        Expr::Identifier(_)
+        | Expr::CompoundIdentifier { .. }
+        | Expr::Literal(_)
+        | Expr::TypedString { .. }
+        | Expr::Function(_)
+        | Expr::Aggregate(_) => {}
+    }
+}
@@ -0,0 +1,68 @@
+use {
+    super::{TranslateError, translate_idents, translate_object_name},
+    crate::{
+        ast::{Action, Privilege},
+        result::Result,
+    },
+    sqlparser::ast::{
+        Action as SqlAction, Expr as SqlExpr, GrantObjects as SqlGrantObjects, Ident as SqlIdent,
+        Password as SqlPassword, Privileges as SqlPrivileges, Value as SqlValue,
+    },
+};
+
+pub fn translate_password(sql_password: &SqlPassword) -> Result<Option<String>> {
+    match sql_password {
+        SqlPassword::Password(SqlExpr::Value(SqlValue::SingleQuotedString(password))) => {
+            Ok(Some(password.to_owned()))
+        }
+        SqlPassword::Password(expr) => {
+            Err(TranslateError::UnsupportedPassword(expr.to_string()).into())
+        }
+        SqlPassword::NullPassword => Ok(None),
+    }
+}
+
+pub fn translate_privileges(sql_privileges: &SqlPrivileges) -> Result<Vec<Privilege>> {
+    let columns =
+        |columns: &Option<Vec<SqlIdent>>| columns.as_ref().map(|columns| translate_idents(columns));
+
+    match sql_privileges {
+        SqlPrivileges::All { .. } => Ok([
+            Action::Select,
+            Action::Insert,
+            Action::Update,
+            Action::Delete,
+        ]
+        .into_iter()
+        .map(|action| Privilege {
+            action,
+            columns: None,
+        })
+        .collect()),
+        SqlPrivileges::Actions(actions) => actions
+            .iter()
+            .map(|sql_action| {
+                let (action, columns) = match sql_action {
+                    SqlAction::Select { columns: c } => (Action::Select, columns(c)),
+                    SqlAction::Insert { columns: c } => (Action::Insert, columns(c)),
+                    SqlAction::Update { columns: c } => (Action::Update, columns(c)),
+                    SqlAction::Delete => (Action::Delete, None),
+                    _ => {
+                        return Err(
+                            TranslateError::UnsupportedPrivilege(sql_action.to_string()).into()
+                        );
+                    }
+                };
+
+                Ok(Privilege { action, columns })
+            })
+            .collect(),
+    }
+}
+
+pub fn translate_grant_objects(sql_grant_objects: &SqlGrantObjects) -> Result<Vec<String>> {
+    match sql_grant_objects {
+        SqlGrantObjects::Tables(names) => names.iter().map(translate_object_name).collect(),
+        _ => Err(TranslateError::UnsupportedGrantObjects(sql_grant_objects.to_string()).into()),
+    }
+}
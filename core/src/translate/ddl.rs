@@ -1,16 +1,24 @@
 use {
     super::{
-        TranslateError, data_type::translate_data_type, expr::translate_expr, translate_object_name,
+        TranslateError, data_type::translate_data_type, expr::translate_expr, translate_idents,
+        translate_object_name,
     },
     crate::{
-        ast::{AlterTableOperation, ColumnDef, ColumnUniqueOption, OperateFunctionArg},
+        ast::{
+            AlterTableOperation, AstLiteral, CheckConstraint, ColumnDef, ColumnUniqueOption, Expr,
+            Function, OperateFunctionArg, OrderByExpr, ToSql, UniqueConstraint,
+        },
+        data::SchemaSequence,
         result::Result,
     },
     sqlparser::ast::{
+        AlterColumnOperation as SqlAlterColumnOperation,
         AlterTableOperation as SqlAlterTableOperation, ColumnDef as SqlColumnDef,
         ColumnOption as SqlColumnOption, ColumnOptionDef as SqlColumnOptionDef,
-        OperateFunctionArg as SqlOperateFunctionArg,
+        DataType as SqlDataType, OperateFunctionArg as SqlOperateFunctionArg,
+        TableConstraint as SqlTableConstraint,
     },
+    std::iter,
 };
 
 pub fn translate_alter_table_operation(
@@ -42,6 +50,14 @@ pub fn translate_alter_table_operation(
                 table_name: translate_object_name(table_name)?,
             })
         }
+        SqlAlterTableOperation::AlterColumn {
+            column_name,
+            op: SqlAlterColumnOperation::SetDataType { data_type, using },
+        } => Ok(AlterTableOperation::AlterColumnType {
+            column_name: column_name.value.to_owned(),
+            data_type: translate_data_type(data_type)?,
+            using: using.as_ref().map(translate_expr).transpose()?,
+        }),
         _ => Err(TranslateError::UnsupportedAlterTableOperation(
             sql_alter_table_operation.to_string(),
         )
@@ -94,6 +110,214 @@ pub fn translate_column_def(sql_column_def: &SqlColumnDef) -> Result<ColumnDef>
     })
 }
 
+/// Translates a column of `CREATE TABLE`, reading `SERIAL` as `INT NOT NULL` defaulting to
+/// the next value of a sequence named after the table and the column. `CHECK` options are left
+/// to [`translate_checks`].
+pub fn translate_table_column_def(
+    table_name: &str,
+    sql_column_def: &SqlColumnDef,
+) -> Result<ColumnDef> {
+    let sql_column_def = &SqlColumnDef {
+        options: sql_column_def
+            .options
+            .iter()
+            .filter(|option| !matches!(option.option, SqlColumnOption::Check(_)))
+            .cloned()
+            .collect(),
+        ..sql_column_def.clone()
+    };
+    let SqlColumnDef {
+        name,
+        data_type,
+        options,
+        ..
+    } = sql_column_def;
+
+    let serial = matches!(
+        data_type,
+        SqlDataType::Custom(type_name, _)
+            if type_name.to_string().eq_ignore_ascii_case("SERIAL")
+    );
+    if !serial {
+        return translate_column_def(sql_column_def);
+    }
+
+    if options
+        .iter()
+        .any(|option| matches!(option.option, SqlColumnOption::Default(_)))
+    {
+        return Err(TranslateError::SerialColumnWithDefault(name.value.to_owned()).into());
+    }
+
+    let column_def = translate_column_def(&SqlColumnDef {
+        data_type: SqlDataType::Int(None),
+        ..sql_column_def.clone()
+    })?;
+    let sequence_name = SchemaSequence::serial_name(table_name, &column_def.name);
+    let default = Function::NextVal(Expr::Literal(AstLiteral::QuotedString(sequence_name)));
+
+    Ok(ColumnDef {
+        nullable: false,
+        default: Some(Expr::Function(Box::new(default))),
+        ..column_def
+    })
+}
+
+/// Marks the columns of the `PRIMARY KEY (...)` constraint of `CREATE TABLE` as its primary key,
+/// each of them `NOT NULL`. More than one column makes a composite primary key, which keys the
+/// rows by the values of its columns together.
+pub fn translate_primary_key(
+    table_name: &str,
+    column_defs: &mut [ColumnDef],
+    constraints: &[SqlTableConstraint],
+) -> Result<()> {
+    let mut primary_keys = constraints
+        .iter()
+        .filter_map(|constraint| match constraint {
+            SqlTableConstraint::PrimaryKey { columns, .. } => Some(columns),
+            _ => None,
+        });
+    let Some(columns) = primary_keys.next() else {
+        return Ok(());
+    };
+
+    let column_primary_key = column_defs.iter().any(|column_def| {
+        matches!(
+            column_def.unique,
+            Some(ColumnUniqueOption { is_primary: true })
+        )
+    });
+    if column_primary_key || primary_keys.next().is_some() {
+        return Err(TranslateError::MultiplePrimaryKeys(table_name.to_owned()).into());
+    }
+
+    for column in columns {
+        let column_def = column_defs
+            .iter_mut()
+            .find(|column_def| column_def.name == column.value)
+            .ok_or_else(|| TranslateError::PrimaryKeyColumnNotFound(column.value.clone()))?;
+
+        column_def.nullable = false;
+        column_def.unique = Some(ColumnUniqueOption { is_primary: true });
+    }
+
+    Ok(())
+}
+
+/// Translates the `CHECK` constraints of `CREATE TABLE`, those of the columns first. Unnamed
+/// ones are named `{table}_{column}_check` or `{table}_check` as PostgreSQL does, numbered
+/// from 1 when the name is taken.
+pub fn translate_checks(
+    table_name: &str,
+    columns: &[SqlColumnDef],
+    constraints: &[SqlTableConstraint],
+) -> Result<Vec<CheckConstraint>> {
+    let column_checks = columns.iter().flat_map(|column_def| {
+        column_def
+            .options
+            .iter()
+            .filter_map(|SqlColumnOptionDef { name, option }| match option {
+                SqlColumnOption::Check(expr) => Some((
+                    name,
+                    format!("{table_name}_{}_check", column_def.name.value),
+                    expr,
+                )),
+                _ => None,
+            })
+    });
+    let table_checks = constraints
+        .iter()
+        .filter_map(|constraint| match constraint {
+            SqlTableConstraint::Check { name, expr } => {
+                Some((name, format!("{table_name}_check"), expr.as_ref()))
+            }
+            _ => None,
+        });
+
+    let mut checks: Vec<CheckConstraint> = Vec::new();
+    for (name, default_name, expr) in column_checks.chain(table_checks) {
+        let taken = |name: &str| checks.iter().any(|check| check.name == name);
+        let name = match name {
+            Some(name) if taken(&name.value) => {
+                return Err(TranslateError::DuplicateCheckConstraint(name.value.clone()).into());
+            }
+            Some(name) => name.value.clone(),
+            None => iter::once(default_name.clone())
+                .chain((1..).map(|i| format!("{default_name}{i}")))
+                .find(|name| !taken(name))
+                .unwrap_or(default_name),
+        };
+
+        checks.push(CheckConstraint {
+            name,
+            expr: translate_expr(expr)?,
+        });
+    }
+
+    Ok(checks)
+}
+
+/// Translates the table-level `UNIQUE` constraints of `CREATE TABLE`. Unnamed ones are named
+/// `{table}_{columns}_key` as PostgreSQL does, numbered from 1 when the name is taken.
+pub fn translate_uniques(
+    table_name: &str,
+    constraints: &[SqlTableConstraint],
+) -> Result<Vec<UniqueConstraint>> {
+    let mut uniques: Vec<UniqueConstraint> = Vec::new();
+    for constraint in constraints {
+        let SqlTableConstraint::Unique { name, columns, .. } = constraint else {
+            continue;
+        };
+
+        let columns = translate_idents(columns);
+        let taken = |name: &str| uniques.iter().any(|unique| unique.name == name);
+        let name = match name {
+            Some(name) if taken(&name.value) => {
+                return Err(TranslateError::DuplicateUniqueConstraint(name.value.clone()).into());
+            }
+            Some(name) => name.value.clone(),
+            None => {
+                let default_name = format!("{table_name}_{}_key", columns.join("_"));
+
+                iter::once(default_name.clone())
+                    .chain((1..).map(|i| format!("{default_name}{i}")))
+                    .find(|name| !taken(name))
+                    .unwrap_or(default_name)
+            }
+        };
+
+        uniques.push(UniqueConstraint { name, columns });
+    }
+
+    Ok(uniques)
+}
+
+/// Names an unnamed index `{table}_{columns}_idx` as PostgreSQL does, taking the name of the
+/// function for a column of a function call, and `expr` for other expressions.
+pub fn translate_index_name(table_name: &str, columns: &[OrderByExpr]) -> String {
+    fn column_name(expr: &Expr) -> String {
+        match expr {
+            Expr::Nested(expr) => column_name(expr),
+            Expr::Identifier(column) => column.to_owned(),
+            Expr::Function(function) => function
+                .to_sql()
+                .split('(')
+                .next()
+                .unwrap_or_default()
+                .to_lowercase(),
+            _ => "expr".to_owned(),
+        }
+    }
+
+    let columns = columns
+        .iter()
+        .map(|OrderByExpr { expr, .. }| column_name(expr))
+        .collect::<Vec<_>>()
+        .join("_");
+
+    format!("{table_name}_{columns}_idx")
+}
+
 pub fn translate_operate_function_arg(arg: &SqlOperateFunctionArg) -> Result<OperateFunctionArg> {
     let name = arg
         .name
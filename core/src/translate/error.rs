@@ -8,9 +8,6 @@ pub enum TranslateError {
     #[error("unimplemented - SELECT DISTINCT ON is not supported")]
     SelectDistinctOnNotSupported,
 
-    #[error("unimplemented - composite index is not supported")]
-    CompositeIndexNotSupported,
-
     #[error("unimplemented - join on update not supported")]
     JoinOnUpdateNotSupported,
 
@@ -65,9 +62,6 @@ pub enum TranslateError {
     #[error("empty function body is not supported")]
     UnsupportedEmptyFunctionBody,
 
-    #[error("unsupported unnamed index")]
-    UnsupportedUnnamedIndex,
-
     #[error(
         "unsupported trim chars: expected: `TRIM((BOTH | LEADING | TRAILING) <text> FROM <expr>)`, got: `TRIM(<expr> [<chars>, ..])` syntax"
     )]
@@ -99,6 +93,21 @@ pub enum TranslateError {
     #[error("qualified wildcard is not supported - COUNT({0})")]
     QualifiedWildcardInCountNotSupported(String),
 
+    #[error("window function requires OVER clause: {0}")]
+    WindowFunctionWithoutOver(String),
+
+    #[error("function does not support OVER clause: {0}")]
+    UnsupportedWindowFunction(String),
+
+    #[error("DISTINCT is not supported in window function: {0}")]
+    DistinctInWindowFunctionNotSupported(String),
+
+    #[error("named window is not supported: {0}")]
+    NamedWindowNotSupported(String),
+
+    #[error("window frame is not supported: {0}")]
+    WindowFrameNotSupported(String),
+
     #[error("order by - NULLS (FIRST | LAST) is not supported")]
     OrderByNullsFirstOrLastNotSupported,
 
@@ -108,6 +117,9 @@ pub enum TranslateError {
     #[error("unsupported SHOW VARIABLE statement: {0}")]
     UnsupportedShowVariableStatement(String),
 
+    #[error("EXPLAIN supports queries only: {0}")]
+    UnsupportedExplainStatement(String),
+
     #[error("unsupported statement: {0}")]
     UnsupportedStatement(String),
 
@@ -159,6 +171,12 @@ pub enum TranslateError {
     #[error("Series should have size")]
     LackOfArgs,
 
+    #[error("FAKE should have size, columns as a string literal and an optional seed")]
+    FakeArgsWrong,
+
+    #[error("invalid FAKE column: {0}")]
+    InvalidFakeColumn(String),
+
     #[error("unreachable empty object")]
     UnreachableEmptyObject,
 
@@ -174,6 +192,9 @@ pub enum TranslateError {
     #[error("cannot create index with reserved name: {0}")]
     ReservedIndexName(String),
 
+    #[error("unsupported index method: {0}")]
+    UnsupportedIndexMethod(String),
+
     #[error("cannot drop primary index")]
     CannotDropPrimary,
 
@@ -182,4 +203,67 @@ pub enum TranslateError {
 
     #[error("unsupported constraint: {0}")]
     UnsupportedConstraint(String),
+
+    #[error("unsupported CREATE USER option: {0}")]
+    UnsupportedCreateUserOption(String),
+
+    #[error("password must be a string literal: {0}")]
+    UnsupportedPassword(String),
+
+    #[error("too many users in CREATE USER, expected exactly one")]
+    TooManyUsersInCreateUser,
+
+    #[error("unsupported privilege: {0}")]
+    UnsupportedPrivilege(String),
+
+    #[error("privileges can only be granted on tables: {0}")]
+    UnsupportedGrantObjects(String),
+
+    #[error("unsupported GRANT option: {0}")]
+    UnsupportedGrantOption(String),
+
+    #[error("unsupported SET variable: {0}")]
+    UnsupportedSetVariable(String),
+
+    #[error("invalid value for SET {name}: {value}")]
+    InvalidSetVariableValue { name: String, value: String },
+
+    #[error("WITH query name specified more than once: {0}")]
+    DuplicateCteName(String),
+
+    #[error("unsupported trigger timing: {0}")]
+    UnsupportedTriggerTiming(String),
+
+    #[error("unsupported trigger event: {0}")]
+    UnsupportedTriggerEvent(String),
+
+    #[error("trigger body can only contain INSERT, UPDATE and DELETE statements: {0}")]
+    UnsupportedTriggerStatement(String),
+
+    #[error("unsupported sequence option: {0}")]
+    UnsupportedSequenceOption(String),
+
+    #[error("sequence option requires an integer: {0}")]
+    InvalidSequenceValue(String),
+
+    #[error("sequence increment must not be zero")]
+    ZeroSequenceIncrement,
+
+    #[error("SERIAL column cannot have a default: {0}")]
+    SerialColumnWithDefault(String),
+
+    #[error("duplicate CHECK constraint name: {0}")]
+    DuplicateCheckConstraint(String),
+
+    #[error("unsupported INSERT conflict clause: {0}")]
+    UnsupportedOnInsert(String),
+
+    #[error("multiple primary keys for table '{0}' are not allowed")]
+    MultiplePrimaryKeys(String),
+
+    #[error("primary key column not found: {0}")]
+    PrimaryKeyColumnNotFound(String),
+
+    #[error("duplicate UNIQUE constraint name: {0}")]
+    DuplicateUniqueConstraint(String),
 }
@@ -0,0 +1,156 @@
+use {
+    super::TranslateError,
+    crate::{
+        ast::{FakeColumn, FakeKind},
+        result::Result,
+    },
+    chrono::NaiveDate,
+    std::collections::HashSet,
+};
+
+/// Translates the columns of `FAKE`, given as `<name> <kind>[(<min>, <max>)]` separated by
+/// commas, e.g. `id SEQUENCE, name NAME, age INTEGER(18, 65), joined DATE(2020-01-01, 2024-12-31)`.
+pub fn translate_fake_columns(columns: &str) -> Result<Vec<FakeColumn>> {
+    let mut names = HashSet::new();
+
+    split_columns(columns)
+        .into_iter()
+        .map(|column| {
+            let column = column.trim();
+            let invalid = || TranslateError::InvalidFakeColumn(column.to_owned());
+
+            let (name, kind) = column.split_once(char::is_whitespace).ok_or_else(invalid)?;
+            let kind = translate_fake_kind(kind.trim()).ok_or_else(invalid)?;
+            if !names.insert(name) {
+                return Err(invalid().into());
+            }
+
+            Ok(FakeColumn {
+                name: name.to_owned(),
+                kind,
+            })
+        })
+        .collect()
+}
+
+/// Splits at the commas outside of parentheses.
+fn split_columns(columns: &str) -> Vec<&str> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut split = Vec::new();
+
+    for (i, c) in columns.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(&columns[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    split.push(&columns[start..]);
+    split
+}
+
+fn translate_fake_kind(kind: &str) -> Option<FakeKind> {
+    let (name, args) = match kind.split_once('(') {
+        Some((name, args)) => {
+            let args = args.strip_suffix(')')?;
+            let (min, max) = args.split_once(',')?;
+
+            (name.trim(), Some((min.trim(), max.trim())))
+        }
+        None => (kind, None),
+    };
+
+    let range = |default: (i64, i64)| -> Option<(i64, i64)> {
+        let (min, max) = match args {
+            Some((min, max)) => (min.parse().ok()?, max.parse().ok()?),
+            None => default,
+        };
+
+        (min <= max).then_some((min, max))
+    };
+
+    match (name.to_uppercase().as_str(), args) {
+        ("SEQUENCE", None) => Some(FakeKind::Sequence),
+        ("NAME", None) => Some(FakeKind::Name),
+        ("EMAIL", None) => Some(FakeKind::Email),
+        ("BOOLEAN", None) => Some(FakeKind::Boolean),
+        ("UUID", None) => Some(FakeKind::Uuid),
+        ("INTEGER" | "INT", _) => range((0, 100)).map(|(min, max)| FakeKind::Integer { min, max }),
+        ("FLOAT", _) => range((0, 1)).map(|(min, max)| FakeKind::Float { min, max }),
+        ("DATE", _) => {
+            let date = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+            let (from, to) = match args {
+                Some((from, to)) => (date(from)?, date(to)?),
+                None => (date("2000-01-01")?, date("2029-12-31")?),
+            };
+
+            (from <= to).then_some(FakeKind::Date { from, to })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::translate_fake_columns,
+        crate::{
+            ast::{FakeColumn, FakeKind},
+            translate::TranslateError,
+        },
+        chrono::NaiveDate,
+    };
+
+    #[test]
+    fn fake_columns() {
+        let column = |name: &str, kind| FakeColumn {
+            name: name.to_owned(),
+            kind,
+        };
+        let date = |date| NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+
+        assert_eq!(
+            translate_fake_columns(
+                "id SEQUENCE, name name, age INTEGER(18, 65), score FLOAT, joined DATE(2020-01-01, 2024-12-31)"
+            ),
+            Ok(vec![
+                column("id", FakeKind::Sequence),
+                column("name", FakeKind::Name),
+                column("age", FakeKind::Integer { min: 18, max: 65 }),
+                column("score", FakeKind::Float { min: 0, max: 1 }),
+                column(
+                    "joined",
+                    FakeKind::Date {
+                        from: date("2020-01-01"),
+                        to: date("2024-12-31")
+                    }
+                ),
+            ])
+        );
+
+        for (columns, invalid) in [
+            ("id", "id"),
+            ("id SEQUENCE, id UUID", "id UUID"),
+            ("age INTEGER(65, 18)", "age INTEGER(65, 18)"),
+            ("age INTEGER(18)", "age INTEGER(18)"),
+            ("email EMAIL(1, 2)", "email EMAIL(1, 2)"),
+            (
+                "joined DATE(2020-01-01, tomorrow)",
+                "joined DATE(2020-01-01, tomorrow)",
+            ),
+            ("id SEQUENCE,", ""),
+            ("color COLOR", "color COLOR"),
+        ] {
+            assert_eq!(
+                translate_fake_columns(columns),
+                Err(TranslateError::InvalidFakeColumn(invalid.to_owned()).into())
+            );
+        }
+    }
+}
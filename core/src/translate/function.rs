@@ -2,11 +2,11 @@ use {
     super::{
         TranslateError,
         ast_literal::{translate_datetime_field, translate_trim_where_field},
-        expr::translate_expr,
+        expr::{translate_expr, translate_order_by_expr},
         translate_data_type, translate_object_name,
     },
     crate::{
-        ast::{Aggregate, CountArgExpr, Expr, Function},
+        ast::{Aggregate, AggregateFunction, CountArgExpr, Expr, Function, WindowSpec},
         result::Result,
     },
     sqlparser::ast::{
@@ -14,7 +14,8 @@ use {
         DateTimeField as SqlDateTimeField, DuplicateTreatment as SqlDuplicateTreatment,
         Expr as SqlExpr, Function as SqlFunction, FunctionArg as SqlFunctionArg,
         FunctionArgExpr as SqlFunctionArgExpr, FunctionArguments as SqlFunctionArguments,
-        TrimWhereField as SqlTrimWhereField,
+        TrimWhereField as SqlTrimWhereField, WindowSpec as SqlWindowSpec,
+        WindowType as SqlWindowType,
     },
 };
 
@@ -194,7 +195,78 @@ pub fn translate_function_arg_exprs(
         .collect::<Result<Vec<_>>>()
 }
 
+fn translate_window(over: &SqlWindowType) -> Result<WindowSpec> {
+    let SqlWindowSpec {
+        window_name,
+        partition_by,
+        order_by,
+        window_frame,
+    } = match over {
+        SqlWindowType::WindowSpec(window_spec) => window_spec,
+        SqlWindowType::NamedWindow(name) => {
+            return Err(TranslateError::NamedWindowNotSupported(name.value.to_owned()).into());
+        }
+    };
+
+    if let Some(name) = window_name {
+        return Err(TranslateError::NamedWindowNotSupported(name.value.to_owned()).into());
+    } else if let Some(window_frame) = window_frame {
+        return Err(TranslateError::WindowFrameNotSupported(window_frame.units.to_string()).into());
+    }
+
+    Ok(WindowSpec {
+        partition_by: partition_by
+            .iter()
+            .map(translate_expr)
+            .collect::<Result<_>>()?,
+        order_by: order_by
+            .iter()
+            .map(translate_order_by_expr)
+            .collect::<Result<_>>()?,
+    })
+}
+
 pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
+    let over = sql_function
+        .over
+        .as_ref()
+        .map(translate_window)
+        .transpose()?;
+    let expr = translate_function_call(sql_function)?;
+
+    let name = || translate_object_name(&sql_function.name).map(|name| name.to_uppercase());
+    match (expr, over) {
+        (Expr::Aggregate(aggregate), None) if aggregate.is_window_function() => {
+            Err(TranslateError::WindowFunctionWithoutOver(name()?).into())
+        }
+        (expr, None) => Ok(expr),
+        (Expr::Aggregate(aggregate), Some(_)) if aggregate.distinct => {
+            Err(TranslateError::DistinctInWindowFunctionNotSupported(name()?).into())
+        }
+        (Expr::Aggregate(aggregate), Some(over)) => Ok(Expr::Aggregate(Box::new(Aggregate {
+            over: Some(over),
+            ..*aggregate
+        }))),
+        (_, Some(_)) => Err(TranslateError::UnsupportedWindowFunction(name()?).into()),
+    }
+}
+
+fn translate_lag_or_lead<T: FnOnce(Expr, Option<Expr>, Option<Expr>) -> AggregateFunction>(
+    func: T,
+    args: Vec<&SqlExpr>,
+    name: String,
+) -> Result<Expr> {
+    check_len_range(name, args.len(), 1, 3)?;
+
+    let expr = translate_expr(args[0])?;
+    let offset = args.get(1).map(|arg| translate_expr(arg)).transpose()?;
+    let default = args.get(2).map(|arg| translate_expr(arg)).transpose()?;
+    let func = func(expr, offset, default);
+
+    Ok(Expr::Aggregate(Box::new(Aggregate::new(func, false))))
+}
+
+fn translate_function_call(sql_function: &SqlFunction) -> Result<Expr> {
     let SqlFunction { name, args, .. } = sql_function;
     let name = translate_object_name(name)?.to_uppercase();
     let (args, distinct) = match args {
@@ -248,6 +320,34 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
         "AVG" => translate_aggregate_one_arg(Aggregate::avg, args, name, distinct),
         "VARIANCE" => translate_aggregate_one_arg(Aggregate::variance, args, name, distinct),
         "STDEV" => translate_aggregate_one_arg(Aggregate::stdev, args, name, distinct),
+        "ROW_NUMBER" | "RANK" | "DENSE_RANK" => {
+            let func = match name.as_str() {
+                "ROW_NUMBER" => AggregateFunction::RowNumber,
+                "RANK" => AggregateFunction::Rank,
+                _ => AggregateFunction::DenseRank,
+            };
+            check_len(name, args.len(), 0)?;
+
+            Ok(Expr::Aggregate(Box::new(Aggregate::new(func, false))))
+        }
+        "LAG" => translate_lag_or_lead(
+            |expr, offset, default| AggregateFunction::Lag {
+                expr,
+                offset,
+                default,
+            },
+            args,
+            name,
+        ),
+        "LEAD" => translate_lag_or_lead(
+            |expr, offset, default| AggregateFunction::Lead {
+                expr,
+                offset,
+                default,
+            },
+            args,
+            name,
+        ),
         "COALESCE" => {
             let exprs = args
                 .into_iter()
@@ -714,6 +814,86 @@ pub fn translate_function(sql_function: &SqlFunction) -> Result<Expr> {
             let list = translate_expr(args[0])?;
             Ok(Expr::Function(Box::new(Function::Dedup(list))))
         }
+        "MATCH" => {
+            check_len_min(name, args.len(), 2)?;
+            let query = translate_expr(args[args.len() - 1])?;
+            let exprs = args[..args.len() - 1]
+                .iter()
+                .copied()
+                .map(translate_expr)
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Expr::Function(Box::new(Function::Match { exprs, query })))
+        }
+        "REGEXP_MATCHES" => {
+            check_len(name, args.len(), 2)?;
+            let expr = translate_expr(args[0])?;
+            let pattern = translate_expr(args[1])?;
+            Ok(Expr::Function(Box::new(Function::RegexpMatches {
+                expr,
+                pattern,
+            })))
+        }
+        "REGEXP_REPLACE" => {
+            check_len(name, args.len(), 3)?;
+            let expr = translate_expr(args[0])?;
+            let pattern = translate_expr(args[1])?;
+            let replacement = translate_expr(args[2])?;
+            Ok(Expr::Function(Box::new(Function::RegexpReplace {
+                expr,
+                pattern,
+                replacement,
+            })))
+        }
+        "REGEXP_EXTRACT" => {
+            check_len_range(name, args.len(), 2, 3)?;
+            let expr = translate_expr(args[0])?;
+            let pattern = translate_expr(args[1])?;
+            let group = (args.len() > 2)
+                .then(|| translate_expr(args[2]))
+                .transpose()?;
+            Ok(Expr::Function(Box::new(Function::RegexpExtract {
+                expr,
+                pattern,
+                group,
+            })))
+        }
+        "JSON_EXTRACT" => {
+            check_len(name, args.len(), 2)?;
+            let expr = translate_expr(args[0])?;
+            let path = translate_expr(args[1])?;
+            Ok(Expr::Function(Box::new(Function::JsonExtract {
+                expr,
+                path,
+            })))
+        }
+        "JSON_SET" => {
+            check_len(name, args.len(), 3)?;
+            let expr = translate_expr(args[0])?;
+            let path = translate_expr(args[1])?;
+            let value = translate_expr(args[2])?;
+            Ok(Expr::Function(Box::new(Function::JsonSet {
+                expr,
+                path,
+                value,
+            })))
+        }
+        "JSON_REMOVE" => {
+            check_len(name, args.len(), 2)?;
+            let expr = translate_expr(args[0])?;
+            let path = translate_expr(args[1])?;
+            Ok(Expr::Function(Box::new(Function::JsonRemove {
+                expr,
+                path,
+            })))
+        }
+        "NEXTVAL" => translate_function_one_arg(Function::NextVal, args, name),
+        "CURRVAL" => translate_function_one_arg(Function::CurrVal, args, name),
+        "SETVAL" => {
+            check_len(name, args.len(), 2)?;
+            let name = translate_expr(args[0])?;
+            let value = translate_expr(args[1])?;
+            Ok(Expr::Function(Box::new(Function::SetVal { name, value })))
+        }
         _ => {
             let exprs = args
                 .into_iter()
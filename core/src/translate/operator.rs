@@ -40,6 +40,9 @@ pub fn translate_binary_operator(
         SqlBinaryOperator::BitwiseAnd => Ok(BinaryOperator::BitwiseAnd),
         SqlBinaryOperator::PGBitwiseShiftLeft => Ok(BinaryOperator::BitwiseShiftLeft),
         SqlBinaryOperator::PGBitwiseShiftRight => Ok(BinaryOperator::BitwiseShiftRight),
+        SqlBinaryOperator::PGRegexMatch => Ok(BinaryOperator::RegexMatch),
+        SqlBinaryOperator::Arrow => Ok(BinaryOperator::Arrow),
+        SqlBinaryOperator::LongArrow => Ok(BinaryOperator::LongArrow),
         _ => Err(TranslateError::UnsupportedBinaryOperator(sql_binary_operator.to_string()).into()),
     }
 }
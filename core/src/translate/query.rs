@@ -1,12 +1,14 @@
 use {
     super::{
-        TranslateError, function::translate_function_arg_exprs, translate_expr, translate_idents,
+        TranslateError, cte::translate_with, fake::translate_fake_columns,
+        function::translate_function_arg_exprs, translate_expr, translate_idents,
         translate_object_name, translate_order_by_expr,
     },
     crate::{
         ast::{
-            AstLiteral, Dictionary, Expr, Join, JoinConstraint, JoinExecutor, JoinOperator, Query,
-            Select, SelectItem, SetExpr, TableAlias, TableFactor, TableWithJoins, Values,
+            AstLiteral, Dictionary, Expr, FakeColumn, Join, JoinConstraint, JoinExecutor,
+            JoinOperator, Query, Select, SelectItem, SetExpr, SetOperator, TableAlias, TableFactor,
+            TableWithJoins, Values,
         },
         result::Result,
     },
@@ -14,7 +16,8 @@ use {
         Distinct as SqlDistinct, Expr as SqlExpr, FunctionArg as SqlFunctionArg,
         GroupByExpr as SqlGroupByExpr, Join as SqlJoin, JoinConstraint as SqlJoinConstraint,
         JoinOperator as SqlJoinOperator, Query as SqlQuery, Select as SqlSelect,
-        SelectItem as SqlSelectItem, SetExpr as SqlSetExpr, TableAlias as SqlTableAlias,
+        SelectItem as SqlSelectItem, SetExpr as SqlSetExpr, SetOperator as SqlSetOperator,
+        SetQuantifier as SqlSetQuantifier, TableAlias as SqlTableAlias,
         TableFactor as SqlTableFactor, TableFunctionArgs as SqlTableFunctionArgs,
         TableWithJoins as SqlTableWithJoins,
     },
@@ -22,6 +25,7 @@ use {
 
 pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
     let SqlQuery {
+        with,
         body,
         order_by,
         limit,
@@ -40,15 +44,20 @@ pub fn translate_query(sql_query: &SqlQuery) -> Result<Query> {
         .map(|offset| translate_expr(&offset.value))
         .transpose()?;
 
-    Ok(Query {
+    let query = Query {
         body,
         order_by,
         limit,
         offset,
-    })
+    };
+
+    match with {
+        Some(with) => translate_with(with, query),
+        None => Ok(query),
+    }
 }
 
-fn translate_set_expr(sql_set_expr: &SqlSetExpr) -> Result<SetExpr> {
+pub(super) fn translate_set_expr(sql_set_expr: &SqlSetExpr) -> Result<SetExpr> {
     match sql_set_expr {
         SqlSetExpr::Select(select) => translate_select(select).map(Box::new).map(SetExpr::Select),
         SqlSetExpr::Values(sqlparser::ast::Values { rows, .. }) => rows
@@ -57,6 +66,43 @@ fn translate_set_expr(sql_set_expr: &SqlSetExpr) -> Result<SetExpr> {
             .collect::<Result<_>>()
             .map(Values)
             .map(SetExpr::Values),
+        SqlSetExpr::SetOperation {
+            op,
+            set_quantifier,
+            left,
+            right,
+        } => {
+            let op = match op {
+                SqlSetOperator::Union => SetOperator::Union,
+                SqlSetOperator::Except => SetOperator::Except,
+                SqlSetOperator::Intersect => SetOperator::Intersect,
+            };
+            let all = match set_quantifier {
+                SqlSetQuantifier::All => true,
+                SqlSetQuantifier::None | SqlSetQuantifier::Distinct => false,
+                _ => {
+                    return Err(
+                        TranslateError::UnsupportedQuerySetExpr(sql_set_expr.to_string()).into(),
+                    );
+                }
+            };
+
+            Ok(SetExpr::SetOperation {
+                op,
+                all,
+                left: translate_set_expr(left).map(Box::new)?,
+                right: translate_set_expr(right).map(Box::new)?,
+            })
+        }
+        SqlSetExpr::Query(sql_query) => match translate_query(sql_query)? {
+            Query {
+                body,
+                order_by,
+                limit: None,
+                offset: None,
+            } if order_by.is_empty() => Ok(body),
+            _ => Err(TranslateError::UnsupportedQuerySetExpr(sql_set_expr.to_string()).into()),
+        },
         _ => Err(TranslateError::UnsupportedQuerySetExpr(sql_set_expr.to_string()).into()),
     }
 }
@@ -182,6 +228,32 @@ fn translate_table_factor(sql_table_factor: &SqlTableFactor) -> Result<TableFact
             None => Err(TranslateError::LackOfArgs.into()),
         }
     };
+    let translate_fake_args =
+        |args: &Vec<SqlFunctionArg>| -> Result<(Expr, Vec<FakeColumn>, Option<Expr>)> {
+            let function_arg_exprs = args
+                .iter()
+                .map(|arg| match arg {
+                    SqlFunctionArg::Named { .. } => {
+                        Err(TranslateError::NamedFunctionArgNotSupported.into())
+                    }
+                    SqlFunctionArg::Unnamed(arg_expr) => Ok(arg_expr),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let mut exprs = translate_function_arg_exprs(function_arg_exprs)?
+                .into_iter()
+                .map(translate_expr);
+
+            match (exprs.next(), exprs.next(), exprs.next(), exprs.next()) {
+                (Some(size), Some(columns), seed, None) => {
+                    let Expr::Literal(AstLiteral::QuotedString(columns)) = columns? else {
+                        return Err(TranslateError::FakeArgsWrong.into());
+                    };
+
+                    Ok((size?, translate_fake_columns(&columns)?, seed.transpose()?))
+                }
+                _ => Err(TranslateError::FakeArgsWrong.into()),
+            }
+        };
 
     match sql_table_factor {
         SqlTableFactor::Table {
@@ -195,6 +267,16 @@ fn translate_table_factor(sql_table_factor: &SqlTableFactor) -> Result<TableFact
                     alias: alias_or_name(alias, object_name),
                     size: translate_table_args(args)?,
                 }),
+                ("FAKE", Some(SqlTableFunctionArgs { args, .. })) => {
+                    let (size, columns, seed) = translate_fake_args(args)?;
+
+                    Ok(TableFactor::Fake {
+                        alias: alias_or_name(alias, object_name),
+                        size,
+                        columns,
+                        seed,
+                    })
+                }
                 ("GLUE_OBJECTS", _) => Ok(TableFactor::Dictionary {
                     dict: Dictionary::GlueObjects,
                     alias: alias_or_name(alias, object_name),
@@ -271,6 +353,7 @@ fn translate_join(sql_join: &SqlJoin) -> Result<Join> {
         SqlJoinOperator::LeftOuter(sql_join_constraint) => {
             translate_constraint(sql_join_constraint).map(JoinOperator::LeftOuter)
         }
+        SqlJoinOperator::CrossJoin => Ok(JoinOperator::Inner(JoinConstraint::None)),
         _ => Err(TranslateError::UnsupportedJoinOperator(format!("{sql_join_operator:?}")).into()),
     }?;
 
@@ -0,0 +1,27 @@
+use {
+    gluesql_core::store::DataRow,
+    serde::{Deserialize, Serialize},
+};
+
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Line of a dump, which is written as JSON Lines.
+///
+/// The header comes first, followed by the schemas of every table and then by their rows.
+/// Rows of different tables may be interleaved, as tables are exported concurrently.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Entry {
+    Header {
+        version: u32,
+    },
+    /// `CREATE TABLE` statement of the table, followed by its `CREATE INDEX` statements.
+    Schema {
+        table_name: String,
+        ddl: String,
+    },
+    Row {
+        table_name: String,
+        row: DataRow,
+    },
+}
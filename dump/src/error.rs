@@ -0,0 +1,27 @@
+use {gluesql_core::error::Error as GlueError, thiserror::Error as ThisError};
+
+pub type Result<T, E = DumpError> = std::result::Result<T, E>;
+
+#[derive(ThisError, Debug)]
+pub enum DumpError {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Glue(#[from] GlueError),
+
+    #[error("invalid dump entry on line {line}: {message}")]
+    InvalidEntry { line: usize, message: String },
+
+    #[error("not a GlueSQL dump, or written by an unsupported version")]
+    UnsupportedFormat,
+
+    #[error("table already exists: {0}")]
+    TableAlreadyExists(String),
+
+    #[error("rows of table {0} come before its schema")]
+    SchemaNotFound(String),
+
+    #[error("row of table {0} has no primary key value")]
+    MissingPrimaryKey(String),
+}
@@ -0,0 +1,348 @@
+#![deny(clippy::str_to_string)]
+
+mod entry;
+mod error;
+
+pub use error::{DumpError, Result};
+
+use {
+    entry::{Entry, FORMAT_VERSION},
+    futures::stream::{self, StreamExt, TryStreamExt},
+    gluesql_core::{
+        ast::{ColumnDef, ColumnUniqueOption, OrderByExpr},
+        data::{Key, Schema, SchemaIndex, SchemaIndexOrd},
+        error::Error as GlueError,
+        store::{DataRow, GStore, GStoreMut},
+    },
+    std::{
+        collections::{BTreeSet, HashMap},
+        io::{BufRead, Write},
+        pin::pin,
+    },
+};
+
+/// Rows restored with a single storage call.
+const BATCH_SIZE: usize = 1000;
+
+/// Logical backup of the tables of a storage, restorable into any other storage.
+///
+/// [`Dump::dump`] writes the schemas and rows as JSON Lines, one entry per line, so that
+/// neither side holds a whole table in memory. [`Dump::restore`] reads them back, creating
+/// the indexes once the rows are in place.
+#[derive(Clone, Debug)]
+pub struct Dump {
+    included: BTreeSet<String>,
+    excluded: BTreeSet<String>,
+    parallelism: usize,
+}
+
+/// Number of tables and rows dumped or restored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub tables: usize,
+    pub rows: usize,
+}
+
+impl Default for Dump {
+    fn default() -> Self {
+        Self {
+            included: BTreeSet::new(),
+            excluded: BTreeSet::new(),
+            parallelism: 4,
+        }
+    }
+}
+
+impl Dump {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the dump and the restore to the included tables.
+    pub fn with_included_table(mut self, table_name: &str) -> Self {
+        self.included.insert(table_name.to_owned());
+        self
+    }
+
+    /// Leaves the table out of the dump and the restore.
+    pub fn with_excluded_table(mut self, table_name: &str) -> Self {
+        self.excluded.insert(table_name.to_owned());
+        self
+    }
+
+    /// Sets how many tables are exported at the same time, 4 by default.
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+
+    fn includes(&self, table_name: &str) -> bool {
+        (self.included.is_empty() || self.included.contains(table_name))
+            && !self.excluded.contains(table_name)
+    }
+
+    /// Writes the tables of `storage` to `writer`, reading them within a single transaction
+    /// when the storage supports one.
+    pub async fn dump<T: GStore + GStoreMut, W: Write>(
+        &self,
+        storage: &mut T,
+        mut writer: W,
+    ) -> Result<Summary> {
+        let autocommit = storage.begin(true).await?;
+        let result = self.write_entries(storage, &mut writer).await;
+
+        if autocommit {
+            storage.rollback().await?;
+        }
+
+        result
+    }
+
+    /// Creates the tables of the dump read from `reader` in `storage` and inserts their rows.
+    ///
+    /// Restoring into a table which already exists fails, and so does restoring indexes into
+    /// a storage without index support. Foreign keys are not checked while restoring.
+    pub async fn restore<T: GStore + GStoreMut, R: BufRead>(
+        &self,
+        storage: &mut T,
+        reader: R,
+    ) -> Result<Summary> {
+        let autocommit = storage.begin(true).await?;
+        let result = self.read_entries(storage, reader).await;
+
+        match (autocommit, &result) {
+            (false, _) => {}
+            (true, Ok(_)) => storage.commit().await?,
+            (true, Err(_)) => storage.rollback().await?,
+        }
+
+        result
+    }
+
+    async fn write_entries<T: GStore, W: Write>(
+        &self,
+        storage: &T,
+        writer: &mut W,
+    ) -> Result<Summary> {
+        write_entry(
+            writer,
+            &Entry::Header {
+                version: FORMAT_VERSION,
+            },
+        )?;
+
+        let schemas = storage
+            .fetch_all_schemas()
+            .await?
+            .into_iter()
+            .filter(|schema| self.includes(&schema.table_name))
+            .collect::<Vec<_>>();
+        for schema in &schemas {
+            let entry = Entry::Schema {
+                table_name: schema.table_name.clone(),
+                ddl: schema.to_ddl(),
+            };
+
+            write_entry(writer, &entry)?;
+        }
+
+        let rows = stream::iter(&schemas)
+            .then(|schema| async move {
+                let table_name = schema.table_name.as_str();
+                let rows = storage.scan_data(table_name).await?;
+
+                Ok::<_, GlueError>(rows.map_ok(move |(_, row)| (table_name, row)))
+            })
+            .try_flatten_unordered(self.parallelism);
+        let mut rows = pin!(rows);
+
+        let mut summary = Summary {
+            tables: schemas.len(),
+            rows: 0,
+        };
+        while let Some((table_name, row)) = rows.try_next().await? {
+            let entry = Entry::Row {
+                table_name: table_name.to_owned(),
+                row,
+            };
+
+            write_entry(writer, &entry)?;
+            summary.rows += 1;
+        }
+
+        writer.flush()?;
+
+        Ok(summary)
+    }
+
+    async fn read_entries<T: GStore + GStoreMut, R: BufRead>(
+        &self,
+        storage: &mut T,
+        reader: R,
+    ) -> Result<Summary> {
+        let mut lines = reader.lines();
+        let header = lines
+            .next()
+            .transpose()?
+            .and_then(|line| serde_json::from_str::<Entry>(&line).ok());
+        if !matches!(
+            header,
+            Some(Entry::Header {
+                version: FORMAT_VERSION
+            })
+        ) {
+            return Err(DumpError::UnsupportedFormat);
+        }
+
+        let mut tables = HashMap::new();
+        let mut summary = Summary::default();
+        for (i, line) in lines.enumerate() {
+            let invalid_entry = |message: String| DumpError::InvalidEntry {
+                line: i + 2,
+                message,
+            };
+
+            let entry = serde_json::from_str::<Entry>(&line?)
+                .map_err(|error| invalid_entry(error.to_string()))?;
+            match entry {
+                Entry::Header { .. } => {
+                    return Err(invalid_entry("header after the first line".to_owned()));
+                }
+                Entry::Schema { table_name, .. } | Entry::Row { table_name, .. }
+                    if !self.includes(&table_name) => {}
+                Entry::Schema { table_name, ddl } => {
+                    if storage.fetch_schema(&table_name).await?.is_some() {
+                        return Err(DumpError::TableAlreadyExists(table_name));
+                    }
+
+                    let schema = Schema::from_ddl(&ddl)?;
+                    let table = Table::new(&schema);
+                    let schema = Schema {
+                        indexes: Vec::new(),
+                        ..schema
+                    };
+
+                    storage.insert_schema(&schema).await?;
+                    tables.insert(table_name, table);
+                    summary.tables += 1;
+                }
+                Entry::Row { table_name, row } => {
+                    let table = tables
+                        .get_mut(&table_name)
+                        .ok_or_else(|| DumpError::SchemaNotFound(table_name.clone()))?;
+
+                    table.rows.push(row);
+                    summary.rows += 1;
+                    if table.rows.len() >= BATCH_SIZE {
+                        table.flush(storage, &table_name).await?;
+                    }
+                }
+            }
+        }
+
+        for (table_name, mut table) in tables {
+            table.flush(storage, &table_name).await?;
+            table.create_indexes(storage, &table_name).await?;
+        }
+
+        Ok(summary)
+    }
+}
+
+/// Table being restored, with the rows not inserted yet.
+struct Table {
+    /// Columns of the table when it has a primary key, to key its rows by
+    primary_key: Option<Vec<ColumnDef>>,
+    indexes: Vec<SchemaIndex>,
+    rows: Vec<DataRow>,
+}
+
+impl Table {
+    fn new(schema: &Schema) -> Self {
+        let primary_key = schema.column_defs.clone().filter(|column_defs| {
+            column_defs.iter().any(|column_def| {
+                column_def.unique == Some(ColumnUniqueOption { is_primary: true })
+            })
+        });
+
+        Self {
+            primary_key,
+            indexes: schema.indexes.clone(),
+            rows: Vec::new(),
+        }
+    }
+
+    async fn flush<T: GStore + GStoreMut>(
+        &mut self,
+        storage: &mut T,
+        table_name: &str,
+    ) -> Result<()> {
+        let rows = std::mem::take(&mut self.rows);
+        let Some(column_defs) = &self.primary_key else {
+            return storage
+                .append_data(table_name, rows)
+                .await
+                .map_err(Into::into);
+        };
+
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                let key = match &row {
+                    DataRow::Vec(values) => Key::primary(column_defs, values)?,
+                    DataRow::Map(_) => None,
+                }
+                .ok_or_else(|| DumpError::MissingPrimaryKey(table_name.to_owned()))?;
+
+                Ok((key, row))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        storage
+            .insert_data(table_name, rows)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn create_indexes<T: GStore + GStoreMut>(
+        &self,
+        storage: &mut T,
+        table_name: &str,
+    ) -> Result<()> {
+        for SchemaIndex {
+            name,
+            exprs,
+            order,
+            predicate,
+            fulltext,
+            ..
+        } in &self.indexes
+        {
+            let asc = match order {
+                SchemaIndexOrd::Asc => Some(true),
+                SchemaIndexOrd::Desc => Some(false),
+                SchemaIndexOrd::Both => None,
+            };
+            let columns = exprs
+                .iter()
+                .map(|expr| OrderByExpr {
+                    expr: expr.clone(),
+                    asc,
+                })
+                .collect::<Vec<_>>();
+
+            storage
+                .create_index(table_name, name, &columns, predicate.as_ref(), *fulltext)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_entry<W: Write>(writer: &mut W, entry: &Entry) -> Result<()> {
+    serde_json::to_writer(&mut *writer, entry).map_err(std::io::Error::from)?;
+    writer.write_all(b"\n")?;
+
+    Ok(())
+}
@@ -0,0 +1,178 @@
+use {
+    gluesql_core::prelude::{Glue, Payload},
+    gluesql_dump::{Dump, DumpError, Summary},
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_sled_storage::SledStorage,
+    sled::Config,
+};
+
+async fn run<T>(glue: &mut Glue<T>, sql: &str) -> Vec<Payload>
+where
+    T: gluesql_core::store::GStore + gluesql_core::store::GStoreMut,
+{
+    glue.execute(sql).await.unwrap()
+}
+
+fn sled(path: &str) -> SledStorage {
+    let config = Config::default().path(path).temporary(true);
+
+    SledStorage::try_from(config).unwrap()
+}
+
+#[tokio::test]
+async fn dump_and_restore() {
+    let mut source = Glue::new(MemoryStorage::default());
+    run(
+        &mut source,
+        "
+        CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT, price DECIMAL NULL);
+        CREATE TABLE Log (message TEXT, logged_at TIMESTAMP);
+        CREATE TABLE Doc;
+        INSERT INTO Item VALUES (1, 'Glue', 1.5), (2, 'SQL', NULL), (3, 'Rust', 3);
+        INSERT INTO Log VALUES ('hello', '2024-01-01T00:00:00'), ('hello', '2024-01-01T00:00:00');
+        INSERT INTO Doc VALUES ('{\"a\": [1, 2], \"b\": {\"c\": true}}');
+        ",
+    )
+    .await;
+
+    let mut dump = Vec::new();
+    let summary = Dump::new()
+        .with_parallelism(2)
+        .dump(&mut source.storage, &mut dump)
+        .await
+        .unwrap();
+    assert_eq!(summary, Summary { tables: 3, rows: 6 });
+
+    let mut target = Glue::new(sled("tmp/dump_and_restore"));
+    let summary = Dump::new()
+        .restore(&mut target.storage, dump.as_slice())
+        .await
+        .unwrap();
+    assert_eq!(summary, Summary { tables: 3, rows: 6 });
+
+    for sql in [
+        "SELECT * FROM Item ORDER BY id",
+        "SELECT * FROM Log",
+        "SELECT * FROM Doc",
+    ] {
+        assert_eq!(run(&mut target, sql).await, run(&mut source, sql).await);
+    }
+
+    assert!(
+        target
+            .execute("INSERT INTO Item VALUES (1, 'Glue', 0)")
+            .await
+            .is_err(),
+        "restored rows are keyed by their primary key",
+    );
+}
+
+#[tokio::test]
+async fn dump_and_restore_indexes() {
+    let mut source = Glue::new(sled("tmp/dump_and_restore_indexes_source"));
+    run(
+        &mut source,
+        "
+        CREATE TABLE Item (id INTEGER, name TEXT);
+        CREATE INDEX idx_name ON Item (name);
+        INSERT INTO Item VALUES (1, 'b'), (2, 'a'), (3, 'c');
+        ",
+    )
+    .await;
+
+    let mut dump = Vec::new();
+    Dump::new()
+        .dump(&mut source.storage, &mut dump)
+        .await
+        .unwrap();
+
+    let mut target = Glue::new(sled("tmp/dump_and_restore_indexes_target"));
+    Dump::new()
+        .restore(&mut target.storage, dump.as_slice())
+        .await
+        .unwrap();
+
+    let sql = "SELECT id FROM Item WHERE name > 'a' ORDER BY name";
+    assert_eq!(run(&mut target, sql).await, run(&mut source, sql).await);
+    assert_eq!(
+        run(&mut target, "SHOW INDEXES FROM Item").await,
+        run(&mut source, "SHOW INDEXES FROM Item").await,
+    );
+}
+
+#[tokio::test]
+async fn dump_and_restore_filters() {
+    let mut source = Glue::new(MemoryStorage::default());
+    run(
+        &mut source,
+        "
+        CREATE TABLE Foo (id INTEGER);
+        CREATE TABLE Bar (id INTEGER);
+        CREATE TABLE Baz (id INTEGER);
+        INSERT INTO Foo VALUES (1);
+        INSERT INTO Bar VALUES (1), (2);
+        INSERT INTO Baz VALUES (1), (2), (3);
+        ",
+    )
+    .await;
+
+    let mut dump = Vec::new();
+    let summary = Dump::new()
+        .with_excluded_table("Baz")
+        .dump(&mut source.storage, &mut dump)
+        .await
+        .unwrap();
+    assert_eq!(summary, Summary { tables: 2, rows: 3 });
+
+    let mut target = MemoryStorage::default();
+    let summary = Dump::new()
+        .with_included_table("Bar")
+        .with_included_table("Baz")
+        .restore(&mut target, dump.as_slice())
+        .await
+        .unwrap();
+    assert_eq!(summary, Summary { tables: 1, rows: 2 });
+
+    let mut target = Glue::new(target);
+    assert!(target.execute("SELECT * FROM Foo").await.is_err());
+    assert_eq!(
+        run(&mut target, "SELECT * FROM Bar").await,
+        run(&mut source, "SELECT * FROM Bar").await,
+    );
+}
+
+#[tokio::test]
+async fn restore_errors() {
+    let mut storage = MemoryStorage::default();
+
+    let result = Dump::new()
+        .restore(&mut storage, "CREATE TABLE Foo;".as_bytes())
+        .await;
+    assert!(matches!(result, Err(DumpError::UnsupportedFormat)));
+
+    let dump = r#"{"header":{"version":1}}
+{"row":{"table_name":"Foo","row":{"Vec":[]}}}"#;
+    let result = Dump::new().restore(&mut storage, dump.as_bytes()).await;
+    assert!(matches!(result, Err(DumpError::SchemaNotFound(name)) if name == "Foo"));
+
+    let dump = r#"{"header":{"version":1}}
+{"schema":{"table_name":"Foo"}}"#;
+    let result = Dump::new().restore(&mut storage, dump.as_bytes()).await;
+    assert!(matches!(
+        result,
+        Err(DumpError::InvalidEntry { line: 2, .. })
+    ));
+
+    let mut glue = Glue::new(storage);
+    run(&mut glue, "CREATE TABLE Foo (id INTEGER)").await;
+    let mut dump = Vec::new();
+    Dump::new()
+        .dump(&mut glue.storage, &mut dump)
+        .await
+        .unwrap();
+
+    let result = Dump::new()
+        .restore(&mut glue.storage, dump.as_slice())
+        .await;
+    assert!(matches!(result, Err(DumpError::TableAlreadyExists(name)) if name == "Foo"));
+}
@@ -0,0 +1,39 @@
+#include <stdio.h>
+
+#include "gluesql.h"
+
+int main(void) {
+    char *error = NULL;
+    GlueSQL *glue = gluesql_new_memory();
+
+    char *result = gluesql_query(
+        glue,
+        "CREATE TABLE Greet (id INTEGER, name TEXT);"
+        "INSERT INTO Greet VALUES (1, 'Hello'), (2, 'World');",
+        &error);
+    if (result == NULL) {
+        fprintf(stderr, "%s\n", error);
+        gluesql_string_free(error);
+        gluesql_free(glue);
+        return 1;
+    }
+    gluesql_string_free(result);
+
+    GlueSQLStatement *statement = gluesql_prepare(glue, "SELECT * FROM Greet;", &error);
+    if (statement == NULL) {
+        fprintf(stderr, "%s\n", error);
+        gluesql_string_free(error);
+        gluesql_free(glue);
+        return 1;
+    }
+
+    result = gluesql_execute(glue, statement, &error);
+    printf("%s\n", result != NULL ? result : error);
+
+    gluesql_string_free(result);
+    gluesql_string_free(error);
+    gluesql_statement_free(statement);
+    gluesql_free(glue);
+
+    return 0;
+}
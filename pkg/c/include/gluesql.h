@@ -0,0 +1,50 @@
+#ifndef GLUESQL_H
+#define GLUESQL_H
+
+/*
+ * C ABI for GlueSQL.
+ *
+ * - Handles (`GlueSQL`, `GlueSQLStatement`) are opaque and owned by the caller.
+ * - Strings returned by the library must be released with `gluesql_string_free`.
+ * - Fallible functions return NULL, or -1 when they return an int, on failure and, when
+ *   `error` is not NULL, write an error message into `*error` which must also be released with `gluesql_string_free`.
+ * - Query results are JSON arrays with one object per executed statement, e.g.
+ *   [{"type":"SELECT","labels":["id","name"],"rows":[{"id":1,"name":"Glue"}]}]
+ *   `labels` keeps the projection order and is omitted for schemaless results.
+ * - Parameters of prepared statements are passed as a JSON array, e.g. [1,"Glue",null], whose
+ *   items are the values of the `$1`, `$2`, ... or `?` placeholders in order.
+ * - A handle must not be used from multiple threads at the same time.
+ */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct GlueSQL GlueSQL;
+typedef struct GlueSQLStatement GlueSQLStatement;
+
+GlueSQL *gluesql_new_memory(void);
+GlueSQL *gluesql_new_shared_memory(void);
+GlueSQL *gluesql_new_json(const char *path, char **error);
+GlueSQL *gluesql_new_sled(const char *path, char **error);
+GlueSQL *gluesql_new_redb(const char *path, char **error);
+void gluesql_free(GlueSQL *glue);
+
+char *gluesql_query(GlueSQL *glue, const char *sql, char **error);
+
+GlueSQLStatement *gluesql_prepare(GlueSQL *glue, const char *sql, char **error);
+char *gluesql_execute(GlueSQL *glue, const GlueSQLStatement *statement, char **error);
+char *gluesql_execute_with_params(GlueSQL *glue, const GlueSQLStatement *statement, const char *params, char **error);
+int gluesql_statement_params(const GlueSQLStatement *statement);
+void gluesql_statement_free(GlueSQLStatement *statement);
+
+int gluesql_backup(GlueSQL *glue, const char *path, char **error);
+
+void gluesql_string_free(char *string);
+const char *gluesql_version(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GLUESQL_H */
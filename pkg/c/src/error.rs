@@ -0,0 +1,53 @@
+use {
+    gluesql_core::prelude::Error,
+    std::{
+        ffi::{CString, c_char},
+        ptr,
+    },
+    thiserror::Error as ThisError,
+};
+
+#[derive(ThisError, Debug, PartialEq)]
+pub enum CError {
+    #[error("null pointer passed as {0}")]
+    NullPointer(&'static str),
+
+    #[error("{0} is not valid UTF-8")]
+    InvalidUtf8(&'static str),
+
+    #[error("params are not a JSON array of values: {0}")]
+    InvalidParams(String),
+
+    #[error("{0}")]
+    GlueSQL(#[from] Error),
+
+    #[error("cannot serialize result: {0}")]
+    Serialize(String),
+}
+
+/// Writes `error` into the caller provided `char **error` out parameter.
+///
+/// The written string is owned by the caller and must be released with `gluesql_string_free`.
+///
+/// # Safety
+/// `out` must be either null or a valid pointer to a writable `char *`.
+pub unsafe fn set_error(out: *mut *mut c_char, error: CError) {
+    if out.is_null() {
+        return;
+    }
+
+    let message = error.to_string().replace('\0', "\\0");
+    let message = CString::new(message).map_or(ptr::null_mut(), CString::into_raw);
+
+    unsafe { *out = message };
+}
+
+/// Resets the `char **error` out parameter so callers can rely on it being null on success.
+///
+/// # Safety
+/// `out` must be either null or a valid pointer to a writable `char *`.
+pub unsafe fn clear_error(out: *mut *mut c_char) {
+    if !out.is_null() {
+        unsafe { *out = ptr::null_mut() };
+    }
+}
@@ -0,0 +1,452 @@
+#![deny(clippy::str_to_string)]
+
+//! C ABI for GlueSQL.
+//!
+//! Every function here is declared in `include/gluesql.h`. Handles are opaque pointers owned by
+//! the caller, strings returned by the library must be released with `gluesql_string_free`, and
+//! fallible functions report failures through a `char **error` out parameter.
+
+mod error;
+mod payload;
+mod storages;
+
+pub use storages::CStorageEngine;
+
+use {
+    error::{CError, clear_error, set_error},
+    futures::executor::block_on,
+    gluesql_core::{
+        ast::Statement,
+        prelude::{Payload, PreparedStatement, Result, Value, execute, parse, plan, translate},
+        sqlparser::ast::Statement as SqlStatement,
+        store::Backup,
+    },
+    gluesql_json_storage::JsonStorage,
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_redb_storage::RedbStorage,
+    gluesql_shared_memory_storage::SharedMemoryStorage,
+    gluesql_sled_storage::SledStorage,
+    serde_json::Value as Json,
+    std::{
+        ffi::{CStr, CString, c_char, c_int},
+        path::Path,
+        ptr,
+    },
+};
+
+/// Opaque database handle created by one of the `gluesql_new_*` constructors.
+pub struct GlueSQL {
+    storage: CStorageEngine,
+}
+
+/// Opaque handle to statements parsed by `gluesql_prepare`. Statements without placeholders are
+/// translated and planned ahead of time as well, the others each time they are run with the
+/// values of their parameters.
+pub struct GlueSQLStatement {
+    prepared: PreparedStatement,
+    planned: Option<Vec<Statement>>,
+}
+
+macro_rules! with_storage {
+    ($engine:expr, $storage:ident => $body:expr) => {
+        match $engine {
+            CStorageEngine::Memory($storage) => $body,
+            CStorageEngine::SharedMemory($storage) => $body,
+            CStorageEngine::Json($storage) => $body,
+            CStorageEngine::Sled($storage) => $body,
+            CStorageEngine::Redb($storage) => $body,
+        }
+    };
+}
+
+impl GlueSQL {
+    pub fn new(storage: CStorageEngine) -> Self {
+        Self { storage }
+    }
+
+    async fn plan(&self, statement: Statement) -> Result<Statement> {
+        with_storage!(&self.storage, storage => plan(storage, statement).await)
+    }
+
+    async fn execute(&mut self, statement: &Statement) -> Result<Payload> {
+        with_storage!(&mut self.storage, storage => execute(storage, statement).await)
+    }
+
+    async fn backup(&self, target_path: &Path) -> Result<()> {
+        with_storage!(&self.storage, storage => storage.backup(target_path).await)
+    }
+
+    async fn prepare(&self, sql: &str) -> Result<Vec<Statement>> {
+        let mut statements = Vec::new();
+        for parsed in parse(sql)?.iter() {
+            let statement = translate(parsed)?;
+            statements.push(self.plan(statement).await?);
+        }
+
+        Ok(statements)
+    }
+
+    async fn query(&mut self, sql: &str) -> Result<Vec<Payload>> {
+        self.execute_parsed(parse(sql)?).await
+    }
+
+    async fn execute_parsed(&mut self, parsed: Vec<SqlStatement>) -> Result<Vec<Payload>> {
+        let mut payloads = Vec::new();
+        for parsed in parsed.iter() {
+            let statement = translate(parsed)?;
+            let statement = self.plan(statement).await?;
+
+            payloads.push(self.execute(&statement).await?);
+        }
+
+        Ok(payloads)
+    }
+
+    /// Runs the statement with `params` as the values of its placeholders, as
+    /// `Glue::execute_with_params` does.
+    async fn execute_prepared(
+        &mut self,
+        statement: &GlueSQLStatement,
+        params: &[Value],
+    ) -> Result<Vec<Payload>> {
+        match (&statement.planned, params) {
+            (Some(statements), []) => self.execute_all(statements).await,
+            _ => {
+                let parsed = statement.prepared.bind(params)?;
+
+                self.execute_parsed(parsed).await
+            }
+        }
+    }
+
+    async fn execute_all(&mut self, statements: &[Statement]) -> Result<Vec<Payload>> {
+        let mut payloads = Vec::with_capacity(statements.len());
+        for statement in statements {
+            payloads.push(self.execute(statement).await?);
+        }
+
+        Ok(payloads)
+    }
+}
+
+/// # Safety
+/// `ptr` must be null or point to a nul-terminated string which outlives `'a`.
+unsafe fn to_str<'a>(ptr: *const c_char, name: &'static str) -> Result<&'a str, CError> {
+    if ptr.is_null() {
+        return Err(CError::NullPointer(name));
+    }
+
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|_| CError::InvalidUtf8(name))
+}
+
+/// Runs `f`, storing its error into `error` and mapping the outcome through `into_raw`.
+///
+/// # Safety
+/// `error` must be either null or a valid pointer to a writable `char *`.
+unsafe fn ffi<T, P>(
+    error: *mut *mut c_char,
+    f: impl FnOnce() -> Result<T, CError>,
+    into_raw: impl FnOnce(T) -> *mut P,
+) -> *mut P {
+    unsafe { clear_error(error) };
+
+    match f() {
+        Ok(value) => into_raw(value),
+        Err(e) => {
+            unsafe { set_error(error, e) };
+
+            ptr::null_mut()
+        }
+    }
+}
+
+fn payloads_into_raw(payloads: Vec<Payload>) -> Result<CString, CError> {
+    let json = payload::convert(payloads)?;
+
+    CString::new(json.to_string()).map_err(|e| CError::Serialize(e.to_string()))
+}
+
+fn new_glue(storage: CStorageEngine) -> *mut GlueSQL {
+    Box::into_raw(Box::new(GlueSQL::new(storage)))
+}
+
+/// Opens a new handle over an empty `MemoryStorage`.
+#[unsafe(no_mangle)]
+pub extern "C" fn gluesql_new_memory() -> *mut GlueSQL {
+    new_glue(CStorageEngine::Memory(MemoryStorage::default()))
+}
+
+/// Opens a new handle over an empty `SharedMemoryStorage`.
+#[unsafe(no_mangle)]
+pub extern "C" fn gluesql_new_shared_memory() -> *mut GlueSQL {
+    new_glue(CStorageEngine::SharedMemory(SharedMemoryStorage::new()))
+}
+
+/// Opens a new handle over a `JsonStorage` rooted at `path`.
+///
+/// # Safety
+/// `path` must be a nul-terminated string and `error` either null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_new_json(
+    path: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut GlueSQL {
+    unsafe {
+        ffi(
+            error,
+            || {
+                let path = to_str(path, "path")?;
+
+                Ok(CStorageEngine::Json(JsonStorage::new(path)?))
+            },
+            new_glue,
+        )
+    }
+}
+
+/// Opens a new handle over a `SledStorage` stored at `path`.
+///
+/// # Safety
+/// `path` must be a nul-terminated string and `error` either null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_new_sled(
+    path: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut GlueSQL {
+    unsafe {
+        ffi(
+            error,
+            || {
+                let path = to_str(path, "path")?;
+
+                Ok(CStorageEngine::Sled(SledStorage::new(path)?))
+            },
+            new_glue,
+        )
+    }
+}
+
+/// Opens a new handle over a `RedbStorage` stored in the file at `path`.
+///
+/// # Safety
+/// `path` must be a nul-terminated string and `error` either null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_new_redb(
+    path: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut GlueSQL {
+    unsafe {
+        ffi(
+            error,
+            || {
+                let path = to_str(path, "path")?;
+
+                Ok(CStorageEngine::Redb(RedbStorage::new(path)?))
+            },
+            new_glue,
+        )
+    }
+}
+
+/// Executes every statement in `sql` and returns the payloads as a JSON array string.
+///
+/// # Safety
+/// `glue` must come from a `gluesql_new_*` constructor, `sql` must be a nul-terminated string
+/// and `error` either null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_query(
+    glue: *mut GlueSQL,
+    sql: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    unsafe {
+        ffi(
+            error,
+            || {
+                let glue = glue.as_mut().ok_or(CError::NullPointer("glue"))?;
+                let sql = to_str(sql, "sql")?;
+                let payloads = block_on(glue.query(sql))?;
+
+                payloads_into_raw(payloads)
+            },
+            CString::into_raw,
+        )
+    }
+}
+
+/// Parses `sql` once so it can be run repeatedly with `gluesql_execute`, or with
+/// `gluesql_execute_with_params` when it has `$1`, `$2`, ... or `?` placeholders.
+///
+/// # Safety
+/// `glue` must come from a `gluesql_new_*` constructor, `sql` must be a nul-terminated string
+/// and `error` either null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_prepare(
+    glue: *mut GlueSQL,
+    sql: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut GlueSQLStatement {
+    unsafe {
+        ffi(
+            error,
+            || {
+                let glue = glue.as_ref().ok_or(CError::NullPointer("glue"))?;
+                let sql = to_str(sql, "sql")?;
+                let prepared = PreparedStatement::new(sql)?;
+                let planned = match prepared.params() {
+                    0 => Some(block_on(glue.prepare(sql))?),
+                    _ => None,
+                };
+
+                Ok(GlueSQLStatement { prepared, planned })
+            },
+            |statement| Box::into_raw(Box::new(statement)),
+        )
+    }
+}
+
+/// Executes a statement prepared by `gluesql_prepare` and returns the payloads as JSON.
+///
+/// # Safety
+/// `glue` and `statement` must be live handles returned by this library and `error` either null
+/// or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_execute(
+    glue: *mut GlueSQL,
+    statement: *const GlueSQLStatement,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    unsafe {
+        ffi(
+            error,
+            || {
+                let glue = glue.as_mut().ok_or(CError::NullPointer("glue"))?;
+                let statement = statement.as_ref().ok_or(CError::NullPointer("statement"))?;
+                let payloads = block_on(glue.execute_prepared(statement, &[]))?;
+
+                payloads_into_raw(payloads)
+            },
+            CString::into_raw,
+        )
+    }
+}
+
+/// Executes a statement prepared by `gluesql_prepare` with `params`, a JSON array of the values
+/// of its placeholders, and returns the payloads as JSON. `params[0]` is the value of `$1` and
+/// of the first `?`.
+///
+/// # Safety
+/// `glue` and `statement` must be live handles returned by this library, `params` must be a
+/// nul-terminated string and `error` either null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_execute_with_params(
+    glue: *mut GlueSQL,
+    statement: *const GlueSQLStatement,
+    params: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    unsafe {
+        ffi(
+            error,
+            || {
+                let glue = glue.as_mut().ok_or(CError::NullPointer("glue"))?;
+                let statement = statement.as_ref().ok_or(CError::NullPointer("statement"))?;
+                let params = to_str(params, "params")?;
+                let params = serde_json::from_str::<Vec<Json>>(params)
+                    .map_err(|e| CError::InvalidParams(e.to_string()))?
+                    .into_iter()
+                    .map(Value::try_from)
+                    .collect::<Result<Vec<_>>>()?;
+                let payloads = block_on(glue.execute_prepared(statement, &params))?;
+
+                payloads_into_raw(payloads)
+            },
+            CString::into_raw,
+        )
+    }
+}
+
+/// Returns the number of parameters a statement prepared by `gluesql_prepare` reads, or -1 when
+/// `statement` is null.
+///
+/// # Safety
+/// `statement` must be null or a live handle returned by `gluesql_prepare`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_statement_params(statement: *const GlueSQLStatement) -> c_int {
+    match unsafe { statement.as_ref() } {
+        Some(statement) => c_int::try_from(statement.prepared.params()).unwrap_or(c_int::MAX),
+        None => -1,
+    }
+}
+
+/// Copies the database to `path`, which must not exist yet. Returns 0 on success and -1 on
+/// failure.
+///
+/// # Safety
+/// `glue` must come from a `gluesql_new_*` constructor, `path` must be a nul-terminated string
+/// and `error` either null or writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_backup(
+    glue: *mut GlueSQL,
+    path: *const c_char,
+    error: *mut *mut c_char,
+) -> c_int {
+    unsafe { clear_error(error) };
+
+    let backup = || -> Result<(), CError> {
+        let glue = unsafe { glue.as_ref() }.ok_or(CError::NullPointer("glue"))?;
+        let path = unsafe { to_str(path, "path") }?;
+
+        block_on(glue.backup(Path::new(path))).map_err(Into::into)
+    };
+
+    match backup() {
+        Ok(()) => 0,
+        Err(e) => {
+            unsafe { set_error(error, e) };
+
+            -1
+        }
+    }
+}
+
+/// Releases a handle returned by `gluesql_prepare`. Passing null is a no-op.
+///
+/// # Safety
+/// `statement` must be null or a handle which has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_statement_free(statement: *mut GlueSQLStatement) {
+    if !statement.is_null() {
+        drop(unsafe { Box::from_raw(statement) });
+    }
+}
+
+/// Releases a handle returned by a `gluesql_new_*` constructor. Passing null is a no-op.
+///
+/// # Safety
+/// `glue` must be null or a handle which has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_free(glue: *mut GlueSQL) {
+    if !glue.is_null() {
+        drop(unsafe { Box::from_raw(glue) });
+    }
+}
+
+/// Releases a string returned by this library. Passing null is a no-op.
+///
+/// # Safety
+/// `string` must be null or a string returned by this library which has not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn gluesql_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Returns the library version as a static nul-terminated string.
+#[unsafe(no_mangle)]
+pub extern "C" fn gluesql_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
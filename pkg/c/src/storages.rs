@@ -0,0 +1,13 @@
+use {
+    gluesql_json_storage::JsonStorage, gluesql_memory_storage::MemoryStorage,
+    gluesql_redb_storage::RedbStorage, gluesql_shared_memory_storage::SharedMemoryStorage,
+    gluesql_sled_storage::SledStorage,
+};
+
+pub enum CStorageEngine {
+    Memory(MemoryStorage),
+    SharedMemory(SharedMemoryStorage),
+    Json(JsonStorage),
+    Sled(SledStorage),
+    Redb(RedbStorage),
+}
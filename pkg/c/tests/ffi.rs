@@ -0,0 +1,230 @@
+use {
+    gluesql_c::{
+        gluesql_backup, gluesql_execute, gluesql_execute_with_params, gluesql_free,
+        gluesql_new_json, gluesql_new_memory, gluesql_prepare, gluesql_query,
+        gluesql_statement_free, gluesql_statement_params, gluesql_string_free, gluesql_version,
+    },
+    serde_json::{Value as Json, json},
+    std::{
+        ffi::{CStr, CString, c_char},
+        ptr,
+    },
+};
+
+unsafe fn take_string(string: *mut c_char) -> String {
+    let owned = unsafe { CStr::from_ptr(string) }
+        .to_str()
+        .unwrap()
+        .to_owned();
+    unsafe { gluesql_string_free(string) };
+
+    owned
+}
+
+unsafe fn query(glue: *mut gluesql_c::GlueSQL, sql: &str) -> Result<Json, String> {
+    let sql = CString::new(sql).unwrap();
+    let mut error = ptr::null_mut();
+    let result = unsafe { gluesql_query(glue, sql.as_ptr(), &mut error) };
+
+    if result.is_null() {
+        Err(unsafe { take_string(error) })
+    } else {
+        assert!(error.is_null());
+
+        Ok(serde_json::from_str(&unsafe { take_string(result) }).unwrap())
+    }
+}
+
+#[test]
+fn query_and_prepare() {
+    unsafe {
+        let glue = gluesql_new_memory();
+
+        assert_eq!(
+            query(
+                glue,
+                "CREATE TABLE Foo (id INTEGER, name TEXT); INSERT INTO Foo VALUES (1, 'a'), (2, 'b');"
+            ),
+            Ok(json!([
+                { "type": "CREATE TABLE" },
+                { "type": "INSERT", "affected": 2 },
+            ]))
+        );
+
+        assert_eq!(
+            query(glue, "SELECT * FROM Foo WHERE id = 2"),
+            Ok(json!([{
+                "type": "SELECT",
+                "rows": [{ "id": 2, "name": "b" }],
+                "labels": ["id", "name"],
+            }]))
+        );
+
+        assert_eq!(
+            query(glue, "SELECT * FROM Bar"),
+            Err("fetch: table not found: Bar".to_owned())
+        );
+
+        let sql = CString::new("UPDATE Foo SET id = id + 10").unwrap();
+        let mut error = ptr::null_mut();
+        let statement = gluesql_prepare(glue, sql.as_ptr(), &mut error);
+        assert!(!statement.is_null());
+
+        for _ in 0..2 {
+            let result = gluesql_execute(glue, statement, &mut error);
+            assert_eq!(
+                serde_json::from_str::<Json>(&take_string(result)).unwrap(),
+                json!([{ "type": "UPDATE", "affected": 2 }])
+            );
+        }
+        gluesql_statement_free(statement);
+
+        assert_eq!(
+            query(glue, "SELECT id FROM Foo"),
+            Ok(json!([{
+                "type": "SELECT",
+                "rows": [{ "id": 21 }, { "id": 22 }],
+                "labels": ["id"],
+            }]))
+        );
+
+        gluesql_free(glue);
+    }
+}
+
+#[test]
+fn execute_with_params() {
+    unsafe {
+        let glue = gluesql_new_memory();
+        query(glue, "CREATE TABLE Foo (id INTEGER, name TEXT NULL);").unwrap();
+
+        let execute = |statement, params: &str| {
+            let params = CString::new(params).unwrap();
+            let mut error = ptr::null_mut();
+            let result = gluesql_execute_with_params(glue, statement, params.as_ptr(), &mut error);
+
+            if result.is_null() {
+                Err(take_string(error))
+            } else {
+                Ok(serde_json::from_str::<Json>(&take_string(result)).unwrap())
+            }
+        };
+
+        let sql = CString::new("INSERT INTO Foo VALUES ($1, $2)").unwrap();
+        let mut error = ptr::null_mut();
+        let insert = gluesql_prepare(glue, sql.as_ptr(), &mut error);
+        assert_eq!(gluesql_statement_params(insert), 2);
+        assert_eq!(
+            execute(insert, r#"[1, "it's"]"#),
+            Ok(json!([{ "type": "INSERT", "affected": 1 }]))
+        );
+        assert_eq!(
+            execute(insert, "[2, null]"),
+            Ok(json!([{ "type": "INSERT", "affected": 1 }]))
+        );
+        assert_eq!(
+            execute(insert, "[3]"),
+            Err("prepared: the statement reads 2 parameters but 1 were given".to_owned())
+        );
+        assert!(
+            execute(insert, "{}")
+                .is_err_and(|error| error.starts_with("params are not a JSON array of values"))
+        );
+
+        let result = gluesql_execute(glue, insert, &mut error);
+        assert!(result.is_null());
+        assert_eq!(
+            take_string(error),
+            "prepared: the statement reads 2 parameters but 0 were given"
+        );
+        gluesql_statement_free(insert);
+
+        let sql = CString::new("SELECT id, name FROM Foo WHERE id > ? ORDER BY id").unwrap();
+        let select = gluesql_prepare(glue, sql.as_ptr(), &mut error);
+        assert_eq!(gluesql_statement_params(select), 1);
+        assert_eq!(
+            execute(select, "[0]"),
+            Ok(json!([{
+                "type": "SELECT",
+                "rows": [{ "id": 1, "name": "it's" }, { "id": 2, "name": null }],
+                "labels": ["id", "name"],
+            }]))
+        );
+        gluesql_statement_free(select);
+
+        assert_eq!(gluesql_statement_params(ptr::null()), -1);
+        gluesql_free(glue);
+    }
+}
+
+#[test]
+fn null_arguments() {
+    unsafe {
+        let mut error = ptr::null_mut();
+        let result = gluesql_query(ptr::null_mut(), ptr::null(), &mut error);
+        assert!(result.is_null());
+        assert_eq!(take_string(error), "null pointer passed as glue");
+
+        let glue = gluesql_new_memory();
+        let result = gluesql_query(glue, ptr::null(), &mut error);
+        assert!(result.is_null());
+        assert_eq!(take_string(error), "null pointer passed as sql");
+
+        gluesql_free(glue);
+        gluesql_free(ptr::null_mut());
+        gluesql_statement_free(ptr::null_mut());
+        gluesql_string_free(ptr::null_mut());
+    }
+}
+
+#[test]
+fn backup() {
+    let _ = std::fs::remove_dir_all("tmp/backup");
+    let source = CString::new("tmp/backup/source").unwrap();
+    let target = CString::new("tmp/backup/target").unwrap();
+
+    unsafe {
+        let mut error = ptr::null_mut();
+        let glue = gluesql_new_json(source.as_ptr(), &mut error);
+        query(
+            glue,
+            "CREATE TABLE Foo (id INTEGER); INSERT INTO Foo VALUES (1), (2);",
+        )
+        .unwrap();
+
+        assert_eq!(gluesql_backup(glue, target.as_ptr(), &mut error), 0);
+        assert!(error.is_null());
+        assert_eq!(gluesql_backup(glue, target.as_ptr(), &mut error), -1);
+        assert_eq!(
+            take_string(error),
+            "backup: backup target already exists: tmp/backup/target"
+        );
+        gluesql_free(glue);
+
+        let glue = gluesql_new_json(target.as_ptr(), &mut error);
+        assert_eq!(
+            query(glue, "SELECT id FROM Foo"),
+            Ok(json!([{
+                "type": "SELECT",
+                "rows": [{ "id": 1 }, { "id": 2 }],
+                "labels": ["id"],
+            }]))
+        );
+        gluesql_free(glue);
+
+        let glue = gluesql_new_memory();
+        assert_eq!(gluesql_backup(glue, target.as_ptr(), &mut error), -1);
+        assert_eq!(
+            take_string(error),
+            "storage: [Storage] Backup::backup is not supported"
+        );
+        gluesql_free(glue);
+    }
+}
+
+#[test]
+fn version() {
+    let version = unsafe { CStr::from_ptr(gluesql_version()) };
+
+    assert_eq!(version.to_str(), Ok(env!("CARGO_PKG_VERSION")));
+}
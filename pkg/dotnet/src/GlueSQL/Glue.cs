@@ -0,0 +1,130 @@
+namespace GlueSQL;
+
+/// <summary>
+/// An embedded GlueSQL database.
+/// </summary>
+/// <remarks>
+/// The native handle is not synchronized, so queries issued concurrently through the async API
+/// are executed one at a time in submission order.
+/// </remarks>
+public sealed class Glue : IDisposable
+{
+    private readonly SemaphoreSlim gate = new(1, 1);
+    private IntPtr handle;
+
+    private Glue(IntPtr handle)
+    {
+        this.handle = handle;
+    }
+
+    public static Glue Memory() => new(Native.NewMemory());
+
+    public static Glue SharedMemory() => new(Native.NewSharedMemory());
+
+    public static Glue Json(string path) => Open(Native.NewJson, path);
+
+    public static Glue Sled(string path) => Open(Native.NewSled, path);
+
+    public static Glue Redb(string path) => Open(Native.NewRedb, path);
+
+    public static string Version => System.Runtime.InteropServices.Marshal.PtrToStringUTF8(Native.Version())!;
+
+    private delegate IntPtr Opener(string path, out IntPtr error);
+
+    private static Glue Open(Opener open, string path)
+    {
+        var glue = open(path, out var error);
+
+        return new Glue(Native.Check(glue, error));
+    }
+
+    /// <summary>Runs every statement in <paramref name="sql"/> and returns one payload per statement.</summary>
+    public IReadOnlyList<Payload> Query(string sql)
+    {
+        gate.Wait();
+        try
+        {
+            var result = Native.Query(Handle, sql, out var error);
+
+            return Payload.ParseAll(Native.TakeString(Native.Check(result, error)));
+        }
+        finally
+        {
+            gate.Release();
+        }
+    }
+
+    /// <inheritdoc cref="Query(string)"/>
+    public async Task<IReadOnlyList<Payload>> QueryAsync(string sql, CancellationToken cancellationToken = default)
+    {
+        await gate.WaitAsync(cancellationToken).ConfigureAwait(false);
+        try
+        {
+            return await Task.Run(
+                () =>
+                {
+                    var result = Native.Query(Handle, sql, out var error);
+
+                    return Payload.ParseAll(Native.TakeString(Native.Check(result, error)));
+                },
+                cancellationToken).ConfigureAwait(false);
+        }
+        finally
+        {
+            gate.Release();
+        }
+    }
+
+    /// <summary>Parses and plans <paramref name="sql"/> once for repeated execution.</summary>
+    public PreparedStatement Prepare(string sql)
+    {
+        gate.Wait();
+        try
+        {
+            var statement = Native.Prepare(Handle, sql, out var error);
+
+            return new PreparedStatement(this, Native.Check(statement, error));
+        }
+        finally
+        {
+            gate.Release();
+        }
+    }
+
+    internal async Task<IReadOnlyList<Payload>> ExecuteAsync(IntPtr statement, CancellationToken cancellationToken)
+    {
+        await gate.WaitAsync(cancellationToken).ConfigureAwait(false);
+        try
+        {
+            return await Task.Run(
+                () =>
+                {
+                    var result = Native.Execute(Handle, statement, out var error);
+
+                    return Payload.ParseAll(Native.TakeString(Native.Check(result, error)));
+                },
+                cancellationToken).ConfigureAwait(false);
+        }
+        finally
+        {
+            gate.Release();
+        }
+    }
+
+    private IntPtr Handle =>
+        handle != IntPtr.Zero ? handle : throw new ObjectDisposedException(nameof(Glue));
+
+    public void Dispose()
+    {
+        gate.Wait();
+        try
+        {
+            Native.Free(handle);
+            handle = IntPtr.Zero;
+        }
+        finally
+        {
+            gate.Release();
+        }
+    }
+}
@@ -0,0 +1,10 @@
+namespace GlueSQL;
+
+/// <summary>Raised when GlueSQL fails to open a storage, parse, plan or execute a statement.</summary>
+public sealed class GlueSQLException : Exception
+{
+    public GlueSQLException(string message)
+        : base(message)
+    {
+    }
+}
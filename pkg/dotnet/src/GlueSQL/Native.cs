@@ -0,0 +1,70 @@
+using System.Runtime.InteropServices;
+
+namespace GlueSQL;
+
+/// <summary>P/Invoke declarations for <c>pkg/c/include/gluesql.h</c>.</summary>
+internal static partial class Native
+{
+    private const string Library = "gluesql";
+
+    [LibraryImport(Library, EntryPoint = "gluesql_new_memory")]
+    internal static partial IntPtr NewMemory();
+
+    [LibraryImport(Library, EntryPoint = "gluesql_new_shared_memory")]
+    internal static partial IntPtr NewSharedMemory();
+
+    [LibraryImport(Library, EntryPoint = "gluesql_new_json", StringMarshalling = StringMarshalling.Utf8)]
+    internal static partial IntPtr NewJson(string path, out IntPtr error);
+
+    [LibraryImport(Library, EntryPoint = "gluesql_new_sled", StringMarshalling = StringMarshalling.Utf8)]
+    internal static partial IntPtr NewSled(string path, out IntPtr error);
+
+    [LibraryImport(Library, EntryPoint = "gluesql_new_redb", StringMarshalling = StringMarshalling.Utf8)]
+    internal static partial IntPtr NewRedb(string path, out IntPtr error);
+
+    [LibraryImport(Library, EntryPoint = "gluesql_free")]
+    internal static partial void Free(IntPtr glue);
+
+    [LibraryImport(Library, EntryPoint = "gluesql_query", StringMarshalling = StringMarshalling.Utf8)]
+    internal static partial IntPtr Query(IntPtr glue, string sql, out IntPtr error);
+
+    [LibraryImport(Library, EntryPoint = "gluesql_prepare", StringMarshalling = StringMarshalling.Utf8)]
+    internal static partial IntPtr Prepare(IntPtr glue, string sql, out IntPtr error);
+
+    [LibraryImport(Library, EntryPoint = "gluesql_execute")]
+    internal static partial IntPtr Execute(IntPtr glue, IntPtr statement, out IntPtr error);
+
+    [LibraryImport(Library, EntryPoint = "gluesql_statement_free")]
+    internal static partial void StatementFree(IntPtr statement);
+
+    [LibraryImport(Library, EntryPoint = "gluesql_string_free")]
+    internal static partial void StringFree(IntPtr value);
+
+    [LibraryImport(Library, EntryPoint = "gluesql_version")]
+    internal static partial IntPtr Version();
+
+    /// <summary>Copies a string owned by the native library and releases the original.</summary>
+    internal static string TakeString(IntPtr value)
+    {
+        try
+        {
+            return Marshal.PtrToStringUTF8(value) ?? string.Empty;
+        }
+        finally
+        {
+            StringFree(value);
+        }
+    }
+
+    /// <summary>Returns <paramref name="result"/> or throws the error written by the native call.</summary>
+    internal static IntPtr Check(IntPtr result, IntPtr error)
+    {
+        if (result != IntPtr.Zero)
+        {
+            return result;
+        }
+
+        var message = error == IntPtr.Zero ? "unknown error" : TakeString(error);
+        throw new GlueSQLException(message);
+    }
+}
@@ -0,0 +1,81 @@
+using System.Text.Json;
+
+namespace GlueSQL;
+
+/// <summary>Result of a single executed statement.</summary>
+public abstract record Payload(string Type)
+{
+    internal static IReadOnlyList<Payload> ParseAll(string json)
+    {
+        using var document = JsonDocument.Parse(json);
+
+        return document.RootElement.EnumerateArray().Select(Parse).ToList();
+    }
+
+    private static Payload Parse(JsonElement element)
+    {
+        var type = element.GetProperty("type").GetString() ?? string.Empty;
+
+        return type switch
+        {
+            "SELECT" => new SelectPayload(
+                element.GetProperty("rows").EnumerateArray().Select(row => new Row(row)).ToList()),
+            "INSERT" or "UPDATE" or "DELETE" or "DROP TABLE" => new AffectedPayload(
+                type,
+                element.GetProperty("affected").GetInt64()),
+            "SHOW COLUMNS" => new ShowColumnsPayload(
+                element.GetProperty("columns")
+                    .EnumerateArray()
+                    .Select(column => new Column(
+                        column.GetProperty("name").GetString()!,
+                        column.GetProperty("type").GetString()!))
+                    .ToList()),
+            "SHOW TABLES" => new ShowTablesPayload(Strings(element.GetProperty("tables"))),
+            "SHOW FUNCTIONS" => new ShowFunctionsPayload(Strings(element.GetProperty("functions"))),
+            "SHOW VERSION" => new ShowVersionPayload(element.GetProperty("version").GetString()!),
+            _ => new StatementPayload(type),
+        };
+    }
+
+    private static IReadOnlyList<string> Strings(JsonElement array) =>
+        array.EnumerateArray().Select(value => value.GetString()!).ToList();
+}
+
+/// <summary><c>SELECT</c> and <c>VALUES</c> results.</summary>
+public sealed record SelectPayload(IReadOnlyList<Row> Rows) : Payload("SELECT");
+
+/// <summary><c>INSERT</c>, <c>UPDATE</c>, <c>DELETE</c> and <c>DROP TABLE</c> results.</summary>
+public sealed record AffectedPayload(string StatementType, long Affected) : Payload(StatementType);
+
+public sealed record Column(string Name, string DataType);
+
+public sealed record ShowColumnsPayload(IReadOnlyList<Column> Columns) : Payload("SHOW COLUMNS");
+
+public sealed record ShowTablesPayload(IReadOnlyList<string> Tables) : Payload("SHOW TABLES");
+
+public sealed record ShowFunctionsPayload(IReadOnlyList<string> Functions) : Payload("SHOW FUNCTIONS");
+
+public sealed record ShowVersionPayload(string Version) : Payload("SHOW VERSION");
+
+/// <summary>Statements without a result body such as <c>CREATE TABLE</c> or <c>COMMIT</c>.</summary>
+public sealed record StatementPayload(string StatementType) : Payload(StatementType);
+
+/// <summary>A single result row keyed by column label.</summary>
+public sealed class Row
+{
+    private readonly Dictionary<string, JsonElement> values;
+
+    internal Row(JsonElement row)
+    {
+        values = row.EnumerateObject().ToDictionary(property => property.Name, property => property.Value.Clone());
+    }
+
+    public IEnumerable<string> Columns => values.Keys;
+
+    public bool IsNull(string column) => values[column].ValueKind == JsonValueKind.Null;
+
+    /// <summary>Reads <paramref name="column"/> as <typeparamref name="T"/>, e.g. <c>long</c>, <c>string</c>, or a class matching the shape of a MAP value.</summary>
+    public T? Get<T>(string column) => values[column].Deserialize<T>();
+
+    public JsonElement this[string column] => values[column];
+}
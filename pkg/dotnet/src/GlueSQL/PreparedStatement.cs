@@ -0,0 +1,29 @@
+namespace GlueSQL;
+
+/// <summary>Statements parsed and planned by <see cref="Glue.Prepare(string)"/>.</summary>
+public sealed class PreparedStatement : IDisposable
+{
+    private readonly Glue glue;
+    private IntPtr handle;
+
+    internal PreparedStatement(Glue glue, IntPtr handle)
+    {
+        this.glue = glue;
+        this.handle = handle;
+    }
+
+    public IReadOnlyList<Payload> Execute() => ExecuteAsync().GetAwaiter().GetResult();
+
+    public Task<IReadOnlyList<Payload>> ExecuteAsync(CancellationToken cancellationToken = default)
+    {
+        ObjectDisposedException.ThrowIf(handle == IntPtr.Zero, this);
+
+        return glue.ExecuteAsync(handle, cancellationToken);
+    }
+
+    public void Dispose()
+    {
+        Native.StatementFree(handle);
+        handle = IntPtr.Zero;
+    }
+}
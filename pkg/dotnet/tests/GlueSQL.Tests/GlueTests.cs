@@ -0,0 +1,64 @@
+using GlueSQL;
+using Xunit;
+
+public class GlueTests
+{
+    [Fact]
+    public async Task QueryReturnsTypedPayloads()
+    {
+        using var glue = Glue.Memory();
+
+        var payloads = await glue.QueryAsync(
+            "CREATE TABLE Foo (id INTEGER, name TEXT NULL);" +
+            "INSERT INTO Foo VALUES (1, 'Glue'), (2, NULL);");
+
+        Assert.Equal(new StatementPayload("CREATE TABLE"), payloads[0]);
+        Assert.Equal(new AffectedPayload("INSERT", 2), payloads[1]);
+
+        var select = Assert.IsType<SelectPayload>(Assert.Single(await glue.QueryAsync("SELECT * FROM Foo")));
+        Assert.Equal(2, select.Rows.Count);
+        Assert.Equal(1L, select.Rows[0].Get<long>("id"));
+        Assert.Equal("Glue", select.Rows[0].Get<string>("name"));
+        Assert.True(select.Rows[1].IsNull("name"));
+    }
+
+    [Fact]
+    public void PreparedStatementRunsRepeatedly()
+    {
+        using var glue = Glue.Memory();
+        glue.Query("CREATE TABLE Counter (n INTEGER); INSERT INTO Counter VALUES (0);");
+
+        using var increment = glue.Prepare("UPDATE Counter SET n = n + 1");
+        increment.Execute();
+        increment.Execute();
+
+        var select = Assert.IsType<SelectPayload>(Assert.Single(glue.Query("SELECT n FROM Counter")));
+        Assert.Equal(2L, select.Rows[0].Get<long>("n"));
+    }
+
+    [Fact]
+    public void ErrorsAreRaisedAsGlueSQLException()
+    {
+        using var glue = Glue.Memory();
+
+        var error = Assert.Throws<GlueSQLException>(() => glue.Query("SELECT * FROM Missing"));
+        Assert.Equal("fetch: table not found: Missing", error.Message);
+    }
+
+    [Fact]
+    public void PersistentStoragesKeepData()
+    {
+        var path = Path.Combine(Path.GetTempPath(), $"gluesql-dotnet-{Guid.NewGuid()}");
+
+        using (var glue = Glue.Sled(path))
+        {
+            glue.Query("CREATE TABLE Item (id INTEGER); INSERT INTO Item VALUES (7);");
+        }
+
+        using (var glue = Glue.Sled(path))
+        {
+            var select = Assert.IsType<SelectPayload>(Assert.Single(glue.Query("SELECT id FROM Item")));
+            Assert.Equal(7L, select.Rows[0].Get<long>("id"));
+        }
+    }
+}
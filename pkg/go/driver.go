@@ -0,0 +1,432 @@
+package gluesql
+
+import (
+	"context"
+	"database/sql"
+	"database/sql/driver"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"sort"
+	"strings"
+	"time"
+)
+
+func init() {
+	sql.Register("gluesql", Driver{})
+}
+
+// Driver is the database/sql driver registered as "gluesql".
+//
+// Supported data source names are "memory", "shared-memory", "json:<path>",
+// "sled:<path>" and "redb:<path>". Every connection of one sql.DB shares a
+// single storage, and an open transaction holds that storage exclusively
+// until it commits or rolls back.
+type Driver struct{}
+
+var (
+	errNamedArgs    = errors.New("gluesql: named arguments are not supported")
+	errNotSelect    = errors.New("gluesql: statement did not return rows")
+	errLastInsertID = errors.New("gluesql: LastInsertId is not supported")
+)
+
+// Open opens a standalone connection. sql.Open uses OpenConnector instead.
+func (d Driver) Open(dsn string) (driver.Conn, error) {
+	connector, err := d.OpenConnector(dsn)
+	if err != nil {
+		return nil, err
+	}
+
+	return connector.Connect(context.Background())
+}
+
+// OpenConnector opens the storage named by dsn once for the whole sql.DB.
+func (d Driver) OpenConnector(dsn string) (driver.Connector, error) {
+	glue, err := openDSN(dsn)
+	if err != nil {
+		return nil, err
+	}
+
+	return &connector{glue: glue, driver: d, sem: make(chan struct{}, 1)}, nil
+}
+
+func openDSN(dsn string) (*Glue, error) {
+	kind, path, _ := strings.Cut(dsn, ":")
+
+	switch kind {
+	case "memory":
+		return OpenMemory(), nil
+	case "shared-memory":
+		return OpenSharedMemory(), nil
+	case "json":
+		return OpenJSON(path)
+	case "sled":
+		return OpenSled(path)
+	case "redb":
+		return OpenRedb(path)
+	default:
+		return nil, fmt.Errorf("gluesql: unsupported data source name %q", dsn)
+	}
+}
+
+type connector struct {
+	glue   *Glue
+	driver Driver
+	// sem is held for a single statement outside of transactions and for the
+	// whole lifetime of a transaction.
+	sem chan struct{}
+}
+
+var _ io.Closer = (*connector)(nil)
+
+func (c *connector) Connect(context.Context) (driver.Conn, error) {
+	return &conn{connector: c}, nil
+}
+
+func (c *connector) Driver() driver.Driver {
+	return c.driver
+}
+
+func (c *connector) Close() error {
+	return c.glue.Close()
+}
+
+func (c *connector) acquire(ctx context.Context) error {
+	select {
+	case c.sem <- struct{}{}:
+		return nil
+	case <-ctx.Done():
+		return ctx.Err()
+	}
+}
+
+func (c *connector) release() {
+	<-c.sem
+}
+
+type conn struct {
+	connector *connector
+	inTx      bool
+}
+
+var (
+	_ driver.ConnBeginTx        = (*conn)(nil)
+	_ driver.ConnPrepareContext = (*conn)(nil)
+	_ driver.ExecerContext      = (*conn)(nil)
+	_ driver.QueryerContext     = (*conn)(nil)
+)
+
+// run executes fn, waiting for the storage outside of transactions. ctx is
+// honored only until fn starts: the native call cannot be interrupted, so its
+// result is returned even if ctx is cancelled meanwhile, and a cancelled
+// caller never misses work which took effect.
+func (c *conn) run(ctx context.Context, fn func() ([]Payload, error)) ([]Payload, error) {
+	if err := ctx.Err(); err != nil {
+		return nil, err
+	}
+
+	if !c.inTx {
+		if err := c.connector.acquire(ctx); err != nil {
+			return nil, err
+		}
+		defer c.connector.release()
+	}
+
+	return fn()
+}
+
+func (c *conn) Prepare(query string) (driver.Stmt, error) {
+	return c.PrepareContext(context.Background(), query)
+}
+
+func (c *conn) PrepareContext(ctx context.Context, query string) (driver.Stmt, error) {
+	var statement *Statement
+	_, err := c.run(ctx, func() ([]Payload, error) {
+		var err error
+		statement, err = c.connector.glue.Prepare(query)
+
+		return nil, err
+	})
+	if err != nil {
+		return nil, err
+	}
+
+	return &stmt{conn: c, statement: statement}, nil
+}
+
+func (c *conn) ExecContext(ctx context.Context, query string, args []driver.NamedValue) (driver.Result, error) {
+	payloads, err := c.query(ctx, query, args)
+	if err != nil {
+		return nil, err
+	}
+
+	return newResult(payloads), nil
+}
+
+func (c *conn) QueryContext(ctx context.Context, query string, args []driver.NamedValue) (driver.Rows, error) {
+	payloads, err := c.query(ctx, query, args)
+	if err != nil {
+		return nil, err
+	}
+
+	return newRows(payloads)
+}
+
+// query runs every statement in query, or, when there are args, prepares
+// query for this call and runs it with args bound to its placeholders.
+func (c *conn) query(ctx context.Context, query string, args []driver.NamedValue) ([]Payload, error) {
+	if len(args) == 0 {
+		return c.run(ctx, func() ([]Payload, error) { return c.connector.glue.Query(query) })
+	}
+
+	values, err := bindArgs(args)
+	if err != nil {
+		return nil, err
+	}
+
+	return c.run(ctx, func() ([]Payload, error) {
+		statement, err := c.connector.glue.Prepare(query)
+		if err != nil {
+			return nil, err
+		}
+		defer statement.Close()
+
+		return statement.Execute(values...)
+	})
+}
+
+func (c *conn) Begin() (driver.Tx, error) {
+	return c.BeginTx(context.Background(), driver.TxOptions{})
+}
+
+func (c *conn) BeginTx(ctx context.Context, _ driver.TxOptions) (driver.Tx, error) {
+	if err := c.connector.acquire(ctx); err != nil {
+		return nil, err
+	}
+
+	if _, err := c.connector.glue.Query("BEGIN"); err != nil {
+		c.connector.release()
+
+		return nil, err
+	}
+
+	c.inTx = true
+
+	return &tx{conn: c}, nil
+}
+
+func (c *conn) Close() error {
+	return nil
+}
+
+type tx struct {
+	conn *conn
+}
+
+func (t *tx) Commit() error {
+	return t.finish("COMMIT")
+}
+
+func (t *tx) Rollback() error {
+	return t.finish("ROLLBACK")
+}
+
+func (t *tx) finish(sql string) error {
+	defer func() {
+		t.conn.inTx = false
+		t.conn.connector.release()
+	}()
+
+	_, err := t.conn.connector.glue.Query(sql)
+
+	return err
+}
+
+type stmt struct {
+	conn      *conn
+	statement *Statement
+}
+
+var (
+	_ driver.StmtExecContext  = (*stmt)(nil)
+	_ driver.StmtQueryContext = (*stmt)(nil)
+)
+
+func (s *stmt) Close() error {
+	return s.statement.Close()
+}
+
+func (s *stmt) NumInput() int {
+	return s.statement.Params()
+}
+
+func (s *stmt) Exec(args []driver.Value) (driver.Result, error) {
+	return s.ExecContext(context.Background(), namedValues(args))
+}
+
+func (s *stmt) Query(args []driver.Value) (driver.Rows, error) {
+	return s.QueryContext(context.Background(), namedValues(args))
+}
+
+func (s *stmt) ExecContext(ctx context.Context, args []driver.NamedValue) (driver.Result, error) {
+	payloads, err := s.execute(ctx, args)
+	if err != nil {
+		return nil, err
+	}
+
+	return newResult(payloads), nil
+}
+
+func (s *stmt) QueryContext(ctx context.Context, args []driver.NamedValue) (driver.Rows, error) {
+	payloads, err := s.execute(ctx, args)
+	if err != nil {
+		return nil, err
+	}
+
+	return newRows(payloads)
+}
+
+func (s *stmt) execute(ctx context.Context, args []driver.NamedValue) ([]Payload, error) {
+	values, err := bindArgs(args)
+	if err != nil {
+		return nil, err
+	}
+
+	return s.conn.run(ctx, func() ([]Payload, error) { return s.statement.Execute(values...) })
+}
+
+func namedValues(args []driver.Value) []driver.NamedValue {
+	named := make([]driver.NamedValue, len(args))
+	for i, arg := range args {
+		named[i] = driver.NamedValue{Ordinal: i + 1, Value: arg}
+	}
+
+	return named
+}
+
+// bindArgs orders args by their position for the $1, $2, ... or ?
+// placeholders. []byte is bound as TEXT and time.Time as a UTC timestamp.
+func bindArgs(args []driver.NamedValue) ([]any, error) {
+	values := make([]any, len(args))
+	for _, arg := range args {
+		if arg.Name != "" {
+			return nil, errNamedArgs
+		}
+
+		switch v := arg.Value.(type) {
+		case []byte:
+			values[arg.Ordinal-1] = string(v)
+		case time.Time:
+			values[arg.Ordinal-1] = v.UTC().Format(time.RFC3339Nano)
+		default:
+			values[arg.Ordinal-1] = v
+		}
+	}
+
+	return values, nil
+}
+
+type result struct {
+	affected int64
+}
+
+func newResult(payloads []Payload) driver.Result {
+	if len(payloads) == 0 {
+		return result{}
+	}
+
+	return result{affected: payloads[len(payloads)-1].Affected}
+}
+
+func (r result) LastInsertId() (int64, error) {
+	return 0, errLastInsertID
+}
+
+func (r result) RowsAffected() (int64, error) {
+	return r.affected, nil
+}
+
+type rows struct {
+	labels []string
+	rows   []map[string]any
+	next   int
+}
+
+func newRows(payloads []Payload) (driver.Rows, error) {
+	if len(payloads) == 0 || payloads[len(payloads)-1].Type != "SELECT" {
+		return nil, errNotSelect
+	}
+
+	payload := payloads[len(payloads)-1]
+	labels := payload.Labels
+	if labels == nil {
+		labels = schemalessLabels(payload.Rows)
+	}
+
+	return &rows{labels: labels, rows: payload.Rows}, nil
+}
+
+// schemalessLabels collects the union of keys of schemaless rows in sorted order.
+func schemalessLabels(rows []map[string]any) []string {
+	seen := map[string]struct{}{}
+	labels := []string{}
+
+	for _, row := range rows {
+		for label := range row {
+			if _, ok := seen[label]; !ok {
+				seen[label] = struct{}{}
+				labels = append(labels, label)
+			}
+		}
+	}
+
+	sort.Strings(labels)
+
+	return labels
+}
+
+func (r *rows) Columns() []string {
+	return r.labels
+}
+
+func (r *rows) Close() error {
+	r.next = len(r.rows)
+
+	return nil
+}
+
+func (r *rows) Next(dest []driver.Value) error {
+	if r.next >= len(r.rows) {
+		return io.EOF
+	}
+
+	row := r.rows[r.next]
+	r.next++
+
+	for i, label := range r.labels {
+		value, err := driverValue(row[label])
+		if err != nil {
+			return err
+		}
+
+		dest[i] = value
+	}
+
+	return nil
+}
+
+func driverValue(value any) (driver.Value, error) {
+	switch v := value.(type) {
+	case nil, bool, string:
+		return v, nil
+	case json.Number:
+		if i, err := v.Int64(); err == nil {
+			return i, nil
+		}
+
+		return v.Float64()
+	default:
+		return json.Marshal(v)
+	}
+}
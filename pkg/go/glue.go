@@ -0,0 +1,266 @@
+// Package gluesql embeds the GlueSQL database engine through its C ABI (pkg/c).
+//
+// It can be used directly through Glue, or as a database/sql driver registered
+// under the name "gluesql":
+//
+//	db, err := sql.Open("gluesql", "sled:/var/data/app")
+package gluesql
+
+/*
+#cgo CFLAGS: -I${SRCDIR}/../c/include
+#cgo LDFLAGS: -L${SRCDIR}/../../target/release -lgluesql_c
+#include <stdlib.h>
+#include "gluesql.h"
+*/
+import "C"
+
+import (
+	"bytes"
+	"encoding/json"
+	"errors"
+	"runtime"
+	"sync"
+	"unsafe"
+)
+
+// Error is returned when GlueSQL fails to open a storage or to run a statement.
+type Error struct {
+	Message string
+}
+
+func (e *Error) Error() string {
+	return e.Message
+}
+
+// Glue is a handle to an embedded database. It is safe for concurrent use;
+// statements on the same Glue run one at a time.
+type Glue struct {
+	mu     sync.Mutex
+	handle *C.GlueSQL
+}
+
+// Payload is the result of a single statement.
+type Payload struct {
+	Type      string           `json:"type"`
+	Affected  int64            `json:"affected"`
+	Labels    []string         `json:"labels"`
+	Rows      []map[string]any `json:"rows"`
+	Columns   []Column         `json:"columns"`
+	Tables    []string         `json:"tables"`
+	Functions []string         `json:"functions"`
+	Version   string           `json:"version"`
+}
+
+// Column describes a column returned by SHOW COLUMNS.
+type Column struct {
+	Name string `json:"name"`
+	Type string `json:"type"`
+}
+
+// OpenMemory opens a database over an empty MemoryStorage.
+func OpenMemory() *Glue {
+	return newGlue(C.gluesql_new_memory())
+}
+
+// OpenSharedMemory opens a database over an empty SharedMemoryStorage.
+func OpenSharedMemory() *Glue {
+	return newGlue(C.gluesql_new_shared_memory())
+}
+
+// OpenJSON opens a database over a JsonStorage rooted at path.
+func OpenJSON(path string) (*Glue, error) {
+	return openPath(path, func(p *C.char, e **C.char) *C.GlueSQL { return C.gluesql_new_json(p, e) })
+}
+
+// OpenSled opens a database over a SledStorage stored at path.
+func OpenSled(path string) (*Glue, error) {
+	return openPath(path, func(p *C.char, e **C.char) *C.GlueSQL { return C.gluesql_new_sled(p, e) })
+}
+
+// OpenRedb opens a database over a RedbStorage stored in the file at path.
+func OpenRedb(path string) (*Glue, error) {
+	return openPath(path, func(p *C.char, e **C.char) *C.GlueSQL { return C.gluesql_new_redb(p, e) })
+}
+
+// Version returns the version of the linked GlueSQL library.
+func Version() string {
+	return C.GoString(C.gluesql_version())
+}
+
+func newGlue(handle *C.GlueSQL) *Glue {
+	glue := &Glue{handle: handle}
+	runtime.SetFinalizer(glue, (*Glue).Close)
+
+	return glue
+}
+
+func openPath(path string, open func(*C.char, **C.char) *C.GlueSQL) (*Glue, error) {
+	cPath := C.CString(path)
+	defer C.free(unsafe.Pointer(cPath))
+
+	var cErr *C.char
+	handle := open(cPath, &cErr)
+	if handle == nil {
+		return nil, takeError(cErr)
+	}
+
+	return newGlue(handle), nil
+}
+
+// Close releases the database. It is safe to call Close more than once.
+func (g *Glue) Close() error {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+
+	if g.handle != nil {
+		C.gluesql_free(g.handle)
+		g.handle = nil
+	}
+
+	return nil
+}
+
+// Query runs every statement in sql and returns one payload per statement.
+func (g *Glue) Query(sql string) ([]Payload, error) {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+
+	return g.query(sql)
+}
+
+func (g *Glue) query(sql string) ([]Payload, error) {
+	if g.handle == nil {
+		return nil, errClosed
+	}
+
+	cSQL := C.CString(sql)
+	defer C.free(unsafe.Pointer(cSQL))
+
+	var cErr *C.char
+	result := C.gluesql_query(g.handle, cSQL, &cErr)
+	if result == nil {
+		return nil, takeError(cErr)
+	}
+
+	return decodePayloads(takeString(result))
+}
+
+// Statement is a statement parsed and planned once by Prepare.
+type Statement struct {
+	glue   *Glue
+	handle *C.GlueSQLStatement
+}
+
+// Prepare parses and plans sql so it can be executed repeatedly.
+func (g *Glue) Prepare(sql string) (*Statement, error) {
+	g.mu.Lock()
+	defer g.mu.Unlock()
+
+	return g.prepare(sql)
+}
+
+func (g *Glue) prepare(sql string) (*Statement, error) {
+	if g.handle == nil {
+		return nil, errClosed
+	}
+
+	cSQL := C.CString(sql)
+	defer C.free(unsafe.Pointer(cSQL))
+
+	var cErr *C.char
+	handle := C.gluesql_prepare(g.handle, cSQL, &cErr)
+	if handle == nil {
+		return nil, takeError(cErr)
+	}
+
+	statement := &Statement{glue: g, handle: handle}
+	runtime.SetFinalizer(statement, (*Statement).Close)
+
+	return statement, nil
+}
+
+// Params returns the number of parameters the statement reads from its $1,
+// $2, ... or ? placeholders.
+func (s *Statement) Params() int {
+	if s.handle == nil {
+		return 0
+	}
+
+	return int(C.gluesql_statement_params(s.handle))
+}
+
+// Execute runs the prepared statement with args as the values of its
+// placeholders, args[0] for $1 and the first ?. The values are passed to
+// GlueSQL as JSON, so they may be nil, booleans, numbers, strings, slices and
+// maps; GlueSQL never reads them as SQL.
+func (s *Statement) Execute(args ...any) ([]Payload, error) {
+	s.glue.mu.Lock()
+	defer s.glue.mu.Unlock()
+
+	return s.execute(args)
+}
+
+func (s *Statement) execute(args []any) ([]Payload, error) {
+	if s.handle == nil || s.glue.handle == nil {
+		return nil, errClosed
+	}
+
+	var cErr *C.char
+	var result *C.char
+	if len(args) == 0 {
+		result = C.gluesql_execute(s.glue.handle, s.handle, &cErr)
+	} else {
+		params, err := json.Marshal(args)
+		if err != nil {
+			return nil, err
+		}
+
+		cParams := C.CString(string(params))
+		defer C.free(unsafe.Pointer(cParams))
+
+		result = C.gluesql_execute_with_params(s.glue.handle, s.handle, cParams, &cErr)
+	}
+	if result == nil {
+		return nil, takeError(cErr)
+	}
+
+	return decodePayloads(takeString(result))
+}
+
+// Close releases the prepared statement. It is safe to call Close more than once.
+func (s *Statement) Close() error {
+	if s.handle != nil {
+		C.gluesql_statement_free(s.handle)
+		s.handle = nil
+	}
+
+	return nil
+}
+
+var errClosed = errors.New("gluesql: database is closed")
+
+func takeString(value *C.char) string {
+	defer C.gluesql_string_free(value)
+
+	return C.GoString(value)
+}
+
+func takeError(value *C.char) error {
+	if value == nil {
+		return &Error{Message: "unknown error"}
+	}
+
+	return &Error{Message: takeString(value)}
+}
+
+func decodePayloads(data string) ([]Payload, error) {
+	decoder := json.NewDecoder(bytes.NewReader([]byte(data)))
+	decoder.UseNumber()
+
+	var payloads []Payload
+	if err := decoder.Decode(&payloads); err != nil {
+		return nil, err
+	}
+
+	return payloads, nil
+}
@@ -0,0 +1,226 @@
+package gluesql
+
+import (
+	"context"
+	"database/sql"
+	"errors"
+	"path/filepath"
+	"strings"
+	"testing"
+	"time"
+)
+
+func TestQuery(t *testing.T) {
+	glue := OpenMemory()
+	defer glue.Close()
+
+	payloads, err := glue.Query("CREATE TABLE Foo (id INTEGER, name TEXT); INSERT INTO Foo VALUES (1, 'a'), (2, 'b');")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if payloads[1].Type != "INSERT" || payloads[1].Affected != 2 {
+		t.Fatalf("unexpected payload: %+v", payloads[1])
+	}
+
+	_, err = glue.Query("SELECT * FROM Missing")
+	var glueErr *Error
+	if !errors.As(err, &glueErr) || glueErr.Message != "fetch: table not found: Missing" {
+		t.Fatalf("unexpected error: %v", err)
+	}
+}
+
+func TestDatabaseSQL(t *testing.T) {
+	db, err := sql.Open("gluesql", "sled:"+filepath.Join(t.TempDir(), "db"))
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer db.Close()
+
+	if _, err := db.Exec("CREATE TABLE Item (id INTEGER, name TEXT NULL)"); err != nil {
+		t.Fatal(err)
+	}
+
+	result, err := db.Exec("INSERT INTO Item VALUES (1, 'Glue'), (2, NULL)")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if affected, _ := result.RowsAffected(); affected != 2 {
+		t.Fatalf("expected 2 affected rows, got %d", affected)
+	}
+
+	stmt, err := db.Prepare("SELECT id, name FROM Item ORDER BY id")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer stmt.Close()
+
+	rows, err := stmt.Query()
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer rows.Close()
+
+	columns, _ := rows.Columns()
+	if len(columns) != 2 || columns[0] != "id" || columns[1] != "name" {
+		t.Fatalf("unexpected columns: %v", columns)
+	}
+
+	var ids []int64
+	var names []sql.NullString
+	for rows.Next() {
+		var id int64
+		var name sql.NullString
+		if err := rows.Scan(&id, &name); err != nil {
+			t.Fatal(err)
+		}
+
+		ids = append(ids, id)
+		names = append(names, name)
+	}
+	if len(ids) != 2 || ids[0] != 1 || names[0].String != "Glue" || names[1].Valid {
+		t.Fatalf("unexpected rows: %v %v", ids, names)
+	}
+
+	tx, err := db.Begin()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if _, err := tx.Exec("DELETE FROM Item"); err != nil {
+		t.Fatal(err)
+	}
+	if err := tx.Rollback(); err != nil {
+		t.Fatal(err)
+	}
+
+	var count int64
+	if err := db.QueryRow("SELECT COUNT(*) FROM Item").Scan(&count); err != nil {
+		t.Fatal(err)
+	}
+	if count != 2 {
+		t.Fatalf("rollback should keep 2 rows, got %d", count)
+	}
+}
+
+func TestContextCancellation(t *testing.T) {
+	db, err := sql.Open("gluesql", "memory")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer db.Close()
+
+	ctx, cancel := context.WithCancel(context.Background())
+	cancel()
+
+	if _, err := db.ExecContext(ctx, "CREATE TABLE Foo (id INTEGER)"); !errors.Is(err, context.Canceled) {
+		t.Fatalf("expected context.Canceled, got %v", err)
+	}
+
+	// a statement waiting for the storage held by a transaction gives up
+	// without running
+	if _, err := db.Exec("CREATE TABLE Foo (id INTEGER)"); err != nil {
+		t.Fatal(err)
+	}
+	tx, err := db.Begin()
+	if err != nil {
+		t.Fatal(err)
+	}
+
+	ctx, cancel = context.WithTimeout(context.Background(), 10*time.Millisecond)
+	defer cancel()
+	if _, err := db.ExecContext(ctx, "INSERT INTO Foo VALUES (1)"); !errors.Is(err, context.DeadlineExceeded) {
+		t.Fatalf("expected context.DeadlineExceeded, got %v", err)
+	}
+	if err := tx.Rollback(); err != nil {
+		t.Fatal(err)
+	}
+
+	var count int64
+	if err := db.QueryRow("SELECT COUNT(*) FROM Foo").Scan(&count); err != nil {
+		t.Fatal(err)
+	}
+	if count != 0 {
+		t.Fatalf("expected no rows, got %d", count)
+	}
+
+	// a native call which has started reports its result even if ctx is
+	// cancelled meanwhile
+	dc, err := Driver{}.OpenConnector("memory")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer dc.(*connector).Close()
+
+	c := &conn{connector: dc.(*connector)}
+	ctx, cancel = context.WithCancel(context.Background())
+	payloads, err := c.run(ctx, func() ([]Payload, error) {
+		cancel()
+
+		return c.connector.glue.Query("CREATE TABLE Bar (id INTEGER)")
+	})
+	if err != nil || len(payloads) != 1 || payloads[0].Type != "CREATE TABLE" {
+		t.Fatalf("unexpected result: %+v %v", payloads, err)
+	}
+}
+
+func TestArguments(t *testing.T) {
+	db, err := sql.Open("gluesql", "memory")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer db.Close()
+
+	if _, err := db.Exec("CREATE TABLE Item (id INTEGER, name TEXT NULL, created TIMESTAMP NULL)"); err != nil {
+		t.Fatal(err)
+	}
+
+	created := time.Date(2024, 5, 1, 12, 30, 0, 0, time.UTC)
+	if _, err := db.Exec("INSERT INTO Item VALUES (?, ?, ?)", 1, "it's", created); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := db.Exec("INSERT INTO Item VALUES ($1, $2, NULL)", 2, nil); err != nil {
+		t.Fatal(err)
+	}
+
+	stmt, err := db.Prepare("INSERT INTO Item (id, name) VALUES ($1, $2)")
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer stmt.Close()
+
+	if _, err := stmt.Exec(3, []byte("Glue")); err != nil {
+		t.Fatal(err)
+	}
+	if _, err := stmt.Exec(4); err == nil || !strings.Contains(err.Error(), "expected 2 arguments, got 1") {
+		t.Fatalf("expected an argument count error, got %v", err)
+	}
+
+	var name, at string
+	if err := db.QueryRow("SELECT name, created FROM Item WHERE id = $1", 1).Scan(&name, &at); err != nil {
+		t.Fatal(err)
+	}
+	if name != "it's" || at != "2024-05-01 12:30:00 UTC" {
+		t.Fatalf("unexpected row: %q %q", name, at)
+	}
+
+	var ids []int64
+	rows, err := db.Query("SELECT id FROM Item WHERE id > ? AND name IS NOT NULL ORDER BY id", 1)
+	if err != nil {
+		t.Fatal(err)
+	}
+	defer rows.Close()
+	for rows.Next() {
+		var id int64
+		if err := rows.Scan(&id); err != nil {
+			t.Fatal(err)
+		}
+
+		ids = append(ids, id)
+	}
+	if len(ids) != 1 || ids[0] != 3 {
+		t.Fatalf("unexpected ids: %v", ids)
+	}
+
+	if _, err := db.Exec("SELECT id FROM Item WHERE id = $1", sql.Named("id", 1)); !errors.Is(err, errNamedArgs) {
+		t.Fatalf("expected errNamedArgs, got %v", err)
+	}
+}
@@ -0,0 +1,3 @@
+module github.com/gluesql/gluesql/pkg/go
+
+go 1.21
@@ -0,0 +1,31 @@
+import init, { Glue } from './dist_runtime/gluesql_js.js';
+
+let loaded = false;
+
+// Deno and Bun load the module from disk with their own file APIs instead of
+// the Node.js `fs` based loader used by `gluesql.node.js`.
+async function readWasm() {
+  const url = new URL('./dist_runtime/gluesql_js_bg.wasm', import.meta.url);
+
+  if (typeof Deno !== 'undefined') {
+    return Deno.readFile(url);
+  }
+
+  if (typeof Bun !== 'undefined') {
+    return Bun.file(url).arrayBuffer();
+  }
+
+  const response = await fetch(url);
+
+  return response.arrayBuffer();
+}
+
+export async function gluesql() {
+  if (!loaded) {
+    await init({ module_or_path: await readWasm() });
+
+    loaded = true;
+  }
+
+  return new Glue();
+}
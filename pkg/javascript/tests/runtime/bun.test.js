@@ -0,0 +1,14 @@
+import { expect, test } from 'bun:test';
+import { gluesql } from '../../gluesql.runtime.js';
+
+test('query in Bun', async () => {
+  const db = await gluesql();
+
+  await db.query(`
+    CREATE TABLE Foo (id INTEGER, name TEXT);
+    INSERT INTO Foo VALUES (1, 'glue'), (2, 'sticky');
+  `);
+
+  const [{ rows }] = await db.query('SELECT * FROM Foo WHERE id = 2;');
+  expect(rows).toEqual([{ id: 2, name: 'sticky' }]);
+});
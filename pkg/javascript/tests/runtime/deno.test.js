@@ -0,0 +1,14 @@
+import { assertEquals } from 'jsr:@std/assert@1';
+import { gluesql } from '../../gluesql.runtime.js';
+
+Deno.test('query in Deno', async () => {
+  const db = await gluesql();
+
+  await db.query(`
+    CREATE TABLE Foo (id INTEGER, name TEXT);
+    INSERT INTO Foo VALUES (1, 'glue'), (2, 'sticky');
+  `);
+
+  const [{ rows }] = await db.query('SELECT * FROM Foo WHERE id = 2;');
+  assertEquals(rows, [{ id: 2, name: 'sticky' }]);
+});
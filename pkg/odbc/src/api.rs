@@ -0,0 +1,980 @@
+//! Exported ODBC entry points, called by the driver manager or linked directly.
+//!
+//! # Safety
+//!
+//! Every function follows the ODBC 3.x contract: handles must have been allocated by
+//! `SQLAllocHandle` and not freed yet, input strings must be valid for the given length or
+//! nul-terminated when it is `SQL_NTS`, and output pointers must be null or valid for writes of
+//! the documented size. Bound columns and parameters must stay valid while they are bound.
+
+#![allow(non_snake_case, clippy::missing_safety_doc, clippy::too_many_arguments)]
+
+use {
+    crate::{
+        catalog,
+        error::{OdbcError, Result},
+        handle::{Dbc, Diagnostics, Done, Env, Handle, call},
+        info::{FUNCTIONS, Info, get_info},
+        statement::{Binding, Parameter, Stmt},
+        sys::*,
+        types::{SqlType, Target},
+    },
+    std::{ffi::CStr, ptr},
+};
+
+/// Reads an input string, `None` when the pointer is null.
+unsafe fn input<'a>(
+    text: *const SqlChar,
+    length: SqlInteger,
+    name: &'static str,
+) -> Result<Option<&'a str>> {
+    if text.is_null() {
+        return Ok(None);
+    }
+
+    let bytes = match length {
+        SQL_NTS => unsafe { CStr::from_ptr(text.cast()) }.to_bytes(),
+        length if length >= 0 => unsafe { std::slice::from_raw_parts(text, length as usize) },
+        _ => return Err(OdbcError::InvalidAttributeValue),
+    };
+
+    std::str::from_utf8(bytes)
+        .map(Some)
+        .map_err(|_| OdbcError::InvalidUtf8(name))
+}
+
+unsafe fn required<'a>(
+    text: *const SqlChar,
+    length: SqlInteger,
+    name: &'static str,
+) -> Result<&'a str> {
+    unsafe { input(text, length, name) }?.ok_or(OdbcError::NullPointer(name))
+}
+
+unsafe fn put<T>(pointer: *mut T, value: T) {
+    if !pointer.is_null() {
+        unsafe { ptr::write_unaligned(pointer, value) };
+    }
+}
+
+/// Writes a nul-terminated string into a buffer of `buffer_length` bytes, cut at a character
+/// boundary when it does not fit, and reports its full length through `length`. A null buffer
+/// only asks for the length.
+unsafe fn output<L: TryFrom<usize>>(
+    value: &str,
+    buffer: *mut SqlChar,
+    buffer_length: isize,
+    length: *mut L,
+) -> Done {
+    if let Ok(len) = L::try_from(value.len()) {
+        unsafe { put(length, len) };
+    }
+
+    if buffer.is_null() {
+        return Done::Success;
+    }
+    if buffer_length <= 0 {
+        return match value.is_empty() {
+            true => Done::Success,
+            false => Done::Truncated,
+        };
+    }
+
+    let mut end = value.len().min(buffer_length as usize - 1);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    unsafe {
+        ptr::copy_nonoverlapping(value.as_ptr(), buffer, end);
+        *buffer.add(end) = 0;
+    }
+
+    match end < value.len() {
+        true => Done::Truncated,
+        false => Done::Success,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLAllocHandle(
+    handle_type: SqlSmallInt,
+    input_handle: SqlHandle,
+    output_handle: *mut SqlHandle,
+) -> SqlReturn {
+    if output_handle.is_null() {
+        return SQL_ERROR;
+    }
+
+    unsafe {
+        match handle_type {
+            SQL_HANDLE_ENV => {
+                *output_handle = Box::into_raw(Box::<Env>::default()).cast();
+                SQL_SUCCESS
+            }
+            SQL_HANDLE_DBC => call::<Env>(input_handle, |env| {
+                let dbc = Box::into_raw(Box::new(Dbc::new(env)));
+                env.connections.push(dbc);
+                *output_handle = dbc.cast();
+
+                Ok(Done::Success)
+            }),
+            SQL_HANDLE_STMT => call::<Dbc>(input_handle, |dbc| {
+                if !dbc.is_connected() {
+                    return Err(OdbcError::NotConnected);
+                }
+
+                let stmt = Box::into_raw(Box::new(Stmt::new(dbc)));
+                dbc.statements.push(stmt);
+                *output_handle = stmt.cast();
+
+                Ok(Done::Success)
+            }),
+            _ => SQL_ERROR,
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLFreeHandle(handle_type: SqlSmallInt, handle: SqlHandle) -> SqlReturn {
+    unsafe {
+        match handle_type {
+            SQL_HANDLE_ENV => {
+                let Some(env) = Env::from_raw(handle) else {
+                    return SQL_INVALID_HANDLE;
+                };
+                if !env.connections.is_empty() {
+                    return call::<Env>(handle, |_| Err(OdbcError::OpenConnections));
+                }
+
+                drop(Box::from_raw(handle.cast::<Env>()));
+                SQL_SUCCESS
+            }
+            SQL_HANDLE_DBC => {
+                let Some(dbc) = Dbc::from_raw(handle) else {
+                    return SQL_INVALID_HANDLE;
+                };
+                if dbc.is_connected() {
+                    let _ = dbc.disconnect();
+                }
+                if let Some(env) = dbc.env.as_mut() {
+                    env.connections
+                        .retain(|connection| !ptr::eq(*connection, dbc));
+                }
+
+                drop(Box::from_raw(handle.cast::<Dbc>()));
+                SQL_SUCCESS
+            }
+            SQL_HANDLE_STMT => {
+                let Some(stmt) = Stmt::from_raw(handle) else {
+                    return SQL_INVALID_HANDLE;
+                };
+                let stmt_ptr = stmt as *mut Stmt;
+                stmt.dbc()
+                    .statements
+                    .retain(|statement| !ptr::eq(*statement, stmt_ptr));
+
+                drop(Box::from_raw(stmt_ptr));
+                SQL_SUCCESS
+            }
+            _ => SQL_ERROR,
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLSetEnvAttr(
+    env: SqlHandle,
+    attribute: SqlInteger,
+    value: SqlPointer,
+    _string_length: SqlInteger,
+) -> SqlReturn {
+    unsafe {
+        call::<Env>(env, |env| {
+            match attribute {
+                SQL_ATTR_ODBC_VERSION => env.odbc_version = value as isize as SqlInteger,
+                SQL_ATTR_CONNECTION_POOLING | SQL_ATTR_CP_MATCH => {}
+                SQL_ATTR_OUTPUT_NTS if value as isize == 1 => {}
+                SQL_ATTR_OUTPUT_NTS => return Err(OdbcError::InvalidAttributeValue),
+                attribute => return Err(OdbcError::UnsupportedAttribute(attribute)),
+            }
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLGetEnvAttr(
+    env: SqlHandle,
+    attribute: SqlInteger,
+    value: SqlPointer,
+    _buffer_length: SqlInteger,
+    string_length: *mut SqlInteger,
+) -> SqlReturn {
+    unsafe {
+        call::<Env>(env, |env| {
+            let value = value.cast::<SqlInteger>();
+            match attribute {
+                SQL_ATTR_ODBC_VERSION => put(value, env.odbc_version),
+                SQL_ATTR_OUTPUT_NTS => put(value, 1),
+                SQL_ATTR_CONNECTION_POOLING | SQL_ATTR_CP_MATCH => put(value, 0),
+                attribute => return Err(OdbcError::UnsupportedAttribute(attribute)),
+            }
+            put(string_length, size_of::<SqlInteger>() as SqlInteger);
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLSetConnectAttr(
+    dbc: SqlHandle,
+    attribute: SqlInteger,
+    value: SqlPointer,
+    _string_length: SqlInteger,
+) -> SqlReturn {
+    unsafe {
+        call::<Dbc>(dbc, |dbc| {
+            match (attribute, value as usize) {
+                (SQL_ATTR_AUTOCOMMIT, SQL_AUTOCOMMIT_ON) => dbc.set_autocommit(true)?,
+                (SQL_ATTR_AUTOCOMMIT, SQL_AUTOCOMMIT_OFF) => dbc.set_autocommit(false)?,
+                (SQL_ATTR_TXN_ISOLATION, isolation)
+                    if isolation == SQL_TXN_SERIALIZABLE as usize => {}
+                (SQL_ATTR_LOGIN_TIMEOUT | SQL_ATTR_CONNECTION_TIMEOUT, _) => {}
+                (SQL_ATTR_AUTOCOMMIT | SQL_ATTR_TXN_ISOLATION, _) => {
+                    return Err(OdbcError::InvalidAttributeValue);
+                }
+                (attribute, _) => return Err(OdbcError::UnsupportedAttribute(attribute)),
+            }
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLGetConnectAttr(
+    dbc: SqlHandle,
+    attribute: SqlInteger,
+    value: SqlPointer,
+    _buffer_length: SqlInteger,
+    string_length: *mut SqlInteger,
+) -> SqlReturn {
+    unsafe {
+        call::<Dbc>(dbc, |dbc| {
+            let value = value.cast::<u32>();
+            match attribute {
+                SQL_ATTR_AUTOCOMMIT => put(value, dbc.autocommit as u32),
+                SQL_ATTR_TXN_ISOLATION => put(value, SQL_TXN_SERIALIZABLE),
+                SQL_ATTR_CONNECTION_DEAD => put(
+                    value,
+                    match dbc.is_connected() {
+                        true => SQL_CD_FALSE,
+                        false => SQL_CD_TRUE,
+                    },
+                ),
+                SQL_ATTR_LOGIN_TIMEOUT | SQL_ATTR_CONNECTION_TIMEOUT => put(value, 0),
+                attribute => return Err(OdbcError::UnsupportedAttribute(attribute)),
+            }
+            put(string_length, size_of::<u32>() as SqlInteger);
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+/// Connects with `server_name` holding either a data source name or a connection string;
+/// the user name and password are ignored as GlueSQL has no authentication.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLConnect(
+    dbc: SqlHandle,
+    server_name: *const SqlChar,
+    name_length: SqlSmallInt,
+    _user_name: *const SqlChar,
+    _user_name_length: SqlSmallInt,
+    _authentication: *const SqlChar,
+    _authentication_length: SqlSmallInt,
+) -> SqlReturn {
+    unsafe {
+        call::<Dbc>(dbc, |dbc| {
+            let server_name = required(server_name, name_length.into(), "ServerName")?;
+            match server_name.contains('=') {
+                true => dbc.connect(server_name)?,
+                false => dbc.connect(&format!("DSN={server_name}"))?,
+            }
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLDriverConnect(
+    dbc: SqlHandle,
+    _window_handle: SqlHandle,
+    in_connection_string: *const SqlChar,
+    in_length: SqlSmallInt,
+    out_connection_string: *mut SqlChar,
+    buffer_length: SqlSmallInt,
+    out_length: *mut SqlSmallInt,
+    _driver_completion: SqlUSmallInt,
+) -> SqlReturn {
+    unsafe {
+        call::<Dbc>(dbc, |dbc| {
+            let connection_string =
+                required(in_connection_string, in_length.into(), "InConnectionString")?;
+            dbc.connect(connection_string)?;
+
+            Ok(output(
+                connection_string,
+                out_connection_string,
+                buffer_length.into(),
+                out_length,
+            ))
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLDisconnect(dbc: SqlHandle) -> SqlReturn {
+    unsafe { call::<Dbc>(dbc, |dbc| dbc.disconnect().map(|_| Done::Success)) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLGetInfo(
+    dbc: SqlHandle,
+    info_type: SqlUSmallInt,
+    info_value: SqlPointer,
+    buffer_length: SqlSmallInt,
+    string_length: *mut SqlSmallInt,
+) -> SqlReturn {
+    unsafe {
+        call::<Dbc>(dbc, |dbc| match get_info(dbc, info_type)? {
+            Info::Str(value) => Ok(output(
+                &value,
+                info_value.cast(),
+                buffer_length.into(),
+                string_length,
+            )),
+            Info::U16(value) => {
+                put(info_value.cast(), value);
+                put(string_length, size_of::<u16>() as SqlSmallInt);
+
+                Ok(Done::Success)
+            }
+            Info::U32(value) => {
+                put(info_value.cast(), value);
+                put(string_length, size_of::<u32>() as SqlSmallInt);
+
+                Ok(Done::Success)
+            }
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLGetFunctions(
+    dbc: SqlHandle,
+    function_id: SqlUSmallInt,
+    supported: *mut SqlUSmallInt,
+) -> SqlReturn {
+    unsafe {
+        call::<Dbc>(dbc, |_| {
+            if supported.is_null() {
+                return Err(OdbcError::NullPointer("Supported"));
+            }
+
+            match function_id {
+                SQL_API_ODBC3_ALL_FUNCTIONS => {
+                    let bitmap =
+                        std::slice::from_raw_parts_mut(supported, SQL_API_ODBC3_ALL_FUNCTIONS_SIZE);
+                    bitmap.fill(0);
+                    for id in FUNCTIONS {
+                        bitmap[(id >> 4) as usize] |= 1 << (id & 0xF);
+                    }
+                }
+                SQL_API_ALL_FUNCTIONS => {
+                    let table = std::slice::from_raw_parts_mut(supported, 100);
+                    for (id, supported) in table.iter_mut().enumerate() {
+                        *supported = FUNCTIONS.contains(&(id as SqlUSmallInt)) as SqlUSmallInt;
+                    }
+                }
+                id => *supported = FUNCTIONS.contains(&id) as SqlUSmallInt,
+            }
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLExecDirect(
+    stmt: SqlHandle,
+    text: *const SqlChar,
+    text_length: SqlInteger,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            stmt.execute_direct(required(text, text_length, "StatementText")?)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLPrepare(
+    stmt: SqlHandle,
+    text: *const SqlChar,
+    text_length: SqlInteger,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            stmt.prepare(required(text, text_length, "StatementText")?)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLExecute(stmt: SqlHandle) -> SqlReturn {
+    unsafe { call::<Stmt>(stmt, Stmt::execute) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLNumResultCols(stmt: SqlHandle, count: *mut SqlSmallInt) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            put(count, stmt.columns().len() as SqlSmallInt);
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLNumParams(stmt: SqlHandle, count: *mut SqlSmallInt) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            put(count, stmt.num_params()? as SqlSmallInt);
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLDescribeCol(
+    stmt: SqlHandle,
+    column_number: SqlUSmallInt,
+    column_name: *mut SqlChar,
+    buffer_length: SqlSmallInt,
+    name_length: *mut SqlSmallInt,
+    data_type: *mut SqlSmallInt,
+    column_size: *mut SqlULen,
+    decimal_digits: *mut SqlSmallInt,
+    nullable: *mut SqlSmallInt,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            let column = stmt.column(column_number)?;
+            put(data_type, column.sql_type.code());
+            put(column_size, column.size);
+            put(decimal_digits, column.decimal_digits);
+            put(nullable, column.nullable);
+
+            Ok(output(
+                &column.name,
+                column_name,
+                buffer_length.into(),
+                name_length,
+            ))
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLColAttribute(
+    stmt: SqlHandle,
+    column_number: SqlUSmallInt,
+    field_identifier: SqlUSmallInt,
+    character_attribute: SqlPointer,
+    buffer_length: SqlSmallInt,
+    string_length: *mut SqlSmallInt,
+    numeric_attribute: *mut SqlLen,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            if field_identifier == SQL_DESC_COUNT {
+                put(numeric_attribute, stmt.columns().len() as SqlLen);
+
+                return Ok(Done::Success);
+            }
+
+            let column = stmt.column(column_number)?;
+            let sql_type = column.sql_type;
+            let quoted = matches!(
+                sql_type,
+                SqlType::VarChar
+                    | SqlType::VarBinary
+                    | SqlType::Date
+                    | SqlType::Time
+                    | SqlType::Timestamp
+                    | SqlType::Guid
+            );
+
+            let text = match field_identifier {
+                SQL_DESC_NAME | SQL_DESC_LABEL | SQL_DESC_BASE_COLUMN_NAME | SQL_COLUMN_NAME => {
+                    Some(column.name.as_str())
+                }
+                SQL_DESC_TYPE_NAME | SQL_DESC_LOCAL_TYPE_NAME => Some(sql_type.name()),
+                SQL_DESC_TABLE_NAME
+                | SQL_DESC_BASE_TABLE_NAME
+                | SQL_DESC_SCHEMA_NAME
+                | SQL_DESC_CATALOG_NAME => Some(""),
+                SQL_DESC_LITERAL_PREFIX | SQL_DESC_LITERAL_SUFFIX if quoted => Some("'"),
+                SQL_DESC_LITERAL_PREFIX | SQL_DESC_LITERAL_SUFFIX => Some(""),
+                _ => None,
+            };
+            if let Some(text) = text {
+                return Ok(output(
+                    text,
+                    character_attribute.cast(),
+                    buffer_length.into(),
+                    string_length,
+                ));
+            }
+
+            let value = match field_identifier {
+                SQL_DESC_CONCISE_TYPE => sql_type.code() as SqlLen,
+                SQL_DESC_TYPE => sql_type.verbose_code().0 as SqlLen,
+                SQL_DESC_DISPLAY_SIZE => column.display_size() as SqlLen,
+                SQL_DESC_LENGTH | SQL_DESC_PRECISION | SQL_COLUMN_LENGTH | SQL_COLUMN_PRECISION => {
+                    column.size as SqlLen
+                }
+                SQL_DESC_OCTET_LENGTH => column.octet_length() as SqlLen,
+                SQL_DESC_SCALE | SQL_COLUMN_SCALE => column.decimal_digits as SqlLen,
+                SQL_DESC_NULLABLE | SQL_COLUMN_NULLABLE => column.nullable as SqlLen,
+                SQL_DESC_UNSIGNED => !sql_type.is_numeric() as SqlLen,
+                SQL_DESC_FIXED_PREC_SCALE | SQL_DESC_AUTO_UNIQUE_VALUE => 0,
+                SQL_DESC_CASE_SENSITIVE => (sql_type == SqlType::VarChar) as SqlLen,
+                SQL_DESC_SEARCHABLE => SQL_PRED_SEARCHABLE,
+                SQL_DESC_UPDATABLE => SQL_ATTR_READWRITE_UNKNOWN,
+                SQL_DESC_UNNAMED => SQL_NAMED,
+                SQL_DESC_NUM_PREC_RADIX => match sql_type.is_numeric() {
+                    true => 10,
+                    false => 0,
+                },
+                field => return Err(OdbcError::UnsupportedField(field)),
+            };
+            put(numeric_attribute, value);
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLBindCol(
+    stmt: SqlHandle,
+    column_number: SqlUSmallInt,
+    target_type: SqlSmallInt,
+    target_value: SqlPointer,
+    buffer_length: SqlLen,
+    indicator: *mut SqlLen,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            if column_number == 0 {
+                return Err(OdbcError::InvalidColumn(column_number));
+            }
+
+            match target_value.is_null() && indicator.is_null() {
+                true => {
+                    stmt.bindings.remove(&column_number);
+                }
+                false => {
+                    let target = Target {
+                        c_type: target_type,
+                        pointer: target_value,
+                        buffer_length,
+                    };
+                    stmt.bindings
+                        .insert(column_number, Binding { target, indicator });
+                }
+            }
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+/// Binds an input parameter, substituted as a literal into the statement when it is executed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLBindParameter(
+    stmt: SqlHandle,
+    parameter_number: SqlUSmallInt,
+    input_output_type: SqlSmallInt,
+    value_type: SqlSmallInt,
+    _parameter_type: SqlSmallInt,
+    _column_size: SqlULen,
+    _decimal_digits: SqlSmallInt,
+    parameter_value: SqlPointer,
+    _buffer_length: SqlLen,
+    indicator: *mut SqlLen,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            if parameter_number == 0 {
+                return Err(OdbcError::InvalidColumn(parameter_number));
+            }
+            if input_output_type != SQL_PARAM_INPUT {
+                return Err(OdbcError::UnsupportedParameterType(input_output_type));
+            }
+
+            stmt.parameters.insert(
+                parameter_number,
+                Parameter {
+                    c_type: value_type,
+                    pointer: parameter_value,
+                    indicator,
+                },
+            );
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLFetch(stmt: SqlHandle) -> SqlReturn {
+    unsafe { call::<Stmt>(stmt, Stmt::fetch) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLGetData(
+    stmt: SqlHandle,
+    column_number: SqlUSmallInt,
+    target_type: SqlSmallInt,
+    target_value: SqlPointer,
+    buffer_length: SqlLen,
+    indicator: *mut SqlLen,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            let target = Target {
+                c_type: target_type,
+                pointer: target_value,
+                buffer_length,
+            };
+
+            stmt.get_data(column_number, target, indicator)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLRowCount(stmt: SqlHandle, row_count: *mut SqlLen) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            put(row_count, stmt.row_count());
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLMoreResults(stmt: SqlHandle) -> SqlReturn {
+    unsafe { call::<Stmt>(stmt, Stmt::more_results) }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLCloseCursor(stmt: SqlHandle) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| match stmt.close() {
+            true => Ok(Done::Success),
+            false => Err(OdbcError::NoResultSet),
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLFreeStmt(stmt: SqlHandle, option: SqlUSmallInt) -> SqlReturn {
+    if option == SQL_DROP {
+        return unsafe { SQLFreeHandle(SQL_HANDLE_STMT, stmt) };
+    }
+
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            match option {
+                SQL_CLOSE => {
+                    stmt.close();
+                }
+                SQL_UNBIND => stmt.bindings.clear(),
+                SQL_RESET_PARAMS => stmt.parameters.clear(),
+                option => return Err(OdbcError::UnsupportedAttribute(option.into())),
+            }
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+/// Statements run synchronously, so there is never anything to cancel.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLCancel(stmt: SqlHandle) -> SqlReturn {
+    unsafe { call::<Stmt>(stmt, |_| Ok(Done::Success)) }
+}
+
+unsafe fn diagnostics<'a>(
+    handle_type: SqlSmallInt,
+    handle: SqlHandle,
+) -> Option<&'a mut Diagnostics> {
+    unsafe {
+        match handle_type {
+            SQL_HANDLE_ENV => Env::from_raw(handle).map(Handle::diagnostics),
+            SQL_HANDLE_DBC => Dbc::from_raw(handle).map(Handle::diagnostics),
+            SQL_HANDLE_STMT => Stmt::from_raw(handle).map(Handle::diagnostics),
+            _ => None,
+        }
+    }
+}
+
+fn return_code(done: Done) -> SqlReturn {
+    match done {
+        Done::Success => SQL_SUCCESS,
+        Done::Truncated => SQL_SUCCESS_WITH_INFO,
+        Done::NoData => SQL_NO_DATA,
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLGetDiagRec(
+    handle_type: SqlSmallInt,
+    handle: SqlHandle,
+    record_number: SqlSmallInt,
+    sqlstate: *mut SqlChar,
+    native_error: *mut SqlInteger,
+    message_text: *mut SqlChar,
+    buffer_length: SqlSmallInt,
+    text_length: *mut SqlSmallInt,
+) -> SqlReturn {
+    let Some(diagnostics) = (unsafe { diagnostics(handle_type, handle) }) else {
+        return SQL_INVALID_HANDLE;
+    };
+    if record_number < 1 || buffer_length < 0 {
+        return SQL_ERROR;
+    }
+    let Some(record) = diagnostics.records.get(record_number as usize - 1) else {
+        return SQL_NO_DATA;
+    };
+
+    unsafe {
+        output(record.state, sqlstate, 6, ptr::null_mut::<SqlSmallInt>());
+        put(native_error, 0);
+
+        return_code(output(
+            &record.message,
+            message_text,
+            buffer_length.into(),
+            text_length,
+        ))
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLGetDiagField(
+    handle_type: SqlSmallInt,
+    handle: SqlHandle,
+    record_number: SqlSmallInt,
+    diag_identifier: SqlSmallInt,
+    diag_info: SqlPointer,
+    buffer_length: SqlSmallInt,
+    string_length: *mut SqlSmallInt,
+) -> SqlReturn {
+    let Some(diagnostics) = (unsafe { diagnostics(handle_type, handle) }) else {
+        return SQL_INVALID_HANDLE;
+    };
+
+    unsafe {
+        match diag_identifier {
+            SQL_DIAG_NUMBER => put(diag_info.cast(), diagnostics.records.len() as SqlInteger),
+            SQL_DIAG_RETURNCODE => put(diag_info.cast(), diagnostics.return_code),
+            SQL_DIAG_SQLSTATE | SQL_DIAG_MESSAGE_TEXT | SQL_DIAG_NATIVE => {
+                if record_number < 1 {
+                    return SQL_ERROR;
+                }
+                let Some(record) = diagnostics.records.get(record_number as usize - 1) else {
+                    return SQL_NO_DATA;
+                };
+
+                let text = match diag_identifier {
+                    SQL_DIAG_SQLSTATE => record.state,
+                    SQL_DIAG_MESSAGE_TEXT => &record.message,
+                    _ => {
+                        put(diag_info.cast::<SqlInteger>(), 0);
+                        return SQL_SUCCESS;
+                    }
+                };
+
+                return return_code(output(
+                    text,
+                    diag_info.cast(),
+                    buffer_length.into(),
+                    string_length,
+                ));
+            }
+            _ => return SQL_ERROR,
+        }
+    }
+
+    SQL_SUCCESS
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLEndTran(
+    handle_type: SqlSmallInt,
+    handle: SqlHandle,
+    completion_type: SqlSmallInt,
+) -> SqlReturn {
+    let commit = match completion_type {
+        SQL_COMMIT => true,
+        SQL_ROLLBACK => false,
+        _ => return SQL_ERROR,
+    };
+
+    unsafe {
+        match handle_type {
+            SQL_HANDLE_DBC => call::<Dbc>(handle, |dbc| {
+                dbc.end_transaction(commit).map(|_| Done::Success)
+            }),
+            SQL_HANDLE_ENV => call::<Env>(handle, |env| {
+                for dbc in env.connections.iter() {
+                    let dbc = &mut **dbc;
+                    if dbc.is_connected() {
+                        dbc.end_transaction(commit)?;
+                    }
+                }
+
+                Ok(Done::Success)
+            }),
+            _ => SQL_ERROR,
+        }
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLTables(
+    stmt: SqlHandle,
+    catalog_name: *const SqlChar,
+    catalog_length: SqlSmallInt,
+    schema_name: *const SqlChar,
+    schema_length: SqlSmallInt,
+    table_name: *const SqlChar,
+    table_length: SqlSmallInt,
+    table_type: *const SqlChar,
+    table_type_length: SqlSmallInt,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            let catalog = input(catalog_name, catalog_length.into(), "CatalogName")?;
+            let schema = input(schema_name, schema_length.into(), "SchemaName")?;
+            let table = input(table_name, table_length.into(), "TableName")?;
+            let table_type = input(table_type, table_type_length.into(), "TableType")?;
+
+            stmt.close();
+            let result = catalog::tables(stmt.dbc(), catalog, schema, table, table_type)?;
+            stmt.set_results(vec![result]);
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLColumns(
+    stmt: SqlHandle,
+    _catalog_name: *const SqlChar,
+    _catalog_length: SqlSmallInt,
+    _schema_name: *const SqlChar,
+    _schema_length: SqlSmallInt,
+    table_name: *const SqlChar,
+    table_length: SqlSmallInt,
+    column_name: *const SqlChar,
+    column_length: SqlSmallInt,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            let table = input(table_name, table_length.into(), "TableName")?;
+            let column = input(column_name, column_length.into(), "ColumnName")?;
+
+            stmt.close();
+            let result = catalog::columns(stmt.dbc(), table, column)?;
+            stmt.set_results(vec![result]);
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLGetTypeInfo(stmt: SqlHandle, data_type: SqlSmallInt) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            stmt.set_results(vec![catalog::type_info(data_type)]);
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLSetStmtAttr(
+    stmt: SqlHandle,
+    attribute: SqlInteger,
+    value: SqlPointer,
+    _string_length: SqlInteger,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            match (attribute, value as usize) {
+                (SQL_ATTR_MAX_ROWS, max_rows) => stmt.max_rows = max_rows,
+                (SQL_ATTR_CURSOR_TYPE, SQL_CURSOR_FORWARD_ONLY)
+                | (SQL_ATTR_CONCURRENCY, SQL_CONCUR_READ_ONLY)
+                | (SQL_ATTR_ROW_ARRAY_SIZE, 1)
+                | (SQL_ATTR_QUERY_TIMEOUT | SQL_ATTR_MAX_LENGTH, _) => {}
+                (SQL_ATTR_CURSOR_TYPE | SQL_ATTR_CONCURRENCY | SQL_ATTR_ROW_ARRAY_SIZE, _) => {
+                    return Err(OdbcError::InvalidAttributeValue);
+                }
+                (attribute, _) => return Err(OdbcError::UnsupportedAttribute(attribute)),
+            }
+
+            Ok(Done::Success)
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn SQLGetStmtAttr(
+    stmt: SqlHandle,
+    attribute: SqlInteger,
+    value: SqlPointer,
+    _buffer_length: SqlInteger,
+    string_length: *mut SqlInteger,
+) -> SqlReturn {
+    unsafe {
+        call::<Stmt>(stmt, |stmt| {
+            let attribute_value = match attribute {
+                SQL_ATTR_MAX_ROWS => stmt.max_rows,
+                SQL_ATTR_CURSOR_TYPE => SQL_CURSOR_FORWARD_ONLY,
+                SQL_ATTR_CONCURRENCY => SQL_CONCUR_READ_ONLY,
+                SQL_ATTR_ROW_ARRAY_SIZE => 1,
+                SQL_ATTR_QUERY_TIMEOUT | SQL_ATTR_MAX_LENGTH => 0,
+                attribute => return Err(OdbcError::UnsupportedAttribute(attribute)),
+            };
+            put(value.cast::<SqlULen>(), attribute_value);
+            put(string_length, size_of::<SqlULen>() as SqlInteger);
+
+            Ok(Done::Success)
+        })
+    }
+}
@@ -0,0 +1,348 @@
+//! Result sets of the catalog functions, `SQLTables`, `SQLColumns` and `SQLGetTypeInfo`.
+
+use {
+    crate::{
+        error::Result,
+        handle::Dbc,
+        statement::ResultSet,
+        sys::*,
+        types::{Column, SqlType},
+    },
+    gluesql_core::{
+        ast::{DataType, ToSql},
+        prelude::Value,
+    },
+};
+
+fn result_set(columns: &[(&str, SqlType)], rows: Vec<Vec<Value>>) -> ResultSet {
+    let columns = columns
+        .iter()
+        .map(|(name, sql_type)| Column::new(name, *sql_type))
+        .collect();
+
+    ResultSet::Rows { columns, rows }
+}
+
+fn text(value: impl Into<String>) -> Value {
+    Value::Str(value.into())
+}
+
+fn optional_text(value: Option<impl Into<String>>) -> Value {
+    value.map(text).unwrap_or(Value::Null)
+}
+
+/// Matches `name` against a search pattern where `%` and `_` are wildcards and `\` escapes.
+/// A missing pattern matches everything.
+fn matches(pattern: Option<&str>, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern {
+            [] => name.is_empty(),
+            ['%', rest @ ..] => (0..=name.len()).any(|i| matches(rest, &name[i..])),
+            ['_', rest @ ..] => !name.is_empty() && matches(rest, &name[1..]),
+            ['\\', c, rest @ ..] | [c, rest @ ..] => {
+                name.first() == Some(c) && matches(rest, &name[1..])
+            }
+        }
+    }
+
+    let Some(pattern) = pattern else {
+        return true;
+    };
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+
+    matches(&pattern, &name)
+}
+
+const TABLES: &[(&str, SqlType)] = &[
+    ("TABLE_CAT", SqlType::VarChar),
+    ("TABLE_SCHEM", SqlType::VarChar),
+    ("TABLE_NAME", SqlType::VarChar),
+    ("TABLE_TYPE", SqlType::VarChar),
+    ("REMARKS", SqlType::VarChar),
+];
+
+/// GlueSQL has neither catalogs nor schemas, so only the table listing and the table type
+/// enumeration of `SQLTables` return rows.
+pub fn tables(
+    dbc: &Dbc,
+    catalog: Option<&str>,
+    schema: Option<&str>,
+    table: Option<&str>,
+    table_type: Option<&str>,
+) -> Result<ResultSet> {
+    let empty = |value: Option<&str>| value.is_none_or(str::is_empty);
+
+    if catalog == Some("%") && empty(schema) && empty(table) {
+        return Ok(result_set(TABLES, Vec::new()));
+    }
+    if schema == Some("%") && empty(catalog) && empty(table) {
+        return Ok(result_set(TABLES, Vec::new()));
+    }
+    if table_type == Some("%") && empty(catalog) && empty(schema) && empty(table) {
+        let row = vec![
+            Value::Null,
+            Value::Null,
+            Value::Null,
+            text("TABLE"),
+            Value::Null,
+        ];
+
+        return Ok(result_set(TABLES, vec![row]));
+    }
+
+    let lists_tables = table_type.is_none_or(|types| {
+        types.is_empty()
+            || types
+                .split(',')
+                .map(|table_type| table_type.trim().trim_matches('\'').to_uppercase())
+                .any(|table_type| table_type == "TABLE" || table_type == "%")
+    });
+    if !lists_tables {
+        return Ok(result_set(TABLES, Vec::new()));
+    }
+
+    let rows = dbc
+        .schemas()?
+        .into_iter()
+        .filter(|schema| matches(table, &schema.table_name))
+        .map(|schema| {
+            vec![
+                Value::Null,
+                Value::Null,
+                text(schema.table_name),
+                text("TABLE"),
+                optional_text(schema.comment),
+            ]
+        })
+        .collect();
+
+    Ok(result_set(TABLES, rows))
+}
+
+const COLUMNS: &[(&str, SqlType)] = &[
+    ("TABLE_CAT", SqlType::VarChar),
+    ("TABLE_SCHEM", SqlType::VarChar),
+    ("TABLE_NAME", SqlType::VarChar),
+    ("COLUMN_NAME", SqlType::VarChar),
+    ("DATA_TYPE", SqlType::SmallInt),
+    ("TYPE_NAME", SqlType::VarChar),
+    ("COLUMN_SIZE", SqlType::Integer),
+    ("BUFFER_LENGTH", SqlType::Integer),
+    ("DECIMAL_DIGITS", SqlType::SmallInt),
+    ("NUM_PREC_RADIX", SqlType::SmallInt),
+    ("NULLABLE", SqlType::SmallInt),
+    ("REMARKS", SqlType::VarChar),
+    ("COLUMN_DEF", SqlType::VarChar),
+    ("SQL_DATA_TYPE", SqlType::SmallInt),
+    ("SQL_DATETIME_SUB", SqlType::SmallInt),
+    ("CHAR_OCTET_LENGTH", SqlType::Integer),
+    ("ORDINAL_POSITION", SqlType::Integer),
+    ("IS_NULLABLE", SqlType::VarChar),
+];
+
+/// Lists the columns of tables with a declared schema; schemaless tables have none to report.
+pub fn columns(dbc: &Dbc, table: Option<&str>, column: Option<&str>) -> Result<ResultSet> {
+    let mut rows = Vec::new();
+
+    for schema in dbc.schemas()? {
+        if !matches(table, &schema.table_name) {
+            continue;
+        }
+
+        for (i, column_def) in schema.column_defs.iter().flatten().enumerate() {
+            if !matches(column, &column_def.name) {
+                continue;
+            }
+
+            let sql_type = SqlType::from(&column_def.data_type);
+            let described = Column::new(&column_def.name, sql_type);
+            let (verbose_type, datetime_sub) = sql_type.verbose_code();
+            let is_variable = matches!(sql_type, SqlType::VarChar | SqlType::VarBinary);
+
+            rows.push(vec![
+                Value::Null,
+                Value::Null,
+                text(schema.table_name.as_str()),
+                text(column_def.name.as_str()),
+                Value::I16(sql_type.code()),
+                text(column_def.data_type.to_string()),
+                Value::I32(described.size as i32),
+                Value::I32(described.octet_length() as i32),
+                match sql_type.is_numeric() || sql_type == SqlType::Timestamp {
+                    true => Value::I16(described.decimal_digits),
+                    false => Value::Null,
+                },
+                match sql_type.is_numeric() {
+                    true => Value::I16(10),
+                    false => Value::Null,
+                },
+                Value::I16(match column_def.nullable {
+                    true => SQL_NULLABLE,
+                    false => SQL_NO_NULLS,
+                }),
+                optional_text(column_def.comment.clone()),
+                optional_text(column_def.default.as_ref().map(ToSql::to_sql)),
+                Value::I16(verbose_type),
+                match datetime_sub {
+                    0 => Value::Null,
+                    sub => Value::I16(sub),
+                },
+                match is_variable {
+                    true => Value::I32(described.octet_length() as i32),
+                    false => Value::Null,
+                },
+                Value::I32(i as i32 + 1),
+                text(match column_def.nullable {
+                    true => "YES",
+                    false => "NO",
+                }),
+            ]);
+        }
+    }
+
+    Ok(result_set(COLUMNS, rows))
+}
+
+const TYPE_INFO: &[(&str, SqlType)] = &[
+    ("TYPE_NAME", SqlType::VarChar),
+    ("DATA_TYPE", SqlType::SmallInt),
+    ("COLUMN_SIZE", SqlType::Integer),
+    ("LITERAL_PREFIX", SqlType::VarChar),
+    ("LITERAL_SUFFIX", SqlType::VarChar),
+    ("CREATE_PARAMS", SqlType::VarChar),
+    ("NULLABLE", SqlType::SmallInt),
+    ("CASE_SENSITIVE", SqlType::SmallInt),
+    ("SEARCHABLE", SqlType::SmallInt),
+    ("UNSIGNED_ATTRIBUTE", SqlType::SmallInt),
+    ("FIXED_PREC_SCALE", SqlType::SmallInt),
+    ("AUTO_UNIQUE_VALUE", SqlType::SmallInt),
+    ("LOCAL_TYPE_NAME", SqlType::VarChar),
+    ("MINIMUM_SCALE", SqlType::SmallInt),
+    ("MAXIMUM_SCALE", SqlType::SmallInt),
+    ("SQL_DATA_TYPE", SqlType::SmallInt),
+    ("SQL_DATETIME_SUB", SqlType::SmallInt),
+    ("NUM_PREC_RADIX", SqlType::Integer),
+    ("INTERVAL_PRECISION", SqlType::SmallInt),
+];
+
+const DATA_TYPES: &[DataType] = &[
+    DataType::Boolean,
+    DataType::Int8,
+    DataType::Int16,
+    DataType::Int32,
+    DataType::Int,
+    DataType::Int128,
+    DataType::Uint8,
+    DataType::Uint16,
+    DataType::Uint32,
+    DataType::Uint64,
+    DataType::Uint128,
+    DataType::Float32,
+    DataType::Float,
+    DataType::Decimal,
+    DataType::Text,
+    DataType::Bytea,
+    DataType::Inet,
+    DataType::Date,
+    DataType::Time,
+    DataType::Timestamp,
+    DataType::Interval,
+    DataType::Uuid,
+    DataType::Map,
+    DataType::List,
+    DataType::Point,
+];
+
+/// Describes the GlueSQL data types mapping to `data_type`, or all of them for `SQL_ALL_TYPES`.
+pub fn type_info(data_type: SqlSmallInt) -> ResultSet {
+    let mut types = DATA_TYPES
+        .iter()
+        .map(|data_type| (data_type, SqlType::from(data_type)))
+        .filter(|(_, sql_type)| data_type == SQL_ALL_TYPES || sql_type.code() == data_type)
+        .collect::<Vec<_>>();
+    types.sort_by_key(|(_, sql_type)| sql_type.code());
+
+    let rows = types
+        .into_iter()
+        .map(|(data_type, sql_type)| {
+            let column = Column::new("", sql_type);
+            let (verbose_type, datetime_sub) = sql_type.verbose_code();
+            let quoted = matches!(
+                sql_type,
+                SqlType::VarChar
+                    | SqlType::VarBinary
+                    | SqlType::Date
+                    | SqlType::Time
+                    | SqlType::Timestamp
+                    | SqlType::Guid
+            );
+            let unsigned = matches!(
+                data_type,
+                DataType::Uint8
+                    | DataType::Uint16
+                    | DataType::Uint32
+                    | DataType::Uint64
+                    | DataType::Uint128
+            );
+            let flag = |value: bool| Value::I16(value as i16);
+            let numeric = |value: Value| match sql_type.is_numeric() {
+                true => value,
+                false => Value::Null,
+            };
+
+            vec![
+                text(data_type.to_string()),
+                Value::I16(sql_type.code()),
+                Value::I32(column.size as i32),
+                match (quoted, sql_type) {
+                    (true, SqlType::VarBinary) => text("X'"),
+                    (true, _) => text("'"),
+                    (false, _) => Value::Null,
+                },
+                match quoted {
+                    true => text("'"),
+                    false => Value::Null,
+                },
+                Value::Null,
+                Value::I16(SQL_NULLABLE),
+                flag(sql_type == SqlType::VarChar),
+                Value::I16(SQL_PRED_SEARCHABLE as i16),
+                numeric(flag(unsigned)),
+                flag(false),
+                numeric(flag(false)),
+                Value::Null,
+                numeric(Value::I16(0)),
+                numeric(Value::I16(match sql_type {
+                    SqlType::Numeric => 38,
+                    _ => 0,
+                })),
+                Value::I16(verbose_type),
+                match datetime_sub {
+                    0 => Value::Null,
+                    sub => Value::I16(sub),
+                },
+                numeric(Value::I32(10)),
+                Value::Null,
+            ]
+        })
+        .collect();
+
+    result_set(TYPE_INFO, rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::matches;
+
+    #[test]
+    fn search_pattern() {
+        assert!(matches(None, "Foo"));
+        assert!(matches(Some("Foo"), "Foo"));
+        assert!(matches(Some("F%"), "Foo"));
+        assert!(matches(Some("F_o"), "Foo"));
+        assert!(matches(Some("my\\_table"), "my_table"));
+        assert!(!matches(Some("my\\_table"), "myXtable"));
+        assert!(!matches(Some("Bar%"), "Foo"));
+    }
+}
@@ -0,0 +1,104 @@
+use {
+    gluesql_core::{data::ConvertError, error::Error as GlueError},
+    thiserror::Error as ThisError,
+};
+
+pub type Result<T, E = OdbcError> = std::result::Result<T, E>;
+
+#[derive(ThisError, Debug)]
+pub enum OdbcError {
+    #[error(transparent)]
+    Glue(#[from] GlueError),
+
+    #[error(transparent)]
+    Convert(#[from] ConvertError),
+
+    #[error("invalid connection string: {0}")]
+    InvalidConnectionString(String),
+
+    #[error("failed to open storage: {0}")]
+    StorageOpen(String),
+
+    #[error("connection is already open")]
+    AlreadyConnected,
+
+    #[error("connection is not open")]
+    NotConnected,
+
+    #[error("environment has allocated connections")]
+    OpenConnections,
+
+    #[error("no result set is open")]
+    NoResultSet,
+
+    #[error("statement has not been prepared")]
+    NotPrepared,
+
+    #[error("column {0} does not exist")]
+    InvalidColumn(u16),
+
+    #[error("parameter {0} is not bound")]
+    UnboundParameter(usize),
+
+    #[error("data-at-execution parameters are not supported")]
+    DataAtExecution,
+
+    #[error("unsupported parameter type {0}, only input parameters are supported")]
+    UnsupportedParameterType(i16),
+
+    #[error("unsupported C data type {0}")]
+    UnsupportedCType(i16),
+
+    #[error("unsupported attribute {0}")]
+    UnsupportedAttribute(i32),
+
+    #[error("unsupported information type {0}")]
+    UnsupportedInfoType(u16),
+
+    #[error("unsupported field identifier {0}")]
+    UnsupportedField(u16),
+
+    #[error("invalid attribute value")]
+    InvalidAttributeValue,
+
+    #[error("null pointer: {0}")]
+    NullPointer(&'static str),
+
+    #[error("invalid utf-8 string: {0}")]
+    InvalidUtf8(&'static str),
+
+    #[error("numeric value out of range")]
+    OutOfRange,
+
+    #[error("string data, right truncated")]
+    Truncated,
+}
+
+impl OdbcError {
+    /// SQLSTATE reported through `SQLGetDiagRec`.
+    pub fn sqlstate(&self) -> &'static str {
+        match self {
+            Self::Glue(GlueError::Parser(_)) => "42000",
+            Self::Glue(_) => "HY000",
+            Self::Convert(_) => "22018",
+            Self::InvalidConnectionString(_) | Self::StorageOpen(_) => "08001",
+            Self::AlreadyConnected => "08002",
+            Self::NotConnected => "08003",
+            Self::OpenConnections | Self::NotPrepared => "HY010",
+            Self::NoResultSet => "24000",
+            Self::InvalidColumn(_) => "07009",
+            Self::UnboundParameter(_) => "07002",
+            Self::DataAtExecution => "HYC00",
+            Self::UnsupportedParameterType(_) => "HY105",
+            Self::UnsupportedCType(_) => "HY003",
+            Self::UnsupportedAttribute(_) => "HYC00",
+            Self::UnsupportedInfoType(_) => "HY096",
+            Self::UnsupportedField(_) => "HY091",
+            Self::InvalidAttributeValue => "HY024",
+            Self::NullPointer(_) => "HY009",
+            Self::InvalidUtf8(_) => "HY090",
+            Self::OutOfRange => "22003",
+            Self::Truncated => "01004",
+        }
+    }
+}
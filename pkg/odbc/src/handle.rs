@@ -0,0 +1,267 @@
+use {
+    crate::{
+        error::{OdbcError, Result},
+        statement::Stmt,
+        storages::{Storage, parse_connection_string, with_storage},
+        sys::*,
+    },
+    futures::executor::block_on,
+    gluesql_core::{
+        ast::Statement,
+        data::Schema,
+        prelude::{Payload, execute, parse, plan, translate},
+        store::Store,
+    },
+};
+
+/// Diagnostic record reported through `SQLGetDiagRec` and `SQLGetDiagField`.
+pub struct Diag {
+    pub state: &'static str,
+    pub message: String,
+}
+
+/// Records of the last function called on a handle, cleared whenever another one is called.
+#[derive(Default)]
+pub struct Diagnostics {
+    pub records: Vec<Diag>,
+    pub return_code: SqlReturn,
+}
+
+impl Diagnostics {
+    fn push(&mut self, error: &OdbcError) {
+        self.records.push(Diag {
+            state: error.sqlstate(),
+            message: format!("[GlueSQL][ODBC] {error}"),
+        });
+    }
+}
+
+/// Outcome of a function which completed without an error.
+pub enum Done {
+    Success,
+    /// Data was truncated to fit the application buffer, reported as `01004`.
+    Truncated,
+    NoData,
+}
+
+/// Handle which can be passed across the C ABI.
+///
+/// Every handle type is `#[repr(C)]` with a distinct tag as its first field, so a handle of the
+/// wrong type is reported as `SQL_INVALID_HANDLE` instead of being misread.
+pub trait Handle: Sized {
+    const TAG: u32;
+
+    fn diagnostics(&mut self) -> &mut Diagnostics;
+
+    /// # Safety
+    /// `handle` must be null or a live handle allocated by `SQLAllocHandle`.
+    unsafe fn from_raw<'a>(handle: SqlHandle) -> Option<&'a mut Self> {
+        if handle.is_null() || unsafe { *handle.cast::<u32>() } != Self::TAG {
+            return None;
+        }
+
+        Some(unsafe { &mut *handle.cast::<Self>() })
+    }
+}
+
+/// Runs `f` against `handle`, recording its diagnostics and mapping the outcome to a return code.
+///
+/// # Safety
+/// `handle` must be null or a live handle allocated by `SQLAllocHandle`.
+pub unsafe fn call<H: Handle>(
+    handle: SqlHandle,
+    f: impl FnOnce(&mut H) -> Result<Done>,
+) -> SqlReturn {
+    let Some(handle) = (unsafe { H::from_raw(handle) }) else {
+        return SQL_INVALID_HANDLE;
+    };
+    handle.diagnostics().records.clear();
+
+    let return_code = match f(handle) {
+        Ok(Done::Success) => SQL_SUCCESS,
+        Ok(Done::Truncated) => {
+            handle.diagnostics().push(&OdbcError::Truncated);
+            SQL_SUCCESS_WITH_INFO
+        }
+        Ok(Done::NoData) => SQL_NO_DATA,
+        Err(error) => {
+            handle.diagnostics().push(&error);
+            SQL_ERROR
+        }
+    };
+    handle.diagnostics().return_code = return_code;
+
+    return_code
+}
+
+#[repr(C)]
+pub struct Env {
+    tag: u32,
+    pub diagnostics: Diagnostics,
+    pub odbc_version: SqlInteger,
+    pub connections: Vec<*mut Dbc>,
+}
+
+impl Handle for Env {
+    const TAG: u32 = 0x474C_0001;
+
+    fn diagnostics(&mut self) -> &mut Diagnostics {
+        &mut self.diagnostics
+    }
+}
+
+impl Default for Env {
+    fn default() -> Self {
+        Self {
+            tag: Self::TAG,
+            diagnostics: Diagnostics::default(),
+            odbc_version: SQL_OV_ODBC3,
+            connections: Vec::new(),
+        }
+    }
+}
+
+#[repr(C)]
+pub struct Dbc {
+    tag: u32,
+    pub diagnostics: Diagnostics,
+    pub env: *mut Env,
+    pub statements: Vec<*mut Stmt>,
+    pub autocommit: bool,
+    /// Data source name the connection was opened with, for `SQL_DATA_SOURCE_NAME`.
+    pub dsn: String,
+    storage: Option<Storage>,
+    in_transaction: bool,
+}
+
+impl Handle for Dbc {
+    const TAG: u32 = 0x474C_0002;
+
+    fn diagnostics(&mut self) -> &mut Diagnostics {
+        &mut self.diagnostics
+    }
+}
+
+impl Dbc {
+    pub fn new(env: *mut Env) -> Self {
+        Self {
+            tag: Self::TAG,
+            diagnostics: Diagnostics::default(),
+            env,
+            statements: Vec::new(),
+            autocommit: true,
+            dsn: String::new(),
+            storage: None,
+            in_transaction: false,
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.storage.is_some()
+    }
+
+    /// Opens the storage described by a connection string such as `STORAGE=sled;PATH=data`.
+    pub fn connect(&mut self, connection_string: &str) -> Result<()> {
+        if self.is_connected() {
+            return Err(OdbcError::AlreadyConnected);
+        }
+
+        let attributes = parse_connection_string(connection_string)?;
+        self.storage = Some(Storage::open(&attributes)?);
+        self.dsn = attributes.get("DSN").cloned().unwrap_or_default();
+
+        Ok(())
+    }
+
+    /// Rolls back an open transaction and frees the statements of the connection.
+    pub fn disconnect(&mut self) -> Result<()> {
+        if !self.is_connected() {
+            return Err(OdbcError::NotConnected);
+        }
+
+        let rollback = self.end_transaction(false);
+        for statement in self.statements.drain(..) {
+            drop(unsafe { Box::from_raw(statement) });
+        }
+        self.storage = None;
+        self.in_transaction = false;
+
+        rollback
+    }
+
+    /// Executes every statement in `sql`. With autocommit off, a transaction is started ahead
+    /// of the first statement and kept open until `SQLEndTran`.
+    pub fn run(&mut self, sql: &str) -> Result<Vec<Payload>> {
+        let Self {
+            storage,
+            autocommit,
+            in_transaction,
+            ..
+        } = self;
+        let storage = storage.as_mut().ok_or(OdbcError::NotConnected)?;
+
+        block_on(async {
+            let mut payloads = Vec::new();
+            for parsed in parse(sql)?.iter() {
+                let statement = translate(parsed)?;
+                let statement =
+                    with_storage!(&*storage, storage => plan(storage, statement).await)?;
+                let is_control = matches!(
+                    statement,
+                    Statement::StartTransaction | Statement::Commit | Statement::Rollback
+                );
+
+                if !*autocommit && !*in_transaction && !is_control {
+                    with_storage!(&mut *storage, storage => execute(storage, &Statement::StartTransaction).await)?;
+                    *in_transaction = true;
+                }
+
+                let payload =
+                    with_storage!(&mut *storage, storage => execute(storage, &statement).await)?;
+                match payload {
+                    Payload::StartTransaction => *in_transaction = true,
+                    Payload::Commit | Payload::Rollback => *in_transaction = false,
+                    _ => {}
+                }
+
+                payloads.push(payload);
+            }
+
+            Ok(payloads)
+        })
+    }
+
+    /// Commits or rolls back the open transaction, if there is one.
+    pub fn end_transaction(&mut self, commit: bool) -> Result<()> {
+        if !self.in_transaction {
+            return Ok(());
+        }
+
+        let statement = match commit {
+            true => Statement::Commit,
+            false => Statement::Rollback,
+        };
+        let storage = self.storage.as_mut().ok_or(OdbcError::NotConnected)?;
+        block_on(async { with_storage!(storage, storage => execute(storage, &statement).await) })?;
+        self.in_transaction = false;
+
+        Ok(())
+    }
+
+    /// Switches autocommit mode, committing the open transaction when it gets turned on.
+    pub fn set_autocommit(&mut self, autocommit: bool) -> Result<()> {
+        if autocommit && !self.autocommit {
+            self.end_transaction(true)?;
+        }
+        self.autocommit = autocommit;
+
+        Ok(())
+    }
+
+    pub fn schemas(&self) -> Result<Vec<Schema>> {
+        let storage = self.storage.as_ref().ok_or(OdbcError::NotConnected)?;
+
+        block_on(async { with_storage!(storage, storage => storage.fetch_all_schemas().await) })
+            .map_err(Into::into)
+    }
+}
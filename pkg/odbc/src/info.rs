@@ -0,0 +1,104 @@
+//! Values returned by `SQLGetInfo` and `SQLGetFunctions`.
+
+use crate::{
+    error::{OdbcError, Result},
+    handle::Dbc,
+    sys::*,
+};
+
+pub enum Info {
+    Str(String),
+    U16(u16),
+    U32(u32),
+}
+
+/// Version formatted as `##.##.####`, as ODBC expects for driver and DBMS versions.
+fn version() -> String {
+    let part = |value: &str| value.parse::<u32>().unwrap_or_default();
+
+    format!(
+        "{:02}.{:02}.{:04}",
+        part(env!("CARGO_PKG_VERSION_MAJOR")),
+        part(env!("CARGO_PKG_VERSION_MINOR")),
+        part(env!("CARGO_PKG_VERSION_PATCH")),
+    )
+}
+
+pub fn get_info(dbc: &Dbc, info_type: SqlUSmallInt) -> Result<Info> {
+    let text = |value: &str| Info::Str(value.to_owned());
+
+    let info = match info_type {
+        SQL_DRIVER_NAME => text("gluesql_odbc"),
+        SQL_DRIVER_VER | SQL_DBMS_VER => Info::Str(version()),
+        SQL_DRIVER_ODBC_VER => text("03.00"),
+        SQL_DBMS_NAME => text("GlueSQL"),
+        SQL_DATA_SOURCE_NAME => Info::Str(dbc.dsn.clone()),
+        SQL_SERVER_NAME | SQL_DATABASE_NAME | SQL_USER_NAME | SQL_KEYWORDS => text(""),
+        SQL_CATALOG_TERM | SQL_SCHEMA_TERM | SQL_CATALOG_NAME_SEPARATOR => text(""),
+        SQL_TABLE_TERM => text("table"),
+        SQL_IDENTIFIER_QUOTE_CHAR => text("\""),
+        SQL_SEARCH_PATTERN_ESCAPE => text("\\"),
+        SQL_CATALOG_NAME | SQL_DATA_SOURCE_READ_ONLY | SQL_MULTIPLE_ACTIVE_TXN => text("N"),
+        SQL_NEED_LONG_DATA_LEN => text("N"),
+        SQL_ACCESSIBLE_TABLES | SQL_MULT_RESULT_SETS | SQL_COLUMN_ALIAS => text("Y"),
+        SQL_MAX_DRIVER_CONNECTIONS | SQL_MAX_CONCURRENT_ACTIVITIES => Info::U16(0),
+        SQL_MAX_COLUMN_NAME_LEN
+        | SQL_MAX_SCHEMA_NAME_LEN
+        | SQL_MAX_CATALOG_NAME_LEN
+        | SQL_MAX_TABLE_NAME_LEN
+        | SQL_MAX_IDENTIFIER_LEN => Info::U16(0),
+        SQL_TXN_CAPABLE => Info::U16(SQL_TC_ALL),
+        SQL_CURSOR_COMMIT_BEHAVIOR | SQL_CURSOR_ROLLBACK_BEHAVIOR => Info::U16(SQL_CB_PRESERVE),
+        SQL_CONCAT_NULL_BEHAVIOR => Info::U16(SQL_CB_NULL),
+        SQL_IDENTIFIER_CASE | SQL_QUOTED_IDENTIFIER_CASE => Info::U16(SQL_IC_SENSITIVE),
+        SQL_DEFAULT_TXN_ISOLATION | SQL_TXN_ISOLATION_OPTION => Info::U32(SQL_TXN_SERIALIZABLE),
+        SQL_GETDATA_EXTENSIONS => Info::U32(SQL_GD_ANY_COLUMN | SQL_GD_ANY_ORDER),
+        SQL_SCROLL_OPTIONS => Info::U32(SQL_SO_FORWARD_ONLY),
+        SQL_SQL_CONFORMANCE => Info::U32(SQL_SC_SQL92_ENTRY),
+        SQL_ODBC_INTERFACE_CONFORMANCE => Info::U32(SQL_OIC_CORE),
+        SQL_CURSOR_SENSITIVITY => Info::U32(SQL_INSENSITIVE),
+        SQL_ASYNC_MODE => Info::U32(SQL_AM_NONE),
+        SQL_SCHEMA_USAGE | SQL_CATALOG_USAGE => Info::U32(0),
+        info_type => return Err(OdbcError::UnsupportedInfoType(info_type)),
+    };
+
+    Ok(info)
+}
+
+pub const FUNCTIONS: &[SqlUSmallInt] = &[
+    SQL_API_SQLALLOCHANDLE,
+    SQL_API_SQLBINDCOL,
+    SQL_API_SQLBINDPARAMETER,
+    SQL_API_SQLCANCEL,
+    SQL_API_SQLCLOSECURSOR,
+    SQL_API_SQLCOLATTRIBUTE,
+    SQL_API_SQLCOLUMNS,
+    SQL_API_SQLCONNECT,
+    SQL_API_SQLDESCRIBECOL,
+    SQL_API_SQLDISCONNECT,
+    SQL_API_SQLDRIVERCONNECT,
+    SQL_API_SQLENDTRAN,
+    SQL_API_SQLEXECDIRECT,
+    SQL_API_SQLEXECUTE,
+    SQL_API_SQLFETCH,
+    SQL_API_SQLFREEHANDLE,
+    SQL_API_SQLFREESTMT,
+    SQL_API_SQLGETCONNECTATTR,
+    SQL_API_SQLGETDATA,
+    SQL_API_SQLGETDIAGFIELD,
+    SQL_API_SQLGETDIAGREC,
+    SQL_API_SQLGETENVATTR,
+    SQL_API_SQLGETFUNCTIONS,
+    SQL_API_SQLGETINFO,
+    SQL_API_SQLGETSTMTATTR,
+    SQL_API_SQLGETTYPEINFO,
+    SQL_API_SQLMORERESULTS,
+    SQL_API_SQLNUMPARAMS,
+    SQL_API_SQLNUMRESULTCOLS,
+    SQL_API_SQLPREPARE,
+    SQL_API_SQLROWCOUNT,
+    SQL_API_SQLSETCONNECTATTR,
+    SQL_API_SQLSETENVATTR,
+    SQL_API_SQLSETSTMTATTR,
+    SQL_API_SQLTABLES,
+];
@@ -0,0 +1,21 @@
+#![deny(clippy::str_to_string)]
+
+//! ODBC driver for GlueSQL.
+//!
+//! The library exports the core ODBC 3.x functions with the C ABI, so it can be registered with
+//! a driver manager such as unixODBC or iODBC, or linked by applications directly. Connecting
+//! opens an embedded storage selected by the `STORAGE` and `PATH` connection string attributes,
+//! and every statement runs in process against it.
+
+mod api;
+mod catalog;
+mod error;
+mod handle;
+mod info;
+mod statement;
+mod storages;
+mod types;
+
+pub mod sys;
+
+pub use {api::*, error::OdbcError};
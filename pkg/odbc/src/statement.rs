@@ -0,0 +1,401 @@
+use {
+    crate::{
+        error::{OdbcError, Result},
+        handle::{Dbc, Diagnostics, Done, Handle},
+        sys::*,
+        types::{Column, Target, literal, read_parameter},
+    },
+    gluesql_core::prelude::{Payload, PayloadVariable, Value},
+    std::collections::{BTreeMap, BTreeSet, VecDeque},
+};
+
+/// Result of a single SQL statement, consumed in order through `SQLMoreResults`.
+pub enum ResultSet {
+    Rows {
+        columns: Vec<Column>,
+        rows: Vec<Vec<Value>>,
+    },
+    /// Number of affected rows, `None` for statements which do not report one.
+    Affected(Option<usize>),
+}
+
+impl ResultSet {
+    fn rows(labels: Vec<String>, rows: Vec<Vec<Value>>) -> Self {
+        let columns = labels
+            .iter()
+            .enumerate()
+            .map(|(i, label)| Column::infer(label, rows.iter().map(move |row| &row[i])))
+            .collect();
+
+        Self::Rows { columns, rows }
+    }
+}
+
+impl From<Payload> for ResultSet {
+    fn from(payload: Payload) -> Self {
+        let labeled = |label: &str, values: Vec<String>| {
+            Self::rows(
+                vec![label.to_owned()],
+                values
+                    .into_iter()
+                    .map(|value| vec![Value::Str(value)])
+                    .collect(),
+            )
+        };
+
+        match payload {
+            Payload::Select { labels, rows } | Payload::Returning { labels, rows } => {
+                Self::rows(labels, rows)
+            }
+            Payload::SelectMap(maps) => {
+                let labels = maps
+                    .iter()
+                    .flat_map(|map| map.keys().cloned())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                let rows = maps
+                    .into_iter()
+                    .map(|mut map| {
+                        labels
+                            .iter()
+                            .map(|label| map.remove(label).unwrap_or(Value::Null))
+                            .collect()
+                    })
+                    .collect();
+
+                Self::rows(labels, rows)
+            }
+            Payload::ShowColumns(columns) => Self::rows(
+                vec!["field".to_owned(), "type".to_owned()],
+                columns
+                    .into_iter()
+                    .map(|(name, data_type)| {
+                        vec![Value::Str(name), Value::Str(data_type.to_string())]
+                    })
+                    .collect(),
+            ),
+            Payload::ShowVariable(PayloadVariable::Version(version)) => {
+                labeled("version", vec![version])
+            }
+            Payload::ShowVariable(PayloadVariable::Tables(names)) => labeled("table", names),
+            Payload::ShowVariable(PayloadVariable::Functions(names)) => labeled("function", names),
+            Payload::Insert(n) | Payload::Update(n) | Payload::Delete(n) => Self::Affected(Some(n)),
+            _ => Self::Affected(None),
+        }
+    }
+}
+
+/// Parameter bound with `SQLBindParameter`, read when the statement is executed.
+pub struct Parameter {
+    pub c_type: SqlSmallInt,
+    pub pointer: SqlPointer,
+    pub indicator: *mut SqlLen,
+}
+
+/// Column bound with `SQLBindCol`, written on every `SQLFetch`.
+pub struct Binding {
+    pub target: Target,
+    pub indicator: *mut SqlLen,
+}
+
+/// Progress of `SQLGetData` calls on the column last read from the current row.
+struct GetData {
+    column: u16,
+    offset: usize,
+    complete: bool,
+}
+
+#[repr(C)]
+pub struct Stmt {
+    tag: u32,
+    pub diagnostics: Diagnostics,
+    dbc: *mut Dbc,
+    prepared: Option<String>,
+    pub parameters: BTreeMap<u16, Parameter>,
+    pub bindings: BTreeMap<u16, Binding>,
+    /// `SQL_ATTR_MAX_ROWS`, where 0 returns every row.
+    pub max_rows: usize,
+    results: VecDeque<ResultSet>,
+    /// Index of the current row of the front result, `None` before the first fetch.
+    row: Option<usize>,
+    get_data: Option<GetData>,
+}
+
+impl Handle for Stmt {
+    const TAG: u32 = 0x474C_0003;
+
+    fn diagnostics(&mut self) -> &mut Diagnostics {
+        &mut self.diagnostics
+    }
+}
+
+impl Stmt {
+    pub fn new(dbc: *mut Dbc) -> Self {
+        Self {
+            tag: Self::TAG,
+            diagnostics: Diagnostics::default(),
+            dbc,
+            prepared: None,
+            parameters: BTreeMap::new(),
+            bindings: BTreeMap::new(),
+            max_rows: 0,
+            results: VecDeque::new(),
+            row: None,
+            get_data: None,
+        }
+    }
+
+    pub fn dbc(&mut self) -> &mut Dbc {
+        // statements are freed by `SQLDisconnect` before their connection goes away
+        unsafe { &mut *self.dbc }
+    }
+
+    pub fn execute_direct(&mut self, sql: &str) -> Result<Done> {
+        self.prepared = None;
+        self.run(sql)
+    }
+
+    pub fn prepare(&mut self, sql: &str) -> Result<Done> {
+        self.close();
+        self.prepared = Some(sql.to_owned());
+
+        Ok(Done::Success)
+    }
+
+    pub fn execute(&mut self) -> Result<Done> {
+        let sql = self.prepared.clone().ok_or(OdbcError::NotPrepared)?;
+        self.run(&sql)
+    }
+
+    pub fn num_params(&self) -> Result<usize> {
+        self.prepared
+            .as_deref()
+            .map(|sql| placeholders(sql).len())
+            .ok_or(OdbcError::NotPrepared)
+    }
+
+    fn run(&mut self, sql: &str) -> Result<Done> {
+        self.close();
+
+        let sql = bind(sql, |i| {
+            let number = i as u16 + 1;
+            let parameter = self
+                .parameters
+                .get(&number)
+                .ok_or(OdbcError::UnboundParameter(number as usize))?;
+            let length = match parameter.indicator.is_null() {
+                true => None,
+                false => Some(unsafe { *parameter.indicator }),
+            };
+
+            let value = match length {
+                Some(SQL_NULL_DATA) => Value::Null,
+                Some(length)
+                    if length <= SQL_LEN_DATA_AT_EXEC_OFFSET || length == SQL_DATA_AT_EXEC =>
+                {
+                    return Err(OdbcError::DataAtExecution);
+                }
+                Some(length) => unsafe {
+                    read_parameter(
+                        parameter.c_type,
+                        parameter.pointer,
+                        (length >= 0).then_some(length),
+                    )?
+                },
+                None => unsafe { read_parameter(parameter.c_type, parameter.pointer, None)? },
+            };
+
+            Ok(literal(&value))
+        })?;
+
+        let payloads = self.dbc().run(&sql)?;
+        self.set_results(payloads.into_iter().map(ResultSet::from).collect());
+
+        Ok(Done::Success)
+    }
+
+    pub fn set_results(&mut self, mut results: Vec<ResultSet>) {
+        if self.max_rows > 0 {
+            for result in results.iter_mut() {
+                if let ResultSet::Rows { rows, .. } = result {
+                    rows.truncate(self.max_rows);
+                }
+            }
+        }
+
+        self.results = results.into();
+        self.row = None;
+        self.get_data = None;
+    }
+
+    /// Closes the cursor and discards pending results, keeping bindings and the prepared SQL.
+    pub fn close(&mut self) -> bool {
+        let open = !self.results.is_empty();
+        self.set_results(Vec::new());
+
+        open
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        match self.results.front() {
+            Some(ResultSet::Rows { columns, .. }) => columns,
+            _ => &[],
+        }
+    }
+
+    /// Looks a column up by its 1-based number.
+    pub fn column(&self, number: u16) -> Result<&Column> {
+        (number as usize)
+            .checked_sub(1)
+            .and_then(|i| self.columns().get(i))
+            .ok_or(OdbcError::InvalidColumn(number))
+    }
+
+    pub fn row_count(&self) -> SqlLen {
+        match self.results.front() {
+            Some(ResultSet::Affected(Some(n))) => *n as SqlLen,
+            Some(ResultSet::Rows { rows, .. }) => rows.len() as SqlLen,
+            _ => -1,
+        }
+    }
+
+    pub fn more_results(&mut self) -> Result<Done> {
+        self.results.pop_front();
+        self.row = None;
+        self.get_data = None;
+
+        Ok(match self.results.is_empty() {
+            true => Done::NoData,
+            false => Done::Success,
+        })
+    }
+
+    fn current_row(&self) -> Result<(&[Column], &[Value])> {
+        match (self.results.front(), self.row) {
+            (Some(ResultSet::Rows { columns, rows }), Some(row)) if row < rows.len() => {
+                Ok((columns, &rows[row]))
+            }
+            _ => Err(OdbcError::NoResultSet),
+        }
+    }
+
+    /// Advances to the next row and fills the columns bound with `SQLBindCol`.
+    pub fn fetch(&mut self) -> Result<Done> {
+        let Some(ResultSet::Rows { rows, .. }) = self.results.front() else {
+            return Err(OdbcError::NoResultSet);
+        };
+
+        let row = self.row.map_or(0, |row| row + 1).min(rows.len());
+        self.row = Some(row);
+        self.get_data = None;
+        if row == rows.len() {
+            return Ok(Done::NoData);
+        }
+
+        let (columns, values) = self.current_row()?;
+        let mut truncated = false;
+        for (number, binding) in self.bindings.iter() {
+            let i = *number as usize - 1;
+            let (value, column) = values
+                .get(i)
+                .zip(columns.get(i))
+                .ok_or(OdbcError::InvalidColumn(*number))?;
+            let (length, cut) = unsafe { binding.target.write(value, column.sql_type, &mut 0)? };
+
+            if !binding.indicator.is_null() {
+                unsafe { *binding.indicator = length };
+            }
+            truncated |= cut;
+        }
+
+        Ok(match truncated {
+            true => Done::Truncated,
+            false => Done::Success,
+        })
+    }
+
+    /// Reads a column of the current row, returning long data in parts on repeated calls.
+    pub fn get_data(
+        &mut self,
+        number: u16,
+        target: Target,
+        indicator: *mut SqlLen,
+    ) -> Result<Done> {
+        let (offset, complete) = match &self.get_data {
+            Some(get_data) if get_data.column == number => (get_data.offset, get_data.complete),
+            _ => (0, false),
+        };
+        if complete {
+            return Ok(Done::NoData);
+        }
+
+        let (columns, values) = self.current_row()?;
+        let i = (number as usize)
+            .checked_sub(1)
+            .ok_or(OdbcError::InvalidColumn(number))?;
+        let (value, column) = values
+            .get(i)
+            .zip(columns.get(i))
+            .ok_or(OdbcError::InvalidColumn(number))?;
+
+        let mut offset = offset;
+        let (length, truncated) = unsafe { target.write(value, column.sql_type, &mut offset)? };
+        if !indicator.is_null() {
+            unsafe { *indicator = length };
+        }
+        self.get_data = Some(GetData {
+            column: number,
+            offset,
+            complete: !truncated,
+        });
+
+        Ok(match truncated {
+            true => Done::Truncated,
+            false => Done::Success,
+        })
+    }
+}
+
+fn placeholders(sql: &str) -> Vec<usize> {
+    let mut found = Vec::new();
+    let mut quote = None;
+
+    for (i, byte) in sql.bytes().enumerate() {
+        match (quote, byte) {
+            (None, b @ (b'\'' | b'"' | b'`')) => quote = Some(b),
+            (Some(q), b) if b == q => quote = None,
+            (None, b'?') => found.push(i),
+            _ => {}
+        }
+    }
+
+    found
+}
+
+fn bind(sql: &str, mut literal: impl FnMut(usize) -> Result<String>) -> Result<String> {
+    let mut bound = String::with_capacity(sql.len());
+    let mut last = 0;
+
+    for (n, position) in placeholders(sql).into_iter().enumerate() {
+        bound.push_str(&sql[last..position]);
+        bound.push_str(&literal(n)?);
+        last = position + 1;
+    }
+    bound.push_str(&sql[last..]);
+
+    Ok(bound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bind;
+
+    #[test]
+    fn bind_placeholders() {
+        let sql = "SELECT * FROM Foo WHERE a = ? AND b = '?' AND c = ?";
+        let bound = bind(sql, |i| Ok(i.to_string())).unwrap();
+
+        assert_eq!(bound, "SELECT * FROM Foo WHERE a = 0 AND b = '?' AND c = 1");
+    }
+}
@@ -0,0 +1,99 @@
+use {
+    crate::error::{OdbcError, Result},
+    gluesql_json_storage::JsonStorage,
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_redb_storage::RedbStorage,
+    gluesql_sled_storage::SledStorage,
+    std::collections::HashMap,
+};
+
+pub enum Storage {
+    Memory(MemoryStorage),
+    Json(JsonStorage),
+    Sled(SledStorage),
+    Redb(RedbStorage),
+}
+
+macro_rules! with_storage {
+    ($engine:expr, $storage:ident => $body:expr) => {
+        match $engine {
+            Storage::Memory($storage) => $body,
+            Storage::Json($storage) => $body,
+            Storage::Sled($storage) => $body,
+            Storage::Redb($storage) => $body,
+        }
+    };
+}
+
+pub(crate) use with_storage;
+
+/// Parses `KEY=value;KEY={value}` pairs, keys case-insensitively.
+pub fn parse_connection_string(input: &str) -> Result<HashMap<String, String>> {
+    let mut attributes = HashMap::new();
+    let mut rest = input.trim();
+
+    while !rest.is_empty() {
+        let (key, tail) = rest
+            .split_once('=')
+            .ok_or_else(|| OdbcError::InvalidConnectionString(input.to_owned()))?;
+        let (value, tail) = match tail.strip_prefix('{') {
+            Some(braced) => {
+                let end = braced
+                    .find('}')
+                    .ok_or_else(|| OdbcError::InvalidConnectionString(input.to_owned()))?;
+                let tail = braced[end + 1..].trim_start();
+
+                (&braced[..end], tail.strip_prefix(';').unwrap_or(tail))
+            }
+            None => tail.split_once(';').unwrap_or((tail, "")),
+        };
+
+        attributes.insert(key.trim().to_uppercase(), value.trim().to_owned());
+        rest = tail.trim_start();
+    }
+
+    Ok(attributes)
+}
+
+impl Storage {
+    /// Opens the storage named by the `STORAGE` and `PATH` connection attributes.
+    pub fn open(attributes: &HashMap<String, String>) -> Result<Self> {
+        let storage = attributes
+            .get("STORAGE")
+            .map(|storage| storage.to_lowercase())
+            .unwrap_or_else(|| "memory".to_owned());
+        let path = attributes.get("PATH");
+        let open_error =
+            |error: gluesql_core::error::Error| OdbcError::StorageOpen(error.to_string());
+
+        match (storage.as_str(), path) {
+            ("memory", _) => Ok(Self::Memory(MemoryStorage::default())),
+            ("json", Some(path)) => JsonStorage::new(path).map(Self::Json).map_err(open_error),
+            ("sled", Some(path)) => SledStorage::new(path).map(Self::Sled).map_err(open_error),
+            ("redb", Some(path)) => RedbStorage::new(path).map(Self::Redb).map_err(open_error),
+            ("json" | "sled" | "redb", None) => Err(OdbcError::InvalidConnectionString(format!(
+                "PATH is required for {storage} storage"
+            ))),
+            _ => Err(OdbcError::InvalidConnectionString(format!(
+                "unknown storage {storage}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_connection_string;
+
+    #[test]
+    fn connection_string() {
+        let attributes =
+            parse_connection_string("Driver={GlueSQL ODBC};storage=sled; Path={C:\\data;1}")
+                .unwrap();
+
+        assert_eq!(attributes["DRIVER"], "GlueSQL ODBC");
+        assert_eq!(attributes["STORAGE"], "sled");
+        assert_eq!(attributes["PATH"], "C:\\data;1");
+        assert!(parse_connection_string("Storage").is_err());
+    }
+}
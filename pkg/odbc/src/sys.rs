@@ -0,0 +1,305 @@
+//! ODBC 3.x types and constants used by the driver, following `sql.h` and `sqlext.h`.
+
+use std::ffi::c_void;
+
+pub type SqlHandle = *mut c_void;
+pub type SqlPointer = *mut c_void;
+pub type SqlChar = u8;
+pub type SqlSmallInt = i16;
+pub type SqlUSmallInt = u16;
+pub type SqlInteger = i32;
+pub type SqlLen = isize;
+pub type SqlULen = usize;
+pub type SqlReturn = i16;
+
+pub const SQL_SUCCESS: SqlReturn = 0;
+pub const SQL_SUCCESS_WITH_INFO: SqlReturn = 1;
+pub const SQL_ERROR: SqlReturn = -1;
+pub const SQL_INVALID_HANDLE: SqlReturn = -2;
+pub const SQL_NO_DATA: SqlReturn = 100;
+
+pub const SQL_NTS: SqlInteger = -3;
+pub const SQL_NULL_DATA: SqlLen = -1;
+pub const SQL_DATA_AT_EXEC: SqlLen = -2;
+pub const SQL_LEN_DATA_AT_EXEC_OFFSET: SqlLen = -100;
+
+pub const SQL_HANDLE_ENV: SqlSmallInt = 1;
+pub const SQL_HANDLE_DBC: SqlSmallInt = 2;
+pub const SQL_HANDLE_STMT: SqlSmallInt = 3;
+
+// environment and connection attributes
+pub const SQL_ATTR_ODBC_VERSION: SqlInteger = 200;
+pub const SQL_ATTR_CONNECTION_POOLING: SqlInteger = 201;
+pub const SQL_ATTR_CP_MATCH: SqlInteger = 202;
+pub const SQL_ATTR_OUTPUT_NTS: SqlInteger = 10001;
+pub const SQL_OV_ODBC3: SqlInteger = 3;
+pub const SQL_ATTR_AUTOCOMMIT: SqlInteger = 102;
+pub const SQL_ATTR_LOGIN_TIMEOUT: SqlInteger = 103;
+pub const SQL_ATTR_TXN_ISOLATION: SqlInteger = 108;
+pub const SQL_ATTR_CONNECTION_TIMEOUT: SqlInteger = 113;
+pub const SQL_ATTR_CONNECTION_DEAD: SqlInteger = 1209;
+pub const SQL_AUTOCOMMIT_OFF: usize = 0;
+pub const SQL_AUTOCOMMIT_ON: usize = 1;
+pub const SQL_CD_FALSE: u32 = 0;
+pub const SQL_CD_TRUE: u32 = 1;
+pub const SQL_TXN_SERIALIZABLE: u32 = 8;
+
+// statement attributes
+pub const SQL_ATTR_QUERY_TIMEOUT: SqlInteger = 0;
+pub const SQL_ATTR_MAX_ROWS: SqlInteger = 1;
+pub const SQL_ATTR_MAX_LENGTH: SqlInteger = 3;
+pub const SQL_ATTR_CURSOR_TYPE: SqlInteger = 6;
+pub const SQL_ATTR_CONCURRENCY: SqlInteger = 7;
+pub const SQL_ATTR_ROW_ARRAY_SIZE: SqlInteger = 27;
+pub const SQL_CURSOR_FORWARD_ONLY: usize = 0;
+pub const SQL_CONCUR_READ_ONLY: usize = 1;
+
+// SQLFreeStmt options
+pub const SQL_CLOSE: SqlUSmallInt = 0;
+pub const SQL_DROP: SqlUSmallInt = 1;
+pub const SQL_UNBIND: SqlUSmallInt = 2;
+pub const SQL_RESET_PARAMS: SqlUSmallInt = 3;
+
+// SQLEndTran completion types
+pub const SQL_COMMIT: SqlSmallInt = 0;
+pub const SQL_ROLLBACK: SqlSmallInt = 1;
+
+// SQLDriverConnect completion
+pub const SQL_DRIVER_NOPROMPT: SqlUSmallInt = 0;
+
+// SQL data types
+pub const SQL_UNKNOWN_TYPE: SqlSmallInt = 0;
+pub const SQL_CHAR: SqlSmallInt = 1;
+pub const SQL_NUMERIC: SqlSmallInt = 2;
+pub const SQL_DECIMAL: SqlSmallInt = 3;
+pub const SQL_INTEGER: SqlSmallInt = 4;
+pub const SQL_SMALLINT: SqlSmallInt = 5;
+pub const SQL_FLOAT: SqlSmallInt = 6;
+pub const SQL_REAL: SqlSmallInt = 7;
+pub const SQL_DOUBLE: SqlSmallInt = 8;
+pub const SQL_DATETIME: SqlSmallInt = 9;
+pub const SQL_VARCHAR: SqlSmallInt = 12;
+pub const SQL_TYPE_DATE: SqlSmallInt = 91;
+pub const SQL_TYPE_TIME: SqlSmallInt = 92;
+pub const SQL_TYPE_TIMESTAMP: SqlSmallInt = 93;
+pub const SQL_LONGVARCHAR: SqlSmallInt = -1;
+pub const SQL_VARBINARY: SqlSmallInt = -3;
+pub const SQL_BIGINT: SqlSmallInt = -5;
+pub const SQL_TINYINT: SqlSmallInt = -6;
+pub const SQL_BIT: SqlSmallInt = -7;
+pub const SQL_WCHAR: SqlSmallInt = -8;
+pub const SQL_WVARCHAR: SqlSmallInt = -9;
+pub const SQL_GUID: SqlSmallInt = -11;
+pub const SQL_ALL_TYPES: SqlSmallInt = 0;
+
+pub const SQL_CODE_DATE: SqlSmallInt = 1;
+pub const SQL_CODE_TIME: SqlSmallInt = 2;
+pub const SQL_CODE_TIMESTAMP: SqlSmallInt = 3;
+
+// C data types
+pub const SQL_C_CHAR: SqlSmallInt = SQL_CHAR;
+pub const SQL_C_WCHAR: SqlSmallInt = SQL_WCHAR;
+pub const SQL_C_LONG: SqlSmallInt = SQL_INTEGER;
+pub const SQL_C_SHORT: SqlSmallInt = SQL_SMALLINT;
+pub const SQL_C_FLOAT: SqlSmallInt = SQL_REAL;
+pub const SQL_C_DOUBLE: SqlSmallInt = SQL_DOUBLE;
+pub const SQL_C_NUMERIC: SqlSmallInt = SQL_NUMERIC;
+pub const SQL_C_DEFAULT: SqlSmallInt = 99;
+pub const SQL_C_TYPE_DATE: SqlSmallInt = SQL_TYPE_DATE;
+pub const SQL_C_TYPE_TIME: SqlSmallInt = SQL_TYPE_TIME;
+pub const SQL_C_TYPE_TIMESTAMP: SqlSmallInt = SQL_TYPE_TIMESTAMP;
+pub const SQL_C_BINARY: SqlSmallInt = -2;
+pub const SQL_C_BIT: SqlSmallInt = SQL_BIT;
+pub const SQL_C_TINYINT: SqlSmallInt = SQL_TINYINT;
+pub const SQL_C_SLONG: SqlSmallInt = -16;
+pub const SQL_C_SSHORT: SqlSmallInt = -15;
+pub const SQL_C_STINYINT: SqlSmallInt = -26;
+pub const SQL_C_ULONG: SqlSmallInt = -18;
+pub const SQL_C_USHORT: SqlSmallInt = -17;
+pub const SQL_C_UTINYINT: SqlSmallInt = -28;
+pub const SQL_C_SBIGINT: SqlSmallInt = -25;
+pub const SQL_C_UBIGINT: SqlSmallInt = -27;
+
+pub const SQL_NO_NULLS: SqlSmallInt = 0;
+pub const SQL_NULLABLE: SqlSmallInt = 1;
+pub const SQL_NULLABLE_UNKNOWN: SqlSmallInt = 2;
+
+pub const SQL_PARAM_INPUT: SqlSmallInt = 1;
+
+// SQLColAttribute fields
+pub const SQL_DESC_CONCISE_TYPE: SqlUSmallInt = 2;
+pub const SQL_DESC_DISPLAY_SIZE: SqlUSmallInt = 6;
+pub const SQL_DESC_UNSIGNED: SqlUSmallInt = 8;
+pub const SQL_DESC_FIXED_PREC_SCALE: SqlUSmallInt = 9;
+pub const SQL_DESC_UPDATABLE: SqlUSmallInt = 10;
+pub const SQL_DESC_AUTO_UNIQUE_VALUE: SqlUSmallInt = 11;
+pub const SQL_DESC_CASE_SENSITIVE: SqlUSmallInt = 12;
+pub const SQL_DESC_SEARCHABLE: SqlUSmallInt = 13;
+pub const SQL_DESC_TYPE_NAME: SqlUSmallInt = 14;
+pub const SQL_DESC_TABLE_NAME: SqlUSmallInt = 15;
+pub const SQL_DESC_SCHEMA_NAME: SqlUSmallInt = 16;
+pub const SQL_DESC_CATALOG_NAME: SqlUSmallInt = 17;
+pub const SQL_DESC_LABEL: SqlUSmallInt = 18;
+pub const SQL_DESC_BASE_TABLE_NAME: SqlUSmallInt = 23;
+pub const SQL_DESC_LITERAL_PREFIX: SqlUSmallInt = 27;
+pub const SQL_DESC_LITERAL_SUFFIX: SqlUSmallInt = 28;
+pub const SQL_DESC_LOCAL_TYPE_NAME: SqlUSmallInt = 29;
+pub const SQL_DESC_NUM_PREC_RADIX: SqlUSmallInt = 32;
+pub const SQL_DESC_COUNT: SqlUSmallInt = 1001;
+pub const SQL_DESC_TYPE: SqlUSmallInt = 1002;
+pub const SQL_DESC_LENGTH: SqlUSmallInt = 1003;
+pub const SQL_DESC_PRECISION: SqlUSmallInt = 1005;
+pub const SQL_DESC_SCALE: SqlUSmallInt = 1006;
+pub const SQL_DESC_NULLABLE: SqlUSmallInt = 1008;
+pub const SQL_DESC_NAME: SqlUSmallInt = 1011;
+pub const SQL_DESC_UNNAMED: SqlUSmallInt = 1012;
+pub const SQL_DESC_OCTET_LENGTH: SqlUSmallInt = 1013;
+pub const SQL_DESC_BASE_COLUMN_NAME: SqlUSmallInt = 1022;
+// ODBC 2 column attributes still sent by some applications
+pub const SQL_COLUMN_NAME: SqlUSmallInt = 1;
+pub const SQL_COLUMN_LENGTH: SqlUSmallInt = 3;
+pub const SQL_COLUMN_PRECISION: SqlUSmallInt = 4;
+pub const SQL_COLUMN_SCALE: SqlUSmallInt = 5;
+pub const SQL_COLUMN_NULLABLE: SqlUSmallInt = 7;
+
+pub const SQL_PRED_SEARCHABLE: isize = 3;
+pub const SQL_ATTR_READWRITE_UNKNOWN: isize = 2;
+pub const SQL_NAMED: isize = 0;
+
+// SQLGetDiagField identifiers
+pub const SQL_DIAG_NUMBER: SqlSmallInt = 2;
+pub const SQL_DIAG_RETURNCODE: SqlSmallInt = 1;
+pub const SQL_DIAG_NATIVE: SqlSmallInt = 5;
+pub const SQL_DIAG_SQLSTATE: SqlSmallInt = 4;
+pub const SQL_DIAG_MESSAGE_TEXT: SqlSmallInt = 6;
+
+// SQLGetFunctions
+pub const SQL_API_ODBC3_ALL_FUNCTIONS: SqlUSmallInt = 999;
+pub const SQL_API_ODBC3_ALL_FUNCTIONS_SIZE: usize = 250;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DateStruct {
+    pub year: i16,
+    pub month: u16,
+    pub day: u16,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeStruct {
+    pub hour: u16,
+    pub minute: u16,
+    pub second: u16,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimestampStruct {
+    pub year: i16,
+    pub month: u16,
+    pub day: u16,
+    pub hour: u16,
+    pub minute: u16,
+    pub second: u16,
+    /// Nanoseconds
+    pub fraction: u32,
+}
+
+// SQLGetInfo information types
+pub const SQL_MAX_DRIVER_CONNECTIONS: SqlUSmallInt = 0;
+pub const SQL_MAX_CONCURRENT_ACTIVITIES: SqlUSmallInt = 1;
+pub const SQL_DATA_SOURCE_NAME: SqlUSmallInt = 2;
+pub const SQL_DRIVER_NAME: SqlUSmallInt = 6;
+pub const SQL_DRIVER_VER: SqlUSmallInt = 7;
+pub const SQL_SERVER_NAME: SqlUSmallInt = 13;
+pub const SQL_SEARCH_PATTERN_ESCAPE: SqlUSmallInt = 14;
+pub const SQL_DATABASE_NAME: SqlUSmallInt = 16;
+pub const SQL_DBMS_NAME: SqlUSmallInt = 17;
+pub const SQL_DBMS_VER: SqlUSmallInt = 18;
+pub const SQL_ACCESSIBLE_TABLES: SqlUSmallInt = 19;
+pub const SQL_CONCAT_NULL_BEHAVIOR: SqlUSmallInt = 22;
+pub const SQL_CURSOR_COMMIT_BEHAVIOR: SqlUSmallInt = 23;
+pub const SQL_CURSOR_ROLLBACK_BEHAVIOR: SqlUSmallInt = 24;
+pub const SQL_DATA_SOURCE_READ_ONLY: SqlUSmallInt = 25;
+pub const SQL_DEFAULT_TXN_ISOLATION: SqlUSmallInt = 26;
+pub const SQL_IDENTIFIER_CASE: SqlUSmallInt = 28;
+pub const SQL_IDENTIFIER_QUOTE_CHAR: SqlUSmallInt = 29;
+pub const SQL_MAX_COLUMN_NAME_LEN: SqlUSmallInt = 30;
+pub const SQL_MAX_SCHEMA_NAME_LEN: SqlUSmallInt = 32;
+pub const SQL_MAX_CATALOG_NAME_LEN: SqlUSmallInt = 34;
+pub const SQL_MAX_TABLE_NAME_LEN: SqlUSmallInt = 35;
+pub const SQL_MULT_RESULT_SETS: SqlUSmallInt = 36;
+pub const SQL_MULTIPLE_ACTIVE_TXN: SqlUSmallInt = 37;
+pub const SQL_SCHEMA_TERM: SqlUSmallInt = 39;
+pub const SQL_CATALOG_NAME_SEPARATOR: SqlUSmallInt = 41;
+pub const SQL_CATALOG_TERM: SqlUSmallInt = 42;
+pub const SQL_SCROLL_OPTIONS: SqlUSmallInt = 44;
+pub const SQL_TABLE_TERM: SqlUSmallInt = 45;
+pub const SQL_TXN_CAPABLE: SqlUSmallInt = 46;
+pub const SQL_USER_NAME: SqlUSmallInt = 47;
+pub const SQL_TXN_ISOLATION_OPTION: SqlUSmallInt = 72;
+pub const SQL_DRIVER_ODBC_VER: SqlUSmallInt = 77;
+pub const SQL_GETDATA_EXTENSIONS: SqlUSmallInt = 81;
+pub const SQL_COLUMN_ALIAS: SqlUSmallInt = 87;
+pub const SQL_KEYWORDS: SqlUSmallInt = 89;
+pub const SQL_SCHEMA_USAGE: SqlUSmallInt = 91;
+pub const SQL_CATALOG_USAGE: SqlUSmallInt = 92;
+pub const SQL_QUOTED_IDENTIFIER_CASE: SqlUSmallInt = 93;
+pub const SQL_NEED_LONG_DATA_LEN: SqlUSmallInt = 111;
+pub const SQL_SQL_CONFORMANCE: SqlUSmallInt = 118;
+pub const SQL_ODBC_INTERFACE_CONFORMANCE: SqlUSmallInt = 152;
+pub const SQL_CURSOR_SENSITIVITY: SqlUSmallInt = 10001;
+pub const SQL_CATALOG_NAME: SqlUSmallInt = 10003;
+pub const SQL_MAX_IDENTIFIER_LEN: SqlUSmallInt = 10005;
+pub const SQL_ASYNC_MODE: SqlUSmallInt = 10021;
+
+pub const SQL_TC_ALL: u16 = 2;
+pub const SQL_CB_NULL: u16 = 0;
+pub const SQL_CB_PRESERVE: u16 = 2;
+pub const SQL_IC_SENSITIVE: u16 = 3;
+pub const SQL_GD_ANY_COLUMN: u32 = 1;
+pub const SQL_GD_ANY_ORDER: u32 = 2;
+pub const SQL_SO_FORWARD_ONLY: u32 = 1;
+pub const SQL_SC_SQL92_ENTRY: u32 = 1;
+pub const SQL_OIC_CORE: u32 = 1;
+pub const SQL_INSENSITIVE: u32 = 1;
+pub const SQL_AM_NONE: u32 = 0;
+
+// SQLGetFunctions function identifiers
+pub const SQL_API_ALL_FUNCTIONS: SqlUSmallInt = 0;
+pub const SQL_API_SQLBINDCOL: SqlUSmallInt = 4;
+pub const SQL_API_SQLCANCEL: SqlUSmallInt = 5;
+pub const SQL_API_SQLCOLATTRIBUTE: SqlUSmallInt = 6;
+pub const SQL_API_SQLCONNECT: SqlUSmallInt = 7;
+pub const SQL_API_SQLDESCRIBECOL: SqlUSmallInt = 8;
+pub const SQL_API_SQLDISCONNECT: SqlUSmallInt = 9;
+pub const SQL_API_SQLEXECDIRECT: SqlUSmallInt = 11;
+pub const SQL_API_SQLEXECUTE: SqlUSmallInt = 12;
+pub const SQL_API_SQLFETCH: SqlUSmallInt = 13;
+pub const SQL_API_SQLFREESTMT: SqlUSmallInt = 16;
+pub const SQL_API_SQLNUMRESULTCOLS: SqlUSmallInt = 18;
+pub const SQL_API_SQLPREPARE: SqlUSmallInt = 19;
+pub const SQL_API_SQLROWCOUNT: SqlUSmallInt = 20;
+pub const SQL_API_SQLCOLUMNS: SqlUSmallInt = 40;
+pub const SQL_API_SQLDRIVERCONNECT: SqlUSmallInt = 41;
+pub const SQL_API_SQLGETDATA: SqlUSmallInt = 43;
+pub const SQL_API_SQLGETFUNCTIONS: SqlUSmallInt = 44;
+pub const SQL_API_SQLGETINFO: SqlUSmallInt = 45;
+pub const SQL_API_SQLGETTYPEINFO: SqlUSmallInt = 47;
+pub const SQL_API_SQLTABLES: SqlUSmallInt = 54;
+pub const SQL_API_SQLMORERESULTS: SqlUSmallInt = 61;
+pub const SQL_API_SQLNUMPARAMS: SqlUSmallInt = 63;
+pub const SQL_API_SQLBINDPARAMETER: SqlUSmallInt = 72;
+pub const SQL_API_SQLALLOCHANDLE: SqlUSmallInt = 1001;
+pub const SQL_API_SQLCLOSECURSOR: SqlUSmallInt = 1003;
+pub const SQL_API_SQLENDTRAN: SqlUSmallInt = 1005;
+pub const SQL_API_SQLFREEHANDLE: SqlUSmallInt = 1006;
+pub const SQL_API_SQLGETCONNECTATTR: SqlUSmallInt = 1007;
+pub const SQL_API_SQLGETDIAGFIELD: SqlUSmallInt = 1010;
+pub const SQL_API_SQLGETDIAGREC: SqlUSmallInt = 1011;
+pub const SQL_API_SQLGETENVATTR: SqlUSmallInt = 1012;
+pub const SQL_API_SQLGETSTMTATTR: SqlUSmallInt = 1014;
+pub const SQL_API_SQLSETCONNECTATTR: SqlUSmallInt = 1016;
+pub const SQL_API_SQLSETENVATTR: SqlUSmallInt = 1019;
+pub const SQL_API_SQLSETSTMTATTR: SqlUSmallInt = 1020;
@@ -0,0 +1,597 @@
+use {
+    crate::{
+        error::{OdbcError, Result},
+        sys::*,
+    },
+    chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike},
+    gluesql_core::{ast::DataType, data::Value},
+    std::ptr,
+};
+
+/// SQL type a result column is described with, derived from the GlueSQL data type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlType {
+    Bit,
+    TinyInt,
+    SmallInt,
+    Integer,
+    BigInt,
+    Numeric,
+    Real,
+    Double,
+    VarChar,
+    VarBinary,
+    Date,
+    Time,
+    Timestamp,
+    Guid,
+}
+
+impl From<&DataType> for SqlType {
+    fn from(data_type: &DataType) -> Self {
+        match data_type {
+            DataType::Boolean => Self::Bit,
+            DataType::Int8 => Self::TinyInt,
+            DataType::Int16 | DataType::Uint8 => Self::SmallInt,
+            DataType::Int32 | DataType::Uint16 => Self::Integer,
+            DataType::Int | DataType::Uint32 => Self::BigInt,
+            DataType::Int128 | DataType::Uint64 | DataType::Uint128 | DataType::Decimal => {
+                Self::Numeric
+            }
+            DataType::Float32 => Self::Real,
+            DataType::Float => Self::Double,
+            DataType::Bytea => Self::VarBinary,
+            DataType::Date => Self::Date,
+            DataType::Time => Self::Time,
+            DataType::Timestamp => Self::Timestamp,
+            DataType::Uuid => Self::Guid,
+            _ => Self::VarChar,
+        }
+    }
+}
+
+impl SqlType {
+    pub fn code(self) -> SqlSmallInt {
+        match self {
+            Self::Bit => SQL_BIT,
+            Self::TinyInt => SQL_TINYINT,
+            Self::SmallInt => SQL_SMALLINT,
+            Self::Integer => SQL_INTEGER,
+            Self::BigInt => SQL_BIGINT,
+            Self::Numeric => SQL_NUMERIC,
+            Self::Real => SQL_REAL,
+            Self::Double => SQL_DOUBLE,
+            Self::VarChar => SQL_VARCHAR,
+            Self::VarBinary => SQL_VARBINARY,
+            Self::Date => SQL_TYPE_DATE,
+            Self::Time => SQL_TYPE_TIME,
+            Self::Timestamp => SQL_TYPE_TIMESTAMP,
+            Self::Guid => SQL_GUID,
+        }
+    }
+
+    /// Verbose type code and datetime subcode, as reported by `SQL_DESC_TYPE`.
+    pub fn verbose_code(self) -> (SqlSmallInt, SqlSmallInt) {
+        match self {
+            Self::Date => (SQL_DATETIME, SQL_CODE_DATE),
+            Self::Time => (SQL_DATETIME, SQL_CODE_TIME),
+            Self::Timestamp => (SQL_DATETIME, SQL_CODE_TIMESTAMP),
+            _ => (self.code(), 0),
+        }
+    }
+
+    /// Name of the GlueSQL type the SQL type stands for.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bit => "BOOLEAN",
+            Self::TinyInt => "INT8",
+            Self::SmallInt => "INT16",
+            Self::Integer => "INT32",
+            Self::BigInt => "INT",
+            Self::Numeric => "DECIMAL",
+            Self::Real => "FLOAT32",
+            Self::Double => "FLOAT",
+            Self::VarChar => "TEXT",
+            Self::VarBinary => "BYTEA",
+            Self::Date => "DATE",
+            Self::Time => "TIME",
+            Self::Timestamp => "TIMESTAMP",
+            Self::Guid => "UUID",
+        }
+    }
+
+    /// C type used when the application binds with `SQL_C_DEFAULT`.
+    fn default_c_type(self) -> SqlSmallInt {
+        match self {
+            Self::Bit => SQL_C_BIT,
+            Self::TinyInt => SQL_C_STINYINT,
+            Self::SmallInt => SQL_C_SSHORT,
+            Self::Integer => SQL_C_SLONG,
+            Self::BigInt => SQL_C_SBIGINT,
+            Self::Real => SQL_C_FLOAT,
+            Self::Double => SQL_C_DOUBLE,
+            Self::VarBinary => SQL_C_BINARY,
+            Self::Date => SQL_C_TYPE_DATE,
+            Self::Time => SQL_C_TYPE_TIME,
+            Self::Timestamp => SQL_C_TYPE_TIMESTAMP,
+            Self::Numeric | Self::VarChar | Self::Guid => SQL_C_CHAR,
+        }
+    }
+
+    /// Column size of fixed size types, `None` for character and binary types.
+    fn fixed_size(self) -> Option<usize> {
+        match self {
+            Self::Bit => Some(1),
+            Self::TinyInt => Some(3),
+            Self::SmallInt => Some(5),
+            Self::Integer => Some(10),
+            Self::BigInt => Some(19),
+            Self::Numeric => Some(38),
+            Self::Real => Some(7),
+            Self::Double => Some(15),
+            Self::Date => Some(10),
+            Self::Time => Some(8),
+            Self::Timestamp => Some(26),
+            Self::Guid => Some(36),
+            Self::VarChar | Self::VarBinary => None,
+        }
+    }
+
+    pub fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            Self::TinyInt
+                | Self::SmallInt
+                | Self::Integer
+                | Self::BigInt
+                | Self::Numeric
+                | Self::Real
+                | Self::Double
+        )
+    }
+}
+
+/// Description of a result set column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    pub name: String,
+    pub sql_type: SqlType,
+    /// Column size in characters, or bytes for binary columns.
+    pub size: usize,
+    pub decimal_digits: i16,
+    pub nullable: SqlSmallInt,
+}
+
+/// Size reported for character and binary columns no row has a value for.
+const DEFAULT_VARIABLE_SIZE: usize = 255;
+
+impl Column {
+    pub fn new(name: &str, sql_type: SqlType) -> Self {
+        Self {
+            name: name.to_owned(),
+            sql_type,
+            size: sql_type.fixed_size().unwrap_or(DEFAULT_VARIABLE_SIZE),
+            decimal_digits: match sql_type {
+                SqlType::Timestamp => 6,
+                _ => 0,
+            },
+            nullable: SQL_NULLABLE_UNKNOWN,
+        }
+    }
+
+    /// Describes a query result column from the values it holds, as GlueSQL results carry no
+    /// type information. Columns mixing several types are described as `SQL_VARCHAR`.
+    pub fn infer<'a>(name: &str, values: impl Iterator<Item = &'a Value> + Clone) -> Self {
+        let mut types = values
+            .clone()
+            .filter_map(|value| value.get_type())
+            .map(|data_type| SqlType::from(&data_type));
+        let sql_type = match types.next() {
+            Some(first) if types.all(|sql_type| sql_type == first) => first,
+            _ => SqlType::VarChar,
+        };
+
+        let mut column = Self::new(name, sql_type);
+        match sql_type {
+            SqlType::VarChar => {
+                column.size = values
+                    .filter(|value| !value.is_null())
+                    .map(|value| text(value).chars().count())
+                    .max()
+                    .unwrap_or(DEFAULT_VARIABLE_SIZE);
+            }
+            SqlType::VarBinary => {
+                column.size = values
+                    .filter_map(|value| match value {
+                        Value::Bytea(bytes) => Some(bytes.len()),
+                        _ => None,
+                    })
+                    .max()
+                    .unwrap_or(DEFAULT_VARIABLE_SIZE);
+            }
+            SqlType::Numeric => {
+                column.decimal_digits = values
+                    .filter_map(|value| match value {
+                        Value::Decimal(decimal) => Some(decimal.scale() as i16),
+                        _ => None,
+                    })
+                    .max()
+                    .unwrap_or(0);
+            }
+            _ => {}
+        }
+
+        column
+    }
+
+    /// Maximum number of characters needed to display a value of the column.
+    pub fn display_size(&self) -> usize {
+        match self.sql_type {
+            SqlType::TinyInt | SqlType::SmallInt | SqlType::Integer | SqlType::BigInt => {
+                self.size + 1
+            }
+            SqlType::Numeric => self.size + 2,
+            SqlType::Real => 14,
+            SqlType::Double => 24,
+            SqlType::VarBinary => self.size * 2,
+            _ => self.size,
+        }
+    }
+
+    /// Length in bytes of the data transferred with the default C type.
+    pub fn octet_length(&self) -> usize {
+        match self.sql_type {
+            SqlType::Bit | SqlType::TinyInt => 1,
+            SqlType::SmallInt => 2,
+            SqlType::Integer | SqlType::Real => 4,
+            SqlType::BigInt | SqlType::Double => 8,
+            SqlType::Date | SqlType::Time => 6,
+            SqlType::Timestamp => 16,
+            SqlType::VarChar => self.size * 4,
+            _ => self.size,
+        }
+    }
+}
+
+/// Character representation of a value, as returned for `SQL_C_CHAR` targets.
+pub fn text(value: &Value) -> String {
+    match value {
+        Value::Bool(true) => "1".to_owned(),
+        Value::Bool(false) => "0".to_owned(),
+        Value::Bytea(bytes) => bytes.iter().map(|byte| format!("{byte:02X}")).collect(),
+        value => String::from(value),
+    }
+}
+
+/// Destination of an `SQLGetData` call or of a column bound with `SQLBindCol`.
+pub struct Target {
+    pub c_type: SqlSmallInt,
+    pub pointer: SqlPointer,
+    pub buffer_length: SqlLen,
+}
+
+impl Target {
+    /// Converts `value` into the target buffer, returning the length indicator and whether the
+    /// data was truncated. Character and binary data resume from `offset`, which is advanced
+    /// past the bytes written so that repeated `SQLGetData` calls return the data in parts.
+    ///
+    /// # Safety
+    /// `pointer` must be null or valid for writes of `buffer_length` bytes, or of the size of
+    /// the C type for fixed size types.
+    pub unsafe fn write(
+        &self,
+        value: &Value,
+        sql_type: SqlType,
+        offset: &mut usize,
+    ) -> Result<(SqlLen, bool)> {
+        if value.is_null() {
+            return Ok((SQL_NULL_DATA, false));
+        }
+
+        let c_type = match self.c_type {
+            SQL_C_DEFAULT => sql_type.default_c_type(),
+            c_type => c_type,
+        };
+
+        unsafe {
+            match c_type {
+                SQL_C_CHAR => Ok(self.write_parts(text(value).as_bytes(), 1, offset)),
+                SQL_C_WCHAR => {
+                    let bytes = text(value)
+                        .encode_utf16()
+                        .flat_map(u16::to_ne_bytes)
+                        .collect::<Vec<_>>();
+
+                    Ok(self.write_parts(&bytes, 2, offset))
+                }
+                SQL_C_BINARY => match value {
+                    Value::Bytea(bytes) => Ok(self.write_parts(bytes, 0, offset)),
+                    value => Ok(self.write_parts(text(value).as_bytes(), 0, offset)),
+                },
+                SQL_C_BIT => self.write_fixed(u8::from(bool::try_from(value)?)),
+                SQL_C_STINYINT | SQL_C_TINYINT => self.write_fixed(signed::<i8>(value)?),
+                SQL_C_UTINYINT => self.write_fixed(unsigned::<u8>(value)?),
+                SQL_C_SSHORT | SQL_C_SHORT => self.write_fixed(signed::<i16>(value)?),
+                SQL_C_USHORT => self.write_fixed(unsigned::<u16>(value)?),
+                SQL_C_SLONG | SQL_C_LONG => self.write_fixed(signed::<i32>(value)?),
+                SQL_C_ULONG => self.write_fixed(unsigned::<u32>(value)?),
+                SQL_C_SBIGINT => self.write_fixed(signed::<i64>(value)?),
+                SQL_C_UBIGINT => self.write_fixed(unsigned::<u64>(value)?),
+                SQL_C_FLOAT => self.write_fixed(f32::try_from(value)?),
+                SQL_C_DOUBLE => self.write_fixed(f64::try_from(value)?),
+                SQL_C_TYPE_DATE => {
+                    let date = NaiveDate::try_from(value)?;
+
+                    self.write_fixed(DateStruct {
+                        year: date.year() as i16,
+                        month: date.month() as u16,
+                        day: date.day() as u16,
+                    })
+                }
+                SQL_C_TYPE_TIME => {
+                    let time = NaiveTime::try_from(value)?;
+
+                    self.write_fixed(TimeStruct {
+                        hour: time.hour() as u16,
+                        minute: time.minute() as u16,
+                        second: time.second() as u16,
+                    })
+                }
+                SQL_C_TYPE_TIMESTAMP => {
+                    let timestamp = match value {
+                        Value::Date(date) => date.and_time(NaiveTime::MIN),
+                        value => NaiveDateTime::try_from(value)?,
+                    };
+
+                    self.write_fixed(TimestampStruct {
+                        year: timestamp.year() as i16,
+                        month: timestamp.month() as u16,
+                        day: timestamp.day() as u16,
+                        hour: timestamp.hour() as u16,
+                        minute: timestamp.minute() as u16,
+                        second: timestamp.second() as u16,
+                        fraction: timestamp.nanosecond(),
+                    })
+                }
+                c_type => Err(OdbcError::UnsupportedCType(c_type)),
+            }
+        }
+    }
+
+    /// Writes as much of `data[offset..]` as fits, followed by a terminator of `nul` bytes.
+    unsafe fn write_parts(&self, data: &[u8], nul: usize, offset: &mut usize) -> (SqlLen, bool) {
+        let remaining = &data[(*offset).min(data.len())..];
+        let capacity = (self.buffer_length.max(0) as usize).saturating_sub(nul);
+        let capacity = capacity - capacity % nul.max(1);
+        let written = remaining.len().min(capacity);
+
+        if !self.pointer.is_null() && self.buffer_length.max(0) as usize >= nul {
+            let pointer = self.pointer.cast::<u8>();
+            unsafe {
+                ptr::copy_nonoverlapping(remaining.as_ptr(), pointer, written);
+                ptr::write_bytes(pointer.add(written), 0, nul);
+            }
+            *offset += written;
+        }
+
+        (remaining.len() as SqlLen, written < remaining.len())
+    }
+
+    unsafe fn write_fixed<T>(&self, value: T) -> Result<(SqlLen, bool)> {
+        if !self.pointer.is_null() {
+            unsafe { ptr::write_unaligned(self.pointer.cast::<T>(), value) };
+        }
+
+        Ok((size_of::<T>() as SqlLen, false))
+    }
+}
+
+fn signed<T: TryFrom<i64>>(value: &Value) -> Result<T> {
+    T::try_from(i64::try_from(value)?).map_err(|_| OdbcError::OutOfRange)
+}
+
+fn unsigned<T: TryFrom<u64>>(value: &Value) -> Result<T> {
+    T::try_from(u64::try_from(value)?).map_err(|_| OdbcError::OutOfRange)
+}
+
+/// Reads a parameter bound with `SQLBindParameter` into a value.
+///
+/// # Safety
+/// `pointer` must be valid for reads of the C type, or of `length` bytes for character and
+/// binary types.
+pub unsafe fn read_parameter(
+    c_type: SqlSmallInt,
+    pointer: SqlPointer,
+    length: Option<SqlLen>,
+) -> Result<Value> {
+    if pointer.is_null() {
+        return Err(OdbcError::NullPointer("parameter value"));
+    }
+
+    let bytes = |units: usize| -> &[u8] {
+        let len = match length {
+            Some(len) if len >= 0 => len as usize,
+            _ => {
+                let mut len = 0;
+                while unsafe { (0..units).any(|i| *pointer.cast::<u8>().add(len + i) != 0) } {
+                    len += units;
+                }
+                len
+            }
+        };
+
+        unsafe { std::slice::from_raw_parts(pointer.cast::<u8>(), len) }
+    };
+    let read = |size| unsafe { std::slice::from_raw_parts(pointer.cast::<u8>(), size) };
+
+    let value = unsafe {
+        match c_type {
+            SQL_C_CHAR | SQL_C_DEFAULT => Value::Str(
+                std::str::from_utf8(bytes(1))
+                    .map_err(|_| OdbcError::InvalidUtf8("parameter value"))?
+                    .to_owned(),
+            ),
+            SQL_C_WCHAR => {
+                let units = bytes(2)
+                    .chunks_exact(2)
+                    .map(|unit| u16::from_ne_bytes([unit[0], unit[1]]))
+                    .collect::<Vec<_>>();
+
+                Value::Str(
+                    String::from_utf16(&units)
+                        .map_err(|_| OdbcError::InvalidUtf8("parameter value"))?,
+                )
+            }
+            SQL_C_BINARY => Value::Bytea(bytes(1).to_vec()),
+            SQL_C_BIT => Value::Bool(read(1)[0] != 0),
+            SQL_C_STINYINT | SQL_C_TINYINT => Value::I8(ptr::read_unaligned(pointer.cast())),
+            SQL_C_UTINYINT => Value::U8(ptr::read_unaligned(pointer.cast())),
+            SQL_C_SSHORT | SQL_C_SHORT => Value::I16(ptr::read_unaligned(pointer.cast())),
+            SQL_C_USHORT => Value::U16(ptr::read_unaligned(pointer.cast())),
+            SQL_C_SLONG | SQL_C_LONG => Value::I32(ptr::read_unaligned(pointer.cast())),
+            SQL_C_ULONG => Value::U32(ptr::read_unaligned(pointer.cast())),
+            SQL_C_SBIGINT => Value::I64(ptr::read_unaligned(pointer.cast())),
+            SQL_C_UBIGINT => Value::U64(ptr::read_unaligned(pointer.cast())),
+            SQL_C_FLOAT => Value::F32(ptr::read_unaligned(pointer.cast())),
+            SQL_C_DOUBLE => Value::F64(ptr::read_unaligned(pointer.cast())),
+            SQL_C_TYPE_DATE => {
+                let date: DateStruct = ptr::read_unaligned(pointer.cast());
+
+                Value::Date(
+                    NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+                        .ok_or(OdbcError::OutOfRange)?,
+                )
+            }
+            SQL_C_TYPE_TIME => {
+                let time: TimeStruct = ptr::read_unaligned(pointer.cast());
+
+                Value::Time(
+                    NaiveTime::from_hms_opt(
+                        time.hour.into(),
+                        time.minute.into(),
+                        time.second.into(),
+                    )
+                    .ok_or(OdbcError::OutOfRange)?,
+                )
+            }
+            SQL_C_TYPE_TIMESTAMP => {
+                let timestamp: TimestampStruct = ptr::read_unaligned(pointer.cast());
+                let date = NaiveDate::from_ymd_opt(
+                    timestamp.year.into(),
+                    timestamp.month.into(),
+                    timestamp.day.into(),
+                );
+                let time = NaiveTime::from_hms_nano_opt(
+                    timestamp.hour.into(),
+                    timestamp.minute.into(),
+                    timestamp.second.into(),
+                    timestamp.fraction,
+                );
+
+                match (date, time) {
+                    (Some(date), Some(time)) => Value::Timestamp(date.and_time(time)),
+                    _ => return Err(OdbcError::OutOfRange),
+                }
+            }
+            c_type => return Err(OdbcError::UnsupportedCType(c_type)),
+        }
+    };
+
+    Ok(value)
+}
+
+/// SQL literal a bound parameter is substituted into a prepared statement with.
+pub fn literal(value: &Value) -> String {
+    let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+
+    match value {
+        Value::Null => "NULL".to_owned(),
+        Value::Bool(value) => value.to_string().to_uppercase(),
+        Value::I8(_)
+        | Value::I16(_)
+        | Value::I32(_)
+        | Value::I64(_)
+        | Value::I128(_)
+        | Value::U8(_)
+        | Value::U16(_)
+        | Value::U32(_)
+        | Value::U64(_)
+        | Value::U128(_)
+        | Value::F32(_)
+        | Value::F64(_)
+        | Value::Decimal(_) => String::from(value),
+        Value::Bytea(bytes) => format!("X{}", quote(&hex(bytes))),
+        Value::Date(date) => format!("DATE {}", quote(&date.to_string())),
+        Value::Time(time) => format!("TIME {}", quote(&time.to_string())),
+        Value::Timestamp(timestamp) => format!("TIMESTAMP {}", quote(&timestamp.to_string())),
+        value => quote(&String::from(value)),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02X}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Column, SqlType, Target, literal},
+        crate::sys::{SQL_C_CHAR, SQL_C_SLONG},
+        gluesql_core::data::Value,
+    };
+
+    #[test]
+    fn infer_column() {
+        let values = [Value::I64(1), Value::Null, Value::I64(3)];
+        assert_eq!(Column::infer("id", values.iter()).sql_type, SqlType::BigInt);
+
+        let values = [Value::Str("abc".to_owned()), Value::Str("abcde".to_owned())];
+        let column = Column::infer("name", values.iter());
+        assert_eq!((column.sql_type, column.size), (SqlType::VarChar, 5));
+
+        let values = [Value::I64(1), Value::Str("a".to_owned())];
+        assert_eq!(
+            Column::infer("mixed", values.iter()).sql_type,
+            SqlType::VarChar
+        );
+    }
+
+    #[test]
+    fn write_in_parts() {
+        let mut buffer = [0_u8; 4];
+        let target = Target {
+            c_type: SQL_C_CHAR,
+            pointer: buffer.as_mut_ptr().cast(),
+            buffer_length: buffer.len() as isize,
+        };
+        let value = Value::Str("GlueSQL".to_owned());
+        let mut offset = 0;
+
+        let written = unsafe { target.write(&value, SqlType::VarChar, &mut offset) }.unwrap();
+        assert_eq!((written, &buffer), ((7, true), b"Glu\0"));
+
+        let written = unsafe { target.write(&value, SqlType::VarChar, &mut offset) }.unwrap();
+        assert_eq!((written, &buffer), ((4, true), b"eSQ\0"));
+
+        let written = unsafe { target.write(&value, SqlType::VarChar, &mut offset) }.unwrap();
+        assert_eq!((written, &buffer[..2]), ((1, false), &b"L\0"[..]));
+
+        let mut int = 0_i32;
+        let target = Target {
+            c_type: SQL_C_SLONG,
+            pointer: (&mut int as *mut i32).cast(),
+            buffer_length: 0,
+        };
+        let written =
+            unsafe { target.write(&Value::Str("42".to_owned()), SqlType::VarChar, &mut 0) };
+        assert_eq!((written.unwrap(), int), ((4, false), 42));
+    }
+
+    #[test]
+    fn parameter_literal() {
+        assert_eq!(literal(&Value::Str("it's".to_owned())), "'it''s'");
+        assert_eq!(literal(&Value::I32(7)), "7");
+        assert_eq!(literal(&Value::Bool(true)), "TRUE");
+        assert_eq!(literal(&Value::Null), "NULL");
+    }
+}
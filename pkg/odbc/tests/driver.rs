@@ -0,0 +1,439 @@
+use {
+    gluesql_odbc::{sys::*, *},
+    std::ptr,
+};
+
+struct Connection {
+    env: SqlHandle,
+    dbc: SqlHandle,
+}
+
+impl Connection {
+    fn open(connection_string: &str) -> Self {
+        unsafe {
+            let mut env = ptr::null_mut();
+            assert_eq!(
+                SQLAllocHandle(SQL_HANDLE_ENV, ptr::null_mut(), &mut env),
+                SQL_SUCCESS
+            );
+            assert_eq!(
+                SQLSetEnvAttr(env, SQL_ATTR_ODBC_VERSION, SQL_OV_ODBC3 as usize as _, 0),
+                SQL_SUCCESS
+            );
+
+            let mut dbc = ptr::null_mut();
+            assert_eq!(SQLAllocHandle(SQL_HANDLE_DBC, env, &mut dbc), SQL_SUCCESS);
+            assert_eq!(
+                SQLDriverConnect(
+                    dbc,
+                    ptr::null_mut(),
+                    connection_string.as_ptr(),
+                    connection_string.len() as i16,
+                    ptr::null_mut(),
+                    0,
+                    ptr::null_mut(),
+                    SQL_DRIVER_NOPROMPT,
+                ),
+                SQL_SUCCESS
+            );
+
+            Self { env, dbc }
+        }
+    }
+
+    fn statement(&self) -> SqlHandle {
+        let mut stmt = ptr::null_mut();
+        assert_eq!(
+            unsafe { SQLAllocHandle(SQL_HANDLE_STMT, self.dbc, &mut stmt) },
+            SQL_SUCCESS
+        );
+
+        stmt
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        unsafe {
+            assert_eq!(SQLDisconnect(self.dbc), SQL_SUCCESS);
+            assert_eq!(SQLFreeHandle(SQL_HANDLE_DBC, self.dbc), SQL_SUCCESS);
+            assert_eq!(SQLFreeHandle(SQL_HANDLE_ENV, self.env), SQL_SUCCESS);
+        }
+    }
+}
+
+fn exec(stmt: SqlHandle, sql: &str) -> SqlReturn {
+    unsafe { SQLExecDirect(stmt, sql.as_ptr(), sql.len() as i32) }
+}
+
+fn sqlstate(handle_type: SqlSmallInt, handle: SqlHandle) -> String {
+    let mut state = [0_u8; 6];
+    let mut message = [0_u8; 256];
+    let mut length = 0;
+    let result = unsafe {
+        SQLGetDiagRec(
+            handle_type,
+            handle,
+            1,
+            state.as_mut_ptr(),
+            ptr::null_mut(),
+            message.as_mut_ptr(),
+            message.len() as i16,
+            &mut length,
+        )
+    };
+    assert_eq!(result, SQL_SUCCESS);
+
+    String::from_utf8(state[..5].to_vec()).unwrap()
+}
+
+fn get_text(stmt: SqlHandle, column: u16) -> Option<String> {
+    let mut buffer = [0_u8; 64];
+    let mut indicator = 0;
+    let result = unsafe {
+        SQLGetData(
+            stmt,
+            column,
+            SQL_C_CHAR,
+            buffer.as_mut_ptr().cast(),
+            buffer.len() as isize,
+            &mut indicator,
+        )
+    };
+    assert_eq!(result, SQL_SUCCESS);
+
+    (indicator != SQL_NULL_DATA)
+        .then(|| String::from_utf8(buffer[..indicator as usize].to_vec()).unwrap())
+}
+
+fn fetch_texts(stmt: SqlHandle) -> Vec<Vec<Option<String>>> {
+    let mut columns = 0;
+    assert_eq!(unsafe { SQLNumResultCols(stmt, &mut columns) }, SQL_SUCCESS);
+
+    let mut rows = Vec::new();
+    while unsafe { SQLFetch(stmt) } == SQL_SUCCESS {
+        rows.push((1..=columns as u16).map(|i| get_text(stmt, i)).collect());
+    }
+
+    rows
+}
+
+fn texts(rows: &[&[Option<&str>]]) -> Vec<Vec<Option<String>>> {
+    rows.iter()
+        .map(|row| row.iter().map(|value| value.map(str::to_owned)).collect())
+        .collect()
+}
+
+#[test]
+fn execute_and_fetch() {
+    let connection = Connection::open("STORAGE=memory");
+    let stmt = connection.statement();
+
+    assert_eq!(
+        exec(stmt, "CREATE TABLE Foo (id INTEGER, name TEXT NULL)"),
+        SQL_SUCCESS
+    );
+    assert_eq!(
+        exec(stmt, "INSERT INTO Foo VALUES (1, 'GlueSQL'), (2, NULL)"),
+        SQL_SUCCESS
+    );
+    let mut row_count = 0;
+    assert_eq!(unsafe { SQLRowCount(stmt, &mut row_count) }, SQL_SUCCESS);
+    assert_eq!(row_count, 2);
+
+    assert_eq!(exec(stmt, "SELECT id, name FROM Foo"), SQL_SUCCESS);
+    let mut name = [0_u8; 16];
+    let (mut name_length, mut data_type, mut size, mut digits, mut nullable) = (0, 0, 0, 0, 0);
+    let result = unsafe {
+        SQLDescribeCol(
+            stmt,
+            2,
+            name.as_mut_ptr(),
+            name.len() as i16,
+            &mut name_length,
+            &mut data_type,
+            &mut size,
+            &mut digits,
+            &mut nullable,
+        )
+    };
+    assert_eq!(result, SQL_SUCCESS);
+    assert_eq!(&name[..name_length as usize], b"name");
+    assert_eq!((data_type, size), (SQL_VARCHAR, 7));
+
+    let mut id = 0_i32;
+    let mut id_indicator = 0;
+    let result = unsafe {
+        SQLBindCol(
+            stmt,
+            1,
+            SQL_C_SLONG,
+            (&mut id as *mut i32).cast(),
+            0,
+            &mut id_indicator,
+        )
+    };
+    assert_eq!(result, SQL_SUCCESS);
+
+    assert_eq!(unsafe { SQLFetch(stmt) }, SQL_SUCCESS);
+    assert_eq!(id, 1);
+
+    let mut part = [0_u8; 5];
+    let mut indicator = 0;
+    let mut get_part = || unsafe {
+        SQLGetData(
+            stmt,
+            2,
+            SQL_C_CHAR,
+            part.as_mut_ptr().cast(),
+            part.len() as isize,
+            &mut indicator,
+        )
+    };
+    assert_eq!(get_part(), SQL_SUCCESS_WITH_INFO);
+    assert_eq!(sqlstate(SQL_HANDLE_STMT, stmt), "01004");
+    assert_eq!(get_part(), SQL_SUCCESS);
+    assert_eq!(get_part(), SQL_NO_DATA);
+
+    assert_eq!(unsafe { SQLFetch(stmt) }, SQL_SUCCESS);
+    assert_eq!(id, 2);
+    assert_eq!(get_text(stmt, 2), None);
+    assert_eq!(unsafe { SQLFetch(stmt) }, SQL_NO_DATA);
+
+    assert_eq!(unsafe { SQLFreeStmt(stmt, SQL_UNBIND) }, SQL_SUCCESS);
+    assert_eq!(exec(stmt, "SELECT 1 AS a; SELECT 'x' AS b"), SQL_SUCCESS);
+    assert_eq!(fetch_texts(stmt), texts(&[&[Some("1")]]));
+    assert_eq!(unsafe { SQLMoreResults(stmt) }, SQL_SUCCESS);
+    assert_eq!(fetch_texts(stmt), texts(&[&[Some("x")]]));
+    assert_eq!(unsafe { SQLMoreResults(stmt) }, SQL_NO_DATA);
+}
+
+#[test]
+fn prepared_parameters() {
+    let connection = Connection::open("Driver={GlueSQL};Storage=memory");
+    let stmt = connection.statement();
+    assert_eq!(
+        exec(stmt, "CREATE TABLE Foo (id INTEGER, name TEXT NULL)"),
+        SQL_SUCCESS
+    );
+
+    let sql = "INSERT INTO Foo VALUES (?, ?)\0";
+    assert_eq!(
+        unsafe { SQLPrepare(stmt, sql.as_ptr(), SQL_NTS) },
+        SQL_SUCCESS
+    );
+    let mut count = 0;
+    assert_eq!(unsafe { SQLNumParams(stmt, &mut count) }, SQL_SUCCESS);
+    assert_eq!(count, 2);
+
+    let id = &mut 0_i32 as *mut i32;
+    let mut name = *b"it's\0";
+    let name_indicator = &mut (SQL_NTS as isize) as *mut isize;
+    unsafe {
+        SQLBindParameter(
+            stmt,
+            1,
+            SQL_PARAM_INPUT,
+            SQL_C_SLONG,
+            SQL_INTEGER,
+            0,
+            0,
+            id.cast(),
+            0,
+            ptr::null_mut(),
+        );
+        SQLBindParameter(
+            stmt,
+            2,
+            SQL_PARAM_INPUT,
+            SQL_C_CHAR,
+            SQL_VARCHAR,
+            16,
+            0,
+            name.as_mut_ptr().cast(),
+            name.len() as isize,
+            name_indicator,
+        );
+    }
+
+    for (next, indicator) in [(1, SQL_NTS as isize), (2, SQL_NULL_DATA)] {
+        unsafe {
+            *id = next;
+            *name_indicator = indicator;
+            assert_eq!(SQLExecute(stmt), SQL_SUCCESS);
+        }
+    }
+
+    assert_eq!(exec(stmt, "SELECT id, name FROM Foo"), SQL_SUCCESS);
+    assert_eq!(
+        fetch_texts(stmt),
+        texts(&[&[Some("1"), Some("it's")], &[Some("2"), None]])
+    );
+
+    assert_eq!(unsafe { SQLFreeStmt(stmt, SQL_RESET_PARAMS) }, SQL_SUCCESS);
+    let sql = "SELECT * FROM Foo WHERE id = ?";
+    assert_eq!(
+        unsafe { SQLPrepare(stmt, sql.as_ptr(), sql.len() as i32) },
+        SQL_SUCCESS
+    );
+    assert_eq!(unsafe { SQLExecute(stmt) }, SQL_ERROR);
+    assert_eq!(sqlstate(SQL_HANDLE_STMT, stmt), "07002");
+}
+
+#[test]
+fn catalog_functions() {
+    let connection = Connection::open("STORAGE=memory");
+    let stmt = connection.statement();
+    assert_eq!(
+        exec(
+            stmt,
+            "CREATE TABLE Foo (id INTEGER PRIMARY KEY, name TEXT NULL); CREATE TABLE Bar (at TIMESTAMP)"
+        ),
+        SQL_SUCCESS
+    );
+
+    let table = "F%";
+    let result = unsafe {
+        SQLTables(
+            stmt,
+            ptr::null(),
+            0,
+            ptr::null(),
+            0,
+            table.as_ptr(),
+            table.len() as i16,
+            ptr::null(),
+            0,
+        )
+    };
+    assert_eq!(result, SQL_SUCCESS);
+    assert_eq!(
+        fetch_texts(stmt),
+        texts(&[&[None, None, Some("Foo"), Some("TABLE"), None]])
+    );
+
+    let table = "Foo";
+    let result = unsafe {
+        SQLColumns(
+            stmt,
+            ptr::null(),
+            0,
+            ptr::null(),
+            0,
+            table.as_ptr(),
+            table.len() as i16,
+            ptr::null(),
+            0,
+        )
+    };
+    assert_eq!(result, SQL_SUCCESS);
+    let columns = fetch_texts(stmt)
+        .into_iter()
+        .map(|row| (row[3].clone(), row[4].clone(), row[17].clone()))
+        .collect::<Vec<_>>();
+    let column = |name: &str, data_type: i16, nullable: &str| {
+        (
+            Some(name.to_owned()),
+            Some(data_type.to_string()),
+            Some(nullable.to_owned()),
+        )
+    };
+    assert_eq!(
+        columns,
+        vec![
+            column("id", SQL_BIGINT, "NO"),
+            column("name", SQL_VARCHAR, "YES")
+        ]
+    );
+
+    assert_eq!(
+        unsafe { SQLGetTypeInfo(stmt, SQL_TYPE_TIMESTAMP) },
+        SQL_SUCCESS
+    );
+    let types = fetch_texts(stmt);
+    assert_eq!(types.len(), 1);
+    assert_eq!(types[0][0].as_deref(), Some("TIMESTAMP"));
+}
+
+#[test]
+fn manual_commit() {
+    let path = "tmp/odbc_manual_commit";
+    let _ = std::fs::remove_dir_all(path);
+    let connection = Connection::open(&format!("STORAGE=sled;PATH={path}"));
+    let stmt = connection.statement();
+    assert_eq!(exec(stmt, "CREATE TABLE Foo (id INTEGER)"), SQL_SUCCESS);
+
+    let autocommit = |value: usize| unsafe {
+        SQLSetConnectAttr(connection.dbc, SQL_ATTR_AUTOCOMMIT, value as _, 0)
+    };
+    let ids = || {
+        assert_eq!(exec(stmt, "SELECT id FROM Foo"), SQL_SUCCESS);
+        fetch_texts(stmt)
+    };
+
+    assert_eq!(autocommit(SQL_AUTOCOMMIT_OFF), SQL_SUCCESS);
+    assert_eq!(exec(stmt, "INSERT INTO Foo VALUES (1)"), SQL_SUCCESS);
+    assert_eq!(ids(), texts(&[&[Some("1")]]));
+    assert_eq!(
+        unsafe { SQLEndTran(SQL_HANDLE_DBC, connection.dbc, SQL_ROLLBACK) },
+        SQL_SUCCESS
+    );
+    assert_eq!(ids(), texts(&[]));
+
+    assert_eq!(exec(stmt, "INSERT INTO Foo VALUES (2)"), SQL_SUCCESS);
+    assert_eq!(
+        unsafe { SQLEndTran(SQL_HANDLE_ENV, connection.env, SQL_COMMIT) },
+        SQL_SUCCESS
+    );
+    assert_eq!(autocommit(SQL_AUTOCOMMIT_ON), SQL_SUCCESS);
+    assert_eq!(ids(), texts(&[&[Some("2")]]));
+
+    drop(connection);
+    std::fs::remove_dir_all(path).unwrap();
+}
+
+#[test]
+fn errors() {
+    let connection = Connection::open("STORAGE=memory");
+    let stmt = connection.statement();
+
+    assert_eq!(exec(stmt, "SELEC 1"), SQL_ERROR);
+    assert_eq!(sqlstate(SQL_HANDLE_STMT, stmt), "42000");
+    assert_eq!(exec(stmt, "SELECT * FROM Missing"), SQL_ERROR);
+    assert_eq!(sqlstate(SQL_HANDLE_STMT, stmt), "HY000");
+    assert_eq!(unsafe { SQLFetch(stmt) }, SQL_ERROR);
+    assert_eq!(sqlstate(SQL_HANDLE_STMT, stmt), "24000");
+
+    assert_eq!(unsafe { SQLFetch(connection.dbc) }, SQL_INVALID_HANDLE);
+    assert_eq!(unsafe { SQLFetch(ptr::null_mut()) }, SQL_INVALID_HANDLE);
+
+    let connection_string = "STORAGE=sled";
+    let result = unsafe {
+        SQLDriverConnect(
+            connection.dbc,
+            ptr::null_mut(),
+            connection_string.as_ptr(),
+            connection_string.len() as i16,
+            ptr::null_mut(),
+            0,
+            ptr::null_mut(),
+            SQL_DRIVER_NOPROMPT,
+        )
+    };
+    assert_eq!(result, SQL_ERROR);
+    assert_eq!(sqlstate(SQL_HANDLE_DBC, connection.dbc), "08002");
+
+    let mut name = [0_u8; 16];
+    let mut length = 0;
+    let result = unsafe {
+        SQLGetInfo(
+            connection.dbc,
+            SQL_DBMS_NAME,
+            name.as_mut_ptr().cast(),
+            name.len() as i16,
+            &mut length,
+        )
+    };
+    assert_eq!(result, SQL_SUCCESS);
+    assert_eq!(&name[..length as usize], b"GlueSQL");
+}
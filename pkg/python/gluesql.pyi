@@ -8,6 +8,7 @@ from typing import Any, Final
 class Glue:
     def __init__(self, storage: "Storage") -> None: ...
     def query(sql: str): ...
+    def backup(path: str) -> None: ...
 
 class Storage(metaclass=ABCMeta):
     pass
@@ -5,10 +5,12 @@ use {
     gluesql_core::{
         ast::Statement,
         prelude::{Payload, execute, parse, plan},
+        store::Backup,
         translate::translate,
     },
     payload::{PyPayload, convert},
     pyo3::{prelude::*, types::PyString},
+    std::path::Path,
     storages::{
         PyJsonStorage, PyMemoryStorage, PySharedMemoryStorage, PySledStorage, PySledStorageConfig,
         PySledStorageModeConfig, PyStorageEngine,
@@ -32,6 +34,16 @@ macro_rules! plan {
     }};
 }
 
+macro_rules! backup {
+    ($storage:expr, $target_path:expr) => {{
+        $storage
+            .0
+            .backup($target_path)
+            .await
+            .map_err(|e| GlueSQLError::new_err(e.to_string()))
+    }};
+}
+
 macro_rules! execute {
     ($storage:expr, $statement:expr) => {{
         execute(&mut $storage.0, $statement)
@@ -64,6 +76,18 @@ impl PyGlue {
             PyStorageEngine::Sled(storage) => execute!(storage, &statement),
         }
     }
+
+    #[tokio::main]
+    pub async fn backup_storage(&self, target_path: &Path) -> PyResult<()> {
+        let storage = &self.storage;
+
+        match storage {
+            PyStorageEngine::Memory(storage) => backup!(storage, target_path),
+            PyStorageEngine::Json(storage) => backup!(storage, target_path),
+            PyStorageEngine::SharedMemory(storage) => backup!(storage, target_path),
+            PyStorageEngine::Sled(storage) => backup!(storage, target_path),
+        }
+    }
 }
 
 #[pymethods]
@@ -89,6 +113,12 @@ impl PyGlue {
 
         Ok(convert(py, payloads))
     }
+
+    pub fn backup(&self, path: &PyString) -> PyResult<()> {
+        let path = path.to_string();
+
+        self.backup_storage(Path::new(&path))
+    }
 }
 
 #[pymodule]
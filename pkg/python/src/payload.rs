@@ -25,6 +25,11 @@ fn convert_payload(payload: Payload) -> Json {
     match payload {
         Payload::Create => json!({ "type": "CREATE TABLE" }),
         Payload::DropTable(num) => json!({ "type": "DROP TABLE", "affected": num }),
+        Payload::DropView(num) => json!({ "type": "DROP VIEW", "affected": num }),
+        Payload::DropSequence(num) => json!({ "type": "DROP SEQUENCE", "affected": num }),
+        Payload::RefreshMaterializedView(num) => {
+            json!({ "type": "REFRESH MATERIALIZED VIEW", "affected": num })
+        }
         Payload::Select { labels, rows } => {
             let rows = rows
                 .into_iter()
@@ -71,6 +76,12 @@ fn convert_payload(payload: Payload) -> Json {
                 "rows": Json::Array(rows),
             })
         }
+        Payload::Returning { labels, rows } => {
+            let mut json = convert_payload(Payload::Select { labels, rows });
+            json["type"] = json!("RETURNING");
+
+            json
+        }
         Payload::ShowColumns(columns) => {
             let columns = columns
                 .into_iter()
@@ -102,9 +113,12 @@ fn convert_payload(payload: Payload) -> Json {
         Payload::AlterTable => json!({ "type": "ALTER TABLE" }),
         Payload::CreateIndex => json!({ "type": "CREATE INDEX" }),
         Payload::DropIndex => json!({ "type": "DROP INDEX" }),
+        Payload::CreateTrigger => json!({ "type": "CREATE TRIGGER" }),
+        Payload::DropTrigger => json!({ "type": "DROP TRIGGER" }),
         Payload::StartTransaction => json!({ "type": "BEGIN" }),
         Payload::Commit => json!({ "type": "COMMIT" }),
         Payload::Rollback => json!({ "type": "ROLLBACK" }),
+        Payload::SetVariable => json!({ "type": "SET" }),
         Payload::ShowVariable(PayloadVariable::Version(version)) => {
             json!({
                 "type": "SHOW VERSION",
@@ -118,6 +132,9 @@ fn convert_payload(payload: Payload) -> Json {
             })
         }
         Payload::DropFunction => json!({ "type": "DROP FUNCTION" }),
+        Payload::DropUser => json!({ "type": "DROP USER" }),
+        Payload::Grant => json!({ "type": "GRANT" }),
+        Payload::Revoke => json!({ "type": "REVOKE" }),
         Payload::ShowVariable(PayloadVariable::Functions(function_names)) => {
             json!({
                 "type": "SHOW FUNCTIONS",
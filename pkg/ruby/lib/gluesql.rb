@@ -0,0 +1,9 @@
+require_relative "gluesql/version"
+
+module GlueSQL
+  # Raised when GlueSQL fails to open a storage, parse, plan or execute a statement.
+  class Error < StandardError; end
+end
+
+require_relative "gluesql/native"
+require_relative "gluesql/glue"
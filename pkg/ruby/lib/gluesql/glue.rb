@@ -0,0 +1,134 @@
+require "json"
+require "monitor"
+
+module GlueSQL
+  # An embedded GlueSQL database.
+  #
+  # Queries on one instance are serialized, so it can be shared between
+  # threads. A transaction block keeps the database to the calling thread
+  # until the block returns.
+  class Glue
+    def self.memory
+      new(Native.gluesql_new_memory)
+    end
+
+    def self.shared_memory
+      new(Native.gluesql_new_shared_memory)
+    end
+
+    def self.json(path)
+      new(Native.call(:gluesql_new_json, path.to_s))
+    end
+
+    def self.sled(path)
+      new(Native.call(:gluesql_new_sled, path.to_s))
+    end
+
+    def self.redb(path)
+      new(Native.call(:gluesql_new_redb, path.to_s))
+    end
+
+    def self.version
+      Native.gluesql_version
+    end
+
+    private_class_method :new
+
+    def initialize(pointer)
+      @handle = FFI::AutoPointer.new(pointer, Native.method(:gluesql_free))
+      @monitor = Monitor.new
+    end
+
+    # Runs every statement in +sql+ and returns one payload hash per statement,
+    # e.g. <tt>{ type: "SELECT", labels: ["id"], rows: [{ "id" => 1 }] }</tt>.
+    def query(sql)
+      @monitor.synchronize do
+        parse(Native.call(:gluesql_query, handle, sql))
+      end
+    end
+
+    # Returns the rows of the last statement in +sql+ as hashes keyed by column.
+    def select(sql)
+      payload = query(sql).last
+      raise Error, "statement did not return rows" unless payload && payload[:type] == "SELECT"
+
+      payload[:rows]
+    end
+
+    # Parses and plans +sql+ once for repeated execution.
+    def prepare(sql)
+      @monitor.synchronize do
+        Statement.new(self, Native.call(:gluesql_prepare, handle, sql))
+      end
+    end
+
+    # Runs the block inside BEGIN / COMMIT, rolling back when it raises.
+    #
+    #   glue.transaction do |tx|
+    #     tx.query("INSERT INTO Account VALUES (1, 100)")
+    #   end
+    def transaction
+      @monitor.synchronize do
+        query("BEGIN")
+        begin
+          result = yield self
+        rescue Exception
+          query("ROLLBACK")
+          raise
+        end
+        query("COMMIT")
+
+        result
+      end
+    end
+
+    def close
+      @monitor.synchronize do
+        @handle&.free
+        @handle = nil
+      end
+    end
+
+    def closed?
+      @handle.nil?
+    end
+
+    # @api private
+    def execute_statement(statement)
+      @monitor.synchronize do
+        parse(Native.call(:gluesql_execute, handle, statement))
+      end
+    end
+
+    private
+
+    def handle
+      @handle or raise Error, "database is closed"
+    end
+
+    def parse(result)
+      JSON.parse(Native.take_string(result)).map do |payload|
+        payload.transform_keys(&:to_sym)
+      end
+    end
+  end
+
+  # Statements parsed and planned by Glue#prepare.
+  class Statement
+    def initialize(glue, pointer)
+      @glue = glue
+      @handle = FFI::AutoPointer.new(pointer, Native.method(:gluesql_statement_free))
+    end
+
+    def execute
+      raise Error, "statement is closed" unless @handle
+
+      @glue.execute_statement(@handle)
+    end
+
+    def close
+      @handle&.free
+      @handle = nil
+    end
+  end
+end
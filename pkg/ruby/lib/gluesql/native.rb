@@ -0,0 +1,52 @@
+require "ffi"
+
+module GlueSQL
+  # Bindings for pkg/c/include/gluesql.h.
+  #
+  # The library is looked up in GLUESQL_LIB first, then in the cargo release
+  # directory of this repository, then on the system library path.
+  module Native
+    extend FFI::Library
+
+    LIBRARY_CANDIDATES = [
+      ENV["GLUESQL_LIB"],
+      File.expand_path("../../../../target/release/#{FFI.map_library_name("gluesql")}", __dir__),
+      "gluesql"
+    ].compact.freeze
+
+    ffi_lib LIBRARY_CANDIDATES
+
+    attach_function :gluesql_new_memory, [], :pointer
+    attach_function :gluesql_new_shared_memory, [], :pointer
+    attach_function :gluesql_new_json, %i[string pointer], :pointer
+    attach_function :gluesql_new_sled, %i[string pointer], :pointer
+    attach_function :gluesql_new_redb, %i[string pointer], :pointer
+    attach_function :gluesql_free, [:pointer], :void
+
+    attach_function :gluesql_query, %i[pointer string pointer], :pointer, blocking: true
+    attach_function :gluesql_prepare, %i[pointer string pointer], :pointer
+    attach_function :gluesql_execute, %i[pointer pointer pointer], :pointer, blocking: true
+    attach_function :gluesql_statement_free, [:pointer], :void
+
+    attach_function :gluesql_string_free, [:pointer], :void
+    attach_function :gluesql_version, [], :string
+
+    # Calls a fallible native function, raising GlueSQL::Error on failure.
+    def self.call(name, *args)
+      error = FFI::MemoryPointer.new(:pointer)
+      result = public_send(name, *args, error)
+      return result unless result.null?
+
+      message = error.read_pointer
+      raise Error, "unknown error" if message.null?
+
+      raise Error, take_string(message)
+    end
+
+    def self.take_string(pointer)
+      pointer.read_string.force_encoding(Encoding::UTF_8)
+    ensure
+      gluesql_string_free(pointer)
+    end
+  end
+end
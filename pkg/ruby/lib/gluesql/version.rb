@@ -0,0 +1,3 @@
+module GlueSQL
+  VERSION = "0.17.0"
+end
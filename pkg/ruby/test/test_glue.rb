@@ -0,0 +1,80 @@
+require "minitest/autorun"
+require "tmpdir"
+require "gluesql"
+
+class TestGlue < Minitest::Test
+  def setup
+    @glue = GlueSQL::Glue.memory
+    @glue.query(<<~SQL)
+      CREATE TABLE Account (id INTEGER, owner TEXT NULL, balance INTEGER);
+      INSERT INTO Account VALUES (1, 'glue', 100), (2, NULL, 50);
+    SQL
+  end
+
+  def teardown
+    @glue.close
+  end
+
+  def test_query_returns_payloads
+    payloads = @glue.query("UPDATE Account SET balance = balance + 1; SELECT id FROM Account WHERE id = 1")
+
+    assert_equal({ type: "UPDATE", affected: 2 }, payloads[0])
+    assert_equal({ type: "SELECT", labels: ["id"], rows: [{ "id" => 1 }] }, payloads[1])
+  end
+
+  def test_select_returns_hashes
+    rows = @glue.select("SELECT * FROM Account ORDER BY id")
+
+    assert_equal [
+      { "id" => 1, "owner" => "glue", "balance" => 100 },
+      { "id" => 2, "owner" => nil, "balance" => 50 }
+    ], rows
+  end
+
+  def test_errors
+    error = assert_raises(GlueSQL::Error) { @glue.query("SELECT * FROM Missing") }
+
+    assert_equal "fetch: table not found: Missing", error.message
+  end
+
+  def test_transaction_commits
+    @glue.transaction do |tx|
+      tx.query("DELETE FROM Account WHERE id = 2")
+    end
+
+    assert_equal [{ "id" => 1 }], @glue.select("SELECT id FROM Account")
+  end
+
+  def test_transaction_rolls_back_on_error
+    assert_raises(RuntimeError) do
+      @glue.transaction do |tx|
+        tx.query("DELETE FROM Account")
+        raise "abort"
+      end
+    end
+
+    assert_equal 2, @glue.select("SELECT COUNT(*) AS n FROM Account").first["n"]
+  end
+
+  def test_prepare
+    statement = @glue.prepare("UPDATE Account SET balance = balance * 2 WHERE id = 1")
+    2.times { statement.execute }
+    statement.close
+
+    assert_equal 400, @glue.select("SELECT balance FROM Account WHERE id = 1").first["balance"]
+  end
+
+  def test_persistent_storage
+    Dir.mktmpdir do |dir|
+      path = File.join(dir, "db.redb")
+
+      glue = GlueSQL::Glue.redb(path)
+      glue.query("CREATE TABLE Item (id INTEGER); INSERT INTO Item VALUES (7);")
+      glue.close
+
+      glue = GlueSQL::Glue.redb(path)
+      assert_equal [{ "id" => 7 }], glue.select("SELECT id FROM Item")
+      glue.close
+    end
+  end
+end
@@ -0,0 +1,25 @@
+// swift-tools-version:5.9
+import PackageDescription
+
+// The native library is built by `cargo build --release -p gluesql-c`.
+// Override its location with the GLUESQL_LIB_DIR environment variable.
+let libraryDirectory = Context.environment["GLUESQL_LIB_DIR"] ?? "\(Context.packageDirectory)/../../target/release"
+
+let package = Package(
+    name: "GlueSQL",
+    platforms: [.macOS(.v12), .iOS(.v15)],
+    products: [
+        .library(name: "GlueSQL", targets: ["GlueSQL"]),
+    ],
+    targets: [
+        .target(
+            name: "CGlueSQL",
+            linkerSettings: [
+                .unsafeFlags(["-L\(libraryDirectory)"]),
+                .linkedLibrary("gluesql"),
+            ]
+        ),
+        .target(name: "GlueSQL", dependencies: ["CGlueSQL"]),
+        .testTarget(name: "GlueSQLTests", dependencies: ["GlueSQL"]),
+    ]
+)
@@ -0,0 +1 @@
+../../../../c/include/gluesql.h
\ No newline at end of file
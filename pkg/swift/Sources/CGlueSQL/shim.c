@@ -0,0 +1 @@
+// SwiftPM requires at least one source file per C target; the symbols come from libgluesql.
@@ -0,0 +1,129 @@
+import CGlueSQL
+import Foundation
+
+/// An embedded GlueSQL database.
+///
+/// `Glue` is an actor, so queries issued from concurrent tasks run one at a time
+/// without blocking the caller's thread.
+public actor Glue {
+    private var handle: OpaquePointer?
+
+    private init(handle: OpaquePointer) {
+        self.handle = handle
+    }
+
+    public static func memory() -> Glue {
+        Glue(handle: gluesql_new_memory())
+    }
+
+    public static func sharedMemory() -> Glue {
+        Glue(handle: gluesql_new_shared_memory())
+    }
+
+    public static func json(path: String) throws -> Glue {
+        Glue(handle: try check { gluesql_new_json(path, &$0) })
+    }
+
+    public static func sled(path: String) throws -> Glue {
+        Glue(handle: try check { gluesql_new_sled(path, &$0) })
+    }
+
+    public static func redb(path: String) throws -> Glue {
+        Glue(handle: try check { gluesql_new_redb(path, &$0) })
+    }
+
+    public static var version: String {
+        String(cString: gluesql_version())
+    }
+
+    deinit {
+        gluesql_free(handle)
+    }
+
+    /// Runs every statement in `sql` and returns one payload per statement.
+    @discardableResult
+    public func query(_ sql: String) throws -> [Payload] {
+        let handle = try liveHandle()
+        let result = try Glue.check { gluesql_query(handle, sql, &$0) }
+
+        return try Glue.decode(result)
+    }
+
+    /// Runs `sql` and decodes the rows of its last statement into `Row`.
+    public func select<Row: Decodable>(_ sql: String, as type: Row.Type = Row.self) throws -> [Row] {
+        guard let payload = try query(sql).last else {
+            return []
+        }
+
+        return try payload.rows(as: type)
+    }
+
+    /// Parses and plans `sql` once for repeated execution.
+    public func prepare(_ sql: String) throws -> PreparedStatement {
+        let handle = try liveHandle()
+        let statement = try Glue.check { gluesql_prepare(handle, sql, &$0) }
+
+        return PreparedStatement(glue: self, handle: statement)
+    }
+
+    func execute(_ statement: OpaquePointer) throws -> [Payload] {
+        let handle = try liveHandle()
+        let result = try Glue.check { gluesql_execute(handle, statement, &$0) }
+
+        return try Glue.decode(result)
+    }
+
+    /// Releases the native database. Further calls throw.
+    public func close() {
+        gluesql_free(handle)
+        handle = nil
+    }
+
+    private func liveHandle() throws -> OpaquePointer {
+        guard let handle else {
+            throw GlueSQLError(message: "database is closed")
+        }
+
+        return handle
+    }
+
+    private static func check<T>(_ call: (inout UnsafeMutablePointer<CChar>?) -> T?) throws -> T {
+        var error: UnsafeMutablePointer<CChar>?
+        if let result = call(&error) {
+            return result
+        }
+
+        guard let error else {
+            throw GlueSQLError(message: "unknown error")
+        }
+        defer { gluesql_string_free(error) }
+
+        throw GlueSQLError(message: String(cString: error))
+    }
+
+    private static func decode(_ result: UnsafeMutablePointer<CChar>) throws -> [Payload] {
+        defer { gluesql_string_free(result) }
+
+        return try JSONDecoder().decode([Payload].self, from: Data(String(cString: result).utf8))
+    }
+}
+
+/// Statements parsed and planned by ``Glue/prepare(_:)``.
+public final class PreparedStatement: @unchecked Sendable {
+    private let glue: Glue
+    private let handle: OpaquePointer
+
+    init(glue: Glue, handle: OpaquePointer) {
+        self.glue = glue
+        self.handle = handle
+    }
+
+    deinit {
+        gluesql_statement_free(handle)
+    }
+
+    @discardableResult
+    public func execute() async throws -> [Payload] {
+        try await glue.execute(handle)
+    }
+}
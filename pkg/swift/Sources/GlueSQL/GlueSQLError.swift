@@ -0,0 +1,6 @@
+/// Raised when GlueSQL fails to open a storage, parse, plan or execute a statement.
+public struct GlueSQLError: Error, Equatable, CustomStringConvertible {
+    public let message: String
+
+    public var description: String { message }
+}
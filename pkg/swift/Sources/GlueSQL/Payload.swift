@@ -0,0 +1,74 @@
+import Foundation
+
+/// Result of a single executed statement.
+public struct Payload: Decodable, Sendable {
+    public let type: String
+    public let affected: Int?
+    public let labels: [String]?
+    public let tables: [String]?
+    public let functions: [String]?
+    public let version: String?
+    public let columns: [ColumnInfo]?
+
+    /// Raw JSON of each returned row; decode it with ``rows(as:decoder:)``.
+    let rows: [JSONValue]?
+
+    public struct ColumnInfo: Decodable, Sendable, Equatable {
+        public let name: String
+        public let type: String
+    }
+
+    /// Decodes the returned rows into `Row`, e.g. a `Codable` struct whose properties match the column labels.
+    public func rows<Row: Decodable>(as _: Row.Type = Row.self, decoder: JSONDecoder = JSONDecoder()) throws -> [Row] {
+        guard let rows else {
+            throw GlueSQLError(message: "\(type) payload does not contain rows")
+        }
+
+        return try rows.map { row in try decoder.decode(Row.self, from: JSONEncoder().encode(row)) }
+    }
+}
+
+/// Type-erased JSON used to keep rows around until the caller picks their Swift type.
+enum JSONValue: Codable, Sendable {
+    case null
+    case bool(Bool)
+    case number(Double)
+    case integer(Int64)
+    case string(String)
+    case array([JSONValue])
+    case object([String: JSONValue])
+
+    init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+
+        if container.decodeNil() {
+            self = .null
+        } else if let value = try? container.decode(Bool.self) {
+            self = .bool(value)
+        } else if let value = try? container.decode(Int64.self) {
+            self = .integer(value)
+        } else if let value = try? container.decode(Double.self) {
+            self = .number(value)
+        } else if let value = try? container.decode(String.self) {
+            self = .string(value)
+        } else if let value = try? container.decode([JSONValue].self) {
+            self = .array(value)
+        } else {
+            self = .object(try container.decode([String: JSONValue].self))
+        }
+    }
+
+    func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+
+        switch self {
+        case .null: try container.encodeNil()
+        case let .bool(value): try container.encode(value)
+        case let .number(value): try container.encode(value)
+        case let .integer(value): try container.encode(value)
+        case let .string(value): try container.encode(value)
+        case let .array(value): try container.encode(value)
+        case let .object(value): try container.encode(value)
+        }
+    }
+}
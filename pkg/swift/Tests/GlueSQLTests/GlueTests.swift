@@ -0,0 +1,68 @@
+import Foundation
+import GlueSQL
+import XCTest
+
+final class GlueTests: XCTestCase {
+    struct Account: Codable, Equatable {
+        let id: Int
+        let owner: String?
+        let balance: Double
+    }
+
+    func testQueryAndCodableRows() async throws {
+        let glue = Glue.memory()
+
+        let payloads = try await glue.query("""
+            CREATE TABLE Account (id INTEGER, owner TEXT NULL, balance FLOAT);
+            INSERT INTO Account VALUES (1, 'glue', 10.5), (2, NULL, 3.0);
+        """)
+        XCTAssertEqual(payloads.map(\.type), ["CREATE TABLE", "INSERT"])
+        XCTAssertEqual(payloads[1].affected, 2)
+
+        let accounts = try await glue.select("SELECT * FROM Account ORDER BY id", as: Account.self)
+        XCTAssertEqual(accounts, [
+            Account(id: 1, owner: "glue", balance: 10.5),
+            Account(id: 2, owner: nil, balance: 3.0),
+        ])
+    }
+
+    func testPreparedStatement() async throws {
+        let glue = Glue.memory()
+        try await glue.query("CREATE TABLE Counter (n INTEGER); INSERT INTO Counter VALUES (0);")
+
+        let increment = try await glue.prepare("UPDATE Counter SET n = n + 1")
+        try await increment.execute()
+        try await increment.execute()
+
+        struct Counter: Decodable { let n: Int }
+        let counters = try await glue.select("SELECT n FROM Counter", as: Counter.self)
+        XCTAssertEqual(counters.first?.n, 2)
+    }
+
+    func testErrors() async throws {
+        let glue = Glue.memory()
+
+        do {
+            try await glue.query("SELECT * FROM Missing")
+            XCTFail("query should fail")
+        } catch let error as GlueSQLError {
+            XCTAssertEqual(error.message, "fetch: table not found: Missing")
+        }
+    }
+
+    func testPersistentStorage() async throws {
+        let path = FileManager.default.temporaryDirectory
+            .appendingPathComponent("gluesql-swift-\(UUID().uuidString)").path
+
+        do {
+            let glue = try Glue.sled(path: path)
+            try await glue.query("CREATE TABLE Item (id INTEGER); INSERT INTO Item VALUES (7);")
+            await glue.close()
+        }
+
+        struct Item: Decodable { let id: Int }
+        let glue = try Glue.sled(path: path)
+        let items = try await glue.select("SELECT id FROM Item", as: Item.self)
+        XCTAssertEqual(items.map(\.id), [7])
+    }
+}
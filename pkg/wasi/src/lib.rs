@@ -0,0 +1,114 @@
+#![deny(clippy::str_to_string)]
+
+//! GlueSQL as a WASI command.
+//!
+//! The crate compiles to `wasm32-wasip1` and runs in server-side WebAssembly runtimes such as
+//! wasmtime or wasmCloud. `MemoryStorage` is used by default; `--json <dir>` switches to a
+//! `JsonStorage` rooted at a directory preopened by the runtime.
+
+mod payload;
+
+use {
+    gluesql_core::prelude::{Glue, Result},
+    gluesql_json_storage::JsonStorage,
+    gluesql_memory_storage::MemoryStorage,
+    serde_json::Value as Json,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum StorageOption {
+    Memory,
+    Json(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    pub storage: StorageOption,
+    /// SQL passed on the command line; read from stdin when `None`.
+    pub sql: Option<String>,
+}
+
+impl Args {
+    pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut storage = StorageOption::Memory;
+        let mut sql = Vec::new();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--json" => {
+                    let path = args.next().ok_or("--json requires a directory")?;
+                    storage = StorageOption::Json(path);
+                }
+                "--memory" => storage = StorageOption::Memory,
+                _ => sql.push(arg),
+            }
+        }
+
+        let sql = (!sql.is_empty()).then(|| sql.join(" "));
+
+        Ok(Self { storage, sql })
+    }
+}
+
+/// Executes `sql` against the selected storage and returns one JSON value per statement.
+pub async fn run(storage: &StorageOption, sql: &str) -> Result<Json> {
+    let payloads = match storage {
+        StorageOption::Memory => Glue::new(MemoryStorage::default()).execute(sql).await?,
+        StorageOption::Json(path) => Glue::new(JsonStorage::new(path)?).execute(sql).await?,
+    };
+
+    payload::convert(payloads)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{Args, StorageOption, run},
+        futures::executor::block_on,
+        serde_json::json,
+    };
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        Args::parse(args.iter().map(|arg| (*arg).to_owned()))
+    }
+
+    #[test]
+    fn parse_args() {
+        assert_eq!(
+            args(&[]),
+            Ok(Args {
+                storage: StorageOption::Memory,
+                sql: None,
+            })
+        );
+        assert_eq!(
+            args(&["--json", "/data", "SELECT", "1"]),
+            Ok(Args {
+                storage: StorageOption::Json("/data".to_owned()),
+                sql: Some("SELECT 1".to_owned()),
+            })
+        );
+        assert_eq!(
+            args(&["--json"]),
+            Err("--json requires a directory".to_owned())
+        );
+    }
+
+    #[test]
+    fn run_memory() {
+        let actual = block_on(run(
+            &StorageOption::Memory,
+            "CREATE TABLE Foo (id INTEGER); INSERT INTO Foo VALUES (1); SELECT * FROM Foo;",
+        ));
+
+        assert_eq!(
+            actual,
+            Ok(json!([
+                { "type": "CREATE TABLE" },
+                { "type": "INSERT", "affected": 1 },
+                { "type": "SELECT", "labels": ["id"], "rows": [{ "id": 1 }] },
+            ]))
+        );
+    }
+}
@@ -0,0 +1,48 @@
+use {
+    futures::executor::block_on,
+    gluesql_wasi::{Args, run},
+    std::{
+        env,
+        io::{self, Read},
+        process::ExitCode,
+    },
+};
+
+fn main() -> ExitCode {
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}");
+            eprintln!("usage: gluesql-wasi [--memory | --json <dir>] [SQL]");
+
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let sql = match args.sql {
+        Some(sql) => sql,
+        None => {
+            let mut sql = String::new();
+            if let Err(error) = io::stdin().read_to_string(&mut sql) {
+                eprintln!("failed to read stdin: {error}");
+
+                return ExitCode::FAILURE;
+            }
+
+            sql
+        }
+    };
+
+    match block_on(run(&args.storage, &sql)) {
+        Ok(payloads) => {
+            println!("{payloads}");
+
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error}");
+
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,296 @@
+//! Conversion of GlueSQL values and schemas into Arrow arrays and schemas.
+
+use {
+    arrow_array::{
+        ArrayRef, BinaryArray, BooleanArray, Date32Array, Float32Array, Float64Array, Int8Array,
+        Int16Array, Int32Array, Int64Array, NullArray, RecordBatch, StringArray,
+        Time64MicrosecondArray, TimestampMicrosecondArray, UInt8Array, UInt16Array, UInt32Array,
+        UInt64Array,
+    },
+    arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef, TimeUnit},
+    chrono::{DateTime, Timelike},
+    gluesql_core::{ast::ColumnDef, prelude::Value},
+    std::sync::Arc,
+};
+
+/// Rows per record batch sent to clients.
+pub const BATCH_SIZE: usize = 8192;
+
+/// Arrow type a GlueSQL column type is sent as.
+///
+/// Types without a lossless Arrow counterpart, such as `DECIMAL`, `INT128` or
+/// `MAP`, are sent as their text form.
+pub fn convert_data_type(data_type: &gluesql_core::ast::DataType) -> DataType {
+    use gluesql_core::ast::DataType as Glue;
+
+    match data_type {
+        Glue::Boolean => DataType::Boolean,
+        Glue::Int8 => DataType::Int8,
+        Glue::Int16 => DataType::Int16,
+        Glue::Int32 => DataType::Int32,
+        Glue::Int => DataType::Int64,
+        Glue::Uint8 => DataType::UInt8,
+        Glue::Uint16 => DataType::UInt16,
+        Glue::Uint32 => DataType::UInt32,
+        Glue::Uint64 => DataType::UInt64,
+        Glue::Float32 => DataType::Float32,
+        Glue::Float => DataType::Float64,
+        Glue::Bytea => DataType::Binary,
+        Glue::Date => DataType::Date32,
+        Glue::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, None),
+        Glue::Time => DataType::Time64(TimeUnit::Microsecond),
+        Glue::Int128
+        | Glue::Uint128
+        | Glue::Text
+        | Glue::Inet
+        | Glue::Interval
+        | Glue::Uuid
+        | Glue::Map
+        | Glue::List
+        | Glue::Decimal
+        | Glue::Point => DataType::Utf8,
+    }
+}
+
+/// Arrow schema of a table; schemaless tables have no columns.
+pub fn convert_schema(column_defs: Option<&[ColumnDef]>) -> Schema {
+    let fields = column_defs
+        .unwrap_or_default()
+        .iter()
+        .map(|column_def| {
+            Field::new(
+                &column_def.name,
+                convert_data_type(&column_def.data_type),
+                column_def.nullable,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Schema::new(fields)
+}
+
+fn data_type_of(value: &Value) -> Option<DataType> {
+    let data_type = match value {
+        Value::Null => return None,
+        Value::Bool(_) => DataType::Boolean,
+        Value::I8(_) => DataType::Int8,
+        Value::I16(_) => DataType::Int16,
+        Value::I32(_) => DataType::Int32,
+        Value::I64(_) => DataType::Int64,
+        Value::U8(_) => DataType::UInt8,
+        Value::U16(_) => DataType::UInt16,
+        Value::U32(_) => DataType::UInt32,
+        Value::U64(_) => DataType::UInt64,
+        Value::F32(_) => DataType::Float32,
+        Value::F64(_) => DataType::Float64,
+        Value::Bytea(_) => DataType::Binary,
+        Value::Date(_) => DataType::Date32,
+        Value::Timestamp(_) => DataType::Timestamp(TimeUnit::Microsecond, None),
+        Value::Time(_) => DataType::Time64(TimeUnit::Microsecond),
+        Value::I128(_)
+        | Value::U128(_)
+        | Value::Decimal(_)
+        | Value::Str(_)
+        | Value::Inet(_)
+        | Value::Interval(_)
+        | Value::Uuid(_)
+        | Value::Map(_)
+        | Value::List(_)
+        | Value::Point(_) => DataType::Utf8,
+    };
+
+    Some(data_type)
+}
+
+/// Infers the Arrow schema of query results from their values.
+///
+/// A column takes the type shared by all of its non-null values, falls back to
+/// text when they disagree, as in schemaless tables, and is `Null` when every
+/// value is null.
+pub fn infer_schema(labels: &[String], rows: &[Vec<Value>]) -> SchemaRef {
+    let fields = labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| {
+            let data_type = rows
+                .iter()
+                .filter_map(|row| row.get(i).and_then(data_type_of))
+                .try_fold(None, |data_type, next| match data_type {
+                    None => Ok(Some(next)),
+                    Some(data_type) if data_type == next => Ok(Some(data_type)),
+                    Some(_) => Err(()),
+                })
+                .map(|data_type| data_type.unwrap_or(DataType::Null))
+                .unwrap_or(DataType::Utf8);
+
+            Field::new(label, data_type, true)
+        })
+        .collect::<Vec<_>>();
+
+    Arc::new(Schema::new(fields))
+}
+
+/// Splits rows into record batches of [`BATCH_SIZE`] following `schema`.
+pub fn convert_rows(
+    schema: &SchemaRef,
+    rows: &[Vec<Value>],
+) -> Result<Vec<RecordBatch>, ArrowError> {
+    rows.chunks(BATCH_SIZE)
+        .map(|rows| {
+            let columns = schema
+                .fields()
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let values = rows.iter().map(|row| row.get(i).unwrap_or(&Value::Null));
+                    convert_column(field.data_type(), values)
+                })
+                .collect();
+
+            RecordBatch::try_new(Arc::clone(schema), columns)
+        })
+        .collect()
+}
+
+fn convert_column<'a>(
+    data_type: &DataType,
+    values: impl ExactSizeIterator<Item = &'a Value>,
+) -> ArrayRef {
+    macro_rules! primitive {
+        ($array:ty, $variant:ident) => {
+            Arc::new(
+                values
+                    .map(|value| match value {
+                        Value::$variant(v) => Some(*v),
+                        _ => None,
+                    })
+                    .collect::<$array>(),
+            )
+        };
+    }
+
+    match data_type {
+        DataType::Null => Arc::new(NullArray::new(values.len())),
+        DataType::Boolean => primitive!(BooleanArray, Bool),
+        DataType::Int8 => primitive!(Int8Array, I8),
+        DataType::Int16 => primitive!(Int16Array, I16),
+        DataType::Int32 => primitive!(Int32Array, I32),
+        DataType::Int64 => primitive!(Int64Array, I64),
+        DataType::UInt8 => primitive!(UInt8Array, U8),
+        DataType::UInt16 => primitive!(UInt16Array, U16),
+        DataType::UInt32 => primitive!(UInt32Array, U32),
+        DataType::UInt64 => primitive!(UInt64Array, U64),
+        DataType::Float32 => primitive!(Float32Array, F32),
+        DataType::Float64 => primitive!(Float64Array, F64),
+        DataType::Binary => Arc::new(
+            values
+                .map(|value| match value {
+                    Value::Bytea(v) => Some(v.as_slice()),
+                    _ => None,
+                })
+                .collect::<BinaryArray>(),
+        ),
+        DataType::Date32 => {
+            let epoch = DateTime::UNIX_EPOCH.date_naive();
+
+            Arc::new(
+                values
+                    .map(|value| match value {
+                        Value::Date(v) => Some((*v - epoch).num_days() as i32),
+                        _ => None,
+                    })
+                    .collect::<Date32Array>(),
+            )
+        }
+        DataType::Timestamp(..) => Arc::new(
+            values
+                .map(|value| match value {
+                    Value::Timestamp(v) => Some(v.and_utc().timestamp_micros()),
+                    _ => None,
+                })
+                .collect::<TimestampMicrosecondArray>(),
+        ),
+        DataType::Time64(_) => Arc::new(
+            values
+                .map(|value| match value {
+                    Value::Time(v) => Some(
+                        v.num_seconds_from_midnight() as i64 * 1_000_000
+                            + (v.nanosecond() / 1_000) as i64,
+                    ),
+                    _ => None,
+                })
+                .collect::<Time64MicrosecondArray>(),
+        ),
+        _ => Arc::new(
+            values
+                .map(|value| (!value.is_null()).then(|| String::from(value)))
+                .collect::<StringArray>(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{convert_rows, infer_schema},
+        arrow_array::{Array, Date32Array, Int64Array, StringArray},
+        arrow_schema::DataType,
+        chrono::NaiveDate,
+        gluesql_core::prelude::Value,
+    };
+
+    #[test]
+    fn infer_and_convert() {
+        let labels = ["id", "name", "born", "empty"].map(ToOwned::to_owned);
+        let rows = vec![
+            vec![
+                Value::I64(1),
+                Value::Str("Glue".to_owned()),
+                Value::Date(NaiveDate::from_ymd_opt(1970, 1, 11).unwrap()),
+                Value::Null,
+            ],
+            vec![Value::Null, Value::I64(3), Value::Null, Value::Null],
+        ];
+
+        let schema = infer_schema(&labels, &rows);
+        let data_types = schema
+            .fields()
+            .iter()
+            .map(|field| field.data_type().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            data_types,
+            vec![
+                DataType::Int64,
+                DataType::Utf8,
+                DataType::Date32,
+                DataType::Null
+            ]
+        );
+
+        let batches = convert_rows(&schema, &rows).unwrap();
+        assert_eq!(batches.len(), 1);
+
+        let batch = &batches[0];
+        let ids = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!((ids.value(0), ids.is_null(1)), (1, true));
+
+        let names = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!((names.value(0), names.value(1)), ("Glue", "3"));
+
+        let born = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Date32Array>()
+            .unwrap();
+        assert_eq!(born.value(0), 10);
+    }
+}
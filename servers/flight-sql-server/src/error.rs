@@ -0,0 +1,43 @@
+use {
+    arrow_schema::ArrowError, gluesql_core::error::Error as GlueError,
+    thiserror::Error as ThisError, tonic::Status,
+};
+
+pub type Result<T, E = FlightSqlServerError> = std::result::Result<T, E>;
+
+#[derive(ThisError, Debug)]
+pub enum FlightSqlServerError {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("transport: {0}")]
+    Transport(#[from] tonic::transport::Error),
+
+    #[error(transparent)]
+    Glue(#[from] GlueError),
+
+    #[error("arrow: {0}")]
+    Arrow(#[from] ArrowError),
+
+    #[error("statement handle not found")]
+    HandleNotFound,
+
+    #[error("transaction was left open and has been rolled back")]
+    UnterminatedTransaction,
+}
+
+impl From<FlightSqlServerError> for Status {
+    fn from(error: FlightSqlServerError) -> Self {
+        let message = error.to_string();
+
+        match error {
+            FlightSqlServerError::HandleNotFound => Status::not_found(message),
+            FlightSqlServerError::UnterminatedTransaction => Status::failed_precondition(message),
+            FlightSqlServerError::Glue(GlueError::StorageMsg(_))
+            | FlightSqlServerError::Io(_)
+            | FlightSqlServerError::Transport(_)
+            | FlightSqlServerError::Arrow(_) => Status::internal(message),
+            FlightSqlServerError::Glue(_) => Status::invalid_argument(message),
+        }
+    }
+}
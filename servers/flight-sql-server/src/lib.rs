@@ -0,0 +1,548 @@
+#![deny(clippy::str_to_string)]
+
+pub mod arrow;
+mod error;
+mod payload;
+
+pub use error::{FlightSqlServerError, Result};
+
+use {
+    arrow_array::{RecordBatch, StringArray},
+    arrow_flight::{
+        Action, FlightDescriptor, FlightEndpoint, FlightInfo, HandshakeRequest, HandshakeResponse,
+        Ticket,
+        encode::FlightDataEncoderBuilder,
+        flight_service_server::{FlightService, FlightServiceServer},
+        sql::{
+            ActionClosePreparedStatementRequest, ActionCreatePreparedStatementRequest,
+            ActionCreatePreparedStatementResult, Any, CommandGetCatalogs, CommandGetDbSchemas,
+            CommandGetSqlInfo, CommandGetTableTypes, CommandGetTables,
+            CommandPreparedStatementQuery, CommandPreparedStatementUpdate, CommandStatementQuery,
+            CommandStatementUpdate, ProstMessageExt, SqlInfo, TicketStatementQuery,
+            metadata::{SqlInfoData, SqlInfoDataBuilder},
+            server::{FlightSqlService, PeekableFlightDataStream},
+        },
+    },
+    arrow_schema::{DataType, Field, Schema, SchemaRef},
+    futures::{Stream, TryStreamExt, stream},
+    gluesql_core::{
+        prelude::{Glue, Payload, Value, parse, plan, translate},
+        store::{GStore, GStoreMut},
+    },
+    payload::Output,
+    prost::Message,
+    std::{
+        collections::HashMap,
+        pin::Pin,
+        sync::{
+            Arc,
+            atomic::{AtomicU64, Ordering},
+        },
+    },
+    tokio::{
+        net::{TcpListener, ToSocketAddrs},
+        sync::Mutex,
+    },
+    tokio_stream::wrappers::TcpListenerStream,
+    tonic::{
+        Request, Response, Status, Streaming, service::Interceptor,
+        service::interceptor::InterceptedService, transport::Server,
+    },
+};
+
+const TABLE_TYPE: &str = "TABLE";
+
+/// Query results waiting to be fetched with `DoGet`.
+struct ResultSet {
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+}
+
+/// Serves a GlueSQL storage over Arrow Flight SQL.
+///
+/// A query is executed when its `FlightInfo` is requested, and the result is
+/// kept until the client fetches it with the returned ticket. Requests are
+/// executed one at a time against a shared [`Glue`]; a transaction must be
+/// committed or rolled back within the statement that started it.
+pub struct FlightSqlServer<T: GStore + GStoreMut> {
+    glue: Arc<Mutex<Glue<T>>>,
+    results: Arc<Mutex<HashMap<Vec<u8>, ResultSet>>>,
+    prepared: Arc<Mutex<HashMap<Vec<u8>, String>>>,
+    handles: Arc<AtomicU64>,
+    sql_info: Arc<SqlInfoData>,
+    api_keys: Arc<Vec<String>>,
+}
+
+impl<T: GStore + GStoreMut> Clone for FlightSqlServer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            glue: Arc::clone(&self.glue),
+            results: Arc::clone(&self.results),
+            prepared: Arc::clone(&self.prepared),
+            handles: Arc::clone(&self.handles),
+            sql_info: Arc::clone(&self.sql_info),
+            api_keys: Arc::clone(&self.api_keys),
+        }
+    }
+}
+
+/// Rejects calls without one of the registered API keys.
+#[derive(Clone)]
+pub struct ApiKeys(Arc<Vec<String>>);
+
+impl Interceptor for ApiKeys {
+    fn call(&mut self, request: Request<()>) -> std::result::Result<Request<()>, Status> {
+        if self.0.is_empty() {
+            return Ok(request);
+        }
+
+        let metadata = request.metadata();
+        let bearer = metadata
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        let api_key = metadata
+            .get("x-api-key")
+            .and_then(|value| value.to_str().ok());
+
+        match bearer.or(api_key) {
+            Some(key) if self.0.iter().any(|api_key| api_key == key) => Ok(request),
+            _ => Err(Status::unauthenticated("missing or invalid API key")),
+        }
+    }
+}
+
+impl<T: GStore + GStoreMut + Send + 'static> FlightSqlServer<T> {
+    pub fn new(storage: T) -> Self {
+        let mut sql_info = SqlInfoDataBuilder::new();
+        sql_info.append(SqlInfo::FlightSqlServerName, "GlueSQL");
+        sql_info.append(SqlInfo::FlightSqlServerVersion, env!("CARGO_PKG_VERSION"));
+        sql_info.append(SqlInfo::FlightSqlServerArrowVersion, "1.3");
+        sql_info.append(SqlInfo::FlightSqlServerReadOnly, false);
+        sql_info.append(SqlInfo::FlightSqlServerTransaction, 0_i32);
+        let sql_info = sql_info.build().expect("valid sql info");
+
+        Self {
+            glue: Arc::new(Mutex::new(Glue::new(storage))),
+            results: Arc::default(),
+            prepared: Arc::default(),
+            handles: Arc::default(),
+            sql_info: Arc::new(sql_info),
+            api_keys: Arc::new(Vec::new()),
+        }
+    }
+
+    /// Accepts calls carrying this key in `authorization: Bearer <key>` or `x-api-key` metadata.
+    ///
+    /// Once a key is registered, calls without a valid key are rejected.
+    pub fn with_api_key(mut self, key: &str) -> Self {
+        Arc::make_mut(&mut self.api_keys).push(key.to_owned());
+        self
+    }
+
+    /// Returns the Flight service to mount on an existing [`Server`].
+    pub fn service(&self) -> InterceptedService<FlightServiceServer<Self>, ApiKeys> {
+        FlightServiceServer::with_interceptor(self.clone(), ApiKeys(Arc::clone(&self.api_keys)))
+    }
+
+    pub async fn bind<A: ToSocketAddrs>(&self, addr: A) -> Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve(listener).await
+    }
+
+    pub async fn serve(&self, listener: TcpListener) -> Result<()> {
+        Server::builder()
+            .add_service(self.service())
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .map_err(Into::into)
+    }
+
+    fn next_handle(&self) -> Vec<u8> {
+        self.handles
+            .fetch_add(1, Ordering::Relaxed)
+            .to_be_bytes()
+            .to_vec()
+    }
+
+    async fn execute(&self, sql: &str) -> Result<Vec<Output>> {
+        let mut glue = self.glue.lock().await;
+        let mut in_transaction = false;
+        let result = run(&mut glue, sql, &mut in_transaction).await;
+
+        if in_transaction {
+            glue.execute("ROLLBACK;").await?;
+            result?;
+
+            return Err(FlightSqlServerError::UnterminatedTransaction);
+        }
+
+        result.map(|payloads| payloads.into_iter().map(Output::from).collect())
+    }
+
+    async fn execute_update(&self, sql: &str) -> Result<i64> {
+        let affected = self
+            .execute(sql)
+            .await?
+            .into_iter()
+            .map(|output| match output {
+                Output::Rows { rows, .. } => rows.len() as u64,
+                Output::Affected(n) => n,
+            })
+            .sum::<u64>();
+
+        Ok(affected as i64)
+    }
+
+    /// Executes the query and keeps its result, returning the `FlightInfo` to fetch it.
+    async fn flight_info(
+        &self,
+        sql: &str,
+        descriptor: FlightDescriptor,
+    ) -> Result<Response<FlightInfo>> {
+        let (labels, rows) = match self.execute(sql).await?.pop() {
+            Some(Output::Rows { labels, rows }) => (labels, rows),
+            Some(Output::Affected(n)) => (vec!["affected".to_owned()], vec![vec![Value::U64(n)]]),
+            None => (Vec::new(), Vec::new()),
+        };
+
+        let schema = arrow::infer_schema(&labels, &rows);
+        let batches = arrow::convert_rows(&schema, &rows)?;
+
+        let handle = self.next_handle();
+        let ticket = TicketStatementQuery {
+            statement_handle: handle.clone().into(),
+        };
+        let endpoint = FlightEndpoint::new().with_ticket(Ticket {
+            ticket: ticket.as_any().encode_to_vec().into(),
+        });
+        let info = FlightInfo::new()
+            .try_with_schema(&schema)?
+            .with_endpoint(endpoint)
+            .with_descriptor(descriptor)
+            .with_total_records(rows.len() as i64);
+
+        self.results
+            .lock()
+            .await
+            .insert(handle, ResultSet { schema, batches });
+
+        Ok(Response::new(info))
+    }
+
+    async fn prepared_sql(&self, handle: &[u8]) -> Result<String> {
+        self.prepared
+            .lock()
+            .await
+            .get(handle)
+            .cloned()
+            .ok_or(FlightSqlServerError::HandleNotFound)
+    }
+}
+
+async fn run<T: GStore + GStoreMut>(
+    glue: &mut Glue<T>,
+    sql: &str,
+    in_transaction: &mut bool,
+) -> Result<Vec<Payload>> {
+    let mut payloads = Vec::new();
+    for statement in parse(sql)?.iter() {
+        let statement = translate(statement)?;
+        let statement = plan(&glue.storage, statement).await?;
+        let payload = glue.execute_stmt(&statement).await?;
+
+        match payload {
+            Payload::StartTransaction => *in_transaction = true,
+            Payload::Commit | Payload::Rollback => *in_transaction = false,
+            _ => {}
+        }
+        payloads.push(payload);
+    }
+
+    Ok(payloads)
+}
+
+fn metadata_info<M: ProstMessageExt>(
+    command: M,
+    schema: &Schema,
+    descriptor: FlightDescriptor,
+) -> Result<Response<FlightInfo>> {
+    let endpoint = FlightEndpoint::new().with_ticket(Ticket {
+        ticket: command.as_any().encode_to_vec().into(),
+    });
+    let info = FlightInfo::new()
+        .try_with_schema(schema)?
+        .with_endpoint(endpoint)
+        .with_descriptor(descriptor);
+
+    Ok(Response::new(info))
+}
+
+type DoGetStream =
+    Pin<Box<dyn Stream<Item = std::result::Result<arrow_flight::FlightData, Status>> + Send>>;
+
+fn encode(schema: SchemaRef, batches: Vec<RecordBatch>) -> Response<DoGetStream> {
+    let stream = FlightDataEncoderBuilder::new()
+        .with_schema(schema)
+        .build(stream::iter(batches.into_iter().map(Ok)))
+        .map_err(Status::from);
+
+    Response::new(Box::pin(stream))
+}
+
+fn table_types_batch() -> Result<(SchemaRef, RecordBatch)> {
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "table_type",
+        DataType::Utf8,
+        false,
+    )]));
+    let column = Arc::new(StringArray::from(vec![TABLE_TYPE]));
+    let batch = RecordBatch::try_new(Arc::clone(&schema), vec![column])?;
+
+    Ok((schema, batch))
+}
+
+#[tonic::async_trait]
+impl<T: GStore + GStoreMut + Send + 'static> FlightSqlService for FlightSqlServer<T> {
+    type FlightService = Self;
+
+    async fn do_handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> std::result::Result<
+        Response<
+            Pin<Box<dyn Stream<Item = std::result::Result<HandshakeResponse, Status>> + Send>>,
+        >,
+        Status,
+    > {
+        let response = HandshakeResponse::default();
+
+        Ok(Response::new(Box::pin(stream::once(async {
+            Ok(response)
+        }))))
+    }
+
+    async fn get_flight_info_statement(
+        &self,
+        query: CommandStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> std::result::Result<Response<FlightInfo>, Status> {
+        self.flight_info(&query.query, request.into_inner())
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_flight_info_prepared_statement(
+        &self,
+        query: CommandPreparedStatementQuery,
+        request: Request<FlightDescriptor>,
+    ) -> std::result::Result<Response<FlightInfo>, Status> {
+        let sql = self.prepared_sql(&query.prepared_statement_handle).await?;
+
+        self.flight_info(&sql, request.into_inner())
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn get_flight_info_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        request: Request<FlightDescriptor>,
+    ) -> std::result::Result<Response<FlightInfo>, Status> {
+        let schema = query.into_builder().schema();
+
+        metadata_info(query, &schema, request.into_inner()).map_err(Into::into)
+    }
+
+    async fn get_flight_info_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        request: Request<FlightDescriptor>,
+    ) -> std::result::Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder().schema();
+
+        metadata_info(query, &schema, request.into_inner()).map_err(Into::into)
+    }
+
+    async fn get_flight_info_tables(
+        &self,
+        query: CommandGetTables,
+        request: Request<FlightDescriptor>,
+    ) -> std::result::Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder().schema();
+
+        metadata_info(query, &schema, request.into_inner()).map_err(Into::into)
+    }
+
+    async fn get_flight_info_table_types(
+        &self,
+        query: CommandGetTableTypes,
+        request: Request<FlightDescriptor>,
+    ) -> std::result::Result<Response<FlightInfo>, Status> {
+        let (schema, _) = table_types_batch()?;
+
+        metadata_info(query, &schema, request.into_inner()).map_err(Into::into)
+    }
+
+    async fn get_flight_info_sql_info(
+        &self,
+        query: CommandGetSqlInfo,
+        request: Request<FlightDescriptor>,
+    ) -> std::result::Result<Response<FlightInfo>, Status> {
+        let schema = query.clone().into_builder(&self.sql_info).schema();
+
+        metadata_info(query, &schema, request.into_inner()).map_err(Into::into)
+    }
+
+    async fn do_get_statement(
+        &self,
+        ticket: TicketStatementQuery,
+        _request: Request<Ticket>,
+    ) -> std::result::Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let ResultSet { schema, batches } = self
+            .results
+            .lock()
+            .await
+            .remove(ticket.statement_handle.as_ref())
+            .ok_or(FlightSqlServerError::HandleNotFound)?;
+
+        Ok(encode(schema, batches))
+    }
+
+    async fn do_get_catalogs(
+        &self,
+        query: CommandGetCatalogs,
+        _request: Request<Ticket>,
+    ) -> std::result::Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let builder = query.into_builder();
+        let schema = builder.schema();
+        let batch = builder.build().map_err(Status::from)?;
+
+        Ok(encode(schema, vec![batch]))
+    }
+
+    async fn do_get_schemas(
+        &self,
+        query: CommandGetDbSchemas,
+        _request: Request<Ticket>,
+    ) -> std::result::Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let builder = query.into_builder();
+        let schema = builder.schema();
+        let batch = builder.build().map_err(Status::from)?;
+
+        Ok(encode(schema, vec![batch]))
+    }
+
+    async fn do_get_tables(
+        &self,
+        query: CommandGetTables,
+        _request: Request<Ticket>,
+    ) -> std::result::Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let schemas = self
+            .glue
+            .lock()
+            .await
+            .storage
+            .fetch_all_schemas()
+            .await
+            .map_err(FlightSqlServerError::from)?;
+
+        let mut builder = query.into_builder();
+        for schema in schemas {
+            let table_schema = arrow::convert_schema(schema.column_defs.as_deref());
+            builder
+                .append("", "", &schema.table_name, TABLE_TYPE, &table_schema)
+                .map_err(Status::from)?;
+        }
+
+        let schema = builder.schema();
+        let batch = builder.build().map_err(Status::from)?;
+
+        Ok(encode(schema, vec![batch]))
+    }
+
+    async fn do_get_table_types(
+        &self,
+        _query: CommandGetTableTypes,
+        _request: Request<Ticket>,
+    ) -> std::result::Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let (schema, batch) = table_types_batch()?;
+
+        Ok(encode(schema, vec![batch]))
+    }
+
+    async fn do_get_sql_info(
+        &self,
+        query: CommandGetSqlInfo,
+        _request: Request<Ticket>,
+    ) -> std::result::Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        let builder = query.into_builder(&self.sql_info);
+        let schema = builder.schema();
+        let batch = builder.build().map_err(Status::from)?;
+
+        Ok(encode(schema, vec![batch]))
+    }
+
+    async fn do_put_statement_update(
+        &self,
+        ticket: CommandStatementUpdate,
+        _request: Request<PeekableFlightDataStream>,
+    ) -> std::result::Result<i64, Status> {
+        self.execute_update(&ticket.query).await.map_err(Into::into)
+    }
+
+    async fn do_put_prepared_statement_update(
+        &self,
+        query: CommandPreparedStatementUpdate,
+        _request: Request<PeekableFlightDataStream>,
+    ) -> std::result::Result<i64, Status> {
+        let sql = self.prepared_sql(&query.prepared_statement_handle).await?;
+
+        self.execute_update(&sql).await.map_err(Into::into)
+    }
+
+    async fn do_action_create_prepared_statement(
+        &self,
+        query: ActionCreatePreparedStatementRequest,
+        _request: Request<Action>,
+    ) -> std::result::Result<ActionCreatePreparedStatementResult, Status> {
+        parse(&query.query).map_err(FlightSqlServerError::from)?;
+
+        let handle = self.next_handle();
+        self.prepared
+            .lock()
+            .await
+            .insert(handle.clone(), query.query);
+
+        Ok(ActionCreatePreparedStatementResult {
+            prepared_statement_handle: handle.into(),
+            ..Default::default()
+        })
+    }
+
+    async fn do_action_close_prepared_statement(
+        &self,
+        query: ActionClosePreparedStatementRequest,
+        _request: Request<Action>,
+    ) -> std::result::Result<(), Status> {
+        self.prepared
+            .lock()
+            .await
+            .remove(query.prepared_statement_handle.as_ref());
+
+        Ok(())
+    }
+
+    async fn do_get_fallback(
+        &self,
+        _request: Request<Ticket>,
+        message: Any,
+    ) -> std::result::Result<Response<<Self as FlightService>::DoGetStream>, Status> {
+        Err(Status::unimplemented(format!(
+            "unsupported ticket: {}",
+            message.type_url
+        )))
+    }
+
+    async fn register_sql_info(&self, _id: i32, _result: &SqlInfo) {}
+}
@@ -0,0 +1,84 @@
+use {
+    gluesql_core::prelude::{Payload, PayloadVariable, Value},
+    std::collections::BTreeSet,
+};
+
+pub enum Output {
+    Rows {
+        labels: Vec<String>,
+        rows: Vec<Vec<Value>>,
+    },
+    Affected(u64),
+}
+
+impl From<Payload> for Output {
+    fn from(payload: Payload) -> Self {
+        let rows = |label: &str, values: Vec<String>| Output::Rows {
+            labels: vec![label.to_owned()],
+            rows: values
+                .into_iter()
+                .map(|value| vec![Value::Str(value)])
+                .collect(),
+        };
+
+        match payload {
+            Payload::Select { labels, rows } | Payload::Returning { labels, rows } => {
+                Output::Rows { labels, rows }
+            }
+            Payload::SelectMap(maps) => {
+                let labels = maps
+                    .iter()
+                    .flat_map(|map| map.keys().cloned())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                let rows = maps
+                    .into_iter()
+                    .map(|mut map| {
+                        labels
+                            .iter()
+                            .map(|label| map.remove(label).unwrap_or(Value::Null))
+                            .collect()
+                    })
+                    .collect();
+
+                Output::Rows { labels, rows }
+            }
+            Payload::ShowColumns(columns) => Output::Rows {
+                labels: vec!["field".to_owned(), "type".to_owned()],
+                rows: columns
+                    .into_iter()
+                    .map(|(name, data_type)| {
+                        vec![Value::Str(name), Value::Str(data_type.to_string())]
+                    })
+                    .collect(),
+            },
+            Payload::ShowVariable(PayloadVariable::Version(version)) => {
+                rows("version", vec![version])
+            }
+            Payload::ShowVariable(PayloadVariable::Tables(names)) => rows("table", names),
+            Payload::ShowVariable(PayloadVariable::Functions(names)) => rows("function", names),
+            Payload::Insert(n) | Payload::Update(n) | Payload::Delete(n) => {
+                Output::Affected(n as u64)
+            }
+            Payload::Create
+            | Payload::DropTable(_)
+            | Payload::DropView(_)
+            | Payload::DropSequence(_)
+            | Payload::RefreshMaterializedView(_)
+            | Payload::DropFunction
+            | Payload::DropUser
+            | Payload::Grant
+            | Payload::Revoke
+            | Payload::AlterTable
+            | Payload::CreateIndex
+            | Payload::DropIndex
+            | Payload::CreateTrigger
+            | Payload::DropTrigger
+            | Payload::StartTransaction
+            | Payload::Commit
+            | Payload::Rollback
+            | Payload::SetVariable => Output::Affected(0),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use {
+    arrow_array::{Array, Int64Array, RecordBatch, StringArray, cast::AsArray},
+    arrow_flight::sql::{CommandGetTables, SqlInfo, client::FlightSqlServiceClient},
+    arrow_schema::{ArrowError, DataType},
+    futures::TryStreamExt,
+    gluesql_core::store::{GStore, GStoreMut},
+    gluesql_flight_sql_server::FlightSqlServer,
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_sled_storage::SledStorage,
+    sled::Config,
+    tokio::net::TcpListener,
+    tonic::transport::{Channel, Endpoint},
+};
+
+async fn connect<T>(server: FlightSqlServer<T>) -> FlightSqlServiceClient<Channel>
+where
+    T: GStore + GStoreMut + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { server.serve(listener).await });
+
+    let channel = Endpoint::from_shared(format!("http://{addr}"))
+        .unwrap()
+        .connect()
+        .await
+        .unwrap();
+
+    FlightSqlServiceClient::new(channel)
+}
+
+async fn fetch(
+    client: &mut FlightSqlServiceClient<Channel>,
+    info: arrow_flight::FlightInfo,
+) -> Vec<RecordBatch> {
+    let ticket = info.endpoint[0].ticket.clone().unwrap();
+
+    client
+        .do_get(ticket)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap()
+}
+
+async fn query(client: &mut FlightSqlServiceClient<Channel>, sql: &str) -> Vec<RecordBatch> {
+    let info = client.execute(sql.to_owned(), None).await.unwrap();
+    fetch(client, info).await
+}
+
+#[tokio::test]
+async fn statement() {
+    let mut client = connect(FlightSqlServer::new(MemoryStorage::default())).await;
+
+    let affected = client
+        .execute_update(
+            "CREATE TABLE Item (id INTEGER, name TEXT NULL);
+             INSERT INTO Item VALUES (1, 'apple'), (2, NULL), (3, 'pear');"
+                .to_owned(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(affected, 3);
+
+    let batches = query(&mut client, "SELECT id, name FROM Item ORDER BY id").await;
+    assert_eq!(batches.len(), 1);
+
+    let batch = &batches[0];
+    let data_types = batch
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.data_type().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(data_types, vec![DataType::Int64, DataType::Utf8]);
+
+    let ids = batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!(ids.values().to_vec(), vec![1, 2, 3]);
+
+    let names = batch.column(1).as_string::<i32>();
+    assert_eq!(
+        names.iter().collect::<Vec<_>>(),
+        vec![Some("apple"), None, Some("pear")]
+    );
+
+    let error = client
+        .execute("SELECT * FROM Missing".to_owned(), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ArrowError::IpcError(_)), "{error:?}");
+}
+
+#[tokio::test]
+async fn batches() {
+    let mut client = connect(FlightSqlServer::new(MemoryStorage::default())).await;
+    client
+        .execute_update(
+            "CREATE TABLE Num (n INTEGER);
+             INSERT INTO Num SELECT N FROM SERIES(10000);"
+                .to_owned(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let batches = query(&mut client, "SELECT n FROM Num").await;
+    assert_eq!(
+        batches
+            .iter()
+            .map(RecordBatch::num_rows)
+            .collect::<Vec<_>>(),
+        vec![8192, 1808]
+    );
+}
+
+#[tokio::test]
+async fn prepared_statement() {
+    let mut client = connect(FlightSqlServer::new(MemoryStorage::default())).await;
+    client
+        .execute_update("CREATE TABLE Item (id INTEGER);".to_owned(), None)
+        .await
+        .unwrap();
+
+    let mut insert = client
+        .prepare("INSERT INTO Item VALUES (1), (2);".to_owned(), None)
+        .await
+        .unwrap();
+    assert_eq!(insert.execute_update().await.unwrap(), 2);
+    insert.close().await.unwrap();
+
+    let mut select = client
+        .prepare("SELECT COUNT(*) AS count FROM Item".to_owned(), None)
+        .await
+        .unwrap();
+    let info = select.execute().await.unwrap();
+    let batches = fetch(&mut client, info).await;
+    let count = batches[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int64Array>()
+        .unwrap();
+    assert_eq!(count.value(0), 2);
+}
+
+#[tokio::test]
+async fn metadata() {
+    let mut client = connect(FlightSqlServer::new(MemoryStorage::default())).await;
+    client
+        .execute_update(
+            "CREATE TABLE Foo (id INTEGER, name TEXT); CREATE TABLE Bar;".to_owned(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let info = client
+        .get_tables(CommandGetTables {
+            table_name_filter_pattern: Some("F%".to_owned()),
+            include_schema: true,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let batches = fetch(&mut client, info).await;
+    let names = batches[0]
+        .column_by_name("table_name")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap();
+    assert_eq!(names.iter().collect::<Vec<_>>(), vec![Some("Foo")]);
+
+    let info = client.get_table_types().await.unwrap();
+    let batches = fetch(&mut client, info).await;
+    assert_eq!(batches[0].column(0).as_string::<i32>().value(0), "TABLE");
+
+    let info = client
+        .get_sql_info(vec![SqlInfo::FlightSqlServerName])
+        .await
+        .unwrap();
+    let batches = fetch(&mut client, info).await;
+    assert_eq!(batches[0].num_rows(), 1);
+}
+
+#[tokio::test]
+async fn api_key() {
+    let mut client =
+        connect(FlightSqlServer::new(MemoryStorage::default()).with_api_key("secret")).await;
+    assert!(
+        client
+            .execute("SHOW TABLES".to_owned(), None)
+            .await
+            .is_err()
+    );
+
+    client.set_header("authorization", "Bearer secret");
+    assert_eq!(query(&mut client, "SHOW VERSION").await[0].num_rows(), 1);
+}
+
+#[tokio::test]
+async fn unterminated_transaction() {
+    let config = Config::default()
+        .path("tmp/flight_sql_server_unterminated_transaction")
+        .temporary(true);
+    let mut client = connect(FlightSqlServer::new(SledStorage::try_from(config).unwrap())).await;
+    client
+        .execute_update("CREATE TABLE Item (id INTEGER);".to_owned(), None)
+        .await
+        .unwrap();
+
+    assert!(
+        client
+            .execute_update("BEGIN; INSERT INTO Item VALUES (1);".to_owned(), None)
+            .await
+            .is_err()
+    );
+
+    let affected = client
+        .execute_update(
+            "BEGIN; INSERT INTO Item VALUES (2); COMMIT;".to_owned(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(affected, 1);
+}
@@ -0,0 +1,36 @@
+use {gluesql_core::error::Error as GlueError, thiserror::Error as ThisError, tonic::Status};
+
+pub type Result<T, E = GrpcServerError> = std::result::Result<T, E>;
+
+#[derive(ThisError, Debug)]
+pub enum GrpcServerError {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("transport: {0}")]
+    Transport(#[from] tonic::transport::Error),
+
+    #[error(transparent)]
+    Glue(#[from] GlueError),
+
+    #[error("missing or invalid API key")]
+    Unauthorized,
+
+    #[error("transaction was left open and has been rolled back")]
+    UnterminatedTransaction,
+}
+
+impl From<GrpcServerError> for Status {
+    fn from(error: GrpcServerError) -> Self {
+        let message = error.to_string();
+
+        match error {
+            GrpcServerError::Unauthorized => Status::unauthenticated(message),
+            GrpcServerError::UnterminatedTransaction => Status::failed_precondition(message),
+            GrpcServerError::Glue(GlueError::StorageMsg(_))
+            | GrpcServerError::Io(_)
+            | GrpcServerError::Transport(_) => Status::internal(message),
+            GrpcServerError::Glue(_) => Status::invalid_argument(message),
+        }
+    }
+}
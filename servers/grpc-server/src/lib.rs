@@ -0,0 +1,269 @@
+#![deny(clippy::str_to_string)]
+
+mod error;
+mod payload;
+
+/// Messages and client generated from `proto/gluesql.proto`.
+pub mod proto {
+    tonic::include_proto!("gluesql.v1");
+}
+
+pub use error::{GrpcServerError, Result};
+
+use {
+    futures::{Stream, stream},
+    gluesql_core::{
+        ast::ToSql,
+        prelude::{Glue, Payload, parse, plan, translate},
+        store::{GStore, GStoreMut},
+    },
+    payload::Output,
+    proto::{
+        Column, DescribeTableRequest, DescribeTableResponse, ExecuteResponse, ListTablesRequest,
+        ListTablesResponse, QueryRequest, QueryResponse, StreamRequest, StreamResponse,
+        VersionRequest, VersionResponse,
+        glue_sql_server::{GlueSql, GlueSqlServer},
+    },
+    std::{pin::Pin, sync::Arc},
+    tokio::{
+        net::{TcpListener, ToSocketAddrs},
+        sync::Mutex,
+    },
+    tokio_stream::wrappers::TcpListenerStream,
+    tonic::{Request, Response, Status, metadata::MetadataMap, transport::Server},
+};
+
+const DEFAULT_BATCH_SIZE: usize = 1024;
+
+/// Serves a GlueSQL storage as the `gluesql.v1.GlueSql` gRPC service.
+///
+/// Requests are executed one at a time against a shared [`Glue`]. A transaction
+/// must be committed or rolled back within the request that started it.
+pub struct GrpcServer<T: GStore + GStoreMut> {
+    glue: Arc<Mutex<Glue<T>>>,
+    api_keys: Arc<Vec<String>>,
+}
+
+impl<T: GStore + GStoreMut> Clone for GrpcServer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            glue: Arc::clone(&self.glue),
+            api_keys: Arc::clone(&self.api_keys),
+        }
+    }
+}
+
+impl<T: GStore + GStoreMut + Send + 'static> GrpcServer<T> {
+    pub fn new(storage: T) -> Self {
+        Self {
+            glue: Arc::new(Mutex::new(Glue::new(storage))),
+            api_keys: Arc::new(Vec::new()),
+        }
+    }
+
+    /// Accepts requests carrying this key in `authorization: Bearer <key>` or `x-api-key` metadata.
+    ///
+    /// Once a key is registered, requests without a valid key are rejected.
+    pub fn with_api_key(mut self, key: &str) -> Self {
+        Arc::make_mut(&mut self.api_keys).push(key.to_owned());
+        self
+    }
+
+    /// Returns the tonic service to mount on an existing [`Server`].
+    pub fn service(&self) -> GlueSqlServer<Self> {
+        GlueSqlServer::new(self.clone())
+    }
+
+    pub async fn bind<A: ToSocketAddrs>(&self, addr: A) -> Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve(listener).await
+    }
+
+    pub async fn serve(&self, listener: TcpListener) -> Result<()> {
+        Server::builder()
+            .add_service(self.service())
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+            .map_err(Into::into)
+    }
+
+    fn authorize(&self, metadata: &MetadataMap) -> Result<()> {
+        if self.api_keys.is_empty() {
+            return Ok(());
+        }
+
+        let bearer = metadata
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        let api_key = metadata
+            .get("x-api-key")
+            .and_then(|value| value.to_str().ok());
+
+        match bearer.or(api_key) {
+            Some(key) if self.api_keys.iter().any(|api_key| api_key == key) => Ok(()),
+            _ => Err(GrpcServerError::Unauthorized),
+        }
+    }
+
+    async fn execute<R>(&self, request: &Request<R>, sql: &str) -> Result<Vec<Output>> {
+        self.authorize(request.metadata())?;
+
+        let mut glue = self.glue.lock().await;
+        let mut in_transaction = false;
+        let result = run(&mut glue, sql, &mut in_transaction).await;
+
+        if in_transaction {
+            glue.execute("ROLLBACK;").await?;
+            result?;
+
+            return Err(GrpcServerError::UnterminatedTransaction);
+        }
+
+        result.map(|payloads| payloads.into_iter().map(Output::from).collect())
+    }
+}
+
+async fn run<T: GStore + GStoreMut>(
+    glue: &mut Glue<T>,
+    sql: &str,
+    in_transaction: &mut bool,
+) -> Result<Vec<Payload>> {
+    let mut payloads = Vec::new();
+    for statement in parse(sql)?.iter() {
+        let statement = translate(statement)?;
+        let statement = plan(&glue.storage, statement).await?;
+        let payload = glue.execute_stmt(&statement).await?;
+
+        match payload {
+            Payload::StartTransaction => *in_transaction = true,
+            Payload::Commit | Payload::Rollback => *in_transaction = false,
+            _ => {}
+        }
+        payloads.push(payload);
+    }
+
+    Ok(payloads)
+}
+
+type ResponseStream = Pin<Box<dyn Stream<Item = Result<StreamResponse, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl<T: GStore + GStoreMut + Send + 'static> GlueSql for GrpcServer<T> {
+    type StreamStream = ResponseStream;
+
+    async fn query(
+        &self,
+        request: Request<QueryRequest>,
+    ) -> Result<Response<QueryResponse>, Status> {
+        let results = self
+            .execute(&request, &request.get_ref().sql)
+            .await?
+            .into_iter()
+            .map(Output::into_result)
+            .collect::<gluesql_core::error::Result<_>>()
+            .map_err(GrpcServerError::from)?;
+
+        Ok(Response::new(QueryResponse { results }))
+    }
+
+    async fn execute(
+        &self,
+        request: Request<QueryRequest>,
+    ) -> Result<Response<ExecuteResponse>, Status> {
+        let commands = GrpcServer::execute(self, &request, &request.get_ref().sql)
+            .await?
+            .into_iter()
+            .map(Output::into_command)
+            .collect();
+
+        Ok(Response::new(ExecuteResponse { commands }))
+    }
+
+    async fn stream(
+        &self,
+        request: Request<StreamRequest>,
+    ) -> Result<Response<Self::StreamStream>, Status> {
+        let batch_size = match request.get_ref().batch_size {
+            0 => DEFAULT_BATCH_SIZE,
+            size => size as usize,
+        };
+        let responses = self
+            .execute(&request, &request.get_ref().sql)
+            .await?
+            .into_iter()
+            .flat_map(move |output| output.into_stream(batch_size))
+            .map(|response| {
+                response
+                    .map(|response| StreamResponse {
+                        response: Some(response),
+                    })
+                    .map_err(|error| GrpcServerError::from(error).into())
+            });
+
+        Ok(Response::new(Box::pin(stream::iter(responses))))
+    }
+
+    async fn list_tables(
+        &self,
+        request: Request<ListTablesRequest>,
+    ) -> Result<Response<ListTablesResponse>, Status> {
+        self.authorize(request.metadata())?;
+
+        let glue = self.glue.lock().await;
+        let tables = glue
+            .storage
+            .fetch_all_schemas()
+            .await
+            .map_err(GrpcServerError::from)?
+            .into_iter()
+            .map(|schema| schema.table_name)
+            .collect();
+
+        Ok(Response::new(ListTablesResponse { tables }))
+    }
+
+    async fn describe_table(
+        &self,
+        request: Request<DescribeTableRequest>,
+    ) -> Result<Response<DescribeTableResponse>, Status> {
+        self.authorize(request.metadata())?;
+
+        let table = &request.get_ref().table;
+        let glue = self.glue.lock().await;
+        let schema = glue
+            .storage
+            .fetch_schema(table)
+            .await
+            .map_err(GrpcServerError::from)?
+            .ok_or_else(|| Status::not_found(format!("table not found: {table}")))?;
+
+        let columns = schema
+            .column_defs
+            .unwrap_or_default()
+            .into_iter()
+            .map(|column_def| Column {
+                name: column_def.name,
+                data_type: column_def.data_type.to_string(),
+                nullable: column_def.nullable,
+                unique: column_def.unique.is_some(),
+                primary_key: column_def.unique.is_some_and(|unique| unique.is_primary),
+                default: column_def.default.map(|expr| expr.to_sql()),
+                comment: column_def.comment,
+            })
+            .collect();
+
+        Ok(Response::new(DescribeTableResponse { columns }))
+    }
+
+    async fn version(
+        &self,
+        request: Request<VersionRequest>,
+    ) -> Result<Response<VersionResponse>, Status> {
+        self.authorize(request.metadata())?;
+
+        Ok(Response::new(VersionResponse {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+        }))
+    }
+}
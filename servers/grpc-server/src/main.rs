@@ -0,0 +1,78 @@
+use {
+    clap::Parser,
+    gluesql_core::store::{GStore, GStoreMut},
+    gluesql_grpc_server::{GrpcServer, Result},
+    gluesql_json_storage::JsonStorage,
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_redb_storage::RedbStorage,
+    gluesql_sled_storage::SledStorage,
+    std::path::PathBuf,
+};
+
+#[derive(Parser, Debug)]
+#[clap(name = "gluesql-grpc-server", about, version)]
+struct Args {
+    /// Address to listen on
+    #[clap(short, long, default_value = "127.0.0.1:50051")]
+    listen: String,
+
+    /// Storage type to store data, default is memory
+    #[clap(short, long, value_parser)]
+    storage: Option<Storage>,
+
+    /// Storage path to load
+    #[clap(short, long, value_parser)]
+    path: Option<PathBuf>,
+
+    /// API key required on every request, can be given multiple times
+    #[clap(long = "api-key", value_parser)]
+    api_keys: Vec<String>,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone)]
+enum Storage {
+    Memory,
+    Sled,
+    Redb,
+    Json,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    match (
+        args.storage.clone().unwrap_or(Storage::Memory),
+        args.path.clone(),
+    ) {
+        (Storage::Memory, None) => serve(MemoryStorage::default(), args).await,
+        (Storage::Memory, Some(_)) => {
+            panic!("failed to load memory-storage: it should be without path")
+        }
+        (Storage::Sled, Some(path)) => {
+            let storage = SledStorage::new(path).expect("failed to load sled-storage");
+            serve(storage, args).await
+        }
+        (Storage::Redb, Some(path)) => {
+            let storage = RedbStorage::new(path).expect("failed to load redb-storage");
+            serve(storage, args).await
+        }
+        (Storage::Json, Some(path)) => {
+            let storage = JsonStorage::new(path).expect("failed to load json-storage");
+            serve(storage, args).await
+        }
+        (storage, None) => panic!("storage path is required for {storage:?}"),
+    }
+}
+
+async fn serve<T: GStore + GStoreMut + Send + 'static>(storage: T, args: Args) -> Result<()> {
+    let server = args
+        .api_keys
+        .iter()
+        .fold(GrpcServer::new(storage), |server, key| {
+            server.with_api_key(key)
+        });
+
+    println!("[gluesql-grpc-server] listening on {}", args.listen);
+    server.bind(args.listen).await
+}
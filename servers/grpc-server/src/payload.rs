@@ -0,0 +1,190 @@
+use {
+    crate::proto::{self, Command, Header, Row, RowBatch, Rows, stream_response, value},
+    gluesql_core::prelude::{Payload, PayloadVariable, Result, Value},
+    std::collections::BTreeSet,
+};
+
+pub enum Output {
+    Rows {
+        labels: Vec<String>,
+        rows: Vec<Vec<Value>>,
+    },
+    Command(Command),
+}
+
+impl From<Payload> for Output {
+    fn from(payload: Payload) -> Self {
+        let rows = |label: &str, values: Vec<String>| Output::Rows {
+            labels: vec![label.to_owned()],
+            rows: values
+                .into_iter()
+                .map(|value| vec![Value::Str(value)])
+                .collect(),
+        };
+        let command = |kind: &str, affected: usize| {
+            Output::Command(Command {
+                kind: kind.to_owned(),
+                affected: affected as u64,
+            })
+        };
+
+        match payload {
+            Payload::Select { labels, rows } | Payload::Returning { labels, rows } => {
+                Output::Rows { labels, rows }
+            }
+            Payload::SelectMap(maps) => {
+                let labels = maps
+                    .iter()
+                    .flat_map(|map| map.keys().cloned())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                let rows = maps
+                    .into_iter()
+                    .map(|mut map| {
+                        labels
+                            .iter()
+                            .map(|label| map.remove(label).unwrap_or(Value::Null))
+                            .collect()
+                    })
+                    .collect();
+
+                Output::Rows { labels, rows }
+            }
+            Payload::ShowColumns(columns) => Output::Rows {
+                labels: vec!["field".to_owned(), "type".to_owned()],
+                rows: columns
+                    .into_iter()
+                    .map(|(name, data_type)| {
+                        vec![Value::Str(name), Value::Str(data_type.to_string())]
+                    })
+                    .collect(),
+            },
+            Payload::ShowVariable(PayloadVariable::Version(version)) => {
+                rows("version", vec![version])
+            }
+            Payload::ShowVariable(PayloadVariable::Tables(names)) => rows("table", names),
+            Payload::ShowVariable(PayloadVariable::Functions(names)) => rows("function", names),
+            Payload::Insert(n) => command("INSERT", n),
+            Payload::Update(n) => command("UPDATE", n),
+            Payload::Delete(n) => command("DELETE", n),
+            Payload::Create => command("CREATE TABLE", 0),
+            Payload::DropTable(n) => command("DROP TABLE", n),
+            Payload::DropView(n) => command("DROP VIEW", n),
+            Payload::DropSequence(n) => command("DROP SEQUENCE", n),
+            Payload::RefreshMaterializedView(n) => command("REFRESH MATERIALIZED VIEW", n),
+            Payload::DropFunction => command("DROP FUNCTION", 0),
+            Payload::DropUser => command("DROP USER", 0),
+            Payload::Grant => command("GRANT", 0),
+            Payload::Revoke => command("REVOKE", 0),
+            Payload::AlterTable => command("ALTER TABLE", 0),
+            Payload::CreateIndex => command("CREATE INDEX", 0),
+            Payload::DropIndex => command("DROP INDEX", 0),
+            Payload::CreateTrigger => command("CREATE TRIGGER", 0),
+            Payload::DropTrigger => command("DROP TRIGGER", 0),
+            Payload::StartTransaction => command("BEGIN", 0),
+            Payload::Commit => command("COMMIT", 0),
+            Payload::Rollback => command("ROLLBACK", 0),
+            Payload::SetVariable => command("SET", 0),
+        }
+    }
+}
+
+impl Output {
+    pub fn into_result(self) -> Result<proto::Result> {
+        let result = match self {
+            Self::Rows { labels, rows } => proto::result::Result::Rows(Rows {
+                labels,
+                rows: rows.into_iter().map(convert_row).collect::<Result<_>>()?,
+            }),
+            Self::Command(command) => proto::result::Result::Command(command),
+        };
+
+        Ok(proto::Result {
+            result: Some(result),
+        })
+    }
+
+    pub fn into_command(self) -> Command {
+        match self {
+            Self::Rows { rows, .. } => Command {
+                kind: "SELECT".to_owned(),
+                affected: rows.len() as u64,
+            },
+            Self::Command(command) => command,
+        }
+    }
+
+    /// Splits the output into a header followed by row batches of `batch_size`.
+    pub fn into_stream(
+        self,
+        batch_size: usize,
+    ) -> Box<dyn Iterator<Item = Result<stream_response::Response>> + Send> {
+        match self {
+            Self::Rows { labels, rows } => {
+                let header = stream_response::Response::Header(Header { labels });
+                let mut rows = rows.into_iter().peekable();
+                let batches = std::iter::from_fn(move || {
+                    rows.peek()?;
+
+                    let batch = rows
+                        .by_ref()
+                        .take(batch_size)
+                        .map(convert_row)
+                        .collect::<Result<_>>()
+                        .map(|rows| stream_response::Response::Batch(RowBatch { rows }));
+                    Some(batch)
+                });
+
+                Box::new(std::iter::once(Ok(header)).chain(batches))
+            }
+            Self::Command(command) => Box::new(std::iter::once(Ok(
+                stream_response::Response::Command(command),
+            ))),
+        }
+    }
+}
+
+fn convert_row(values: Vec<Value>) -> Result<Row> {
+    values
+        .into_iter()
+        .map(convert_value)
+        .collect::<Result<_>>()
+        .map(|values| Row { values })
+}
+
+fn convert_value(value: Value) -> Result<proto::Value> {
+    let text = String::from(&value);
+    let value = match value {
+        Value::Null => value::Value::Null(proto::Null {}),
+        Value::Bool(v) => value::Value::Bool(v),
+        Value::I8(v) => value::Value::Int(v.into()),
+        Value::I16(v) => value::Value::Int(v.into()),
+        Value::I32(v) => value::Value::Int(v.into()),
+        Value::I64(v) => value::Value::Int(v),
+        Value::U8(v) => value::Value::Uint(v.into()),
+        Value::U16(v) => value::Value::Uint(v.into()),
+        Value::U32(v) => value::Value::Uint(v.into()),
+        Value::U64(v) => value::Value::Uint(v),
+        Value::F32(v) => value::Value::Float(v.into()),
+        Value::F64(v) => value::Value::Float(v),
+        Value::I128(_) | Value::U128(_) | Value::Decimal(_) => value::Value::Decimal(text),
+        Value::Str(v) => value::Value::Str(v),
+        Value::Bytea(v) => value::Value::Bytea(v),
+        Value::Inet(_) => value::Value::Inet(text),
+        Value::Date(_) => value::Value::Date(text),
+        Value::Timestamp(_) => value::Value::Timestamp(text),
+        Value::Time(_) => value::Value::Time(text),
+        Value::Interval(_) => value::Value::Interval(text),
+        Value::Uuid(_) => value::Value::Uuid(text),
+        Value::Map(_) | Value::List(_) => {
+            value::Value::Json(serde_json::Value::try_from(value)?.to_string())
+        }
+        Value::Point(point) => value::Value::Point(proto::Point {
+            x: point.x,
+            y: point.y,
+        }),
+    };
+
+    Ok(proto::Value { value: Some(value) })
+}
@@ -0,0 +1,260 @@
+use {
+    gluesql_core::store::{GStore, GStoreMut},
+    gluesql_grpc_server::{
+        GrpcServer,
+        proto::{
+            Command, DescribeTableRequest, Header, ListTablesRequest, QueryRequest, Row, RowBatch,
+            StreamRequest, Value, VersionRequest, glue_sql_client::GlueSqlClient, result,
+            stream_response, value,
+        },
+    },
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_sled_storage::SledStorage,
+    sled::Config,
+    tokio::net::TcpListener,
+    tonic::{Code, Request, transport::Channel},
+};
+
+async fn connect<T>(server: GrpcServer<T>) -> GlueSqlClient<Channel>
+where
+    T: GStore + GStoreMut + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { server.serve(listener).await });
+
+    GlueSqlClient::connect(format!("http://{addr}"))
+        .await
+        .unwrap()
+}
+
+fn query(sql: &str) -> QueryRequest {
+    QueryRequest {
+        sql: sql.to_owned(),
+    }
+}
+
+fn int(v: i64) -> Value {
+    Value {
+        value: Some(value::Value::Int(v)),
+    }
+}
+
+fn command(kind: &str, affected: u64) -> Command {
+    Command {
+        kind: kind.to_owned(),
+        affected,
+    }
+}
+
+#[tokio::test]
+async fn query_and_execute() {
+    let mut client = connect(GrpcServer::new(MemoryStorage::default())).await;
+
+    let commands = client
+        .execute(query(
+            "CREATE TABLE Item (id INTEGER, name TEXT, price DECIMAL NULL);
+             INSERT INTO Item VALUES (1, 'apple', 1.5), (2, 'pear', NULL);",
+        ))
+        .await
+        .unwrap()
+        .into_inner()
+        .commands;
+    assert_eq!(
+        commands,
+        vec![command("CREATE TABLE", 0), command("INSERT", 2)]
+    );
+
+    let results = client
+        .query(query("SELECT id, name, price FROM Item ORDER BY id"))
+        .await
+        .unwrap()
+        .into_inner()
+        .results;
+    let Some(result::Result::Rows(rows)) = &results[0].result else {
+        panic!("expected rows: {results:?}");
+    };
+    assert_eq!(rows.labels, vec!["id", "name", "price"]);
+    assert_eq!(
+        rows.rows
+            .iter()
+            .map(|row| row
+                .values
+                .iter()
+                .map(|v| v.value.clone().unwrap())
+                .collect())
+            .collect::<Vec<Vec<_>>>(),
+        vec![
+            vec![
+                value::Value::Int(1),
+                value::Value::Str("apple".to_owned()),
+                value::Value::Decimal("1.5".to_owned()),
+            ],
+            vec![
+                value::Value::Int(2),
+                value::Value::Str("pear".to_owned()),
+                value::Value::Null(Default::default()),
+            ],
+        ]
+    );
+
+    let status = client
+        .query(query("SELECT * FROM Missing"))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn stream() {
+    let mut client = connect(GrpcServer::new(MemoryStorage::default())).await;
+    client
+        .execute(query(
+            "CREATE TABLE Item (id INTEGER);
+             INSERT INTO Item VALUES (1), (2), (3), (4), (5);",
+        ))
+        .await
+        .unwrap();
+
+    let mut stream = client
+        .stream(StreamRequest {
+            sql: "SELECT id FROM Item; DELETE FROM Item;".to_owned(),
+            batch_size: 2,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    let mut responses = Vec::new();
+    while let Some(response) = stream.message().await.unwrap() {
+        responses.push(response.response.unwrap());
+    }
+
+    let batch = |ids: &[i64]| {
+        stream_response::Response::Batch(RowBatch {
+            rows: ids
+                .iter()
+                .map(|id| Row {
+                    values: vec![int(*id)],
+                })
+                .collect(),
+        })
+    };
+    assert_eq!(
+        responses,
+        vec![
+            stream_response::Response::Header(Header {
+                labels: vec!["id".to_owned()],
+            }),
+            batch(&[1, 2]),
+            batch(&[3, 4]),
+            batch(&[5]),
+            stream_response::Response::Command(command("DELETE", 5)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn metadata() {
+    let mut client = connect(GrpcServer::new(MemoryStorage::default())).await;
+    client
+        .execute(query(
+            "CREATE TABLE Foo (id INTEGER PRIMARY KEY, name TEXT NULL DEFAULT 'glue');
+             CREATE TABLE Bar;",
+        ))
+        .await
+        .unwrap();
+
+    let tables = client
+        .list_tables(ListTablesRequest {})
+        .await
+        .unwrap()
+        .into_inner()
+        .tables;
+    assert_eq!(tables, vec!["Bar", "Foo"]);
+
+    let columns = client
+        .describe_table(DescribeTableRequest {
+            table: "Foo".to_owned(),
+        })
+        .await
+        .unwrap()
+        .into_inner()
+        .columns;
+    assert_eq!(columns.len(), 2);
+    assert_eq!(
+        (columns[0].name.as_str(), columns[0].data_type.as_str()),
+        ("id", "INT")
+    );
+    assert!(columns[0].primary_key && !columns[0].nullable);
+    assert!(columns[1].nullable && !columns[1].unique);
+    assert_eq!(columns[1].default.as_deref(), Some("'glue'"));
+
+    let status = client
+        .describe_table(DescribeTableRequest {
+            table: "Missing".to_owned(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+
+    let version = client
+        .version(VersionRequest {})
+        .await
+        .unwrap()
+        .into_inner()
+        .version;
+    assert_eq!(version, env!("CARGO_PKG_VERSION"));
+}
+
+#[tokio::test]
+async fn api_key() {
+    let mut client =
+        connect(GrpcServer::new(MemoryStorage::default()).with_api_key("secret")).await;
+
+    let status = client.version(VersionRequest {}).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let mut request = Request::new(VersionRequest {});
+    request
+        .metadata_mut()
+        .insert("authorization", "Bearer secret".parse().unwrap());
+    assert!(client.version(request).await.is_ok());
+
+    let mut request = Request::new(ListTablesRequest {});
+    request
+        .metadata_mut()
+        .insert("x-api-key", "secret".parse().unwrap());
+    assert!(client.list_tables(request).await.is_ok());
+}
+
+#[tokio::test]
+async fn unterminated_transaction() {
+    let config = Config::default()
+        .path("tmp/grpc_server_unterminated_transaction")
+        .temporary(true);
+    let mut client = connect(GrpcServer::new(SledStorage::try_from(config).unwrap())).await;
+    client
+        .execute(query("CREATE TABLE Item (id INTEGER);"))
+        .await
+        .unwrap();
+
+    let status = client
+        .execute(query("BEGIN; INSERT INTO Item VALUES (1);"))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    client
+        .execute(query("BEGIN; INSERT INTO Item VALUES (2); COMMIT;"))
+        .await
+        .unwrap();
+
+    let commands = client
+        .execute(query("SELECT id FROM Item"))
+        .await
+        .unwrap()
+        .into_inner()
+        .commands;
+    assert_eq!(commands, vec![command("SELECT", 1)]);
+}
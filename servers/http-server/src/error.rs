@@ -0,0 +1,48 @@
+use {
+    axum::{
+        Json,
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    },
+    gluesql_core::error::Error as GlueError,
+    gluesql_server_session::SessionError,
+    serde_json::json,
+    thiserror::Error as ThisError,
+};
+
+pub type Result<T, E = HttpServerError> = std::result::Result<T, E>;
+
+#[derive(ThisError, Debug)]
+pub enum HttpServerError {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Glue(#[from] GlueError),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+
+    #[error("missing or invalid API key")]
+    Unauthorized,
+
+    #[error("request body is not valid: {0}")]
+    InvalidBody(String),
+
+    #[error("transaction was left open and has been rolled back")]
+    UnterminatedTransaction,
+}
+
+impl IntoResponse for HttpServerError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::Session(SessionError::TooManyConnections(_)) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::InvalidBody(_) | Self::UnterminatedTransaction => StatusCode::BAD_REQUEST,
+            Self::Glue(GlueError::StorageMsg(_)) | Self::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::Glue(_) => StatusCode::BAD_REQUEST,
+        };
+
+        (status, Json(json!({ "error": self.to_string() }))).into_response()
+    }
+}
@@ -0,0 +1,276 @@
+#![deny(clippy::str_to_string)]
+
+mod error;
+mod payload;
+
+pub use error::{HttpServerError, Result};
+
+use {
+    axum::{
+        Json, Router,
+        body::{Body, Bytes},
+        extract::{Query, State},
+        http::{HeaderMap, header},
+        response::{IntoResponse, Response},
+        routing::{get, post},
+    },
+    futures::{
+        SinkExt, StreamExt,
+        channel::mpsc::{self, Sender},
+        future, stream,
+    },
+    gluesql_core::{
+        data::Row,
+        prelude::{Payload, PayloadVariable, parse},
+        store::{GStore, GStoreMut},
+    },
+    gluesql_server_session::{ApiKeys, Authenticator, Session, SessionManager},
+    serde::Deserialize,
+    std::sync::Arc,
+    tokio::net::{TcpListener, ToSocketAddrs},
+};
+
+const NDJSON: &str = "application/x-ndjson";
+
+/// Lines of an NDJSON response produced ahead of the client reading them.
+const NDJSON_BUFFER: usize = 64;
+
+/// Serves a GlueSQL storage over HTTP.
+///
+/// - `POST /query` runs the SQL in the request body and returns the payloads as JSON.
+///   Send `Accept: application/x-ndjson` or `?format=ndjson` to stream them as NDJSON, the
+///   rows of a query as the executor reads them.
+/// - `GET /tables` returns the table names.
+///
+/// Each request runs in its own session against a shared
+/// [`Glue`](gluesql_core::prelude::Glue), one at a time. A transaction must be committed
+/// or rolled back within the request that started it.
+pub struct HttpServer<T: GStore + GStoreMut> {
+    sessions: SessionManager<T>,
+    api_keys: Arc<ApiKeys>,
+}
+
+impl<T: GStore + GStoreMut> Clone for HttpServer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sessions: self.sessions.clone(),
+            api_keys: Arc::clone(&self.api_keys),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct QueryRequest {
+    sql: String,
+}
+
+#[derive(Deserialize)]
+struct QueryParams {
+    format: Option<String>,
+}
+
+impl<T: GStore + GStoreMut + Send + 'static> HttpServer<T> {
+    pub fn new(storage: T) -> Self {
+        Self {
+            sessions: SessionManager::new(storage),
+            api_keys: Arc::default(),
+        }
+    }
+
+    /// Accepts requests carrying this key in `Authorization: Bearer <key>` or `X-API-Key`.
+    ///
+    /// Once a key is registered, requests without a valid key are rejected.
+    pub fn with_api_key(mut self, key: &str) -> Self {
+        Arc::make_mut(&mut self.api_keys).0.push(key.to_owned());
+        let api_keys = ApiKeys::clone(&self.api_keys);
+
+        self.with_authenticator(api_keys)
+    }
+
+    /// Accepts requests carrying a token which passes [`Authenticator::verify_token`],
+    /// replacing the registered API keys.
+    pub fn with_authenticator(mut self, authenticator: impl Authenticator + 'static) -> Self {
+        self.sessions = self.sessions.with_authenticator(authenticator);
+        self
+    }
+
+    /// Rejects requests with `503 Service Unavailable` while `max_connections` are running.
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.sessions = self.sessions.with_max_connections(max_connections);
+        self
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/query", post(query::<T>))
+            .route("/tables", get(tables::<T>))
+            .with_state(self.clone())
+    }
+
+    pub async fn bind<A: ToSocketAddrs>(&self, addr: A) -> Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve(listener).await
+    }
+
+    pub async fn serve(&self, listener: TcpListener) -> Result<()> {
+        axum::serve(listener, self.router())
+            .await
+            .map_err(Into::into)
+    }
+
+    fn authorize(&self, headers: &HeaderMap) -> Result<()> {
+        let Some(authenticator) = self.sessions.authenticator() else {
+            return Ok(());
+        };
+
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        let api_key = headers
+            .get("x-api-key")
+            .and_then(|value| value.to_str().ok());
+
+        match bearer.or(api_key) {
+            Some(key) if authenticator.verify_token(key) => Ok(()),
+            _ => Err(HttpServerError::Unauthorized),
+        }
+    }
+
+    async fn execute(&self, sql: &str) -> Result<Vec<Payload>> {
+        let mut session = self.sessions.open()?;
+        let result = run(&mut session, sql).await;
+
+        close(session, result).await
+    }
+
+    /// Runs the SQL as [`Self::execute`] does, sending the NDJSON lines of the payloads into
+    /// `lines` as they are produced.
+    async fn stream(&self, sql: &str, lines: &mut Sender<Result<String>>) -> Result<()> {
+        let mut session = self.sessions.open()?;
+        let result = run_into(&mut session, sql, lines).await;
+
+        close(session, result).await
+    }
+}
+
+async fn run<T: GStore + GStoreMut + Send + 'static>(
+    session: &mut Session<T>,
+    sql: &str,
+) -> Result<Vec<Payload>> {
+    let mut payloads = Vec::new();
+    for statement in parse(sql)?.iter() {
+        payloads.extend(session.execute(statement).await?);
+    }
+
+    Ok(payloads)
+}
+
+/// Runs the statements, sending a line per row of a query as the executor reads it and a
+/// line per other payload. Lines the client is gone for are dropped, and the statements
+/// still run to the end.
+async fn run_into<T: GStore + GStoreMut + Send + 'static>(
+    session: &mut Session<T>,
+    sql: &str,
+    lines: &mut Sender<Result<String>>,
+) -> Result<()> {
+    for statement in parse(sql)?.iter() {
+        let mut rows = (&mut *lines).with(|row: Row| {
+            future::ready(Ok::<_, mpsc::SendError>(
+                payload::ndjson_row(row).map_err(Into::into),
+            ))
+        });
+        let Some(payload) = session.execute_into(statement, &mut rows).await? else {
+            continue;
+        };
+
+        for line in payload::ndjson(vec![payload]) {
+            let _ = lines.send(line.map_err(Into::into)).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Closes the session, failing a request which left a transaction open.
+async fn close<T: GStore + GStoreMut + Send + 'static, R>(
+    session: Session<T>,
+    result: Result<R>,
+) -> Result<R> {
+    let in_transaction = session.in_transaction();
+    session.close().await?;
+
+    if in_transaction {
+        result?;
+
+        return Err(HttpServerError::UnterminatedTransaction);
+    }
+
+    result
+}
+
+async fn query<T: GStore + GStoreMut + Send + 'static>(
+    State(server): State<HttpServer<T>>,
+    Query(params): Query<QueryParams>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response> {
+    server.authorize(&headers)?;
+
+    let header = |name| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+    };
+    let sql = if header(header::CONTENT_TYPE).starts_with("application/json") {
+        serde_json::from_slice::<QueryRequest>(&body)
+            .map(|request| request.sql)
+            .map_err(|error| HttpServerError::InvalidBody(error.to_string()))?
+    } else {
+        String::from_utf8(body.to_vec())
+            .map_err(|error| HttpServerError::InvalidBody(error.to_string()))?
+    };
+    let ndjson = params.format.as_deref() == Some("ndjson") || header(header::ACCEPT) == NDJSON;
+
+    if !ndjson {
+        let payloads = server.execute(&sql).await?;
+
+        return Ok(Json(payload::convert(payloads)?).into_response());
+    }
+
+    let (mut sender, mut receiver) = mpsc::channel(NDJSON_BUFFER);
+    tokio::spawn(async move {
+        if let Err(error) = server.stream(&sql, &mut sender).await {
+            let _ = sender.send(Err(error)).await;
+        }
+    });
+
+    // an error before the first line still gets its status, later ones abort the body
+    let first = match receiver.next().await {
+        Some(Err(error)) => return Err(error),
+        first => first,
+    };
+    let body = Body::from_stream(stream::iter(first).chain(receiver));
+
+    Ok(([(header::CONTENT_TYPE, NDJSON)], body).into_response())
+}
+
+async fn tables<T: GStore + GStoreMut + Send + 'static>(
+    State(server): State<HttpServer<T>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<String>>> {
+    server.authorize(&headers)?;
+
+    let tables = server
+        .execute("SHOW TABLES;")
+        .await?
+        .into_iter()
+        .flat_map(|payload| match payload {
+            Payload::ShowVariable(PayloadVariable::Tables(tables)) => tables,
+            _ => Vec::new(),
+        })
+        .collect();
+
+    Ok(Json(tables))
+}
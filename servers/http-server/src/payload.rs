@@ -0,0 +1,150 @@
+use {
+    gluesql_core::{
+        data::Row,
+        prelude::{Payload, PayloadVariable, Result, Value},
+    },
+    serde_json::{Map, Value as Json, json},
+};
+
+pub fn convert(payloads: Vec<Payload>) -> Result<Json> {
+    payloads
+        .into_iter()
+        .map(convert_payload)
+        .collect::<Result<Vec<_>>>()
+        .map(Json::Array)
+}
+
+fn convert_rows<I, R>(rows: I) -> Result<Json>
+where
+    I: IntoIterator<Item = R>,
+    R: IntoIterator<Item = (String, Value)>,
+{
+    rows.into_iter()
+        .map(convert_row)
+        .collect::<Result<Vec<_>>>()
+        .map(Json::Array)
+}
+
+fn convert_row<R: IntoIterator<Item = (String, Value)>>(row: R) -> Result<Json> {
+    row.into_iter()
+        .map(|(key, value)| Json::try_from(value).map(|value| (key, value)))
+        .collect::<Result<Map<_, _>>>()
+        .map(Json::Object)
+}
+
+fn convert_payload(payload: Payload) -> Result<Json> {
+    let json = match payload {
+        Payload::Create => json!({ "type": "CREATE TABLE" }),
+        Payload::DropTable(num) => json!({ "type": "DROP TABLE", "affected": num }),
+        Payload::DropView(num) => json!({ "type": "DROP VIEW", "affected": num }),
+        Payload::DropSequence(num) => json!({ "type": "DROP SEQUENCE", "affected": num }),
+        Payload::RefreshMaterializedView(num) => {
+            json!({ "type": "REFRESH MATERIALIZED VIEW", "affected": num })
+        }
+        Payload::Select { labels, rows } => {
+            let rows = rows
+                .into_iter()
+                .map(|values| labels.iter().cloned().zip(values).collect::<Vec<_>>());
+
+            json!({
+                "type": "SELECT",
+                "rows": convert_rows(rows)?,
+                "labels": labels,
+            })
+        }
+        Payload::SelectMap(rows) => json!({
+            "type": "SELECT",
+            "rows": convert_rows(rows)?,
+        }),
+        Payload::Returning { labels, rows } => {
+            let mut json = convert_payload(Payload::Select { labels, rows })?;
+            json["type"] = json!("RETURNING");
+
+            json
+        }
+        Payload::ShowColumns(columns) => {
+            let columns = columns
+                .into_iter()
+                .map(|(name, data_type)| {
+                    json!({
+                        "name": name,
+                        "type": data_type.to_string(),
+                    })
+                })
+                .collect();
+
+            json!({
+                "type": "SHOW COLUMNS",
+                "columns": Json::Array(columns),
+            })
+        }
+        Payload::Insert(num) => json!({
+            "type": "INSERT",
+            "affected": num
+        }),
+        Payload::Update(num) => json!({
+            "type": "UPDATE",
+            "affected": num
+        }),
+        Payload::Delete(num) => json!({
+            "type": "DELETE",
+            "affected": num
+        }),
+        Payload::AlterTable => json!({ "type": "ALTER TABLE" }),
+        Payload::CreateIndex => json!({ "type": "CREATE INDEX" }),
+        Payload::DropIndex => json!({ "type": "DROP INDEX" }),
+        Payload::CreateTrigger => json!({ "type": "CREATE TRIGGER" }),
+        Payload::DropTrigger => json!({ "type": "DROP TRIGGER" }),
+        Payload::StartTransaction => json!({ "type": "BEGIN" }),
+        Payload::Commit => json!({ "type": "COMMIT" }),
+        Payload::Rollback => json!({ "type": "ROLLBACK" }),
+        Payload::SetVariable => json!({ "type": "SET" }),
+        Payload::ShowVariable(PayloadVariable::Version(version)) => json!({
+            "type": "SHOW VERSION",
+            "version": version
+        }),
+        Payload::ShowVariable(PayloadVariable::Tables(table_names)) => json!({
+            "type": "SHOW TABLES",
+            "tables": table_names
+        }),
+        Payload::DropFunction => json!({ "type": "DROP FUNCTION" }),
+        Payload::DropUser => json!({ "type": "DROP USER" }),
+        Payload::Grant => json!({ "type": "GRANT" }),
+        Payload::Revoke => json!({ "type": "REVOKE" }),
+        Payload::ShowVariable(PayloadVariable::Functions(function_names)) => json!({
+            "type": "SHOW FUNCTIONS",
+            "functions": function_names
+        }),
+    };
+
+    Ok(json)
+}
+
+/// Serializes payloads as newline delimited JSON, one line per SELECT row and
+/// one line per other payload, converting rows lazily as the body is streamed.
+pub fn ndjson(payloads: Vec<Payload>) -> impl Iterator<Item = Result<String>> {
+    payloads.into_iter().flat_map(|payload| {
+        let lines: Box<dyn Iterator<Item = Result<Json>> + Send> = match payload {
+            Payload::Select { labels, rows } | Payload::Returning { labels, rows } => {
+                Box::new(rows.into_iter().map(move |values| {
+                    let row = labels.iter().cloned().zip(values).collect::<Vec<_>>();
+                    convert_row(row)
+                }))
+            }
+            Payload::SelectMap(rows) => Box::new(rows.into_iter().map(convert_row)),
+            payload => Box::new(std::iter::once(convert_payload(payload))),
+        };
+
+        lines.map(|line| line.map(|line| format!("{line}\n")))
+    })
+}
+
+/// Serializes a row of a query as a line of newline delimited JSON.
+pub fn ndjson_row(row: Row) -> Result<String> {
+    let line = match row {
+        Row::Vec { columns, values } => convert_row(columns.iter().cloned().zip(values)),
+        Row::Map(values) => convert_row(values),
+    }?;
+
+    Ok(format!("{line}\n"))
+}
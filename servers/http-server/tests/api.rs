@@ -0,0 +1,205 @@
+use {
+    axum::{
+        Router,
+        body::Body,
+        http::{Request, StatusCode, header},
+    },
+    gluesql_http_server::HttpServer,
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_sled_storage::SledStorage,
+    http_body_util::BodyExt,
+    serde_json::{Value as Json, json},
+    sled::Config,
+    tower::ServiceExt,
+};
+
+async fn send(router: &Router, request: Request<Body>) -> (StatusCode, String) {
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn query(sql: &str) -> Request<Body> {
+    Request::post("/query")
+        .body(Body::from(sql.to_owned()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn query_json() {
+    let router = HttpServer::new(MemoryStorage::default()).router();
+
+    let (status, _) = send(
+        &router,
+        query("CREATE TABLE Item (id INTEGER, name TEXT); INSERT INTO Item VALUES (1, 'apple');"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let request = Request::post("/query")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(r#"{ "sql": "SELECT * FROM Item" }"#))
+        .unwrap();
+    let (status, body) = send(&router, request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        serde_json::from_str::<Json>(&body).unwrap(),
+        json!([{
+            "type": "SELECT",
+            "labels": ["id", "name"],
+            "rows": [{ "id": 1, "name": "apple" }],
+        }])
+    );
+
+    let (status, body) = send(&router, query("SELECT * FROM Missing")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.contains("error"));
+}
+
+#[tokio::test]
+async fn query_ndjson() {
+    let router = HttpServer::new(MemoryStorage::default()).router();
+    send(
+        &router,
+        query("CREATE TABLE Item (id INTEGER); INSERT INTO Item VALUES (1), (2), (3);"),
+    )
+    .await;
+
+    let request = Request::post("/query?format=ndjson")
+        .body(Body::from("SELECT id FROM Item; DELETE FROM Item;"))
+        .unwrap();
+    let (status, body) = send(&router, request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body.lines()
+            .map(|line| serde_json::from_str::<Json>(line).unwrap())
+            .collect::<Vec<_>>(),
+        vec![
+            json!({ "id": 1 }),
+            json!({ "id": 2 }),
+            json!({ "id": 3 }),
+            json!({ "type": "DELETE", "affected": 3 }),
+        ]
+    );
+
+    let request = Request::post("/query?format=ndjson")
+        .body(Body::from("SELECT id FROM Missing"))
+        .unwrap();
+    let (status, body) = send(&router, request).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body.contains("error"));
+}
+
+#[tokio::test]
+async fn query_ndjson_streams_rows() {
+    let router = HttpServer::new(MemoryStorage::default())
+        .with_max_connections(1)
+        .router();
+    let values = (1..=1000)
+        .map(|id| format!("({id})"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    send(
+        &router,
+        query(&format!(
+            "CREATE TABLE Item (id INTEGER); INSERT INTO Item VALUES {values};"
+        )),
+    )
+    .await;
+
+    let request = Request::post("/query?format=ndjson")
+        .body(Body::from("SELECT id FROM Item"))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut body = response.into_body();
+    let first = body.frame().await.unwrap().unwrap().into_data().unwrap();
+    assert_eq!(first, "{\"id\":1}\n");
+
+    // the query still holds the only session, as its rows are not all read yet
+    let request = Request::get("/tables").body(Body::empty()).unwrap();
+    assert_eq!(
+        send(&router, request).await.0,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    let rest = body.collect().await.unwrap().to_bytes();
+    assert_eq!(
+        String::from_utf8(rest.to_vec()).unwrap().lines().count(),
+        999
+    );
+
+    let request = Request::get("/tables").body(Body::empty()).unwrap();
+    assert_eq!(send(&router, request).await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn tables() {
+    let router = HttpServer::new(MemoryStorage::default()).router();
+    send(
+        &router,
+        query("CREATE TABLE Foo (id INTEGER); CREATE TABLE Bar (id INTEGER);"),
+    )
+    .await;
+
+    let request = Request::get("/tables").body(Body::empty()).unwrap();
+    let (status, body) = send(&router, request).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, r#"["Bar","Foo"]"#);
+}
+
+#[tokio::test]
+async fn api_key() {
+    let router = HttpServer::new(MemoryStorage::default())
+        .with_api_key("secret")
+        .router();
+
+    let request = Request::get("/tables").body(Body::empty()).unwrap();
+    assert_eq!(send(&router, request).await.0, StatusCode::UNAUTHORIZED);
+
+    let request = Request::get("/tables")
+        .header(header::AUTHORIZATION, "Bearer wrong")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&router, request).await.0, StatusCode::UNAUTHORIZED);
+
+    let request = Request::get("/tables")
+        .header(header::AUTHORIZATION, "Bearer secret")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&router, request).await.0, StatusCode::OK);
+
+    let request = Request::get("/tables")
+        .header("X-API-Key", "secret")
+        .body(Body::empty())
+        .unwrap();
+    assert_eq!(send(&router, request).await.0, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn unterminated_transaction() {
+    let config = Config::default()
+        .path("tmp/http_server_unterminated_transaction")
+        .temporary(true);
+    let router = HttpServer::new(SledStorage::try_from(config).unwrap()).router();
+    send(&router, query("CREATE TABLE Item (id INTEGER);")).await;
+
+    let (status, _) = send(&router, query("BEGIN; INSERT INTO Item VALUES (1);")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = send(
+        &router,
+        query("BEGIN; INSERT INTO Item VALUES (2); COMMIT;"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, body) = send(&router, query("SELECT id FROM Item")).await;
+    assert_eq!(
+        serde_json::from_str::<Json>(&body).unwrap()[0]["rows"],
+        json!([{ "id": 2 }])
+    );
+}
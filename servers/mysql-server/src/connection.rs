@@ -0,0 +1,601 @@
+use {
+    crate::{
+        error::{MySqlServerError, Result},
+        packet::{Body, BufMutExt, PacketStream},
+        payload::Output,
+        types::{ColumnType, parameter_literal},
+    },
+    gluesql_core::{
+        prelude::{Value, parse},
+        sqlparser::ast::Statement as SqlStatement,
+        store::{GStore, GStoreMut},
+    },
+    gluesql_server_session::{Session, SessionManager},
+    std::{
+        collections::HashMap,
+        hash::{BuildHasher, RandomState},
+    },
+    tokio::io::{AsyncRead, AsyncWrite},
+};
+
+pub const SERVER_VERSION: &str = "8.0.0-GlueSQL";
+const AUTH_PLUGIN: &str = "mysql_native_password";
+
+const CLIENT_LONG_PASSWORD: u32 = 0x0000_0001;
+const CLIENT_FOUND_ROWS: u32 = 0x0000_0002;
+const CLIENT_LONG_FLAG: u32 = 0x0000_0004;
+const CLIENT_CONNECT_WITH_DB: u32 = 0x0000_0008;
+const CLIENT_PROTOCOL_41: u32 = 0x0000_0200;
+const CLIENT_TRANSACTIONS: u32 = 0x0000_2000;
+const CLIENT_SECURE_CONNECTION: u32 = 0x0000_8000;
+const CLIENT_MULTI_STATEMENTS: u32 = 0x0001_0000;
+const CLIENT_MULTI_RESULTS: u32 = 0x0002_0000;
+const CLIENT_PS_MULTI_RESULTS: u32 = 0x0004_0000;
+const CLIENT_PLUGIN_AUTH: u32 = 0x0008_0000;
+const CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA: u32 = 0x0020_0000;
+
+const CAPABILITIES: u32 = CLIENT_LONG_PASSWORD
+    | CLIENT_FOUND_ROWS
+    | CLIENT_LONG_FLAG
+    | CLIENT_CONNECT_WITH_DB
+    | CLIENT_PROTOCOL_41
+    | CLIENT_TRANSACTIONS
+    | CLIENT_SECURE_CONNECTION
+    | CLIENT_MULTI_STATEMENTS
+    | CLIENT_MULTI_RESULTS
+    | CLIENT_PS_MULTI_RESULTS
+    | CLIENT_PLUGIN_AUTH
+    | CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA;
+
+const SERVER_STATUS_IN_TRANS: u16 = 0x0001;
+const SERVER_STATUS_AUTOCOMMIT: u16 = 0x0002;
+const SERVER_MORE_RESULTS_EXISTS: u16 = 0x0008;
+
+const COM_QUIT: u8 = 0x01;
+const COM_INIT_DB: u8 = 0x02;
+const COM_QUERY: u8 = 0x03;
+const COM_PING: u8 = 0x0E;
+const COM_STMT_PREPARE: u8 = 0x16;
+const COM_STMT_EXECUTE: u8 = 0x17;
+const COM_STMT_CLOSE: u8 = 0x19;
+const COM_STMT_RESET: u8 = 0x1A;
+
+const UTF8MB4_GENERAL_CI: u8 = 45;
+
+struct Prepared {
+    sql: String,
+    param_count: usize,
+    /// Parameter types of the last execution; clients only resend them when they change.
+    param_types: Vec<(u8, bool)>,
+}
+
+pub struct Connection<S, T: GStore + GStoreMut> {
+    stream: PacketStream<S>,
+    sessions: SessionManager<T>,
+    /// Opened before the handshake, which announces its id as the connection id.
+    session: Option<Session<T>>,
+    statements: HashMap<u32, Prepared>,
+    next_statement_id: u32,
+}
+
+impl<S, T> Connection<S, T>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+    T: GStore + GStoreMut + Send + 'static,
+{
+    pub fn new(stream: S, sessions: SessionManager<T>) -> Self {
+        Self {
+            stream: PacketStream::new(stream),
+            sessions,
+            session: None,
+            statements: HashMap::new(),
+            next_statement_id: 1,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<()> {
+        let result = self.serve().await;
+
+        if let Some(session) = self.session.take() {
+            session.close().await?;
+        }
+
+        result
+    }
+
+    async fn serve(&mut self) -> Result<()> {
+        let session = match self.sessions.open() {
+            Ok(session) => session,
+            Err(error) => {
+                self.error(&error.into()).await?;
+                return self.stream.flush().await;
+            }
+        };
+        let connection_id = session.id();
+        self.session = Some(session);
+
+        if !self.handshake(connection_id).await? {
+            return Ok(());
+        }
+
+        while let Some(packet) = self.stream.read().await? {
+            let mut body = Body(&packet);
+            let command = body.u8()?;
+
+            let result = match command {
+                COM_QUIT => return Ok(()),
+                COM_INIT_DB | COM_PING | COM_STMT_RESET => self.ok(0, 0).await,
+                COM_QUERY => {
+                    let sql = String::from_utf8_lossy(body.rest()).into_owned();
+                    self.query(&sql).await
+                }
+                COM_STMT_PREPARE => {
+                    let sql = String::from_utf8_lossy(body.rest()).into_owned();
+                    self.prepare(sql).await
+                }
+                COM_STMT_EXECUTE => self.execute_statement(&mut body).await,
+                COM_STMT_CLOSE => {
+                    self.statements.remove(&body.u32()?);
+                    continue;
+                }
+                command => Err(MySqlServerError::UnknownCommand(command)),
+            };
+
+            if let Err(error) = result {
+                if is_fatal(&error) {
+                    return Err(error);
+                }
+
+                self.error(&error).await?;
+            }
+
+            self.stream.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn handshake(&mut self, connection_id: u32) -> Result<bool> {
+        let scramble = scramble();
+
+        let mut payload = vec![10];
+        payload.put_cstr(SERVER_VERSION);
+        payload.put_u32(connection_id);
+        payload.extend_from_slice(&scramble[..8]);
+        payload.push(0);
+        payload.put_u16(CAPABILITIES as u16);
+        payload.push(UTF8MB4_GENERAL_CI);
+        payload.put_u16(SERVER_STATUS_AUTOCOMMIT);
+        payload.put_u16((CAPABILITIES >> 16) as u16);
+        payload.push(scramble.len() as u8 + 1);
+        payload.extend_from_slice(&[0; 10]);
+        payload.extend_from_slice(&scramble[8..]);
+        payload.push(0);
+        payload.put_cstr(AUTH_PLUGIN);
+        self.stream.write(&payload).await?;
+        self.stream.flush().await?;
+
+        let Some(packet) = self.stream.read().await? else {
+            return Ok(false);
+        };
+        let mut body = Body(&packet);
+        let capabilities = body.u32()?;
+        if capabilities & CLIENT_PROTOCOL_41 == 0 {
+            return Err(MySqlServerError::UnsupportedClient);
+        }
+
+        body.bytes(4 + 1 + 23)?;
+        let user = body.cstr()?;
+        let mut auth = if capabilities & CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA != 0 {
+            body.lenenc_bytes()?.to_vec()
+        } else {
+            let len = body.u8()? as usize;
+            body.bytes(len)?.to_vec()
+        };
+        if capabilities & CLIENT_CONNECT_WITH_DB != 0 && !body.is_empty() {
+            body.cstr()?;
+        }
+        let plugin = match capabilities & CLIENT_PLUGIN_AUTH != 0 && !body.is_empty() {
+            true => body.cstr()?,
+            false => AUTH_PLUGIN.to_owned(),
+        };
+
+        if self.sessions.requires_login() {
+            if plugin != AUTH_PLUGIN {
+                let mut payload = vec![0xFE];
+                payload.put_cstr(AUTH_PLUGIN);
+                payload.extend_from_slice(&scramble);
+                payload.push(0);
+                self.stream.write(&payload).await?;
+                self.stream.flush().await?;
+
+                auth = match self.stream.read().await? {
+                    Some(packet) => packet,
+                    None => return Ok(false),
+                };
+            }
+
+            if !self
+                .sessions
+                .verify_native_password(&user, &scramble, &auth)
+                .await
+            {
+                self.error(&MySqlServerError::AccessDenied(user)).await?;
+                self.stream.flush().await?;
+                return Ok(false);
+            }
+        }
+
+        if let Some(session) = self
+            .session
+            .as_mut()
+            .filter(|_| self.sessions.user_catalog())
+        {
+            session.set_user(Some(&user));
+        }
+
+        self.ok(0, 0).await?;
+        self.stream.flush().await?;
+
+        Ok(true)
+    }
+
+    async fn query(&mut self, sql: &str) -> Result<()> {
+        if let Some((labels, row)) = system_variables(sql) {
+            let types = row.iter().map(ColumnType::of).collect::<Vec<_>>();
+            return self.text_rows(&labels, &types, vec![row], 0).await;
+        }
+
+        let statements = parse(sql)?;
+        if statements.is_empty() {
+            return self.ok(0, 0).await;
+        }
+
+        let last = statements.len() - 1;
+        for (i, statement) in statements.iter().enumerate() {
+            let more = if i < last {
+                SERVER_MORE_RESULTS_EXISTS
+            } else {
+                0
+            };
+
+            match self.execute(statement).await? {
+                Output::Rows { labels, rows } => {
+                    let types = infer_types(labels.len(), &rows);
+                    self.text_rows(&labels, &types, rows, more).await?;
+                }
+                Output::Affected(n) => self.ok(n, more).await?,
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn prepare(&mut self, sql: String) -> Result<()> {
+        let param_count = placeholders(&sql).len();
+        if parse(bind(&sql, |_| Ok("NULL".to_owned()))?)?.len() > 1 {
+            return Err(MySqlServerError::MultipleStatements);
+        }
+
+        let id = self.next_statement_id;
+        self.next_statement_id = self.next_statement_id.wrapping_add(1);
+        self.statements.insert(
+            id,
+            Prepared {
+                sql,
+                param_count,
+                param_types: Vec::new(),
+            },
+        );
+
+        let mut payload = vec![0];
+        payload.put_u32(id);
+        payload.put_u16(0);
+        payload.put_u16(param_count as u16);
+        payload.push(0);
+        payload.put_u16(0);
+        self.stream.write(&payload).await?;
+
+        if param_count > 0 {
+            for _ in 0..param_count {
+                let definition = ColumnType::VarString.definition("?");
+                self.stream.write(&definition).await?;
+            }
+            self.eof(0).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn execute_statement(&mut self, body: &mut Body<'_>) -> Result<()> {
+        let id = body.u32()?;
+        body.bytes(1 + 4)?;
+
+        let prepared = self
+            .statements
+            .get_mut(&id)
+            .ok_or(MySqlServerError::StatementNotFound(id))?;
+
+        let mut literals = Vec::with_capacity(prepared.param_count);
+        if prepared.param_count > 0 {
+            let null_bitmap = body.bytes(prepared.param_count.div_ceil(8))?.to_vec();
+            if body.u8()? == 1 {
+                prepared.param_types = (0..prepared.param_count)
+                    .map(|_| Ok((body.u8()?, body.u8()? & 0x80 != 0)))
+                    .collect::<Result<_>>()?;
+            }
+
+            for i in 0..prepared.param_count {
+                let (kind, unsigned) = prepared.param_types.get(i).copied().unwrap_or((6, false));
+                let literal = match null_bitmap[i / 8] & (1 << (i % 8)) != 0 {
+                    true => "NULL".to_owned(),
+                    false => parameter_literal(kind, unsigned, body)?,
+                };
+                literals.push(literal);
+            }
+        }
+
+        let sql = bind(&prepared.sql, |i| Ok(literals[i].clone()))?;
+        let Some(statement) = parse(sql)?.pop() else {
+            return self.ok(0, 0).await;
+        };
+
+        match self.execute(&statement).await? {
+            Output::Rows { labels, rows } => {
+                let types = infer_types(labels.len(), &rows);
+                self.binary_rows(&labels, &types, rows).await
+            }
+            Output::Affected(n) => self.ok(n, 0).await,
+        }
+    }
+
+    async fn execute(&mut self, statement: &SqlStatement) -> Result<Output> {
+        let session = self
+            .session
+            .as_mut()
+            .ok_or_else(|| MySqlServerError::Protocol("query before handshake".to_owned()))?;
+
+        Ok(match session.execute(statement).await? {
+            Some(payload) => payload.into(),
+            None => Output::Affected(0),
+        })
+    }
+
+    async fn columns(&mut self, labels: &[String], types: &[ColumnType]) -> Result<()> {
+        let mut payload = Vec::new();
+        payload.put_lenenc_int(labels.len() as u64);
+        self.stream.write(&payload).await?;
+
+        for (label, column_type) in labels.iter().zip(types) {
+            self.stream.write(&column_type.definition(label)).await?;
+        }
+
+        self.eof(0).await
+    }
+
+    async fn text_rows(
+        &mut self,
+        labels: &[String],
+        types: &[ColumnType],
+        rows: Vec<Vec<Value>>,
+        more: u16,
+    ) -> Result<()> {
+        self.columns(labels, types).await?;
+
+        for row in rows {
+            let mut payload = Vec::new();
+            for (value, column_type) in row.iter().zip(types) {
+                column_type.encode_text(value, &mut payload)?;
+            }
+            self.stream.write(&payload).await?;
+        }
+
+        self.eof(more).await
+    }
+
+    async fn binary_rows(
+        &mut self,
+        labels: &[String],
+        types: &[ColumnType],
+        rows: Vec<Vec<Value>>,
+    ) -> Result<()> {
+        self.columns(labels, types).await?;
+
+        for row in rows {
+            let mut null_bitmap = vec![0; (row.len() + 7 + 2) / 8];
+            let mut values = Vec::new();
+            for (i, (value, column_type)) in row.iter().zip(types).enumerate() {
+                match value.is_null() {
+                    true => null_bitmap[(i + 2) / 8] |= 1 << ((i + 2) % 8),
+                    false => column_type.encode_binary(value, &mut values)?,
+                }
+            }
+
+            let mut payload = vec![0];
+            payload.extend(null_bitmap);
+            payload.extend(values);
+            self.stream.write(&payload).await?;
+        }
+
+        self.eof(0).await
+    }
+
+    fn status(&self) -> u16 {
+        match self.session.as_ref().is_some_and(Session::in_transaction) {
+            true => SERVER_STATUS_IN_TRANS,
+            false => SERVER_STATUS_AUTOCOMMIT,
+        }
+    }
+
+    async fn ok(&mut self, affected: u64, more: u16) -> Result<()> {
+        let mut payload = vec![0];
+        payload.put_lenenc_int(affected);
+        payload.put_lenenc_int(0);
+        payload.put_u16(self.status() | more);
+        payload.put_u16(0);
+
+        self.stream.write(&payload).await
+    }
+
+    async fn eof(&mut self, more: u16) -> Result<()> {
+        let mut payload = vec![0xFE];
+        payload.put_u16(0);
+        payload.put_u16(self.status() | more);
+
+        self.stream.write(&payload).await
+    }
+
+    async fn error(&mut self, error: &MySqlServerError) -> Result<()> {
+        let (code, state) = error.code();
+        let mut payload = vec![0xFF];
+        payload.put_u16(code);
+        payload.push(b'#');
+        payload.extend_from_slice(state.as_bytes());
+        payload.extend_from_slice(error.to_string().as_bytes());
+
+        self.stream.write(&payload).await
+    }
+}
+
+fn is_fatal(error: &MySqlServerError) -> bool {
+    matches!(
+        error,
+        MySqlServerError::Io(_) | MySqlServerError::Protocol(_)
+    )
+}
+
+fn infer_types(len: usize, rows: &[Vec<Value>]) -> Vec<ColumnType> {
+    (0..len)
+        .map(|i| ColumnType::infer(rows.iter().filter_map(|row| row.get(i))))
+        .collect()
+}
+
+/// 20 bytes of printable, non-NUL auth plugin data.
+fn scramble() -> [u8; 20] {
+    let state = RandomState::new();
+    let mut scramble = [0; 20];
+    for (i, byte) in scramble.iter_mut().enumerate() {
+        *byte = (state.hash_one(i) % 94) as u8 + 33;
+    }
+
+    scramble
+}
+
+/// Answers `SELECT @@variable, ...` queries clients send right after connecting.
+fn system_variables(sql: &str) -> Option<(Vec<String>, Vec<Value>)> {
+    let sql = sql.trim().trim_end_matches(';').trim_end();
+    let lower = sql.to_ascii_lowercase();
+    if !lower.starts_with("select ") {
+        return None;
+    }
+
+    let end = lower.rfind(" limit ").unwrap_or(lower.len());
+    let mut labels = Vec::new();
+    let mut row = Vec::new();
+    for item in sql.get(7..end)?.split(',') {
+        let item = item.trim();
+        let lower = item.to_ascii_lowercase();
+        let (expr, label) = match lower.find(" as ") {
+            Some(i) => (&lower[..i], item[i + 4..].trim()),
+            None => (lower.as_str(), item),
+        };
+        let name = expr.trim_end().strip_prefix("@@")?;
+        let name = ["session.", "global.", "local."]
+            .iter()
+            .find_map(|scope| name.strip_prefix(scope))
+            .unwrap_or(name);
+
+        let value = match name {
+            "version" => Value::Str(SERVER_VERSION.to_owned()),
+            "version_comment" => Value::Str("GlueSQL".to_owned()),
+            "max_allowed_packet" => Value::I64(64 * 1024 * 1024),
+            "auto_increment_increment" | "autocommit" => Value::I64(1),
+            "lower_case_table_names" => Value::I64(0),
+            "wait_timeout" | "interactive_timeout" => Value::I64(28800),
+            "net_write_timeout" => Value::I64(60),
+            "character_set_client"
+            | "character_set_connection"
+            | "character_set_results"
+            | "character_set_server" => Value::Str("utf8mb4".to_owned()),
+            "collation_connection" | "collation_server" => {
+                Value::Str("utf8mb4_general_ci".to_owned())
+            }
+            "time_zone" | "system_time_zone" => Value::Str("UTC".to_owned()),
+            "transaction_isolation" | "tx_isolation" => Value::Str("REPEATABLE-READ".to_owned()),
+            "sql_mode" => Value::Str(String::new()),
+            _ => Value::Null,
+        };
+
+        labels.push(label.trim_matches('`').to_owned());
+        row.push(value);
+    }
+
+    Some((labels, row))
+}
+
+/// Finds `?` placeholders outside of quoted strings and identifiers.
+fn placeholders(sql: &str) -> Vec<usize> {
+    let mut found = Vec::new();
+    let mut quote = None;
+
+    for (i, byte) in sql.bytes().enumerate() {
+        match (quote, byte) {
+            (None, b @ (b'\'' | b'"' | b'`')) => quote = Some(b),
+            (Some(q), b) if b == q => quote = None,
+            (None, b'?') => found.push(i),
+            _ => {}
+        }
+    }
+
+    found
+}
+
+fn bind(sql: &str, mut literal: impl FnMut(usize) -> Result<String>) -> Result<String> {
+    let mut bound = String::with_capacity(sql.len());
+    let mut last = 0;
+
+    for (n, position) in placeholders(sql).into_iter().enumerate() {
+        bound.push_str(&sql[last..position]);
+        bound.push_str(&literal(n)?);
+        last = position + 1;
+    }
+    bound.push_str(&sql[last..]);
+
+    Ok(bound)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{bind, system_variables},
+        crate::types::quote,
+        gluesql_core::prelude::Value,
+    };
+
+    #[test]
+    fn bind_placeholders() {
+        let sql = "SELECT * FROM Foo WHERE a = ? AND b = '?' AND c = ?";
+        let bound = bind(sql, |i| Ok(quote(&format!("{i}'")))).unwrap();
+
+        assert_eq!(
+            bound,
+            "SELECT * FROM Foo WHERE a = '0''' AND b = '?' AND c = '1'''"
+        );
+    }
+
+    #[test]
+    fn select_system_variables() {
+        let (labels, row) =
+            system_variables("select @@version_comment limit 1").expect("system variables");
+        assert_eq!(labels, vec!["@@version_comment"]);
+        assert_eq!(row, vec![Value::Str("GlueSQL".to_owned())]);
+
+        let (labels, row) =
+            system_variables("SELECT @@session.auto_increment_increment AS Inc, @@Foo")
+                .expect("system variables");
+        assert_eq!(labels, vec!["Inc", "@@Foo"]);
+        assert_eq!(row, vec![Value::I64(1), Value::Null]);
+
+        assert!(system_variables("SELECT 1").is_none());
+    }
+}
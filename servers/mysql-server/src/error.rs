@@ -0,0 +1,58 @@
+use {
+    gluesql_core::error::Error as GlueError, gluesql_server_session::SessionError,
+    thiserror::Error as ThisError,
+};
+
+pub type Result<T, E = MySqlServerError> = std::result::Result<T, E>;
+
+#[derive(ThisError, Debug)]
+pub enum MySqlServerError {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Glue(#[from] GlueError),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+
+    #[error("protocol violation: {0}")]
+    Protocol(String),
+
+    #[error("client does not support protocol 4.1")]
+    UnsupportedClient,
+
+    #[error("unknown command: {0:#04x}")]
+    UnknownCommand(u8),
+
+    #[error("unknown prepared statement handler ({0})")]
+    StatementNotFound(u32),
+
+    #[error("cannot insert multiple commands into a prepared statement")]
+    MultipleStatements,
+
+    #[error("unsupported parameter type: {0:#04x}")]
+    UnsupportedParameter(u8),
+
+    #[error("invalid parameter value: {0}")]
+    InvalidParameter(String),
+
+    #[error("Access denied for user '{0}'")]
+    AccessDenied(String),
+}
+
+impl MySqlServerError {
+    /// MySQL error code and SQLSTATE reported to the client in an `ERR` packet.
+    pub fn code(&self) -> (u16, &'static str) {
+        match self {
+            Self::Glue(GlueError::Parser(_)) | Self::MultipleStatements => (1064, "42000"),
+            Self::Glue(_) => (1105, "HY000"),
+            Self::Session(SessionError::TooManyConnections(_)) => (1040, "08004"),
+            Self::Io(_) | Self::Protocol(_) | Self::UnsupportedClient => (1043, "08S01"),
+            Self::UnknownCommand(_) => (1047, "08S01"),
+            Self::StatementNotFound(_) => (1243, "HY000"),
+            Self::UnsupportedParameter(_) | Self::InvalidParameter(_) => (1210, "HY000"),
+            Self::AccessDenied(_) => (1045, "28000"),
+        }
+    }
+}
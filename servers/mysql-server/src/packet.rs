@@ -0,0 +1,196 @@
+use {
+    crate::error::{MySqlServerError, Result},
+    tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufStream},
+};
+
+const MAX_PAYLOAD_LEN: usize = 0xFF_FFFF;
+
+/// Frames payloads into packets, tracking the sequence id of the current command.
+pub struct PacketStream<S> {
+    stream: BufStream<S>,
+    seq: u8,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> PacketStream<S> {
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: BufStream::new(stream),
+            seq: 0,
+        }
+    }
+
+    /// Reads the next payload, returning `None` once the client has gone away.
+    pub async fn read(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut payload = Vec::new();
+
+        loop {
+            let mut header = [0; 4];
+            match self.stream.read_exact(&mut header).await {
+                Ok(_) => {}
+                Err(error)
+                    if error.kind() == std::io::ErrorKind::UnexpectedEof && payload.is_empty() =>
+                {
+                    return Ok(None);
+                }
+                Err(error) => return Err(error.into()),
+            }
+
+            let len = u32::from_le_bytes([header[0], header[1], header[2], 0]) as usize;
+            self.seq = header[3].wrapping_add(1);
+
+            let start = payload.len();
+            payload.resize(start + len, 0);
+            self.stream.read_exact(&mut payload[start..]).await?;
+
+            if len < MAX_PAYLOAD_LEN {
+                return Ok(Some(payload));
+            }
+        }
+    }
+
+    pub async fn write(&mut self, payload: &[u8]) -> Result<()> {
+        let mut chunks = payload.chunks(MAX_PAYLOAD_LEN).peekable();
+        if chunks.peek().is_none() {
+            return self.write_chunk(&[]).await;
+        }
+
+        while let Some(chunk) = chunks.next() {
+            self.write_chunk(chunk).await?;
+            if chunks.peek().is_none() && chunk.len() == MAX_PAYLOAD_LEN {
+                self.write_chunk(&[]).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn write_chunk(&mut self, chunk: &[u8]) -> Result<()> {
+        let len = (chunk.len() as u32).to_le_bytes();
+        self.stream
+            .write_all(&[len[0], len[1], len[2], self.seq])
+            .await?;
+        self.stream.write_all(chunk).await?;
+        self.seq = self.seq.wrapping_add(1);
+
+        Ok(())
+    }
+
+    pub async fn flush(&mut self) -> Result<()> {
+        self.stream.flush().await.map_err(Into::into)
+    }
+}
+
+pub trait BufMutExt {
+    fn put_u16(&mut self, value: u16);
+    fn put_u32(&mut self, value: u32);
+    fn put_lenenc_int(&mut self, value: u64);
+    fn put_lenenc_bytes(&mut self, value: &[u8]);
+    fn put_cstr(&mut self, value: &str);
+}
+
+impl BufMutExt for Vec<u8> {
+    fn put_u16(&mut self, value: u16) {
+        self.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u32(&mut self, value: u32) {
+        self.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_lenenc_int(&mut self, value: u64) {
+        match value {
+            0..=0xFA => self.push(value as u8),
+            0xFB..=0xFFFF => {
+                self.push(0xFC);
+                self.extend_from_slice(&(value as u16).to_le_bytes());
+            }
+            0x1_0000..=0xFF_FFFF => {
+                self.push(0xFD);
+                self.extend_from_slice(&(value as u32).to_le_bytes()[..3]);
+            }
+            _ => {
+                self.push(0xFE);
+                self.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+
+    fn put_lenenc_bytes(&mut self, value: &[u8]) {
+        self.put_lenenc_int(value.len() as u64);
+        self.extend_from_slice(value);
+    }
+
+    fn put_cstr(&mut self, value: &str) {
+        self.extend_from_slice(value.as_bytes());
+        self.push(0);
+    }
+}
+
+pub struct Body<'a>(pub &'a [u8]);
+
+impl<'a> Body<'a> {
+    pub fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(MySqlServerError::Protocol("packet too short".to_owned()));
+        }
+
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn rest(&mut self) -> &'a [u8] {
+        std::mem::take(&mut self.0)
+    }
+
+    pub fn u8(&mut self) -> Result<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    pub fn u16(&mut self) -> Result<u16> {
+        self.bytes(2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn u32(&mut self) -> Result<u32> {
+        self.bytes(4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub fn lenenc_int(&mut self) -> Result<u64> {
+        let first = self.u8()?;
+        let mut int = |len: usize| -> Result<u64> {
+            let mut value = [0; 8];
+            value[..len].copy_from_slice(self.bytes(len)?);
+            Ok(u64::from_le_bytes(value))
+        };
+
+        match first {
+            0xFC => int(2),
+            0xFD => int(3),
+            0xFE => int(8),
+            value => Ok(value as u64),
+        }
+    }
+
+    pub fn lenenc_bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.lenenc_int()? as usize;
+        self.bytes(len)
+    }
+
+    pub fn cstr(&mut self) -> Result<String> {
+        let end = self
+            .0
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(self.0.len());
+        let value = String::from_utf8_lossy(&self.0[..end]).into_owned();
+        self.0 = self.0.get(end + 1..).unwrap_or_default();
+
+        Ok(value)
+    }
+}
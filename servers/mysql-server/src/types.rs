@@ -0,0 +1,368 @@
+use {
+    crate::{
+        error::{MySqlServerError, Result},
+        packet::{Body, BufMutExt},
+    },
+    chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike},
+    gluesql_core::{
+        error::Error as GlueError,
+        prelude::{DataType, Value},
+    },
+    rust_decimal::Decimal,
+};
+
+const TYPE_DECIMAL: u8 = 0x00;
+const TYPE_TINY: u8 = 0x01;
+const TYPE_SHORT: u8 = 0x02;
+const TYPE_LONG: u8 = 0x03;
+const TYPE_FLOAT: u8 = 0x04;
+const TYPE_DOUBLE: u8 = 0x05;
+const TYPE_NULL: u8 = 0x06;
+const TYPE_TIMESTAMP: u8 = 0x07;
+const TYPE_LONGLONG: u8 = 0x08;
+const TYPE_INT24: u8 = 0x09;
+const TYPE_DATE: u8 = 0x0A;
+const TYPE_TIME: u8 = 0x0B;
+const TYPE_DATETIME: u8 = 0x0C;
+const TYPE_YEAR: u8 = 0x0D;
+const TYPE_VARCHAR: u8 = 0x0F;
+const TYPE_JSON: u8 = 0xF5;
+const TYPE_NEWDECIMAL: u8 = 0xF6;
+const TYPE_TINY_BLOB: u8 = 0xF9;
+const TYPE_MEDIUM_BLOB: u8 = 0xFA;
+const TYPE_LONG_BLOB: u8 = 0xFB;
+const TYPE_BLOB: u8 = 0xFC;
+const TYPE_VAR_STRING: u8 = 0xFD;
+const TYPE_STRING: u8 = 0xFE;
+
+const UNSIGNED_FLAG: u16 = 0x20;
+const BINARY_FLAG: u16 = 0x80;
+
+const CHARSET_UTF8MB4: u16 = 45;
+const CHARSET_BINARY: u16 = 63;
+
+/// MySQL column type a GlueSQL value is reported as in a column definition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnType {
+    Tiny { unsigned: bool },
+    Short { unsigned: bool },
+    Long { unsigned: bool },
+    LongLong { unsigned: bool },
+    Float,
+    Double,
+    Decimal,
+    VarString,
+    Blob,
+    Date,
+    Time,
+    DateTime,
+    Json,
+}
+
+impl ColumnType {
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Bool(_) | Value::I8(_) => Self::Tiny { unsigned: false },
+            Value::U8(_) => Self::Tiny { unsigned: true },
+            Value::I16(_) => Self::Short { unsigned: false },
+            Value::U16(_) => Self::Short { unsigned: true },
+            Value::I32(_) => Self::Long { unsigned: false },
+            Value::U32(_) => Self::Long { unsigned: true },
+            Value::I64(_) => Self::LongLong { unsigned: false },
+            Value::U64(_) => Self::LongLong { unsigned: true },
+            Value::I128(_) | Value::U128(_) | Value::Decimal(_) => Self::Decimal,
+            Value::F32(_) => Self::Float,
+            Value::F64(_) => Self::Double,
+            Value::Bytea(_) => Self::Blob,
+            Value::Date(_) => Self::Date,
+            Value::Time(_) => Self::Time,
+            Value::Timestamp(_) => Self::DateTime,
+            Value::Map(_) | Value::List(_) => Self::Json,
+            Value::Str(_)
+            | Value::Inet(_)
+            | Value::Interval(_)
+            | Value::Uuid(_)
+            | Value::Point(_)
+            | Value::Null => Self::VarString,
+        }
+    }
+
+    /// Picks the column type from the first non-null value, falling back to `VAR_STRING`.
+    pub fn infer<'a>(values: impl IntoIterator<Item = &'a Value>) -> Self {
+        values
+            .into_iter()
+            .find(|value| !value.is_null())
+            .map(Self::of)
+            .unwrap_or(Self::VarString)
+    }
+
+    fn code(&self) -> u8 {
+        match self {
+            Self::Tiny { .. } => TYPE_TINY,
+            Self::Short { .. } => TYPE_SHORT,
+            Self::Long { .. } => TYPE_LONG,
+            Self::LongLong { .. } => TYPE_LONGLONG,
+            Self::Float => TYPE_FLOAT,
+            Self::Double => TYPE_DOUBLE,
+            Self::Decimal => TYPE_NEWDECIMAL,
+            Self::VarString => TYPE_VAR_STRING,
+            Self::Blob => TYPE_BLOB,
+            Self::Date => TYPE_DATE,
+            Self::Time => TYPE_TIME,
+            Self::DateTime => TYPE_DATETIME,
+            Self::Json => TYPE_JSON,
+        }
+    }
+
+    fn data_type(&self) -> Option<DataType> {
+        match self {
+            Self::Tiny { unsigned: false } => Some(DataType::Int8),
+            Self::Tiny { unsigned: true } => Some(DataType::Uint8),
+            Self::Short { unsigned: false } => Some(DataType::Int16),
+            Self::Short { unsigned: true } => Some(DataType::Uint16),
+            Self::Long { unsigned: false } => Some(DataType::Int32),
+            Self::Long { unsigned: true } => Some(DataType::Uint32),
+            Self::LongLong { unsigned: false } => Some(DataType::Int),
+            Self::LongLong { unsigned: true } => Some(DataType::Uint64),
+            Self::Float => Some(DataType::Float32),
+            Self::Double => Some(DataType::Float),
+            Self::Date => Some(DataType::Date),
+            Self::Time => Some(DataType::Time),
+            Self::DateTime => Some(DataType::Timestamp),
+            Self::Decimal | Self::VarString | Self::Blob | Self::Json => None,
+        }
+    }
+
+    /// Writes a `ColumnDefinition41` packet payload.
+    pub fn definition(&self, name: &str) -> Vec<u8> {
+        let (charset, length, flags, decimals) = match self {
+            Self::Tiny { unsigned } => (CHARSET_BINARY, 4, unsigned_flag(*unsigned), 0),
+            Self::Short { unsigned } => (CHARSET_BINARY, 6, unsigned_flag(*unsigned), 0),
+            Self::Long { unsigned } => (CHARSET_BINARY, 11, unsigned_flag(*unsigned), 0),
+            Self::LongLong { unsigned } => (CHARSET_BINARY, 20, unsigned_flag(*unsigned), 0),
+            Self::Float => (CHARSET_BINARY, 12, 0, 31),
+            Self::Double => (CHARSET_BINARY, 22, 0, 31),
+            Self::Decimal => (CHARSET_BINARY, 65, 0, 31),
+            Self::Date => (CHARSET_BINARY, 10, BINARY_FLAG, 0),
+            Self::Time => (CHARSET_BINARY, 17, BINARY_FLAG, 6),
+            Self::DateTime => (CHARSET_BINARY, 26, BINARY_FLAG, 6),
+            Self::Blob => (CHARSET_BINARY, u32::MAX, BINARY_FLAG, 0),
+            Self::VarString | Self::Json => (CHARSET_UTF8MB4, u32::MAX, 0, 0),
+        };
+
+        let mut payload = Vec::new();
+        for part in ["def", "", "", "", name, name] {
+            payload.put_lenenc_bytes(part.as_bytes());
+        }
+        payload.push(0x0C);
+        payload.put_u16(charset);
+        payload.put_u32(length);
+        payload.push(self.code());
+        payload.put_u16(flags);
+        payload.push(decimals);
+        payload.put_u16(0);
+
+        payload
+    }
+
+    fn cast<'a>(&self, value: &'a Value, casted: &'a mut Option<Value>) -> Result<&'a Value> {
+        match self.data_type() {
+            Some(data_type) if ColumnType::of(value) != *self => {
+                Ok(casted.insert(value.cast(&data_type)?))
+            }
+            _ => Ok(value),
+        }
+    }
+
+    /// Appends a value of a text protocol row.
+    pub fn encode_text(&self, value: &Value, out: &mut Vec<u8>) -> Result<()> {
+        if value.is_null() {
+            out.push(0xFB);
+            return Ok(());
+        }
+
+        let mut casted = None;
+        match self.cast(value, &mut casted)? {
+            Value::Bool(value) => out.put_lenenc_bytes(if *value { b"1" } else { b"0" }),
+            Value::Bytea(bytes) => out.put_lenenc_bytes(bytes),
+            value => out.put_lenenc_bytes(String::from(value).as_bytes()),
+        }
+
+        Ok(())
+    }
+
+    /// Appends a non-null value of a binary protocol row.
+    pub fn encode_binary(&self, value: &Value, out: &mut Vec<u8>) -> Result<()> {
+        let mut casted = None;
+        let value = self.cast(value, &mut casted)?;
+
+        match self {
+            Self::Tiny { unsigned: false } => out.push(convert::<i8>(value)? as u8),
+            Self::Tiny { unsigned: true } => out.push(convert::<u8>(value)?),
+            Self::Short { unsigned: false } => out.extend(convert::<i16>(value)?.to_le_bytes()),
+            Self::Short { unsigned: true } => out.extend(convert::<u16>(value)?.to_le_bytes()),
+            Self::Long { unsigned: false } => out.extend(convert::<i32>(value)?.to_le_bytes()),
+            Self::Long { unsigned: true } => out.extend(convert::<u32>(value)?.to_le_bytes()),
+            Self::LongLong { unsigned: false } => out.extend(convert::<i64>(value)?.to_le_bytes()),
+            Self::LongLong { unsigned: true } => out.extend(convert::<u64>(value)?.to_le_bytes()),
+            Self::Float => out.extend(convert::<f32>(value)?.to_le_bytes()),
+            Self::Double => out.extend(convert::<f64>(value)?.to_le_bytes()),
+            Self::Date => {
+                let date = convert::<NaiveDate>(value)?;
+                out.push(4);
+                out.put_u16(date.year() as u16);
+                out.extend([date.month() as u8, date.day() as u8]);
+            }
+            Self::DateTime => {
+                let datetime = convert::<NaiveDateTime>(value)?;
+                out.push(11);
+                out.put_u16(datetime.year() as u16);
+                out.extend([
+                    datetime.month() as u8,
+                    datetime.day() as u8,
+                    datetime.hour() as u8,
+                    datetime.minute() as u8,
+                    datetime.second() as u8,
+                ]);
+                out.put_u32(datetime.and_utc().timestamp_subsec_micros());
+            }
+            Self::Time => {
+                let time = convert::<NaiveTime>(value)?;
+                out.extend([12, 0]);
+                out.put_u32(0);
+                out.extend([time.hour() as u8, time.minute() as u8, time.second() as u8]);
+                out.put_u32(time.nanosecond() / 1000);
+            }
+            Self::Blob => match value {
+                Value::Bytea(bytes) => out.put_lenenc_bytes(bytes),
+                value => out.put_lenenc_bytes(String::from(value).as_bytes()),
+            },
+            Self::Decimal | Self::VarString | Self::Json => {
+                out.put_lenenc_bytes(String::from(value).as_bytes())
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn unsigned_flag(unsigned: bool) -> u16 {
+    if unsigned { UNSIGNED_FLAG } else { 0 }
+}
+
+fn convert<'a, V>(value: &'a Value) -> Result<V>
+where
+    V: TryFrom<&'a Value>,
+    GlueError: From<V::Error>,
+{
+    V::try_from(value).map_err(|error| MySqlServerError::Glue(error.into()))
+}
+
+/// Reads a binary protocol parameter of `COM_STMT_EXECUTE` as a SQL literal.
+pub fn parameter_literal(kind: u8, unsigned: bool, body: &mut Body<'_>) -> Result<String> {
+    macro_rules! int {
+        ($signed: ty, $unsigned: ty) => {{
+            let bytes = body
+                .bytes(size_of::<$signed>())?
+                .try_into()
+                .unwrap_or_default();
+            if unsigned {
+                <$unsigned>::from_le_bytes(bytes).to_string()
+            } else {
+                <$signed>::from_le_bytes(bytes).to_string()
+            }
+        }};
+    }
+
+    Ok(match kind {
+        TYPE_NULL => "NULL".to_owned(),
+        TYPE_TINY => int!(i8, u8),
+        TYPE_SHORT | TYPE_YEAR => int!(i16, u16),
+        TYPE_LONG | TYPE_INT24 => int!(i32, u32),
+        TYPE_LONGLONG => int!(i64, u64),
+        TYPE_FLOAT => f32::from_le_bytes(body.bytes(4)?.try_into().unwrap_or_default()).to_string(),
+        TYPE_DOUBLE => {
+            f64::from_le_bytes(body.bytes(8)?.try_into().unwrap_or_default()).to_string()
+        }
+        TYPE_DECIMAL | TYPE_NEWDECIMAL => {
+            let value = String::from_utf8_lossy(body.lenenc_bytes()?);
+
+            value
+                .parse::<Decimal>()
+                .map_err(|_| MySqlServerError::InvalidParameter(value.clone().into_owned()))?
+                .to_string()
+        }
+        TYPE_TINY_BLOB | TYPE_MEDIUM_BLOB | TYPE_LONG_BLOB | TYPE_BLOB => {
+            format!("X'{}'", hex::encode(body.lenenc_bytes()?))
+        }
+        TYPE_VARCHAR | TYPE_VAR_STRING | TYPE_STRING | TYPE_JSON => {
+            match std::str::from_utf8(body.lenenc_bytes()?) {
+                Ok(value) => quote(value),
+                Err(error) => return Err(MySqlServerError::Protocol(error.to_string())),
+            }
+        }
+        TYPE_DATE | TYPE_DATETIME | TYPE_TIMESTAMP => {
+            let len = body.u8()? as usize;
+            let mut part = Body(body.bytes(len)?);
+            let (year, month, day) = match len {
+                0 => (0, 0, 0),
+                _ => (part.u16()?, part.u8()?, part.u8()?),
+            };
+            let date = format!("{year:04}-{month:02}-{day:02}");
+
+            if kind == TYPE_DATE {
+                quote(&date)
+            } else {
+                let (hour, minute, second) = match len {
+                    7 | 11 => (part.u8()?, part.u8()?, part.u8()?),
+                    _ => (0, 0, 0),
+                };
+                let micros = if len == 11 { part.u32()? } else { 0 };
+
+                quote(&format!(
+                    "{date} {hour:02}:{minute:02}:{second:02}.{micros:06}"
+                ))
+            }
+        }
+        TYPE_TIME => {
+            let len = body.u8()? as usize;
+            let mut part = Body(body.bytes(len)?);
+            let (negative, days, hour, minute, second) = match len {
+                0 => (0, 0, 0, 0, 0),
+                _ => (part.u8()?, part.u32()?, part.u8()?, part.u8()?, part.u8()?),
+            };
+            let micros = if len == 12 { part.u32()? } else { 0 };
+            let sign = if negative == 1 { "-" } else { "" };
+            let hours = days * 24 + hour as u32;
+
+            quote(&format!(
+                "{sign}{hours:02}:{minute:02}:{second:02}.{micros:06}"
+            ))
+        }
+        kind => return Err(MySqlServerError::UnsupportedParameter(kind)),
+    })
+}
+
+pub fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::{TYPE_NEWDECIMAL, parameter_literal},
+        crate::packet::Body,
+    };
+
+    #[test]
+    fn decimal_parameter_literal() {
+        let literal = |value: &str| {
+            let packet = [&[value.len() as u8], value.as_bytes()].concat();
+
+            parameter_literal(TYPE_NEWDECIMAL, false, &mut Body(&packet)).ok()
+        };
+
+        assert_eq!(literal("-12.50"), Some("-12.50".to_owned()));
+        assert_eq!(literal("1); DROP TABLE t; --"), None);
+    }
+}
@@ -0,0 +1,198 @@
+use {
+    gluesql_core::store::{GStore, GStoreMut},
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_mysql_server::MySqlServer,
+    gluesql_sled_storage::SledStorage,
+    mysql_async::{Conn, Opts, OptsBuilder, Row, Value, prelude::Queryable},
+    sled::Config,
+    tokio::net::TcpListener,
+};
+
+async fn listen<T>(server: MySqlServer<T>) -> u16
+where
+    T: GStore + GStoreMut + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { server.serve(listener).await });
+
+    port
+}
+
+fn opts(port: u16) -> OptsBuilder {
+    OptsBuilder::default()
+        .ip_or_hostname("127.0.0.1")
+        .tcp_port(port)
+        .user(Some("glue"))
+        .prefer_socket(false)
+}
+
+#[tokio::test]
+async fn text_query() {
+    let port = listen(MySqlServer::new(MemoryStorage::default())).await;
+    let mut conn = Conn::new(opts(port)).await.unwrap();
+
+    conn.query_drop(
+        "CREATE TABLE Item (id INTEGER, name TEXT, price DECIMAL);
+         INSERT INTO Item VALUES (1, 'apple', 1.5), (2, 'pear', NULL);",
+    )
+    .await
+    .unwrap();
+
+    let rows: Vec<(i64, String, Option<String>)> = conn
+        .query("SELECT id, name, price FROM Item ORDER BY id")
+        .await
+        .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            (1, "apple".to_owned(), Some("1.5".to_owned())),
+            (2, "pear".to_owned(), None),
+        ]
+    );
+
+    conn.query_drop("UPDATE Item SET name = 'fig' WHERE id = 2")
+        .await
+        .unwrap();
+    assert_eq!(conn.affected_rows(), 1);
+
+    let version: Option<String> = conn
+        .query_first("SELECT @@version_comment LIMIT 1")
+        .await
+        .unwrap();
+    assert_eq!(version.as_deref(), Some("GlueSQL"));
+
+    let error = conn.query_drop("SELECT * FROM Missing").await.unwrap_err();
+    assert!(matches!(error, mysql_async::Error::Server(_)));
+
+    conn.disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn prepared_statement() {
+    let port = listen(MySqlServer::new(MemoryStorage::default())).await;
+    let mut conn = Conn::new(opts(port)).await.unwrap();
+
+    conn.query_drop("CREATE TABLE Item (id INTEGER, name TEXT, ok BOOLEAN, at TIMESTAMP)")
+        .await
+        .unwrap();
+    conn.exec_drop(
+        "INSERT INTO Item VALUES (?, ?, TRUE, ?)",
+        (1_i64, "apple", "2024-01-02 03:04:05"),
+    )
+    .await
+    .unwrap();
+    conn.exec_drop(
+        "INSERT INTO Item VALUES (?, ?, FALSE, ?)",
+        (2_i64, "it's", None::<String>),
+    )
+    .await
+    .unwrap();
+
+    let rows: Vec<Row> = conn
+        .exec(
+            "SELECT id, name, ok, at FROM Item WHERE id >= ? ORDER BY id",
+            (1,),
+        )
+        .await
+        .unwrap();
+    let rows = rows
+        .into_iter()
+        .map(|mut row| {
+            (
+                row.take::<i64, _>(0).unwrap(),
+                row.take::<String, _>(1).unwrap(),
+                row.take::<bool, _>(2).unwrap(),
+                row.take::<Value, _>(3).unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        rows,
+        vec![
+            (
+                1,
+                "apple".to_owned(),
+                true,
+                Value::Date(2024, 1, 2, 3, 4, 5, 0)
+            ),
+            (2, "it's".to_owned(), false, Value::NULL),
+        ]
+    );
+
+    conn.disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn transaction() {
+    let config = Config::default()
+        .path("tmp/mysql_server_transaction")
+        .temporary(true);
+    let storage = SledStorage::try_from(config).unwrap();
+    let port = listen(MySqlServer::new(storage)).await;
+    let mut conn = Conn::new(opts(port)).await.unwrap();
+
+    conn.query_drop("CREATE TABLE Item (id INTEGER)")
+        .await
+        .unwrap();
+    conn.query_drop("BEGIN; INSERT INTO Item VALUES (1); COMMIT;")
+        .await
+        .unwrap();
+    conn.query_drop("BEGIN; INSERT INTO Item VALUES (2); ROLLBACK;")
+        .await
+        .unwrap();
+
+    let count: Option<i64> = conn.query_first("SELECT COUNT(*) FROM Item").await.unwrap();
+    assert_eq!(count, Some(1));
+
+    conn.disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn password() {
+    let server = MySqlServer::new(MemoryStorage::default()).with_password("glue", "sticky");
+    let port = listen(server).await;
+
+    let error = Conn::new(Opts::from(opts(port).pass(Some("wrong"))))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        mysql_async::Error::Server(ref error) if error.code == 1045
+    ));
+
+    let mut conn = Conn::new(opts(port).pass(Some("sticky"))).await.unwrap();
+    conn.query_drop("SELECT 1").await.unwrap();
+    conn.disconnect().await.unwrap();
+}
+
+#[tokio::test]
+async fn max_connections() {
+    let server = MySqlServer::new(MemoryStorage::default()).with_max_connections(1);
+    let port = listen(server).await;
+
+    let mut conn = Conn::new(opts(port)).await.unwrap();
+    conn.query_drop("USE shop").await.unwrap();
+
+    let error = Conn::new(opts(port)).await.unwrap_err();
+    assert!(matches!(
+        error,
+        mysql_async::Error::Server(ref error) if error.code == 1040
+    ));
+
+    conn.disconnect().await.unwrap();
+
+    // the slot is released once the server has processed `COM_QUIT`
+    let mut conn = loop {
+        match Conn::new(opts(port)).await {
+            Ok(conn) => break conn,
+            Err(mysql_async::Error::Server(error)) if error.code == 1040 => {
+                tokio::task::yield_now().await;
+            }
+            Err(error) => panic!("unexpected error: {error}"),
+        }
+    };
+    conn.query_drop("SELECT 1").await.unwrap();
+    conn.disconnect().await.unwrap();
+}
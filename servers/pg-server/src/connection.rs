@@ -0,0 +1,635 @@
+use {
+    crate::{
+        error::{PgServerError, Result},
+        message::{BackendMessage, Field, FrontendMessage, Startup, read_message, read_startup},
+        payload::Output,
+        types::{PgType, parameter_literal, parameter_type},
+    },
+    gluesql_core::{
+        parse_sql::parse_expr,
+        prelude::{Value, parse},
+        sqlparser::ast::{SetExpr as SqlSetExpr, Statement as SqlStatement},
+        store::{GStore, GStoreMut},
+    },
+    gluesql_server_session::{Session, SessionManager},
+    std::collections::{HashMap, VecDeque},
+    tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufStream},
+};
+
+const SERVER_VERSION: &str = "14.0 (GlueSQL)";
+
+struct Prepared {
+    sql: String,
+    param_types: Vec<u32>,
+    /// Statement with every parameter bound to NULL, sampled by `Describe`.
+    statement: Option<SqlStatement>,
+    columns: Option<Vec<(String, PgType)>>,
+}
+
+struct Portal {
+    statement: Option<SqlStatement>,
+    result_formats: Vec<i16>,
+    columns: Option<Vec<PgType>>,
+    state: PortalState,
+}
+
+enum PortalState {
+    Pending,
+    Empty,
+    Rows {
+        labels: Vec<String>,
+        types: Vec<PgType>,
+        rows: VecDeque<Vec<Value>>,
+        tag: &'static str,
+    },
+    Done(String),
+}
+
+pub struct Connection<S, T: GStore + GStoreMut> {
+    stream: BufStream<S>,
+    out: Vec<u8>,
+    sessions: SessionManager<T>,
+    /// Opened once the client has logged in.
+    session: Option<Session<T>>,
+    statements: HashMap<String, Prepared>,
+    portals: HashMap<String, Portal>,
+    /// Set after an error in the extended protocol; messages are skipped until `Sync`.
+    skip_until_sync: bool,
+}
+
+impl<S, T> Connection<S, T>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send,
+    T: GStore + GStoreMut + Send + 'static,
+{
+    pub fn new(stream: S, sessions: SessionManager<T>) -> Self {
+        Self {
+            stream: BufStream::new(stream),
+            out: Vec::new(),
+            sessions,
+            session: None,
+            statements: HashMap::new(),
+            portals: HashMap::new(),
+            skip_until_sync: false,
+        }
+    }
+
+    pub async fn run(mut self) -> Result<()> {
+        let result = self.serve().await;
+
+        if let Some(session) = self.session.take() {
+            session.close().await?;
+        }
+
+        result
+    }
+
+    async fn serve(&mut self) -> Result<()> {
+        if !self.startup().await? {
+            return Ok(());
+        }
+
+        while let Some(message) = read_message(&mut self.stream).await? {
+            match message {
+                FrontendMessage::Terminate => break,
+                FrontendMessage::Query(sql) => self.simple_query(&sql).await?,
+                FrontendMessage::Sync => {
+                    self.skip_until_sync = false;
+                    self.ready_for_query().await?;
+                }
+                FrontendMessage::Flush => self.flush().await?,
+                _ if self.skip_until_sync => {}
+                message => {
+                    if let Err(error) = self.extended(message).await {
+                        if is_fatal(&error) {
+                            return Err(error);
+                        }
+
+                        self.error(&error);
+                        self.skip_until_sync = true;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn startup(&mut self) -> Result<bool> {
+        let params = loop {
+            match read_startup(&mut self.stream).await? {
+                Startup::SslRequest | Startup::GssEncRequest => {
+                    self.stream.write_all(b"N").await?;
+                    self.stream.flush().await?;
+                }
+                Startup::Cancel => return Ok(false),
+                Startup::Params(params) => break params,
+            }
+        };
+
+        let user = params.get("user").cloned().unwrap_or_default();
+        if self.sessions.requires_login() {
+            self.send(BackendMessage::AuthenticationCleartextPassword);
+            self.flush().await?;
+
+            let password = match read_message(&mut self.stream).await? {
+                Some(FrontendMessage::Password(password)) => password,
+                _ => return Ok(false),
+            };
+
+            if !self.sessions.verify(&user, &password).await {
+                self.error(&PgServerError::AuthenticationFailed(user));
+                self.flush().await?;
+                return Ok(false);
+            }
+        }
+
+        let mut session = match self.sessions.open() {
+            Ok(session) => session,
+            Err(error) => {
+                self.error(&error.into());
+                self.flush().await?;
+                return Ok(false);
+            }
+        };
+        if self.sessions.user_catalog() {
+            session.set_user(Some(&user));
+        }
+        let process_id = session.id() as i32;
+        self.session = Some(session);
+
+        self.send(BackendMessage::AuthenticationOk);
+        for (name, value) in [
+            ("server_version", SERVER_VERSION),
+            ("server_encoding", "UTF8"),
+            ("client_encoding", "UTF8"),
+            ("DateStyle", "ISO, MDY"),
+            ("TimeZone", "UTC"),
+            ("integer_datetimes", "on"),
+            ("standard_conforming_strings", "on"),
+        ] {
+            self.send(BackendMessage::ParameterStatus(name, value));
+        }
+        self.send(BackendMessage::BackendKeyData {
+            process_id,
+            secret_key: 0,
+        });
+        self.ready_for_query().await?;
+
+        Ok(true)
+    }
+
+    async fn simple_query(&mut self, sql: &str) -> Result<()> {
+        let statements = match parse(sql) {
+            Ok(statements) => statements,
+            Err(error) => {
+                self.error(&error.into());
+                return self.ready_for_query().await;
+            }
+        };
+
+        if statements.is_empty() {
+            self.send(BackendMessage::EmptyQueryResponse);
+        }
+
+        for statement in statements.iter() {
+            let result = match self.execute(statement).await {
+                Ok(Output::Rows { labels, rows, tag }) => {
+                    let types = infer_types(labels.len(), &rows);
+                    let count = rows.len();
+
+                    self.send_row_description(&labels, &types, &[]);
+                    self.send_data_rows(&types, &[], rows).map(|_| {
+                        self.send(BackendMessage::CommandComplete(&format!("{tag} {count}")));
+                    })
+                }
+                Ok(Output::Command(tag)) => {
+                    self.send(BackendMessage::CommandComplete(&tag));
+                    Ok(())
+                }
+                Err(error) => Err(error),
+            };
+
+            if let Err(error) = result {
+                if is_fatal(&error) {
+                    return Err(error);
+                }
+
+                self.error(&error);
+                break;
+            }
+        }
+
+        self.ready_for_query().await
+    }
+
+    async fn extended(&mut self, message: FrontendMessage) -> Result<()> {
+        match message {
+            FrontendMessage::Parse {
+                name,
+                query,
+                param_types,
+            } => {
+                let count = placeholders(&query).iter().map(|(_, _, n)| *n).max();
+                let param_types = (0..count.unwrap_or_default())
+                    .map(|i| parameter_type(param_types.get(i).copied().unwrap_or_default()))
+                    .collect();
+                let statement = single(&bind(&query, |_| Ok("NULL".to_owned()))?)?;
+
+                self.statements.insert(
+                    name,
+                    Prepared {
+                        sql: query,
+                        param_types,
+                        statement,
+                        columns: None,
+                    },
+                );
+                self.send(BackendMessage::ParseComplete);
+            }
+            FrontendMessage::Bind {
+                portal,
+                statement,
+                param_formats,
+                params,
+                result_formats,
+            } => {
+                let prepared = self
+                    .statements
+                    .get(&statement)
+                    .ok_or(PgServerError::StatementNotFound(statement))?;
+
+                if params.len() != prepared.param_types.len() {
+                    return Err(PgServerError::ParameterCount {
+                        expected: prepared.param_types.len(),
+                        actual: params.len(),
+                    });
+                }
+
+                let sql = bind(&prepared.sql, |i| {
+                    parameter_literal(
+                        prepared.param_types[i],
+                        format_code(&param_formats, i),
+                        params[i].as_deref(),
+                    )
+                })?;
+                let columns = prepared
+                    .columns
+                    .as_ref()
+                    .map(|columns| columns.iter().map(|(_, t)| *t).collect());
+
+                self.portals.insert(
+                    portal,
+                    Portal {
+                        statement: single(&sql)?,
+                        result_formats,
+                        columns,
+                        state: PortalState::Pending,
+                    },
+                );
+                self.send(BackendMessage::BindComplete);
+            }
+            FrontendMessage::Describe { kind: b'S', name } => {
+                let prepared = self
+                    .statements
+                    .get(&name)
+                    .ok_or_else(|| PgServerError::StatementNotFound(name.clone()))?;
+                let param_types = prepared.param_types.clone();
+                let statement = prepared
+                    .statement
+                    .clone()
+                    .filter(returns_rows)
+                    .map(sample)
+                    .transpose()?;
+
+                let columns = match statement {
+                    Some(statement) => match self.execute(&statement).await? {
+                        Output::Rows { labels, rows, .. } => {
+                            let types = infer_types(labels.len(), &rows);
+                            Some(labels.into_iter().zip(types).collect::<Vec<_>>())
+                        }
+                        Output::Command(_) => None,
+                    },
+                    None => None,
+                };
+
+                self.send(BackendMessage::ParameterDescription(&param_types));
+                match &columns {
+                    Some(columns) => {
+                        let (labels, types): (Vec<_>, Vec<_>) = columns.iter().cloned().unzip();
+                        self.send_row_description(&labels, &types, &[]);
+                    }
+                    None => self.send(BackendMessage::NoData),
+                }
+
+                if let Some(prepared) = self.statements.get_mut(&name) {
+                    prepared.columns = columns;
+                }
+            }
+            FrontendMessage::Describe { kind: b'P', name } => {
+                let mut portal = self
+                    .portals
+                    .remove(&name)
+                    .ok_or_else(|| PgServerError::PortalNotFound(name.clone()))?;
+
+                let result = async {
+                    if portal.statement.as_ref().is_some_and(returns_rows) {
+                        self.materialize(&mut portal).await?;
+                    }
+
+                    match &portal.state {
+                        PortalState::Rows { labels, types, .. } => {
+                            self.send_row_description(labels, types, &portal.result_formats);
+                        }
+                        _ => self.send(BackendMessage::NoData),
+                    }
+
+                    Ok::<_, PgServerError>(())
+                }
+                .await;
+
+                self.portals.insert(name, portal);
+                result?;
+            }
+            FrontendMessage::Execute {
+                portal: name,
+                max_rows,
+            } => {
+                let mut portal = self
+                    .portals
+                    .remove(&name)
+                    .ok_or_else(|| PgServerError::PortalNotFound(name.clone()))?;
+
+                let result = async {
+                    self.materialize(&mut portal).await?;
+
+                    match &mut portal.state {
+                        PortalState::Pending => {}
+                        PortalState::Empty => self.send(BackendMessage::EmptyQueryResponse),
+                        PortalState::Done(tag) => self.send(BackendMessage::CommandComplete(tag)),
+                        PortalState::Rows {
+                            types, rows, tag, ..
+                        } => {
+                            let count = match max_rows {
+                                n if n > 0 => (n as usize).min(rows.len()),
+                                _ => rows.len(),
+                            };
+                            let batch = rows.drain(..count).collect::<Vec<_>>();
+                            let tag = *tag;
+                            let types = types.clone();
+
+                            self.send_data_rows(&types, &portal.result_formats, batch)?;
+                            if matches!(&portal.state, PortalState::Rows { rows, .. } if !rows.is_empty()) {
+                                self.send(BackendMessage::PortalSuspended);
+                            } else {
+                                self.send(BackendMessage::CommandComplete(&format!("{tag} {count}")));
+                                portal.state = PortalState::Done(format!("{tag} 0"));
+                            }
+                        }
+                    }
+
+                    Ok::<_, PgServerError>(())
+                }
+                .await;
+
+                self.portals.insert(name, portal);
+                result?;
+            }
+            FrontendMessage::Describe { kind, .. } | FrontendMessage::Close { kind, .. }
+                if kind != b'S' && kind != b'P' =>
+            {
+                return Err(PgServerError::Protocol(format!(
+                    "invalid target type '{}'",
+                    kind as char
+                )));
+            }
+            FrontendMessage::Close { kind, name } => {
+                if kind == b'S' {
+                    self.statements.remove(&name);
+                } else {
+                    self.portals.remove(&name);
+                }
+                self.send(BackendMessage::CloseComplete);
+            }
+            _ => {
+                return Err(PgServerError::Protocol("unexpected message".to_owned()));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn materialize(&mut self, portal: &mut Portal) -> Result<()> {
+        if !matches!(portal.state, PortalState::Pending) {
+            return Ok(());
+        }
+
+        let Some(statement) = portal.statement.as_ref() else {
+            portal.state = PortalState::Empty;
+            return Ok(());
+        };
+
+        portal.state = match self.execute(statement).await? {
+            Output::Rows { labels, rows, tag } => {
+                let types = match portal.columns.take() {
+                    Some(types) if types.len() == labels.len() => types,
+                    _ => infer_types(labels.len(), &rows),
+                };
+
+                PortalState::Rows {
+                    labels,
+                    types,
+                    rows: rows.into(),
+                    tag,
+                }
+            }
+            Output::Command(tag) => PortalState::Done(tag),
+        };
+
+        Ok(())
+    }
+
+    async fn execute(&mut self, statement: &SqlStatement) -> Result<Output> {
+        let session = self
+            .session
+            .as_mut()
+            .ok_or_else(|| PgServerError::Protocol("query before startup".to_owned()))?;
+
+        Ok(match session.execute(statement).await? {
+            Some(payload) => payload.into(),
+            None => Output::Command("SET".to_owned()),
+        })
+    }
+
+    fn send_row_description(&mut self, labels: &[String], types: &[PgType], formats: &[i16]) {
+        let fields = labels
+            .iter()
+            .zip(types)
+            .enumerate()
+            .map(|(i, (label, pg_type))| field(label, *pg_type, format_code(formats, i)))
+            .collect::<Vec<_>>();
+        self.send(BackendMessage::RowDescription(&fields));
+    }
+
+    fn send_data_rows(
+        &mut self,
+        types: &[PgType],
+        formats: &[i16],
+        rows: Vec<Vec<Value>>,
+    ) -> Result<()> {
+        for row in rows {
+            let values = row
+                .iter()
+                .zip(types)
+                .enumerate()
+                .map(|(i, (value, pg_type))| pg_type.encode(value, format_code(formats, i)))
+                .collect::<Result<Vec<_>>>()?;
+            self.send(BackendMessage::DataRow(&values));
+        }
+
+        Ok(())
+    }
+
+    fn send(&mut self, message: BackendMessage<'_>) {
+        message.write(&mut self.out);
+    }
+
+    fn error(&mut self, error: &PgServerError) {
+        let message = error.to_string();
+        self.send(BackendMessage::ErrorResponse {
+            code: error.code(),
+            message: &message,
+        });
+    }
+
+    async fn ready_for_query(&mut self) -> Result<()> {
+        let status = if self.session.as_ref().is_some_and(Session::in_transaction) {
+            b'T'
+        } else {
+            b'I'
+        };
+        self.send(BackendMessage::ReadyForQuery(status));
+        self.flush().await
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.stream.write_all(&self.out).await?;
+        self.stream.flush().await?;
+        self.out.clear();
+
+        Ok(())
+    }
+}
+
+fn is_fatal(error: &PgServerError) -> bool {
+    matches!(
+        error,
+        PgServerError::Io(_) | PgServerError::Protocol(_) | PgServerError::UnsupportedProtocol(_)
+    )
+}
+
+fn returns_rows(statement: &SqlStatement) -> bool {
+    matches!(
+        statement,
+        SqlStatement::Query(_)
+            | SqlStatement::ShowTables { .. }
+            | SqlStatement::ShowFunctions { .. }
+            | SqlStatement::ShowVariable { .. }
+            | SqlStatement::ShowColumns { .. }
+    )
+}
+
+/// Rewrites a SELECT to fetch a single row without its WHERE clause, so that
+/// `Describe` can report column types even when parameters are still unbound.
+fn sample(mut statement: SqlStatement) -> Result<SqlStatement> {
+    if let SqlStatement::Query(query) = &mut statement {
+        if let SqlSetExpr::Select(select) = query.body.as_mut() {
+            select.selection = None;
+            select.having = None;
+        }
+
+        query.limit = Some(parse_expr("1")?);
+        query.offset = None;
+    }
+
+    Ok(statement)
+}
+
+fn infer_types(len: usize, rows: &[Vec<Value>]) -> Vec<PgType> {
+    (0..len)
+        .map(|i| PgType::infer(rows.iter().filter_map(|row| row.get(i))))
+        .collect()
+}
+
+fn field(name: &str, pg_type: PgType, format: i16) -> Field {
+    Field {
+        name: name.to_owned(),
+        type_oid: pg_type.oid(),
+        type_size: pg_type.size(),
+        format,
+    }
+}
+
+/// Format codes apply to all columns when a single code is given.
+fn format_code(formats: &[i16], i: usize) -> i16 {
+    match formats {
+        [] => 0,
+        [format] => *format,
+        formats => formats.get(i).copied().unwrap_or_default(),
+    }
+}
+
+fn single(sql: &str) -> Result<Option<SqlStatement>> {
+    let mut statements = parse(sql)?;
+    if statements.len() > 1 {
+        return Err(PgServerError::MultipleStatements);
+    }
+
+    Ok(statements.pop())
+}
+
+/// Finds `$n` placeholders outside of quoted strings and identifiers as `(start, end, n)`.
+fn placeholders(sql: &str) -> Vec<(usize, usize, usize)> {
+    let bytes = sql.as_bytes();
+    let mut found = Vec::new();
+    let mut quote = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match (quote, bytes[i]) {
+            (None, b @ (b'\'' | b'"')) => quote = Some(b),
+            (Some(q), b) if b == q => quote = None,
+            (None, b'$') => {
+                let end = bytes[i + 1..]
+                    .iter()
+                    .position(|b| !b.is_ascii_digit())
+                    .map_or(bytes.len(), |n| i + 1 + n);
+
+                if let Ok(n @ 1..) = sql[i + 1..end].parse::<usize>() {
+                    found.push((i, end, n));
+                    i = end;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    found
+}
+
+fn bind(sql: &str, mut literal: impl FnMut(usize) -> Result<String>) -> Result<String> {
+    let mut bound = String::with_capacity(sql.len());
+    let mut last = 0;
+
+    for (start, end, n) in placeholders(sql) {
+        bound.push_str(&sql[last..start]);
+        bound.push_str(&literal(n - 1)?);
+        last = end;
+    }
+    bound.push_str(&sql[last..]);
+
+    Ok(bound)
+}
@@ -0,0 +1,66 @@
+use {
+    gluesql_core::error::Error as GlueError, gluesql_server_session::SessionError,
+    thiserror::Error as ThisError,
+};
+
+pub type Result<T, E = PgServerError> = std::result::Result<T, E>;
+
+#[derive(ThisError, Debug)]
+pub enum PgServerError {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Glue(#[from] GlueError),
+
+    #[error(transparent)]
+    Session(#[from] SessionError),
+
+    #[error("protocol violation: {0}")]
+    Protocol(String),
+
+    #[error("unsupported protocol version: {0}")]
+    UnsupportedProtocol(i32),
+
+    #[error("prepared statement \"{0}\" does not exist")]
+    StatementNotFound(String),
+
+    #[error("portal \"{0}\" does not exist")]
+    PortalNotFound(String),
+
+    #[error("cannot insert multiple commands into a prepared statement")]
+    MultipleStatements,
+
+    #[error(
+        "bind message supplies {actual} parameters, but prepared statement requires {expected}"
+    )]
+    ParameterCount { expected: usize, actual: usize },
+
+    #[error("unsupported binary format for parameter type oid {0}")]
+    UnsupportedBinaryParameter(u32),
+
+    #[error("invalid parameter value: {0}")]
+    InvalidParameter(String),
+
+    #[error("password authentication failed for user \"{0}\"")]
+    AuthenticationFailed(String),
+}
+
+impl PgServerError {
+    /// SQLSTATE code reported to the client in an `ErrorResponse`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Glue(GlueError::Parser(_)) | Self::MultipleStatements => "42601",
+            Self::Glue(_) => "XX000",
+            Self::Session(SessionError::TooManyConnections(_)) => "53300",
+            Self::Io(_) => "08006",
+            Self::Protocol(_) | Self::UnsupportedProtocol(_) => "08P01",
+            Self::StatementNotFound(_) => "26000",
+            Self::PortalNotFound(_) => "34000",
+            Self::ParameterCount { .. } => "08P01",
+            Self::UnsupportedBinaryParameter(_) => "0A000",
+            Self::InvalidParameter(_) => "22023",
+            Self::AuthenticationFailed(_) => "28P01",
+        }
+    }
+}
@@ -0,0 +1,103 @@
+#![deny(clippy::str_to_string)]
+
+mod connection;
+mod error;
+mod message;
+mod payload;
+mod types;
+
+pub use error::{PgServerError, Result};
+
+pub use gluesql_server_session::{Authenticator, Credentials};
+
+use {
+    connection::Connection,
+    gluesql_core::store::{GStore, GStoreMut},
+    gluesql_server_session::SessionManager,
+    tokio::{
+        io::{AsyncRead, AsyncWrite},
+        net::{TcpListener, ToSocketAddrs},
+    },
+};
+
+/// Serves a GlueSQL storage over the PostgreSQL frontend/backend protocol (v3).
+///
+/// All connections share one [`Glue`](gluesql_core::prelude::Glue) instance. Statements
+/// from different connections are serialized, and a connection inside an explicit
+/// transaction holds the storage exclusively until it commits or rolls back.
+pub struct PgServer<T: GStore + GStoreMut> {
+    sessions: SessionManager<T>,
+}
+
+impl<T: GStore + GStoreMut> Clone for PgServer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sessions: self.sessions.clone(),
+        }
+    }
+}
+
+impl<T: GStore + GStoreMut + Send + 'static> PgServer<T> {
+    pub fn new(storage: T) -> Self {
+        Self {
+            sessions: SessionManager::new(storage),
+        }
+    }
+
+    /// Requires clients to log in with the given user and cleartext password.
+    ///
+    /// Connections are not encrypted, so only use this on trusted networks.
+    pub fn with_password(self, user: &str, password: &str) -> Self {
+        self.with_authenticator(Credentials {
+            user: user.to_owned(),
+            password: password.to_owned(),
+        })
+    }
+
+    /// Requires clients to log in with a cleartext password checked by `authenticator`.
+    pub fn with_authenticator(mut self, authenticator: impl Authenticator + 'static) -> Self {
+        self.sessions = self.sessions.with_authenticator(authenticator);
+        self
+    }
+
+    /// Also lets clients log in as the users created with `CREATE USER`, running their
+    /// statements with the privileges granted to them.
+    pub fn with_user_catalog(mut self) -> Self {
+        self.sessions = self.sessions.with_user_catalog();
+        self
+    }
+
+    /// Rejects new connections while `max_connections` clients are connected.
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.sessions = self.sessions.with_max_connections(max_connections);
+        self
+    }
+
+    pub async fn bind<A: ToSocketAddrs>(&self, addr: A) -> Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve(listener).await
+    }
+
+    /// Accepts connections until the listener fails, serving each on its own task.
+    pub async fn serve(&self, listener: TcpListener) -> Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            stream.set_nodelay(true)?;
+
+            let server = self.clone();
+            tokio::spawn(async move {
+                if let Err(error) = server.handle(stream).await {
+                    eprintln!("[gluesql-pg-server] connection closed: {error}");
+                }
+            });
+        }
+    }
+
+    /// Runs a single client session on an already established stream.
+    pub async fn handle<S>(&self, stream: S) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send,
+    {
+        Connection::new(stream, self.sessions.clone()).run().await
+    }
+}
@@ -0,0 +1,122 @@
+use {
+    clap::Parser,
+    gluesql_json_storage::JsonStorage,
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_pg_server::{PgServer, Result},
+    gluesql_redb_storage::RedbStorage,
+    gluesql_sled_storage::SledStorage,
+    std::path::PathBuf,
+};
+
+#[derive(Parser, Debug)]
+#[clap(name = "gluesql-pg-server", about, version)]
+struct Args {
+    /// Address to listen on
+    #[clap(short, long, default_value = "127.0.0.1:5432")]
+    listen: String,
+
+    /// Storage type to store data, default is memory
+    #[clap(short, long, value_parser)]
+    storage: Option<Storage>,
+
+    /// Storage path to load
+    #[clap(short, long, value_parser)]
+    path: Option<PathBuf>,
+
+    /// User name required to log in, requires --password
+    #[clap(short, long, requires = "password")]
+    user: Option<String>,
+
+    /// Cleartext password required to log in
+    #[clap(long, requires = "user")]
+    password: Option<String>,
+
+    /// Logs clients in as the users created with CREATE USER, checking their privileges.
+    /// --user and --password, if given, are created as a superuser
+    #[clap(long)]
+    user_catalog: bool,
+
+    /// Maximum number of clients connected at the same time
+    #[clap(long)]
+    max_connections: Option<usize>,
+
+    /// OpenTelemetry collector to export tracing spans to, e.g. http://localhost:4317
+    #[cfg(feature = "otlp")]
+    #[clap(long)]
+    otlp_endpoint: Option<String>,
+
+    /// Records exported queries with their literals replaced by `?`
+    #[cfg(feature = "otlp")]
+    #[clap(long, requires = "otlp-endpoint")]
+    redact_queries: bool,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone)]
+enum Storage {
+    Memory,
+    Sled,
+    Redb,
+    Json,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    #[cfg(feature = "otlp")]
+    let _telemetry = args.otlp_endpoint.as_deref().map(|endpoint| {
+        use gluesql_core::telemetry::{QueryText, set_query_text};
+
+        if args.redact_queries {
+            set_query_text(QueryText::Redacted);
+        }
+        gluesql_server_session::Telemetry::init("gluesql-pg-server", endpoint)
+            .expect("failed to start the OTLP exporter")
+    });
+    let path = args.path.clone();
+
+    match (args.storage.clone().unwrap_or(Storage::Memory), path) {
+        (Storage::Memory, None) => serve(MemoryStorage::default(), args).await,
+        (Storage::Memory, Some(_)) => {
+            panic!("failed to load memory-storage: it should be without path")
+        }
+        (Storage::Sled, Some(path)) => {
+            let storage = SledStorage::new(path).expect("failed to load sled-storage");
+            serve(storage, args).await
+        }
+        (Storage::Redb, Some(path)) => {
+            let storage = RedbStorage::new(path).expect("failed to load redb-storage");
+            serve(storage, args).await
+        }
+        (Storage::Json, Some(path)) => {
+            let storage = JsonStorage::new(path).expect("failed to load json-storage");
+            serve(storage, args).await
+        }
+        (storage, None) => panic!("storage path is required for {storage:?}"),
+    }
+}
+
+async fn serve<T>(mut storage: T, args: Args) -> Result<()>
+where
+    T: gluesql_core::store::GStore + gluesql_core::store::GStoreMut + Send + 'static,
+{
+    if let (true, Some(user), Some(password)) = (args.user_catalog, &args.user, &args.password) {
+        gluesql_server_session::create_superuser(&mut storage, user, password).await?;
+    }
+
+    let server = PgServer::new(storage);
+    let server = match (args.user, args.password) {
+        (Some(user), Some(password)) => server.with_password(&user, &password),
+        _ => server,
+    };
+    let server = match args.user_catalog {
+        true => server.with_user_catalog(),
+        false => server,
+    };
+    let server = match args.max_connections {
+        Some(max_connections) => server.with_max_connections(max_connections),
+        None => server,
+    };
+
+    println!("[gluesql-pg-server] listening on {}", args.listen);
+    server.bind(args.listen).await
+}
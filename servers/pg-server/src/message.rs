@@ -0,0 +1,344 @@
+use {
+    crate::error::{PgServerError, Result},
+    std::collections::HashMap,
+    tokio::io::{AsyncRead, AsyncReadExt},
+};
+
+const PROTOCOL_VERSION: i32 = 196_608;
+const SSL_REQUEST: i32 = 80_877_103;
+const GSSENC_REQUEST: i32 = 80_877_104;
+const CANCEL_REQUEST: i32 = 80_877_102;
+
+/// Upper bound of a single frontend message, guarding against bogus lengths.
+const MAX_MESSAGE_LEN: usize = 64 * 1024 * 1024;
+
+pub enum Startup {
+    SslRequest,
+    GssEncRequest,
+    Cancel,
+    Params(HashMap<String, String>),
+}
+
+pub enum FrontendMessage {
+    Query(String),
+    Parse {
+        name: String,
+        query: String,
+        param_types: Vec<u32>,
+    },
+    Bind {
+        portal: String,
+        statement: String,
+        param_formats: Vec<i16>,
+        params: Vec<Option<Vec<u8>>>,
+        result_formats: Vec<i16>,
+    },
+    Describe {
+        kind: u8,
+        name: String,
+    },
+    Execute {
+        portal: String,
+        max_rows: i32,
+    },
+    Close {
+        kind: u8,
+        name: String,
+    },
+    Password(String),
+    Sync,
+    Flush,
+    Terminate,
+}
+
+pub async fn read_startup<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Startup> {
+    let len = read_len(reader, 4).await?;
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body).await?;
+
+    let mut body = Body(&body);
+    match body.i32()? {
+        SSL_REQUEST => Ok(Startup::SslRequest),
+        GSSENC_REQUEST => Ok(Startup::GssEncRequest),
+        CANCEL_REQUEST => Ok(Startup::Cancel),
+        PROTOCOL_VERSION => {
+            let mut params = HashMap::new();
+            loop {
+                let key = body.cstr()?;
+                if key.is_empty() {
+                    break;
+                }
+
+                params.insert(key, body.cstr()?);
+            }
+
+            Ok(Startup::Params(params))
+        }
+        version => Err(PgServerError::UnsupportedProtocol(version)),
+    }
+}
+
+/// Reads the next message, returning `None` once the client has gone away.
+pub async fn read_message<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<FrontendMessage>> {
+    let tag = match reader.read_u8().await {
+        Ok(tag) => tag,
+        Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+
+    let len = read_len(reader, 4).await?;
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body).await?;
+    let mut body = Body(&body);
+
+    let message = match tag {
+        b'Q' => FrontendMessage::Query(body.cstr()?),
+        b'P' => {
+            let name = body.cstr()?;
+            let query = body.cstr()?;
+            let param_types = (0..body.i16()?)
+                .map(|_| body.i32().map(|oid| oid as u32))
+                .collect::<Result<_>>()?;
+
+            FrontendMessage::Parse {
+                name,
+                query,
+                param_types,
+            }
+        }
+        b'B' => {
+            let portal = body.cstr()?;
+            let statement = body.cstr()?;
+            let param_formats = (0..body.i16()?)
+                .map(|_| body.i16())
+                .collect::<Result<_>>()?;
+            let params = (0..body.i16()?)
+                .map(|_| match body.i32()? {
+                    -1 => Ok(None),
+                    len => body.bytes(len as usize).map(|bytes| Some(bytes.to_vec())),
+                })
+                .collect::<Result<_>>()?;
+            let result_formats = (0..body.i16()?)
+                .map(|_| body.i16())
+                .collect::<Result<_>>()?;
+
+            FrontendMessage::Bind {
+                portal,
+                statement,
+                param_formats,
+                params,
+                result_formats,
+            }
+        }
+        b'D' => FrontendMessage::Describe {
+            kind: body.u8()?,
+            name: body.cstr()?,
+        },
+        b'E' => FrontendMessage::Execute {
+            portal: body.cstr()?,
+            max_rows: body.i32()?,
+        },
+        b'C' => FrontendMessage::Close {
+            kind: body.u8()?,
+            name: body.cstr()?,
+        },
+        b'p' => FrontendMessage::Password(body.cstr()?),
+        b'S' => FrontendMessage::Sync,
+        b'H' => FrontendMessage::Flush,
+        b'X' => FrontendMessage::Terminate,
+        tag => {
+            return Err(PgServerError::Protocol(format!(
+                "unknown message type '{}'",
+                tag as char
+            )));
+        }
+    };
+
+    Ok(Some(message))
+}
+
+async fn read_len<R: AsyncRead + Unpin>(reader: &mut R, header: usize) -> Result<usize> {
+    let len = reader.read_i32().await? as usize;
+    if !(header..=MAX_MESSAGE_LEN).contains(&len) {
+        return Err(PgServerError::Protocol(format!(
+            "invalid message length {len}"
+        )));
+    }
+
+    Ok(len - header)
+}
+
+struct Body<'a>(&'a [u8]);
+
+impl<'a> Body<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(PgServerError::Protocol("message too short".to_owned()));
+        }
+
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        self.bytes(2)
+            .map(|bytes| i16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        self.bytes(4)
+            .map(|bytes| i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn cstr(&mut self) -> Result<String> {
+        let end = self
+            .0
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| PgServerError::Protocol("unterminated string".to_owned()))?;
+        let value = String::from_utf8(self.0[..end].to_vec())
+            .map_err(|_| PgServerError::Protocol("invalid utf-8 string".to_owned()))?;
+        self.0 = &self.0[end + 1..];
+
+        Ok(value)
+    }
+}
+
+pub enum BackendMessage<'a> {
+    AuthenticationOk,
+    AuthenticationCleartextPassword,
+    ParameterStatus(&'a str, &'a str),
+    BackendKeyData { process_id: i32, secret_key: i32 },
+    ReadyForQuery(u8),
+    RowDescription(&'a [Field]),
+    DataRow(&'a [Option<Vec<u8>>]),
+    CommandComplete(&'a str),
+    EmptyQueryResponse,
+    ErrorResponse { code: &'a str, message: &'a str },
+    ParseComplete,
+    BindComplete,
+    CloseComplete,
+    NoData,
+    ParameterDescription(&'a [u32]),
+    PortalSuspended,
+}
+
+pub struct Field {
+    pub name: String,
+    pub type_oid: u32,
+    pub type_size: i16,
+    pub format: i16,
+}
+
+impl BackendMessage<'_> {
+    pub fn write(&self, out: &mut Vec<u8>) {
+        let tag = match self {
+            Self::AuthenticationOk | Self::AuthenticationCleartextPassword => b'R',
+            Self::ParameterStatus(..) => b'S',
+            Self::BackendKeyData { .. } => b'K',
+            Self::ReadyForQuery(_) => b'Z',
+            Self::RowDescription(_) => b'T',
+            Self::DataRow(_) => b'D',
+            Self::CommandComplete(_) => b'C',
+            Self::EmptyQueryResponse => b'I',
+            Self::ErrorResponse { .. } => b'E',
+            Self::ParseComplete => b'1',
+            Self::BindComplete => b'2',
+            Self::CloseComplete => b'3',
+            Self::NoData => b'n',
+            Self::ParameterDescription(_) => b't',
+            Self::PortalSuspended => b's',
+        };
+
+        out.push(tag);
+        let start = out.len();
+        out.extend_from_slice(&[0; 4]);
+
+        match self {
+            Self::AuthenticationOk => put_i32(out, 0),
+            Self::AuthenticationCleartextPassword => put_i32(out, 3),
+            Self::ParameterStatus(name, value) => {
+                put_cstr(out, name);
+                put_cstr(out, value);
+            }
+            Self::BackendKeyData {
+                process_id,
+                secret_key,
+            } => {
+                put_i32(out, *process_id);
+                put_i32(out, *secret_key);
+            }
+            Self::ReadyForQuery(status) => out.push(*status),
+            Self::RowDescription(fields) => {
+                put_i16(out, fields.len() as i16);
+                for field in fields.iter() {
+                    put_cstr(out, &field.name);
+                    put_i32(out, 0);
+                    put_i16(out, 0);
+                    put_i32(out, field.type_oid as i32);
+                    put_i16(out, field.type_size);
+                    put_i32(out, -1);
+                    put_i16(out, field.format);
+                }
+            }
+            Self::DataRow(values) => {
+                put_i16(out, values.len() as i16);
+                for value in values.iter() {
+                    match value {
+                        Some(value) => {
+                            put_i32(out, value.len() as i32);
+                            out.extend_from_slice(value);
+                        }
+                        None => put_i32(out, -1),
+                    }
+                }
+            }
+            Self::CommandComplete(tag) => put_cstr(out, tag),
+            Self::ErrorResponse { code, message } => {
+                for (field, value) in [
+                    (b'S', "ERROR"),
+                    (b'V', "ERROR"),
+                    (b'C', code),
+                    (b'M', message),
+                ] {
+                    out.push(field);
+                    put_cstr(out, value);
+                }
+                out.push(0);
+            }
+            Self::ParameterDescription(oids) => {
+                put_i16(out, oids.len() as i16);
+                for oid in oids.iter() {
+                    put_i32(out, *oid as i32);
+                }
+            }
+            Self::EmptyQueryResponse
+            | Self::ParseComplete
+            | Self::BindComplete
+            | Self::CloseComplete
+            | Self::NoData
+            | Self::PortalSuspended => {}
+        }
+
+        let len = (out.len() - start) as i32;
+        out[start..start + 4].copy_from_slice(&len.to_be_bytes());
+    }
+}
+
+fn put_i16(out: &mut Vec<u8>, value: i16) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_i32(out: &mut Vec<u8>, value: i32) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_cstr(out: &mut Vec<u8>, value: &str) {
+    out.extend_from_slice(value.as_bytes());
+    out.push(0);
+}
@@ -0,0 +1,105 @@
+use {
+    gluesql_core::prelude::{Payload, PayloadVariable, Value},
+    std::collections::BTreeSet,
+};
+
+pub enum Output {
+    Rows {
+        labels: Vec<String>,
+        rows: Vec<Vec<Value>>,
+        /// Command tag prefix, `SELECT` for queries and `SHOW` for SHOW statements.
+        tag: &'static str,
+    },
+    Command(String),
+}
+
+impl From<Payload> for Output {
+    fn from(payload: Payload) -> Self {
+        let rows = |labels: &[&str], rows: Vec<Vec<Value>>| Output::Rows {
+            labels: labels.iter().map(|label| (*label).to_owned()).collect(),
+            rows,
+            tag: "SHOW",
+        };
+        let command = |tag: &str| Output::Command(tag.to_owned());
+
+        match payload {
+            Payload::Select { labels, rows } | Payload::Returning { labels, rows } => {
+                Output::Rows {
+                    labels,
+                    rows,
+                    tag: "SELECT",
+                }
+            }
+            Payload::SelectMap(maps) => {
+                let labels = maps
+                    .iter()
+                    .flat_map(|map| map.keys().cloned())
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                let rows = maps
+                    .into_iter()
+                    .map(|mut map| {
+                        labels
+                            .iter()
+                            .map(|label| map.remove(label).unwrap_or(Value::Null))
+                            .collect()
+                    })
+                    .collect();
+
+                Output::Rows {
+                    labels,
+                    rows,
+                    tag: "SELECT",
+                }
+            }
+            Payload::ShowColumns(columns) => rows(
+                &["field", "type"],
+                columns
+                    .into_iter()
+                    .map(|(name, data_type)| {
+                        vec![Value::Str(name), Value::Str(data_type.to_string())]
+                    })
+                    .collect(),
+            ),
+            Payload::ShowVariable(PayloadVariable::Version(version)) => {
+                rows(&["version"], vec![vec![Value::Str(version)]])
+            }
+            Payload::ShowVariable(PayloadVariable::Tables(names)) => rows(
+                &["table"],
+                names
+                    .into_iter()
+                    .map(|name| vec![Value::Str(name)])
+                    .collect(),
+            ),
+            Payload::ShowVariable(PayloadVariable::Functions(names)) => rows(
+                &["function"],
+                names
+                    .into_iter()
+                    .map(|name| vec![Value::Str(name)])
+                    .collect(),
+            ),
+            Payload::Insert(n) => Output::Command(format!("INSERT 0 {n}")),
+            Payload::Update(n) => Output::Command(format!("UPDATE {n}")),
+            Payload::Delete(n) => Output::Command(format!("DELETE {n}")),
+            Payload::Create => command("CREATE TABLE"),
+            Payload::DropTable(_) => command("DROP TABLE"),
+            Payload::DropView(_) => command("DROP VIEW"),
+            Payload::DropSequence(_) => command("DROP SEQUENCE"),
+            Payload::RefreshMaterializedView(_) => command("REFRESH MATERIALIZED VIEW"),
+            Payload::DropFunction => command("DROP FUNCTION"),
+            Payload::DropUser => command("DROP ROLE"),
+            Payload::Grant => command("GRANT"),
+            Payload::Revoke => command("REVOKE"),
+            Payload::AlterTable => command("ALTER TABLE"),
+            Payload::CreateIndex => command("CREATE INDEX"),
+            Payload::DropIndex => command("DROP INDEX"),
+            Payload::CreateTrigger => command("CREATE TRIGGER"),
+            Payload::DropTrigger => command("DROP TRIGGER"),
+            Payload::StartTransaction => command("BEGIN"),
+            Payload::Commit => command("COMMIT"),
+            Payload::Rollback => command("ROLLBACK"),
+            Payload::SetVariable => command("SET"),
+        }
+    }
+}
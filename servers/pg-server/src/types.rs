@@ -0,0 +1,366 @@
+use {
+    crate::error::{PgServerError, Result},
+    chrono::{NaiveDate, NaiveDateTime, NaiveTime},
+    gluesql_core::{
+        error::Error as GlueError,
+        prelude::{DataType, Value},
+    },
+    rust_decimal::Decimal,
+    uuid::Uuid,
+};
+
+const BOOL: u32 = 16;
+const BYTEA: u32 = 17;
+const INT8: u32 = 20;
+const INT2: u32 = 21;
+const INT4: u32 = 23;
+const TEXT: u32 = 25;
+const JSON: u32 = 114;
+const FLOAT4: u32 = 700;
+const FLOAT8: u32 = 701;
+const VARCHAR: u32 = 1043;
+const DATE: u32 = 1082;
+const TIME: u32 = 1083;
+const TIMESTAMP: u32 = 1114;
+const NUMERIC: u32 = 1700;
+const UUID: u32 = 2950;
+
+/// Postgres type a GlueSQL value is reported as in a `RowDescription`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PgType {
+    Bool,
+    Int2,
+    Int4,
+    Int8,
+    Float4,
+    Float8,
+    Numeric,
+    Text,
+    Bytea,
+    Date,
+    Time,
+    Timestamp,
+    Uuid,
+    Json,
+}
+
+impl PgType {
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::Bool(_) => Self::Bool,
+            Value::I8(_) | Value::I16(_) | Value::U8(_) => Self::Int2,
+            Value::I32(_) | Value::U16(_) => Self::Int4,
+            Value::I64(_) | Value::U32(_) => Self::Int8,
+            Value::I128(_) | Value::U64(_) | Value::U128(_) | Value::Decimal(_) => Self::Numeric,
+            Value::F32(_) => Self::Float4,
+            Value::F64(_) => Self::Float8,
+            Value::Bytea(_) => Self::Bytea,
+            Value::Date(_) => Self::Date,
+            Value::Time(_) => Self::Time,
+            Value::Timestamp(_) => Self::Timestamp,
+            Value::Uuid(_) => Self::Uuid,
+            Value::Map(_) | Value::List(_) => Self::Json,
+            Value::Str(_) | Value::Inet(_) | Value::Interval(_) | Value::Point(_) | Value::Null => {
+                Self::Text
+            }
+        }
+    }
+
+    /// Picks the column type from the first non-null value, falling back to `text`.
+    pub fn infer<'a>(values: impl IntoIterator<Item = &'a Value>) -> Self {
+        values
+            .into_iter()
+            .find(|value| !value.is_null())
+            .map(Self::of)
+            .unwrap_or(Self::Text)
+    }
+
+    pub fn oid(&self) -> u32 {
+        match self {
+            Self::Bool => BOOL,
+            Self::Int2 => INT2,
+            Self::Int4 => INT4,
+            Self::Int8 => INT8,
+            Self::Float4 => FLOAT4,
+            Self::Float8 => FLOAT8,
+            Self::Numeric => NUMERIC,
+            Self::Text => TEXT,
+            Self::Bytea => BYTEA,
+            Self::Date => DATE,
+            Self::Time => TIME,
+            Self::Timestamp => TIMESTAMP,
+            Self::Uuid => UUID,
+            Self::Json => JSON,
+        }
+    }
+
+    pub fn size(&self) -> i16 {
+        match self {
+            Self::Bool => 1,
+            Self::Int2 => 2,
+            Self::Int4 | Self::Float4 | Self::Date => 4,
+            Self::Int8 | Self::Float8 | Self::Time | Self::Timestamp => 8,
+            Self::Uuid => 16,
+            Self::Numeric | Self::Text | Self::Bytea | Self::Json => -1,
+        }
+    }
+
+    fn data_type(&self) -> Option<DataType> {
+        match self {
+            Self::Bool => Some(DataType::Boolean),
+            Self::Int2 => Some(DataType::Int16),
+            Self::Int4 => Some(DataType::Int32),
+            Self::Int8 => Some(DataType::Int),
+            Self::Float4 => Some(DataType::Float32),
+            Self::Float8 => Some(DataType::Float),
+            Self::Numeric => Some(DataType::Decimal),
+            Self::Bytea => Some(DataType::Bytea),
+            Self::Date => Some(DataType::Date),
+            Self::Time => Some(DataType::Time),
+            Self::Timestamp => Some(DataType::Timestamp),
+            Self::Uuid => Some(DataType::Uuid),
+            Self::Text | Self::Json => None,
+        }
+    }
+
+    /// Encodes a value for a column of this type, in text (`0`) or binary (`1`) format.
+    pub fn encode(&self, value: &Value, format: i16) -> Result<Option<Vec<u8>>> {
+        if value.is_null() {
+            return Ok(None);
+        }
+
+        let casted;
+        let value = match self.data_type() {
+            Some(data_type) if PgType::of(value) != *self => {
+                casted = value.cast(&data_type)?;
+                &casted
+            }
+            _ => value,
+        };
+
+        if format == 0 {
+            return Ok(Some(text(value).into_bytes()));
+        }
+
+        let bytes = match self {
+            Self::Bool => vec![convert::<bool>(value)? as u8],
+            Self::Int2 => convert::<i16>(value)?.to_be_bytes().to_vec(),
+            Self::Int4 => convert::<i32>(value)?.to_be_bytes().to_vec(),
+            Self::Int8 => convert::<i64>(value)?.to_be_bytes().to_vec(),
+            Self::Float4 => convert::<f32>(value)?.to_be_bytes().to_vec(),
+            Self::Float8 => convert::<f64>(value)?.to_be_bytes().to_vec(),
+            Self::Numeric => numeric(&text(value)),
+            Self::Text | Self::Json => text(value).into_bytes(),
+            Self::Bytea => match value {
+                Value::Bytea(bytes) => bytes.clone(),
+                _ => text(value).into_bytes(),
+            },
+            Self::Date => {
+                let days = (convert::<NaiveDate>(value)? - epoch().date()).num_days();
+                (days as i32).to_be_bytes().to_vec()
+            }
+            Self::Time => {
+                let time = convert::<NaiveTime>(value)?;
+                let micros = (time - NaiveTime::MIN)
+                    .num_microseconds()
+                    .unwrap_or_default();
+                micros.to_be_bytes().to_vec()
+            }
+            Self::Timestamp => {
+                let timestamp = convert::<NaiveDateTime>(value)?;
+                let micros = (timestamp - epoch()).num_microseconds().unwrap_or_default();
+                micros.to_be_bytes().to_vec()
+            }
+            Self::Uuid => match value {
+                Value::Uuid(uuid) => uuid.to_be_bytes().to_vec(),
+                _ => text(value).into_bytes(),
+            },
+        };
+
+        Ok(Some(bytes))
+    }
+}
+
+fn convert<'a, V>(value: &'a Value) -> Result<V>
+where
+    V: TryFrom<&'a Value>,
+    GlueError: From<V::Error>,
+{
+    V::try_from(value).map_err(|error| PgServerError::Glue(error.into()))
+}
+
+fn epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2000, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .unwrap_or_default()
+}
+
+/// Text representation following Postgres output conventions.
+pub fn text(value: &Value) -> String {
+    match value {
+        Value::Bool(true) => "t".to_owned(),
+        Value::Bool(false) => "f".to_owned(),
+        Value::Bytea(bytes) => format!("\\x{}", hex::encode(bytes)),
+        _ => String::from(value),
+    }
+}
+
+/// Binary `numeric`: base-10000 digit groups with weight, sign and display scale.
+fn numeric(text: &str) -> Vec<u8> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let (integer, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let integer = integer.trim_start_matches('0');
+
+    let integer_pad = (4 - integer.len() % 4) % 4;
+    let fraction_pad = (4 - fraction.len() % 4) % 4;
+    let digits = format!(
+        "{}{integer}{fraction}{}",
+        "0".repeat(integer_pad),
+        "0".repeat(fraction_pad)
+    );
+    let mut groups = digits
+        .as_bytes()
+        .chunks(4)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .fold(0i16, |acc, digit| acc * 10 + (digit - b'0') as i16)
+        })
+        .collect::<Vec<_>>();
+
+    let mut weight = ((integer.len() + integer_pad) / 4) as i16 - 1;
+    let leading = groups.iter().take_while(|group| **group == 0).count();
+    groups.drain(..leading);
+    weight -= leading as i16;
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+    if groups.is_empty() {
+        weight = 0;
+    }
+
+    let sign: u16 = if negative && !groups.is_empty() {
+        0x4000
+    } else {
+        0
+    };
+
+    let mut out = Vec::with_capacity(8 + groups.len() * 2);
+    out.extend_from_slice(&(groups.len() as i16).to_be_bytes());
+    out.extend_from_slice(&weight.to_be_bytes());
+    out.extend_from_slice(&sign.to_be_bytes());
+    out.extend_from_slice(&(fraction.len() as i16).to_be_bytes());
+    for group in groups {
+        out.extend_from_slice(&group.to_be_bytes());
+    }
+
+    out
+}
+
+/// Type reported for a parameter the client left unspecified.
+pub fn parameter_type(oid: u32) -> u32 {
+    match oid {
+        0 => TEXT,
+        oid => oid,
+    }
+}
+
+/// Renders a bound parameter as a SQL literal which replaces its `$n` placeholder.
+pub fn parameter_literal(oid: u32, format: i16, value: Option<&[u8]>) -> Result<String> {
+    let Some(value) = value else {
+        return Ok("NULL".to_owned());
+    };
+
+    if format == 0 {
+        let value = std::str::from_utf8(value)
+            .map_err(|_| PgServerError::InvalidParameter("invalid utf-8".to_owned()))?;
+
+        return match oid {
+            INT2 | INT4 | INT8 | FLOAT4 | FLOAT8 | NUMERIC => value
+                .parse::<Decimal>()
+                .map(|_| value.to_owned())
+                .map_err(|_| PgServerError::InvalidParameter(value.to_owned())),
+            BOOL => match value {
+                "t" | "true" | "1" | "on" | "yes" => Ok("TRUE".to_owned()),
+                "f" | "false" | "0" | "off" | "no" => Ok("FALSE".to_owned()),
+                _ => Err(PgServerError::InvalidParameter(value.to_owned())),
+            },
+            BYTEA => match value.strip_prefix("\\x") {
+                Some(hex) => hex::decode(hex)
+                    .map(|bytes| format!("X'{}'", hex::encode(bytes)))
+                    .map_err(|_| PgServerError::InvalidParameter(value.to_owned())),
+                None => Ok(format!("X'{}'", hex::encode(value))),
+            },
+            _ => Ok(quote(value)),
+        };
+    }
+
+    let fixed = |len: usize| -> Result<&[u8]> {
+        (value.len() == len)
+            .then_some(value)
+            .ok_or_else(|| PgServerError::InvalidParameter(format!("expected {len} bytes")))
+    };
+
+    Ok(match oid {
+        BOOL => (if fixed(1)?[0] != 0 { "TRUE" } else { "FALSE" }).to_owned(),
+        INT2 => i16::from_be_bytes(fixed(2)?.try_into().unwrap_or_default()).to_string(),
+        INT4 => i32::from_be_bytes(fixed(4)?.try_into().unwrap_or_default()).to_string(),
+        INT8 => i64::from_be_bytes(fixed(8)?.try_into().unwrap_or_default()).to_string(),
+        FLOAT4 => f32::from_be_bytes(fixed(4)?.try_into().unwrap_or_default()).to_string(),
+        FLOAT8 => f64::from_be_bytes(fixed(8)?.try_into().unwrap_or_default()).to_string(),
+        TEXT | VARCHAR | JSON | 0 => {
+            let value = std::str::from_utf8(value)
+                .map_err(|_| PgServerError::InvalidParameter("invalid utf-8".to_owned()))?;
+            quote(value)
+        }
+        BYTEA => format!("X'{}'", hex::encode(value)),
+        UUID => {
+            let uuid = u128::from_be_bytes(fixed(16)?.try_into().unwrap_or_default());
+            quote(&Uuid::from_u128(uuid).to_string())
+        }
+        oid => return Err(PgServerError::UnsupportedBinaryParameter(oid)),
+    })
+}
+
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BYTEA, NUMERIC, TEXT, numeric, parameter_literal};
+
+    fn decode(bytes: &[u8]) -> (i16, i16, u16, i16, Vec<i16>) {
+        let word = |i: usize| i16::from_be_bytes([bytes[i], bytes[i + 1]]);
+        let ndigits = word(0);
+        let digits = (0..ndigits as usize).map(|i| word(8 + i * 2)).collect();
+
+        (ndigits, word(2), word(4) as u16, word(6), digits)
+    }
+
+    #[test]
+    fn encode_numeric() {
+        assert_eq!(decode(&numeric("0")), (0, 0, 0, 0, vec![]));
+        assert_eq!(
+            decode(&numeric("12345.678")),
+            (3, 1, 0, 3, vec![1, 2345, 6780])
+        );
+        assert_eq!(decode(&numeric("-0.0001")), (1, -1, 0x4000, 4, vec![1]));
+        assert_eq!(decode(&numeric("10000")), (1, 1, 0, 0, vec![1]));
+    }
+
+    #[test]
+    fn text_parameter_literal() {
+        let literal = |oid, value: &str| parameter_literal(oid, 0, Some(value.as_bytes())).ok();
+
+        assert_eq!(literal(BYTEA, "\\x00ff"), Some("X'00ff'".to_owned()));
+        assert_eq!(literal(BYTEA, "\\x00'); DROP TABLE t; --"), None);
+        assert_eq!(literal(BYTEA, "ab"), Some("X'6162'".to_owned()));
+        assert_eq!(literal(NUMERIC, "1.5"), Some("1.5".to_owned()));
+        assert_eq!(literal(NUMERIC, "1); DROP TABLE t"), None);
+        assert_eq!(literal(TEXT, "it's"), Some("'it''s'".to_owned()));
+    }
+}
@@ -0,0 +1,241 @@
+use {
+    gluesql_core::{
+        prelude::Glue,
+        store::{GStore, GStoreMut},
+    },
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_pg_server::PgServer,
+    gluesql_sled_storage::SledStorage,
+    sled::Config,
+    tokio::net::TcpListener,
+    tokio_postgres::{Client, NoTls, SimpleQueryMessage, types::Type},
+};
+
+async fn connect<T>(server: PgServer<T>, config: &str) -> Client
+where
+    T: GStore + GStoreMut + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { server.serve(listener).await });
+
+    let config = format!("host=127.0.0.1 port={port} {config}");
+    let (client, connection) = tokio_postgres::connect(&config, NoTls).await.unwrap();
+    tokio::spawn(connection);
+
+    client
+}
+
+#[tokio::test]
+async fn simple_query() {
+    let client = connect(PgServer::new(MemoryStorage::default()), "user=glue").await;
+
+    client
+        .batch_execute(
+            "CREATE TABLE Item (id INTEGER, name TEXT, price DECIMAL);
+             INSERT INTO Item VALUES (1, 'apple', 1.5), (2, 'pear', NULL);",
+        )
+        .await
+        .unwrap();
+
+    let messages = client
+        .simple_query("SELECT id, name, price FROM Item ORDER BY id")
+        .await
+        .unwrap();
+    let rows = messages
+        .iter()
+        .filter_map(|message| match message {
+            SimpleQueryMessage::Row(row) => Some((0..3).map(|i| row.get(i)).collect::<Vec<_>>()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        rows,
+        vec![
+            vec![Some("1"), Some("apple"), Some("1.5")],
+            vec![Some("2"), Some("pear"), None],
+        ]
+    );
+    assert!(matches!(
+        messages.last(),
+        Some(SimpleQueryMessage::CommandComplete(2))
+    ));
+
+    let error = client
+        .simple_query("SELECT * FROM Missing")
+        .await
+        .unwrap_err();
+    assert!(error.as_db_error().is_some());
+}
+
+#[tokio::test]
+async fn extended_query() {
+    let client = connect(PgServer::new(MemoryStorage::default()), "user=glue").await;
+
+    client
+        .batch_execute(
+            "CREATE TABLE Item (id INTEGER, name TEXT, ok BOOLEAN);
+             INSERT INTO Item VALUES (1, 'apple', TRUE), (2, 'pear', FALSE);",
+        )
+        .await
+        .unwrap();
+
+    let statement = client
+        .prepare_typed(
+            "INSERT INTO Item VALUES ($1, $2, $3)",
+            &[Type::INT8, Type::TEXT, Type::BOOL],
+        )
+        .await
+        .unwrap();
+    let inserted = client
+        .execute(&statement, &[&3_i64, &"fig", &true])
+        .await
+        .unwrap();
+    assert_eq!(inserted, 1);
+
+    let statement = client
+        .prepare_typed(
+            "SELECT id, name, ok FROM Item WHERE id > $1 ORDER BY id",
+            &[Type::INT8],
+        )
+        .await
+        .unwrap();
+    let rows = client.query(&statement, &[&1_i64]).await.unwrap();
+    let rows = rows
+        .iter()
+        .map(|row| {
+            (
+                row.get::<_, i64>(0),
+                row.get::<_, String>(1),
+                row.get::<_, bool>(2),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        rows,
+        vec![(2, "pear".to_owned(), false), (3, "fig".to_owned(), true)]
+    );
+
+    let row = client
+        .query_one("SELECT name FROM Item WHERE name = $1", &[&"apple"])
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, &str>(0), "apple");
+}
+
+#[tokio::test]
+async fn transaction() {
+    let config = Config::default()
+        .path("tmp/pg_server_transaction")
+        .temporary(true);
+    let storage = SledStorage::try_from(config).unwrap();
+    let mut client = connect(PgServer::new(storage), "user=glue").await;
+
+    client
+        .batch_execute("CREATE TABLE Item (id INTEGER);")
+        .await
+        .unwrap();
+
+    let transaction = client.transaction().await.unwrap();
+    transaction
+        .batch_execute("INSERT INTO Item VALUES (1);")
+        .await
+        .unwrap();
+    transaction.commit().await.unwrap();
+
+    let transaction = client.transaction().await.unwrap();
+    transaction
+        .batch_execute("INSERT INTO Item VALUES (2);")
+        .await
+        .unwrap();
+    transaction.rollback().await.unwrap();
+
+    let row = client
+        .query_one("SELECT COUNT(*) AS count FROM Item", &[])
+        .await
+        .unwrap();
+    assert_eq!(row.get::<_, i64>(0), 1);
+}
+
+#[tokio::test]
+async fn password() {
+    let server = PgServer::new(MemoryStorage::default()).with_password("glue", "sticky");
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { server.serve(listener).await });
+
+    let config = format!("host=127.0.0.1 port={port} user=glue password=wrong");
+    let error = match tokio_postgres::connect(&config, NoTls).await {
+        Ok(_) => panic!("login with a wrong password should fail"),
+        Err(error) => error,
+    };
+    assert_eq!(
+        error.as_db_error().map(|error| error.code().code()),
+        Some("28P01")
+    );
+
+    let config = format!("host=127.0.0.1 port={port} user=glue password=sticky");
+    let (client, connection) = tokio_postgres::connect(&config, NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client.simple_query("SELECT 1").await.unwrap();
+}
+
+#[tokio::test]
+async fn max_connections() {
+    let server = PgServer::new(MemoryStorage::default()).with_max_connections(1);
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { server.serve(listener).await });
+
+    let config = format!("host=127.0.0.1 port={port} user=glue");
+    let (client, connection) = tokio_postgres::connect(&config, NoTls).await.unwrap();
+    let connection = tokio::spawn(connection);
+    client
+        .simple_query("SET search_path TO public")
+        .await
+        .unwrap();
+
+    let error = match tokio_postgres::connect(&config, NoTls).await {
+        Ok(_) => panic!("connections beyond the limit should be rejected"),
+        Err(error) => error,
+    };
+    assert_eq!(
+        error.as_db_error().map(|error| error.code().code()),
+        Some("53300")
+    );
+
+    drop(client);
+    connection.await.unwrap().unwrap();
+
+    let (client, connection) = tokio_postgres::connect(&config, NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client.simple_query("SELECT 1").await.unwrap();
+}
+
+#[tokio::test]
+async fn user_catalog() {
+    let mut glue = Glue::new(MemoryStorage::default());
+    glue.execute(
+        "CREATE TABLE Foo (id INTEGER, secret TEXT);
+        CREATE USER glue WITH PASSWORD 'sticky';
+        GRANT SELECT (id) ON Foo TO glue;",
+    )
+    .await
+    .unwrap();
+
+    let server = PgServer::new(glue.storage).with_user_catalog();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move { server.serve(listener).await });
+
+    let config = format!("host=127.0.0.1 port={port} user=glue password=wrong");
+    assert!(tokio_postgres::connect(&config, NoTls).await.is_err());
+
+    let config = format!("host=127.0.0.1 port={port} user=glue password=sticky");
+    let (client, connection) = tokio_postgres::connect(&config, NoTls).await.unwrap();
+    tokio::spawn(connection);
+    client.simple_query("SELECT id FROM Foo").await.unwrap();
+    assert!(client.simple_query("SELECT secret FROM Foo").await.is_err());
+}
@@ -0,0 +1,50 @@
+use gluesql_core::data::{native_password_hash, verify_native_password};
+
+/// Hook deciding who may open a session.
+///
+/// Servers receiving the password itself call [`Authenticator::verify`], servers with the
+/// `mysql_native_password` challenge-response login call
+/// [`Authenticator::verify_native_password`], and token based servers call
+/// [`Authenticator::verify_token`]. None of them reads a stored password.
+pub trait Authenticator: Send + Sync {
+    fn verify(&self, _user: &str, _password: &str) -> bool {
+        false
+    }
+
+    /// Checks the `response` a `mysql_native_password` login sent to `scramble`, see
+    /// [`verify_native_password`].
+    fn verify_native_password(&self, _user: &str, _scramble: &[u8], _response: &[u8]) -> bool {
+        false
+    }
+
+    fn verify_token(&self, _token: &str) -> bool {
+        false
+    }
+}
+
+/// A single user logging in with a password.
+pub struct Credentials {
+    pub user: String,
+    pub password: String,
+}
+
+impl Authenticator for Credentials {
+    fn verify(&self, user: &str, password: &str) -> bool {
+        user == self.user && password == self.password
+    }
+
+    fn verify_native_password(&self, user: &str, scramble: &[u8], response: &[u8]) -> bool {
+        user == self.user
+            && verify_native_password(&native_password_hash(&self.password), scramble, response)
+    }
+}
+
+/// Accepts any of the registered API keys as a token; there are no users to log in with.
+#[derive(Clone, Default)]
+pub struct ApiKeys(pub Vec<String>);
+
+impl Authenticator for ApiKeys {
+    fn verify_token(&self, token: &str) -> bool {
+        self.0.iter().any(|key| key == token)
+    }
+}
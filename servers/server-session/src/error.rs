@@ -0,0 +1,17 @@
+use thiserror::Error as ThisError;
+
+#[derive(ThisError, Debug, PartialEq)]
+pub enum SessionError {
+    #[error("too many connections, at most {0} are allowed")]
+    TooManyConnections(usize),
+}
+
+#[cfg(feature = "otlp")]
+#[derive(ThisError, Debug, PartialEq)]
+pub enum TelemetryError {
+    #[error("failed to build the OTLP exporter: {0}")]
+    Exporter(String),
+
+    #[error("failed to install the tracing subscriber: {0}")]
+    Subscriber(String),
+}
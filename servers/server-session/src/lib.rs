@@ -0,0 +1,186 @@
+#![deny(clippy::str_to_string)]
+
+mod auth;
+mod error;
+mod session;
+#[cfg(feature = "otlp")]
+mod telemetry;
+
+pub use {
+    auth::{ApiKeys, Authenticator, Credentials},
+    error::SessionError,
+    session::Session,
+};
+#[cfg(feature = "otlp")]
+pub use {error::TelemetryError, telemetry::Telemetry};
+
+use {
+    gluesql_core::{
+        ast::Statement,
+        data::User,
+        error::Result,
+        executor::execute,
+        prelude::Glue,
+        store::{GStore, GStoreMut},
+    },
+    std::sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    tokio::sync::{Mutex, Semaphore},
+};
+
+/// Opens the client sessions of a server over one shared [`Glue`].
+///
+/// Clones share the storage, the connection limit and the authenticator, so a server
+/// keeps one manager and hands a clone to every connection task.
+pub struct SessionManager<T: GStore + GStoreMut> {
+    glue: Arc<Mutex<Glue<T>>>,
+    authenticator: Option<Arc<dyn Authenticator>>,
+    user_catalog: bool,
+    max_connections: Option<(usize, Arc<Semaphore>)>,
+    next_id: Arc<AtomicU32>,
+}
+
+impl<T: GStore + GStoreMut> Clone for SessionManager<T> {
+    fn clone(&self) -> Self {
+        Self {
+            glue: Arc::clone(&self.glue),
+            authenticator: self.authenticator.clone(),
+            user_catalog: self.user_catalog,
+            max_connections: self.max_connections.clone(),
+            next_id: Arc::clone(&self.next_id),
+        }
+    }
+}
+
+impl<T: GStore + GStoreMut + Send + 'static> SessionManager<T> {
+    pub fn new(storage: T) -> Self {
+        Self {
+            glue: Arc::new(Mutex::new(Glue::new(storage))),
+            authenticator: None,
+            user_catalog: false,
+            max_connections: None,
+            next_id: Arc::new(AtomicU32::new(1)),
+        }
+    }
+
+    /// Requires clients to pass `authenticator` before a session is opened for them.
+    pub fn with_authenticator(mut self, authenticator: impl Authenticator + 'static) -> Self {
+        self.authenticator = Some(Arc::new(authenticator));
+        self
+    }
+
+    /// Logs clients in as the users created with `CREATE USER`, whose sessions run with the
+    /// privileges granted to them. Users of the authenticator are tried first.
+    pub fn with_user_catalog(mut self) -> Self {
+        self.user_catalog = true;
+        self
+    }
+
+    /// Limits the number of sessions open at the same time.
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = Some((max_connections, Arc::new(Semaphore::new(max_connections))));
+        self
+    }
+
+    /// Returns the authenticator, or `None` when every client is trusted.
+    pub fn authenticator(&self) -> Option<&dyn Authenticator> {
+        self.authenticator.as_deref()
+    }
+
+    pub fn user_catalog(&self) -> bool {
+        self.user_catalog
+    }
+
+    /// Whether clients have to log in, with either the authenticator or the user catalog.
+    pub fn requires_login(&self) -> bool {
+        self.authenticator.is_some() || self.user_catalog
+    }
+
+    /// Checks the password `user` logged in with, against the authenticator and then the
+    /// user catalog.
+    ///
+    /// Looking a user up in the catalog waits for a session holding the storage in a
+    /// transaction.
+    pub async fn verify(&self, user: &str, password: &str) -> bool {
+        if self
+            .authenticator
+            .as_ref()
+            .is_some_and(|authenticator| authenticator.verify(user, password))
+        {
+            return true;
+        }
+
+        self.catalog_user(user)
+            .await
+            .and_then(|user| user.password)
+            .is_some_and(|stored| stored.verify(password))
+    }
+
+    /// Checks the `response` of a `mysql_native_password` login to `scramble`, as
+    /// [`SessionManager::verify`] checks a password.
+    pub async fn verify_native_password(
+        &self,
+        user: &str,
+        scramble: &[u8],
+        response: &[u8],
+    ) -> bool {
+        if self.authenticator.as_ref().is_some_and(|authenticator| {
+            authenticator.verify_native_password(user, scramble, response)
+        }) {
+            return true;
+        }
+
+        self.catalog_user(user)
+            .await
+            .and_then(|user| user.password)
+            .is_some_and(|stored| stored.verify_native(scramble, response))
+    }
+
+    async fn catalog_user(&self, user: &str) -> Option<User> {
+        if !self.user_catalog {
+            return None;
+        }
+
+        self.glue.lock().await.storage.fetch_user(user).await.ok()?
+    }
+
+    pub fn glue(&self) -> &Arc<Mutex<Glue<T>>> {
+        &self.glue
+    }
+
+    /// Opens a session, failing when the connection limit is reached.
+    ///
+    /// The slot is released once the returned session is dropped.
+    pub fn open(&self) -> Result<Session<T>, SessionError> {
+        let permit = match &self.max_connections {
+            Some((max_connections, semaphore)) => Some(
+                Arc::clone(semaphore)
+                    .try_acquire_owned()
+                    .map_err(|_| SessionError::TooManyConnections(*max_connections))?,
+            ),
+            None => None,
+        };
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        Ok(Session::new(id, Arc::clone(&self.glue), permit))
+    }
+}
+
+/// Creates the superuser unless a user of that name exists, so that a server started with
+/// the user catalog has someone to grant privileges as.
+pub async fn create_superuser<T: GStore + GStoreMut>(
+    storage: &mut T,
+    name: &str,
+    password: &str,
+) -> Result<()> {
+    let statement = Statement::CreateUser {
+        if_not_exists: true,
+        name: name.to_owned(),
+        password: Some(password.to_owned()),
+        superuser: true,
+    };
+
+    execute(storage, &statement).await.map(|_| ())
+}
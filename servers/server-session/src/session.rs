@@ -0,0 +1,247 @@
+use {
+    futures::{Sink, sink::Drain},
+    gluesql_core::{
+        ast::Statement,
+        data::Row,
+        error::Result,
+        executor::authorize,
+        prelude::{Glue, Payload, plan, translate},
+        sqlparser::ast::{
+            CreateTable as SqlCreateTable, Expr as SqlExpr, ObjectName, Statement as SqlStatement,
+            Use as SqlUse, Value as SqlValue,
+        },
+        store::{GStore, GStoreMut},
+    },
+    std::{
+        collections::{BTreeMap, BTreeSet},
+        sync::Arc,
+    },
+    tokio::sync::{Mutex, OwnedMutexGuard, OwnedSemaphorePermit},
+};
+
+/// State of a single client connection.
+///
+/// Statements run against the storage shared by every session. A session inside an
+/// explicit transaction holds the storage exclusively until it commits or rolls back.
+/// Call [`Session::close`] when the client goes away, so that an open transaction is
+/// rolled back and the temporary tables are dropped.
+pub struct Session<T: GStore + GStoreMut> {
+    id: u32,
+    glue: Arc<Mutex<Glue<T>>>,
+    /// User whose privileges are checked, `None` to allow every statement.
+    user: Option<String>,
+    /// Exclusive access to the storage held while a transaction is open.
+    transaction: Option<OwnedMutexGuard<Glue<T>>>,
+    variables: BTreeMap<String, String>,
+    /// Tables created with `CREATE TEMPORARY TABLE`, dropped on close.
+    ///
+    /// GlueSQL has no session scoped tables, so other sessions can see them until then.
+    temporary_tables: BTreeSet<String>,
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl<T: GStore + GStoreMut + Send + 'static> Session<T> {
+    pub(crate) fn new(
+        id: u32,
+        glue: Arc<Mutex<Glue<T>>>,
+        permit: Option<OwnedSemaphorePermit>,
+    ) -> Self {
+        Self {
+            id,
+            glue,
+            user: None,
+            transaction: None,
+            variables: BTreeMap::new(),
+            temporary_tables: BTreeSet::new(),
+            _permit: permit,
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Runs the following statements as the user, see [`Glue::set_user`].
+    pub fn set_user(&mut self, user: Option<&str>) {
+        self.user = user.map(ToOwned::to_owned);
+    }
+
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    pub fn in_transaction(&self) -> bool {
+        self.transaction.is_some()
+    }
+
+    /// Looks up a variable assigned with `SET`; names are case insensitive.
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.variables.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    pub fn variables(&self) -> &BTreeMap<String, String> {
+        &self.variables
+    }
+
+    pub fn temporary_tables(&self) -> impl Iterator<Item = &str> {
+        self.temporary_tables.iter().map(String::as_str)
+    }
+
+    /// Runs a statement, returning `None` for `SET` and `USE` statements which only change
+    /// the session state.
+    pub async fn execute(&mut self, statement: &SqlStatement) -> Result<Option<Payload>> {
+        self.run(statement, None::<&mut Drain<Row>>).await
+    }
+
+    /// Runs a statement as [`Session::execute`] does, sending the rows of a query into `sink`
+    /// as they are read instead of collecting them, see [`Glue::execute_stmt_into`].
+    pub async fn execute_into<S: Sink<Row> + Unpin>(
+        &mut self,
+        statement: &SqlStatement,
+        sink: &mut S,
+    ) -> Result<Option<Payload>> {
+        self.run(statement, Some(sink)).await
+    }
+
+    async fn run<S: Sink<Row> + Unpin>(
+        &mut self,
+        statement: &SqlStatement,
+        sink: Option<&mut S>,
+    ) -> Result<Option<Payload>> {
+        if self.set(statement) {
+            return Ok(None);
+        }
+
+        let in_transaction = self.transaction.is_some();
+        let mut glue = self.lock().await;
+
+        let result = async {
+            let statement = translate(statement)?;
+            let statement = plan(&glue.storage, statement).await?;
+            if let Some(user) = &self.user {
+                authorize(&glue.storage, user, &statement).await?;
+            }
+
+            match sink {
+                Some(sink) => glue.execute_stmt_into(&statement, sink).await,
+                None => glue.execute_stmt(&statement).await,
+            }
+        }
+        .await;
+
+        let in_transaction = match &result {
+            Ok(Payload::StartTransaction) => true,
+            Ok(Payload::Commit | Payload::Rollback) => false,
+            _ => in_transaction,
+        };
+        if in_transaction {
+            self.transaction = Some(glue);
+        }
+
+        let payload = result?;
+        match (statement, &payload) {
+            (
+                SqlStatement::CreateTable(SqlCreateTable {
+                    temporary: true,
+                    name,
+                    ..
+                }),
+                Payload::Create,
+            ) => {
+                self.temporary_tables.extend(table_name(name));
+            }
+            (SqlStatement::Drop { names, .. }, Payload::DropTable(_)) => {
+                for name in names.iter().filter_map(table_name) {
+                    self.temporary_tables.remove(&name);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(Some(payload))
+    }
+
+    /// Rolls back the open transaction and drops the temporary tables.
+    pub async fn close(mut self) -> Result<()> {
+        let in_transaction = self.transaction.is_some();
+        let mut glue = self.lock().await;
+
+        if in_transaction {
+            glue.execute("ROLLBACK;").await?;
+        }
+
+        if !self.temporary_tables.is_empty() {
+            let statement = Statement::DropTable {
+                if_exists: true,
+                names: std::mem::take(&mut self.temporary_tables)
+                    .into_iter()
+                    .collect(),
+                cascade: false,
+            };
+            glue.execute_stmt(&statement).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn lock(&mut self) -> OwnedMutexGuard<Glue<T>> {
+        match self.transaction.take() {
+            Some(glue) => glue,
+            None => Arc::clone(&self.glue).lock_owned().await,
+        }
+    }
+
+    fn set(&mut self, statement: &SqlStatement) -> bool {
+        match statement {
+            SqlStatement::SetVariable {
+                variables, value, ..
+            } => {
+                let value = value.iter().map(text).collect::<Vec<_>>().join(", ");
+                for name in variables.iter() {
+                    self.variables
+                        .insert(name.to_string().to_lowercase(), value.clone());
+                }
+            }
+            SqlStatement::SetTimeZone { value, .. } => {
+                self.variables.insert("timezone".to_owned(), text(value));
+            }
+            SqlStatement::SetNames { charset_name, .. } => {
+                self.variables
+                    .insert("names".to_owned(), charset_name.clone());
+            }
+            SqlStatement::SetNamesDefault {} => {
+                self.variables.remove("names");
+            }
+            SqlStatement::Use(SqlUse::Default) => {
+                self.variables.remove("database");
+            }
+            SqlStatement::Use(
+                SqlUse::Catalog(name)
+                | SqlUse::Schema(name)
+                | SqlUse::Database(name)
+                | SqlUse::Warehouse(name)
+                | SqlUse::Object(name),
+            ) => {
+                self.variables
+                    .insert("database".to_owned(), name.to_string());
+            }
+            SqlStatement::SetTransaction { .. } => {}
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+fn text(expr: &SqlExpr) -> String {
+    match expr {
+        SqlExpr::Value(SqlValue::SingleQuotedString(value))
+        | SqlExpr::Value(SqlValue::DoubleQuotedString(value)) => value.clone(),
+        SqlExpr::Identifier(ident) => ident.value.clone(),
+        expr => expr.to_string(),
+    }
+}
+
+fn table_name(name: &ObjectName) -> Option<String> {
+    name.0.last().map(|ident| ident.value.clone())
+}
@@ -0,0 +1,52 @@
+use {
+    crate::error::TelemetryError,
+    opentelemetry::trace::TracerProvider,
+    opentelemetry_otlp::{SpanExporter, WithExportConfig},
+    opentelemetry_sdk::{Resource, trace::SdkTracerProvider},
+    tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt},
+};
+
+/// Exports the `tracing` spans of the process to an OpenTelemetry collector over OTLP/gRPC.
+///
+/// Spans are batched in the background and flushed when this is dropped, so a server keeps it
+/// alive until it stops serving. Which spans are exported follows `RUST_LOG`, `info` by
+/// default; the storage spans carrying the I/O timings are at the `debug` level.
+pub struct Telemetry {
+    provider: SdkTracerProvider,
+}
+
+impl Telemetry {
+    /// Installs the exporter as the global subscriber. Must be called within a Tokio runtime.
+    ///
+    /// `endpoint` is the collector address, e.g. `http://localhost:4317`.
+    pub fn init(service_name: &str, endpoint: &str) -> Result<Self, TelemetryError> {
+        let exporter = SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()
+            .map_err(|e| TelemetryError::Exporter(e.to_string()))?;
+        let resource = Resource::builder()
+            .with_service_name(service_name.to_owned())
+            .build();
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(resource)
+            .build();
+
+        let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+        let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("gluesql"));
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(layer)
+            .try_init()
+            .map_err(|e| TelemetryError::Subscriber(e.to_string()))?;
+
+        Ok(Self { provider })
+    }
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        let _ = self.provider.shutdown();
+    }
+}
@@ -0,0 +1,187 @@
+use {
+    gluesql_core::{
+        prelude::{Payload, PayloadVariable, Value, parse},
+        store::{GStore, GStoreMut},
+    },
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_server_session::{
+        ApiKeys, Authenticator, Credentials, Session, SessionError, SessionManager,
+        create_superuser,
+    },
+    gluesql_sled_storage::SledStorage,
+    sled::Config,
+};
+
+async fn run<T>(session: &mut Session<T>, sql: &str) -> Vec<Option<Payload>>
+where
+    T: GStore + GStoreMut + Send + 'static,
+{
+    let mut payloads = Vec::new();
+    for statement in parse(sql).unwrap().iter() {
+        payloads.push(session.execute(statement).await.unwrap());
+    }
+
+    payloads
+}
+
+async fn tables<T>(session: &mut Session<T>) -> Vec<String>
+where
+    T: GStore + GStoreMut + Send + 'static,
+{
+    match run(session, "SHOW TABLES").await.pop() {
+        Some(Some(Payload::ShowVariable(PayloadVariable::Tables(tables)))) => tables,
+        payload => panic!("unexpected payload: {payload:?}"),
+    }
+}
+
+#[tokio::test]
+async fn variables() {
+    let sessions = SessionManager::new(MemoryStorage::default());
+    let mut first = sessions.open().unwrap();
+    let mut second = sessions.open().unwrap();
+    assert_ne!(first.id(), second.id());
+
+    let payloads = run(
+        &mut first,
+        "SET search_path TO 'public'; SET TIME ZONE 'UTC'; SET client_encoding = UTF8;",
+    )
+    .await;
+    assert_eq!(payloads, vec![None, None, None]);
+    assert_eq!(first.variable("SEARCH_PATH"), Some("public"));
+    assert_eq!(first.variable("timezone"), Some("UTC"));
+    assert_eq!(first.variable("client_encoding"), Some("UTF8"));
+    assert_eq!(second.variable("search_path"), None);
+
+    run(&mut second, "SET application_name = 'psql'; USE shop;").await;
+    assert_eq!(second.variable("database"), Some("shop"));
+    assert_eq!(second.variables().len(), 2);
+}
+
+#[tokio::test]
+async fn temporary_tables() {
+    let sessions = SessionManager::new(MemoryStorage::default());
+    let mut session = sessions.open().unwrap();
+
+    run(
+        &mut session,
+        "CREATE TABLE Kept (id INTEGER);
+         CREATE TEMPORARY TABLE Scratch (id INTEGER);
+         CREATE TEMPORARY TABLE Dropped (id INTEGER);
+         DROP TABLE Dropped;",
+    )
+    .await;
+    assert_eq!(
+        session.temporary_tables().collect::<Vec<_>>(),
+        vec!["Scratch"]
+    );
+    session.close().await.unwrap();
+
+    let mut session = sessions.open().unwrap();
+    assert_eq!(tables(&mut session).await, vec!["Kept".to_owned()]);
+}
+
+#[tokio::test]
+async fn transactions() {
+    let path = "tmp/server_session_transactions";
+    let _ = std::fs::remove_dir_all(path);
+    let config = Config::default().path(path).temporary(true);
+    let sessions = SessionManager::new(SledStorage::try_from(config).unwrap());
+
+    let mut session = sessions.open().unwrap();
+    run(&mut session, "CREATE TABLE Item (id INTEGER); BEGIN;").await;
+    assert!(session.in_transaction());
+    assert!(sessions.glue().try_lock().is_err());
+
+    run(&mut session, "INSERT INTO Item VALUES (1); COMMIT;").await;
+    assert!(!session.in_transaction());
+    assert!(sessions.glue().try_lock().is_ok());
+
+    run(&mut session, "BEGIN; INSERT INTO Item VALUES (2);").await;
+    session.close().await.unwrap();
+
+    let mut session = sessions.open().unwrap();
+    let rows = match run(&mut session, "SELECT id FROM Item").await.pop() {
+        Some(Some(Payload::Select { rows, .. })) => rows,
+        payload => panic!("unexpected payload: {payload:?}"),
+    };
+    assert_eq!(rows, vec![vec![Value::I64(1)]]);
+}
+
+#[tokio::test]
+async fn connection_limit() {
+    let sessions = SessionManager::new(MemoryStorage::default()).with_max_connections(1);
+
+    let session = sessions.open().unwrap();
+    assert_eq!(
+        sessions.open().err(),
+        Some(SessionError::TooManyConnections(1))
+    );
+
+    drop(session);
+    assert!(sessions.open().is_ok());
+}
+
+#[test]
+fn authenticators() {
+    let credentials = Credentials {
+        user: "glue".to_owned(),
+        password: "sticky".to_owned(),
+    };
+    assert!(!credentials.verify_native_password("glue", b"01234567890123456789", &[]));
+    assert!(credentials.verify("glue", "sticky"));
+    assert!(!credentials.verify("glue", "wrong"));
+    assert!(!credentials.verify("other", "sticky"));
+    assert!(!credentials.verify_token("sticky"));
+
+    let api_keys = ApiKeys(vec!["secret".to_owned()]);
+    assert!(api_keys.verify_token("secret"));
+    assert!(!api_keys.verify_token("wrong"));
+    assert!(!api_keys.verify("glue", "secret"));
+
+    let sessions = SessionManager::new(MemoryStorage::default());
+    assert!(sessions.authenticator().is_none());
+
+    let sessions = sessions.with_authenticator(api_keys);
+    assert!(
+        sessions
+            .authenticator()
+            .is_some_and(|auth| auth.verify_token("secret"))
+    );
+}
+
+#[tokio::test]
+async fn user_catalog() {
+    let mut storage = MemoryStorage::default();
+    create_superuser(&mut storage, "admin", "secret")
+        .await
+        .unwrap();
+
+    let sessions = SessionManager::new(storage).with_user_catalog();
+    assert!(sessions.requires_login());
+    assert!(sessions.verify("admin", "secret").await);
+    assert!(!sessions.verify("admin", "wrong").await);
+
+    let mut admin = sessions.open().unwrap();
+    admin.set_user(Some("admin"));
+    run(
+        &mut admin,
+        "CREATE TABLE Foo (id INTEGER, secret TEXT);
+        CREATE USER glue WITH PASSWORD 'sticky';
+        GRANT SELECT (id) ON Foo TO glue;",
+    )
+    .await;
+    assert!(sessions.verify("glue", "sticky").await);
+    assert!(!sessions.verify("nobody", "sticky").await);
+    assert!(
+        !sessions
+            .verify_native_password("glue", b"01234567890123456789", &[])
+            .await
+    );
+
+    let mut glue = sessions.open().unwrap();
+    glue.set_user(Some("glue"));
+    run(&mut glue, "SELECT id FROM Foo").await;
+
+    let statement = parse("SELECT secret FROM Foo").unwrap().remove(0);
+    assert!(glue.execute(&statement).await.is_err());
+}
@@ -0,0 +1,66 @@
+use {gluesql_core::ast::Statement, std::collections::BTreeSet};
+
+/// Tables modified by a committed statement or transaction.
+///
+/// Published on [`WsServer::changes`](crate::WsServer::changes) for every write
+/// executed through the server, after its transaction commits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    pub tables: BTreeSet<String>,
+}
+
+/// Collects the changes of executed statements, holding back the ones made
+/// inside a transaction until it commits.
+#[derive(Default)]
+pub(crate) struct ChangeLog {
+    pub in_transaction: bool,
+    pending: BTreeSet<String>,
+    committed: BTreeSet<String>,
+}
+
+impl ChangeLog {
+    pub fn record(&mut self, statement: &Statement) {
+        let tables = match statement {
+            Statement::StartTransaction => {
+                self.in_transaction = true;
+                return;
+            }
+            Statement::Commit => {
+                self.in_transaction = false;
+                self.committed.append(&mut self.pending);
+                return;
+            }
+            Statement::Rollback => {
+                self.in_transaction = false;
+                self.pending.clear();
+                return;
+            }
+            Statement::Insert { table_name, .. }
+            | Statement::Update { table_name, .. }
+            | Statement::Delete { table_name, .. } => vec![table_name],
+            Statement::CreateTable { name, .. } | Statement::AlterTable { name, .. } => {
+                vec![name]
+            }
+            Statement::DropTable { names, .. } => names.iter().collect(),
+            _ => return,
+        };
+
+        let target = match self.in_transaction {
+            true => &mut self.pending,
+            false => &mut self.committed,
+        };
+        target.extend(tables.into_iter().cloned());
+    }
+
+    /// Discards the changes of an open transaction once it has been rolled back.
+    pub fn rollback(&mut self) {
+        self.in_transaction = false;
+        self.pending.clear();
+    }
+
+    pub fn into_change(self) -> Option<Change> {
+        (!self.committed.is_empty()).then_some(Change {
+            tables: self.committed,
+        })
+    }
+}
@@ -0,0 +1,42 @@
+use {
+    axum::{
+        http::StatusCode,
+        response::{IntoResponse, Response},
+    },
+    gluesql_core::error::Error as GlueError,
+    thiserror::Error as ThisError,
+};
+
+pub type Result<T, E = WsServerError> = std::result::Result<T, E>;
+
+#[derive(ThisError, Debug)]
+pub enum WsServerError {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    Glue(#[from] GlueError),
+
+    #[error("missing or invalid API key")]
+    Unauthorized,
+
+    #[error("message is not valid: {0}")]
+    InvalidMessage(String),
+
+    #[error("only a single SELECT statement can be subscribed")]
+    InvalidSubscription,
+
+    #[error("transaction was left open and has been rolled back")]
+    UnterminatedTransaction,
+}
+
+impl IntoResponse for WsServerError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            _ => StatusCode::BAD_REQUEST,
+        };
+
+        (status, self.to_string()).into_response()
+    }
+}
@@ -0,0 +1,339 @@
+#![deny(clippy::str_to_string)]
+
+mod change;
+mod error;
+mod message;
+mod payload;
+
+pub use {
+    change::Change,
+    error::{Result, WsServerError},
+};
+
+use {
+    axum::{
+        Router,
+        extract::{
+            Query, State, WebSocketUpgrade,
+            ws::{Message, WebSocket},
+        },
+        http::{HeaderMap, header},
+        response::Response,
+        routing::get,
+    },
+    change::ChangeLog,
+    gluesql_core::{
+        ast::Statement,
+        plan::fetch_schema_map,
+        prelude::{Glue, Payload, parse, plan, translate},
+        store::{GStore, GStoreMut},
+    },
+    message::{ClientMessage, ServerMessage},
+    serde::Deserialize,
+    serde_json::Value as Json,
+    std::{
+        collections::{BTreeMap, BTreeSet},
+        sync::Arc,
+    },
+    tokio::{
+        net::{TcpListener, ToSocketAddrs},
+        sync::{Mutex, broadcast},
+    },
+};
+
+const CHANGE_CAPACITY: usize = 1024;
+
+/// Serves a GlueSQL storage over WebSocket at `/ws`, pushing live query results.
+///
+/// Clients send JSON messages to run queries once or to subscribe to a SELECT
+/// statement. Whenever a write made through this server commits, subscriptions
+/// reading any of the modified tables are re-evaluated and their new result is
+/// pushed if it changed.
+///
+/// Requests are executed one at a time against a shared [`Glue`]. A transaction
+/// must be committed or rolled back within the message that started it.
+pub struct WsServer<T: GStore + GStoreMut> {
+    glue: Arc<Mutex<Glue<T>>>,
+    changes: broadcast::Sender<Change>,
+    api_keys: Arc<Vec<String>>,
+}
+
+impl<T: GStore + GStoreMut> Clone for WsServer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            glue: Arc::clone(&self.glue),
+            changes: self.changes.clone(),
+            api_keys: Arc::clone(&self.api_keys),
+        }
+    }
+}
+
+struct Subscription {
+    statement: Statement,
+    tables: BTreeSet<String>,
+    last: Json,
+}
+
+#[derive(Deserialize)]
+struct ConnectParams {
+    api_key: Option<String>,
+}
+
+impl<T: GStore + GStoreMut + Send + 'static> WsServer<T> {
+    pub fn new(storage: T) -> Self {
+        Self {
+            glue: Arc::new(Mutex::new(Glue::new(storage))),
+            changes: broadcast::channel(CHANGE_CAPACITY).0,
+            api_keys: Arc::new(Vec::new()),
+        }
+    }
+
+    /// Accepts connections carrying this key in `Authorization: Bearer <key>`,
+    /// `X-API-Key` or the `api_key` query parameter.
+    ///
+    /// Once a key is registered, connections without a valid key are rejected.
+    pub fn with_api_key(mut self, key: &str) -> Self {
+        Arc::make_mut(&mut self.api_keys).push(key.to_owned());
+        self
+    }
+
+    /// Subscribes to the tables changed by writes executed through this server.
+    pub fn changes(&self) -> broadcast::Receiver<Change> {
+        self.changes.subscribe()
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/ws", get(upgrade::<T>))
+            .with_state(self.clone())
+    }
+
+    pub async fn bind<A: ToSocketAddrs>(&self, addr: A) -> Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve(listener).await
+    }
+
+    pub async fn serve(&self, listener: TcpListener) -> Result<()> {
+        axum::serve(listener, self.router())
+            .await
+            .map_err(Into::into)
+    }
+
+    fn authorize(&self, headers: &HeaderMap, param: Option<&str>) -> Result<()> {
+        if self.api_keys.is_empty() {
+            return Ok(());
+        }
+
+        let bearer = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        let api_key = headers
+            .get("x-api-key")
+            .and_then(|value| value.to_str().ok());
+
+        match bearer.or(api_key).or(param) {
+            Some(key) if self.api_keys.iter().any(|api_key| api_key == key) => Ok(()),
+            _ => Err(WsServerError::Unauthorized),
+        }
+    }
+
+    async fn handle(self, mut socket: WebSocket) {
+        let mut changes = self.changes();
+        let mut subscriptions = BTreeMap::new();
+
+        loop {
+            let replies = tokio::select! {
+                message = socket.recv() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        vec![self.on_message(&text, &mut subscriptions).await]
+                    }
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => continue,
+                },
+                change = changes.recv() => match change {
+                    Ok(change) => self.refresh(&mut subscriptions, Some(&change.tables)).await,
+                    Err(broadcast::error::RecvError::Lagged(_)) => {
+                        self.refresh(&mut subscriptions, None).await
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            };
+
+            for reply in replies {
+                let text = serde_json::to_string(&reply).expect("server message serializes");
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn on_message(
+        &self,
+        text: &str,
+        subscriptions: &mut BTreeMap<String, Subscription>,
+    ) -> ServerMessage {
+        let message = match serde_json::from_str::<ClientMessage>(text) {
+            Ok(message) => message,
+            Err(error) => {
+                return ServerMessage::Error {
+                    id: None,
+                    error: WsServerError::InvalidMessage(error.to_string()).to_string(),
+                };
+            }
+        };
+
+        match message {
+            ClientMessage::Query { id, sql } => {
+                let payloads = self
+                    .execute(&sql)
+                    .await
+                    .and_then(|payloads| payload::convert(payloads).map_err(WsServerError::from));
+
+                match payloads {
+                    Ok(payloads) => ServerMessage::Result { id, payloads },
+                    Err(error) => ServerMessage::Error {
+                        id: Some(id),
+                        error: error.to_string(),
+                    },
+                }
+            }
+            ClientMessage::Subscribe { id, sql } => match self.subscribe(&sql).await {
+                Ok(subscription) => {
+                    let payload = subscription.last.clone();
+                    subscriptions.insert(id.clone(), subscription);
+
+                    ServerMessage::Snapshot { id, payload }
+                }
+                Err(error) => ServerMessage::Error {
+                    id: Some(id),
+                    error: error.to_string(),
+                },
+            },
+            ClientMessage::Unsubscribe { id } => {
+                subscriptions.remove(&id);
+
+                ServerMessage::Unsubscribed { id }
+            }
+        }
+    }
+
+    async fn subscribe(&self, sql: &str) -> Result<Subscription> {
+        let statement = match parse(sql)?.as_slice() {
+            [statement] => translate(statement)?,
+            _ => return Err(WsServerError::InvalidSubscription),
+        };
+        if !matches!(statement, Statement::Query(_)) {
+            return Err(WsServerError::InvalidSubscription);
+        }
+
+        let (tables, last) = self.evaluate(&statement).await?;
+
+        Ok(Subscription {
+            statement,
+            tables,
+            last,
+        })
+    }
+
+    /// Re-evaluates the subscriptions reading any of `tables`, or all of them when
+    /// `None`, returning updates for the results that changed.
+    async fn refresh(
+        &self,
+        subscriptions: &mut BTreeMap<String, Subscription>,
+        tables: Option<&BTreeSet<String>>,
+    ) -> Vec<ServerMessage> {
+        let mut updates = Vec::new();
+
+        for (id, subscription) in subscriptions.iter_mut() {
+            if tables.is_some_and(|tables| tables.is_disjoint(&subscription.tables)) {
+                continue;
+            }
+
+            match self.evaluate(&subscription.statement).await {
+                Ok((tables, payload)) => {
+                    subscription.tables = tables;
+                    if payload != subscription.last {
+                        subscription.last = payload.clone();
+                        updates.push(ServerMessage::Update {
+                            id: id.clone(),
+                            payload,
+                        });
+                    }
+                }
+                Err(error) => updates.push(ServerMessage::Error {
+                    id: Some(id.clone()),
+                    error: error.to_string(),
+                }),
+            }
+        }
+
+        updates
+    }
+
+    async fn evaluate(&self, statement: &Statement) -> Result<(BTreeSet<String>, Json)> {
+        let mut glue = self.glue.lock().await;
+        let tables = fetch_schema_map(&glue.storage, statement)
+            .await?
+            .into_keys()
+            .collect();
+        let statement = plan(&glue.storage, statement.clone()).await?;
+        let payload = glue.execute_stmt(&statement).await?;
+
+        Ok((tables, payload::convert_payload(payload)?))
+    }
+
+    async fn execute(&self, sql: &str) -> Result<Vec<Payload>> {
+        let mut glue = self.glue.lock().await;
+        let mut log = ChangeLog::default();
+        let result = run(&mut glue, sql, &mut log).await;
+
+        let result = match log.in_transaction {
+            true => {
+                log.rollback();
+                match glue.execute("ROLLBACK;").await {
+                    Ok(_) => result.and(Err(WsServerError::UnterminatedTransaction)),
+                    Err(error) => Err(error.into()),
+                }
+            }
+            false => result,
+        };
+        drop(glue);
+
+        if let Some(change) = log.into_change() {
+            let _ = self.changes.send(change);
+        }
+
+        result
+    }
+}
+
+async fn run<T: GStore + GStoreMut>(
+    glue: &mut Glue<T>,
+    sql: &str,
+    log: &mut ChangeLog,
+) -> Result<Vec<Payload>> {
+    let mut payloads = Vec::new();
+    for statement in parse(sql)?.iter() {
+        let statement = translate(statement)?;
+        let statement = plan(&glue.storage, statement).await?;
+        let payload = glue.execute_stmt(&statement).await?;
+
+        log.record(&statement);
+        payloads.push(payload);
+    }
+
+    Ok(payloads)
+}
+
+async fn upgrade<T: GStore + GStoreMut + Send + 'static>(
+    State(server): State<WsServer<T>>,
+    Query(params): Query<ConnectParams>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> Result<Response> {
+    server.authorize(&headers, params.api_key.as_deref())?;
+
+    Ok(ws.on_upgrade(move |socket| server.handle(socket)))
+}
@@ -0,0 +1,49 @@
+use {
+    serde::{Deserialize, Serialize},
+    serde_json::Value as Json,
+};
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Runs the statements once.
+    Query {
+        id: String,
+        sql: String,
+    },
+    /// Runs a SELECT statement and pushes its result again whenever it changes.
+    Subscribe {
+        id: String,
+        sql: String,
+    },
+    Unsubscribe {
+        id: String,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Result {
+        id: String,
+        payloads: Json,
+    },
+    /// Initial result of a subscription.
+    Snapshot {
+        id: String,
+        payload: Json,
+    },
+    /// Result of a subscription re-evaluated after its tables changed.
+    Update {
+        id: String,
+        payload: Json,
+    },
+    Unsubscribed {
+        id: String,
+    },
+    Error {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        error: String,
+    },
+}
@@ -0,0 +1,195 @@
+use {
+    futures::{SinkExt, StreamExt},
+    gluesql_core::store::{GStore, GStoreMut},
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_sled_storage::SledStorage,
+    gluesql_ws_server::WsServer,
+    serde_json::{Value as Json, json},
+    sled::Config,
+    std::time::Duration,
+    tokio::{
+        net::{TcpListener, TcpStream},
+        time::timeout,
+    },
+    tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async, tungstenite::Message},
+};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn spawn<T>(server: WsServer<T>) -> String
+where
+    T: GStore + GStoreMut + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { server.serve(listener).await });
+
+    format!("ws://{addr}/ws")
+}
+
+async fn send(socket: &mut Socket, message: Json) {
+    socket
+        .send(Message::Text(message.to_string().into()))
+        .await
+        .unwrap();
+}
+
+async fn recv(socket: &mut Socket) -> Json {
+    loop {
+        let message = timeout(Duration::from_secs(5), socket.next())
+            .await
+            .expect("message within timeout")
+            .unwrap()
+            .unwrap();
+
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+async fn query(socket: &mut Socket, id: &str, sql: &str) -> Json {
+    send(socket, json!({ "type": "query", "id": id, "sql": sql })).await;
+    recv(socket).await
+}
+
+fn rows(ids: &[i64]) -> Json {
+    json!({
+        "type": "SELECT",
+        "labels": ["id"],
+        "rows": ids.iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+    })
+}
+
+#[tokio::test]
+async fn query_once() {
+    let url = spawn(WsServer::new(MemoryStorage::default())).await;
+    let (mut socket, _) = connect_async(url).await.unwrap();
+
+    let reply = query(
+        &mut socket,
+        "1",
+        "CREATE TABLE Item (id INTEGER); INSERT INTO Item VALUES (1);",
+    )
+    .await;
+    assert_eq!(
+        reply,
+        json!({
+            "type": "result",
+            "id": "1",
+            "payloads": [{ "type": "CREATE TABLE" }, { "type": "INSERT", "affected": 1 }],
+        })
+    );
+
+    let reply = query(&mut socket, "2", "SELECT * FROM Missing").await;
+    assert_eq!(
+        (reply["type"].as_str(), reply["id"].as_str()),
+        (Some("error"), Some("2"))
+    );
+
+    send(&mut socket, json!({ "type": "unknown" })).await;
+    assert_eq!(recv(&mut socket).await["type"], "error");
+}
+
+#[tokio::test]
+async fn subscribe() {
+    let url = spawn(WsServer::new(MemoryStorage::default())).await;
+    let (mut watcher, _) = connect_async(&url).await.unwrap();
+    let (mut writer, _) = connect_async(&url).await.unwrap();
+
+    query(
+        &mut writer,
+        "1",
+        "CREATE TABLE Item (id INTEGER); CREATE TABLE Other (id INTEGER);",
+    )
+    .await;
+    query(&mut writer, "2", "INSERT INTO Item VALUES (1);").await;
+
+    send(
+        &mut watcher,
+        json!({ "type": "subscribe", "id": "items", "sql": "SELECT id FROM Item ORDER BY id" }),
+    )
+    .await;
+    assert_eq!(
+        recv(&mut watcher).await,
+        json!({ "type": "snapshot", "id": "items", "payload": rows(&[1]) })
+    );
+
+    query(&mut writer, "3", "INSERT INTO Other VALUES (9);").await;
+    query(&mut writer, "4", "UPDATE Item SET id = 1;").await;
+    query(&mut writer, "5", "INSERT INTO Item VALUES (2);").await;
+    assert_eq!(
+        recv(&mut watcher).await,
+        json!({ "type": "update", "id": "items", "payload": rows(&[1, 2]) })
+    );
+
+    send(
+        &mut watcher,
+        json!({ "type": "unsubscribe", "id": "items" }),
+    )
+    .await;
+    assert_eq!(
+        recv(&mut watcher).await,
+        json!({ "type": "unsubscribed", "id": "items" })
+    );
+
+    query(&mut writer, "6", "DELETE FROM Item;").await;
+    let reply = query(&mut watcher, "7", "SELECT COUNT(*) AS count FROM Item").await;
+    assert_eq!(reply["type"], "result");
+
+    send(
+        &mut watcher,
+        json!({ "type": "subscribe", "id": "bad", "sql": "DELETE FROM Item" }),
+    )
+    .await;
+    assert_eq!(recv(&mut watcher).await["type"], "error");
+}
+
+#[tokio::test]
+async fn changes_after_commit() {
+    let config = Config::default()
+        .path("tmp/ws_server_changes_after_commit")
+        .temporary(true);
+    let server = WsServer::new(SledStorage::try_from(config).unwrap());
+    let mut changes = server.changes();
+    let url = spawn(server).await;
+    let (mut socket, _) = connect_async(url).await.unwrap();
+
+    query(&mut socket, "1", "CREATE TABLE Item (id INTEGER);").await;
+    assert_eq!(
+        changes.recv().await.unwrap().tables,
+        ["Item".to_owned()].into()
+    );
+
+    let reply = query(&mut socket, "2", "BEGIN; INSERT INTO Item VALUES (1);").await;
+    assert_eq!(reply["type"], "error");
+
+    query(
+        &mut socket,
+        "3",
+        "BEGIN; INSERT INTO Item VALUES (2); ROLLBACK;",
+    )
+    .await;
+    query(&mut socket, "4", "BEGIN; DELETE FROM Item; COMMIT;").await;
+    assert_eq!(
+        changes.recv().await.unwrap().tables,
+        ["Item".to_owned()].into()
+    );
+    assert!(changes.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn api_key() {
+    let url = spawn(WsServer::new(MemoryStorage::default()).with_api_key("secret")).await;
+
+    assert!(connect_async(&url).await.is_err());
+    assert!(connect_async(format!("{url}?api_key=wrong")).await.is_err());
+
+    let (mut socket, _) = connect_async(format!("{url}?api_key=secret"))
+        .await
+        .unwrap();
+    assert_eq!(
+        query(&mut socket, "1", "SHOW TABLES").await["type"],
+        "result"
+    );
+}
@@ -92,3 +92,6 @@ impl gluesql_core::store::IndexMut for CompositeStorage {}
 impl Metadata for CompositeStorage {}
 impl gluesql_core::store::CustomFunction for CompositeStorage {}
 impl gluesql_core::store::CustomFunctionMut for CompositeStorage {}
+impl gluesql_core::store::User for CompositeStorage {}
+impl gluesql_core::store::UserMut for CompositeStorage {}
+impl gluesql_core::store::Backup for CompositeStorage {}
@@ -3,14 +3,15 @@ use {
     async_trait::async_trait,
     futures::stream::{self, StreamExt, TryStreamExt},
     gluesql_core::{
-        data::{Key, Schema},
+        data::{Key, Schema, TableStatistics},
         error::Result,
-        store::{DataRow, RowIter, Store},
+        store::{DataRow, Predicate, RowIter, Store},
     },
 };
 
 #[async_trait]
 impl Store for CompositeStorage {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let schemas = stream::iter(self.storages.values())
             .map(AsRef::as_ref)
@@ -24,6 +25,7 @@ impl Store for CompositeStorage {
         Ok(schemas)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         for storage in self.storages.values() {
             let schema = storage.fetch_schema(table_name).await?;
@@ -36,6 +38,7 @@ impl Store for CompositeStorage {
         Ok(None)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
         self.fetch_storage(table_name)
             .await?
@@ -43,10 +46,44 @@ impl Store for CompositeStorage {
             .await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         self.fetch_storage(table_name)
             .await?
             .scan_data(table_name)
             .await
     }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn scan_data_filtered<'a>(
+        &'a self,
+        table_name: &str,
+        predicate: &Predicate,
+    ) -> Result<RowIter<'a>> {
+        self.fetch_storage(table_name)
+            .await?
+            .scan_data_filtered(table_name, predicate)
+            .await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn scan_data_partitioned<'a>(
+        &'a self,
+        table_name: &str,
+        predicate: Option<&Predicate>,
+        partitions: usize,
+    ) -> Result<Vec<RowIter<'a>>> {
+        self.fetch_storage(table_name)
+            .await?
+            .scan_data_partitioned(table_name, predicate, partitions)
+            .await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn fetch_statistics(&self, table_name: &str) -> Result<Option<TableStatistics>> {
+        self.fetch_storage(table_name)
+            .await?
+            .fetch_statistics(table_name)
+            .await
+    }
 }
@@ -2,7 +2,7 @@ use {
     super::CompositeStorage,
     async_trait::async_trait,
     gluesql_core::{
-        data::{Key, Schema},
+        data::{Key, Schema, TableStatistics},
         error::{Error, Result},
         store::{DataRow, StoreMut},
     },
@@ -10,6 +10,7 @@ use {
 
 #[async_trait]
 impl StoreMut for CompositeStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let storage = schema
             .engine
@@ -34,6 +35,7 @@ impl StoreMut for CompositeStorage {
         }
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         self.fetch_storage_mut(table_name)
             .await?
@@ -41,6 +43,7 @@ impl StoreMut for CompositeStorage {
             .await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         self.fetch_storage_mut(table_name)
             .await?
@@ -48,6 +51,7 @@ impl StoreMut for CompositeStorage {
             .await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
         self.fetch_storage_mut(table_name)
             .await?
@@ -55,10 +59,19 @@ impl StoreMut for CompositeStorage {
             .await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         self.fetch_storage_mut(table_name)
             .await?
             .delete_data(table_name, keys)
             .await
     }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = statistics.table_name))]
+    async fn insert_statistics(&mut self, statistics: TableStatistics) -> Result<()> {
+        self.fetch_storage_mut(&statistics.table_name)
+            .await?
+            .insert_statistics(statistics)
+            .await
+    }
 }
@@ -5,7 +5,7 @@ mod store_mut;
 use {
     error::{CsvStorageError, ResultExt},
     gluesql_core::{
-        ast::{ColumnDef, ColumnUniqueOption, DataType},
+        ast::{ColumnDef, DataType},
         data::{Key, Schema, Value},
         error::Result,
         parse_sql::parse_data_type,
@@ -71,7 +71,12 @@ impl CsvStorage {
                 indexes: Vec::new(),
                 engine: None,
                 foreign_keys: Vec::new(),
+                checks: Vec::new(),
+                uniques: Vec::new(),
                 comment: None,
+                view: None,
+                triggers: Vec::new(),
+                sequence: None,
             };
 
             return Ok(Some((schema, true)));
@@ -148,8 +153,6 @@ impl CsvStorage {
                 .into_records()
                 .enumerate()
                 .map(move |(index, record)| {
-                    let mut key: Option<Key> = None;
-
                     let values = record
                         .map_storage_err()?
                         .into_iter()
@@ -165,15 +168,12 @@ impl CsvStorage {
                                 data_type => value.cast(data_type)?,
                             };
 
-                            if column_def.unique == Some(ColumnUniqueOption { is_primary: true }) {
-                                key = Key::try_from(&value).map(Some)?;
-                            }
-
                             Ok(value)
                         })
                         .collect::<Result<Vec<Value>>>()?;
 
-                    let key = key.unwrap_or(Key::U64(index as u64));
+                    let key =
+                        Key::primary(&column_defs, &values)?.unwrap_or(Key::U64(index as u64));
                     let row = DataRow::Vec(values);
 
                     Ok((key, row))
@@ -254,6 +254,9 @@ impl CsvStorage {
 impl AlterTable for CsvStorage {}
 impl CustomFunction for CsvStorage {}
 impl CustomFunctionMut for CsvStorage {}
+impl gluesql_core::store::User for CsvStorage {}
+impl gluesql_core::store::UserMut for CsvStorage {}
+impl gluesql_core::store::Backup for CsvStorage {}
 impl Index for CsvStorage {}
 impl IndexMut for CsvStorage {}
 impl Transaction for CsvStorage {}
@@ -15,18 +15,22 @@ use {
 
 #[async_trait]
 impl Store for CsvStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         self.fetch_schema(table_name)
             .map(|schema| schema.map(|(schema, _)| schema))
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let paths = fs::read_dir(&self.path).map_storage_err()?;
         let mut schemas = paths
             .map(|result| {
                 let path = result.map_storage_err()?.path();
                 let extension = path.extension().and_then(OsStr::to_str);
-                if extension != Some("csv") || path.to_string_lossy().ends_with(".types.csv") {
+                let is_data =
+                    extension == Some("csv") && !path.to_string_lossy().ends_with(".types.csv");
+                if !is_data && extension != Some("sql") {
                     return Ok(None);
                 }
 
@@ -35,6 +39,22 @@ impl Store for CsvStorage {
                     .and_then(OsStr::to_str)
                     .map_storage_err(CsvStorageError::FileNotFound)?;
 
+                // Views have a schema file but no data file
+                if !is_data {
+                    if self.data_path(table_name).exists() {
+                        return Ok(None);
+                    }
+
+                    let view = self
+                        .fetch_schema(table_name)
+                        .ok()
+                        .flatten()
+                        .map(|(schema, _)| schema)
+                        .filter(|schema| schema.view.is_some());
+
+                    return Ok(view);
+                }
+
                 self.fetch_schema(table_name)?
                     .map(|(schema, _)| schema)
                     .map_storage_err(CsvStorageError::TableDoesNotExist)
@@ -48,6 +68,7 @@ impl Store for CsvStorage {
         Ok(schemas)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, target: &Key) -> Result<Option<DataRow>> {
         let (_, rows) = self.scan_data(table_name)?;
 
@@ -62,6 +83,7 @@ impl Store for CsvStorage {
         Ok(None)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         let rows = self.scan_data(table_name).map(|(_, rows)| rows)?;
 
@@ -22,6 +22,7 @@ use {
 
 #[async_trait]
 impl StoreMut for CsvStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let schema_path = self.schema_path(schema.table_name.as_str());
         let ddl = schema.to_ddl();
@@ -48,6 +49,7 @@ impl StoreMut for CsvStorage {
             .map_storage_err()
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         let data_path = self.data_path(table_name);
         if data_path.exists() {
@@ -67,6 +69,7 @@ impl StoreMut for CsvStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         let (columns, prev_rows) = self.scan_data(table_name)?;
 
@@ -94,6 +97,7 @@ impl StoreMut for CsvStorage {
         }
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, mut rows: Vec<(Key, DataRow)>) -> Result<()> {
         let (columns, prev_rows) = self.scan_data(table_name)?;
 
@@ -104,6 +108,7 @@ impl StoreMut for CsvStorage {
         self.write(table_name, columns, merged)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         let (columns, prev_rows) = self.scan_data(table_name)?;
         let rows = prev_rows.filter_map(|item| {
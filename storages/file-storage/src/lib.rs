@@ -80,3 +80,6 @@ impl Transaction for FileStorage {}
 impl Metadata for FileStorage {}
 impl CustomFunction for FileStorage {}
 impl CustomFunctionMut for FileStorage {}
+impl gluesql_core::store::User for FileStorage {}
+impl gluesql_core::store::UserMut for FileStorage {}
+impl gluesql_core::store::Backup for FileStorage {}
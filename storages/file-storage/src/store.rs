@@ -12,6 +12,7 @@ use {
 
 #[async_trait]
 impl Store for FileStorage {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let mut schemas = fs::read_dir(&self.path)
             .map_storage_err()?
@@ -34,6 +35,7 @@ impl Store for FileStorage {
         Ok(schemas)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         let path = self.path(table_name).with_extension("sql");
         if !path.exists() {
@@ -43,6 +45,7 @@ impl Store for FileStorage {
         self.fetch_schema(path).map(Some)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
         let path = self.data_path(table_name, key)?;
         if !path.exists() {
@@ -59,6 +62,7 @@ impl Store for FileStorage {
             .map(Some)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         let path = self.path(table_name);
         let mut entries = fs::read_dir(path)
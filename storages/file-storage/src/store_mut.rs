@@ -16,6 +16,7 @@ use {
 
 #[async_trait]
 impl StoreMut for FileStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let table_name = schema.table_name.clone();
         let schema = schema.to_ddl();
@@ -35,6 +36,7 @@ impl StoreMut for FileStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         let path = self.path(table_name);
         if !path.exists() {
@@ -49,6 +51,7 @@ impl StoreMut for FileStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         for row in rows {
             let key = Key::Uuid(Uuid::now_v7().as_u128());
@@ -63,6 +66,7 @@ impl StoreMut for FileStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
         for (key, row) in rows {
             let path = self.data_path(table_name, &key)?;
@@ -76,6 +80,7 @@ impl StoreMut for FileStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         for key in keys {
             let path = self.data_path(table_name, &key)?;
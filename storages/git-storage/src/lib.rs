@@ -162,3 +162,6 @@ impl Transaction for GitStorage {}
 impl Metadata for GitStorage {}
 impl CustomFunction for GitStorage {}
 impl CustomFunctionMut for GitStorage {}
+impl gluesql_core::store::User for GitStorage {}
+impl gluesql_core::store::UserMut for GitStorage {}
+impl gluesql_core::store::Backup for GitStorage {}
@@ -10,18 +10,22 @@ use {
 
 #[async_trait]
 impl Store for GitStorage {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         self.get_store().fetch_all_schemas().await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         self.get_store().fetch_schema(table_name).await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
         self.get_store().fetch_data(table_name, key).await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         self.get_store().scan_data(table_name).await
     }
@@ -10,6 +10,7 @@ use {
 
 #[async_trait]
 impl StoreMut for GitStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         self.get_store_mut().insert_schema(schema).await?;
 
@@ -19,12 +20,14 @@ impl StoreMut for GitStorage {
         ))
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         self.get_store_mut().delete_schema(table_name).await?;
 
         self.add_and_commit("[GitStorage::delete_schema] {table_name}")
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         let n = rows.len();
         if n == 0 {
@@ -38,6 +41,7 @@ impl StoreMut for GitStorage {
         ))
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
         let n = rows.len();
         if n == 0 {
@@ -51,6 +55,7 @@ impl StoreMut for GitStorage {
         ))
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         let n = keys.len();
         if n == 0 {
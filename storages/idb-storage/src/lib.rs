@@ -209,6 +209,7 @@ impl IdbStorage {
 
 #[async_trait]
 impl Store for IdbStorage {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let transaction = self
             .database
@@ -249,6 +250,7 @@ impl Store for IdbStorage {
         Ok(schemas)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         let transaction = self
             .database
@@ -280,6 +282,7 @@ impl Store for IdbStorage {
         Ok(schema)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, target: &Key) -> Result<Option<DataRow>> {
         let column_defs = self
             .fetch_schema(table_name)
@@ -320,6 +323,7 @@ impl Store for IdbStorage {
         Ok(row)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         let column_defs = self
             .fetch_schema(table_name)
@@ -401,6 +405,7 @@ impl Store for IdbStorage {
 
 #[async_trait]
 impl StoreMut for IdbStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let schema_exists = self
             .fetch_schema(&schema.table_name)
@@ -453,6 +458,7 @@ impl StoreMut for IdbStorage {
             .map(|_| ())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         self.alter_object_store(table_name.to_owned(), AlterType::DeleteSchema)
             .await?;
@@ -485,6 +491,7 @@ impl StoreMut for IdbStorage {
             .map(|_| ())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn append_data(&mut self, table_name: &str, new_rows: Vec<DataRow>) -> Result<()> {
         let transaction = self
             .database
@@ -524,6 +531,7 @@ impl StoreMut for IdbStorage {
             .map(|_| ())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn insert_data(&mut self, table_name: &str, new_rows: Vec<(Key, DataRow)>) -> Result<()> {
         let transaction = self
             .database
@@ -567,6 +575,7 @@ impl StoreMut for IdbStorage {
             .map(|_| ())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         let transaction = self
             .database
@@ -609,6 +618,9 @@ impl gluesql_core::store::Transaction for IdbStorage {}
 impl Metadata for IdbStorage {}
 impl gluesql_core::store::CustomFunction for IdbStorage {}
 impl gluesql_core::store::CustomFunctionMut for IdbStorage {}
+impl gluesql_core::store::User for IdbStorage {}
+impl gluesql_core::store::UserMut for IdbStorage {}
+impl gluesql_core::store::Backup for IdbStorage {}
 
 /// `IdbStorage` holds `web_sys` types that are not `Send` by default, but it
 /// is used only on `wasm32` targets where execution happens on a single
@@ -0,0 +1,48 @@
+use {
+    crate::{JsonStorage, error::ResultExt},
+    async_trait::async_trait,
+    gluesql_core::{
+        error::Result,
+        store::{Backup, BackupError, Store},
+    },
+    std::{fs, path::Path},
+};
+
+#[async_trait]
+impl Backup for JsonStorage {
+    /// Reads the files of every table into memory before writing any of them, so the copy is
+    /// not left half written when a table file cannot be read.
+    async fn backup(&self, target_path: &Path) -> Result<()> {
+        if target_path.exists() {
+            return Err(BackupError::TargetAlreadyExists(target_path.display().to_string()).into());
+        }
+
+        let mut files = Vec::new();
+        for schema in self.fetch_all_schemas().await? {
+            let table_name = schema.table_name.as_str();
+            for path in [
+                self.schema_path(table_name),
+                self.json_path(table_name),
+                self.jsonl_path(table_name),
+            ] {
+                if !path.exists() {
+                    continue;
+                }
+
+                let content = fs::read(&path).map_storage_err()?;
+                files.push((path, content));
+            }
+        }
+
+        fs::create_dir_all(target_path).map_storage_err()?;
+        for (path, content) in files {
+            let Some(file_name) = path.file_name() else {
+                continue;
+            };
+
+            fs::write(target_path.join(file_name), content).map_storage_err()?;
+        }
+
+        Ok(())
+    }
+}
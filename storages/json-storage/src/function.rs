@@ -1,7 +1,9 @@
 use {
     super::JsonStorage,
-    gluesql_core::store::{CustomFunction, CustomFunctionMut},
+    gluesql_core::store::{CustomFunction, CustomFunctionMut, User, UserMut},
 };
 
 impl CustomFunction for JsonStorage {}
 impl CustomFunctionMut for JsonStorage {}
+impl User for JsonStorage {}
+impl UserMut for JsonStorage {}
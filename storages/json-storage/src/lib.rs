@@ -1,4 +1,5 @@
 mod alter_table;
+mod backup;
 pub mod error;
 mod function;
 mod index;
@@ -9,7 +10,6 @@ mod transaction;
 use {
     error::{JsonStorageError, OptionExt, ResultExt},
     gluesql_core::{
-        ast::ColumnUniqueOption,
         data::{Key, Schema, value::BTreeMapJsonExt},
         error::{Error, Result},
         store::{DataRow, Metadata},
@@ -54,23 +54,42 @@ impl JsonStorage {
         }
 
         let schema_path = self.schema_path(table_name);
-        let (column_defs, foreign_keys, comment) = match schema_path.exists() {
-            true => {
-                let mut file = File::open(&schema_path).map_storage_err()?;
-                let mut ddl = String::new();
-                file.read_to_string(&mut ddl).map_storage_err()?;
-
-                let schema = Schema::from_ddl(&ddl)?;
-                if schema.table_name != table_name {
-                    return Err(Error::StorageMsg(
-                        JsonStorageError::TableNameDoesNotMatchWithFile.to_string(),
-                    ));
-                }
+        let (column_defs, foreign_keys, checks, uniques, comment, view, triggers, sequence) =
+            match schema_path.exists() {
+                true => {
+                    let mut file = File::open(&schema_path).map_storage_err()?;
+                    let mut ddl = String::new();
+                    file.read_to_string(&mut ddl).map_storage_err()?;
+
+                    let schema = Schema::from_ddl(&ddl)?;
+                    if schema.table_name != table_name {
+                        return Err(Error::StorageMsg(
+                            JsonStorageError::TableNameDoesNotMatchWithFile.to_string(),
+                        ));
+                    }
 
-                (schema.column_defs, schema.foreign_keys, schema.comment)
-            }
-            false => (None, Vec::new(), None),
-        };
+                    (
+                        schema.column_defs,
+                        schema.foreign_keys,
+                        schema.checks,
+                        schema.uniques,
+                        schema.comment,
+                        schema.view,
+                        schema.triggers,
+                        schema.sequence,
+                    )
+                }
+                false => (
+                    None,
+                    Vec::new(),
+                    Vec::new(),
+                    Vec::new(),
+                    None,
+                    None,
+                    Vec::new(),
+                    None,
+                ),
+            };
 
         Ok(Some(Schema {
             table_name: table_name.to_owned(),
@@ -78,7 +97,12 @@ impl JsonStorage {
             indexes: vec![],
             engine: None,
             foreign_keys,
+            checks,
+            uniques,
             comment,
+            view,
+            triggers,
+            sequence,
         }))
     }
 
@@ -164,18 +188,12 @@ impl JsonStorage {
                 }
             };
 
-            let mut key: Option<Key> = None;
             let mut values = Vec::with_capacity(column_defs.len());
             for column_def in column_defs {
                 let value = json.get(&column_def.name).map_storage_err(
                     JsonStorageError::ColumnDoesNotExist(column_def.name.clone()),
                 )?;
 
-                if column_def.unique == Some(ColumnUniqueOption { is_primary: true }) {
-                    let value = value.cast(&column_def.data_type)?;
-                    key = Some(value.try_into().map_storage_err()?);
-                }
-
                 let value = match value.get_type() {
                     Some(data_type) if data_type != column_def.data_type => {
                         value.cast(&column_def.data_type)?
@@ -186,7 +204,7 @@ impl JsonStorage {
                 values.push(value);
             }
 
-            let key = match key {
+            let key = match Key::primary(column_defs, &values)? {
                 Some(key) => key,
                 None => get_index_key()?,
             };
@@ -15,10 +15,12 @@ use {
 
 #[async_trait]
 impl Store for JsonStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         self.fetch_schema(table_name)
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let paths = fs::read_dir(&self.path).map_storage_err()?;
         let mut schemas = paths
@@ -46,6 +48,7 @@ impl Store for JsonStorage {
         Ok(schemas)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, target: &Key) -> Result<Option<DataRow>> {
         for item in self.scan_data(table_name)?.0 {
             let (key, row) = item?;
@@ -58,6 +61,7 @@ impl Store for JsonStorage {
         Ok(None)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         let rows = self.scan_data(table_name)?.0;
 
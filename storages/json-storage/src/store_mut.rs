@@ -21,6 +21,7 @@ use {
 
 #[async_trait]
 impl StoreMut for JsonStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let data_path = self.jsonl_path(schema.table_name.as_str());
         File::create(data_path).map_storage_err()?;
@@ -34,6 +35,7 @@ impl StoreMut for JsonStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         let json_path = self.json_path(table_name);
         let jsonl_path = self.jsonl_path(table_name);
@@ -52,6 +54,7 @@ impl StoreMut for JsonStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         let json_path = self.json_path(table_name);
         if json_path.exists() {
@@ -79,6 +82,7 @@ impl StoreMut for JsonStorage {
         }
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, mut rows: Vec<(Key, DataRow)>) -> Result<()> {
         let (prev_rows, schema) = self.scan_data(table_name)?;
         rows.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
@@ -89,6 +93,7 @@ impl StoreMut for JsonStorage {
         self.rewrite(schema, merged)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         let (prev_rows, schema) = self.scan_data(table_name)?;
         let rows = prev_rows
@@ -0,0 +1,53 @@
+use {
+    gluesql_core::{
+        error::Error,
+        prelude::{Glue, Value::*},
+        store::BackupError,
+    },
+    gluesql_json_storage::JsonStorage,
+    std::fs::remove_dir_all,
+    test_suite::{concat_with, row, select, stringify_label},
+};
+
+#[tokio::test]
+async fn json_backup() {
+    let path = "tmp/json_backup";
+    let target_path = "tmp/json_backup_target";
+    let _ = remove_dir_all(path);
+    let _ = remove_dir_all(target_path);
+
+    let mut glue = Glue::new(JsonStorage::new(path).unwrap());
+    glue.execute("CREATE TABLE Foo (id INTEGER, name TEXT);")
+        .await
+        .unwrap();
+    glue.execute("CREATE TABLE Logs;").await.unwrap();
+    glue.execute("INSERT INTO Foo VALUES (1, 'Glue'), (2, 'SQL');")
+        .await
+        .unwrap();
+    glue.execute(r#"INSERT INTO Logs VALUES ('{"id": 1}');"#)
+        .await
+        .unwrap();
+
+    glue.backup(target_path).await.unwrap();
+    assert_eq!(
+        glue.backup(target_path).await,
+        Err(Error::Backup(BackupError::TargetAlreadyExists(
+            target_path.to_owned()
+        )))
+    );
+
+    let mut backup = Glue::new(JsonStorage::new(target_path).unwrap());
+    assert_eq!(
+        backup.execute("SELECT id, name FROM Foo").await,
+        Ok(vec![select!(
+            id  | name;
+            I64 | Str;
+            1     "Glue".to_owned();
+            2     "SQL".to_owned()
+        )])
+    );
+    assert_eq!(
+        backup.execute("SELECT * FROM Logs").await,
+        glue.execute("SELECT * FROM Logs").await
+    );
+}
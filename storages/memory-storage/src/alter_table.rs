@@ -19,6 +19,7 @@ impl AlterTable for MemoryStorage {
 
         new_table_name.clone_into(&mut item.schema.table_name);
         self.items.insert(new_table_name.to_owned(), item);
+        self.statistics.remove(table_name);
 
         Ok(())
     }
@@ -10,9 +10,15 @@ use {
     futures::stream::iter,
     gluesql_core::{
         chrono::Utc,
-        data::{CustomFunction as StructCustomFunction, Key, Schema, Value},
+        data::{
+            CustomFunction as StructCustomFunction, Key, Schema, TableStatistics,
+            User as StructUser, Value,
+        },
         error::Result,
-        store::{CustomFunction, CustomFunctionMut, DataRow, RowIter, Store, StoreMut},
+        store::{
+            CustomFunction, CustomFunctionMut, DataRow, Predicate, RowIter, Store, StoreMut, User,
+            UserMut,
+        },
     },
     serde::{Deserialize, Serialize},
     std::collections::{BTreeMap, HashMap},
@@ -30,6 +36,10 @@ pub struct MemoryStorage {
     pub items: HashMap<String, Item>,
     pub metadata: HashMap<String, BTreeMap<String, Value>>,
     pub functions: HashMap<String, StructCustomFunction>,
+    #[serde(default)]
+    pub users: BTreeMap<String, StructUser>,
+    #[serde(default)]
+    pub statistics: HashMap<String, TableStatistics>,
 }
 
 impl MemoryStorage {
@@ -39,6 +49,47 @@ impl MemoryStorage {
             None => vec![],
         }
     }
+
+    pub fn scan_data_filtered(
+        &self,
+        table_name: &str,
+        predicate: &Predicate,
+    ) -> Vec<(Key, DataRow)> {
+        match self.items.get(table_name) {
+            Some(Item { schema, rows }) => {
+                let column_defs = schema.column_defs.as_deref();
+
+                rows.iter()
+                    .filter(|(_, row)| predicate.check(column_defs, row))
+                    .map(|(key, row)| (key.clone(), row.clone()))
+                    .collect()
+            }
+            None => vec![],
+        }
+    }
+
+    /// Rows of the table split into up to `partitions` runs of about the same length, in the
+    /// order of [`MemoryStorage::scan_data`].
+    pub fn scan_data_partitioned(
+        &self,
+        table_name: &str,
+        predicate: Option<&Predicate>,
+        partitions: usize,
+    ) -> Vec<Vec<(Key, DataRow)>> {
+        let rows = match predicate {
+            Some(predicate) => self.scan_data_filtered(table_name, predicate),
+            None => self.scan_data(table_name),
+        };
+        let size = rows.len().div_ceil(partitions.max(1)).max(1);
+        let mut rows = rows.into_iter().peekable();
+
+        std::iter::from_fn(|| {
+            rows.peek()
+                .is_some()
+                .then(|| rows.by_ref().take(size).collect())
+        })
+        .collect()
+    }
 }
 
 #[async_trait]
@@ -67,8 +118,35 @@ impl CustomFunctionMut for MemoryStorage {
     }
 }
 
+#[async_trait]
+impl User for MemoryStorage {
+    async fn fetch_user(&self, name: &str) -> Result<Option<StructUser>> {
+        Ok(self.users.get(name).cloned())
+    }
+
+    async fn fetch_all_users(&self) -> Result<Vec<StructUser>> {
+        Ok(self.users.values().cloned().collect())
+    }
+}
+
+#[async_trait]
+impl UserMut for MemoryStorage {
+    async fn insert_user(&mut self, user: StructUser) -> Result<()> {
+        self.users.insert(user.name.clone(), user);
+        Ok(())
+    }
+
+    async fn delete_user(&mut self, name: &str) -> Result<()> {
+        self.users.remove(name);
+        Ok(())
+    }
+}
+
+impl gluesql_core::store::Backup for MemoryStorage {}
+
 #[async_trait]
 impl Store for MemoryStorage {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let mut schemas = self
             .items
@@ -79,6 +157,7 @@ impl Store for MemoryStorage {
 
         Ok(schemas)
     }
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         self.items
             .get(table_name)
@@ -86,6 +165,7 @@ impl Store for MemoryStorage {
             .transpose()
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
         let row = self
             .items
@@ -95,6 +175,7 @@ impl Store for MemoryStorage {
         Ok(row)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         let rows = MemoryStorage::scan_data(self, table_name)
             .into_iter()
@@ -102,10 +183,44 @@ impl Store for MemoryStorage {
 
         Ok(Box::pin(iter(rows)))
     }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn scan_data_filtered<'a>(
+        &'a self,
+        table_name: &str,
+        predicate: &Predicate,
+    ) -> Result<RowIter<'a>> {
+        let rows = MemoryStorage::scan_data_filtered(self, table_name, predicate)
+            .into_iter()
+            .map(Ok);
+
+        Ok(Box::pin(iter(rows)))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn scan_data_partitioned<'a>(
+        &'a self,
+        table_name: &str,
+        predicate: Option<&Predicate>,
+        partitions: usize,
+    ) -> Result<Vec<RowIter<'a>>> {
+        let partitions =
+            MemoryStorage::scan_data_partitioned(self, table_name, predicate, partitions)
+                .into_iter()
+                .map(|rows| -> RowIter<'a> { Box::pin(iter(rows.into_iter().map(Ok))) })
+                .collect();
+
+        Ok(partitions)
+    }
+
+    async fn fetch_statistics(&self, table_name: &str) -> Result<Option<TableStatistics>> {
+        Ok(self.statistics.get(table_name).cloned())
+    }
 }
 
 #[async_trait]
 impl StoreMut for MemoryStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let created = BTreeMap::from([(
             "CREATED".to_owned(),
@@ -124,13 +239,16 @@ impl StoreMut for MemoryStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         self.items.remove(table_name);
         self.metadata.remove(table_name);
+        self.statistics.remove(table_name);
 
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         if let Some(item) = self.items.get_mut(table_name) {
             for row in rows {
@@ -143,6 +261,7 @@ impl StoreMut for MemoryStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
         if let Some(item) = self.items.get_mut(table_name) {
             for (key, row) in rows {
@@ -153,6 +272,7 @@ impl StoreMut for MemoryStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         if let Some(item) = self.items.get_mut(table_name) {
             for key in keys {
@@ -162,4 +282,11 @@ impl StoreMut for MemoryStorage {
 
         Ok(())
     }
+
+    async fn insert_statistics(&mut self, statistics: TableStatistics) -> Result<()> {
+        self.statistics
+            .insert(statistics.table_name.clone(), statistics);
+
+        Ok(())
+    }
 }
@@ -21,13 +21,18 @@ impl Tester<MemoryStorage> for MemoryTester {
     }
 }
 
-generate_store_tests!(tokio::test, MemoryTester);
-
-generate_alter_table_tests!(tokio::test, MemoryTester);
-
-generate_metadata_table_tests!(tokio::test, MemoryTester);
-
-generate_custom_function_tests!(tokio::test, MemoryTester);
+generate_conformance_tests!(
+    tokio::test,
+    MemoryTester,
+    [
+        basic,
+        alter_table,
+        metadata,
+        custom_function,
+        user,
+        pushdown
+    ]
+);
 
 macro_rules! exec {
     ($glue: ident $sql: literal) => {
@@ -96,3 +101,529 @@ async fn memory_storage_transaction() {
     test!(glue "COMMIT", Ok(vec![Payload::Commit]));
     test!(glue "ROLLBACK", Ok(vec![Payload::Rollback]));
 }
+
+#[tokio::test]
+async fn memory_storage_audit() {
+    use {
+        gluesql_core::{audit::AuditSink, prelude::Glue},
+        std::sync::{Arc, Mutex},
+    };
+
+    let records = Arc::new(Mutex::new(Vec::new()));
+    let sink = {
+        let records = Arc::clone(&records);
+
+        AuditSink::Callback(Box::new(move |record| {
+            records.lock().unwrap().push(record.clone());
+        }))
+    };
+
+    let storage = MemoryStorage::default();
+    let mut glue = Glue::new(storage).with_audit(sink);
+
+    exec!(glue "CREATE TABLE Audited (id INTEGER);");
+    glue.execute("CREATE USER glue;").await.unwrap();
+    glue.set_user(Some("glue"));
+    assert!(
+        glue.execute("INSERT INTO Audited VALUES (1);")
+            .await
+            .is_err()
+    );
+
+    let records = records.lock().unwrap();
+    let summary = records
+        .iter()
+        .map(|record| {
+            (
+                record.user.as_deref(),
+                record.statement.as_str(),
+                record.tables.clone(),
+                record.error.is_some(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            (None, "CREATE TABLE", vec!["Audited".to_owned()], false),
+            (None, "CREATE USER", vec![], false),
+            (Some("glue"), "INSERT", vec!["Audited".to_owned()], true),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn memory_storage_cache() {
+    use {
+        gluesql_core::{
+            cache::QueryCache,
+            data::Value,
+            prelude::{Glue, Payload},
+            store::{DataRow, StoreMut},
+        },
+        std::time::Duration,
+    };
+
+    macro_rules! rows {
+        ($glue: ident $sql: literal) => {
+            match $glue.execute($sql).await.unwrap().remove(0) {
+                Payload::Select { rows, .. } => rows.len(),
+                payload => panic!("unexpected payload: {payload:?}"),
+            }
+        };
+    }
+
+    let append = |glue: &mut Glue<MemoryStorage>| {
+        let row = DataRow::Vec(vec![Value::I64(10)]);
+
+        futures::executor::block_on(glue.storage.append_data("Cached", vec![row])).unwrap();
+    };
+
+    let storage = MemoryStorage::default();
+    let mut glue = Glue::new(storage).with_cache(QueryCache::new(2));
+
+    exec!(glue "CREATE TABLE Cached (id INTEGER);");
+    exec!(glue "CREATE TABLE Other (id INTEGER);");
+    assert_eq!(rows!(glue "SELECT id FROM Cached"), 0);
+
+    // writes bypassing the glue leave the cached result in place
+    append(&mut glue);
+    assert_eq!(rows!(glue "select id  from Cached"), 0);
+
+    exec!(glue "INSERT INTO Other VALUES (1);");
+    assert_eq!(rows!(glue "SELECT id FROM Cached"), 0);
+
+    exec!(glue "INSERT INTO Cached VALUES (2);");
+    assert_eq!(rows!(glue "SELECT id FROM Cached"), 2);
+
+    // the least recently used entry is evicted once the cache is full
+    exec!(glue "SELECT * FROM Other;");
+    exec!(glue "SELECT id FROM Other;");
+    assert_eq!(glue.cache().map(QueryCache::len), Some(2));
+    append(&mut glue);
+    assert_eq!(rows!(glue "SELECT id FROM Cached"), 3);
+
+    exec!(glue "CREATE TABLE Another (id INTEGER);");
+    assert_eq!(glue.cache().map(QueryCache::len), Some(0));
+
+    // triggers may write any table
+    exec!(glue "CREATE TRIGGER copy AFTER INSERT ON Another BEGIN INSERT INTO Cached VALUES (NEW.id); END;");
+    assert_eq!(rows!(glue "SELECT id FROM Cached"), 3);
+    exec!(glue "INSERT INTO Another VALUES (4);");
+    assert_eq!(rows!(glue "SELECT id FROM Cached"), 4);
+
+    // deletes cascading through foreign keys write the referencing tables
+    exec!(glue "CREATE TABLE Parent (id INTEGER PRIMARY KEY);");
+    exec!(glue "CREATE TABLE Child (id INTEGER, parent_id INTEGER, FOREIGN KEY (parent_id) REFERENCES Parent (id) ON DELETE CASCADE);");
+    exec!(glue "CREATE TABLE Orphan (id INTEGER, parent_id INTEGER NULL, FOREIGN KEY (parent_id) REFERENCES Parent (id) ON DELETE SET NULL);");
+    exec!(glue "INSERT INTO Parent VALUES (1), (2);");
+    exec!(glue "INSERT INTO Child VALUES (1, 1), (2, 2);");
+    exec!(glue "INSERT INTO Orphan VALUES (1, 1);");
+    assert_eq!(rows!(glue "SELECT id FROM Child"), 2);
+    assert_eq!(
+        rows!(glue "SELECT id FROM Orphan WHERE parent_id IS NULL"),
+        0
+    );
+    exec!(glue "DELETE FROM Parent WHERE id = 1;");
+    assert_eq!(rows!(glue "SELECT id FROM Child"), 1);
+    assert_eq!(
+        rows!(glue "SELECT id FROM Orphan WHERE parent_id IS NULL"),
+        1
+    );
+
+    let storage = MemoryStorage::default();
+    let mut glue = Glue::new(storage).with_cache(QueryCache::new(8).with_ttl(Duration::ZERO));
+
+    exec!(glue "CREATE TABLE Cached (id INTEGER);");
+    assert_eq!(rows!(glue "SELECT id FROM Cached"), 0);
+    append(&mut glue);
+    assert_eq!(rows!(glue "SELECT id FROM Cached"), 1);
+
+    let storage = MemoryStorage::default();
+    let mut glue = Glue::new(storage).with_cache(QueryCache::new(8).with_max_rows(1));
+
+    exec!(glue "CREATE TABLE Cached (id INTEGER);");
+    exec!(glue "INSERT INTO Cached VALUES (1), (2);");
+    exec!(glue "SELECT * FROM Cached;");
+    exec!(glue "SELECT id FROM Cached WHERE id = 1;");
+    assert_eq!(glue.cache().map(QueryCache::len), Some(1));
+}
+
+#[tokio::test]
+async fn memory_storage_check() {
+    use gluesql_core::{
+        data::{Key, Value::*},
+        prelude::{Glue, Payload},
+        store::{DataRow, StoreMut},
+    };
+
+    let storage = MemoryStorage::default();
+    let mut glue = Glue::new(storage);
+
+    exec!(glue "CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT);");
+    exec!(glue "CREATE TABLE Log (n INTEGER);");
+    exec!(glue "INSERT INTO Item VALUES (1, 'Glue');");
+
+    let row = |values| DataRow::Vec(values);
+    glue.storage
+        .insert_data(
+            "Item",
+            vec![
+                (Key::I64(2), row(vec![I64(1), Str("SQL".to_owned())])),
+                (Key::I64(3), row(vec![Null, Str("Rust".to_owned())])),
+            ],
+        )
+        .await
+        .unwrap();
+    glue.storage
+        .append_data(
+            "Log",
+            vec![row(vec![Str("one".to_owned())]), row(vec![I64(1), I64(2)])],
+        )
+        .await
+        .unwrap();
+
+    let Payload::Select { rows, .. } = glue.execute("CHECK DATABASE;").await.unwrap().remove(0)
+    else {
+        panic!("CHECK DATABASE should return rows");
+    };
+    let errors = rows
+        .into_iter()
+        .filter(|row| row[2] == Str("ERROR".to_owned()))
+        .map(|row| {
+            (
+                String::from(&row[0]),
+                String::from(&row[1]),
+                String::from(&row[3]),
+            )
+        })
+        .collect::<Vec<_>>();
+    let error = |table: &str, check: &str, detail: &str| {
+        (table.to_owned(), check.to_owned(), detail.to_owned())
+    };
+
+    assert_eq!(
+        errors,
+        vec![
+            error("Item", "ROW_TYPE", r#"row 3: column "id" is NULL"#),
+            error("Item", "PRIMARY_KEY", "row 3: primary key is NULL"),
+            error("Item", "PRIMARY_KEY", "primary key 1 is used by 2 rows"),
+            error(
+                "Log",
+                "ROW_TYPE",
+                r#"row 1: column "n" holds TEXT instead of INT"#
+            ),
+            error("Log", "ROW_TYPE", "row 2: 2 values for 1 columns"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn memory_storage_parallel() {
+    use gluesql_core::{
+        data::Value::I64,
+        prelude::{Glue, Payload},
+    };
+
+    let mut glue = Glue::new(MemoryStorage::default());
+
+    exec!(glue "CREATE TABLE Item (id INTEGER, kind INTEGER, price INTEGER NULL, name TEXT);");
+    exec!(glue "
+        INSERT INTO Item
+        SELECT
+            N,
+            N % 7,
+            CASE WHEN N % 5 = 0 THEN NULL ELSE N % 100 END,
+            CASE N % 3 WHEN 0 THEN 'a' WHEN 1 THEN 'b' ELSE 'c' END
+        FROM SERIES(5000);
+    ");
+
+    let mut sequential = Glue::new(glue.storage.clone());
+    let mut parallel = Glue::new(glue.storage).with_parallelism(4);
+
+    test!(
+        parallel "SELECT COUNT(*), SUM(id) FROM Item",
+        Ok(vec![Payload::Select {
+            labels: vec!["COUNT(*)".to_owned(), "SUM(id)".to_owned()],
+            rows: vec![vec![I64(5000), I64(12502500)]],
+        }])
+    );
+
+    for sql in [
+        "SELECT kind, COUNT(*), SUM(price), MIN(price), MAX(price), AVG(price) FROM Item GROUP BY kind",
+        // the second batch of rows starts with a NULL price
+        "SELECT MIN(price), MAX(price), COUNT(price) FROM Item",
+        "SELECT COUNT(DISTINCT price), SUM(DISTINCT kind), VARIANCE(price), STDEV(price) FROM Item WHERE id > 100",
+        "SELECT name, kind, COUNT(*) AS count FROM Item GROUP BY name, kind HAVING COUNT(*) > 238 ORDER BY count DESC, name",
+        "SELECT kind, SUM(price) * 2 + MAX(id) FROM Item WHERE price IS NOT NULL GROUP BY kind",
+        "SELECT kind FROM Item WHERE price IS NULL GROUP BY kind",
+    ] {
+        assert_eq!(
+            parallel.execute(sql).await,
+            sequential.execute(sql).await,
+            "{sql}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn memory_storage_parallel_scan() {
+    use {
+        futures::stream::{self, StreamExt},
+        gluesql_core::{
+            data::{Key, Schema, Value::I64},
+            error::Result,
+            prelude::{Glue, Payload},
+            store::{
+                AlterTable, Backup, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut,
+                Metadata, Predicate, RowIter, Store, StoreMut, Transaction, User, UserMut,
+            },
+        },
+        std::{sync::Arc, time::Duration},
+        tokio::{sync::Barrier, time::timeout},
+    };
+
+    /// Memory storage whose partitions each wait for all of them to be read before giving
+    /// their rows, so a query only finishes when its partitions are read side by side.
+    struct BarrierStorage {
+        storage: MemoryStorage,
+        barrier: Arc<Barrier>,
+    }
+
+    #[async_trait]
+    impl Store for BarrierStorage {
+        async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
+            self.storage.fetch_schema(table_name).await
+        }
+
+        async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
+            self.storage.fetch_all_schemas().await
+        }
+
+        async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
+            self.storage.fetch_data(table_name, key).await
+        }
+
+        async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
+            Store::scan_data(&self.storage, table_name).await
+        }
+
+        async fn scan_data_partitioned<'a>(
+            &'a self,
+            table_name: &str,
+            predicate: Option<&Predicate>,
+            partitions: usize,
+        ) -> Result<Vec<RowIter<'a>>> {
+            let partitions =
+                Store::scan_data_partitioned(&self.storage, table_name, predicate, partitions)
+                    .await?
+                    .into_iter()
+                    .map(|rows| -> RowIter<'a> {
+                        let barrier = Arc::clone(&self.barrier);
+                        let wait = stream::once(async move {
+                            barrier.wait().await;
+                        })
+                        .filter_map(|_| async { None });
+
+                        Box::pin(wait.chain(rows))
+                    })
+                    .collect();
+
+            Ok(partitions)
+        }
+    }
+
+    impl Index for BarrierStorage {}
+    impl IndexMut for BarrierStorage {}
+    impl Metadata for BarrierStorage {}
+    impl CustomFunction for BarrierStorage {}
+    impl CustomFunctionMut for BarrierStorage {}
+    impl User for BarrierStorage {}
+    impl UserMut for BarrierStorage {}
+    impl Backup for BarrierStorage {}
+    impl StoreMut for BarrierStorage {}
+    impl AlterTable for BarrierStorage {}
+    impl Transaction for BarrierStorage {}
+
+    let mut glue = Glue::new(MemoryStorage::default());
+
+    exec!(glue "CREATE TABLE Item (id INTEGER, kind INTEGER);");
+    exec!(glue "INSERT INTO Item SELECT N, N % 3 FROM SERIES(5000);");
+
+    let storage = BarrierStorage {
+        storage: glue.storage,
+        barrier: Arc::new(Barrier::new(4)),
+    };
+    let mut glue = Glue::new(storage).with_parallelism(4);
+    let sql = "SELECT kind, COUNT(*), SUM(id) FROM Item GROUP BY kind";
+
+    assert_eq!(
+        timeout(Duration::from_secs(10), glue.execute(sql))
+            .await
+            .expect("partitions are not read side by side"),
+        Ok(vec![Payload::Select {
+            labels: vec![
+                "kind".to_owned(),
+                "COUNT(*)".to_owned(),
+                "SUM(id)".to_owned()
+            ],
+            rows: vec![
+                vec![I64(1), I64(1667), I64(4167500)],
+                vec![I64(2), I64(1667), I64(4169167)],
+                vec![I64(0), I64(1666), I64(4165833)],
+            ],
+        }])
+    );
+}
+
+#[tokio::test]
+async fn memory_storage_spilled_sort() {
+    use gluesql_core::{data::Value::I64, prelude::Glue};
+
+    let mut glue = Glue::new(MemoryStorage::default());
+
+    exec!(glue "CREATE TABLE Item (id INTEGER, kind INTEGER);");
+    // more rows than ORDER BY sorts in memory, which are sorted in runs spilled to files
+    exec!(glue "INSERT INTO Item SELECT N, N % 3 FROM SERIES(100000);");
+
+    let payloads = glue
+        .execute("SELECT id, kind FROM Item ORDER BY kind DESC")
+        .await
+        .unwrap();
+    let rows = payloads[0]
+        .select()
+        .unwrap()
+        .map(|row| (row["id"].clone(), row["kind"].clone()))
+        .collect::<Vec<_>>();
+    let expected = [2, 1, 0]
+        .into_iter()
+        .flat_map(|kind| (1..=100000).filter(move |id| id % 3 == kind))
+        .map(|id| (I64(id), I64(id % 3)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(rows, expected);
+}
+
+#[tokio::test]
+async fn memory_storage_prepared() {
+    use {
+        gluesql_core::{
+            data::Value::{Decimal, I64, Null, Str},
+            prelude::{Glue, Payload},
+            prepared::PreparedError,
+        },
+        std::str::FromStr,
+    };
+
+    let mut glue = Glue::new(MemoryStorage::default());
+
+    exec!(glue "CREATE TABLE Item (id INTEGER, name TEXT NULL);");
+
+    let insert = glue.prepare("INSERT INTO Item VALUES ($1, $2)").unwrap();
+    assert_eq!(insert.params(), 2);
+    for params in [
+        [I64(1), Str("Glue".to_owned())],
+        [I64(2), Str("it's'); DROP TABLE Item; --".to_owned())],
+        [I64(3), Null],
+    ] {
+        assert_eq!(
+            glue.execute_with_params(&insert, &params).await,
+            Ok(vec![Payload::Insert(1)])
+        );
+    }
+
+    let select = glue
+        .prepare("SELECT id, name FROM Item WHERE id >= ? AND id < ? ORDER BY id")
+        .unwrap();
+    assert_eq!(
+        glue.execute_with_params(&select, &[I64(2), I64(4)]).await,
+        Ok(vec![Payload::Select {
+            labels: vec!["id".to_owned(), "name".to_owned()],
+            rows: vec![
+                vec![I64(2), Str("it's'); DROP TABLE Item; --".to_owned())],
+                vec![I64(3), Null],
+            ],
+        }])
+    );
+    assert_eq!(
+        glue.execute_with_params(&select, &[I64(1)]).await,
+        Err(PreparedError::ParamCountMismatch {
+            expected: 2,
+            found: 1
+        }
+        .into())
+    );
+
+    test!(
+        glue "SELECT COUNT(*) FROM Item",
+        Ok(vec![Payload::Select {
+            labels: vec!["COUNT(*)".to_owned()],
+            rows: vec![vec![I64(3)]],
+        }])
+    );
+
+    exec!(glue "CREATE TABLE Price (amount DECIMAL);");
+
+    let amount =
+        Decimal(rust_decimal::Decimal::from_str("12345678901234.567890123456789").unwrap());
+    let insert = glue.prepare("INSERT INTO Price VALUES ($1)").unwrap();
+    assert_eq!(
+        glue.execute_with_params(&insert, std::slice::from_ref(&amount))
+            .await,
+        Ok(vec![Payload::Insert(1)])
+    );
+
+    let select = glue
+        .prepare("SELECT amount, $1 AS param FROM Price WHERE amount = $1")
+        .unwrap();
+    assert_eq!(
+        glue.execute_with_params(&select, std::slice::from_ref(&amount))
+            .await,
+        Ok(vec![Payload::Select {
+            labels: vec!["amount".to_owned(), "param".to_owned()],
+            rows: vec![vec![amount.clone(), amount]],
+        }])
+    );
+}
+
+#[tokio::test]
+async fn memory_storage_statement_cache() {
+    use gluesql_core::{
+        cache::{CacheStats, StatementCache},
+        data::Value::I64,
+        prelude::{Glue, Payload},
+    };
+
+    let select = |id| Payload::Select {
+        labels: vec!["id".to_owned()],
+        rows: vec![vec![I64(id)]],
+    };
+
+    for cache in [StatementCache::new(2), StatementCache::new(2).with_plans()] {
+        let mut glue = Glue::new(MemoryStorage::default()).with_statement_cache(cache);
+
+        exec!(glue "CREATE TABLE Item (id INTEGER);");
+        exec!(glue "INSERT INTO Item VALUES (1);");
+        test!(glue "SELECT id FROM Item", Ok(vec![select(1)]));
+        exec!(glue "INSERT INTO Item VALUES (2);");
+        test!(glue "SELECT id FROM Item WHERE id = 2", Ok(vec![select(2)]));
+        test!(glue "SELECT id FROM Item WHERE id = 2", Ok(vec![select(2)]));
+
+        // plans are dropped once the schema changes
+        exec!(glue "ALTER TABLE Item RENAME COLUMN id TO num;");
+        test!(
+            glue "SELECT num AS id FROM Item WHERE num = 2",
+            Ok(vec![select(2)])
+        );
+
+        let plans = glue.statement_cache().is_some_and(StatementCache::plans);
+        assert_eq!(
+            glue.statement_cache().map(StatementCache::len),
+            Some(if plans { 1 } else { 2 })
+        );
+        assert_eq!(
+            glue.statement_cache().map(StatementCache::stats),
+            Some(CacheStats { hits: 1, misses: 7 })
+        );
+    }
+}
@@ -1,12 +1,25 @@
 use {
-    gluesql_core::ast::{Expr, ForeignKey},
+    gluesql_core::{
+        ast::{CheckConstraint, Expr, ForeignKey, UniqueConstraint},
+        data::{SchemaSequence, SchemaTrigger, SchemaView},
+    },
     serde::{Deserialize, Serialize},
 };
 
 #[derive(Serialize, Deserialize)]
 pub struct TableDescription {
     pub foreign_keys: Vec<ForeignKey>,
+    #[serde(default)]
+    pub checks: Vec<CheckConstraint>,
+    #[serde(default)]
+    pub uniques: Vec<UniqueConstraint>,
     pub comment: Option<String>,
+    #[serde(default)]
+    pub view: Option<SchemaView>,
+    #[serde(default)]
+    pub triggers: Vec<SchemaTrigger>,
+    #[serde(default)]
+    pub sequence: Option<SchemaSequence>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -38,6 +38,9 @@ impl Metadata for MongoStorage {}
 impl AlterTable for MongoStorage {}
 impl CustomFunction for MongoStorage {}
 impl CustomFunctionMut for MongoStorage {}
+impl gluesql_core::store::User for MongoStorage {}
+impl gluesql_core::store::UserMut for MongoStorage {}
+impl gluesql_core::store::Backup for MongoStorage {}
 impl Index for MongoStorage {}
 impl IndexMut for MongoStorage {}
 impl Transaction for MongoStorage {}
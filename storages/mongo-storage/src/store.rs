@@ -4,7 +4,7 @@ use {
         description::{ColumnDescription, TableDescription},
         error::{MongoStorageError, OptionExt, ResultExt},
         row::{IntoRow, key::KeyIntoBson, value::IntoValue},
-        utils::get_primary_key,
+        utils::{get_primary_key, predicate_filter, primary_key_sort},
     },
     async_trait::async_trait,
     futures::{Stream, StreamExt, TryStreamExt, stream},
@@ -14,7 +14,7 @@ use {
         error::Result,
         parse_sql::parse_data_type,
         prelude::{Error, Value},
-        store::{DataRow, RowIter, Store},
+        store::{DataRow, Predicate, RowIter, Store},
         translate::translate_data_type,
     },
     mongodb::{
@@ -31,6 +31,7 @@ use {
 
 #[async_trait]
 impl Store for MongoStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         self.fetch_schemas_iter(Some(table_name))
             .await?
@@ -39,6 +40,7 @@ impl Store for MongoStorage {
             .transpose()
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let mut schemas = self
             .fetch_schemas_iter(None)
@@ -51,13 +53,14 @@ impl Store for MongoStorage {
         Ok(schemas)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, target: &Key) -> Result<Option<DataRow>> {
         let column_defs = self
             .get_column_defs(table_name)
             .await?
             .map_storage_err(MongoStorageError::ConflictFetchData)?;
 
-        let primary_key = get_primary_key(&column_defs)
+        get_primary_key(&column_defs)
             .ok_or(MongoStorageError::Unreachable)
             .map_storage_err()?;
 
@@ -65,7 +68,7 @@ impl Store for MongoStorage {
         let projection = doc! {"_id": 0};
         let options = FindOptions::builder()
             .projection(projection)
-            .sort(doc! { primary_key.name.clone(): 1 })
+            .sort(primary_key_sort(&column_defs))
             .build();
 
         let mut cursor = self
@@ -92,7 +95,29 @@ impl Store for MongoStorage {
             .transpose()
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
+        self.scan_documents(table_name, Document::new()).await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn scan_data_filtered<'a>(
+        &'a self,
+        table_name: &str,
+        predicate: &Predicate,
+    ) -> Result<RowIter<'a>> {
+        let filter = predicate_filter(predicate).unwrap_or_default();
+
+        self.scan_documents(table_name, filter).await
+    }
+}
+
+impl MongoStorage {
+    async fn scan_documents<'a>(
+        &'a self,
+        table_name: &str,
+        filter: Document,
+    ) -> Result<RowIter<'a>> {
         let column_defs = self.get_column_defs(table_name).await?;
 
         let primary_key = column_defs
@@ -102,15 +127,15 @@ impl Store for MongoStorage {
         let has_primary = primary_key.is_some();
 
         let options = FindOptions::builder();
-        let options = match primary_key {
-            Some(primary_key) => options.sort(doc! { primary_key.name.to_owned(): 1}).build(),
-            None => options.build(),
+        let options = match (primary_key, &column_defs) {
+            (Some(_), Some(column_defs)) => options.sort(primary_key_sort(column_defs)).build(),
+            _ => options.build(),
         };
 
         let cursor = self
             .db
             .collection::<Document>(table_name)
-            .find(Document::new(), options)
+            .find(filter, options)
             .await
             .map_storage_err()?;
 
@@ -150,9 +175,7 @@ impl Store for MongoStorage {
 
         Ok(Box::pin(row_iter))
     }
-}
 
-impl MongoStorage {
     async fn fetch_schemas_iter<'a>(
         &'a self,
         table_name: Option<&'a str>,
@@ -277,7 +300,12 @@ impl MongoStorage {
             let table_description = validator.get_str("description").map_storage_err()?;
             let TableDescription {
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view,
+                triggers,
+                sequence,
             } = from_str::<TableDescription>(table_description).map_storage_err()?;
 
             let schema = Schema {
@@ -286,7 +314,12 @@ impl MongoStorage {
                 indexes: Vec::new(),
                 engine: None,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view,
+                triggers,
+                sequence,
             };
 
             Ok::<_, Error>(schema)
@@ -1,7 +1,7 @@
 use {
     crate::{
         MongoStorage,
-        description::ColumnDescription,
+        description::{ColumnDescription, TableDescription},
         error::{MongoStorageError, OptionExt, ResultExt},
         row::{
             data_type::{BsonType, IntoRange},
@@ -25,7 +25,7 @@ use {
 
 struct IndexInfo {
     name: String,
-    key: String,
+    keys: Vec<String>,
     index_type: IndexType,
 }
 
@@ -36,6 +36,7 @@ enum IndexType {
 
 #[async_trait]
 impl StoreMut for MongoStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let (labels, column_types, indexes) = schema
             .column_defs
@@ -59,17 +60,29 @@ impl StoreMut for MongoStorage {
                         if let Some(ColumnUniqueOption { is_primary }) = &column_def.unique {
                             match *is_primary {
                                 true => {
-                                    indexes.push(IndexInfo {
-                                        name: format!("{column_name}_PK"),
-                                        key: column_name.clone(),
-                                        index_type: IndexType::Primary,
-                                    });
+                                    // columns of a composite primary key share a compound index
+                                    let primary =
+                                        indexes.iter_mut().find(|index: &&mut IndexInfo| {
+                                            matches!(index.index_type, IndexType::Primary)
+                                        });
+
+                                    match primary {
+                                        Some(index) => {
+                                            index.keys.push(column_name.clone());
+                                            index.name = format!("{}_PK", index.keys.join("_"));
+                                        }
+                                        None => indexes.push(IndexInfo {
+                                            name: format!("{column_name}_PK"),
+                                            keys: vec![column_name.clone()],
+                                            index_type: IndexType::Primary,
+                                        }),
+                                    }
                                 }
                                 false => {
                                     bson_type = vec![data_type, "null"];
                                     indexes.push(IndexInfo {
                                         name: format!("{column_name}_UNIQUE"),
-                                        key: column_name.clone(),
+                                        keys: vec![column_name.clone()],
                                         index_type: IndexType::Unique,
                                     });
                                 }
@@ -121,8 +134,16 @@ impl StoreMut for MongoStorage {
             .transpose()?
             .unwrap_or_default();
 
-        let comment = schema.comment.as_ref().map(ToOwned::to_owned);
-        let validator = Validator::new(labels, column_types, schema.foreign_keys.clone(), comment)?;
+        let table_description = TableDescription {
+            foreign_keys: schema.foreign_keys.clone(),
+            checks: schema.checks.clone(),
+            uniques: schema.uniques.clone(),
+            comment: schema.comment.clone(),
+            view: schema.view.clone(),
+            triggers: schema.triggers.clone(),
+            sequence: schema.sequence,
+        };
+        let validator = Validator::new(labels, column_types, table_description)?;
 
         let schema_exists = self
             .fetch_schema(&schema.table_name)
@@ -158,7 +179,7 @@ impl StoreMut for MongoStorage {
             .map(
                 |IndexInfo {
                      name,
-                     key,
+                     keys,
                      index_type,
                  }| {
                     let index_options = IndexOptions::builder().unique(true);
@@ -166,14 +187,14 @@ impl StoreMut for MongoStorage {
                         IndexType::Primary => index_options.name(name).build(),
                         IndexType::Unique => index_options
                             .partial_filter_expression(
-                                doc! { "partialFilterExpression": { key.clone(): { "$ne": null } } }, 
+                                doc! { "partialFilterExpression": { keys[0].clone(): { "$ne": null } } }, 
                             )
                             .name(name)
                             .build(),
                     };
 
                     mongodb::IndexModel::builder()
-                        .keys(doc! {key: 1})
+                        .keys(keys.into_iter().map(|key| (key, Bson::Int32(1))).collect::<Document>())
                         .options(index_options)
                         .build()
                 },
@@ -188,6 +209,7 @@ impl StoreMut for MongoStorage {
             .map_storage_err()
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         self.db
             .collection::<Document>(table_name)
@@ -196,6 +218,7 @@ impl StoreMut for MongoStorage {
             .map_storage_err()
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         let column_defs = self.get_column_defs(table_name).await?;
 
@@ -238,6 +261,7 @@ impl StoreMut for MongoStorage {
             .map_storage_err()
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
         let column_defs = self.get_column_defs(table_name).await?;
 
@@ -283,6 +307,7 @@ impl StoreMut for MongoStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         let column_defs = self.get_column_defs(table_name).await?;
         let primary_key = column_defs
@@ -1,9 +1,11 @@
 use {
-    crate::{description::TableDescription, error::ResultExt},
-    bson::{Document, doc},
+    crate::{description::TableDescription, error::ResultExt, row::value::IntoBson},
+    bson::{Bson, Document, doc},
     gluesql_core::{
-        ast::{ColumnDef, ForeignKey},
+        ast::ColumnDef,
         error::Result,
+        prelude::Value,
+        store::{CompareOperator, Predicate},
     },
     mongodb::options::CreateCollectionOptions,
     serde_json::to_string,
@@ -15,6 +17,94 @@ pub fn get_primary_key(column_defs: &[ColumnDef]) -> Option<&ColumnDef> {
         .find(|column_def| column_def.unique.map(|x| x.is_primary).unwrap_or(false))
 }
 
+/// Sorts rows by the columns of the primary key, in the order of the table.
+pub fn primary_key_sort(column_defs: &[ColumnDef]) -> Document {
+    column_defs
+        .iter()
+        .filter(|column_def| column_def.unique.map(|x| x.is_primary).unwrap_or(false))
+        .map(|column_def| (column_def.name.clone(), Bson::Int32(1)))
+        .collect()
+}
+
+/// Filter of `find` for the predicate, `None` when no part of it translates. Parts of `AND`
+/// which do not translate are left out, which MongoDB may match more rows for but never
+/// fewer, as GlueSQL checks the rows returned again.
+pub fn predicate_filter(predicate: &Predicate) -> Option<Document> {
+    let field = |column: &str| {
+        let plain = column != "_id" && !column.starts_with('$') && !column.contains('.');
+
+        plain.then(|| column.to_owned())
+    };
+
+    match predicate {
+        Predicate::Compare { column, op, value } => {
+            let field = field(column)?;
+            let op = match op {
+                CompareOperator::Eq => "$eq",
+                CompareOperator::NotEq => "$ne",
+                CompareOperator::Lt => "$lt",
+                CompareOperator::LtEq => "$lte",
+                CompareOperator::Gt => "$gt",
+                CompareOperator::GtEq => "$gte",
+            };
+            let value = predicate_value(value)?;
+
+            Some(doc! { field: { op: value } })
+        }
+        Predicate::IsNull(column) => {
+            let field = field(column)?;
+
+            Some(doc! { field: Bson::Null })
+        }
+        Predicate::IsNotNull(column) => {
+            let field = field(column)?;
+
+            Some(doc! { field: { "$ne": Bson::Null } })
+        }
+        Predicate::In { column, values } => {
+            let field = field(column)?;
+            let values = values
+                .iter()
+                .map(predicate_value)
+                .collect::<Option<Vec<_>>>()?;
+
+            Some(doc! { field: { "$in": values } })
+        }
+        Predicate::And(predicates) => {
+            let filters = predicates
+                .iter()
+                .filter_map(predicate_filter)
+                .collect::<Vec<_>>();
+
+            (!filters.is_empty()).then(|| doc! { "$and": filters })
+        }
+        Predicate::Or(predicates) => {
+            let filters = predicates
+                .iter()
+                .map(predicate_filter)
+                .collect::<Option<Vec<_>>>()?;
+
+            Some(doc! { "$or": filters })
+        }
+    }
+}
+
+/// Values MongoDB compares in the order GlueSQL does: numbers whatever their bson type,
+/// booleans, and strings byte by byte.
+fn predicate_value(value: &Value) -> Option<Bson> {
+    match value {
+        Value::I8(_)
+        | Value::I16(_)
+        | Value::I32(_)
+        | Value::I64(_)
+        | Value::F32(_)
+        | Value::F64(_)
+        | Value::Bool(_)
+        | Value::Str(_) => value.clone().into_bson().ok(),
+        _ => None,
+    }
+}
+
 pub struct Validator {
     pub document: Document,
 }
@@ -23,8 +113,7 @@ impl Validator {
     pub fn new(
         labels: Vec<String>,
         column_types: Document,
-        foreign_keys: Vec<ForeignKey>,
-        comment: Option<String>,
+        table_description: TableDescription,
     ) -> Result<Self> {
         let mut required = vec!["_id".to_owned()];
         required.extend(labels);
@@ -35,13 +124,7 @@ impl Validator {
         properties.extend(column_types);
 
         let additional_properties = matches!(required.len(), 1);
-        let table_description = to_string(
-            &(TableDescription {
-                foreign_keys,
-                comment,
-            }),
-        )
-        .map_storage_err()?;
+        let table_description = to_string(&table_description).map_storage_err()?;
 
         let document = doc! {
             "$jsonSchema": {
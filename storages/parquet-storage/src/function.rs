@@ -1,7 +1,10 @@
 use {
     super::ParquetStorage,
-    gluesql_core::store::{CustomFunction, CustomFunctionMut},
+    gluesql_core::store::{Backup, CustomFunction, CustomFunctionMut, User, UserMut},
 };
 
 impl CustomFunctionMut for ParquetStorage {}
 impl CustomFunction for ParquetStorage {}
+impl User for ParquetStorage {}
+impl UserMut for ParquetStorage {}
+impl Backup for ParquetStorage {}
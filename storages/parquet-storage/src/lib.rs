@@ -2,8 +2,8 @@ use {
     column_def::ParquetSchemaType,
     error::{OptionExt, ParquetStorageError, ResultExt},
     gluesql_core::{
-        ast::{ColumnDef, ColumnUniqueOption, ForeignKey},
-        data::Schema,
+        ast::{CheckConstraint, ColumnDef, ForeignKey, UniqueConstraint},
+        data::{Schema, SchemaSequence, SchemaTrigger, SchemaView},
         error::{Error, Result},
         prelude::{DataType, Key, Value},
         store::{DataRow, Metadata},
@@ -62,12 +62,55 @@ impl ParquetStorage {
         let mut is_schemaless = false;
         let mut foreign_keys = Vec::new();
         let mut comment = None;
+        let mut view = None;
+        let mut triggers = Vec::new();
+        let mut sequence = None;
+        let mut checks = Vec::new();
+        let mut uniques = Vec::new();
         if let Some(metadata) = key_value_file_metadata {
             for kv in metadata.iter() {
                 if kv.key == "schemaless" {
                     is_schemaless = matches!(kv.value.as_deref(), Some("true"));
                 } else if kv.key == "comment" {
                     comment.clone_from(&kv.value)
+                } else if kv.key == "view" {
+                    view = kv
+                        .value
+                        .as_deref()
+                        .map(from_str::<SchemaView>)
+                        .transpose()
+                        .map_storage_err()?;
+                } else if kv.key == "triggers" {
+                    triggers = kv
+                        .value
+                        .as_deref()
+                        .map(from_str::<Vec<SchemaTrigger>>)
+                        .transpose()
+                        .map_storage_err()?
+                        .unwrap_or_default();
+                } else if kv.key == "checks" {
+                    checks = kv
+                        .value
+                        .as_deref()
+                        .map(from_str::<Vec<CheckConstraint>>)
+                        .transpose()
+                        .map_storage_err()?
+                        .unwrap_or_default();
+                } else if kv.key == "uniques" {
+                    uniques = kv
+                        .value
+                        .as_deref()
+                        .map(from_str::<Vec<UniqueConstraint>>)
+                        .transpose()
+                        .map_storage_err()?
+                        .unwrap_or_default();
+                } else if kv.key == "sequence" {
+                    sequence = kv
+                        .value
+                        .as_deref()
+                        .map(from_str::<SchemaSequence>)
+                        .transpose()
+                        .map_storage_err()?;
                 } else if kv.key.starts_with("foreign_key") {
                     let fk = kv
                         .value
@@ -106,7 +149,12 @@ impl ParquetStorage {
             indexes: vec![],
             engine: None,
             foreign_keys,
+            checks,
+            uniques,
             comment,
+            view,
+            triggers,
+            sequence,
         }))
     }
 
@@ -138,17 +186,14 @@ impl ParquetStorage {
             for record in row_iter {
                 let record: Row = record.map_storage_err()?;
                 let mut row = Vec::new();
-                let mut key = None;
 
                 for (idx, (_, field)) in record.get_column_iter().enumerate() {
                     let value = ParquetField(field.clone()).to_value(&fetched_schema, idx)?;
-                    row.push(value.clone());
-
-                    if column_defs[idx].unique == Some(ColumnUniqueOption { is_primary: true }) {
-                        key = Key::try_from(&value).ok();
-                    }
+                    row.push(value);
                 }
 
+                let key = Key::primary(column_defs, &row).ok().flatten();
+
                 let generated_key = key.unwrap_or_else(|| {
                     let generated = Key::U64(key_counter);
                     key_counter += 1;
@@ -192,7 +237,12 @@ impl ParquetStorage {
             indexes: vec![],
             engine: None,
             foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            uniques: Vec::new(),
             comment: None,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
         }
     }
 }
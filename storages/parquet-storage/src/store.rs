@@ -15,10 +15,12 @@ use {
 
 #[async_trait]
 impl Store for ParquetStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         self.fetch_schema(table_name)
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let paths = fs::read_dir(&self.path).map_storage_err()?;
         let mut schemas = paths
@@ -43,6 +45,7 @@ impl Store for ParquetStorage {
         Ok(schemas)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, target: &Key) -> Result<Option<DataRow>> {
         for item in self.scan_data(table_name)?.0 {
             let (key, row) = item?;
@@ -54,6 +57,7 @@ impl Store for ParquetStorage {
         return Ok(None);
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         let rows = self.scan_data(table_name)?.0;
         Ok(Box::pin(iter(rows)))
@@ -62,6 +62,7 @@ lazy_static! {
 
 #[async_trait]
 impl StoreMut for ParquetStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let data_path = self.data_path(schema.table_name.as_str());
         let file = File::create(data_path).map_storage_err()?;
@@ -70,6 +71,7 @@ impl StoreMut for ParquetStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         let schema_path = self.data_path(table_name);
         if schema_path.exists() {
@@ -78,6 +80,7 @@ impl StoreMut for ParquetStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         let schema_path = self.data_path(table_name);
         let (prev_rows, schema) = self.scan_data(table_name)?;
@@ -91,6 +94,7 @@ impl StoreMut for ParquetStorage {
         self.write(schema, rows, file)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, mut rows: Vec<(Key, DataRow)>) -> Result<()> {
         let (prev_rows, schema) = self.scan_data(table_name)?;
 
@@ -101,6 +105,7 @@ impl StoreMut for ParquetStorage {
         self.rewrite(schema, merged)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         let (prev_rows, schema) = self.scan_data(table_name)?;
         let rows = prev_rows
@@ -512,6 +517,41 @@ impl ParquetStorage {
             });
         }
 
+        if let Some(view) = &schema.view {
+            metadata.push(KeyValue {
+                key: "view".to_owned(),
+                value: Some(serde_json::to_string(view).map_storage_err()?),
+            });
+        }
+
+        if let Some(sequence) = &schema.sequence {
+            metadata.push(KeyValue {
+                key: "sequence".to_owned(),
+                value: Some(serde_json::to_string(sequence).map_storage_err()?),
+            });
+        }
+
+        if !schema.checks.is_empty() {
+            metadata.push(KeyValue {
+                key: "checks".to_owned(),
+                value: Some(serde_json::to_string(&schema.checks).map_storage_err()?),
+            });
+        }
+
+        if !schema.uniques.is_empty() {
+            metadata.push(KeyValue {
+                key: "uniques".to_owned(),
+                value: Some(serde_json::to_string(&schema.uniques).map_storage_err()?),
+            });
+        }
+
+        if !schema.triggers.is_empty() {
+            metadata.push(KeyValue {
+                key: "triggers".to_owned(),
+                value: Some(serde_json::to_string(&schema.triggers).map_storage_err()?),
+            });
+        }
+
         if schema.comment.is_some() {
             metadata.push(KeyValue {
                 key: "comment".to_owned(),
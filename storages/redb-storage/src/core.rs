@@ -4,10 +4,10 @@ use {
     bincode::{deserialize, serialize},
     futures::stream::iter,
     gluesql_core::{
-        data::{Key, Schema},
+        data::{Key, LegacySchema, Schema},
         store::{DataRow, RowIter},
     },
-    redb::{Database, ReadableTable, TableDefinition, WriteTransaction},
+    redb::{Database, ReadableTable, TableDefinition, TableError, WriteTransaction},
     std::path::Path,
     uuid::Uuid,
 };
@@ -15,14 +15,16 @@ use {
 const SCHEMA_TABLE_NAME: &str = "__SCHEMA__";
 const SCHEMA_TABLE: TableDefinition<&str, Vec<u8>> = TableDefinition::new(SCHEMA_TABLE_NAME);
 
+/// Header of the schemas written with the current layout of [`Schema`], ending with its
+/// version. Schemas without it were written as [`LegacySchema`], which starts with the length
+/// of the table name and so never with the header.
+const SCHEMA_HEADER: &[u8] = b"GLUESQL_SCHEMA/1";
+
 type Result<T> = std::result::Result<T, StorageError>;
 
 pub enum TransactionState {
     None,
-    Active {
-        txn: Box<WriteTransaction>,
-        autocommit: bool,
-    },
+    Active { txn: Box<WriteTransaction> },
 }
 
 pub struct StorageCore {
@@ -90,8 +92,7 @@ impl StorageCore {
             .iter()?
             .map(|entry| {
                 let value = entry?.1.value();
-                let schema = deserialize(&value)?;
-                Ok(schema)
+                deserialize_schema(&value)
             })
             .collect()
     }
@@ -101,13 +102,13 @@ impl StorageCore {
             TransactionState::Active { txn, .. } => txn
                 .open_table(SCHEMA_TABLE)?
                 .get(table_name)?
-                .map(|v| deserialize(&v.value())),
+                .map(|v| deserialize_schema(&v.value())),
             TransactionState::None => self
                 .db
                 .begin_write()?
                 .open_table(SCHEMA_TABLE)?
                 .get(table_name)?
-                .map(|v| deserialize(&v.value())),
+                .map(|v| deserialize_schema(&v.value())),
         }
         .transpose()?;
 
@@ -131,23 +132,22 @@ impl StorageCore {
     }
 
     pub fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
-        if let TransactionState::Active { autocommit, txn } = &self.state {
-            if !autocommit {
-                let table_def = self.data_table_def(table_name)?;
-                let table = txn.open_table(table_def)?;
-
-                let rows: Vec<_> = table
-                    .iter()?
-                    .map(|entry| {
-                        let value = entry?.1.value();
-                        let (key, row): (Key, DataRow) = deserialize(&value)?;
-
-                        Ok((key, row))
-                    })
-                    .collect::<Result<_>>()?;
-
-                return Ok(Box::pin(iter(rows.into_iter().map(Ok))));
-            }
+        // scans read the writes of the transaction, which statements run by triggers rely on
+        if let TransactionState::Active { txn } = &self.state {
+            let table_def = self.data_table_def(table_name)?;
+            let table = txn.open_table(table_def)?;
+
+            let rows: Vec<_> = table
+                .iter()?
+                .map(|entry| {
+                    let value = entry?.1.value();
+                    let (key, row): (Key, DataRow) = deserialize(&value)?;
+
+                    Ok((key, row))
+                })
+                .collect::<Result<_>>()?;
+
+            return Ok(Box::pin(iter(rows.into_iter().map(Ok))));
         }
 
         let read_txn = self.db.begin_read()?;
@@ -173,7 +173,7 @@ impl StorageCore {
         let data_def = self.data_table_def(&schema.table_name)?;
         let txn = self.txn_mut()?;
         let mut table = txn.open_table(SCHEMA_TABLE)?;
-        let value = serialize(&schema)?;
+        let value = serialize_schema(schema)?;
         table.insert(schema.table_name.as_str(), value)?;
         txn.open_table(data_def)?;
 
@@ -248,7 +248,6 @@ impl StorageCore {
                 let write_txn = self.db.begin_write()?;
                 self.state = TransactionState::Active {
                     txn: Box::new(write_txn),
-                    autocommit,
                 };
 
                 Ok(autocommit)
@@ -272,3 +271,58 @@ impl StorageCore {
         Ok(())
     }
 }
+
+// Backup
+impl StorageCore {
+    /// Copies the tables as of a read transaction, which neither waits for nor blocks the
+    /// write transaction running meanwhile.
+    pub fn backup(&self, target_path: &Path) -> Result<()> {
+        let read_txn = self.db.begin_read()?;
+        let target = Database::create(target_path)?;
+        let schema_table = match read_txn.open_table(SCHEMA_TABLE) {
+            Ok(table) => table,
+            Err(TableError::TableDoesNotExist(_)) => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let target_txn = target.begin_write()?;
+        {
+            let mut target_schema_table = target_txn.open_table(SCHEMA_TABLE)?;
+            for entry in schema_table.iter()? {
+                let (table_name, schema) = entry?;
+                let table_name = table_name.value();
+                target_schema_table.insert(table_name, schema.value())?;
+
+                let table_def = self.data_table_def(table_name)?;
+                let table = read_txn.open_table(table_def)?;
+                let mut target_table = target_txn.open_table(table_def)?;
+                for entry in table.iter()? {
+                    let (key, row) = entry?;
+                    target_table.insert(key.value(), row.value())?;
+                }
+            }
+        }
+        target_txn.commit()?;
+
+        Ok(())
+    }
+}
+
+fn serialize_schema(schema: &Schema) -> Result<Vec<u8>> {
+    let value = SCHEMA_HEADER
+        .iter()
+        .copied()
+        .chain(serialize(schema)?)
+        .collect();
+
+    Ok(value)
+}
+
+fn deserialize_schema(value: &[u8]) -> Result<Schema> {
+    let schema = match value.strip_prefix(SCHEMA_HEADER) {
+        Some(value) => deserialize(value)?,
+        None => deserialize::<LegacySchema>(value)?.into(),
+    };
+
+    Ok(schema)
+}
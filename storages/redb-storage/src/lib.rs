@@ -10,8 +10,8 @@ use {
         data::{Key, Schema},
         error::Result,
         store::{
-            AlterTable, CustomFunction, CustomFunctionMut, DataRow, Index, IndexMut, Metadata,
-            RowIter, Store, StoreMut, Transaction,
+            AlterTable, Backup, BackupError, CustomFunction, CustomFunctionMut, DataRow, Index,
+            IndexMut, Metadata, RowIter, Store, StoreMut, Transaction,
         },
     },
     redb::Database,
@@ -32,18 +32,22 @@ impl RedbStorage {
 
 #[async_trait]
 impl Store for RedbStorage {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         self.0.fetch_all_schemas().map_err(Into::into)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         self.0.fetch_schema(table_name).map_err(Into::into)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
         self.0.fetch_data(table_name, key).map_err(Into::into)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         self.0.scan_data(table_name).map_err(Into::into)
     }
@@ -51,14 +55,17 @@ impl Store for RedbStorage {
 
 #[async_trait]
 impl StoreMut for RedbStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         self.0.insert_schema(schema).await.map_err(Into::into)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         self.0.delete_schema(table_name).await.map_err(Into::into)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         self.0
             .append_data(table_name, rows)
@@ -66,6 +73,7 @@ impl StoreMut for RedbStorage {
             .map_err(Into::into)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
         self.0
             .insert_data(table_name, rows)
@@ -73,6 +81,7 @@ impl StoreMut for RedbStorage {
             .map_err(Into::into)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         self.0
             .delete_data(table_name, keys)
@@ -81,6 +90,17 @@ impl StoreMut for RedbStorage {
     }
 }
 
+#[async_trait]
+impl Backup for RedbStorage {
+    async fn backup(&self, target_path: &Path) -> Result<()> {
+        if target_path.exists() {
+            return Err(BackupError::TargetAlreadyExists(target_path.display().to_string()).into());
+        }
+
+        self.0.backup(target_path).map_err(Into::into)
+    }
+}
+
 #[async_trait]
 impl Transaction for RedbStorage {
     async fn begin(&mut self, autocommit: bool) -> Result<bool> {
@@ -102,3 +122,5 @@ impl IndexMut for RedbStorage {}
 impl Metadata for RedbStorage {}
 impl CustomFunction for RedbStorage {}
 impl CustomFunctionMut for RedbStorage {}
+impl gluesql_core::store::User for RedbStorage {}
+impl gluesql_core::store::UserMut for RedbStorage {}
@@ -0,0 +1,58 @@
+use {
+    gluesql_core::{
+        error::Error,
+        prelude::{Glue, Value::*},
+        store::BackupError,
+    },
+    gluesql_redb_storage::RedbStorage,
+    std::fs::remove_file,
+    test_suite::{concat_with, row, select, stringify_label},
+};
+
+#[tokio::test]
+async fn redb_backup() {
+    let _ = std::fs::create_dir("tmp");
+    let path = "tmp/redb_backup";
+    let target_path = "tmp/redb_backup_target";
+    let _ = remove_file(path);
+    let _ = remove_file(target_path);
+
+    let mut glue = Glue::new(RedbStorage::new(path).unwrap());
+    glue.execute("CREATE TABLE Foo (id INTEGER PRIMARY KEY, name TEXT);")
+        .await
+        .unwrap();
+    glue.execute("CREATE TABLE Bar (id INTEGER);")
+        .await
+        .unwrap();
+    glue.execute("INSERT INTO Foo VALUES (1, 'Glue'), (2, 'SQL');")
+        .await
+        .unwrap();
+
+    glue.execute("BEGIN;").await.unwrap();
+    glue.execute("INSERT INTO Foo VALUES (3, 'Uncommitted');")
+        .await
+        .unwrap();
+    glue.backup(target_path).await.unwrap();
+    assert_eq!(
+        glue.backup(target_path).await,
+        Err(Error::Backup(BackupError::TargetAlreadyExists(
+            target_path.to_owned()
+        )))
+    );
+    glue.execute("COMMIT;").await.unwrap();
+
+    let mut backup = Glue::new(RedbStorage::new(target_path).unwrap());
+    assert_eq!(
+        backup.execute("SELECT id, name FROM Foo").await,
+        Ok(vec![select!(
+            id  | name;
+            I64 | Str;
+            1     "Glue".to_owned();
+            2     "SQL".to_owned()
+        )])
+    );
+    assert_eq!(
+        backup.execute("SHOW TABLES").await,
+        glue.execute("SHOW TABLES").await
+    );
+}
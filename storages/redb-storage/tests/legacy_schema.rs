@@ -0,0 +1,78 @@
+use {
+    gluesql_core::{
+        ast::{ColumnDef, DataType},
+        data::{LegacySchema, Schema},
+        prelude::{Glue, Payload, PayloadVariable, Value::*},
+        store::Store,
+    },
+    gluesql_redb_storage::RedbStorage,
+    redb::{Database, TableDefinition},
+    std::fs::remove_file,
+    test_suite::{row, select, stringify_label},
+};
+
+#[tokio::test]
+async fn redb_legacy_schema() {
+    let _ = std::fs::create_dir("tmp");
+    let path = "tmp/redb_legacy_schema";
+    let _ = remove_file(path);
+
+    let column_def = |name: &str, data_type| ColumnDef {
+        name: name.to_owned(),
+        data_type,
+        nullable: false,
+        default: None,
+        unique: None,
+        comment: None,
+    };
+    let schema = LegacySchema {
+        table_name: "Legacy".to_owned(),
+        column_defs: Some(vec![
+            column_def("id", DataType::Int),
+            column_def("name", DataType::Text),
+        ]),
+        indexes: Vec::new(),
+        engine: None,
+        foreign_keys: Vec::new(),
+        comment: Some("written before schemas were versioned".to_owned()),
+    };
+
+    let schema_table: TableDefinition<&str, Vec<u8>> = TableDefinition::new("__SCHEMA__");
+    let data_table: TableDefinition<&[u8], Vec<u8>> = TableDefinition::new("Legacy");
+    let db = Database::create(path).unwrap();
+    let txn = db.begin_write().unwrap();
+    txn.open_table(schema_table)
+        .unwrap()
+        .insert("Legacy", bincode::serialize(&schema).unwrap())
+        .unwrap();
+    txn.open_table(data_table).unwrap();
+    txn.commit().unwrap();
+    drop(db);
+
+    let storage = RedbStorage::new(path).unwrap();
+    assert_eq!(
+        storage.fetch_schema("Legacy").await.unwrap(),
+        Some(Schema::from(schema))
+    );
+
+    let mut glue = Glue::new(storage);
+    glue.execute("INSERT INTO Legacy VALUES (1, 'Glue'), (2, 'SQL');")
+        .await
+        .unwrap();
+    glue.execute("CREATE TABLE Fresh (id INTEGER);")
+        .await
+        .unwrap();
+    assert_eq!(
+        glue.execute("SELECT id, name FROM Legacy WHERE id = 2;")
+            .await
+            .unwrap(),
+        vec![select!(id | name; I64 | Str; 2 "SQL".to_owned())]
+    );
+    assert_eq!(
+        glue.execute("SHOW TABLES;").await.unwrap(),
+        vec![Payload::ShowVariable(PayloadVariable::Tables(vec![
+            "Fresh".to_owned(),
+            "Legacy".to_owned(),
+        ]))]
+    );
+}
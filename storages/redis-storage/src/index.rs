@@ -2,7 +2,7 @@ use {
     super::RedisStorage,
     async_trait::async_trait,
     gluesql_core::{
-        ast::{IndexOperator, OrderByExpr},
+        ast::{Expr, IndexOperator, OrderByExpr},
         data::Value,
         error::{Error, Result},
         store::{Index, IndexMut, RowIter},
@@ -32,7 +32,9 @@ impl IndexMut for RedisStorage {
         &mut self,
         _table_name: &str,
         _index_name: &str,
-        _column: &OrderByExpr,
+        _columns: &[OrderByExpr],
+        _predicate: Option<&Expr>,
+        _fulltext: bool,
     ) -> Result<()> {
         Err(Error::StorageMsg(
             "[RedisStorage] index is not supported".to_owned(),
@@ -228,8 +228,13 @@ impl CustomFunctionMut for RedisStorage {
     }
 }
 
+impl gluesql_core::store::User for RedisStorage {}
+impl gluesql_core::store::UserMut for RedisStorage {}
+impl gluesql_core::store::Backup for RedisStorage {}
+
 #[async_trait]
 impl Store for RedisStorage {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let mut schemas = Vec::<Schema>::new();
         let scan_schema_key = Self::redis_generate_scan_schema_key(&self.namespace);
@@ -272,6 +277,7 @@ impl Store for RedisStorage {
         Ok(schemas)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         let mut found = None;
         let scan_schema_key = Self::redis_generate_scan_schema_key(&self.namespace);
@@ -320,6 +326,7 @@ impl Store for RedisStorage {
         Ok(found)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
         let key = Self::redis_generate_key(&self.namespace, table_name, key)?;
         // It's not a problem if the value with the key is removed by another client.
@@ -339,6 +346,7 @@ impl Store for RedisStorage {
         Ok(None)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         // First read all keys of the table
         let redis_keys: Vec<String> = {
@@ -389,6 +397,7 @@ impl Store for RedisStorage {
 
 #[async_trait]
 impl StoreMut for RedisStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let current_time = Value::Timestamp(Utc::now().naive_utc());
         let current_time_value = serde_json::to_string(&current_time).map_err(|e| {
@@ -416,6 +425,7 @@ impl StoreMut for RedisStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         let redis_key_iter: Vec<String> = self.redis_execute_scan(table_name)?;
         for key in redis_key_iter {
@@ -444,6 +454,7 @@ impl StoreMut for RedisStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         for row in rows {
             // Even multiple clients can get an unique value with INCR command.
@@ -471,6 +482,7 @@ impl StoreMut for RedisStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
         for (key, row) in rows {
             let redis_key = Self::redis_generate_key(&self.namespace, table_name, &key)?;
@@ -485,6 +497,7 @@ impl StoreMut for RedisStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         for key in keys {
             let redis_key = Self::redis_generate_key(&self.namespace, table_name, &key)?;
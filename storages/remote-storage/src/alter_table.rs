@@ -0,0 +1,75 @@
+use {
+    super::RemoteStorage,
+    async_trait::async_trait,
+    gluesql_core::{
+        ast::{ColumnDef, DataType, Expr, ToSql},
+        error::Result,
+        store::AlterTable,
+    },
+};
+
+/// Forwards `ALTER TABLE` to the server instead of rewriting the remote rows locally.
+#[async_trait]
+impl AlterTable for RemoteStorage {
+    async fn rename_schema(&mut self, table_name: &str, new_table_name: &str) -> Result<()> {
+        self.execute(format!(
+            r#"ALTER TABLE "{table_name}" RENAME TO "{new_table_name}";"#
+        ))
+        .await
+    }
+
+    async fn rename_column(
+        &mut self,
+        table_name: &str,
+        old_column_name: &str,
+        new_column_name: &str,
+    ) -> Result<()> {
+        self.execute(format!(
+            r#"ALTER TABLE "{table_name}" RENAME COLUMN "{old_column_name}" TO "{new_column_name}";"#
+        ))
+        .await
+    }
+
+    async fn add_column(&mut self, table_name: &str, column_def: &ColumnDef) -> Result<()> {
+        self.execute(format!(
+            r#"ALTER TABLE "{table_name}" ADD COLUMN {};"#,
+            column_def.to_sql()
+        ))
+        .await
+    }
+
+    async fn drop_column(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+        if_exists: bool,
+    ) -> Result<()> {
+        let if_exists = match if_exists {
+            true => "IF EXISTS ",
+            false => "",
+        };
+
+        self.execute(format!(
+            r#"ALTER TABLE "{table_name}" DROP COLUMN {if_exists}"{column_name}";"#
+        ))
+        .await
+    }
+
+    async fn alter_column_type(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+        using: Option<&Expr>,
+    ) -> Result<()> {
+        let using = match using {
+            Some(using) => format!(" USING {}", using.to_sql()),
+            None => String::new(),
+        };
+
+        self.execute(format!(
+            r#"ALTER TABLE "{table_name}" ALTER COLUMN "{column_name}" TYPE {data_type}{using};"#
+        ))
+        .await
+    }
+}
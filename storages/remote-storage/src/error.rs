@@ -0,0 +1,40 @@
+use {gluesql_core::error::Error, thiserror::Error, tonic::Status};
+
+pub trait ResultExt<T, E: ToString> {
+    fn map_storage_err(self) -> Result<T, Error>;
+}
+
+impl<T, E: ToString> ResultExt<T, E> for std::result::Result<T, E> {
+    fn map_storage_err(self) -> Result<T, Error> {
+        self.map_err(|e| e.to_string()).map_err(Error::StorageMsg)
+    }
+}
+
+pub trait StatusExt<T> {
+    /// Reports only the message of a failed call, which carries the remote error.
+    fn map_status_err(self) -> Result<T, Error>;
+}
+
+impl<T> StatusExt<T> for std::result::Result<T, Status> {
+    fn map_status_err(self) -> Result<T, Error> {
+        self.map_err(|status| Error::StorageMsg(status.message().to_owned()))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RemoteStorageError {
+    #[error("api key is not a valid header value")]
+    InvalidApiKey,
+
+    #[error("table does not exist: {0}")]
+    TableDoesNotExist(String),
+
+    #[error("remote table without a primary key only supports appending rows: {0}")]
+    PrimaryKeyRequired(String),
+
+    #[error("key of a composite primary key expected on table: {0}")]
+    CompositeKeyRequired(String),
+
+    #[error("unexpected response from the remote server")]
+    UnexpectedResponse,
+}
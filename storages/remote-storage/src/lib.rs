@@ -0,0 +1,171 @@
+#![deny(clippy::str_to_string)]
+
+mod alter_table;
+mod error;
+mod store;
+mod store_mut;
+mod value;
+
+pub use error::RemoteStorageError;
+
+use {
+    error::{ResultExt, StatusExt},
+    gluesql_core::{
+        data::{Key, Schema},
+        error::Result,
+        store::{CustomFunction, CustomFunctionMut, Index, IndexMut, Metadata, Store, Transaction},
+    },
+    gluesql_grpc_server::proto::{self, QueryRequest, glue_sql_client::GlueSqlClient},
+    tonic::{
+        Request,
+        metadata::{AsciiMetadataValue, MetadataValue},
+        transport::Channel,
+    },
+};
+
+/// Storage whose tables live on a remote GlueSQL instance served by `gluesql-grpc-server`.
+///
+/// Every call is forwarded to the server, so `RemoteStorage` can be pushed into a
+/// `CompositeStorage` next to local storages to query local and remote tables together.
+/// The remote server commits each request on its own, so explicit transactions are not
+/// supported.
+#[derive(Clone)]
+pub struct RemoteStorage {
+    client: GlueSqlClient<Channel>,
+    api_key: Option<AsciiMetadataValue>,
+    engine: Option<String>,
+}
+
+impl RemoteStorage {
+    /// Connects to a gRPC server, e.g. `http://127.0.0.1:50051`.
+    pub async fn connect(endpoint: &str) -> Result<Self> {
+        let client = GlueSqlClient::connect(endpoint.to_owned())
+            .await
+            .map_storage_err()?;
+
+        Ok(Self {
+            client,
+            api_key: None,
+            engine: None,
+        })
+    }
+
+    /// Sends `key` as the `x-api-key` metadata of every request.
+    pub fn with_api_key(mut self, key: &str) -> Result<Self> {
+        let key = MetadataValue::try_from(key)
+            .map_err(|_| RemoteStorageError::InvalidApiKey)
+            .map_storage_err()?;
+
+        self.api_key = Some(key);
+        Ok(self)
+    }
+
+    /// Reports `engine` as the engine of every remote table, which is how a
+    /// `CompositeStorage` registering this storage under `engine` finds them.
+    pub fn with_engine(mut self, engine: &str) -> Self {
+        self.engine = Some(engine.to_owned());
+        self
+    }
+
+    fn request<T>(&self, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        if let Some(key) = &self.api_key {
+            request.metadata_mut().insert("x-api-key", key.clone());
+        }
+
+        request
+    }
+
+    async fn query(&self, sql: String) -> Result<Vec<proto::Result>> {
+        let request = self.request(QueryRequest { sql });
+
+        self.client
+            .clone()
+            .query(request)
+            .await
+            .map_status_err()
+            .map(|response| response.into_inner().results)
+    }
+
+    async fn execute(&self, sql: String) -> Result<()> {
+        let request = self.request(QueryRequest { sql });
+
+        self.client
+            .clone()
+            .execute(request)
+            .await
+            .map_status_err()
+            .map(|_| ())
+    }
+
+    /// Columns holding the key of each row, empty when rows are keyed by their position.
+    fn primary_key(schema: &Schema) -> Vec<&str> {
+        schema
+            .column_defs
+            .iter()
+            .flatten()
+            .filter(|column_def| column_def.unique.is_some_and(|unique| unique.is_primary))
+            .map(|column_def| column_def.name.as_str())
+            .collect()
+    }
+
+    async fn fetch_schema_or_err(&self, table_name: &str) -> Result<Schema> {
+        self.fetch_schema(table_name)
+            .await?
+            .ok_or_else(|| RemoteStorageError::TableDoesNotExist(table_name.to_owned()))
+            .map_storage_err()
+    }
+
+    fn key_filter(table_name: &str, schema: &Schema, keys: Vec<Key>) -> Result<String> {
+        let columns = Self::primary_key(schema);
+        if let [column] = columns.as_slice() {
+            let keys = keys
+                .into_iter()
+                .map(|key| value::literal(key.into()))
+                .collect::<Result<Vec<_>>>()?
+                .join(", ");
+
+            return Ok(format!(r#""{column}" IN ({keys})"#));
+        }
+
+        if columns.is_empty() {
+            return Err(RemoteStorageError::PrimaryKeyRequired(
+                table_name.to_owned(),
+            ))
+            .map_storage_err();
+        }
+
+        let keys = keys
+            .into_iter()
+            .map(|key| {
+                let Key::Composite(keys) = key else {
+                    return Err(RemoteStorageError::CompositeKeyRequired(
+                        table_name.to_owned(),
+                    ))
+                    .map_storage_err();
+                };
+
+                columns
+                    .iter()
+                    .zip(keys)
+                    .map(|(column, key)| {
+                        value::literal(key.into()).map(|key| format!(r#""{column}" = {key}"#))
+                    })
+                    .collect::<Result<Vec<_>>>()
+                    .map(|conditions| format!("({})", conditions.join(" AND ")))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(keys.join(" OR "))
+    }
+}
+
+impl Index for RemoteStorage {}
+impl IndexMut for RemoteStorage {}
+impl Metadata for RemoteStorage {}
+impl Transaction for RemoteStorage {}
+impl CustomFunction for RemoteStorage {}
+impl CustomFunctionMut for RemoteStorage {}
+impl gluesql_core::store::User for RemoteStorage {}
+impl gluesql_core::store::UserMut for RemoteStorage {}
+impl gluesql_core::store::Backup for RemoteStorage {}
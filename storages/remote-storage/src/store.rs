@@ -0,0 +1,164 @@
+use {
+    super::{RemoteStorage, RemoteStorageError, ResultExt, StatusExt, value::convert},
+    async_trait::async_trait,
+    futures::stream::iter,
+    gluesql_core::{
+        ast::{ColumnDef, ColumnUniqueOption},
+        data::{Key, Schema},
+        error::Result,
+        parse_sql::{parse_data_type, parse_expr},
+        store::{DataRow, RowIter, Store},
+        translate::{translate_data_type, translate_expr},
+    },
+    gluesql_grpc_server::proto::{self, Column, DescribeTableRequest, ListTablesRequest, result},
+    std::collections::BTreeMap,
+    tonic::Code,
+};
+
+impl RemoteStorage {
+    fn column_def(column: Column) -> Result<ColumnDef> {
+        let Column {
+            name,
+            data_type,
+            nullable,
+            unique,
+            primary_key,
+            default,
+            comment,
+        } = column;
+
+        Ok(ColumnDef {
+            name,
+            data_type: translate_data_type(&parse_data_type(data_type)?)?,
+            nullable,
+            default: default
+                .map(|default| translate_expr(&parse_expr(default)?))
+                .transpose()?,
+            unique: unique.then_some(ColumnUniqueOption {
+                is_primary: primary_key,
+            }),
+            comment,
+        })
+    }
+
+    async fn rows(&self, schema: &Schema, filter: Option<String>) -> Result<Vec<(Key, DataRow)>> {
+        let mut sql = format!(r#"SELECT * FROM "{}""#, schema.table_name);
+        if let Some(filter) = filter {
+            sql.push_str(" WHERE ");
+            sql.push_str(&filter);
+        }
+
+        let Some(proto::Result {
+            result: Some(result::Result::Rows(proto::Rows { labels, rows })),
+        }) = self.query(sql).await?.pop()
+        else {
+            return Err(RemoteStorageError::UnexpectedResponse).map_storage_err();
+        };
+
+        rows.into_iter()
+            .enumerate()
+            .map(|(position, row)| {
+                let row = match &schema.column_defs {
+                    Some(column_defs) => row
+                        .values
+                        .into_iter()
+                        .zip(column_defs)
+                        .map(|(value, column_def)| convert(value, Some(&column_def.data_type)))
+                        .collect::<Result<Vec<_>>>()
+                        .map(DataRow::Vec)?,
+                    None => labels
+                        .iter()
+                        .cloned()
+                        .zip(row.values)
+                        .map(|(label, value)| convert(value, None).map(|value| (label, value)))
+                        .filter(|entry| !matches!(entry, Ok((_, value)) if value.is_null()))
+                        .collect::<Result<BTreeMap<_, _>>>()
+                        .map(DataRow::Map)?,
+                };
+                let key = match (&schema.column_defs, &row) {
+                    (Some(column_defs), DataRow::Vec(values)) => Key::primary(column_defs, values)?,
+                    _ => None,
+                }
+                .unwrap_or(Key::I64(position as i64));
+
+                Ok((key, row))
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Store for RemoteStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
+        let request = self.request(DescribeTableRequest {
+            table: table_name.to_owned(),
+        });
+        let columns = match self.client.clone().describe_table(request).await {
+            Ok(response) => response.into_inner().columns,
+            Err(status) if status.code() == Code::NotFound => return Ok(None),
+            Err(status) => return Err(status).map_status_err(),
+        };
+
+        let column_defs = columns
+            .into_iter()
+            .map(Self::column_def)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some(Schema {
+            table_name: table_name.to_owned(),
+            column_defs: (!column_defs.is_empty()).then_some(column_defs),
+            indexes: Vec::new(),
+            engine: self.engine.clone(),
+            foreign_keys: Vec::new(),
+            checks: Vec::new(),
+            uniques: Vec::new(),
+            comment: None,
+            view: None,
+            triggers: Vec::new(),
+            sequence: None,
+        }))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
+        let request = self.request(ListTablesRequest {});
+        let tables = self
+            .client
+            .clone()
+            .list_tables(request)
+            .await
+            .map_status_err()?
+            .into_inner()
+            .tables;
+
+        let mut schemas = Vec::with_capacity(tables.len());
+        for table_name in tables {
+            schemas.extend(self.fetch_schema(&table_name).await?);
+        }
+
+        Ok(schemas)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
+        let schema = self.fetch_schema_or_err(table_name).await?;
+        let filter = match Self::primary_key(&schema).is_empty() {
+            false => Some(Self::key_filter(table_name, &schema, vec![key.clone()])?),
+            true => None,
+        };
+        let rows = self.rows(&schema, filter).await?;
+
+        Ok(rows
+            .into_iter()
+            .find_map(|(row_key, row)| (&row_key == key).then_some(row)))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
+        let schema = self.fetch_schema_or_err(table_name).await?;
+        let rows = self.rows(&schema, None).await?;
+
+        Ok(Box::pin(iter(rows.into_iter().map(Ok))))
+    }
+}
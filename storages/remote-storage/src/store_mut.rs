@@ -0,0 +1,90 @@
+use {
+    super::{RemoteStorage, value::literal},
+    async_trait::async_trait,
+    gluesql_core::{
+        data::{Key, Schema, Value},
+        error::Result,
+        store::{DataRow, StoreMut},
+    },
+};
+
+impl RemoteStorage {
+    fn insert_sql(table_name: &str, rows: Vec<DataRow>) -> Result<String> {
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                let values = match row {
+                    DataRow::Vec(values) => values
+                        .into_iter()
+                        .map(literal)
+                        .collect::<Result<Vec<_>>>()?
+                        .join(", "),
+                    DataRow::Map(map) => literal(Value::Map(map))?,
+                };
+
+                Ok(format!("({values})"))
+            })
+            .collect::<Result<Vec<_>>>()?
+            .join(", ");
+
+        Ok(format!(r#"INSERT INTO "{table_name}" VALUES {rows};"#))
+    }
+}
+
+#[async_trait]
+impl StoreMut for RemoteStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
+    async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
+        let schema = Schema {
+            engine: None,
+            ..schema.clone()
+        };
+
+        self.execute(schema.to_ddl()).await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
+        self.execute(format!(r#"DROP TABLE IF EXISTS "{table_name}";"#))
+            .await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
+    async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        self.execute(Self::insert_sql(table_name, rows)?).await
+    }
+
+    /// Replaces the rows with the same primary key, deleting and inserting them in one request.
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
+    async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        let schema = self.fetch_schema_or_err(table_name).await?;
+        let (keys, rows): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+        let filter = Self::key_filter(table_name, &schema, keys)?;
+        let sql = format!(
+            r#"DELETE FROM "{table_name}" WHERE {filter}; {}"#,
+            Self::insert_sql(table_name, rows)?
+        );
+
+        self.execute(sql).await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
+    async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+
+        let schema = self.fetch_schema_or_err(table_name).await?;
+        let filter = Self::key_filter(table_name, &schema, keys)?;
+        self.execute(format!(r#"DELETE FROM "{table_name}" WHERE {filter};"#))
+            .await
+    }
+}
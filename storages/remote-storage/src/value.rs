@@ -0,0 +1,56 @@
+use {
+    gluesql_core::{
+        ast::{Expr, ToSql},
+        data::{Point, Value},
+        error::Result,
+        prelude::DataType,
+    },
+    gluesql_grpc_server::proto::{self, value},
+};
+
+/// Restores a value sent by the server, typed as the column it was read from.
+///
+/// Values of schemaless tables take the type their wire representation implies.
+pub fn convert(value: proto::Value, data_type: Option<&DataType>) -> Result<Value> {
+    let (value, default_type) = match value.value {
+        None | Some(value::Value::Null(_)) => return Ok(Value::Null),
+        Some(value::Value::Bool(v)) => (Value::Bool(v), DataType::Boolean),
+        Some(value::Value::Int(v)) => (Value::I64(v), DataType::Int),
+        Some(value::Value::Uint(v)) => (Value::U64(v), DataType::Uint64),
+        Some(value::Value::Float(v)) => (Value::F64(v), DataType::Float),
+        Some(value::Value::Decimal(v)) => (Value::Str(v), DataType::Decimal),
+        Some(value::Value::Str(v)) => (Value::Str(v), DataType::Text),
+        Some(value::Value::Bytea(v)) => (Value::Bytea(v), DataType::Bytea),
+        Some(value::Value::Inet(v)) => (Value::Str(v), DataType::Inet),
+        Some(value::Value::Date(v)) => (Value::Str(v), DataType::Date),
+        Some(value::Value::Timestamp(v)) => (Value::Str(v), DataType::Timestamp),
+        Some(value::Value::Time(v)) => (Value::Str(v), DataType::Time),
+        Some(value::Value::Interval(v)) => (Value::Str(v), DataType::Interval),
+        Some(value::Value::Uuid(v)) => (Value::Str(v), DataType::Uuid),
+        Some(value::Value::Json(v)) => {
+            let data_type = match v.starts_with('[') {
+                true => DataType::List,
+                false => DataType::Map,
+            };
+
+            (Value::Str(v), data_type)
+        }
+        Some(value::Value::Point(point)) => {
+            (Value::Point(Point::new(point.x, point.y)), DataType::Point)
+        }
+    };
+
+    value.cast(data_type.unwrap_or(&default_type))
+}
+
+/// Renders a value as a SQL literal without losing precision.
+pub fn literal(value: Value) -> Result<String> {
+    let literal = match value {
+        Value::I128(_) | Value::U128(_) | Value::Decimal(_) => String::from(&value),
+        Value::F32(_) | Value::F64(_) => String::from(&value),
+        Value::Interval(_) => format!("INTERVAL {}", String::from(&value)),
+        value => Expr::try_from(value)?.to_sql(),
+    };
+
+    Ok(literal)
+}
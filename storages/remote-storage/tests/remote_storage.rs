@@ -0,0 +1,198 @@
+use {
+    gluesql_composite_storage::CompositeStorage,
+    gluesql_core::{
+        prelude::{DataType, Error, Glue, Payload, PayloadVariable, Value::*},
+        store::{GStore, GStoreMut},
+    },
+    gluesql_grpc_server::GrpcServer,
+    gluesql_memory_storage::MemoryStorage,
+    gluesql_remote_storage::RemoteStorage,
+    test_suite::*,
+    tokio::net::TcpListener,
+};
+
+async fn serve(server: GrpcServer<MemoryStorage>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { server.serve(listener).await });
+
+    format!("http://{addr}")
+}
+
+async fn select<T: GStore + GStoreMut>(glue: &mut Glue<T>, sql: &str) -> Payload {
+    glue.execute(sql).await.unwrap().into_iter().next().unwrap()
+}
+
+#[tokio::test]
+async fn remote_table() {
+    let endpoint = serve(GrpcServer::new(MemoryStorage::default())).await;
+    let mut glue = Glue::new(RemoteStorage::connect(&endpoint).await.unwrap());
+
+    glue.execute(
+        "CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT, price DECIMAL NULL);
+         INSERT INTO Item VALUES (1, 'apple', 1.5), (2, 'pear', NULL), (3, 'fig', 0.25);
+         UPDATE Item SET price = 2.5 WHERE id = 2;
+         DELETE FROM Item WHERE id = 3;",
+    )
+    .await
+    .unwrap();
+
+    // a second client sees the rows stored on the server
+    let mut other = Glue::new(RemoteStorage::connect(&endpoint).await.unwrap());
+    assert_eq!(
+        select(&mut other, "SELECT id, name, price FROM Item WHERE id = 2").await,
+        select!(
+            id  | name                  | price
+            I64 | Str                   | Decimal;
+            2     "pear".to_owned()       "2.5".parse().unwrap()
+        )
+    );
+    assert_eq!(
+        select(&mut other, "SELECT id FROM Item ORDER BY id").await,
+        select!(id I64; 1; 2)
+    );
+
+    glue.execute("ALTER TABLE Item RENAME TO Fruit;")
+        .await
+        .unwrap();
+    assert_eq!(
+        select(&mut other, "SHOW TABLES").await,
+        Payload::ShowVariable(PayloadVariable::Tables(vec!["Fruit".to_owned()]))
+    );
+}
+
+#[tokio::test]
+async fn value_types() {
+    let endpoint = serve(GrpcServer::new(MemoryStorage::default())).await;
+    let mut glue = Glue::new(RemoteStorage::connect(&endpoint).await.unwrap());
+
+    glue.execute(
+        "CREATE TABLE Typed (
+            id INT8 PRIMARY KEY,
+            amount UINT16,
+            big INT128,
+            day DATE,
+            at TIMESTAMP,
+            span INTERVAL,
+            tags LIST,
+            meta MAP
+        );
+        INSERT INTO Typed VALUES (
+            1, 7, 170141183460469231731687303715884105727, '2024-01-31', '2024-01-31 12:34:56',
+            INTERVAL '1-2' YEAR TO MONTH, '[1, \"a\"]', '{\"k\": true}'
+        );",
+    )
+    .await
+    .unwrap();
+
+    let Payload::Select { rows, .. } = select(&mut glue, "SELECT * FROM Typed").await else {
+        panic!("expected rows");
+    };
+    let types = rows[0]
+        .iter()
+        .map(|value| value.get_type())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        vec![
+            Some(DataType::Int8),
+            Some(DataType::Uint16),
+            Some(DataType::Int128),
+            Some(DataType::Date),
+            Some(DataType::Timestamp),
+            Some(DataType::Interval),
+            Some(DataType::List),
+            Some(DataType::Map),
+        ]
+    );
+    assert_eq!(rows[0][2], I128(i128::MAX));
+}
+
+#[tokio::test]
+async fn without_primary_key() {
+    let endpoint = serve(GrpcServer::new(MemoryStorage::default())).await;
+    let mut glue = Glue::new(RemoteStorage::connect(&endpoint).await.unwrap());
+
+    glue.execute(
+        "CREATE TABLE Log (message TEXT);
+         INSERT INTO Log VALUES ('a'), ('b');
+         CREATE TABLE Doc;
+         INSERT INTO Doc VALUES ('{\"id\": 1, \"tag\": \"x\"}');",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        select(&mut glue, "SELECT message FROM Log").await,
+        select!(message Str; "a".to_owned(); "b".to_owned())
+    );
+    assert_eq!(
+        select(&mut glue, "SELECT id, tag FROM Doc").await,
+        select!(id | tag I64 | Str; 1 "x".to_owned())
+    );
+    assert_eq!(
+        glue.execute("DELETE FROM Log WHERE message = 'a'")
+            .await
+            .unwrap_err(),
+        Error::StorageMsg(
+            "remote table without a primary key only supports appending rows: Log".to_owned()
+        )
+    );
+}
+
+#[tokio::test]
+async fn composite() {
+    let endpoint = serve(GrpcServer::new(MemoryStorage::default())).await;
+    let remote = RemoteStorage::connect(&endpoint)
+        .await
+        .unwrap()
+        .with_engine("REMOTE");
+
+    let mut storage = CompositeStorage::new();
+    storage.push("MEMORY", MemoryStorage::default());
+    storage.push("REMOTE", remote);
+    storage.set_default("MEMORY");
+    let mut glue = Glue::new(storage);
+
+    glue.execute(
+        "CREATE TABLE Foo (id INTEGER PRIMARY KEY) ENGINE = MEMORY;
+         CREATE TABLE Bar (id INTEGER PRIMARY KEY, foo_id INTEGER) ENGINE = REMOTE;
+         INSERT INTO Foo VALUES (1), (2);
+         INSERT INTO Bar VALUES (10, 1), (20, 2), (30, 1);",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        select(
+            &mut glue,
+            "SELECT Bar.id, Foo.id AS foo_id FROM Bar JOIN Foo ON Bar.foo_id = Foo.id WHERE Bar.foo_id = 1",
+        )
+        .await,
+        select!(
+            id  | foo_id
+            I64 | I64;
+            10    1;
+            30    1
+        )
+    );
+}
+
+#[tokio::test]
+async fn api_key() {
+    let endpoint = serve(GrpcServer::new(MemoryStorage::default()).with_api_key("secret")).await;
+
+    let mut glue = Glue::new(RemoteStorage::connect(&endpoint).await.unwrap());
+    assert_eq!(
+        glue.execute("SHOW TABLES").await.unwrap_err(),
+        Error::StorageMsg("missing or invalid API key".to_owned())
+    );
+
+    let storage = RemoteStorage::connect(&endpoint)
+        .await
+        .unwrap()
+        .with_api_key("secret")
+        .unwrap();
+    let mut glue = Glue::new(storage);
+    glue.execute("SHOW TABLES").await.unwrap();
+}
@@ -1,7 +1,11 @@
 use {
     super::SharedMemoryStorage,
     async_trait::async_trait,
-    gluesql_core::{ast::ColumnDef, error::Result, store::AlterTable},
+    gluesql_core::{
+        ast::{ColumnDef, DataType, Expr},
+        error::Result,
+        store::AlterTable,
+    },
     std::sync::Arc,
 };
 
@@ -48,4 +52,19 @@ impl AlterTable for SharedMemoryStorage {
             .drop_column(table_name, column_name, if_exists)
             .await
     }
+
+    async fn alter_column_type(
+        &mut self,
+        table_name: &str,
+        column_name: &str,
+        data_type: &DataType,
+        using: Option<&Expr>,
+    ) -> Result<()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        database
+            .alter_column_type(table_name, column_name, data_type, using)
+            .await
+    }
 }
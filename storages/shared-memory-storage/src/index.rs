@@ -2,7 +2,7 @@ use {
     super::SharedMemoryStorage,
     async_trait::async_trait,
     gluesql_core::{
-        ast::{IndexOperator, OrderByExpr},
+        ast::{Expr, IndexOperator, OrderByExpr},
         data::Value,
         error::{Error, Result},
         store::{Index, IndexMut, RowIter},
@@ -30,7 +30,9 @@ impl IndexMut for SharedMemoryStorage {
         &mut self,
         _table_name: &str,
         _index_name: &str,
-        _column: &OrderByExpr,
+        _columns: &[OrderByExpr],
+        _predicate: Option<&Expr>,
+        _fulltext: bool,
     ) -> Result<()> {
         Err(Error::StorageMsg(
             "[Shared MemoryStorage] index is not supported".to_owned(),
@@ -8,9 +8,9 @@ use {
     async_trait::async_trait,
     futures::stream,
     gluesql_core::{
-        data::{Key, Schema},
+        data::{Key, Schema, TableStatistics},
         error::Result,
-        store::{DataRow, Metadata, RowIter, Store, StoreMut},
+        store::{DataRow, Metadata, Predicate, RowIter, Store, StoreMut},
     },
     gluesql_memory_storage::MemoryStorage,
     std::sync::Arc,
@@ -46,12 +46,14 @@ impl From<MemoryStorage> for SharedMemoryStorage {
 
 #[async_trait]
 impl Store for SharedMemoryStorage {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let database = Arc::clone(&self.database);
         let database = database.read().await;
 
         database.fetch_all_schemas().await
     }
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         let database = Arc::clone(&self.database);
         let database = database.read().await;
@@ -59,6 +61,7 @@ impl Store for SharedMemoryStorage {
         database.fetch_schema(table_name).await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
         let database = Arc::clone(&self.database);
         let database = database.read().await;
@@ -66,6 +69,7 @@ impl Store for SharedMemoryStorage {
         database.fetch_data(table_name, key).await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         let rows = self
             .database
@@ -77,10 +81,54 @@ impl Store for SharedMemoryStorage {
 
         Ok(Box::pin(stream::iter(rows)))
     }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn scan_data_filtered<'a>(
+        &'a self,
+        table_name: &str,
+        predicate: &Predicate,
+    ) -> Result<RowIter<'a>> {
+        let rows = self
+            .database
+            .read()
+            .await
+            .scan_data_filtered(table_name, predicate)
+            .into_iter()
+            .map(Ok);
+
+        Ok(Box::pin(stream::iter(rows)))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn scan_data_partitioned<'a>(
+        &'a self,
+        table_name: &str,
+        predicate: Option<&Predicate>,
+        partitions: usize,
+    ) -> Result<Vec<RowIter<'a>>> {
+        let partitions = self
+            .database
+            .read()
+            .await
+            .scan_data_partitioned(table_name, predicate, partitions)
+            .into_iter()
+            .map(|rows| -> RowIter<'a> { Box::pin(stream::iter(rows.into_iter().map(Ok))) })
+            .collect();
+
+        Ok(partitions)
+    }
+
+    async fn fetch_statistics(&self, table_name: &str) -> Result<Option<TableStatistics>> {
+        let database = Arc::clone(&self.database);
+        let database = database.read().await;
+
+        database.fetch_statistics(table_name).await
+    }
 }
 
 #[async_trait]
 impl StoreMut for SharedMemoryStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let database = Arc::clone(&self.database);
         let mut database = database.write().await;
@@ -88,6 +136,7 @@ impl StoreMut for SharedMemoryStorage {
         database.insert_schema(schema).await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         let database = Arc::clone(&self.database);
         let mut database = database.write().await;
@@ -95,6 +144,7 @@ impl StoreMut for SharedMemoryStorage {
         database.delete_schema(table_name).await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         let database = Arc::clone(&self.database);
         let mut database = database.write().await;
@@ -102,6 +152,7 @@ impl StoreMut for SharedMemoryStorage {
         database.append_data(table_name, rows).await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
         let database = Arc::clone(&self.database);
         let mut database = database.write().await;
@@ -109,14 +160,25 @@ impl StoreMut for SharedMemoryStorage {
         database.insert_data(table_name, rows).await
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         let database = Arc::clone(&self.database);
         let mut database = database.write().await;
 
         database.delete_data(table_name, keys).await
     }
+
+    async fn insert_statistics(&mut self, statistics: TableStatistics) -> Result<()> {
+        let database = Arc::clone(&self.database);
+        let mut database = database.write().await;
+
+        database.insert_statistics(statistics).await
+    }
 }
 
 impl Metadata for SharedMemoryStorage {}
 impl gluesql_core::store::CustomFunction for SharedMemoryStorage {}
 impl gluesql_core::store::CustomFunctionMut for SharedMemoryStorage {}
+impl gluesql_core::store::User for SharedMemoryStorage {}
+impl gluesql_core::store::UserMut for SharedMemoryStorage {}
+impl gluesql_core::store::Backup for SharedMemoryStorage {}
@@ -21,9 +21,11 @@ impl Tester<SharedMemoryStorage> for SharedMemoryTester {
     }
 }
 
-generate_store_tests!(tokio::test, SharedMemoryTester);
-
-generate_alter_table_tests!(tokio::test, SharedMemoryTester);
+generate_conformance_tests!(
+    tokio::test,
+    SharedMemoryTester,
+    [basic, alter_table, pushdown, concurrency]
+);
 
 macro_rules! exec {
     ($glue: ident $sql: literal) => {
@@ -52,7 +52,12 @@ impl AlterTable for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view,
+                triggers,
+                sequence,
                 ..
             } = old_schema
                 .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_owned()).into())
@@ -64,7 +69,12 @@ impl AlterTable for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view,
+                triggers,
+                sequence,
             };
 
             bincode::serialize(&old_snapshot)
@@ -79,6 +89,7 @@ impl AlterTable for SledStorage {
                 .map_err(ConflictableTransactionError::Abort)?;
             let new_schema_key = format!("schema/{new_table_name}");
             tree.insert(new_schema_key.as_bytes(), value)?;
+            tree.remove(format!("statistics/{table_name}").as_bytes())?;
 
             // replace data
             for (old_key, value) in items.iter() {
@@ -164,7 +175,11 @@ impl AlterTable for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment: schema_comment,
+                triggers,
+                sequence,
                 ..
             } = snapshot
                 .get(txid, None)
@@ -215,7 +230,12 @@ impl AlterTable for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment: schema_comment,
+                view: None,
+                triggers,
+                sequence,
             };
             let (snapshot, _) = snapshot.update(txid, schema);
             let value = bincode::serialize(&snapshot)
@@ -269,7 +289,12 @@ impl AlterTable for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view,
+                triggers,
+                sequence,
             } = schema_snapshot
                 .get(txid, None)
                 .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_owned()).into())
@@ -368,7 +393,12 @@ impl AlterTable for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view,
+                triggers,
+                sequence,
             };
             let (schema_snapshot, _) = schema_snapshot.update(txid, schema);
             let schema_value = bincode::serialize(&schema_snapshot)
@@ -425,7 +455,12 @@ impl AlterTable for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view,
+                triggers,
+                sequence,
             } = schema_snapshot
                 .get(txid, None)
                 .ok_or_else(|| AlterTableError::TableNotFound(table_name.to_owned()).into())
@@ -507,7 +542,12 @@ impl AlterTable for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view,
+                triggers,
+                sequence,
             };
             let (schema_snapshot, _) = schema_snapshot.update(txid, schema);
             let schema_value = bincode::serialize(&schema_snapshot)
@@ -0,0 +1,27 @@
+use {
+    super::{SledStorage, err_into},
+    async_trait::async_trait,
+    gluesql_core::{
+        error::Result,
+        store::{Backup, BackupError},
+    },
+    std::path::Path,
+};
+
+#[async_trait]
+impl Backup for SledStorage {
+    /// Copies the trees through [`SledStorage::export`], which reads them without locking.
+    /// Versions written by a transaction still running are copied along with its lock, so
+    /// opening the backup rolls them back.
+    async fn backup(&self, target_path: &Path) -> Result<()> {
+        if target_path.exists() {
+            return Err(BackupError::TargetAlreadyExists(target_path.display().to_string()).into());
+        }
+
+        let mut backup = SledStorage::new(target_path)?;
+        backup.import(self.export()?)?;
+        backup.tree.flush().map_err(err_into)?;
+
+        Ok(())
+    }
+}
@@ -1,8 +1,8 @@
 use {
     super::{
         SledStorage, Snapshot, State, err_into,
-        index_sync::{build_index_key, build_index_key_prefix},
-        lock,
+        index_sync::{build_composite_index_key, build_index_key, build_index_key_prefix},
+        key, lock,
     },
     async_trait::async_trait,
     futures::stream::iter,
@@ -61,22 +61,44 @@ impl Index for SledStorage {
                     };
                     let lower = || build_index_key_prefix(table_name, index_name);
                     let upper = || incr(build_index_key_prefix(table_name, index_name));
-                    let key = build_index_key(table_name, index_name, value)?;
-
-                    match op {
-                        IndexOperator::Eq => match self.tree.get(&key).transpose() {
-                            Some(v) => DataIds::Once(once(v)),
-                            None => DataIds::Empty(empty()),
-                        },
-                        IndexOperator::Gt => {
-                            DataIds::Range(self.tree.range(incr(key)..upper()).map(map))
-                        }
-                        IndexOperator::GtEq => {
-                            DataIds::Range(self.tree.range(key..upper()).map(map))
-                        }
-                        IndexOperator::Lt => DataIds::Range(self.tree.range(lower()..key).map(map)),
-                        IndexOperator::LtEq => {
-                            DataIds::Range(self.tree.range(lower()..=key).map(map))
+
+                    if let Value::List(mut values) = value {
+                        // A composite index scans the keys equal to the leading values and
+                        // compared by the last one, all of which share the leading values.
+                        let key =
+                            build_composite_index_key(table_name, index_name, values.clone())?;
+                        values.pop();
+                        let lower = build_composite_index_key(table_name, index_name, values)?;
+                        let upper = incr(lower.clone());
+                        let range = match op {
+                            IndexOperator::Eq => self.tree.range(key.clone()..incr(key)),
+                            IndexOperator::Gt => self.tree.range(incr(key)..upper),
+                            IndexOperator::GtEq => self.tree.range(key..upper),
+                            IndexOperator::Lt => self.tree.range(lower..key),
+                            IndexOperator::LtEq => self.tree.range(lower..incr(key)),
+                        };
+
+                        DataIds::Range(range.map(map))
+                    } else {
+                        let key = build_index_key(table_name, index_name, value)?;
+
+                        match op {
+                            IndexOperator::Eq => match self.tree.get(&key).transpose() {
+                                Some(v) => DataIds::Once(once(v)),
+                                None => DataIds::Empty(empty()),
+                            },
+                            IndexOperator::Gt => {
+                                DataIds::Range(self.tree.range(incr(key)..upper()).map(map))
+                            }
+                            IndexOperator::GtEq => {
+                                DataIds::Range(self.tree.range(key..upper()).map(map))
+                            }
+                            IndexOperator::Lt => {
+                                DataIds::Range(self.tree.range(lower()..key).map(map))
+                            }
+                            IndexOperator::LtEq => {
+                                DataIds::Range(self.tree.range(lower()..=key).map(map))
+                            }
                         }
                     }
                 }
@@ -95,7 +117,7 @@ impl Index for SledStorage {
         };
         let lock_txid = lock::fetch(&self.tree, txid, created_at, self.tx_timeout)?;
 
-        let prefix_len = build_index_key_prefix(table_name, index_name).len();
+        let prefix_len = key::data_prefix(table_name).len();
         let tree = self.tree.clone();
         let flat_map = move |keys: Result<IVec>| {
             #[derive(Iterator)]
@@ -10,7 +10,7 @@ use {
     async_trait::async_trait,
     futures::stream::TryStreamExt,
     gluesql_core::{
-        ast::OrderByExpr,
+        ast::{Expr, OrderByExpr},
         chrono::Utc,
         data::{Schema, SchemaIndex, SchemaIndexOrd},
         error::{Error, IndexError, Result},
@@ -43,7 +43,9 @@ impl IndexMut for SledStorage {
         &mut self,
         table_name: &str,
         index_name: &str,
-        column: &OrderByExpr,
+        columns: &[OrderByExpr],
+        predicate: Option<&Expr>,
+        fulltext: bool,
     ) -> Result<()> {
         let rows = self
             .scan_data(table_name)
@@ -61,8 +63,6 @@ impl IndexMut for SledStorage {
                 }
             };
 
-            let index_expr = &column.expr;
-
             let (schema_key, schema_snapshot) = fetch_schema(tree, table_name)?;
             let schema_snapshot = schema_snapshot
                 .ok_or_else(|| IndexError::TableNotFound(table_name.to_owned()).into())
@@ -74,7 +74,11 @@ impl IndexMut for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                triggers,
+                sequence,
                 ..
             } = schema
                 .ok_or_else(|| IndexError::ConflictTableNotFound(table_name.to_owned()).into())
@@ -88,8 +92,13 @@ impl IndexMut for SledStorage {
 
             let index = SchemaIndex {
                 name: index_name.to_owned(),
-                expr: index_expr.clone(),
+                exprs: columns
+                    .iter()
+                    .map(|OrderByExpr { expr, .. }| expr.clone())
+                    .collect(),
                 order: SchemaIndexOrd::Both,
+                predicate: predicate.cloned(),
+                fulltext,
                 created: Utc::now().naive_utc(),
             };
 
@@ -104,7 +113,12 @@ impl IndexMut for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view: None,
+                triggers,
+                sequence,
             };
 
             let index_sync = IndexSync::from_schema(tree, txid, &schema);
@@ -136,7 +150,8 @@ impl IndexMut for SledStorage {
         });
 
         if self.check_retry(tx_result)? {
-            self.create_index(table_name, index_name, column).await?;
+            self.create_index(table_name, index_name, columns, predicate, fulltext)
+                .await?;
         }
 
         Ok(())
@@ -170,7 +185,11 @@ impl IndexMut for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                triggers,
+                sequence,
                 ..
             } = schema
                 .ok_or_else(|| IndexError::ConflictTableNotFound(table_name.to_owned()).into())
@@ -195,7 +214,12 @@ impl IndexMut for SledStorage {
                 indexes,
                 engine,
                 foreign_keys,
+                checks,
+                uniques,
                 comment,
+                view: None,
+                triggers,
+                sequence,
             };
 
             let index_sync = IndexSync::from_schema(tree, txid, &schema);
@@ -1,11 +1,13 @@
 use {
     super::{Snapshot, err_into, fetch_schema, key},
     gluesql_core::{
-        ast::Expr,
-        data::schema::{Schema, SchemaIndex},
+        data::{
+            fulltext,
+            schema::{Schema, SchemaIndex},
+        },
         error::{Error, IndexError, Result},
         executor::evaluate_stateless,
-        prelude::Value,
+        prelude::{Key, Value},
         store::DataRow,
     },
     sled::{
@@ -103,22 +105,12 @@ impl<'a> IndexSync<'a> {
         data_key: &IVec,
         row: &DataRow,
     ) -> ConflictableTransactionResult<(), Error> {
-        let SchemaIndex {
-            name: index_name,
-            expr: index_expr,
-            ..
-        } = index;
+        let index_keys =
+            evaluate_index_keys(self.table_name, index, self.columns.as_deref(), row).await?;
 
-        let index_key = &evaluate_index_key(
-            self.table_name,
-            index_name,
-            index_expr,
-            self.columns.as_deref(),
-            row,
-        )
-        .await?;
-
-        self.insert_index_data(index_key, data_key)?;
+        for index_key in index_keys {
+            self.insert_index_data(&index_key, data_key)?;
+        }
 
         Ok(())
     }
@@ -130,32 +122,19 @@ impl<'a> IndexSync<'a> {
         new_row: &DataRow,
     ) -> ConflictableTransactionResult<(), Error> {
         for index in self.indexes.iter() {
-            let SchemaIndex {
-                name: index_name,
-                expr: index_expr,
-                ..
-            } = index;
-
-            let old_index_key = &evaluate_index_key(
-                self.table_name,
-                index_name,
-                index_expr,
-                self.columns.as_deref(),
-                old_row,
-            )
-            .await?;
-
-            let new_index_key = &evaluate_index_key(
-                self.table_name,
-                index_name,
-                index_expr,
-                self.columns.as_deref(),
-                new_row,
-            )
-            .await?;
-
-            self.delete_index_data(old_index_key, data_key)?;
-            self.insert_index_data(new_index_key, data_key)?;
+            let columns = self.columns.as_deref();
+            let old_index_keys =
+                evaluate_index_keys(self.table_name, index, columns, old_row).await?;
+            let new_index_keys =
+                evaluate_index_keys(self.table_name, index, columns, new_row).await?;
+
+            for old_index_key in old_index_keys {
+                self.delete_index_data(&old_index_key, data_key)?;
+            }
+
+            for new_index_key in new_index_keys {
+                self.insert_index_data(&new_index_key, data_key)?;
+            }
         }
 
         Ok(())
@@ -179,22 +158,12 @@ impl<'a> IndexSync<'a> {
         data_key: &IVec,
         row: &DataRow,
     ) -> ConflictableTransactionResult<(), Error> {
-        let SchemaIndex {
-            name: index_name,
-            expr: index_expr,
-            ..
-        } = index;
-
-        let index_key = &evaluate_index_key(
-            self.table_name,
-            index_name,
-            index_expr,
-            self.columns.as_deref(),
-            row,
-        )
-        .await?;
+        let index_keys =
+            evaluate_index_keys(self.table_name, index, self.columns.as_deref(), row).await?;
 
-        self.delete_index_data(index_key, data_key)?;
+        for index_key in index_keys {
+            self.delete_index_data(&index_key, data_key)?;
+        }
 
         Ok(())
     }
@@ -267,22 +236,57 @@ impl<'a> IndexSync<'a> {
     }
 }
 
-async fn evaluate_index_key(
+/// Index keys of the row, none when the row is out of a partial index, and one for each of the
+/// terms of its text in a full-text index
+async fn evaluate_index_keys(
     table_name: &str,
-    index_name: &str,
-    index_expr: &Expr,
+    index: &SchemaIndex,
     columns: Option<&[String]>,
     row: &DataRow,
-) -> ConflictableTransactionResult<Vec<u8>, Error> {
-    let context = Some(row.as_context(columns));
-    let evaluated = evaluate_stateless(context, index_expr)
-        .await
-        .map_err(ConflictableTransactionError::Abort)?;
-    let value: Value = evaluated
-        .try_into()
-        .map_err(ConflictableTransactionError::Abort)?;
-
-    build_index_key(table_name, index_name, value).map_err(ConflictableTransactionError::Abort)
+) -> ConflictableTransactionResult<Vec<Vec<u8>>, Error> {
+    let SchemaIndex {
+        name: index_name,
+        exprs: index_exprs,
+        predicate,
+        fulltext,
+        ..
+    } = index;
+
+    if let Some(predicate) = predicate {
+        let context = Some(row.as_context(columns));
+        let matched = evaluate_stateless(context, predicate)
+            .await
+            .and_then(Value::try_from)
+            .map_err(ConflictableTransactionError::Abort)?;
+
+        if matched != Value::Bool(true) {
+            return Ok(Vec::new());
+        }
+    }
+
+    let mut values = Vec::with_capacity(index_exprs.len());
+    for index_expr in index_exprs {
+        let context = Some(row.as_context(columns));
+        let value = evaluate_stateless(context, index_expr)
+            .await
+            .and_then(Value::try_from)
+            .map_err(ConflictableTransactionError::Abort)?;
+
+        values.push(value);
+    }
+
+    match <[Value; 1]>::try_from(values) {
+        Ok([Value::Null]) if *fulltext => Ok(Vec::new()),
+        Ok([value]) if *fulltext => fulltext::distinct_terms(&String::from(value))
+            .into_iter()
+            .map(|term| build_index_key(table_name, index_name, Value::Str(term)))
+            .collect(),
+        Ok([value]) => build_index_key(table_name, index_name, value).map(|key| vec![key]),
+        Err(values) => {
+            build_composite_index_key(table_name, index_name, values).map(|key| vec![key])
+        }
+    }
+    .map_err(ConflictableTransactionError::Abort)
 }
 
 pub fn build_index_key_prefix(table_name: &str, index_name: &str) -> Vec<u8> {
@@ -295,3 +299,20 @@ pub fn build_index_key(table_name: &str, index_name: &str, value: Value) -> Resu
         .chain(value.to_cmp_be_bytes()?)
         .collect::<Vec<_>>())
 }
+
+/// Key of a composite index, of which the keys sharing the leading values share a prefix too
+pub fn build_composite_index_key(
+    table_name: &str,
+    index_name: &str,
+    values: Vec<Value>,
+) -> Result<Vec<u8>> {
+    let keys = values
+        .into_iter()
+        .map(Key::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(build_index_key_prefix(table_name, index_name)
+        .into_iter()
+        .chain(Key::Composite(keys).to_cmp_be_bytes()?)
+        .collect::<Vec<_>>())
+}
@@ -1,6 +1,7 @@
 #![deny(clippy::str_to_string)]
 
 mod alter_table;
+mod backup;
 mod error;
 mod gc;
 mod index;
@@ -20,7 +21,7 @@ use {
     self::snapshot::Snapshot,
     error::{err_into, tx_err_into},
     gluesql_core::{
-        data::Schema,
+        data::{LegacySchema, Schema},
         error::{Error, Result},
         store::Metadata,
     },
@@ -35,6 +36,10 @@ use {
 /// default transaction timeout : 1 hour
 const DEFAULT_TX_TIMEOUT: u128 = 3600 * 1000;
 
+/// Version of the layout of the schemas kept under `schema/`, stored as `schema_version`.
+/// Databases without it hold their schemas as [`LegacySchema`].
+const SCHEMA_VERSION: u64 = 1;
+
 #[derive(Debug, Clone)]
 pub enum State {
     Idle,
@@ -59,16 +64,29 @@ type ExportData<T> = (u64, Vec<(Vec<u8>, Vec<u8>, T)>);
 impl SledStorage {
     pub fn new<P: AsRef<std::path::Path>>(filename: P) -> Result<Self> {
         let tree = sled::open(filename).map_err(err_into)?;
+
+        Self::open(tree)
+    }
+
+    /// Wraps a database which has just been opened, rolling back the transaction left
+    /// holding the lock by a process which stopped before finishing it, and rewriting the
+    /// schemas of a database written by an earlier version in their current layout.
+    fn open(tree: Db) -> Result<Self> {
+        migrate_schemas(&tree)?;
+
         let id_offset = get_id_offset(&tree)?;
         let state = State::Idle;
         let tx_timeout = Some(DEFAULT_TX_TIMEOUT);
 
-        Ok(Self {
+        let storage = Self {
             tree,
             id_offset,
             state,
             tx_timeout,
-        })
+        };
+        storage.rollback_stale_lock()?;
+
+        Ok(storage)
     }
 
     pub fn set_transaction_timeout(&mut self, tx_timeout: Option<u128>) {
@@ -86,7 +104,11 @@ impl SledStorage {
         let (new_id_offset, data) = export;
         let old_id_offset = get_id_offset(&self.tree)?;
 
+        // an export of a database written by an earlier version has no schema version and
+        // is migrated once imported
+        self.tree.remove("schema_version").map_err(err_into)?;
         self.tree.import(data);
+        migrate_schemas(&self.tree)?;
 
         if new_id_offset > old_id_offset {
             self.tree
@@ -105,19 +127,40 @@ impl TryFrom<Config> for SledStorage {
 
     fn try_from(config: Config) -> Result<Self> {
         let tree = config.open().map_err(err_into)?;
-        let id_offset = get_id_offset(&tree)?;
-        let state = State::Idle;
-        let tx_timeout = Some(DEFAULT_TX_TIMEOUT);
 
-        Ok(Self {
-            tree,
-            id_offset,
-            state,
-            tx_timeout,
-        })
+        Self::open(tree)
     }
 }
 
+fn migrate_schemas(tree: &Db) -> Result<()> {
+    if tree.get("schema_version").map_err(err_into)?.is_some() {
+        return Ok(());
+    }
+
+    let schemas = tree
+        .scan_prefix("schema/")
+        .map(|item| {
+            let (key, value) = item.map_err(err_into)?;
+            let snapshot: Snapshot<LegacySchema> =
+                bincode::deserialize(&value).map_err(err_into)?;
+            let snapshot = bincode::serialize(&snapshot.map(Schema::from)).map_err(err_into)?;
+
+            Ok((key, snapshot))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    tree.transaction(|tree| {
+        for (key, snapshot) in &schemas {
+            tree.insert(key, snapshot.as_slice())?;
+        }
+
+        tree.insert("schema_version", &SCHEMA_VERSION.to_be_bytes())?;
+
+        Ok(())
+    })
+    .map_err(tx_err_into)
+}
+
 fn get_id_offset(tree: &Db) -> Result<u64> {
     tree.get("id_offset")
         .map_err(err_into)?
@@ -148,3 +191,5 @@ fn fetch_schema(
 impl Metadata for SledStorage {}
 impl gluesql_core::store::CustomFunction for SledStorage {}
 impl gluesql_core::store::CustomFunctionMut for SledStorage {}
+impl gluesql_core::store::User for SledStorage {}
+impl gluesql_core::store::UserMut for SledStorage {}
@@ -13,6 +13,29 @@ struct SnapshotItem<T> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Snapshot<T>(Vec<SnapshotItem<T>>);
 
+impl<T> Snapshot<T> {
+    /// Converts the data of each item, keeping the transactions which created and deleted it.
+    pub fn map<U>(self, f: impl Fn(T) -> U) -> Snapshot<U> {
+        let items = self
+            .0
+            .into_iter()
+            .map(
+                |SnapshotItem {
+                     data,
+                     created_by,
+                     deleted_by,
+                 }| SnapshotItem {
+                    data: f(data),
+                    created_by,
+                    deleted_by,
+                },
+            )
+            .collect();
+
+        Snapshot(items)
+    }
+}
+
 impl<T: Clone> Snapshot<T> {
     pub fn new(txid: u64, data: T) -> Self {
         Self(vec![SnapshotItem {
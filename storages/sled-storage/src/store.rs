@@ -3,7 +3,7 @@ use {
     async_trait::async_trait,
     futures::stream::iter,
     gluesql_core::{
-        data::{Key, Schema},
+        data::{Key, Schema, TableStatistics},
         error::{Error, Result},
         store::{DataRow, RowIter, Store},
     },
@@ -16,6 +16,7 @@ impl SledStorage {
 
 #[async_trait]
 impl Store for SledStorage {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let (txid, created_at) = match self.state {
             State::Transaction {
@@ -38,6 +39,7 @@ impl Store for SledStorage {
             .collect::<Result<Vec<_>>>()
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         let (txid, created_at, temp) = match self.state {
             State::Transaction {
@@ -65,6 +67,7 @@ impl Store for SledStorage {
         Ok(schema)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, key: &Key) -> Result<Option<DataRow>> {
         let (txid, created_at) = match self.state {
             State::Transaction {
@@ -93,6 +96,7 @@ impl Store for SledStorage {
         Ok(row)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         let (txid, created_at) = match self.state {
             State::Transaction {
@@ -124,4 +128,15 @@ impl Store for SledStorage {
 
         Ok(Box::pin(iter(result_set)))
     }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
+    async fn fetch_statistics(&self, table_name: &str) -> Result<Option<TableStatistics>> {
+        let key = format!("statistics/{table_name}");
+
+        self.tree
+            .get(key.as_bytes())
+            .map_err(err_into)?
+            .map(|value| bincode::deserialize(&value).map_err(err_into))
+            .transpose()
+    }
 }
@@ -10,7 +10,7 @@ use {
     async_io::block_on,
     async_trait::async_trait,
     gluesql_core::{
-        data::{Key, Schema},
+        data::{Key, Schema, TableStatistics},
         error::{Error, IndexError, Result},
         store::{DataRow, StoreMut},
     },
@@ -19,6 +19,7 @@ use {
 
 #[async_trait]
 impl StoreMut for SledStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let state = &self.state;
         let tx_timeout = self.tx_timeout;
@@ -68,6 +69,7 @@ impl StoreMut for SledStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         let prefix = format!("data/{table_name}/");
         let items = self
@@ -109,6 +111,7 @@ impl StoreMut for SledStorage {
 
             tree.insert(key.as_bytes(), snapshot)?;
             tree.insert(temp_key, key.as_bytes())?;
+            tree.remove(format!("statistics/{table_name}").as_bytes())?;
 
             let index_sync = IndexSync::from_schema(tree, txid, &schema);
 
@@ -157,6 +160,7 @@ impl StoreMut for SledStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn append_data(&mut self, table_name: &str, rows: Vec<DataRow>) -> Result<()> {
         let id_offset = self.id_offset;
         let state = &self.state;
@@ -213,6 +217,7 @@ impl StoreMut for SledStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, rows = rows.len()))]
     async fn insert_data(&mut self, table_name: &str, rows: Vec<(Key, DataRow)>) -> Result<()> {
         let state = &self.state;
         let tx_timeout = self.tx_timeout;
@@ -290,6 +295,7 @@ impl StoreMut for SledStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         let state = &self.state;
         let tx_timeout = self.tx_timeout;
@@ -359,4 +365,16 @@ impl StoreMut for SledStorage {
 
         Ok(())
     }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(table = statistics.table_name))]
+    async fn insert_statistics(&mut self, statistics: TableStatistics) -> Result<()> {
+        let key = format!("statistics/{}", statistics.table_name);
+        let statistics = bincode::serialize(&statistics).map_err(err_into)?;
+
+        self.tree
+            .insert(key.as_bytes(), statistics)
+            .map_err(err_into)?;
+
+        Ok(())
+    }
 }
@@ -214,6 +214,28 @@ impl SledStorage {
             .map_err(tx_err_into)
     }
 
+    /// Rolls back the transaction holding the lock, which cannot be running anymore once the
+    /// database has just been opened.
+    pub(crate) fn rollback_stale_lock(&self) -> Result<()> {
+        let Lock { lock_txid, .. } = self
+            .tree
+            .get("lock/")
+            .map_err(err_into)?
+            .map(|l| bincode::deserialize(&l))
+            .transpose()
+            .map_err(err_into)?
+            .unwrap_or_default();
+
+        let Some(lock_txid) = lock_txid else {
+            return Ok(());
+        };
+
+        self.rollback_txid(lock_txid)?;
+        self.tree
+            .transaction(move |tree| lock::release(tree, lock_txid))
+            .map_err(tx_err_into)
+    }
+
     pub fn check_retry(
         &mut self,
         tx_result: StdResult<TxPayload, TransactionError<Error>>,
@@ -0,0 +1,59 @@
+use {
+    gluesql_core::{
+        error::Error,
+        prelude::{Glue, Value::*},
+        store::BackupError,
+    },
+    gluesql_sled_storage::SledStorage,
+    std::fs::remove_dir_all,
+    test_suite::{concat_with, row, select, stringify_label},
+};
+
+#[tokio::test]
+async fn sled_backup() {
+    let path = "tmp/sled_backup";
+    let target_path = "tmp/sled_backup_target";
+    let _ = remove_dir_all(path);
+    let _ = remove_dir_all(target_path);
+
+    let mut glue = Glue::new(SledStorage::new(path).unwrap());
+    glue.execute("CREATE TABLE Foo (id INTEGER PRIMARY KEY, name TEXT);")
+        .await
+        .unwrap();
+    glue.execute("INSERT INTO Foo VALUES (1, 'Glue'), (2, 'SQL');")
+        .await
+        .unwrap();
+
+    glue.execute("BEGIN;").await.unwrap();
+    glue.execute("INSERT INTO Foo VALUES (3, 'Uncommitted');")
+        .await
+        .unwrap();
+    glue.backup(target_path).await.unwrap();
+    assert_eq!(
+        glue.backup(target_path).await,
+        Err(Error::Backup(BackupError::TargetAlreadyExists(
+            target_path.to_owned()
+        )))
+    );
+    glue.execute("COMMIT;").await.unwrap();
+
+    let mut backup = Glue::new(SledStorage::new(target_path).unwrap());
+    assert_eq!(
+        backup.execute("SELECT id, name FROM Foo").await,
+        Ok(vec![select!(
+            id  | name;
+            I64 | Str;
+            1     "Glue".to_owned();
+            2     "SQL".to_owned()
+        )])
+    );
+
+    backup
+        .execute("INSERT INTO Foo VALUES (3, 'Backup');")
+        .await
+        .unwrap();
+    assert_eq!(
+        backup.execute("SELECT name FROM Foo WHERE id = 3").await,
+        Ok(vec![select!(name Str; "Backup".to_owned())])
+    );
+}
@@ -0,0 +1,84 @@
+use {
+    gluesql_core::{
+        ast::{ColumnDef, DataType, Expr},
+        chrono::NaiveDate,
+        data::{LegacySchema, LegacySchemaIndex, Schema, SchemaIndexOrd},
+        prelude::{Glue, Value::*},
+        store::Store,
+    },
+    gluesql_sled_storage::{SledStorage, sled},
+    std::fs::remove_dir_all,
+    test_suite::{row, select, stringify_label},
+};
+
+#[tokio::test]
+async fn sled_legacy_schema() {
+    let path = "tmp/sled_legacy_schema";
+    let _ = remove_dir_all(path);
+
+    let column_def = |name: &str, data_type| ColumnDef {
+        name: name.to_owned(),
+        data_type,
+        nullable: false,
+        default: None,
+        unique: None,
+        comment: None,
+    };
+    let schema = LegacySchema {
+        table_name: "Legacy".to_owned(),
+        column_defs: Some(vec![
+            column_def("id", DataType::Int),
+            column_def("name", DataType::Text),
+        ]),
+        indexes: vec![LegacySchemaIndex {
+            name: "idx_id".to_owned(),
+            expr: Expr::Identifier("id".to_owned()),
+            order: SchemaIndexOrd::Both,
+            created: NaiveDate::from_ymd_opt(2024, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        }],
+        engine: None,
+        foreign_keys: Vec::new(),
+        comment: Some("written before schemas were versioned".to_owned()),
+    };
+
+    // a snapshot of one item, its data then the transactions which created and deleted it
+    let snapshot = vec![(schema.clone(), 0_u64, None::<u64>)];
+    let tree = sled::open(path).unwrap();
+    tree.insert("schema/Legacy", bincode::serialize(&snapshot).unwrap())
+        .unwrap();
+    tree.flush().unwrap();
+    drop(tree);
+
+    // the file lock is released once the background flusher of the dropped db stops
+    let mut retries = 0;
+    let storage = loop {
+        match SledStorage::new(path) {
+            Ok(storage) => break storage,
+            Err(_) if retries < 100 => {
+                retries += 1;
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            Err(e) => panic!("SledStorage::new: {e:?}"),
+        }
+    };
+    assert_eq!(
+        storage.fetch_schema("Legacy").await.unwrap(),
+        Some(Schema::from(schema))
+    );
+
+    let mut glue = Glue::new(storage);
+    glue.execute("INSERT INTO Legacy VALUES (1, 'Glue'), (2, 'SQL');")
+        .await
+        .unwrap();
+    let actual = glue
+        .execute("SELECT id, name FROM Legacy WHERE id = 2;")
+        .await
+        .unwrap()
+        .into_iter()
+        .next()
+        .unwrap();
+    assert_eq!(actual, select!(id | name; I64 | Str; 2 "SQL".to_owned()));
+}
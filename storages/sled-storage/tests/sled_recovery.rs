@@ -0,0 +1,56 @@
+use {
+    async_trait::async_trait, gluesql_core::prelude::Glue, gluesql_sled_storage::SledStorage,
+    test_suite::*,
+};
+
+struct SledRecoveryTester {
+    glue: Glue<SledStorage>,
+    path: String,
+}
+
+#[async_trait(?Send)]
+impl Tester<SledStorage> for SledRecoveryTester {
+    async fn new(namespace: &str) -> Self {
+        let path = format!("tmp/{namespace}");
+
+        if let Err(e) = std::fs::remove_dir_all(&path) {
+            println!("fs::remove_dir_all {e:?}");
+        }
+
+        let storage = SledStorage::new(&path).expect("SledStorage::new");
+        let glue = Glue::new(storage);
+
+        SledRecoveryTester { glue, path }
+    }
+
+    fn get_glue(&mut self) -> &mut Glue<SledStorage> {
+        &mut self.glue
+    }
+}
+
+#[async_trait(?Send)]
+impl RecoveryTester<SledStorage> for SledRecoveryTester {
+    async fn reopen(self) -> Self {
+        let Self { glue, path } = self;
+        drop(glue);
+
+        // the file lock is released once the background flusher of the dropped db stops
+        let mut retries = 0;
+        let storage = loop {
+            match SledStorage::new(&path) {
+                Ok(storage) => break storage,
+                Err(_) if retries < 100 => {
+                    retries += 1;
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+                Err(e) => panic!("SledStorage::new: {e:?}"),
+            }
+        };
+        let glue = Glue::new(storage);
+
+        SledRecoveryTester { glue, path }
+    }
+}
+
+generate_recovery_tests!(tokio::test, SledRecoveryTester);
+generate_transaction_recovery_tests!(tokio::test, SledRecoveryTester);
@@ -86,11 +86,15 @@ async fn sled_basic() {
     );
 }
 
-generate_store_tests!(tokio::test, SledTester);
-generate_index_tests!(tokio::test, SledTester);
-generate_transaction_tests!(tokio::test, SledTester);
-generate_alter_table_tests!(tokio::test, SledTester);
-generate_alter_table_index_tests!(tokio::test, SledTester);
-generate_transaction_alter_table_tests!(tokio::test, SledTester);
-generate_transaction_index_tests!(tokio::test, SledTester);
-generate_metadata_index_tests!(tokio::test, SledTester);
+generate_conformance_tests!(
+    tokio::test,
+    SledTester,
+    [
+        basic,
+        alter_table,
+        index,
+        transaction,
+        pushdown,
+        concurrency
+    ]
+);
@@ -88,6 +88,7 @@ impl WebStorage {
 
 #[async_trait]
 impl Store for WebStorage {
+    #[tracing::instrument(level = "debug", skip_all)]
     async fn fetch_all_schemas(&self) -> Result<Vec<Schema>> {
         let mut table_names: Vec<String> = self.get(TABLE_NAMES_PATH)?.unwrap_or_default();
         table_names.sort();
@@ -101,10 +102,12 @@ impl Store for WebStorage {
             .collect::<Result<Vec<_>>>()
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_schema(&self, table_name: &str) -> Result<Option<Schema>> {
         self.get(format!("{}/{}", SCHEMA_PATH, table_name))
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn fetch_data(&self, table_name: &str, target: &Key) -> Result<Option<DataRow>> {
         let path = format!("{}/{}", DATA_PATH, table_name);
         let row = self
@@ -116,6 +119,7 @@ impl Store for WebStorage {
         Ok(row)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn scan_data<'a>(&'a self, table_name: &str) -> Result<RowIter<'a>> {
         let path = format!("{}/{}", DATA_PATH, table_name);
         let mut rows = self.get::<Vec<(Key, DataRow)>>(path)?.unwrap_or_default();
@@ -142,6 +146,7 @@ impl Store for WebStorage {
 
 #[async_trait]
 impl StoreMut for WebStorage {
+    #[tracing::instrument(level = "debug", skip_all, fields(table = schema.table_name))]
     async fn insert_schema(&mut self, schema: &Schema) -> Result<()> {
         let mut table_names: Vec<String> = self.get(TABLE_NAMES_PATH)?.unwrap_or_default();
         table_names.push(schema.table_name.clone());
@@ -150,6 +155,7 @@ impl StoreMut for WebStorage {
         self.set(format!("{}/{}", SCHEMA_PATH, schema.table_name), schema)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn delete_schema(&mut self, table_name: &str) -> Result<()> {
         let mut table_names: Vec<String> = self.get(TABLE_NAMES_PATH)?.unwrap_or_default();
         table_names
@@ -163,6 +169,7 @@ impl StoreMut for WebStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn append_data(&mut self, table_name: &str, new_rows: Vec<DataRow>) -> Result<()> {
         let path = format!("{}/{}", DATA_PATH, table_name);
         let rows = self.get::<Vec<(Key, DataRow)>>(&path)?.unwrap_or_default();
@@ -177,6 +184,7 @@ impl StoreMut for WebStorage {
         self.set(path, rows)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name))]
     async fn insert_data(&mut self, table_name: &str, new_rows: Vec<(Key, DataRow)>) -> Result<()> {
         let path = format!("{}/{}", DATA_PATH, table_name);
         let mut rows = self.get::<Vec<(Key, DataRow)>>(&path)?.unwrap_or_default();
@@ -192,6 +200,7 @@ impl StoreMut for WebStorage {
         self.set(path, rows)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(table = table_name, keys = keys.len()))]
     async fn delete_data(&mut self, table_name: &str, keys: Vec<Key>) -> Result<()> {
         let path = format!("{}/{}", DATA_PATH, table_name);
         let mut rows = self.get::<Vec<(Key, DataRow)>>(&path)?.unwrap_or_default();
@@ -213,3 +222,6 @@ impl Transaction for WebStorage {}
 impl Metadata for WebStorage {}
 impl CustomFunction for WebStorage {}
 impl CustomFunctionMut for WebStorage {}
+impl gluesql_core::store::User for WebStorage {}
+impl gluesql_core::store::UserMut for WebStorage {}
+impl gluesql_core::store::Backup for WebStorage {}
@@ -1,12 +1,14 @@
 use {
     crate::*,
+    bigdecimal::BigDecimal,
     gluesql_core::{
         ast::*,
-        data::Value::*,
-        error::{AlterError, AlterTableError, EvaluateError, TranslateError},
-        executor::Referencing,
+        data::{Literal, Value::*, value::ConvertError},
+        error::{AlterError, AlterTableError, EvaluateError, TranslateError, ValueError},
+        executor::{Referencing, ValidateError},
         prelude::Payload,
     },
+    std::borrow::Cow,
 };
 
 test_case!(alter_table_rename, {
@@ -255,3 +257,115 @@ test_case!(alter_table_add_drop, {
         g.test(sql, expected).await;
     }
 });
+
+test_case!(alter_table_column_type, {
+    let g = get_tester!();
+
+    let test_cases = [
+        (
+            "CREATE TABLE Item (
+                id INTEGER PRIMARY KEY,
+                price TEXT,
+                qty INTEGER DEFAULT 1,
+                code TEXT UNIQUE
+            );",
+            Ok(Payload::Create),
+        ),
+        (
+            "INSERT INTO Item VALUES (1, '10', 2, '1'), (2, '25', 3, 'a');",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "ALTER TABLE Item ALTER COLUMN price TYPE INTEGER;",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "SELECT id, price FROM Item;",
+            Ok(select!(id | price; I64 | I64; 1 10; 2 25)),
+        ),
+        (
+            "ALTER TABLE Item ALTER COLUMN price SET DATA TYPE DECIMAL USING price * qty;",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "SELECT id, price FROM Item;",
+            Ok(select!(id | price; I64 | Decimal; 1 20.into(); 2 75.into())),
+        ),
+        (
+            "ALTER TABLE Item ALTER COLUMN code TYPE INTEGER;",
+            Err(ConvertError {
+                value: Str("a".to_owned()),
+                data_type: DataType::Int,
+            }
+            .into()),
+        ),
+        (
+            "SELECT id, code FROM Item;",
+            Ok(select!(id | code; I64 | Str; 1 "1".to_owned(); 2 "a".to_owned())),
+        ),
+        (
+            "ALTER TABLE Item ALTER COLUMN code TYPE INTEGER USING 0;",
+            Err(ValidateError::DuplicateEntryOnUniqueField(I64(0), "code".to_owned()).into()),
+        ),
+        (
+            "ALTER TABLE Item ALTER COLUMN code TYPE FLOAT USING 1.5;",
+            Err(
+                AlterError::UnsupportedDataTypeForUniqueColumn("code".to_owned(), DataType::Float)
+                    .into(),
+            ),
+        ),
+        (
+            "ALTER TABLE Item ALTER COLUMN qty TYPE BOOLEAN;",
+            Err(ValueError::IncompatibleLiteralForDataType {
+                data_type: DataType::Boolean,
+                literal: format!("{:?}", Literal::Number(Cow::Owned(BigDecimal::from(1)))),
+            }
+            .into()),
+        ),
+        (
+            "ALTER TABLE Item ALTER COLUMN id TYPE TEXT USING 'item-' || id;",
+            Ok(Payload::AlterTable),
+        ),
+        (
+            "SELECT id, price FROM Item WHERE id = 'item-2';",
+            Ok(select!(id | price; Str | Decimal; "item-2".to_owned() 75.into())),
+        ),
+        (
+            "ALTER TABLE Item ALTER COLUMN nothing TYPE TEXT;",
+            Err(AlterTableError::AlteringColumnNotFound("nothing".to_owned()).into()),
+        ),
+        (
+            "CREATE TABLE Referenced (id INTEGER PRIMARY KEY);",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE TABLE Referencing (
+                id INTEGER,
+                referenced_id INTEGER,
+                FOREIGN KEY (referenced_id) REFERENCES Referenced (id)
+            );",
+            Ok(Payload::Create),
+        ),
+        (
+            "ALTER TABLE Referencing ALTER COLUMN referenced_id TYPE TEXT",
+            Err(AlterError::CannotAlterReferencingColumn {
+                referencing: Referencing {
+                    table_name: "Referencing".to_owned(),
+                    foreign_key: ForeignKey {
+                        name: "FK_referenced_id-Referenced_id".to_owned(),
+                        referencing_column_name: "referenced_id".to_owned(),
+                        referenced_table_name: "Referenced".to_owned(),
+                        referenced_column_name: "id".to_owned(),
+                        on_delete: ReferentialAction::NoAction,
+                        on_update: ReferentialAction::NoAction,
+                    },
+                },
+            }
+            .into()),
+        ),
+    ];
+
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+});
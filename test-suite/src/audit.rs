@@ -0,0 +1,64 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AuditError, TranslateError, ValueError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(audit, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER, name TEXT);").await;
+
+    let test_cases = [
+        ("SET audit = ON", Err(AuditError::SinkNotFound.into())),
+        (
+            "SET verbose = ON",
+            Err(TranslateError::UnsupportedSetVariable("verbose".to_owned()).into()),
+        ),
+        (
+            "SET audit = 3",
+            Err(TranslateError::InvalidSetVariableValue {
+                name: "audit".to_owned(),
+                value: "3".to_owned(),
+            }
+            .into()),
+        ),
+        ("SET audit_table = 'AuditLog'", Ok(Payload::SetVariable)),
+        (
+            "INSERT INTO Item VALUES (1, 'Glue'), (2, 'SQL')",
+            Ok(Payload::Insert(2)),
+        ),
+        (
+            "SELECT Item.name FROM Item JOIN (SELECT 1 AS id) AS One ON Item.id = One.id",
+            Ok(select!(name Str; "Glue".to_owned())),
+        ),
+        (
+            "SELECT id / 0 AS ratio FROM Item",
+            Err(ValueError::DivisorShouldNotBeZero.into()),
+        ),
+        ("SET audit = OFF", Ok(Payload::SetVariable)),
+        ("DELETE FROM Item", Ok(Payload::Delete(2))),
+    ];
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+
+    g.test(
+        "SELECT user_name, statement, tables, row_count, error FROM AuditLog ORDER BY executed_at",
+        Ok(select_with_null!(
+            user_name | statement           | tables             | row_count | error;
+            Null        Str("SET".to_owned())    Str("AuditLog".to_owned()) Null      Null;
+            Null        Str("INSERT".to_owned()) Str("Item".to_owned())     I64(2)    Null;
+            Null        Str("SELECT".to_owned()) Str("Item".to_owned())     I64(1)    Null;
+            Null        Str("SELECT".to_owned()) Str("Item".to_owned())     Null      Str("value: the divisor should not be zero".to_owned());
+            Null        Str("SET".to_owned())    Str("".to_owned())         Null      Null
+        )),
+    )
+    .await;
+
+    g.test("SET audit = ON", Ok(Payload::SetVariable)).await;
+    g.test("SELECT COUNT(*) AS n FROM AuditLog", Ok(select!(n I64; 6)))
+        .await;
+});
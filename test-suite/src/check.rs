@@ -0,0 +1,63 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::ExecuteError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(check, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT NULL)")
+        .await;
+    g.run("CREATE TABLE Copy (id INTEGER, name TEXT NULL)")
+        .await;
+    g.run("INSERT INTO Item VALUES (1, 'Glue'), (2, NULL), (3, 'SQL')")
+        .await;
+    g.run("INSERT INTO Copy VALUES (3, 'SQL'), (1, 'Glue'), (2, NULL)")
+        .await;
+
+    let checksum = |payload: Payload| match payload {
+        Payload::Select { rows, .. } => rows
+            .into_iter()
+            .find(|row| row[1] == Str("CHECKSUM".to_owned()))
+            .map(|row| row[3].clone()),
+        _ => None,
+    };
+    let item = checksum(g.run("CHECK TABLE Item").await);
+    let copy = checksum(g.run("check table Copy").await);
+    assert!(matches!(&item, Some(Str(checksum)) if checksum.len() == 32));
+    assert_eq!(
+        item, copy,
+        "checksums do not depend on the row order or the storage keys"
+    );
+
+    g.run("DELETE FROM Copy WHERE id = 2").await;
+    let copy = checksum(g.run("CHECK TABLE Copy").await);
+    assert_ne!(item, copy);
+
+    let payloads = g
+        .get_glue()
+        .execute("CHECK DATABASE; SELECT 1 AS n;")
+        .await
+        .unwrap();
+    assert!(matches!(&payloads[0], Payload::Select { rows, .. } if rows.len() == 5));
+    assert_eq!(payloads[1], select!(n I64; 1));
+
+    g.test(
+        r#"CHECK TABLE "Item""#,
+        Ok(select_with_null!(
+            TABLE_NAME | CHECK | STATUS | DETAIL;
+            Str("Item".to_owned()) Str("ROW_TYPE".to_owned()) Str("OK".to_owned()) Null;
+            Str("Item".to_owned()) Str("PRIMARY_KEY".to_owned()) Str("OK".to_owned()) Null;
+            Str("Item".to_owned()) Str("CHECKSUM".to_owned()) Str("OK".to_owned()) item.unwrap()
+        )),
+    )
+    .await;
+    g.test(
+        "CHECK TABLE Missing",
+        Err(ExecuteError::TableNotFound("Missing".to_owned()).into()),
+    )
+    .await;
+});
@@ -0,0 +1,71 @@
+use {
+    crate::*,
+    futures::future::try_join_all,
+    gluesql_core::{
+        prelude::{Glue, Result, Value::*},
+        store::{GStore, GStoreMut},
+    },
+};
+
+const WRITERS: i64 = 4;
+const ROWS: i64 = 10;
+
+/// Writes through several [`Glue`]s sharing clones of the storage at the same time, so it
+/// is only for storages whose clones share their data.
+pub async fn writes<T>(mut tester: impl Tester<T>)
+where
+    T: GStore + GStoreMut + Clone,
+{
+    let glue = tester.get_glue();
+    glue.execute("CREATE TABLE Item (id INTEGER, writer INTEGER)")
+        .await
+        .unwrap();
+
+    let writers = (0..WRITERS).map(|writer| {
+        let mut glue = Glue::new(glue.storage.clone());
+
+        async move {
+            glue.execute(format!("CREATE TABLE Writer{writer} (id INTEGER)"))
+                .await?;
+
+            for id in 0..ROWS {
+                glue.execute(format!("INSERT INTO Item VALUES ({id}, {writer})"))
+                    .await?;
+                glue.execute(format!("INSERT INTO Writer{writer} VALUES ({id})"))
+                    .await?;
+            }
+
+            glue.execute(format!(
+                "DELETE FROM Item WHERE writer = {writer} AND id % 2 = 0"
+            ))
+            .await?;
+
+            Result::<()>::Ok(())
+        }
+    });
+    try_join_all(writers).await.unwrap();
+
+    let mut other = Glue::new(glue.storage.clone());
+    for glue in [glue, &mut other] {
+        assert_eq!(
+            glue.execute("SELECT writer, COUNT(*) FROM Item GROUP BY writer ORDER BY writer")
+                .await,
+            Ok(vec![select!(
+                writer | "COUNT(*)"
+                I64    | I64;
+                0        5;
+                1        5;
+                2        5;
+                3        5
+            )])
+        );
+
+        for writer in 0..WRITERS {
+            assert_eq!(
+                glue.execute(format!("SELECT COUNT(*) FROM Writer{writer}"))
+                    .await,
+                Ok(vec![select!("COUNT(*)" I64; ROWS)])
+            );
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use {
+    crate::*,
+    gluesql_core::{error::EvaluateError, prelude::Value::*},
+};
+
+test_case!(correlated_subquery, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Users (id INTEGER, name TEXT)").await;
+    g.run("INSERT INTO Users VALUES (1, 'Ada'), (2, 'Bob'), (3, 'Cid'), (NULL, 'Dan')")
+        .await;
+    g.run("CREATE TABLE Score (user_id INTEGER NULL, score INTEGER, game TEXT)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Score VALUES
+            (1, 10, 'chess'),
+            (1, 30, 'go'),
+            (2, 20, 'chess'),
+            (2, 5, 'go'),
+            (NULL, 99, 'chess');
+    ",
+    )
+    .await;
+
+    g.named_test(
+        "aggregate of the rows matching the outer row",
+        "
+        SELECT
+            u.name,
+            (SELECT MAX(score) FROM Score s WHERE s.user_id = u.id) AS best,
+            (SELECT SUM(score) FROM Score WHERE user_id = u.id AND game = 'chess') AS chess
+        FROM Users u
+        ORDER BY u.name
+        ",
+        Ok(select_with_null!(
+            name                  | best    | chess;
+            Str("Ada".to_owned())   I64(30)   I64(10);
+            Str("Bob".to_owned())   I64(20)   I64(20);
+            Str("Cid".to_owned())   Null      Null;
+            Str("Dan".to_owned())   Null      Null
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "outer expression in the correlated comparison",
+        "
+        SELECT id, (SELECT MIN(score) FROM Score WHERE Score.user_id = Users.id + 1) AS next
+        FROM Users
+        WHERE id IS NOT NULL
+        ORDER BY id
+        ",
+        Ok(select_with_null!(
+            id     | next;
+            I64(1)   I64(5);
+            I64(2)   Null;
+            I64(3)   Null
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "COUNT of the matching rows",
+        "SELECT name, (SELECT COUNT(*) FROM Score WHERE user_id = id) AS games FROM Users ORDER BY name",
+        Ok(select_with_null!(
+            name                  | games;
+            Str("Ada".to_owned())   I64(2);
+            Str("Bob".to_owned())   I64(2);
+            Str("Cid".to_owned())   Null;
+            Str("Dan".to_owned())   Null
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "non aggregated subquery",
+        "
+        SELECT name, (SELECT game FROM Score WHERE user_id = id AND score > 15) AS game
+        FROM Users
+        ORDER BY name
+        ",
+        Ok(select_with_null!(
+            name                  | game;
+            Str("Ada".to_owned())   Str("go".to_owned());
+            Str("Bob".to_owned())   Str("chess".to_owned());
+            Str("Cid".to_owned())   Null;
+            Str("Dan".to_owned())   Null
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "subquery returning more than one row",
+        "SELECT name, (SELECT score FROM Score WHERE user_id = id) AS score FROM Users",
+        Err(EvaluateError::MoreThanOneRowReturned.into()),
+    )
+    .await;
+});
@@ -0,0 +1,136 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{FetchError, TranslateError},
+        prelude::Value::*,
+    },
+};
+
+test_case!(cte, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Employee (id INTEGER, name TEXT, manager_id INTEGER NULL)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Employee VALUES
+            (1, 'Ada', NULL),
+            (2, 'Bob', 1),
+            (3, 'Cid', 1),
+            (4, 'Dan', 2),
+            (5, 'Eve', 4);
+    ",
+    )
+    .await;
+    g.run("CREATE TABLE Edge (src INTEGER, dst INTEGER)").await;
+    g.run("INSERT INTO Edge VALUES (1, 2), (2, 3), (3, 1), (3, 4)")
+        .await;
+
+    g.named_test(
+        "non recursive WITH",
+        "
+        WITH Manager AS (SELECT id, name FROM Employee WHERE manager_id IS NULL),
+             Report (report_name, boss_id) AS (SELECT name, manager_id FROM Employee)
+        SELECT Report.report_name FROM Report JOIN Manager ON Manager.id = Report.boss_id
+        ORDER BY report_name
+        ",
+        Ok(select!(report_name Str; "Bob".to_owned(); "Cid".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "WITH in a subquery",
+        "
+        SELECT name FROM Employee
+        WHERE id IN (WITH Leaf AS (SELECT id FROM Employee WHERE id > 3) SELECT id FROM Leaf)
+        ",
+        Ok(select!(name Str; "Dan".to_owned(); "Eve".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "counting with WITH RECURSIVE",
+        "
+        WITH RECURSIVE Counter (n) AS (
+            SELECT 1
+            UNION ALL
+            SELECT n + 1 FROM Counter WHERE n < 5
+        )
+        SELECT n, n * n AS square FROM Counter
+        ",
+        Ok(select!(
+            n   | square;
+            I64 | I64;
+            1     1;
+            2     4;
+            3     9;
+            4     16;
+            5     25
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "org chart with a join",
+        "
+        WITH RECURSIVE Chain (id, name, depth) AS (
+            SELECT id, name, 0 FROM Employee WHERE manager_id IS NULL
+            UNION ALL
+            SELECT e.id, e.name, c.depth + 1
+            FROM Employee e JOIN Chain c ON e.manager_id = c.id
+        )
+        SELECT name, depth FROM Chain ORDER BY depth, name
+        ",
+        Ok(select!(
+            name            | depth;
+            Str             | I64;
+            "Ada".to_owned()  0;
+            "Bob".to_owned()  1;
+            "Cid".to_owned()  1;
+            "Dan".to_owned()  2;
+            "Eve".to_owned()  3
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "graph reachability with UNION stops on cycles",
+        "
+        WITH RECURSIVE Reachable (node) AS (
+            SELECT 1
+            UNION
+            SELECT Edge.dst FROM Edge JOIN Reachable ON Edge.src = Reachable.node
+        )
+        SELECT node FROM Reachable ORDER BY node
+        ",
+        Ok(select!(node I64; 1; 2; 3; 4)),
+    )
+    .await;
+
+    g.named_test(
+        "recursive table with an alias",
+        "
+        WITH RECURSIVE Counter (n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM Counter WHERE n < 3)
+        SELECT c.n FROM Counter AS c WHERE c.n > 1
+        ",
+        Ok(select!(n I64; 2; 3)),
+    )
+    .await;
+
+    g.named_test(
+        "recursion without an end",
+        "
+        WITH RECURSIVE Counter (n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM Counter)
+        SELECT * FROM Counter
+        ",
+        Err(FetchError::RecursionDepthExceeded("Counter".to_owned(), 1000).into()),
+    )
+    .await;
+
+    g.named_test(
+        "same WITH query name twice",
+        "WITH a AS (SELECT 1), a AS (SELECT 2) SELECT * FROM a",
+        Err(TranslateError::DuplicateCteName("a".to_owned()).into()),
+    )
+    .await;
+});
@@ -0,0 +1,96 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::TranslateError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(explain, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER, name TEXT, price INTEGER)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Item VALUES
+            (1, 'Glue', 100),
+            (2, 'SQL', 200),
+            (3, 'Rust', 300),
+            (4, 'Glue', 400);
+    ",
+    )
+    .await;
+
+    macro_rules! s {
+        ($v: literal) => {
+            Str($v.to_owned())
+        };
+    }
+
+    g.test(
+        "EXPLAIN SELECT id, name FROM Item WHERE price > 150 ORDER BY id DESC LIMIT 2",
+        Ok(select_with_null!(
+            ID     | PARENT_ID | OPERATOR     | DETAIL;
+            I64(1)   Null        s!("LIMIT")    s!("LIMIT 2");
+            I64(2)   I64(1)      s!("SORT")     s!("id DESC");
+            I64(3)   I64(2)      s!("PROJECT")  s!("id, name");
+            I64(4)   I64(3)      s!("FILTER")   s!("price > 150");
+            I64(5)   I64(4)      s!("SCAN")     s!("Item")
+        )),
+    )
+    .await;
+
+    g.test(
+        "EXPLAIN SELECT name, COUNT(*) FROM Item GROUP BY name",
+        Ok(select_with_null!(
+            ID     | PARENT_ID | OPERATOR       | DETAIL;
+            I64(1)   Null        s!("PROJECT")    s!("name, COUNT(*)");
+            I64(2)   I64(1)      s!("AGGREGATE")  s!("GROUP BY name");
+            I64(3)   I64(2)      s!("SCAN")       s!("Item")
+        )),
+    )
+    .await;
+
+    g.test(
+        "EXPLAIN VALUES (1), (2)",
+        Ok(select_with_null!(
+            ID     | PARENT_ID | OPERATOR    | DETAIL;
+            I64(1)   Null        s!("VALUES")  s!("2 rows")
+        )),
+    )
+    .await;
+
+    let counts = |payload: Payload| match payload {
+        Payload::Select { labels, rows } => {
+            assert_eq!(
+                labels,
+                ["ID", "PARENT_ID", "OPERATOR", "DETAIL", "ROWS", "ELAPSED"]
+            );
+
+            rows.into_iter()
+                .map(|row| (row[2].clone(), row[4].clone()))
+                .collect::<Vec<_>>()
+        }
+        _ => Vec::new(),
+    };
+    // `price - 50` is not a column the storage can filter by, so the scan reads every row
+    let payload = g
+        .run("EXPLAIN ANALYZE SELECT id FROM Item WHERE price - 50 > 100 ORDER BY id DESC")
+        .await;
+    assert_eq!(
+        counts(payload),
+        [
+            (s!("SORT"), I64(3)),
+            (s!("PROJECT"), I64(3)),
+            (s!("FILTER"), I64(3)),
+            (s!("SCAN"), I64(4)),
+        ]
+    );
+
+    g.test(
+        "EXPLAIN DELETE FROM Item",
+        Err(TranslateError::UnsupportedExplainStatement("DELETE FROM Item".to_owned()).into()),
+    )
+    .await;
+});
@@ -0,0 +1,107 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{FetchError, TranslateError},
+        prelude::{DataType, Payload, Value::*},
+    },
+};
+
+test_case!(fake, {
+    let g = get_tester!();
+
+    g.test(
+        "SELECT * FROM FAKE(3, 'id SEQUENCE')",
+        Ok(select!(
+            id
+            I64;
+            1;
+            2;
+            3
+        )),
+    )
+    .await;
+    g.test("SELECT F.n FROM fake(0, 'n INTEGER') AS F", Ok(select!(n)))
+        .await;
+
+    let columns = "id SEQUENCE, name NAME, email EMAIL, age INTEGER(18, 65), score FLOAT(0, 10), \
+                   active BOOLEAN, joined DATE(2020-01-01, 2020-12-31), uid UUID";
+    g.type_match(
+        &format!("SELECT * FROM FAKE(5, '{columns}', 42)"),
+        &[
+            DataType::Int,
+            DataType::Text,
+            DataType::Text,
+            DataType::Int,
+            DataType::Float,
+            DataType::Boolean,
+            DataType::Date,
+            DataType::Uuid,
+        ],
+    )
+    .await;
+
+    let seeded = g
+        .run(&format!("SELECT * FROM FAKE(20, '{columns}', 42)"))
+        .await;
+    assert_eq!(
+        g.run(&format!("SELECT * FROM FAKE(20, '{columns}', 42)"))
+            .await,
+        seeded,
+        "the same seed generates the same rows"
+    );
+    assert_ne!(
+        g.run(&format!("SELECT * FROM FAKE(20, '{columns}', 43)"))
+            .await,
+        seeded,
+    );
+
+    g.count(
+        "SELECT * FROM FAKE(200, 'age INTEGER(18, 65), joined DATE(2020-01-01, 2020-12-31)')
+         WHERE age < 18 OR age > 65 OR joined < '2020-01-01' OR joined > '2020-12-31'",
+        0,
+    )
+    .await;
+    g.count(
+        "SELECT * FROM FAKE(50, 'email EMAIL') WHERE email LIKE '%@example.com'",
+        50,
+    )
+    .await;
+
+    g.run("CREATE TABLE Person (id INTEGER, name TEXT, age INTEGER)")
+        .await;
+    g.test(
+        "INSERT INTO Person SELECT * FROM FAKE(1000, 'id SEQUENCE, name NAME, age INTEGER(0, 99)', 1)",
+        Ok(Payload::Insert(1000)),
+    )
+    .await;
+    g.count("SELECT * FROM Person WHERE id BETWEEN 1 AND 1000", 1000)
+        .await;
+
+    g.test(
+        "CREATE TABLE FakeTable AS SELECT * FROM FAKE(10, 'id SEQUENCE, uid UUID')",
+        Ok(Payload::Create),
+    )
+    .await;
+    g.count("SELECT * FROM FakeTable", 10).await;
+
+    g.test(
+        "SELECT * FROM FAKE(-1, 'id SEQUENCE')",
+        Err(FetchError::FakeSizeWrong(-1).into()),
+    )
+    .await;
+    g.test(
+        "SELECT * FROM FAKE(3)",
+        Err(TranslateError::FakeArgsWrong.into()),
+    )
+    .await;
+    g.test(
+        "SELECT * FROM FAKE(3, 1)",
+        Err(TranslateError::FakeArgsWrong.into()),
+    )
+    .await;
+    g.test(
+        "SELECT * FROM FAKE(3, 'id COLOR')",
+        Err(TranslateError::InvalidFakeColumn("id COLOR".to_owned()).into()),
+    )
+    .await;
+});
@@ -7,7 +7,7 @@ use {
         },
         error::{DeleteError, InsertError, TranslateError, UpdateError},
         executor::{AlterError, Referencing},
-        prelude::Payload,
+        prelude::{Payload, Value::*},
     },
 };
 
@@ -87,18 +87,6 @@ test_case!(foreign_key, {
     )
     .await;
 
-    g.named_test(
-        "Unsupported foreign key option: CASCADE",
-        "CREATE TABLE ReferencingTable (
-            id INT,
-            name TEXT,
-            referenced_id INT,
-            FOREIGN KEY (referenced_id) REFERENCES ReferencedTableWithPK (id) ON DELETE CASCADE
-        );",
-        Err(TranslateError::UnsupportedConstraint("CASCADE".to_owned()).into()),
-    )
-    .await;
-
     g.named_test(
         "Unsupported foreign key option: SET DEFAULT",
         "CREATE TABLE ReferencingTable (
@@ -111,18 +99,6 @@ test_case!(foreign_key, {
     )
     .await;
 
-    g.named_test(
-        "Unsupported foreign key option: SET NULL",
-        "CREATE TABLE ReferencingTable (
-            id INT,
-            name TEXT,
-            referenced_id INT,
-            FOREIGN KEY (referenced_id) REFERENCES ReferencedTableWithPK (id) ON DELETE SET NULL
-        );",
-        Err(TranslateError::UnsupportedConstraint("SET NULL".to_owned()).into()),
-    )
-    .await;
-
     g.named_test(
         "Referencing column not found",
         "CREATE TABLE ReferencingTable (
@@ -148,7 +124,7 @@ test_case!(foreign_key, {
     .await;
 
     g.named_test(
-        "Creating table with foreign key should be succeeded if referenced table has primary key. NO ACTION(=RESTRICT) is default",
+        "Creating table with foreign key should be succeeded if referenced table has primary key. NO ACTION is default",
         "CREATE TABLE ReferencingTable (
             id INT,
             name TEXT,
@@ -284,7 +260,7 @@ test_case!(foreign_key, {
                         referenced_table_name: "ReferencedTableWithPK".to_owned(),
                         referenced_column_name: "id".to_owned(),
                         on_delete: ReferentialAction::NoAction,
-                        on_update: ReferentialAction::NoAction,
+                        on_update: ReferentialAction::Restrict,
                     },
                 },
                 Referencing {
@@ -330,3 +306,142 @@ test_case!(foreign_key, {
     )
     .await;
 });
+
+test_case!(referential_action, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Author (id INTEGER PRIMARY KEY, name TEXT);")
+        .await;
+    g.run(
+        "CREATE TABLE Book (
+            id INTEGER PRIMARY KEY,
+            author_id INTEGER,
+            FOREIGN KEY (author_id) REFERENCES Author (id) ON DELETE CASCADE
+        );",
+    )
+    .await;
+    g.run(
+        "CREATE TABLE Review (
+            id INTEGER PRIMARY KEY,
+            book_id INTEGER,
+            FOREIGN KEY (book_id) REFERENCES Book (id) ON DELETE SET NULL
+        );",
+    )
+    .await;
+    g.run("INSERT INTO Author VALUES (1, 'Ann'), (2, 'Bob');")
+        .await;
+    g.run("INSERT INTO Book VALUES (10, 1), (11, 1), (20, 2);")
+        .await;
+    g.run("INSERT INTO Review VALUES (100, 10), (101, 11), (200, 20);")
+        .await;
+
+    g.named_test(
+        "ON DELETE CASCADE deletes the referencing rows, and counts only the rows of the table",
+        "DELETE FROM Author WHERE id = 1;",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+    g.test(
+        "SELECT id, author_id FROM Book",
+        Ok(select!(id | author_id; I64 | I64; 20 2)),
+    )
+    .await;
+    g.named_test(
+        "ON DELETE SET NULL sets the referencing column of cascaded deletions to NULL",
+        "SELECT id, book_id FROM Review",
+        Ok(select_with_null!(
+            id  | book_id;
+            I64(100)    Null;
+            I64(101)    Null;
+            I64(200)    I64(20)
+        )),
+    )
+    .await;
+
+    g.run(
+        "CREATE TABLE Loan (
+            id INTEGER PRIMARY KEY,
+            book_id INTEGER,
+            FOREIGN KEY (book_id) REFERENCES Book (id) ON DELETE RESTRICT
+        );",
+    )
+    .await;
+    g.run("INSERT INTO Loan VALUES (1000, 20);").await;
+
+    g.named_test(
+        "RESTRICT on a cascaded deletion fails the deletion",
+        "DELETE FROM Book WHERE id = 20;",
+        Err(DeleteError::ReferencingColumnExists("Loan.book_id".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "RESTRICT further down a cascade fails the deletion before any row is deleted",
+        "DELETE FROM Author WHERE id = 2;",
+        Err(DeleteError::ReferencingColumnExists("Loan.book_id".to_owned()).into()),
+    )
+    .await;
+    g.test("SELECT id FROM Author", Ok(select!(id; I64; 2)))
+        .await;
+
+    g.run("DELETE FROM Loan;").await;
+    g.run("DELETE FROM Author WHERE id = 2;").await;
+    g.test("SELECT * FROM Book", Ok(select!(id | author_id)))
+        .await;
+    g.test(
+        "SELECT id, book_id FROM Review",
+        Ok(select_with_null!(
+            id  | book_id;
+            I64(100)    Null;
+            I64(101)    Null;
+            I64(200)    Null
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "SET NULL needs a nullable referencing column",
+        "CREATE TABLE Note (
+            id INTEGER PRIMARY KEY,
+            author_id INTEGER NOT NULL,
+            FOREIGN KEY (author_id) REFERENCES Author (id) ON DELETE SET NULL
+        );",
+        Err(AlterError::SetNullOnNotNullColumn("author_id".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "SET DEFAULT is not supported",
+        "CREATE TABLE Note (
+            id INTEGER PRIMARY KEY,
+            author_id INTEGER,
+            FOREIGN KEY (author_id) REFERENCES Author (id) ON DELETE SET DEFAULT
+        );",
+        Err(TranslateError::UnsupportedConstraint("SET DEFAULT".to_owned()).into()),
+    )
+    .await;
+
+    g.run(
+        "CREATE TABLE Category (
+            id INTEGER PRIMARY KEY,
+            parent_id INTEGER,
+            FOREIGN KEY (parent_id) REFERENCES Category (id) ON DELETE CASCADE ON UPDATE CASCADE
+        );",
+    )
+    .await;
+    for sql in [
+        "INSERT INTO Category VALUES (1, NULL), (4, NULL);",
+        "INSERT INTO Category VALUES (2, 1);",
+        "INSERT INTO Category VALUES (3, 2);",
+    ] {
+        g.run(sql).await;
+    }
+
+    g.named_test(
+        "CASCADE follows a self referencing table down to the leaves",
+        "DELETE FROM Category WHERE id = 1;",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+    g.test("SELECT id FROM Category", Ok(select!(id; I64; 4)))
+        .await;
+});
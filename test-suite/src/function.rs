@@ -28,12 +28,14 @@ pub mod hex;
 pub mod ifnull;
 pub mod initcap;
 pub mod is_empty;
+pub mod json;
 pub mod keys;
 pub mod last_day;
 pub mod left_right;
 pub mod length;
 pub mod lpad_rpad;
 pub mod ltrim_rtrim;
+pub mod match_against;
 pub mod math_function;
 pub mod md5;
 pub mod now;
@@ -43,6 +45,7 @@ pub mod position;
 pub mod prepend;
 pub mod radians;
 pub mod rand;
+pub mod regexp;
 pub mod repeat;
 pub mod replace;
 pub mod reverse;
@@ -0,0 +1,130 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{EvaluateError, TranslateError, ValueError},
+        prelude::{
+            Payload,
+            Value::{self, *},
+        },
+    },
+};
+
+test_case!(json, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Post (id INTEGER, doc MAP NULL)").await;
+    g.run(
+        r#"
+        INSERT INTO Post VALUES
+            (1, '{"title": "Glue", "author": {"name": "Ann", "tags": ["rust", "sql"]}}'),
+            (2, '{"title": "Sled", "author": {"name": "Bo"}}'),
+            (3, NULL);
+        "#,
+    )
+    .await;
+
+    let m = |s: &str| Value::parse_json_map(s).unwrap();
+    let l = |s: &str| Value::parse_json_list(s).unwrap();
+    let s = |v: &str| Str(v.to_owned());
+
+    g.test(
+        "
+        SELECT
+            id,
+            JSON_EXTRACT(doc, '$.author.name') AS name,
+            JSON_EXTRACT(doc, '$.author.tags[1]') AS tag,
+            JSON_EXTRACT(doc, '$.author.tags') AS tags
+        FROM Post
+        ",
+        Ok(select_with_null!(
+            id     | name     | tag      | tags;
+            I64(1)   s("Ann")   s("sql")   l(r#"["rust", "sql"]"#);
+            I64(2)   s("Bo")    Null       Null;
+            I64(3)   Null       Null       Null
+        )),
+    )
+    .await;
+
+    g.test(
+        "
+        SELECT
+            id,
+            doc -> 'author' AS author,
+            doc -> 'author' ->> 'name' AS name,
+            doc -> 'author' -> 'tags' -> 0 AS tag,
+            doc ->> 'author' AS author_text
+        FROM Post
+        WHERE (doc -> 'author' ->> 'name') = 'Ann'
+        ",
+        Ok(select_with_null!(
+            id     | author                                           | name     | tag       | author_text;
+            I64(1)   m(r#"{"name": "Ann", "tags": ["rust", "sql"]}"#)   s("Ann")   s("rust")   s(r#"{"name":"Ann","tags":["rust","sql"]}"#)
+        )),
+    )
+    .await;
+
+    g.test(
+        "
+        UPDATE Post
+        SET doc = JSON_REMOVE(JSON_SET(doc, '$.author.age', 30), '$.title')
+        WHERE id = 2
+        ",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+    g.test(
+        "UPDATE Post SET doc = JSON_SET(doc, '$.author.tags[5]', 'glue') WHERE id = 1",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+    g.test(
+        "SELECT id, doc FROM Post WHERE id < 3",
+        Ok(select_with_null!(
+            id     | doc;
+            I64(1)   m(r#"{"title": "Glue", "author": {"name": "Ann", "tags": ["rust", "sql", "glue"]}}"#);
+            I64(2)   m(r#"{"author": {"name": "Bo", "age": 30}}"#)
+        )),
+    )
+    .await;
+
+    g.test(
+        "SELECT JSON_SET(doc, '$.id', id) AS doc FROM Post WHERE id = 3",
+        Ok(select_with_null!(doc; Null)),
+    )
+    .await;
+    g.test(
+        "SELECT JSON_EXTRACT(doc, 'author') AS name FROM Post WHERE id = 1",
+        Err(ValueError::InvalidJsonPath("author".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "SELECT JSON_REMOVE(doc, '$') AS doc FROM Post WHERE id = 1",
+        Err(ValueError::JsonRemoveDocument.into()),
+    )
+    .await;
+    g.test(
+        "SELECT JSON_EXTRACT(id, '$') AS doc FROM Post WHERE id = 1",
+        Err(ValueError::SelectorRequiresMapOrListTypes.into()),
+    )
+    .await;
+    g.test(
+        "SELECT id -> 'a' AS doc FROM Post WHERE id = 1",
+        Err(ValueError::SelectorRequiresMapOrListTypes.into()),
+    )
+    .await;
+    g.test(
+        "SELECT JSON_EXTRACT(doc, 1) AS doc FROM Post WHERE id = 1",
+        Err(EvaluateError::FunctionRequiresStringValue("JSON_EXTRACT".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "SELECT JSON_SET(doc, '$.a') AS doc FROM Post",
+        Err(TranslateError::FunctionArgsLengthNotMatching {
+            name: "JSON_SET".to_owned(),
+            expected: 3,
+            found: 2,
+        }
+        .into()),
+    )
+    .await;
+});
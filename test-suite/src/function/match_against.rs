@@ -0,0 +1,85 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{EvaluateError, TranslateError},
+        prelude::Value::*,
+    },
+};
+
+test_case!(match_against, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Post (id INTEGER, title TEXT, body TEXT NULL)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Post VALUES
+            (1, 'Rust engine', 'A SQL engine written in Rust.'),
+            (2, 'Rust tips', 'Rust, rust and more RUST!'),
+            (3, 'Python', 'Scripting with Python'),
+            (4, 'Untitled', NULL);
+    ",
+    )
+    .await;
+
+    g.test(
+        "SELECT id, MATCH(title) AGAINST('rust engine') AS score FROM Post",
+        Ok(select!(
+            id  | score
+            I64 | F64;
+            1     1.0;
+            2     0.5;
+            3     0.0;
+            4     0.0
+        )),
+    )
+    .await;
+
+    g.test(
+        "
+        SELECT id FROM Post
+        WHERE MATCH(body) AGAINST('rust') > 0
+        ORDER BY MATCH(body) AGAINST('rust') DESC
+        ",
+        Ok(select!(
+            id
+            I64;
+            2;
+            1
+        )),
+    )
+    .await;
+
+    g.test(
+        "SELECT id, MATCH(title, body) AGAINST('untitled') AS score FROM Post WHERE id = 4",
+        Ok(select!(
+            id  | score
+            I64 | F64;
+            4     0.5
+        )),
+    )
+    .await;
+
+    g.test(
+        "SELECT MATCH(body) AGAINST(NULL) AS score FROM Post WHERE id = 1",
+        Ok(select_with_null!(score; Null)),
+    )
+    .await;
+
+    g.test(
+        "SELECT MATCH(id) AGAINST('1') AS score FROM Post",
+        Err(EvaluateError::FunctionRequiresStringValue("MATCH".to_owned()).into()),
+    )
+    .await;
+
+    g.test(
+        "SELECT MATCH(title) AS score FROM Post",
+        Err(TranslateError::FunctionArgsLengthNotMatchingMin {
+            name: "MATCH".to_owned(),
+            expected_minimum: 2,
+            found: 1,
+        }
+        .into()),
+    )
+    .await;
+});
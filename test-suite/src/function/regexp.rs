@@ -0,0 +1,149 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{EvaluateError, StringExtError, TranslateError, ValueError},
+        prelude::Value::*,
+    },
+};
+
+test_case!(regexp, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Log (id INTEGER, message TEXT NULL)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Log VALUES
+            (1, 'GET /items/42 took 120ms'),
+            (2, 'POST /users/7 took 35ms'),
+            (3, 'health check'),
+            (4, NULL);
+        ",
+    )
+    .await;
+
+    g.test(
+        r"SELECT id, message ~ '^[A-Z]+ /' AS request FROM Log",
+        Ok(select_with_null!(
+            id     | request;
+            I64(1)   Bool(true);
+            I64(2)   Bool(true);
+            I64(3)   Bool(false);
+            I64(4)   Null
+        )),
+    )
+    .await;
+    g.test(
+        r"SELECT id FROM Log WHERE message ~ '/users/\d+'",
+        Ok(select!(id I64; 2)),
+    )
+    .await;
+
+    g.test(
+        r"SELECT REGEXP_MATCHES(message, '\d+') AS numbers FROM Log WHERE id = 1",
+        Ok(select_with_null!(
+            numbers;
+            List(vec![Str("42".to_owned()), Str("120".to_owned())])
+        )),
+    )
+    .await;
+    g.named_test(
+        "matches of a pattern with groups are lists of the groups",
+        r"SELECT REGEXP_MATCHES(message, '/(\w+)/(\d+)( took)?') AS paths FROM Log WHERE id = 2",
+        Ok(select_with_null!(
+            paths;
+            List(vec![List(vec![
+                Str("users".to_owned()),
+                Str("7".to_owned()),
+                Str(" took".to_owned())
+            ])])
+        )),
+    )
+    .await;
+    g.test(
+        r"SELECT REGEXP_MATCHES(message, '\d+') AS numbers FROM Log WHERE id = 3",
+        Ok(select_with_null!(numbers; List(Vec::new()))),
+    )
+    .await;
+
+    g.test(
+        r"SELECT REGEXP_REPLACE(message, '/(\w+)/\d+', '/$1/:id') AS path FROM Log WHERE id < 3",
+        Ok(select!(
+            path
+            Str;
+            "GET /items/:id took 120ms".to_owned();
+            "POST /users/:id took 35ms".to_owned()
+        )),
+    )
+    .await;
+
+    g.test(
+        r"
+        SELECT
+            REGEXP_EXTRACT(message, '\d+ms') AS elapsed,
+            REGEXP_EXTRACT(message, '/(\w+)/', 1) AS resource
+        FROM Log
+        ORDER BY id
+        ",
+        Ok(select_with_null!(
+            elapsed                  | resource;
+            Str("120ms".to_owned())    Str("items".to_owned());
+            Str("35ms".to_owned())     Str("users".to_owned());
+            Null                       Null;
+            Null                       Null
+        )),
+    )
+    .await;
+
+    g.test(
+        "SELECT REGEXP_EXTRACT(message, '(a)|(b)', 2) AS b FROM Log WHERE id = 3",
+        Ok(select_with_null!(b; Null)),
+    )
+    .await;
+    g.test(
+        "SELECT REGEXP_EXTRACT(message, '(a)', 2) AS b FROM Log WHERE id = 3",
+        Err(EvaluateError::RegexGroupOutOfRange {
+            name: "REGEXP_EXTRACT".to_owned(),
+            group: 2,
+            groups: 1,
+        }
+        .into()),
+    )
+    .await;
+    g.test(
+        "SELECT REGEXP_REPLACE(message, '(', '') AS b FROM Log",
+        Err(StringExtError::InvalidRegexPattern("(".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "SELECT REGEXP_MATCHES(id, '1') AS b FROM Log",
+        Err(EvaluateError::FunctionRequiresStringValue("REGEXP_MATCHES".to_owned()).into()),
+    )
+    .await;
+    g.test(
+        "SELECT id ~ '1' AS b FROM Log",
+        Err(ValueError::RegexMatchOnNonString {
+            base: I64(1),
+            pattern: Str("1".to_owned()),
+        }
+        .into()),
+    )
+    .await;
+    g.test(
+        "SELECT REGEXP_EXTRACT(message) AS b FROM Log",
+        Err(TranslateError::FunctionArgsLengthNotWithinRange {
+            name: "REGEXP_EXTRACT".to_owned(),
+            expected_minimum: 2,
+            expected_maximum: 3,
+            found: 1,
+        }
+        .into()),
+    )
+    .await;
+
+    g.test(
+        "SELECT 'GlueSQL' ~ 'SQL$' AS matched",
+        Ok(select!(matched Bool; true)),
+    )
+    .await;
+});
@@ -1,19 +1,33 @@
 mod and;
 mod basic;
+mod check;
+mod composite;
 mod expr;
+mod fulltext;
+mod group_by;
+mod merge_join;
 mod nested;
 mod null;
 mod order_by;
+mod partial;
 mod showindexes;
+mod statistics;
 mod value;
 
 pub use {
     and::and,
     basic::basic,
+    check::check,
+    composite::composite,
     expr::expr,
+    fulltext::fulltext,
+    group_by::group_by,
+    merge_join::merge_join,
     nested::nested,
     null::null,
     order_by::{order_by, order_by_multi},
+    partial::partial,
     showindexes::showindexes,
+    statistics::statistics,
     value::value,
 };
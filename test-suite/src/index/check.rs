@@ -0,0 +1,36 @@
+use {
+    crate::*,
+    gluesql_core::prelude::{Payload, Value::*},
+};
+
+test_case!(check, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Test (id INTEGER, name TEXT NULL)")
+        .await;
+    g.run("INSERT INTO Test VALUES (1, 'Hello'), (1, 'World'), (2, NULL)")
+        .await;
+    g.run("CREATE INDEX idx_id ON Test (id)").await;
+    g.run("CREATE INDEX idx_name ON Test (name)").await;
+    g.run("UPDATE Test SET name = 'Job' WHERE name IS NULL")
+        .await;
+    g.run("DELETE FROM Test WHERE name = 'World'").await;
+
+    let statuses = match g.run("CHECK TABLE Test").await {
+        Payload::Select { rows, .. } => rows
+            .into_iter()
+            .map(|row| (row[1].clone(), row[2].clone()))
+            .collect::<Vec<_>>(),
+        payload => panic!("unexpected payload: {payload:?}"),
+    };
+    let status = |check: &str| (Str(check.to_owned()), Str("OK".to_owned()));
+    assert_eq!(
+        statuses,
+        vec![
+            status("ROW_TYPE"),
+            status("INDEX idx_id"),
+            status("INDEX idx_name"),
+            status("CHECKSUM"),
+        ]
+    );
+});
@@ -0,0 +1,132 @@
+use {
+    crate::{expr as parse_expr, *},
+    gluesql_core::{
+        ast::{Expr, IndexItem, IndexOperator::*},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(composite, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Pair (
+            id INTEGER,
+            a INTEGER,
+            b INTEGER
+        )
+    ",
+    )
+    .await;
+
+    g.run(
+        "
+        INSERT INTO Pair
+            (id, a, b)
+        VALUES
+            (1, 1, 3),
+            (2, 1, 7),
+            (3, 1, 9),
+            (4, 2, 1),
+            (5, 2, 8),
+            (6, 3, 5);
+    ",
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_ab ON Pair (a, b)",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    macro_rules! idx_composite {
+        ($name: literal, $asc: expr, $op: expr, [$( $value: literal ),+]) => {
+            vec![IndexItem::NonClustered {
+                name: $name.to_owned(),
+                asc: $asc,
+                cmp_expr: Some((
+                    $op,
+                    Expr::Array {
+                        elem: vec![$( parse_expr($value) ),+],
+                    },
+                )),
+            }]
+        };
+    }
+
+    g.test_idx(
+        "SELECT id FROM Pair WHERE a = 1 AND b > 5",
+        Ok(select!(id I64; 2; 3)),
+        idx_composite!("idx_ab", None, Gt, ["1", "5"]),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Pair WHERE 8 = b AND a = 2",
+        Ok(select!(id I64; 5)),
+        idx_composite!("idx_ab", None, Eq, ["2", "8"]),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Pair WHERE a = 1",
+        Ok(select!(id I64; 1; 2; 3)),
+        idx_composite!("idx_ab", None, Eq, ["1"]),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Pair WHERE a = 1 AND b < 9 AND id > 1",
+        Ok(select!(id I64; 2)),
+        idx_composite!("idx_ab", None, Lt, ["1", "9"]),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Pair WHERE b > 5",
+        Ok(select!(id I64; 2; 3; 5)),
+        idx!(),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Pair ORDER BY a DESC, b DESC",
+        Ok(select!(id I64; 6; 5; 4; 3; 2; 1)),
+        idx!(idx_ab, DESC),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Pair WHERE a = 1 ORDER BY b DESC",
+        Ok(select!(id I64; 3; 2; 1)),
+        idx_composite!("idx_ab", Some(false), Eq, ["1"]),
+    )
+    .await;
+
+    g.run("UPDATE Pair SET b = 4 WHERE id = 3").await;
+    g.run("DELETE FROM Pair WHERE id = 1").await;
+
+    g.test_idx(
+        "SELECT id, b FROM Pair WHERE a = 1 AND b >= 4",
+        Ok(select!(
+            id  | b
+            I64 | I64;
+            3     4;
+            2     7
+        )),
+        idx_composite!("idx_ab", None, GtEq, ["1", "4"]),
+    )
+    .await;
+
+    g.test("CREATE INDEX ON Pair (b, id)", Ok(Payload::CreateIndex))
+        .await;
+
+    g.test_idx(
+        "SELECT a FROM Pair WHERE b = 5 AND id = 6",
+        Ok(select!(a I64; 3)),
+        idx_composite!("Pair_b_id_idx", None, Eq, ["5", "6"]),
+    )
+    .await;
+});
@@ -157,4 +157,31 @@ test_case!(expr, {
         idx!(idx_cast, Eq, "'4'"),
     )
     .await;
+
+    g.run("CREATE TABLE Member (id INTEGER, email TEXT)").await;
+    g.run("INSERT INTO Member VALUES (1, 'Alice@Example.com'), (2, 'bob@example.com')")
+        .await;
+
+    g.test(
+        "CREATE INDEX ON Member ((LOWER(email)))",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id, email FROM Member WHERE LOWER(email) = 'alice@example.com'",
+        Ok(select!(
+            id  | email
+            I64 | Str;
+            1     "Alice@Example.com".to_owned()
+        )),
+        idx!(Member_lower_idx, Eq, "'alice@example.com'"),
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_now ON Test (NOW())",
+        Err(AlterError::UnsupportedIndexExpr(parse_expr("NOW()")).into()),
+    )
+    .await;
 });
@@ -0,0 +1,116 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, TranslateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(fulltext, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Log (id INTEGER, message TEXT NULL)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Log VALUES
+            (1, 'Disk error: disk /dev/sda1 is full'),
+            (2, 'Network error, retrying'),
+            (3, 'Backup done'),
+            (4, NULL),
+            (5, 'disk check passed');
+    ",
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_message ON Log USING FULLTEXT (message)",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    let score = 2.0 / 3.0 + 0.5;
+    g.test_idx(
+        "
+        SELECT id, MATCH(message) AGAINST('disk error') AS score
+        FROM Log
+        WHERE MATCH(message) AGAINST('disk error') > 0
+        ORDER BY score DESC, id
+        ",
+        Ok(select!(
+            id  | score
+            I64 | F64;
+            1     score;
+            2     0.5;
+            5     0.5
+        )),
+        idx!(idx_message, MATCH, "'disk error'"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Log WHERE id < 5 AND 0 < MATCH(message) AGAINST('DISK')",
+        Ok(select!(id I64; 1)),
+        idx!(idx_message, MATCH, "'DISK'"),
+    )
+    .await;
+
+    g.run("UPDATE Log SET message = 'Disk replaced' WHERE id = 2")
+        .await;
+    g.run("DELETE FROM Log WHERE id = 5").await;
+    g.test_idx(
+        "SELECT id FROM Log WHERE MATCH(message) AGAINST('disk') > 0 ORDER BY id",
+        Ok(select!(id I64; 1; 2)),
+        idx!(idx_message, MATCH, "'disk'"),
+    )
+    .await;
+    g.test_idx(
+        "SELECT id FROM Log WHERE MATCH(message) AGAINST('network') > 0",
+        Ok(select!(id)),
+        idx!(idx_message, MATCH, "'network'"),
+    )
+    .await;
+
+    // rows without any of the terms match as well
+    g.test_idx(
+        "SELECT id FROM Log WHERE MATCH(message) AGAINST('disk') >= 0 ORDER BY id",
+        Ok(select!(id I64; 1; 2; 3; 4)),
+        idx!(),
+    )
+    .await;
+    // a full-text index does not index the values
+    g.test_idx(
+        "SELECT id FROM Log WHERE message = 'Backup done'",
+        Ok(select!(id I64; 3)),
+        idx!(),
+    )
+    .await;
+
+    let statuses = match g.run("CHECK TABLE Log").await {
+        Payload::Select { rows, .. } => rows
+            .into_iter()
+            .map(|row| (row[1].clone(), row[2].clone()))
+            .collect::<Vec<_>>(),
+        payload => panic!("unexpected payload: {payload:?}"),
+    };
+    let status = |check: &str| (Str(check.to_owned()), Str("OK".to_owned()));
+    assert_eq!(
+        statuses,
+        vec![
+            status("ROW_TYPE"),
+            status("INDEX idx_message"),
+            status("CHECKSUM"),
+        ]
+    );
+
+    g.test(
+        "CREATE INDEX idx_id_message ON Log USING FULLTEXT (id, message)",
+        Err(AlterError::FullTextIndexExprCount(2).into()),
+    )
+    .await;
+    g.test(
+        "CREATE INDEX idx_hash ON Log USING HASH (message)",
+        Err(TranslateError::UnsupportedIndexMethod("HASH".to_owned()).into()),
+    )
+    .await;
+});
@@ -0,0 +1,91 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::IndexOperator::*,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(group_by, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER, player_id INTEGER NULL, amount INTEGER)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Item VALUES
+            (1, 2, 30),
+            (2, 1, 20),
+            (3, 3, 5),
+            (4, NULL, 60),
+            (5, 1, 40),
+            (6, 5, 10),
+            (7, 2, 10),
+            (8, NULL, 15);
+    ",
+    )
+    .await;
+    g.test(
+        "CREATE INDEX idx_player_id ON Item (player_id)",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.test_idx(
+        "
+        SELECT player_id, SUM(amount) AS total
+        FROM Item
+        GROUP BY player_id
+        ORDER BY player_id
+        ",
+        Ok(select_with_null!(
+            player_id | total;
+            I64(1)      I64(60);
+            I64(2)      I64(40);
+            I64(3)      I64(5);
+            I64(5)      I64(10);
+            Null        I64(75)
+        )),
+        idx!(idx_player_id),
+    )
+    .await;
+
+    g.test_idx(
+        "
+        SELECT player_id, amount > 20 AS large, COUNT(*) AS num
+        FROM Item
+        WHERE player_id > 1
+        GROUP BY player_id, amount > 20
+        ORDER BY player_id, large
+        ",
+        Ok(select_with_null!(
+            player_id | large       | num;
+            I64(2)      Bool(false)   I64(1);
+            I64(2)      Bool(true)    I64(1);
+            I64(3)      Bool(false)   I64(1);
+            I64(5)      Bool(false)   I64(1);
+            Null        Bool(false)   I64(1);
+            Null        Bool(true)    I64(1)
+        )),
+        idx!(idx_player_id, Gt, "1"),
+    )
+    .await;
+
+    g.test_idx(
+        "
+        SELECT Item.player_id, SUM(amount) AS total
+        FROM Item
+        GROUP BY Item.player_id
+        HAVING SUM(amount) > 30
+        ORDER BY player_id DESC
+        ",
+        Ok(select_with_null!(
+            player_id | total;
+            Null        I64(75);
+            I64(2)      I64(40);
+            I64(1)      I64(60)
+        )),
+        idx!(idx_player_id, DESC),
+    )
+    .await;
+});
@@ -0,0 +1,115 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::IndexOperator::*,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(merge_join, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Player (id INTEGER, name TEXT)").await;
+    g.run("CREATE TABLE PlayerItem (user_id INTEGER NULL, amount INTEGER)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Player VALUES
+            (3, 'Jorno'),
+            (1, 'Taehoon'),
+            (4, 'Berry'),
+            (2, 'Mike');
+    ",
+    )
+    .await;
+    g.run(
+        "
+        INSERT INTO PlayerItem VALUES
+            (2, 30),
+            (1, 20),
+            (3, 5),
+            (NULL, 60),
+            (1, 40),
+            (5, 10),
+            (2, 50);
+    ",
+    )
+    .await;
+
+    for sql in [
+        "CREATE INDEX idx_id ON Player (id)",
+        "CREATE INDEX idx_user_id ON PlayerItem (user_id)",
+    ] {
+        g.test(sql, Ok(Payload::CreateIndex)).await;
+    }
+
+    macro_rules! s {
+        ($v: literal) => {
+            Str($v.to_owned())
+        };
+    }
+
+    let operators = |payload: Payload| match payload {
+        Payload::Select { rows, .. } => rows.into_iter().map(|row| row[2].clone()).collect(),
+        _ => Vec::new(),
+    };
+    let join = "
+        SELECT Player.name, PlayerItem.amount
+        FROM Player
+        JOIN PlayerItem ON Player.id = PlayerItem.user_id AND PlayerItem.amount > 10
+    ";
+
+    let sql = format!("{join} ORDER BY id");
+    let payload = g.run(&format!("EXPLAIN {sql}")).await;
+    assert!(operators(payload).contains(&s!("MERGE JOIN")), "{sql}");
+    g.test_idx(
+        &sql,
+        Ok(select!(
+            name                 | amount
+            Str                  | I64;
+            "Taehoon".to_owned()   20;
+            "Taehoon".to_owned()   40;
+            "Mike".to_owned()      30;
+            "Mike".to_owned()      50
+        )),
+        idx!(idx_id),
+    )
+    .await;
+
+    let sql = format!("{join} WHERE id >= 2");
+    let payload = g.run(&format!("EXPLAIN {sql}")).await;
+    assert!(operators(payload).contains(&s!("MERGE JOIN")), "{sql}");
+    g.test_idx(
+        &sql,
+        Ok(select!(
+            name              | amount
+            Str               | I64;
+            "Mike".to_owned()   30;
+            "Mike".to_owned()   50
+        )),
+        idx!(idx_id, GtEq, "2"),
+    )
+    .await;
+
+    g.test(
+        "
+        SELECT Player.name, PlayerItem.amount
+        FROM Player
+        LEFT JOIN PlayerItem ON Player.id = PlayerItem.user_id
+        ORDER BY id
+        ",
+        Ok(select_with_null!(
+            name            | amount;
+            s!("Taehoon")     I64(20);
+            s!("Taehoon")     I64(40);
+            s!("Mike")        I64(30);
+            s!("Mike")        I64(50);
+            s!("Jorno")       I64(5);
+            s!("Berry")       Null
+        )),
+    )
+    .await;
+
+    let payload = g.run(&format!("EXPLAIN {join}")).await;
+    assert!(operators(payload).contains(&s!("HASH JOIN")), "{join}");
+});
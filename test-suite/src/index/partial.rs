@@ -0,0 +1,109 @@
+use {
+    crate::{expr as parse_expr, *},
+    gluesql_core::{
+        ast::IndexOperator::*,
+        error::AlterError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(partial, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Item (
+            id INTEGER,
+            price INTEGER,
+            active BOOLEAN NULL
+        )
+    ",
+    )
+    .await;
+
+    g.run(
+        "
+        INSERT INTO Item
+            (id, price, active)
+        VALUES
+            (1, 100, TRUE),
+            (2, 200, FALSE),
+            (3, 300, TRUE),
+            (4, 400, NULL);
+    ",
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_active_price ON Item (price) WHERE active = TRUE",
+        Ok(Payload::CreateIndex),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id, price FROM Item WHERE active = TRUE AND price > 150",
+        Ok(select!(
+            id  | price
+            I64 | I64;
+            3     300
+        )),
+        idx!(idx_active_price, Gt, "150"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id, price FROM Item WHERE price > 150 AND (active = TRUE)",
+        Ok(select!(
+            id  | price
+            I64 | I64;
+            3     300
+        )),
+        idx!(idx_active_price, Gt, "150"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id, price FROM Item WHERE price > 150",
+        Ok(select!(
+            id  | price
+            I64 | I64;
+            2     200;
+            3     300;
+            4     400
+        )),
+        idx!(),
+    )
+    .await;
+
+    g.run("INSERT INTO Item VALUES (5, 500, TRUE), (6, 600, FALSE)")
+        .await;
+    g.run("UPDATE Item SET active = TRUE WHERE id = 2").await;
+    g.run("UPDATE Item SET active = FALSE WHERE id = 3").await;
+    g.run("DELETE FROM Item WHERE id = 1").await;
+
+    g.test_idx(
+        "SELECT id, price FROM Item WHERE active = TRUE AND price > 0",
+        Ok(select!(
+            id  | price
+            I64 | I64;
+            2     200;
+            5     500
+        )),
+        idx!(idx_active_price, Gt, "0"),
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_subquery ON Item (price) WHERE id IN (SELECT id FROM Item)",
+        Err(
+            AlterError::UnsupportedIndexPredicate(parse_expr("id IN (SELECT id FROM Item)")).into(),
+        ),
+    )
+    .await;
+
+    g.test(
+        "CREATE INDEX idx_missing ON Item (price) WHERE stock > 0",
+        Err(AlterError::IndexPredicateColumnNotFound("stock".to_owned()).into()),
+    )
+    .await;
+});
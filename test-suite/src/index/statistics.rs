@@ -0,0 +1,105 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::IndexOperator::*,
+        error::ExecuteError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(statistics, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Stat (id INTEGER, kind INTEGER, note TEXT NULL)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Stat VALUES
+            (1, 1, 'x'),
+            (2, 1, 'y'),
+            (3, 1, NULL),
+            (4, 1, NULL),
+            (5, 1, NULL),
+            (6, 1, NULL),
+            (7, 1, NULL),
+            (8, 1, NULL),
+            (9, 1, NULL),
+            (10, 2, NULL);
+    ",
+    )
+    .await;
+
+    for sql in [
+        "CREATE INDEX idx_id ON Stat (id)",
+        "CREATE INDEX idx_kind ON Stat (kind)",
+        "CREATE INDEX idx_note ON Stat (note)",
+    ] {
+        g.test(sql, Ok(Payload::CreateIndex)).await;
+    }
+
+    g.test_idx(
+        "SELECT id FROM Stat WHERE kind = 1 AND id = 3",
+        Ok(select!(id I64; 3)),
+        idx!(idx_kind, Eq, "1"),
+    )
+    .await;
+
+    g.test(
+        "ANALYZE Stat",
+        Ok(select_with_null!(
+            TABLE_NAME             | ROW_COUNT | COLUMN_NAME          | DISTINCT_COUNT | NULL_COUNT | MIN                  | MAX;
+            Str("Stat".to_owned())   I64(10)     Str("id".to_owned())   I64(10)          I64(0)       I64(1)                 I64(10);
+            Str("Stat".to_owned())   I64(10)     Str("kind".to_owned()) I64(2)           I64(0)       I64(1)                 I64(2);
+            Str("Stat".to_owned())   I64(10)     Str("note".to_owned()) I64(2)           I64(8)       Str("x".to_owned())    Str("y".to_owned())
+        )),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Stat WHERE kind = 1 AND id = 3",
+        Ok(select!(id I64; 3)),
+        idx!(idx_id, Eq, "3"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Stat WHERE kind = 2",
+        Ok(select!(id I64; 10)),
+        idx!(),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Stat WHERE id > 8",
+        Ok(select!(id I64; 9; 10)),
+        idx!(idx_id, Gt, "8"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Stat WHERE id < 8 AND kind = 1",
+        Ok(select!(id I64; 1; 2; 3; 4; 5; 6; 7)),
+        idx!(),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Stat WHERE note IS NOT NULL",
+        Ok(select!(id I64; 1; 2)),
+        idx!(idx_note, Lt, "NULL"),
+    )
+    .await;
+
+    g.test_idx(
+        "SELECT id FROM Stat WHERE note IS NULL AND id = 10",
+        Ok(select!(id I64; 10)),
+        idx!(idx_id, Eq, "10"),
+    )
+    .await;
+
+    g.test(
+        "ANALYZE TABLE Missing",
+        Err(ExecuteError::TableNotFound("Missing".to_owned()).into()),
+    )
+    .await;
+});
@@ -1,7 +1,7 @@
 use {
     crate::*,
     gluesql_core::{
-        error::InsertError,
+        error::{InsertError, TranslateError, ValidateError},
         prelude::{Payload, Value::*},
     },
 };
@@ -101,3 +101,123 @@ CREATE TABLE Test (
     )
     .await;
 });
+
+test_case!(on_conflict, {
+    let g = get_tester!();
+
+    g.run(
+        "
+CREATE TABLE Account (
+    id INTEGER PRIMARY KEY,
+    email TEXT UNIQUE,
+    name TEXT,
+    visits INTEGER DEFAULT 1
+);",
+    )
+    .await;
+    g.run("INSERT INTO Account VALUES (1, 'a@glue', 'Alice', 1), (2, 'b@glue', 'Bob', 1);")
+        .await;
+
+    g.named_test(
+        "DO NOTHING skips the rows conflicting on the primary key",
+        "INSERT INTO Account VALUES (1, 'x@glue', 'Xavier', 1), (3, 'c@glue', 'Carol', 1) ON CONFLICT (id) DO NOTHING;",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.named_test(
+        "DO NOTHING without a target looks at every unique column",
+        "INSERT INTO Account VALUES (4, 'b@glue', 'Bobby', 1), (5, 'e@glue', 'Eve', 1) ON CONFLICT DO NOTHING;",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.named_test(
+        "DO NOTHING skips rows conflicting with rows inserted by the same statement",
+        "INSERT INTO Account VALUES (6, 'f@glue', 'Frank', 1), (6, 'g@glue', 'Grace', 1) ON CONFLICT (id) DO NOTHING;",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.named_test(
+        "DO UPDATE updates the row conflicted with, reading the proposed row as EXCLUDED",
+        "INSERT INTO Account VALUES (1, 'a@glue', 'Alicia', 1), (7, 'h@glue', 'Heidi', 1)
+        ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name, visits = visits + EXCLUDED.visits;",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.named_test(
+        "DO UPDATE on a unique column may set it to the value it has",
+        "INSERT INTO Account (id, email, name) VALUES (8, 'b@glue', 'Robert')
+        ON CONFLICT (email) DO UPDATE SET email = EXCLUDED.email, name = EXCLUDED.name;",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.named_test(
+        "DO UPDATE leaves the rows its WHERE is not true for",
+        "INSERT INTO Account VALUES (1, 'a@glue', 'Ally', 1), (2, 'b@glue', 'Rob', 1)
+        ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name WHERE Account.visits > 1;",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+
+    g.test(
+        "SELECT id, email, name, visits FROM Account ORDER BY id;",
+        Ok(select!(
+            id     | email                  | name                   | visits
+            I64    | Str                    | Str                    | I64;
+            1        "a@glue".to_owned()      "Ally".to_owned()        2;
+            2        "b@glue".to_owned()      "Robert".to_owned()      1;
+            3        "c@glue".to_owned()      "Carol".to_owned()       1;
+            5        "e@glue".to_owned()      "Eve".to_owned()         1;
+            6        "f@glue".to_owned()      "Frank".to_owned()       1;
+            7        "h@glue".to_owned()      "Heidi".to_owned()       1
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "DO UPDATE cannot update a row twice",
+        "INSERT INTO Account VALUES (1, 'a@glue', 'A', 1), (1, 'a@glue', 'B', 1)
+        ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name;",
+        Err(InsertError::ConflictRowAffectedTwice.into()),
+    )
+    .await;
+    g.named_test(
+        "DO UPDATE requires a conflict target",
+        "INSERT INTO Account VALUES (1, 'a@glue', 'A', 1) ON CONFLICT DO UPDATE SET name = 'A';",
+        Err(InsertError::ConflictTargetRequired.into()),
+    )
+    .await;
+    g.named_test(
+        "conflict target must be unique",
+        "INSERT INTO Account VALUES (1, 'a@glue', 'A', 1) ON CONFLICT (name) DO NOTHING;",
+        Err(InsertError::ConflictTargetNotUnique("name".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "conflicts on other unique columns still fail",
+        "INSERT INTO Account VALUES (9, 'a@glue', 'A', 1) ON CONFLICT (id) DO NOTHING;",
+        Err(ValidateError::DuplicateEntryOnUniqueField(
+            Str("a@glue".to_owned()),
+            "email".to_owned(),
+        )
+        .into()),
+    )
+    .await;
+    g.named_test(
+        "ON CONSTRAINT is not supported",
+        "INSERT INTO Account VALUES (1, 'a@glue', 'A', 1) ON CONFLICT ON CONSTRAINT pk DO NOTHING;",
+        Err(TranslateError::UnsupportedOnInsert(
+            "ON CONFLICT ON CONSTRAINT pk DO NOTHING".to_owned(),
+        )
+        .into()),
+    )
+    .await;
+
+    g.run("CREATE TABLE Logs;").await;
+    g.named_test(
+        "ON CONFLICT requires a schema",
+        r#"INSERT INTO Logs VALUES ('{"id": 1}') ON CONFLICT DO NOTHING;"#,
+        Err(InsertError::OnConflictOnSchemalessTable("Logs".to_owned()).into()),
+    )
+    .await;
+});
@@ -333,8 +333,8 @@ test_case!(project, {
             TranslateError::UnsupportedJoinConstraint("USING".to_owned()).into(),
         ),
         (
-            "SELECT * FROM TableA CROSS JOIN TableA as A;",
-            TranslateError::UnsupportedJoinOperator("CrossJoin".to_owned()).into(),
+            "SELECT * FROM TableA CROSS APPLY TableA as A;",
+            TranslateError::UnsupportedJoinOperator("CrossApply".to_owned()).into(),
         ),
         (
             "SELECT id FROM Users JOIN Testers ON Users.id = Testers.id;",
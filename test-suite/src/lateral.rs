@@ -0,0 +1,72 @@
+use {crate::*, gluesql_core::prelude::Value::*};
+
+test_case!(lateral, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Users (id INTEGER, name TEXT)").await;
+    g.run("INSERT INTO Users VALUES (1, 'Ada'), (2, 'Bob'), (3, 'Cid')")
+        .await;
+    g.run("CREATE TABLE Score (user_id INTEGER, score INTEGER)")
+        .await;
+    g.run("INSERT INTO Score VALUES (1, 10), (1, 30), (1, 20), (2, 5), (2, 15)")
+        .await;
+
+    g.named_test(
+        "top N per group with CROSS JOIN LATERAL",
+        "
+        SELECT u.name, t.score
+        FROM Users u
+        CROSS JOIN LATERAL (
+            SELECT score FROM Score s WHERE s.user_id = u.id ORDER BY score DESC LIMIT 2
+        ) AS t
+        ORDER BY u.name, t.score DESC
+        ",
+        Ok(select!(
+            name             | score;
+            Str              | I64;
+            "Ada".to_owned()   30;
+            "Ada".to_owned()   20;
+            "Bob".to_owned()   15;
+            "Bob".to_owned()   5
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "LEFT JOIN LATERAL keeps the rows without a match",
+        "
+        SELECT u.name, t.best
+        FROM Users u
+        LEFT JOIN LATERAL (
+            SELECT score AS best FROM Score WHERE user_id = u.id ORDER BY score DESC LIMIT 1
+        ) AS t ON TRUE
+        ORDER BY u.name
+        ",
+        Ok(select_with_null!(
+            name                  | best;
+            Str("Ada".to_owned())   I64(30);
+            Str("Bob".to_owned())   I64(15);
+            Str("Cid".to_owned())   Null
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "LATERAL subquery with an ON condition on its columns",
+        "
+        SELECT u.name, t.total
+        FROM Users u
+        JOIN LATERAL (SELECT SUM(score) AS total FROM Score WHERE user_id = u.id) AS t
+            ON t.total > 20
+        ",
+        Ok(select!(name | total; Str | I64; "Ada".to_owned() 60)),
+    )
+    .await;
+
+    g.named_test(
+        "CROSS JOIN of tables",
+        "SELECT COUNT(*) AS cnt FROM Users CROSS JOIN Score",
+        Ok(select!(cnt I64; 15)),
+    )
+    .await;
+});
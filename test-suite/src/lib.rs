@@ -5,13 +5,18 @@ pub mod alter;
 pub mod arithmetic;
 pub mod array;
 pub mod ast_builder;
+pub mod audit;
 pub mod basic;
 pub mod bitwise_and;
 pub mod bitwise_shift_left;
 pub mod bitwise_shift_right;
 pub mod case;
+pub mod check;
 pub mod column_alias;
 pub mod concat;
+pub mod concurrency;
+pub mod correlated_subquery;
+pub mod cte;
 pub mod custom_function;
 pub mod data_type;
 pub mod default;
@@ -19,7 +24,9 @@ pub mod delete;
 pub mod dictionary;
 pub mod dictionary_index;
 pub mod distinct;
+pub mod explain;
 pub mod expr;
+pub mod fake;
 pub mod filter;
 pub mod foreign_key;
 pub mod function;
@@ -27,27 +34,38 @@ pub mod index;
 pub mod inline_view;
 pub mod insert;
 pub mod join;
+pub mod lateral;
 pub mod like_ilike;
 pub mod limit;
 pub mod metadata;
 pub mod migrate;
+pub mod migration;
 pub mod nested_select;
 pub mod nullable;
 pub mod order_by;
 pub mod ordering;
 pub mod primary_key;
 pub mod project;
+pub mod pushdown;
+pub mod recovery;
+pub mod returning;
 pub mod schemaless;
+pub mod sequence;
 pub mod series;
+pub mod set_operation;
 pub mod show_columns;
 pub mod store;
 pub mod synthesize;
 pub mod transaction;
+pub mod trigger;
 pub mod type_match;
 pub mod unary_operator;
 pub mod update;
+pub mod user;
 pub mod validate;
 pub mod values;
+pub mod view;
+pub mod window;
 
 pub mod tester;
 
@@ -76,6 +94,7 @@ macro_rules! generate_store_tests {
         }
         glue!(update, update::update);
         glue!(insert, insert::insert);
+        glue!(insert_on_conflict, insert::on_conflict);
         glue!(delete, delete::delete);
         glue!(basic, basic::basic);
         glue!(array, array::array);
@@ -108,6 +127,8 @@ macro_rules! generate_store_tests {
         glue!(filter, filter::filter);
         glue!(inline_view, inline_view::inline_view);
         glue!(values, values::values);
+        glue!(audit, audit::audit);
+        glue!(migration, migration::migration);
         glue!(unary_operator, unary_operator::unary_operator);
         glue!(function_upper_lower, function::upper_lower::upper_lower);
         glue!(function_initcap, function::initcap::initcap);
@@ -178,14 +199,33 @@ macro_rules! generate_store_tests {
         glue!(join_project, join::project);
         glue!(migrate, migrate::migrate);
         glue!(nested_select, nested_select::nested_select);
+        glue!(
+            correlated_subquery,
+            correlated_subquery::correlated_subquery
+        );
+        glue!(lateral, lateral::lateral);
         glue!(primary_key, primary_key::primary_key);
+        glue!(composite_primary_key, primary_key::composite_primary_key);
         glue!(foreign_key, foreign_key::foreign_key);
+        glue!(referential_action, foreign_key::referential_action);
         glue!(series, series::series);
+        glue!(check, check::check);
+        glue!(explain, explain::explain);
+        glue!(fake, fake::fake);
         glue!(nullable, nullable::nullable);
         glue!(nullable_text, nullable::nullable_text);
         glue!(nullable_implicit_insert, nullable::nullable_implicit_insert);
         glue!(ordering, ordering::ordering);
         glue!(order_by, order_by::order_by);
+        glue!(window, window::window);
+        glue!(cte, cte::cte);
+        glue!(view, view::view);
+        glue!(materialized_view, view::materialized_view);
+        glue!(trigger, trigger::trigger);
+        glue!(sequence, sequence::sequence);
+        glue!(returning, returning::returning);
+        glue!(serial, sequence::serial);
+        glue!(set_operation, set_operation::set_operation);
         glue!(sql_types, data_type::sql_types::sql_types);
         glue!(show_columns, show_columns::show_columns);
         glue!(distinct, distinct::distinct);
@@ -211,7 +251,12 @@ macro_rules! generate_store_tests {
         glue!(point, data_type::point::point);
         glue!(null, data_type::null::null);
         glue!(synthesize, synthesize::synthesize);
+        glue!(validate_check, validate::check::check);
         glue!(validate_unique, validate::unique::unique);
+        glue!(
+            validate_unique_constraint,
+            validate::unique::unique_constraint
+        );
         glue!(validate_types, validate::types::types);
         glue!(function_extract, function::extract::extract);
         glue!(function_radians, function::radians::radians);
@@ -237,6 +282,12 @@ macro_rules! generate_store_tests {
         glue!(column_alias, column_alias::column_alias);
         glue!(function_splice, function::splice::splice);
         glue!(function_dedup, function::dedup::dedup);
+        glue!(
+            function_match_against,
+            function::match_against::match_against
+        );
+        glue!(function_regexp, function::regexp::regexp);
+        glue!(function_json, function::json::json);
 
         // ast-builder
         glue!(ast_builder_basic, ast_builder::basic::basic);
@@ -335,6 +386,7 @@ macro_rules! generate_store_tests {
         // schemaless data support
         glue!(schemaless_basic, schemaless::basic);
         glue!(schemaless_error, schemaless::error);
+        glue!(schemaless_json, schemaless::json);
 
         glue!(store_insert_schema, store::insert_schema::insert_schema);
 
@@ -354,6 +406,7 @@ macro_rules! generate_alter_table_tests {
 
         glue!(alter_table_rename, alter::alter_table_rename);
         glue!(alter_table_add_drop, alter::alter_table_add_drop);
+        glue!(alter_table_column_type, alter::alter_table_column_type);
     };
 }
 
@@ -370,6 +423,19 @@ macro_rules! generate_custom_function_tests {
     };
 }
 
+#[macro_export]
+macro_rules! generate_user_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(user, user::user);
+    };
+}
+
 #[macro_export]
 macro_rules! generate_index_tests {
     ($test: meta, $storage: ident) => {
@@ -387,7 +453,14 @@ macro_rules! generate_index_tests {
         glue!(index_value, index::value);
         glue!(index_order_by, index::order_by);
         glue!(index_order_by_multi, index::order_by_multi);
+        glue!(index_partial, index::partial);
+        glue!(index_composite, index::composite);
+        glue!(index_statistics, index::statistics);
+        glue!(index_merge_join, index::merge_join);
+        glue!(index_group_by, index::group_by);
+        glue!(index_fulltext, index::fulltext);
         glue!(showindexes, index::showindexes);
+        glue!(index_check, index::check);
         glue!(dictionary_index, dictionary_index::ditionary_index);
     };
 }
@@ -492,3 +565,144 @@ macro_rules! generate_metadata_index_tests {
         glue!(metadata_index, metadata::index::index);
     };
 }
+
+#[macro_export]
+macro_rules! generate_pushdown_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(pushdown_primary_key, pushdown::primary_key);
+        glue!(pushdown_filter, pushdown::filter);
+    };
+}
+
+#[macro_export]
+macro_rules! generate_concurrency_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(concurrency_writes, concurrency::writes);
+    };
+}
+
+#[macro_export]
+macro_rules! generate_recovery_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(recovery_committed, recovery::committed);
+    };
+}
+
+#[macro_export]
+macro_rules! generate_transaction_recovery_tests {
+    ($test: meta, $storage: ident) => {
+        macro_rules! glue {
+            ($title: ident, $func: path) => {
+                declare_test_fn!($test, $storage, $title, $func);
+            };
+        }
+
+        glue!(transaction_recovery, recovery::transaction);
+    };
+}
+
+/// Generates the tests of every capability the storage declares, along with the tests of
+/// each combination of them.
+///
+/// ```ignore
+/// generate_conformance_tests!(tokio::test, SledTester, [basic, index, transaction, pushdown]);
+/// ```
+///
+/// The capabilities are
+/// * `basic` - `Store` and `StoreMut`, required by all the others
+/// * `alter_table` - `AlterTable`
+/// * `index` - `Index` and `IndexMut`
+/// * `transaction` - `Transaction`
+/// * `metadata` - `Metadata`, with the creation time of each table
+/// * `custom_function` - `CustomFunction` and `CustomFunctionMut`
+/// * `user` - `UserCatalog`
+/// * `pushdown` - primary key lookups served by `Store::fetch_data` and scans filtered by
+///   `Store::scan_data_filtered`
+/// * `concurrency` - writes through clones of the storage, which should share their data
+/// * `recovery` - reopening the storage, its tester implementing [`RecoveryTester`]
+#[macro_export]
+macro_rules! generate_conformance_tests {
+    ($test: meta, $storage: ident, [$($capability: ident),* $(,)?]) => {
+        $($crate::generate_conformance_tests!(@capability $test, $storage, $capability);)*
+        $crate::generate_conformance_tests!(@combine $test, $storage, [$($capability)*], no no no no);
+    };
+
+    (@capability $test: meta, $storage: ident, basic) => {
+        $crate::generate_store_tests!($test, $storage);
+    };
+    (@capability $test: meta, $storage: ident, alter_table) => {
+        $crate::generate_alter_table_tests!($test, $storage);
+    };
+    (@capability $test: meta, $storage: ident, index) => {
+        $crate::generate_index_tests!($test, $storage);
+        $crate::generate_metadata_index_tests!($test, $storage);
+    };
+    (@capability $test: meta, $storage: ident, transaction) => {
+        $crate::generate_transaction_tests!($test, $storage);
+    };
+    (@capability $test: meta, $storage: ident, metadata) => {
+        $crate::generate_metadata_table_tests!($test, $storage);
+    };
+    (@capability $test: meta, $storage: ident, custom_function) => {
+        $crate::generate_custom_function_tests!($test, $storage);
+    };
+    (@capability $test: meta, $storage: ident, user) => {
+        $crate::generate_user_tests!($test, $storage);
+    };
+    (@capability $test: meta, $storage: ident, pushdown) => {
+        $crate::generate_pushdown_tests!($test, $storage);
+    };
+    (@capability $test: meta, $storage: ident, concurrency) => {
+        $crate::generate_concurrency_tests!($test, $storage);
+    };
+    (@capability $test: meta, $storage: ident, recovery) => {
+        $crate::generate_recovery_tests!($test, $storage);
+    };
+
+    // Scans the capabilities for the ones combined with others: alter_table, index,
+    // transaction and recovery, in this order.
+    (@combine $test: meta, $storage: ident, [alter_table $($rest: ident)*], $a: ident $i: ident $t: ident $r: ident) => {
+        $crate::generate_conformance_tests!(@combine $test, $storage, [$($rest)*], yes $i $t $r);
+    };
+    (@combine $test: meta, $storage: ident, [index $($rest: ident)*], $a: ident $i: ident $t: ident $r: ident) => {
+        $crate::generate_conformance_tests!(@combine $test, $storage, [$($rest)*], $a yes $t $r);
+    };
+    (@combine $test: meta, $storage: ident, [transaction $($rest: ident)*], $a: ident $i: ident $t: ident $r: ident) => {
+        $crate::generate_conformance_tests!(@combine $test, $storage, [$($rest)*], $a $i yes $r);
+    };
+    (@combine $test: meta, $storage: ident, [recovery $($rest: ident)*], $a: ident $i: ident $t: ident $r: ident) => {
+        $crate::generate_conformance_tests!(@combine $test, $storage, [$($rest)*], $a $i $t yes);
+    };
+    (@combine $test: meta, $storage: ident, [$other: ident $($rest: ident)*], $a: ident $i: ident $t: ident $r: ident) => {
+        $crate::generate_conformance_tests!(@combine $test, $storage, [$($rest)*], $a $i $t $r);
+    };
+    (@combine $test: meta, $storage: ident, [], $a: ident $i: ident $t: ident $r: ident) => {
+        $crate::generate_conformance_tests!(@both $test, $storage, $a $i, generate_alter_table_index_tests);
+        $crate::generate_conformance_tests!(@both $test, $storage, $t $a, generate_transaction_alter_table_tests);
+        $crate::generate_conformance_tests!(@both $test, $storage, $t $i, generate_transaction_index_tests);
+        $crate::generate_conformance_tests!(@both $test, $storage, $t $r, generate_transaction_recovery_tests);
+    };
+
+    (@both $test: meta, $storage: ident, yes yes, $generate: ident) => {
+        $crate::$generate!($test, $storage);
+    };
+    (@both $test: meta, $storage: ident, $x: ident $y: ident, $generate: ident) => {};
+}
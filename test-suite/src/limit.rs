@@ -42,6 +42,28 @@ test_case!(limit, {
             "SELECT * FROM Test ORDER BY id DESC LIMIT 3",
             select!(id; I64; 8; 7; 6),
         ),
+        (
+            "SELECT id, id % 3 AS m FROM Test ORDER BY id % 3 LIMIT 4 OFFSET 1",
+            select!(
+                id  | m;
+                I64 | I64;
+                6     0;
+                1     1;
+                4     1;
+                7     1
+            ),
+        ),
+        (
+            "SELECT DISTINCT id % 3 AS m FROM Test ORDER BY m LIMIT 2",
+            select!(m; I64; 0; 1),
+        ),
+        (
+            "SELECT * FROM Test ORDER BY id LIMIT 0",
+            Payload::Select {
+                labels: vec!["id".to_owned()],
+                rows: vec![],
+            },
+        ),
         (
             "SELECT id, COUNT(*) as c FROM Test GROUP BY id LIMIT 3 OFFSET 2",
             select!(
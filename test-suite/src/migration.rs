@@ -0,0 +1,106 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{FetchError, MigrationError},
+        migration::{Migration, Migrator},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(migration, {
+    let glue = get_glue!();
+
+    let create_item = Migration::new(
+        1,
+        "create_item",
+        "CREATE TABLE Item (id INTEGER, name TEXT);",
+    )
+    .with_down("DROP TABLE Item;");
+    let seed_item = Migration::new(
+        2,
+        "seed_item",
+        "INSERT INTO Item VALUES (1, 'Glue'); INSERT INTO Item VALUES (2, 'SQL');",
+    )
+    .with_down("DELETE FROM Item;");
+    let create_price = Migration::new(3, "create_price", "CREATE TABLE Price (id INTEGER);");
+
+    assert_eq!(
+        Migrator::new(vec![create_item.clone(), create_item.clone()]).unwrap_err(),
+        MigrationError::DuplicateVersion(1).into()
+    );
+
+    let migrator = Migrator::new(vec![
+        create_price.clone(),
+        create_item.clone(),
+        seed_item.clone(),
+    ])
+    .unwrap();
+    let applied = |statuses: Vec<_>| {
+        statuses
+            .into_iter()
+            .map(|status: gluesql_core::migration::MigrationStatus| {
+                (status.version, status.applied_at.is_some())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        applied(migrator.status(glue).await.unwrap()),
+        vec![(1, false), (2, false), (3, false)]
+    );
+    assert_eq!(migrator.up(glue).await, Ok(vec![1, 2, 3]));
+    assert_eq!(migrator.up(glue).await, Ok(vec![]));
+    assert_eq!(
+        applied(migrator.status(glue).await.unwrap()),
+        vec![(1, true), (2, true), (3, true)]
+    );
+    assert_eq!(
+        glue.execute("SELECT id, name FROM Item").await,
+        Ok(vec![select!(
+            id  | name;
+            I64 | Str;
+            1     "Glue".to_owned();
+            2     "SQL".to_owned()
+        )])
+    );
+
+    assert_eq!(
+        migrator.down(glue, 1).await,
+        Err(MigrationError::DownNotFound(3).into())
+    );
+
+    let migrator = Migrator::new(vec![
+        create_item,
+        seed_item,
+        create_price.with_down("DROP TABLE Price;"),
+    ])
+    .unwrap();
+    assert_eq!(migrator.down(glue, 2).await, Ok(vec![3, 2]));
+    assert_eq!(
+        applied(migrator.status(glue).await.unwrap()),
+        vec![(1, true), (2, false), (3, false)]
+    );
+    assert_eq!(
+        glue.execute("SELECT * FROM Item").await,
+        Ok(vec![Payload::Select {
+            labels: vec!["id".to_owned(), "name".to_owned()],
+            rows: vec![]
+        }])
+    );
+
+    let migrator = Migrator::new(vec![Migration::new(1, "broken", "SELECT * FROM Missing;")])
+        .unwrap()
+        .with_table("Versions");
+    assert_eq!(
+        migrator.up(glue).await,
+        Err(FetchError::TableNotFound("Missing".to_owned()).into())
+    );
+    assert_eq!(
+        applied(migrator.status(glue).await.unwrap()),
+        vec![(1, false)]
+    );
+    assert_eq!(
+        glue.execute("SELECT version FROM __migrations").await,
+        Ok(vec![select!(version I64; 1)])
+    );
+});
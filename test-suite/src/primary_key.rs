@@ -2,7 +2,7 @@ use {
     crate::*,
     gluesql_core::{
         data::Value::*,
-        error::{UpdateError, ValidateError, ValueError},
+        error::{AlterError, TranslateError, UpdateError, ValidateError, ValueError},
         prelude::{Key, Payload},
     },
 };
@@ -153,3 +153,157 @@ test_case!(primary_key, {
     )
     .await;
 });
+
+test_case!(composite_primary_key, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Student (
+            id INTEGER PRIMARY KEY,
+            name TEXT
+        );
+    ",
+    )
+    .await;
+    g.run(
+        "
+        CREATE TABLE Course (
+            id INTEGER PRIMARY KEY,
+            title TEXT
+        );
+    ",
+    )
+    .await;
+    g.run(
+        "
+        CREATE TABLE Enrollment (
+            student_id INTEGER,
+            course_id INTEGER,
+            grade TEXT,
+            PRIMARY KEY (student_id, course_id),
+            FOREIGN KEY (student_id) REFERENCES Student (id),
+            FOREIGN KEY (course_id) REFERENCES Course (id)
+        );
+    ",
+    )
+    .await;
+    g.run("INSERT INTO Student VALUES (1, 'Alice'), (2, 'Bob');")
+        .await;
+    g.run("INSERT INTO Course VALUES (10, 'Math'), (20, 'Art');")
+        .await;
+    g.test(
+        "INSERT INTO Enrollment VALUES (2, 10, 'B'), (1, 20, 'A'), (1, 10, 'C');",
+        Ok(Payload::Insert(3)),
+    )
+    .await;
+
+    g.named_test(
+        "rows are ordered by the columns of the primary key",
+        "SELECT student_id, course_id, grade FROM Enrollment",
+        Ok(select!(
+            student_id | course_id | grade
+            I64        | I64       | Str;
+            1            10          "C".to_owned();
+            1            20          "A".to_owned();
+            2            10          "B".to_owned()
+        )),
+    )
+    .await;
+    g.test(
+        "
+        SELECT s.name, c.title
+        FROM Enrollment e
+        JOIN Student s ON s.id = e.student_id
+        JOIN Course c ON c.id = e.course_id
+        WHERE e.course_id = 10
+        ",
+        Ok(select!(
+            name               | title
+            Str                | Str;
+            "Alice".to_owned()   "Math".to_owned();
+            "Bob".to_owned()     "Math".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "composite primary key rejects a duplicate pair",
+        "INSERT INTO Enrollment VALUES (1, 10, 'A');",
+        Err(
+            ValidateError::DuplicateEntryOnPrimaryKeyField(Key::Composite(vec![
+                Key::I64(1),
+                Key::I64(10),
+            ]))
+            .into(),
+        ),
+    )
+    .await;
+    g.named_test(
+        "columns of a composite primary key are NOT NULL",
+        "INSERT INTO Enrollment VALUES (2, NULL, 'A');",
+        Err(ValueError::NullValueOnNotNullField.into()),
+    )
+    .await;
+    g.named_test(
+        "UPDATE is not allowed for a column of a composite primary key",
+        "UPDATE Enrollment SET course_id = 20 WHERE student_id = 2",
+        Err(UpdateError::UpdateOnPrimaryKeyNotSupported("course_id".to_owned()).into()),
+    )
+    .await;
+
+    g.test(
+        "UPDATE Enrollment SET grade = 'A+' WHERE student_id = 1 AND course_id = 10",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+    g.test(
+        "DELETE FROM Enrollment WHERE student_id = 1 AND course_id = 20",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+    g.test(
+        "INSERT INTO Enrollment VALUES (2, 20, 'A');",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.test(
+        "SELECT student_id, course_id, grade FROM Enrollment",
+        Ok(select!(
+            student_id | course_id | grade
+            I64        | I64       | Str;
+            1            10          "A+".to_owned();
+            2            10          "B".to_owned();
+            2            20          "A".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "foreign key cannot reference a column of a composite primary key",
+        "
+        CREATE TABLE Attendance (
+            student_id INTEGER,
+            FOREIGN KEY (student_id) REFERENCES Enrollment (student_id)
+        );
+        ",
+        Err(AlterError::ReferencingNonPKColumn {
+            referenced_table: "Enrollment".to_owned(),
+            referenced_column: "student_id".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.named_test(
+        "table cannot have a second primary key",
+        "CREATE TABLE Twice (a INTEGER PRIMARY KEY, b INTEGER, PRIMARY KEY (a, b));",
+        Err(TranslateError::MultiplePrimaryKeys("Twice".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "columns of the primary key must exist",
+        "CREATE TABLE Missing (a INTEGER, PRIMARY KEY (a, b));",
+        Err(TranslateError::PrimaryKeyColumnNotFound("b".to_owned()).into()),
+    )
+    .await;
+});
@@ -0,0 +1,148 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::IndexItem,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(primary_key, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT)")
+        .await;
+    g.run("INSERT INTO Item VALUES (1, 'Glue'), (2, 'SQL'), (3, 'Rust')")
+        .await;
+
+    let found = || {
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            2     "SQL".to_owned()
+        ))
+    };
+    let not_found = || Ok(select!(id | name));
+
+    g.test_idx(
+        "SELECT id, name FROM Item WHERE id = 2",
+        found(),
+        vec![IndexItem::PrimaryKey(expr("2"))],
+    )
+    .await;
+    g.test_idx(
+        "SELECT id, name FROM Item WHERE 2 = id AND name = 'SQL'",
+        found(),
+        vec![IndexItem::PrimaryKey(expr("2"))],
+    )
+    .await;
+    g.test_idx(
+        "SELECT id, name FROM Item WHERE id = 2 AND name = 'Glue'",
+        not_found(),
+        vec![IndexItem::PrimaryKey(expr("2"))],
+    )
+    .await;
+    g.test_idx(
+        "SELECT id, name FROM Item WHERE id = 4",
+        not_found(),
+        vec![IndexItem::PrimaryKey(expr("4"))],
+    )
+    .await;
+    g.test_idx(
+        "SELECT id, name FROM Item WHERE id + 0 = 2",
+        found(),
+        vec![],
+    )
+    .await;
+
+    g.run("UPDATE Item SET name = 'SQL!' WHERE id = 2").await;
+    g.test_idx(
+        "SELECT id, name FROM Item WHERE id = 2",
+        Ok(select!(
+            id  | name
+            I64 | Str;
+            2     "SQL!".to_owned()
+        )),
+        vec![IndexItem::PrimaryKey(expr("2"))],
+    )
+    .await;
+
+    g.run("DELETE FROM Item WHERE id = 2").await;
+    g.test_idx(
+        "SELECT id, name FROM Item WHERE id = 2",
+        not_found(),
+        vec![IndexItem::PrimaryKey(expr("2"))],
+    )
+    .await;
+    g.count("SELECT * FROM Item", 2).await;
+
+    g.run("CREATE TABLE Tag (name TEXT PRIMARY KEY, weight INTEGER)")
+        .await;
+    g.run("INSERT INTO Tag VALUES ('b', 2), ('a', 1), ('c', 3)")
+        .await;
+    g.test_idx(
+        "SELECT weight FROM Tag WHERE name = 'b'",
+        Ok(select!(weight I64; 2)),
+        vec![IndexItem::PrimaryKey(expr("'b'"))],
+    )
+    .await;
+});
+
+test_case!(filter, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER, name TEXT NULL, rate FLOAT, flag BOOLEAN)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Item VALUES
+            (1, 'Glue', 0.5, TRUE),
+            (2, 'SQL', 1.5, FALSE),
+            (3, NULL, 2.5, TRUE),
+            (4, 'Rust', 3.5, FALSE);
+    ",
+    )
+    .await;
+
+    for (sql, expected) in [
+        ("SELECT id FROM Item WHERE id = 2", vec![2]),
+        ("SELECT id FROM Item WHERE 2 < id", vec![3, 4]),
+        ("SELECT id FROM Item WHERE rate <= 1.5", vec![1, 2]),
+        ("SELECT id FROM Item WHERE name <> 'SQL'", vec![1, 4]),
+        ("SELECT id FROM Item WHERE name IS NULL", vec![3]),
+        ("SELECT id FROM Item WHERE name IS NOT NULL", vec![1, 2, 4]),
+        ("SELECT id FROM Item WHERE flag = TRUE", vec![1, 3]),
+        ("SELECT id FROM Item WHERE id IN (1, NULL, 4)", vec![1, 4]),
+        ("SELECT id FROM Item WHERE id BETWEEN 2 AND 3", vec![2, 3]),
+        (
+            "SELECT id FROM Item WHERE id = 1 OR name = 'Rust'",
+            vec![1, 4],
+        ),
+        ("SELECT id FROM Item WHERE id > 1 AND id + 1 = 4", vec![3]),
+        ("SELECT id FROM Item WHERE id = 1 OR id + 1 = 4", vec![1, 3]),
+        ("SELECT id FROM Item WHERE NOT id = 1 AND flag", vec![3]),
+        ("SELECT i.id FROM Item i WHERE i.name = 'Glue'", vec![1]),
+        (
+            "SELECT a.id FROM Item a JOIN Item b ON a.id = b.id + 1 WHERE a.rate > 1 AND b.flag",
+            vec![2, 4],
+        ),
+    ] {
+        let rows = expected.into_iter().map(|id| vec![I64(id)]).collect();
+        let labels = vec!["id".to_owned()];
+
+        g.test(sql, Ok(Payload::Select { labels, rows })).await;
+    }
+
+    g.run("UPDATE Item SET name = 'Glue!' WHERE name = 'Glue' OR id = 3")
+        .await;
+    g.run("DELETE FROM Item WHERE rate > 3").await;
+    g.test(
+        "SELECT id, name FROM Item",
+        Ok(select_with_null!(
+            id     | name;
+            I64(1)   Str("Glue!".to_owned());
+            I64(2)   Str("SQL".to_owned());
+            I64(3)   Null
+        )),
+    )
+    .await;
+});
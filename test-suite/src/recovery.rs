@@ -0,0 +1,98 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::FetchError,
+        prelude::{Payload, Value::*},
+        store::{GStore, GStoreMut},
+    },
+};
+
+/// Checks that what was written before the storage was reopened is all still there.
+pub async fn committed<T, U>(mut tester: U)
+where
+    T: GStore + GStoreMut,
+    U: RecoveryTester<T>,
+{
+    tester
+        .run("CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT NULL)")
+        .await;
+    tester.run("CREATE TABLE Dropped (id INTEGER)").await;
+    tester
+        .run("INSERT INTO Item VALUES (1, 'Glue'), (2, 'SQL'), (3, NULL)")
+        .await;
+    tester
+        .run("UPDATE Item SET name = 'Rust' WHERE id = 3")
+        .await;
+    tester.run("DELETE FROM Item WHERE id = 2").await;
+    tester.run("DROP TABLE Dropped").await;
+
+    let mut tester = tester.reopen().await;
+
+    tester
+        .test(
+            "SELECT id, name FROM Item",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                1     "Glue".to_owned();
+                3     "Rust".to_owned()
+            )),
+        )
+        .await;
+    tester
+        .test(
+            "SELECT * FROM Dropped",
+            Err(FetchError::TableNotFound("Dropped".to_owned()).into()),
+        )
+        .await;
+
+    tester
+        .test("INSERT INTO Item VALUES (2, 'SQL')", Ok(Payload::Insert(1)))
+        .await;
+    tester.count("SELECT * FROM Item", 3).await;
+}
+
+/// Checks that a transaction left unfinished when the storage was reopened is rolled back,
+/// without keeping the storage from starting new ones.
+pub async fn transaction<T, U>(mut tester: U)
+where
+    T: GStore + GStoreMut,
+    U: RecoveryTester<T>,
+{
+    tester
+        .run("CREATE TABLE Item (id INTEGER, name TEXT)")
+        .await;
+    tester.run("INSERT INTO Item VALUES (1, 'Glue')").await;
+
+    tester.run("BEGIN").await;
+    tester.run("INSERT INTO Item VALUES (2, 'SQL')").await;
+    tester
+        .run("UPDATE Item SET name = 'Rust' WHERE id = 1")
+        .await;
+    tester.run("CREATE TABLE Unfinished (id INTEGER)").await;
+
+    let mut tester = tester.reopen().await;
+
+    tester
+        .test(
+            "SELECT id, name FROM Item",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                1     "Glue".to_owned()
+            )),
+        )
+        .await;
+    tester
+        .test(
+            "SELECT * FROM Unfinished",
+            Err(FetchError::TableNotFound("Unfinished".to_owned()).into()),
+        )
+        .await;
+
+    tester.run("BEGIN").await;
+    tester.run("INSERT INTO Item VALUES (2, 'SQL')").await;
+    tester.run("COMMIT").await;
+    tester.run("INSERT INTO Item VALUES (3, 'Rust')").await;
+    tester.count("SELECT * FROM Item", 3).await;
+}
@@ -0,0 +1,118 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{ExecuteError, FetchError},
+        prelude::{
+            Payload,
+            Value::{self, *},
+        },
+    },
+};
+
+fn returned(labels: &[&str], rows: Vec<Vec<Value>>) -> Payload {
+    Payload::Returning {
+        labels: labels.iter().map(|label| (*label).to_owned()).collect(),
+        rows,
+    }
+}
+
+test_case!(returning, {
+    let g = get_tester!();
+
+    g.run(
+        "
+CREATE TABLE Item (
+    id INTEGER PRIMARY KEY,
+    name TEXT,
+    price INTEGER DEFAULT 100
+);",
+    )
+    .await;
+
+    g.named_test(
+        "INSERT RETURNING * returns the rows with their defaults",
+        "INSERT INTO Item (id, name) VALUES (1, 'Glue'), (2, 'SQL') RETURNING *;",
+        Ok(returned(
+            &["id", "name", "price"],
+            vec![
+                vec![I64(1), Str("Glue".to_owned()), I64(100)],
+                vec![I64(2), Str("SQL".to_owned()), I64(100)],
+            ],
+        )),
+    )
+    .await;
+    g.named_test(
+        "RETURNING evaluates expressions with their labels",
+        "INSERT INTO Item VALUES (3, 'Rust', 30) RETURNING id, price * 2 AS doubled;",
+        Ok(returned(&["id", "doubled"], vec![vec![I64(3), I64(60)]])),
+    )
+    .await;
+    g.named_test(
+        "INSERT ON CONFLICT RETURNING returns inserted and updated rows",
+        "INSERT INTO Item VALUES (3, 'Rust', 35), (4, 'Go', 40)
+        ON CONFLICT (id) DO UPDATE SET price = EXCLUDED.price
+        RETURNING id, price;",
+        Ok(returned(
+            &["id", "price"],
+            vec![vec![I64(4), I64(40)], vec![I64(3), I64(35)]],
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "UPDATE RETURNING returns the rows after the update",
+        "UPDATE Item SET price = price + 1 WHERE id < 3 RETURNING Item.*;",
+        Ok(returned(
+            &["id", "name", "price"],
+            vec![
+                vec![I64(1), Str("Glue".to_owned()), I64(101)],
+                vec![I64(2), Str("SQL".to_owned()), I64(101)],
+            ],
+        )),
+    )
+    .await;
+    g.named_test(
+        "DELETE RETURNING returns the deleted rows",
+        "DELETE FROM Item WHERE id = 4 RETURNING name;",
+        Ok(returned(&["name"], vec![vec![Str("Go".to_owned())]])),
+    )
+    .await;
+    g.named_test(
+        "nothing affected returns no rows",
+        "DELETE FROM Item WHERE id = 10 RETURNING id;",
+        Ok(returned(&["id"], Vec::new())),
+    )
+    .await;
+    g.named_test(
+        "without RETURNING the count is returned",
+        "DELETE FROM Item WHERE id = 3;",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+    g.test("SELECT id FROM Item;", Ok(select!(id I64; 1; 2)))
+        .await;
+
+    g.named_test(
+        "RETURNING of another table fails",
+        "UPDATE Item SET price = 0 RETURNING Other.*;",
+        Err(FetchError::TableAliasNotFound("Other".to_owned()).into()),
+    )
+    .await;
+
+    g.run("CREATE TABLE Logs;").await;
+    g.named_test(
+        "schemaless rows return the fields named",
+        r#"INSERT INTO Logs VALUES ('{"id": 1, "level": "info"}') RETURNING id, level;"#,
+        Ok(returned(
+            &["id", "level"],
+            vec![vec![I64(1), Str("info".to_owned())]],
+        )),
+    )
+    .await;
+    g.named_test(
+        "RETURNING * needs a schema",
+        "DELETE FROM Logs RETURNING *;",
+        Err(ExecuteError::ReturningWildcardOnSchemalessTable("Logs".to_owned()).into()),
+    )
+    .await;
+});
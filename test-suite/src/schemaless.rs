@@ -1,4 +1,5 @@
 mod basic;
 mod error;
+mod json;
 
-pub use {basic::basic, error::error};
+pub use {basic::basic, error::error, json::json};
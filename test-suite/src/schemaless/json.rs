@@ -0,0 +1,69 @@
+use {
+    crate::*,
+    gluesql_core::prelude::{
+        Payload,
+        Value::{self, *},
+    },
+    serde_json::json,
+};
+
+test_case!(json, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Purchase").await;
+    g.run(
+        format!(
+            "INSERT INTO Purchase VALUES ('{}'), ('{}');",
+            json!({
+                "id": 1,
+                "customer": { "name": "Ann", "address": { "city": "Seoul" } },
+                "lines": [{ "sku": "A-1", "qty": 2 }, { "sku": "B-7", "qty": 1 }]
+            }),
+            json!({
+                "id": 2,
+                "customer": { "name": "Bo" },
+                "lines": []
+            }),
+        )
+        .as_str(),
+    )
+    .await;
+
+    g.test(
+        "
+        SELECT
+            id,
+            customer ->> 'name' AS name,
+            JSON_EXTRACT(customer, '$.address.city') AS city,
+            JSON_EXTRACT(lines, '$[0].sku') AS sku
+        FROM Purchase
+        ",
+        Ok(select_with_null!(
+            id     | name                  | city                    | sku;
+            I64(1)   Str("Ann".to_owned())   Str("Seoul".to_owned())   Str("A-1".to_owned());
+            I64(2)   Str("Bo".to_owned())    Null                      Null
+        )),
+    )
+    .await;
+
+    g.test(
+        "
+        UPDATE Purchase
+        SET
+            customer = JSON_SET(customer, '$.address.city', 'Busan'),
+            lines = JSON_REMOVE(JSON_SET(lines, '$[1].qty', 3), '$[0]')
+        WHERE id = 1
+        ",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+    g.test(
+        "SELECT * FROM Purchase WHERE id = 1",
+        Ok(select_map![json!({
+            "id": 1,
+            "customer": { "name": "Ann", "address": { "city": "Busan" } },
+            "lines": [{ "sku": "B-7", "qty": 3 }]
+        })]),
+    )
+    .await;
+});
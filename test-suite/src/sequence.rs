@@ -0,0 +1,239 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, EvaluateError, ExecuteError, PlanError, TranslateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(sequence, {
+    let g = get_tester!();
+
+    g.named_test(
+        "CREATE SEQUENCE",
+        "CREATE SEQUENCE Ticket",
+        Ok(Payload::Create),
+    )
+    .await;
+    g.named_test(
+        "CURRVAL before the first NEXTVAL",
+        "SELECT CURRVAL('Ticket')",
+        Err(ExecuteError::SequenceValueNotDefined("Ticket".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "NEXTVAL starts at 1",
+        "SELECT NEXTVAL('Ticket') AS id",
+        Ok(select!(id I64; 1)),
+    )
+    .await;
+    g.named_test(
+        "each call of NEXTVAL takes the next value",
+        "SELECT NEXTVAL('Ticket') AS a, NEXTVAL('Ticket') AS b, CURRVAL('Ticket') AS c",
+        Ok(select!(a | b | c; I64 | I64 | I64; 2 3 3)),
+    )
+    .await;
+    g.named_test(
+        "a sequence reads as its last value",
+        "SELECT * FROM Ticket",
+        Ok(select!(last_value I64; 3)),
+    )
+    .await;
+
+    g.named_test(
+        "SETVAL returns the value set",
+        "SELECT SETVAL('Ticket', 10) AS id",
+        Ok(select!(id I64; 10)),
+    )
+    .await;
+    g.test("SELECT NEXTVAL('Ticket') AS id", Ok(select!(id I64; 11)))
+        .await;
+
+    g.run("CREATE TABLE Item (id INTEGER PRIMARY KEY, name TEXT)")
+        .await;
+    g.named_test(
+        "NEXTVAL in VALUES",
+        "INSERT INTO Item VALUES (NEXTVAL('Ticket'), 'Glue'), (NEXTVAL('Ticket'), 'SQL')",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.test(
+        "SELECT id, name FROM Item",
+        Ok(select!(
+            id  | name;
+            I64 | Str;
+            12    "Glue".to_owned();
+            13    "SQL".to_owned()
+        )),
+    )
+    .await;
+
+    g.run("CREATE SEQUENCE Countdown INCREMENT BY -10 START WITH 100")
+        .await;
+    g.named_test(
+        "INCREMENT BY and START WITH",
+        "VALUES (NEXTVAL('Countdown')), (NEXTVAL('Countdown'))",
+        Ok(select!(column1 I64; 100; 90)),
+    )
+    .await;
+
+    g.named_test(
+        "CREATE SEQUENCE with a taken name",
+        "CREATE SEQUENCE Item",
+        Err(AlterError::TableAlreadyExists("Item".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "CREATE SEQUENCE IF NOT EXISTS",
+        "CREATE SEQUENCE IF NOT EXISTS Ticket",
+        Ok(Payload::Create),
+    )
+    .await;
+    g.test("SELECT CURRVAL('Ticket') AS id", Ok(select!(id I64; 13)))
+        .await;
+
+    g.named_test(
+        "sequence functions are only resolved as whole values",
+        "SELECT NEXTVAL('Ticket') + 1",
+        Err(EvaluateError::SequenceFunctionNotAllowed("NEXTVAL".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "sequence functions are not resolved for each row",
+        "UPDATE Item SET name = NEXTVAL('Ticket')",
+        Err(EvaluateError::SequenceFunctionNotAllowed("NEXTVAL".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "NEXTVAL of a table",
+        "SELECT NEXTVAL('Item')",
+        Err(AlterError::SequenceNotFound("Item".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "sequences only change through NEXTVAL and SETVAL",
+        "INSERT INTO Ticket VALUES (1)",
+        Err(PlanError::SequenceNotWritable("Ticket".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "DROP TABLE on a sequence",
+        "DROP TABLE Ticket",
+        Err(AlterError::DropTableOnSequence("Ticket".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "unsupported sequence options",
+        "CREATE SEQUENCE Looping CYCLE",
+        Err(TranslateError::UnsupportedSequenceOption("CYCLE".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "sequences move on",
+        "CREATE SEQUENCE Still INCREMENT BY 0",
+        Err(TranslateError::ZeroSequenceIncrement.into()),
+    )
+    .await;
+
+    g.named_test(
+        "DROP SEQUENCE",
+        "DROP SEQUENCE Ticket, Countdown",
+        Ok(Payload::DropSequence(2)),
+    )
+    .await;
+    g.named_test(
+        "DROP SEQUENCE IF EXISTS",
+        "DROP SEQUENCE IF EXISTS Ticket",
+        Ok(Payload::DropSequence(0)),
+    )
+    .await;
+    g.named_test(
+        "DROP SEQUENCE on a table",
+        "DROP SEQUENCE Item",
+        Err(AlterError::SequenceNotFound("Item".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "NEXTVAL of a dropped sequence",
+        "SELECT NEXTVAL('Ticket')",
+        Err(AlterError::SequenceNotFound("Ticket".to_owned()).into()),
+    )
+    .await;
+});
+
+test_case!(serial, {
+    let g = get_tester!();
+
+    g.named_test(
+        "SERIAL creates the sequence of the column",
+        "CREATE TABLE Post (id SERIAL PRIMARY KEY, title TEXT)",
+        Ok(Payload::Create),
+    )
+    .await;
+    g.named_test(
+        "SERIAL columns take the next value of their sequence by default",
+        "INSERT INTO Post (title) VALUES ('Hello'), ('World')",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.run("INSERT INTO Post VALUES (100, 'Given')").await;
+    g.run("INSERT INTO Post (title) VALUES ('Again')").await;
+    g.test(
+        "SELECT id, title FROM Post ORDER BY id",
+        Ok(select!(
+            id  | title;
+            I64 | Str;
+            1     "Hello".to_owned();
+            2     "World".to_owned();
+            3     "Again".to_owned();
+            100   "Given".to_owned()
+        )),
+    )
+    .await;
+    g.test(
+        "SELECT CURRVAL('Post_id_seq') AS id",
+        Ok(select!(id I64; 3)),
+    )
+    .await;
+
+    g.run("CREATE TABLE Comment (body TEXT, id SERIAL)").await;
+    g.named_test(
+        "SERIAL columns after the given values",
+        "INSERT INTO Comment VALUES ('First'), ('Second')",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.test(
+        "SELECT id, body FROM Comment",
+        Ok(select!(
+            id  | body;
+            I64 | Str;
+            1     "First".to_owned();
+            2     "Second".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "SERIAL with a default",
+        "CREATE TABLE Broken (id SERIAL DEFAULT 1)",
+        Err(TranslateError::SerialColumnWithDefault("id".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "dropping a table drops the sequences of its SERIAL columns",
+        "DROP TABLE Post",
+        Ok(Payload::DropTable(1)),
+    )
+    .await;
+    g.test(
+        "SELECT NEXTVAL('Post_id_seq')",
+        Err(AlterError::SequenceNotFound("Post_id_seq".to_owned()).into()),
+    )
+    .await;
+    g.run("CREATE TABLE Post (id SERIAL PRIMARY KEY, title TEXT)")
+        .await;
+    g.run("INSERT INTO Post (title) VALUES ('Restart')").await;
+    g.test("SELECT id FROM Post", Ok(select!(id I64; 1))).await;
+});
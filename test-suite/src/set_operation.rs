@@ -0,0 +1,133 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::{DataType, SetOperator},
+        error::SelectError,
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(set_operation, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE A (n INTEGER NULL)").await;
+    g.run("INSERT INTO A VALUES (1), (1), (1), (2), (3), (NULL), (NULL)")
+        .await;
+    g.run("CREATE TABLE B (n INTEGER NULL, ratio FLOAT)").await;
+    g.run("INSERT INTO B VALUES (1, 0.5), (1, 1.5), (3, 2.5), (4, 3.5), (NULL, 4.5)")
+        .await;
+
+    g.named_test(
+        "UNION removes duplicates, NULLs included",
+        "SELECT n FROM A UNION SELECT n FROM B ORDER BY n",
+        Ok(select_with_null!(n; I64(1); I64(2); I64(3); I64(4); Null)),
+    )
+    .await;
+
+    g.named_test(
+        "UNION ALL keeps duplicates",
+        "SELECT COUNT(*) AS cnt FROM (SELECT n FROM A UNION ALL SELECT n FROM B) AS U",
+        Ok(select!(cnt I64; 12)),
+    )
+    .await;
+
+    g.named_test(
+        "INTERSECT",
+        "SELECT n FROM A INTERSECT SELECT n FROM B ORDER BY n",
+        Ok(select_with_null!(n; I64(1); I64(3); Null)),
+    )
+    .await;
+
+    g.named_test(
+        "INTERSECT ALL keeps the lower count of each row",
+        "SELECT n FROM A INTERSECT ALL SELECT n FROM B ORDER BY n",
+        Ok(select_with_null!(n; I64(1); I64(1); I64(3); Null)),
+    )
+    .await;
+
+    g.named_test(
+        "EXCEPT",
+        "SELECT n FROM A EXCEPT SELECT n FROM B ORDER BY n",
+        Ok(select!(n I64; 2)),
+    )
+    .await;
+
+    g.named_test(
+        "EXCEPT ALL subtracts the count of each row",
+        "SELECT n FROM A EXCEPT ALL SELECT n FROM B ORDER BY n",
+        Ok(select_with_null!(n; I64(1); I64(2); Null)),
+    )
+    .await;
+
+    g.named_test(
+        "INTERSECT binds tighter than UNION and EXCEPT",
+        "SELECT 4 AS n UNION SELECT n FROM A EXCEPT SELECT n FROM A INTERSECT SELECT 2",
+        Ok(select_with_null!(n; I64(4); I64(1); I64(3); Null)),
+    )
+    .await;
+
+    g.named_test(
+        "parentheses, ORDER BY, LIMIT and OFFSET of the whole query",
+        "
+        (SELECT n FROM A UNION SELECT 4) EXCEPT SELECT NULL
+        ORDER BY n DESC
+        LIMIT 2 OFFSET 1
+        ",
+        Ok(select!(n I64; 3; 2)),
+    )
+    .await;
+
+    g.named_test(
+        "columns are labeled after the first query",
+        "SELECT 1 AS first, 'a' AS second UNION ALL VALUES (2, 'b')",
+        Ok(select!(
+            first | second;
+            I64   | Str;
+            1       "a".to_owned();
+            2       "b".to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "numeric columns of different types are matched",
+        "SELECT n FROM A WHERE n = 2 UNION ALL SELECT ratio FROM B WHERE n = 3",
+        Ok(select!(n F64; 2.0; 2.5)),
+    )
+    .await;
+
+    g.named_test(
+        "set operation in a subquery",
+        "SELECT ratio FROM B WHERE n IN (SELECT n FROM A EXCEPT SELECT 3) ORDER BY ratio",
+        Ok(select!(ratio F64; 0.5; 1.5)),
+    )
+    .await;
+
+    g.run("CREATE TABLE C (n INTEGER NULL)").await;
+    g.named_test(
+        "INSERT from a set operation",
+        "INSERT INTO C SELECT n FROM A INTERSECT SELECT n FROM B",
+        Ok(Payload::Insert(3)),
+    )
+    .await;
+
+    let test_cases = [
+        (
+            "SELECT n FROM A UNION SELECT n, ratio FROM B",
+            SelectError::SetOperationColumnsDifferent(SetOperator::Union).into(),
+        ),
+        (
+            "SELECT n FROM A INTERSECT SELECT 'text'",
+            SelectError::SetOperationTypesDifferent {
+                op: SetOperator::Intersect,
+                left: DataType::Int,
+                right: DataType::Text,
+            }
+            .into(),
+        ),
+    ];
+
+    for (sql, error) in test_cases {
+        g.test(sql, Err(error)).await;
+    }
+});
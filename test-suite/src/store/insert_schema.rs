@@ -23,7 +23,12 @@ test_case!(insert_schema, {
         indexes: Vec::new(),
         engine: None,
         foreign_keys: Vec::new(),
+        checks: Vec::new(),
+        uniques: Vec::new(),
         comment: Some("this is comment for table".to_owned()),
+        view: None,
+        triggers: Vec::new(),
+        sequence: None,
     };
 
     storage.begin(true).await.unwrap();
@@ -57,7 +62,12 @@ test_case!(insert_schema, {
         indexes: Vec::new(),
         engine: None,
         foreign_keys: Vec::new(),
+        checks: Vec::new(),
+        uniques: Vec::new(),
         comment: Some("this is comment for schemaless table".to_owned()),
+        view: None,
+        triggers: Vec::new(),
+        sequence: None,
     };
     storage.insert_schema(&schema).await.unwrap();
 
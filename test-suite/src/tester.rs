@@ -190,6 +190,15 @@ pub trait Tester<T: GStore + GStoreMut> {
     }
 }
 
+/// Implemented by the testers of persistent storages to run the recovery tests, declared as
+/// the `recovery` capability of [`generate_conformance_tests!`].
+#[async_trait(?Send)]
+pub trait RecoveryTester<T: GStore + GStoreMut>: Tester<T> + Sized {
+    /// Drops the storage as it is, without finishing its transaction, and opens it again
+    /// from the same location.
+    async fn reopen(self) -> Self;
+}
+
 #[macro_export]
 macro_rules! test_case {
     ($name: ident, $content: expr) => {
@@ -10,6 +10,15 @@ macro_rules! idx {
     () => {
         vec![]
     };
+    ($name: path, MATCH, $sql_expr: literal) => {
+        vec![gluesql_core::ast::IndexItem::FullText {
+            name: stringify_label!($name).to_owned(),
+            query: gluesql_core::translate::translate_expr(
+                &gluesql_core::parse_sql::parse_expr($sql_expr).unwrap(),
+            )
+            .unwrap(),
+        }]
+    };
     ($name: path, $op: path, $sql_expr: literal) => {
         vec![gluesql_core::ast::IndexItem::NonClustered {
             name: stringify_label!($name).to_owned(),
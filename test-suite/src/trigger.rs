@@ -0,0 +1,264 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, InsertError, TranslateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(trigger, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Account (id INTEGER PRIMARY KEY, owner TEXT, balance INTEGER)")
+        .await;
+    g.run(
+        "CREATE TABLE AccountLog (event TEXT, id INTEGER, old_balance INTEGER NULL, new_balance INTEGER NULL)",
+    )
+    .await;
+    g.run("CREATE TABLE Stats (id INTEGER PRIMARY KEY, accounts INTEGER)")
+        .await;
+    g.run("INSERT INTO Account VALUES (1, 'Alice', 100)").await;
+    g.run("INSERT INTO Stats VALUES (1, 1)").await;
+
+    g.named_test(
+        "CREATE TRIGGER",
+        "
+        CREATE TRIGGER log_insert AFTER INSERT ON Account FOR EACH ROW
+        BEGIN
+            INSERT INTO AccountLog VALUES ('insert', NEW.id, NULL, NEW.balance);
+            UPDATE Stats SET accounts = accounts + 1 WHERE id = 1;
+        END
+        ",
+        Ok(Payload::CreateTrigger),
+    )
+    .await;
+    g.named_test(
+        "creating a trigger keeps the rows of its table",
+        "SELECT id, owner FROM Account",
+        Ok(select!(id | owner; I64 | Str; 1 "Alice".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "AFTER INSERT runs the body for each inserted row",
+        "INSERT INTO Account VALUES (2, 'Bob', 200), (3, 'O''Brien', 300)",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.test(
+        "SELECT event, id, new_balance FROM AccountLog ORDER BY id",
+        Ok(select_with_null!(
+            event                | id    | new_balance;
+            Str("insert".to_owned()) I64(2) I64(200);
+            Str("insert".to_owned()) I64(3) I64(300)
+        )),
+    )
+    .await;
+    g.test("SELECT accounts FROM Stats", Ok(select!(accounts I64; 3)))
+        .await;
+
+    g.run(
+        "
+        CREATE TRIGGER log_owner AFTER INSERT ON Account
+        BEGIN
+            INSERT INTO AccountLog VALUES (NEW.owner, NEW.id, NULL, NULL);
+        END
+        ",
+    )
+    .await;
+    g.run("INSERT INTO Account VALUES (4, 'O''Neil', 0)").await;
+    g.named_test(
+        "text of the changed row is read as a literal",
+        "SELECT event FROM AccountLog WHERE id = 4 AND new_balance IS NULL",
+        Ok(select!(event Str; "O'Neil".to_owned())),
+    )
+    .await;
+
+    g.run("CREATE TABLE Price (id INTEGER, amount DECIMAL)")
+        .await;
+    g.run("CREATE TABLE PriceLog (old_id INTEGER, new_id INTEGER, amount DECIMAL)")
+        .await;
+    g.run(
+        "
+        CREATE TRIGGER log_price AFTER UPDATE ON Price
+        WHEN new.id <> Old.id
+        BEGIN
+            INSERT INTO PriceLog VALUES (old.id, New.id, new.amount);
+        END
+        ",
+    )
+    .await;
+    g.run("INSERT INTO Price VALUES (1, 12345678901234.567890123456789)")
+        .await;
+    g.run("UPDATE Price SET id = 2").await;
+    // storages keeping decimals as JSON numbers round them, so the log is compared with the
+    // amount the storage holds
+    g.named_test(
+        "NEW and OLD are read in any case, and decimals without losing digits",
+        "SELECT old_id, new_id, amount = (SELECT amount FROM Price) AS same FROM PriceLog",
+        Ok(select!(
+            old_id | new_id | same;
+            I64    | I64    | Bool;
+            1        2        true
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "CREATE TRIGGER with a taken name",
+        "CREATE TRIGGER log_owner AFTER DELETE ON Account BEGIN DELETE FROM AccountLog; END",
+        Err(AlterError::TriggerAlreadyExists("log_owner".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "CREATE OR REPLACE TRIGGER",
+        "
+        CREATE OR REPLACE TRIGGER log_owner AFTER UPDATE ON Account
+        WHEN NEW.balance <> OLD.balance
+        BEGIN
+            INSERT INTO AccountLog VALUES ('update', NEW.id, OLD.balance, NEW.balance);
+        END
+        ",
+        Ok(Payload::CreateTrigger),
+    )
+    .await;
+
+    g.named_test(
+        "AFTER UPDATE reads both OLD and NEW, for the rows WHEN holds for",
+        "UPDATE Account SET balance = balance + 50 WHERE id < 3",
+        Ok(Payload::Update(2)),
+    )
+    .await;
+    g.run("UPDATE Account SET owner = 'Bobby' WHERE id = 2")
+        .await;
+    g.test(
+        "SELECT id, old_balance, new_balance FROM AccountLog WHERE event = 'update' ORDER BY id",
+        Ok(select!(
+            id  | old_balance | new_balance;
+            I64 | I64         | I64;
+            1     100           150;
+            2     200           250
+        )),
+    )
+    .await;
+
+    g.run(
+        "
+        CREATE TRIGGER log_delete BEFORE DELETE ON Account
+        BEGIN
+            INSERT INTO AccountLog VALUES ('delete', OLD.id, OLD.balance, NEW.balance);
+            UPDATE Stats SET accounts = accounts - 1 WHERE id = 1;
+        END
+        ",
+    )
+    .await;
+    g.named_test(
+        "BEFORE DELETE reads the deleted row as OLD, and NEW as NULL",
+        "DELETE FROM Account WHERE id = 3",
+        Ok(Payload::Delete(1)),
+    )
+    .await;
+    g.test(
+        "SELECT id, old_balance, new_balance FROM AccountLog WHERE event = 'delete'",
+        Ok(select_with_null!(
+            id     | old_balance | new_balance;
+            I64(3)   I64(300)      Null
+        )),
+    )
+    .await;
+    g.test("SELECT accounts FROM Stats", Ok(select!(accounts I64; 3)))
+        .await;
+
+    g.run(
+        "
+        CREATE TRIGGER check_owner BEFORE INSERT ON Account
+        BEGIN
+            INSERT INTO Missing VALUES (NEW.id);
+        END
+        ",
+    )
+    .await;
+    g.named_test(
+        "a failing BEFORE trigger stops the statement before any row is written",
+        "INSERT INTO Account VALUES (5, 'Eve', 500)",
+        Err(InsertError::TableNotFound("Missing".to_owned()).into()),
+    )
+    .await;
+    g.count("SELECT * FROM Account WHERE id = 5", 0).await;
+
+    g.named_test(
+        "DROP TRIGGER",
+        "DROP TRIGGER check_owner ON Account",
+        Ok(Payload::DropTrigger),
+    )
+    .await;
+    g.run("INSERT INTO Account VALUES (5, 'Eve', 500)").await;
+    g.count("SELECT * FROM Account WHERE id = 5", 1).await;
+
+    g.named_test(
+        "DROP TRIGGER IF EXISTS",
+        "DROP TRIGGER IF EXISTS check_owner ON Account",
+        Ok(Payload::DropTrigger),
+    )
+    .await;
+    g.named_test(
+        "DROP TRIGGER on a missing trigger",
+        "DROP TRIGGER check_owner ON Account",
+        Err(AlterError::TriggerNotFound("check_owner".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "triggers whose writes would set them off again",
+        "CREATE TRIGGER again AFTER INSERT ON AccountLog BEGIN UPDATE Account SET balance = 0; END",
+        Err(AlterError::TriggerFiresItself("AccountLog".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "CREATE TRIGGER on a missing table",
+        "CREATE TRIGGER t AFTER INSERT ON Missing BEGIN DELETE FROM Stats; END",
+        Err(AlterError::TableNotFound("Missing".to_owned()).into()),
+    )
+    .await;
+    g.run("CREATE VIEW Rich AS SELECT * FROM Account WHERE balance > 200")
+        .await;
+    g.named_test(
+        "CREATE TRIGGER on a view",
+        "CREATE TRIGGER t AFTER INSERT ON Rich BEGIN DELETE FROM Stats; END",
+        Err(AlterError::TriggerOnView("Rich".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "trigger bodies only modify tables",
+        "CREATE TRIGGER t AFTER INSERT ON Account BEGIN SELECT * FROM Stats; END",
+        Err(
+            TranslateError::UnsupportedTriggerStatement(r#"SELECT * FROM Stats"#.to_owned()).into(),
+        ),
+    )
+    .await;
+    g.named_test(
+        "INSTEAD OF triggers are not supported",
+        "CREATE TRIGGER t INSTEAD OF INSERT ON Account BEGIN DELETE FROM Stats; END",
+        Err(TranslateError::UnsupportedTriggerTiming("INSTEAD OF".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "UPDATE OF columns is not supported",
+        "CREATE TRIGGER t AFTER UPDATE OF balance ON Account BEGIN DELETE FROM Stats; END",
+        Err(TranslateError::UnsupportedTriggerEvent("UPDATE OF balance".to_owned()).into()),
+    )
+    .await;
+
+    g.run("DROP VIEW Rich").await;
+    g.named_test(
+        "dropping a table drops its triggers",
+        "DROP TABLE Account",
+        Ok(Payload::DropTable(1)),
+    )
+    .await;
+    g.run("CREATE TABLE Account (id INTEGER PRIMARY KEY, owner TEXT, balance INTEGER)")
+        .await;
+    g.run("INSERT INTO Account VALUES (1, 'Alice', 100)").await;
+    g.test("SELECT accounts FROM Stats", Ok(select!(accounts I64; 4)))
+        .await;
+});
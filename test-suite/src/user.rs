@@ -0,0 +1,188 @@
+use {
+    crate::*,
+    gluesql_core::{
+        ast::Action,
+        error::{AlterError, PrivilegeError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(user, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Employee (id INTEGER, name TEXT, salary INTEGER);")
+        .await;
+    g.run("INSERT INTO Employee VALUES (1, 'Alice', 100), (2, 'Bob', 200);")
+        .await;
+    g.run("CREATE TABLE Bonus (employee_id INTEGER PRIMARY KEY, amount INTEGER);")
+        .await;
+    g.run("INSERT INTO Bonus VALUES (1, 10);").await;
+    g.run("CREATE TABLE Team (id INTEGER);").await;
+
+    let denied = |action, table_name: &str| {
+        Err(PrivilegeError::PermissionDenied {
+            user: "alice".to_owned(),
+            action,
+            table_name: table_name.to_owned(),
+        }
+        .into())
+    };
+
+    let test_cases = [
+        (
+            "CREATE USER alice WITH PASSWORD 'secret'",
+            Ok(Payload::Create),
+        ),
+        (
+            "CREATE USER alice",
+            Err(AlterError::UserAlreadyExists("alice".to_owned()).into()),
+        ),
+        ("CREATE USER IF NOT EXISTS alice", Ok(Payload::Create)),
+        ("CREATE USER admin SUPERUSER", Ok(Payload::Create)),
+        (
+            "GRANT SELECT (id, name) ON Employee TO alice",
+            Ok(Payload::Grant),
+        ),
+        (
+            "GRANT SELECT (amount) ON Bonus TO alice",
+            Ok(Payload::Grant),
+        ),
+        (
+            "GRANT SELECT (age) ON Employee TO alice",
+            Err(AlterError::ColumnNotFound("Employee.age".to_owned()).into()),
+        ),
+        (
+            "GRANT SELECT ON Missing TO alice",
+            Err(AlterError::TableNotFound("Missing".to_owned()).into()),
+        ),
+        (
+            "GRANT SELECT ON Employee TO nobody",
+            Err(AlterError::UserNotFound("nobody".to_owned()).into()),
+        ),
+    ];
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+
+    g.get_glue().set_user(Some("alice"));
+    let test_cases = [
+        (
+            "SELECT id, name FROM Employee",
+            Ok(select!(
+                id  | name
+                I64 | Str;
+                1     "Alice".to_owned();
+                2     "Bob".to_owned()
+            )),
+        ),
+        ("SELECT COUNT(*) AS n FROM Employee", Ok(select!(n I64; 2))),
+        ("SELECT * FROM Employee", denied(Action::Select, "Employee")),
+        (
+            "SELECT id FROM Employee WHERE salary > 150",
+            denied(Action::Select, "Employee"),
+        ),
+        ("SELECT amount FROM Bonus", Ok(select!(amount I64; 10))),
+        (
+            "SELECT amount FROM Bonus WHERE employee_id = 1",
+            denied(Action::Select, "Bonus"),
+        ),
+        (
+            "SELECT id FROM Employee WHERE id = (SELECT MAX(salary) FROM Employee)",
+            denied(Action::Select, "Employee"),
+        ),
+        ("SELECT * FROM Team", denied(Action::Select, "Team")),
+        (
+            "INSERT INTO Employee VALUES (3, 'Carol', 300)",
+            denied(Action::Insert, "Employee"),
+        ),
+        (
+            "CREATE TABLE Project (id INTEGER)",
+            Err(PrivilegeError::SuperuserRequired("alice".to_owned()).into()),
+        ),
+        (
+            "GRANT SELECT ON Team TO alice",
+            Err(PrivilegeError::SuperuserRequired("alice".to_owned()).into()),
+        ),
+    ];
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+
+    g.get_glue().set_user(Some("admin"));
+    g.run("GRANT INSERT, UPDATE (name) ON Employee TO alice")
+        .await;
+    g.run("GRANT ALL PRIVILEGES ON Team TO alice").await;
+
+    g.get_glue().set_user(Some("alice"));
+    let test_cases = [
+        (
+            "INSERT INTO Employee VALUES (3, 'Carol', 300)",
+            Ok(Payload::Insert(1)),
+        ),
+        (
+            "UPDATE Employee SET name = 'Dave' WHERE id = 3",
+            Ok(Payload::Update(1)),
+        ),
+        (
+            "UPDATE Employee SET name = 'Dave' WHERE salary = 300",
+            denied(Action::Select, "Employee"),
+        ),
+        (
+            "UPDATE Employee SET salary = 0",
+            denied(Action::Update, "Employee"),
+        ),
+        ("DELETE FROM Employee", denied(Action::Delete, "Employee")),
+        ("INSERT INTO Team VALUES (1)", Ok(Payload::Insert(1))),
+        ("DELETE FROM Team", Ok(Payload::Delete(1))),
+        (
+            "SELECT * FROM Team",
+            Ok(Payload::Select {
+                labels: vec!["id".to_owned()],
+                rows: Vec::new(),
+            }),
+        ),
+    ];
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+
+    g.get_glue().set_user(Some("admin"));
+    g.run("REVOKE SELECT (name) ON Employee FROM alice").await;
+
+    g.get_glue().set_user(Some("alice"));
+    g.test(
+        "SELECT name FROM Employee",
+        denied(Action::Select, "Employee"),
+    )
+    .await;
+    g.test(
+        "SELECT id FROM Employee WHERE id = 1",
+        Ok(select!(id I64; 1)),
+    )
+    .await;
+
+    g.get_glue().set_user(Some("admin"));
+    let test_cases = [
+        ("CREATE TABLE Project (id INTEGER)", Ok(Payload::Create)),
+        ("DROP USER alice", Ok(Payload::DropUser)),
+        (
+            "DROP USER alice",
+            Err(AlterError::UserNotFound("alice".to_owned()).into()),
+        ),
+        ("DROP USER IF EXISTS alice", Ok(Payload::DropUser)),
+    ];
+    for (sql, expected) in test_cases {
+        g.test(sql, expected).await;
+    }
+
+    g.get_glue().set_user(Some("alice"));
+    g.test(
+        "SELECT 1",
+        Err(PrivilegeError::UserNotFound("alice".to_owned()).into()),
+    )
+    .await;
+
+    g.get_glue().set_user(None);
+    g.test("DROP TABLE Project", Ok(Payload::DropTable(1)))
+        .await;
+});
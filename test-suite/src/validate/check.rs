@@ -0,0 +1,148 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, TranslateError, ValidateError},
+        prelude::{Payload, Value::*},
+    },
+};
+
+test_case!(check, {
+    let g = get_tester!();
+
+    g.named_test(
+        "column and table CHECK constraints",
+        "CREATE TABLE Item (
+            id INTEGER PRIMARY KEY,
+            price INTEGER CHECK (price > 0),
+            discount INTEGER,
+            CONSTRAINT discount_under_price CHECK (discount < price)
+        );",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.named_test(
+        "rows passing the checks are inserted",
+        "INSERT INTO Item VALUES (1, 100, 10), (2, 50, NULL);",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.named_test(
+        "column check fails on INSERT",
+        "INSERT INTO Item VALUES (3, 0, 0);",
+        Err(ValidateError::CheckConstraintViolated("Item_price_check".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "table check fails on INSERT",
+        "INSERT INTO Item VALUES (3, 10, 20);",
+        Err(ValidateError::CheckConstraintViolated("discount_under_price".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "checks are validated on INSERT with SELECT",
+        "INSERT INTO Item SELECT id + 10, price - 100, NULL FROM Item;",
+        Err(ValidateError::CheckConstraintViolated("Item_price_check".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "column check fails on UPDATE",
+        "UPDATE Item SET price = -1 WHERE id = 2;",
+        Err(ValidateError::CheckConstraintViolated("Item_price_check".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "table check reads the columns UPDATE leaves alone",
+        "UPDATE Item SET price = 5 WHERE id = 1;",
+        Err(ValidateError::CheckConstraintViolated("discount_under_price".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "a check evaluating to NULL passes",
+        "UPDATE Item SET price = 5 WHERE id = 2;",
+        Ok(Payload::Update(1)),
+    )
+    .await;
+    g.test(
+        "SELECT id, price, discount FROM Item",
+        Ok(select_with_null!(
+            id     | price    | discount;
+            I64(1)   I64(100)   I64(10);
+            I64(2)   I64(5)     Null
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "columns read by a check cannot be dropped",
+        "ALTER TABLE Item DROP COLUMN discount;",
+        Err(AlterError::CannotAlterCheckedColumn {
+            check: "discount_under_price".to_owned(),
+            column: "discount".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.named_test(
+        "columns read by a check cannot be renamed",
+        "ALTER TABLE Item RENAME COLUMN price TO cost;",
+        Err(AlterError::CannotAlterCheckedColumn {
+            check: "Item_price_check".to_owned(),
+            column: "price".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "unnamed checks of the same column are numbered",
+        "CREATE TABLE Range (
+            low INTEGER CHECK (low >= 0) CHECK (low < 100),
+            high INTEGER,
+            CHECK (low <= high),
+            CHECK (high < 1000)
+        );",
+        Ok(Payload::Create),
+    )
+    .await;
+    g.run("INSERT INTO Range VALUES (1, 2);").await;
+    for (sql, name) in [
+        ("INSERT INTO Range VALUES (-1, 2);", "Range_low_check"),
+        ("INSERT INTO Range VALUES (100, 200);", "Range_low_check1"),
+        ("INSERT INTO Range VALUES (2, 1);", "Range_check"),
+        ("INSERT INTO Range VALUES (1, 1000);", "Range_check1"),
+    ] {
+        g.test(
+            sql,
+            Err(ValidateError::CheckConstraintViolated(name.to_owned()).into()),
+        )
+        .await;
+    }
+
+    g.named_test(
+        "check names must be unique",
+        "CREATE TABLE Dup (
+            a INTEGER CONSTRAINT positive CHECK (a > 0),
+            CONSTRAINT positive CHECK (a < 10)
+        );",
+        Err(TranslateError::DuplicateCheckConstraint("positive".to_owned()).into()),
+    )
+    .await;
+    g.named_test(
+        "checks can only read columns of the table",
+        "CREATE TABLE Wrong (a INTEGER CHECK (b > 0));",
+        Err(AlterError::CheckColumnNotFound {
+            check: "Wrong_a_check".to_owned(),
+            column: "b".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.named_test(
+        "checks cannot read other rows",
+        "CREATE TABLE Wrong (a INTEGER CHECK (a > (SELECT MAX(price) FROM Item)));",
+        Err(AlterError::UnsupportedCheckExpr("Wrong_a_check".to_owned()).into()),
+    )
+    .await;
+});
@@ -1,6 +1,9 @@
 use {
     crate::*,
-    gluesql_core::{error::ValidateError, prelude::Value},
+    gluesql_core::{
+        error::{AlterError, InsertError, TranslateError, ValidateError},
+        prelude::{Payload, Value},
+    },
 };
 
 test_case!(unique, {
@@ -86,3 +89,134 @@ test_case!(unique, {
         g.test(sql, Err(error)).await;
     }
 });
+
+test_case!(unique_constraint, {
+    let g = get_tester!();
+
+    g.run(
+        "
+        CREATE TABLE Seat (
+            hall TEXT,
+            row INTEGER,
+            number INTEGER,
+            holder TEXT,
+            UNIQUE (hall, row, number),
+            CONSTRAINT one_seat_per_holder UNIQUE (hall, holder)
+        )
+        ",
+    )
+    .await;
+    g.run("INSERT INTO Seat VALUES ('A', 1, 1, 'Alice'), ('A', 1, 2, 'Bob'), ('B', 1, 1, 'Alice')")
+        .await;
+
+    g.named_test(
+        "rows differing on any column of the constraint do not conflict",
+        "INSERT INTO Seat VALUES ('A', 2, 1, NULL), ('B', 1, 2, 'Bob')",
+        Ok(Payload::Insert(2)),
+    )
+    .await;
+    g.named_test(
+        "rows with NULL in a column of the constraint never conflict",
+        "INSERT INTO Seat VALUES ('A', NULL, 1, NULL), ('A', NULL, 1, NULL), ('C', 1, 1, NULL)",
+        Ok(Payload::Insert(3)),
+    )
+    .await;
+
+    let error_cases = [
+        (
+            "INSERT INTO Seat VALUES ('A', 1, 2, 'Carol')",
+            ValidateError::DuplicateEntryOnUniqueConstraint(
+                vec![Value::Str("A".to_owned()), Value::I64(1), Value::I64(2)],
+                "Seat_hall_row_number_key".to_owned(),
+            )
+            .into(),
+        ),
+        (
+            "INSERT INTO Seat VALUES ('C', 5, 5, 'Dave'), ('C', 5, 5, 'Erin')",
+            ValidateError::DuplicateEntryOnUniqueConstraint(
+                vec![Value::Str("C".to_owned()), Value::I64(5), Value::I64(5)],
+                "Seat_hall_row_number_key".to_owned(),
+            )
+            .into(),
+        ),
+        (
+            "INSERT INTO Seat VALUES ('A', 3, 3, 'Alice')",
+            ValidateError::DuplicateEntryOnUniqueConstraint(
+                vec![Value::Str("A".to_owned()), Value::Str("Alice".to_owned())],
+                "one_seat_per_holder".to_owned(),
+            )
+            .into(),
+        ),
+        (
+            "UPDATE Seat SET number = 1 WHERE holder = 'Bob' AND hall = 'A'",
+            ValidateError::DuplicateEntryOnUniqueConstraint(
+                vec![Value::Str("A".to_owned()), Value::I64(1), Value::I64(1)],
+                "Seat_hall_row_number_key".to_owned(),
+            )
+            .into(),
+        ),
+        (
+            "UPDATE Seat SET holder = 'Alice' WHERE hall = 'B' AND number = 2",
+            ValidateError::DuplicateEntryOnUniqueConstraint(
+                vec![Value::Str("B".to_owned()), Value::Str("Alice".to_owned())],
+                "one_seat_per_holder".to_owned(),
+            )
+            .into(),
+        ),
+    ];
+
+    for (sql, error) in error_cases {
+        g.test(sql, Err(error)).await;
+    }
+
+    g.named_test(
+        "UPDATE may keep the values of the rows it updates",
+        "UPDATE Seat SET number = number, holder = holder WHERE hall = 'A'",
+        Ok(Payload::Update(5)),
+    )
+    .await;
+    g.named_test(
+        "ON CONFLICT looks at the constraint its target names all the columns of",
+        "INSERT INTO Seat VALUES ('A', 1, 1, 'Zoe') ON CONFLICT (hall, row, number) DO UPDATE SET holder = EXCLUDED.holder",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.test(
+        "SELECT holder FROM Seat WHERE hall = 'A' AND row = 1 AND number = 1",
+        Ok(select!(holder Value::Str; "Zoe".to_owned())),
+    )
+    .await;
+    g.named_test(
+        "ON CONFLICT target naming only a part of a constraint",
+        "INSERT INTO Seat VALUES ('A', 1, 1, 'Zoe') ON CONFLICT (hall, row) DO NOTHING",
+        Err(InsertError::ConflictTargetNotUnique("hall".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "columns of a UNIQUE constraint cannot be dropped",
+        "ALTER TABLE Seat DROP COLUMN row",
+        Err(AlterError::CannotAlterUniqueColumn {
+            unique: "Seat_hall_row_number_key".to_owned(),
+            column: "row".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.named_test(
+        "columns of a UNIQUE constraint must exist",
+        "CREATE TABLE Missing (a INTEGER, UNIQUE (a, b))",
+        Err(AlterError::UniqueColumnNotFound {
+            unique: "Missing_a_b_key".to_owned(),
+            column: "b".to_owned(),
+        }
+        .into()),
+    )
+    .await;
+    g.named_test(
+        "UNIQUE constraint names cannot repeat",
+        "CREATE TABLE Twice (a INTEGER, b INTEGER, CONSTRAINT ab UNIQUE (a, b), CONSTRAINT ab UNIQUE (b, a))",
+        Err(TranslateError::DuplicateUniqueConstraint("ab".to_owned()).into()),
+    )
+    .await;
+});
@@ -0,0 +1,306 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{AlterError, PlanError},
+        prelude::{DataType, Payload, Value::*},
+    },
+};
+
+test_case!(view, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Item (id INTEGER, name TEXT, price INTEGER)")
+        .await;
+    g.run("INSERT INTO Item VALUES (1, 'Apple', 300), (2, 'Banana', 150), (3, 'Cherry', 900)")
+        .await;
+
+    g.named_test(
+        "CREATE VIEW",
+        "CREATE VIEW Cheap AS SELECT id, name FROM Item WHERE price < 500",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.named_test(
+        "views are read like tables",
+        "SELECT name FROM Cheap ORDER BY id",
+        Ok(select!(name Str; "Apple".to_owned(); "Banana".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "views read the current rows of their tables",
+        "INSERT INTO Item VALUES (4, 'Durian', 450)",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.count("SELECT * FROM Cheap", 3).await;
+
+    g.named_test(
+        "views can be joined and aliased",
+        "
+        SELECT c.name, i.price
+        FROM Cheap c
+        JOIN Item i ON i.id = c.id
+        WHERE c.id IN (SELECT id FROM Cheap WHERE id > 1)
+        ORDER BY c.id
+        ",
+        Ok(select!(
+            name                 | price;
+            Str                  | I64;
+            "Banana".to_owned()    150;
+            "Durian".to_owned()    450
+        )),
+    )
+    .await;
+
+    g.run("CREATE VIEW CheapName (label) AS SELECT name FROM Cheap WHERE id < 3")
+        .await;
+    g.named_test(
+        "views reading views, with a column list",
+        "SELECT label FROM CheapName ORDER BY label",
+        Ok(select!(label Str; "Apple".to_owned(); "Banana".to_owned())),
+    )
+    .await;
+
+    g.named_test(
+        "SHOW CREATE VIEW",
+        "SHOW CREATE VIEW Cheap",
+        Ok(select!(
+            VIEW_NAME | CREATE_VIEW;
+            Str       | Str;
+            "Cheap".to_owned()   r#"CREATE VIEW "Cheap" AS SELECT "id" AS "id", "name" AS "name" FROM "Item" WHERE "price" < 500;"#.to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "CREATE VIEW with a taken name",
+        "CREATE VIEW Item AS SELECT 1",
+        Err(AlterError::TableAlreadyExists("Item".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "CREATE VIEW reading a missing table",
+        "CREATE VIEW Missing AS SELECT * FROM Nothing",
+        Err(AlterError::TableNotFound("Nothing".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "CREATE OR REPLACE VIEW cannot make a view read itself",
+        "CREATE OR REPLACE VIEW Cheap AS SELECT * FROM CheapName",
+        Err(AlterError::ViewReadsItself("Cheap".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "views are read only",
+        "DELETE FROM Cheap",
+        Err(PlanError::ViewNotWritable("Cheap".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "DROP TABLE with dependent views",
+        "DROP TABLE Item",
+        Err(AlterError::CannotDropWithDependentViews {
+            name: "Item".to_owned(),
+            views: vec!["Cheap".to_owned()],
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "DROP VIEW with dependent views",
+        "DROP VIEW Cheap",
+        Err(AlterError::CannotDropWithDependentViews {
+            name: "Cheap".to_owned(),
+            views: vec!["CheapName".to_owned()],
+        }
+        .into()),
+    )
+    .await;
+
+    g.named_test(
+        "DROP TABLE on a view",
+        "DROP TABLE Cheap",
+        Err(AlterError::DropTableOnView("Cheap".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "CREATE OR REPLACE VIEW",
+        "CREATE OR REPLACE VIEW CheapName AS SELECT id FROM Item",
+        Ok(Payload::Create),
+    )
+    .await;
+    g.count("SELECT * FROM CheapName", 4).await;
+
+    g.named_test("DROP VIEW", "DROP VIEW CheapName", Ok(Payload::DropView(1)))
+        .await;
+    g.named_test(
+        "DROP VIEW IF EXISTS",
+        "DROP VIEW IF EXISTS CheapName, Item",
+        Ok(Payload::DropView(0)),
+    )
+    .await;
+    g.named_test(
+        "DROP VIEW on a table",
+        "DROP VIEW Item",
+        Err(AlterError::ViewNotFound("Item".to_owned()).into()),
+    )
+    .await;
+
+    g.run("CREATE VIEW CheapName AS SELECT name FROM Cheap")
+        .await;
+    g.named_test(
+        "DROP TABLE CASCADE drops the dependent views",
+        "DROP TABLE Item CASCADE",
+        Ok(Payload::DropTable(1)),
+    )
+    .await;
+    g.named_test(
+        "SHOW CREATE VIEW of a dropped view",
+        "SHOW CREATE VIEW CheapName",
+        Err(AlterError::ViewNotFound("CheapName".to_owned()).into()),
+    )
+    .await;
+});
+
+test_case!(materialized_view, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Sale (id INTEGER, region TEXT, amount INTEGER)")
+        .await;
+    g.run("INSERT INTO Sale VALUES (1, 'East', 100), (2, 'West', 300), (3, 'East', 200)")
+        .await;
+    g.run("CREATE VIEW EastSale AS SELECT * FROM Sale WHERE region = 'East'")
+        .await;
+
+    g.named_test(
+        "CREATE MATERIALIZED VIEW",
+        "
+        CREATE MATERIALIZED VIEW RegionTotal AS
+        SELECT region, SUM(amount) AS total, NULL AS note FROM Sale GROUP BY region
+        ",
+        Ok(Payload::Create),
+    )
+    .await;
+
+    g.named_test(
+        "materialized views are typed after their rows",
+        "SHOW COLUMNS FROM RegionTotal",
+        Ok(Payload::ShowColumns(vec![
+            ("region".to_owned(), DataType::Text),
+            ("total".to_owned(), DataType::Int),
+            ("note".to_owned(), DataType::Text),
+        ])),
+    )
+    .await;
+
+    g.named_test(
+        "materialized views keep the rows of their query",
+        "INSERT INTO Sale VALUES (4, 'West', 50)",
+        Ok(Payload::Insert(1)),
+    )
+    .await;
+    g.test(
+        "SELECT region, total FROM RegionTotal ORDER BY region",
+        Ok(select!(
+            region             | total;
+            Str                | I64;
+            "East".to_owned()    300;
+            "West".to_owned()    300
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "REFRESH MATERIALIZED VIEW",
+        "REFRESH MATERIALIZED VIEW RegionTotal",
+        Ok(Payload::RefreshMaterializedView(2)),
+    )
+    .await;
+    g.test(
+        "SELECT region, total FROM RegionTotal ORDER BY region",
+        Ok(select!(
+            region             | total;
+            Str                | I64;
+            "East".to_owned()    300;
+            "West".to_owned()    350
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "materialized views can read views",
+        "CREATE MATERIALIZED VIEW EastCopy AS SELECT id, amount FROM EastSale",
+        Ok(Payload::Create),
+    )
+    .await;
+    g.run("DELETE FROM Sale WHERE id = 1").await;
+    g.count("SELECT * FROM EastCopy", 2).await;
+    g.named_test(
+        "REFRESH MATERIALIZED VIEW reading views",
+        "REFRESH MATERIALIZED VIEW EastCopy",
+        Ok(Payload::RefreshMaterializedView(1)),
+    )
+    .await;
+
+    g.named_test(
+        "SHOW CREATE VIEW of a materialized view",
+        "SHOW CREATE VIEW EastCopy",
+        Ok(select!(
+            VIEW_NAME | CREATE_VIEW;
+            Str       | Str;
+            "EastCopy".to_owned()   r#"CREATE MATERIALIZED VIEW "EastCopy" AS SELECT "id" AS "id", "amount" AS "amount" FROM "EastSale";"#.to_owned()
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "materialized views are read only",
+        "UPDATE RegionTotal SET total = 0",
+        Err(PlanError::ViewNotWritable("RegionTotal".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "REFRESH MATERIALIZED VIEW of a view",
+        "REFRESH MATERIALIZED VIEW EastSale",
+        Err(AlterError::MaterializedViewNotFound("EastSale".to_owned()).into()),
+    )
+    .await;
+
+    g.named_test(
+        "DROP MATERIALIZED VIEW",
+        "DROP MATERIALIZED VIEW EastCopy",
+        Ok(Payload::DropView(1)),
+    )
+    .await;
+    g.named_test(
+        "DROP TABLE with dependent materialized views",
+        "DROP TABLE Sale",
+        Err(AlterError::CannotDropWithDependentViews {
+            name: "Sale".to_owned(),
+            views: vec!["EastSale".to_owned(), "RegionTotal".to_owned()],
+        }
+        .into()),
+    )
+    .await;
+    g.named_test(
+        "DROP TABLE CASCADE drops the dependent materialized views",
+        "DROP TABLE Sale CASCADE",
+        Ok(Payload::DropTable(1)),
+    )
+    .await;
+    g.named_test(
+        "REFRESH MATERIALIZED VIEW of a dropped view",
+        "REFRESH MATERIALIZED VIEW RegionTotal",
+        Err(AlterError::MaterializedViewNotFound("RegionTotal".to_owned()).into()),
+    )
+    .await;
+});
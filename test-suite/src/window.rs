@@ -0,0 +1,143 @@
+use {
+    crate::*,
+    gluesql_core::{
+        error::{EvaluateError, TranslateError},
+        prelude::Value::*,
+    },
+};
+
+test_case!(window, {
+    let g = get_tester!();
+
+    g.run("CREATE TABLE Employee (id INTEGER, dept TEXT, salary INTEGER)")
+        .await;
+    g.run(
+        "
+        INSERT INTO Employee VALUES
+            (1, 'A', 100),
+            (2, 'A', 200),
+            (3, 'A', 200),
+            (4, 'B', 150),
+            (5, 'B', 50);
+    ",
+    )
+    .await;
+
+    g.named_test(
+        "ranking functions with PARTITION BY",
+        "
+        SELECT
+            id,
+            ROW_NUMBER() OVER (PARTITION BY dept ORDER BY salary DESC) AS rn,
+            RANK() OVER (PARTITION BY dept ORDER BY salary DESC) AS rnk,
+            DENSE_RANK() OVER (ORDER BY salary DESC) AS drnk
+        FROM Employee
+        ORDER BY id
+        ",
+        Ok(select!(
+            id  | rn  | rnk | drnk;
+            I64 | I64 | I64 | I64;
+            1     3     3     3;
+            2     1     1     1;
+            3     2     1     1;
+            4     1     1     2;
+            5     2     2     4
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "LAG and LEAD with offset and default",
+        "
+        SELECT
+            id,
+            LAG(salary) OVER (ORDER BY id) AS prev,
+            LEAD(salary, 2, 0) OVER (ORDER BY id) AS next
+        FROM Employee
+        ORDER BY id
+        ",
+        Ok(select_with_null!(
+            id     | prev     | next;
+            I64(1)   Null       I64(200);
+            I64(2)   I64(100)   I64(150);
+            I64(3)   I64(200)   I64(50);
+            I64(4)   I64(200)   I64(0);
+            I64(5)   I64(150)   I64(0)
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "aggregates over a window, running with ORDER BY",
+        "
+        SELECT
+            id,
+            SUM(salary) OVER (PARTITION BY dept ORDER BY salary) AS running,
+            SUM(salary) OVER (PARTITION BY dept) AS total,
+            COUNT(*) OVER () AS cnt
+        FROM Employee
+        ORDER BY id
+        ",
+        Ok(select!(
+            id  | running | total | cnt;
+            I64 | I64     | I64   | I64;
+            1     100       500     5;
+            2     500       500     5;
+            3     500       500     5;
+            4     200       200     5;
+            5     50        200     5
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "window functions over grouped rows",
+        "
+        SELECT dept, SUM(salary) AS total, RANK() OVER (ORDER BY SUM(salary) DESC) AS rnk
+        FROM Employee
+        GROUP BY dept
+        ORDER BY dept
+        ",
+        Ok(select!(
+            dept            | total | rnk;
+            Str             | I64   | I64;
+            "A".to_owned()    500     1;
+            "B".to_owned()    200     2
+        )),
+    )
+    .await;
+
+    g.named_test(
+        "window function in ORDER BY",
+        "SELECT id FROM Employee ORDER BY ROW_NUMBER() OVER (ORDER BY salary DESC, id DESC)",
+        Ok(select!(id I64; 3; 2; 4; 1; 5)),
+    )
+    .await;
+
+    let test_cases = [
+        (
+            "SELECT ROW_NUMBER() FROM Employee",
+            TranslateError::WindowFunctionWithoutOver("ROW_NUMBER".to_owned()).into(),
+        ),
+        (
+            "SELECT UPPER(dept) OVER () FROM Employee",
+            TranslateError::UnsupportedWindowFunction("UPPER".to_owned()).into(),
+        ),
+        (
+            "SELECT COUNT(DISTINCT id) OVER () FROM Employee",
+            TranslateError::DistinctInWindowFunctionNotSupported("COUNT".to_owned()).into(),
+        ),
+        (
+            "SELECT SUM(salary) OVER (ORDER BY id ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) FROM Employee",
+            TranslateError::WindowFrameNotSupported("ROWS".to_owned()).into(),
+        ),
+        (
+            "SELECT id FROM Employee WHERE ROW_NUMBER() OVER () > 1",
+            EvaluateError::WindowFunctionNotAllowed("ROW_NUMBER() OVER ()".to_owned()).into(),
+        ),
+    ];
+
+    for (sql, error) in test_cases {
+        g.test(sql, Err(error)).await;
+    }
+});